@@ -0,0 +1,137 @@
+//! Record (or verify) chain-data fixtures from a live RPC.
+//!
+//! ```text
+//! record-fixtures [--verify] [--rpc URL] [--dir DIR] [DEX:POOL_ID:COIN_A:COIN_B ...]
+//! ```
+//!
+//! Pools default to every fixture already in `--dir` plus `MONITORED_POOLS`,
+//! so a bare `cargo run -p arb-collector --bin record-fixtures` refreshes
+//! everything. `--verify` re-fetches and diffs structure only (keys and JSON
+//! types, not values), exiting non-zero on upstream schema drift.
+
+use anyhow::{Context, Result};
+use arb_collector::fixtures::{self, PoolFixture, RECORDED_FIXTURES_DIR};
+use arb_types::config::{parse_pool_entry, PoolConfig};
+use arb_types::rpc::RpcClient;
+use std::path::PathBuf;
+use std::time::Duration;
+
+struct Args {
+    verify: bool,
+    rpc_url: String,
+    dir: PathBuf,
+    pools: Vec<PoolConfig>,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut verify = false;
+    let mut rpc_url = std::env::var("SUI_RPC_URL").ok();
+    let mut dir = PathBuf::from(RECORDED_FIXTURES_DIR);
+    let mut pools = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--verify" => verify = true,
+            "--rpc" => rpc_url = Some(args.next().context("--rpc needs a URL")?),
+            "--dir" => dir = PathBuf::from(args.next().context("--dir needs a path")?),
+            "-h" | "--help" => {
+                println!("record-fixtures [--verify] [--rpc URL] [--dir DIR] [DEX:POOL_ID:COIN_A:COIN_B ...]");
+                std::process::exit(0);
+            }
+            entry => pools.push(
+                parse_pool_entry(entry).with_context(|| format!("Invalid pool entry: {entry}"))?,
+            ),
+        }
+    }
+
+    if pools.is_empty() {
+        if dir.exists() {
+            pools.extend(fixtures::load_all(&dir)?.iter().map(PoolFixture::pool_config));
+        }
+        pools.extend(
+            std::env::var("MONITORED_POOLS")
+                .unwrap_or_default()
+                .split(',')
+                .filter(|s| !s.trim().is_empty())
+                .filter_map(|e| parse_pool_entry(e.trim())),
+        );
+    }
+    pools.sort_by(|a, b| a.pool_id.cmp(&b.pool_id));
+    pools.dedup_by(|a, b| a.pool_id == b.pool_id);
+
+    Ok(Args {
+        verify,
+        rpc_url: rpc_url.context("Set SUI_RPC_URL or pass --rpc")?,
+        dir,
+        pools,
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    let args = parse_args()?;
+    if args.pools.is_empty() {
+        anyhow::bail!("No pools: pass DEX:POOL_ID:COIN_A:COIN_B entries or set MONITORED_POOLS");
+    }
+
+    let rpc = RpcClient::single(&args.rpc_url).with_timeout(Duration::from_secs(15));
+    let mut failures = 0usize;
+
+    for pool in &args.pools {
+        let fetched = match fixtures::record(&rpc, pool).await {
+            Ok(f) => f,
+            Err(e) => {
+                eprintln!("FAIL  {} {}: {:#}", pool.dex, pool.pool_id, e);
+                failures += 1;
+                continue;
+            }
+        };
+
+        if !args.verify {
+            let path = fixtures::save(&args.dir, &fetched)?;
+            println!("wrote {}", path.display());
+            continue;
+        }
+
+        let path = fixtures::fixture_path(&args.dir, &pool.dex, &pool.pool_id);
+        let recorded = match fixtures::load(&path) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("MISS  {} {}: {:#}", pool.dex, pool.pool_id, e);
+                failures += 1;
+                continue;
+            }
+        };
+
+        let mut diffs = fixtures::diff_structure(&recorded.object, &fetched.object);
+        match (&recorded.dynamic_field, &fetched.dynamic_field) {
+            (Some(a), Some(b)) => diffs.extend(
+                fixtures::diff_structure(a, b)
+                    .into_iter()
+                    .map(|d| format!("dynamic_field {}", d)),
+            ),
+            (None, None) => {}
+            (a, _) => diffs.push(format!(
+                "dynamic_field: {}",
+                if a.is_some() { "no longer present" } else { "now present" }
+            )),
+        }
+
+        if diffs.is_empty() {
+            println!("ok    {} {}", pool.dex, pool.pool_id);
+        } else {
+            failures += 1;
+            println!("DRIFT {} {}", pool.dex, pool.pool_id);
+            for d in diffs {
+                println!("        {}", d);
+            }
+        }
+    }
+
+    if failures > 0 {
+        anyhow::bail!("{} of {} pools failed", failures, args.pools.len());
+    }
+    Ok(())
+}
@@ -0,0 +1,525 @@
+//! DeepBook V3 order book depth.
+//!
+//! A DeepBook pool's price lives in its order book, not in the pool object,
+//! so the poller reads the book separately: a `sui_devInspectTransactionBlock`
+//! of `pool::get_level2_ticks_from_mid<Base, Quote>(pool, ticks, clock)`,
+//! which returns the bid and ask levels nearest the mid without executing
+//! anything. Dev-inspect takes a BCS `TransactionKind`, which is encoded
+//! here directly — it's one Move call with three inputs.
+//!
+//! If dev-inspect fails (e.g. the configured package version is disabled
+//! for the pool), the top level of each side is read from the PoolInner's
+//! `bids`/`asks` BigVectors instead: one `suix_getDynamicFieldObject` per
+//! level of the tree, down its right edge (bids) or left edge (asks).
+
+use anyhow::{Context, Result};
+use arb_types::encoding::{parse_address, string, type_tag, uleb128};
+use arb_types::pool::{BookDepth, BookLevel, PoolState};
+use arb_types::rpc::RpcClient;
+use arb_types::DecimalRegistry;
+use base64::Engine as _;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::debug;
+
+use crate::parsers::field_u64;
+use crate::rpc_poller::PoolMeta;
+
+/// DeepBook V3 package (original publish). Pools reject calls through
+/// package versions they've disabled, so deployments may need the latest
+/// published-at address instead (`DEEPBOOK_V3_PACKAGE_ID`).
+pub const DEFAULT_DEEPBOOK_V3_PACKAGE: &str =
+    "0x2c8d603bc51326b8c13cef9dd07031a408a48dddb541963357661df5d3204809";
+
+/// Levels fetched on each side of the mid.
+pub const DEFAULT_BOOK_TICKS: u64 = 10;
+
+/// DeepBook prices are quote per base in raw units, scaled by 1e9.
+const FLOAT_SCALING: f64 = 1_000_000_000.0;
+
+/// Dev-inspect needs a sender; nothing is signed or charged.
+const INSPECT_SENDER: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+/// `0x6` Clock: shared since genesis, initial shared version 1.
+const CLOCK_ID: &str = "0x6";
+const CLOCK_INITIAL_SHARED_VERSION: u64 = 1;
+
+/// Per-request dev-inspect timeout.
+const BOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Reads DeepBook V3 order books via dev-inspect.
+#[derive(Clone)]
+pub struct BookFetcher {
+    rpc: RpcClient,
+    package_id: String,
+    ticks: u64,
+    decimals: DecimalRegistry,
+}
+
+impl BookFetcher {
+    pub fn new(rpc_url: &str, package_id: &str) -> Self {
+        Self {
+            rpc: RpcClient::single(rpc_url).with_timeout(BOOK_TIMEOUT),
+            package_id: package_id.to_string(),
+            ticks: DEFAULT_BOOK_TICKS,
+            decimals: DecimalRegistry::new(),
+        }
+    }
+
+    /// Coin decimals for the human-unit best bid/ask (default: the static table).
+    pub fn with_decimals(mut self, decimals: DecimalRegistry) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    pub fn decimals(&self) -> &DecimalRegistry {
+        &self.decimals
+    }
+
+    /// Read through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(BOOK_TIMEOUT);
+        self
+    }
+
+    /// Levels to fetch on each side of the mid (at least 1).
+    pub fn with_ticks(mut self, ticks: u64) -> Self {
+        self.ticks = ticks.max(1);
+        self
+    }
+
+    /// Fetch the book of `pool`, a shared object first shared at
+    /// `initial_shared_version`. An empty book is an empty [`BookDepth`].
+    pub async fn fetch(&self, pool: &PoolMeta, initial_shared_version: u64) -> Result<BookDepth> {
+        let tx_kind = level2_tx_kind(&self.package_id, pool, initial_shared_version, self.ticks)?;
+        let params = json!([INSPECT_SENDER, base64::engine::general_purpose::STANDARD.encode(tx_kind)]);
+        let body = self
+            .rpc
+            .call("sui_devInspectTransactionBlock", params)
+            .await
+            .context("DeepBook book request failed")?;
+        let depth = parse_level2(&body)?;
+        debug!(
+            pool = %pool.object_id,
+            bids = %depth.bids.len(),
+            asks = %depth.asks.len(),
+            "DeepBook book fetched"
+        );
+        Ok(depth)
+    }
+
+    /// Read the top level of each side from `pool_inner`'s `bids` and
+    /// `asks` BigVectors (the PoolInner content the parser reads). An empty
+    /// side has no level.
+    pub async fn fetch_top_of_book(&self, pool_inner: &Value) -> Result<BookDepth> {
+        let book = pool_inner.pointer("/fields/book/fields").context("PoolInner has no book")?;
+        let bids = BigVector::parse(book.get("bids").context("Book has no bids")?)?;
+        let asks = BigVector::parse(book.get("asks").context("Book has no asks")?)?;
+        Ok(BookDepth {
+            bids: self.top_level(&bids, true).await?.into_iter().collect(),
+            asks: self.top_level(&asks, false).await?.into_iter().collect(),
+        })
+    }
+
+    /// The best level of one side: the highest-priced orders of the bids,
+    /// the lowest of the asks. Only orders in the same leaf slice as the
+    /// best one count toward its quantity.
+    async fn top_level(&self, side: &BigVector, bids: bool) -> Result<Option<BookLevel>> {
+        if side.length == 0 {
+            return Ok(None);
+        }
+        let mut slice_id = side.root_id;
+        for _ in 0..side.depth {
+            let inner = self.slice(&side.id, slice_id).await?;
+            let children = inner.get("vals").and_then(Value::as_array).context("Inner slice has no vals")?;
+            let child = if bids { children.last() } else { children.first() };
+            slice_id = child.and_then(as_u64).context("Empty inner slice")?;
+        }
+        let leaf = self.slice(&side.id, slice_id).await?;
+        let keys = leaf.get("keys").and_then(Value::as_array).context("Leaf slice has no keys")?;
+        let orders = leaf.get("vals").and_then(Value::as_array).context("Leaf slice has no vals")?;
+        // Order IDs carry the price in their high 64 bits
+        let orders = keys
+            .iter()
+            .zip(orders)
+            .map(|(key, order)| {
+                let order_id: u128 = key.as_str().and_then(|k| k.parse().ok()).context("Malformed order ID")?;
+                let fields = order.get("fields").context("Order has no fields")?;
+                let open = field_u64(fields, "quantity")?.saturating_sub(field_u64(fields, "filled_quantity")?);
+                Ok(((order_id >> 64) as u64, open))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let prices = orders.iter().map(|&(price, _)| price);
+        let best = if bids { prices.max() } else { prices.min() }.context("Empty leaf slice")?;
+        Ok(Some(BookLevel {
+            price: best as f64 / FLOAT_SCALING,
+            quantity: orders.iter().filter(|&&(price, _)| price == best).map(|&(_, open)| open).sum(),
+        }))
+    }
+
+    /// The `Slice` stored under `slice_id` in the BigVector `parent`.
+    async fn slice(&self, parent: &str, slice_id: u64) -> Result<Value> {
+        let body = self
+            .rpc
+            .call("suix_getDynamicFieldObject", json!([parent, { "type": "u64", "value": slice_id.to_string() }]))
+            .await
+            .context("BigVector slice request failed")?;
+        if let Some(error) = body.get("error") {
+            anyhow::bail!("RPC error: {}", error);
+        }
+        body.pointer("/result/data/content/fields/value/fields")
+            .cloned()
+            .with_context(|| format!("BigVector slice {} not found", slice_id))
+    }
+}
+
+/// A `big_vector::BigVector` header. Its slices are dynamic fields of `id`
+/// keyed by `u64` slice ID, `depth` levels of inner slices above the leaves.
+struct BigVector {
+    id: String,
+    depth: u64,
+    length: u64,
+    root_id: u64,
+}
+
+impl BigVector {
+    fn parse(value: &Value) -> Result<Self> {
+        let fields = value.get("fields").context("BigVector has no fields")?;
+        Ok(Self {
+            id: fields.pointer("/id/id").and_then(Value::as_str).context("BigVector has no ID")?.to_string(),
+            depth: field_u64(fields, "depth")?,
+            length: field_u64(fields, "length")?,
+            root_id: field_u64(fields, "root_id")?,
+        })
+    }
+}
+
+fn as_u64(v: &Value) -> Option<u64> {
+    v.as_u64().or_else(|| v.as_str()?.parse().ok())
+}
+
+/// Store `depth` on `state`: best bid/ask from the top levels in human
+/// units, normalized with `decimals` (None for an empty side), plus the raw
+/// levels themselves.
+pub fn apply_book(state: &mut PoolState, depth: BookDepth, decimals: &DecimalRegistry) {
+    let factor = decimals.adjustment_factor(&state.coin_type_a, &state.coin_type_b);
+    state.best_bid = depth.best_bid().map(|p| p * factor);
+    state.best_ask = depth.best_ask().map(|p| p * factor);
+    state.book_depth = Some(depth);
+}
+
+/// A shared object's `initial_shared_version`, from a `sui_multiGetObjects`
+/// entry's `data` (requires `showOwner`).
+pub fn initial_shared_version(data: &Value) -> Option<u64> {
+    let v = data.pointer("/owner/Shared/initial_shared_version")?;
+    v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+}
+
+/// Decode a devInspect response: four `vector<u64>` return values — bid
+/// prices, bid quantities, ask prices, ask quantities.
+pub(crate) fn parse_level2(body: &Value) -> Result<BookDepth> {
+    if let Some(error) = body.get("error") {
+        anyhow::bail!("RPC error: {}", error);
+    }
+    let result = body.get("result").context("Missing result in devInspect response")?;
+    if let Some(error) = result.get("error").and_then(Value::as_str) {
+        anyhow::bail!("devInspect failed: {}", error);
+    }
+    let values = result
+        .pointer("/results/0/returnValues")
+        .and_then(Value::as_array)
+        .context("Missing returnValues in devInspect response")?;
+    anyhow::ensure!(values.len() == 4, "Expected 4 return values, got {}", values.len());
+
+    let vectors = values
+        .iter()
+        .map(|v| {
+            let bytes: Vec<u8> = v
+                .get(0)
+                .and_then(Value::as_array)
+                .context("Malformed return value")?
+                .iter()
+                .map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()).context("Malformed return byte"))
+                .collect::<Result<_>>()?;
+            decode_u64_vector(&bytes)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let levels = |prices: &[u64], quantities: &[u64]| -> Vec<BookLevel> {
+        prices
+            .iter()
+            .zip(quantities)
+            .map(|(&price, &quantity)| BookLevel { price: price as f64 / FLOAT_SCALING, quantity })
+            .collect()
+    };
+    Ok(BookDepth {
+        bids: levels(&vectors[0], &vectors[1]),
+        asks: levels(&vectors[2], &vectors[3]),
+    })
+}
+
+fn decode_u64_vector(bytes: &[u8]) -> Result<Vec<u64>> {
+    let mut len = 0u64;
+    let mut shift = 0;
+    let mut pos = 0;
+    loop {
+        let byte = *bytes.get(pos).context("Truncated vector length")?;
+        len |= u64::from(byte & 0x7f) << shift;
+        pos += 1;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+        anyhow::ensure!(shift < 64, "Vector length overflows u64");
+    }
+    let body = &bytes[pos..];
+    anyhow::ensure!(body.len() as u64 == len * 8, "Vector of {} u64s has {} bytes", len, body.len());
+    Ok(body
+        .chunks_exact(8)
+        .map(|c| u64::from_le_bytes(c.try_into().expect("8-byte chunk")))
+        .collect())
+}
+
+// ── BCS encoding of the dev-inspect transaction ──
+
+/// `TransactionKind::ProgrammableTransaction` with one Move call:
+/// `get_level2_ticks_from_mid<A, B>(Input(0) pool, Input(1) ticks, Input(2) clock)`.
+fn level2_tx_kind(package_id: &str, pool: &PoolMeta, initial_shared_version: u64, ticks: u64) -> Result<Vec<u8>> {
+    let mut out = vec![0]; // TransactionKind::ProgrammableTransaction
+
+    // Inputs
+    uleb128(&mut out, 3);
+    shared_object(&mut out, &pool.object_id, initial_shared_version)?;
+    out.push(0); // CallArg::Pure
+    uleb128(&mut out, 8);
+    out.extend_from_slice(&ticks.to_le_bytes());
+    shared_object(&mut out, CLOCK_ID, CLOCK_INITIAL_SHARED_VERSION)?;
+
+    // Commands
+    uleb128(&mut out, 1);
+    out.push(0); // Command::MoveCall
+    out.extend_from_slice(&parse_address(package_id)?);
+    string(&mut out, "pool");
+    string(&mut out, "get_level2_ticks_from_mid");
+    uleb128(&mut out, 2);
+    type_tag(&mut out, &pool.coin_type_a)?;
+    type_tag(&mut out, &pool.coin_type_b)?;
+    uleb128(&mut out, 3);
+    for input in 0u16..3 {
+        out.push(1); // Argument::Input
+        out.extend_from_slice(&input.to_le_bytes());
+    }
+    Ok(out)
+}
+
+/// `CallArg::Object(ObjectArg::SharedObject { id, initial_shared_version, mutable: false })`
+fn shared_object(out: &mut Vec<u8>, object_id: &str, initial_shared_version: u64) -> Result<()> {
+    out.extend_from_slice(&[1, 1]);
+    out.extend_from_slice(&parse_address(object_id)?);
+    out.extend_from_slice(&initial_shared_version.to_le_bytes());
+    out.push(0);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arb_types::test_support::{spawn_mock_rpc, Reply};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn u64_vector(values: &[u64]) -> Value {
+        let mut bytes = Vec::new();
+        uleb128(&mut bytes, values.len() as u64);
+        for v in values {
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+        json!([bytes, "vector<u64>"])
+    }
+
+    #[test]
+    fn test_level2_tx_kind_layout() {
+        let meta = PoolMeta {
+            object_id: "0xb0b".into(),
+            dex: "deepbook".into(),
+            coin_type_a: "0x2::sui::SUI".into(),
+            coin_type_b: "0xdba::usdc::USDC".into(),
+        };
+        let tx = level2_tx_kind(DEFAULT_DEEPBOOK_V3_PACKAGE, &meta, 389_750_322, 10).unwrap();
+        // Kind, 3 inputs, pool shared object
+        assert_eq!(&tx[..4], &[0, 3, 1, 1]);
+        assert_eq!(&tx[34..36], &[0x0b, 0x0b]);
+        assert_eq!(&tx[36..44], &389_750_322u64.to_le_bytes());
+        // Immutable pool, then the pure u64 tick count
+        assert_eq!(tx[44], 0);
+        assert_eq!(&tx[45..47], &[0, 8]);
+        assert_eq!(&tx[47..55], &10u64.to_le_bytes());
+        // Ends with the three Input arguments
+        assert_eq!(&tx[tx.len() - 10..], &[3, 1, 0, 0, 1, 1, 0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn test_parse_level2_response() {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "result": {
+            "effects": { "status": { "status": "success" } },
+            "results": [{ "returnValues": [
+                u64_vector(&[3_500_000, 3_490_000]),
+                u64_vector(&[10_000_000_000, 5_000_000_000]),
+                u64_vector(&[3_510_000]),
+                u64_vector(&[2_000_000_000]),
+            ]}]
+        }});
+        let depth = parse_level2(&body).unwrap();
+        assert_eq!(depth.bids.len(), 2);
+        assert_eq!(depth.bids[0], BookLevel { price: 0.0035, quantity: 10_000_000_000 });
+        assert_eq!(depth.best_ask(), Some(0.00351));
+
+        // DeepBook fixture pool: no price until the book is applied
+        let fixture = crate::fixtures::load_dex_for_tests("deepbook").unwrap().remove(0);
+        let mut state = fixture.parse(0).unwrap();
+        assert_eq!(state.price_a_in_b(), None);
+        apply_book(&mut state, depth, &sui_usdc_decimals());
+        assert!((state.price_a_in_b().unwrap() - 0.003505).abs() < 1e-12);
+        // 0.0035 raw USDC per raw SUI is 3.50 USDC per SUI
+        assert!((state.best_bid.unwrap() - 3.5).abs() < 1e-9);
+        assert!((state.best_ask.unwrap() - 3.51).abs() < 1e-9);
+
+        // Empty book: no best bid/ask
+        let empty = json!({ "result": { "results": [{ "returnValues": [
+            u64_vector(&[]), u64_vector(&[]), u64_vector(&[]), u64_vector(&[]),
+        ]}]}});
+        let depth = parse_level2(&empty).unwrap();
+        assert_eq!((depth.best_bid(), depth.best_ask()), (None, None));
+
+        let aborted = json!({ "result": { "error": "MoveAbort(..., 6) in command 0" } });
+        assert!(parse_level2(&aborted).unwrap_err().to_string().contains("MoveAbort"));
+    }
+
+    fn sui_usdc_decimals() -> DecimalRegistry {
+        let decimals = DecimalRegistry::new();
+        decimals.insert("0x2::sui::SUI", 9);
+        decimals.insert("0xdba34672e30cb065b1f93e3ab55318768fd6fef66c15942c9f7cb846e2f900e7::usdc::USDC", 6);
+        decimals
+    }
+
+    /// A PoolInner whose book has these `bids` and `asks` BigVector headers:
+    /// `(id, depth, length, root_id)`.
+    fn pool_inner(bids: (&str, u64, u64, u64), asks: (&str, u64, u64, u64)) -> Value {
+        let header = |(id, depth, length, root_id): (&str, u64, u64, u64)| {
+            json!({
+                "type": "0x2c8d::big_vector::BigVector<0x2c8d::order::Order>",
+                "fields": {
+                    "id": { "id": id }, "depth": depth, "length": length.to_string(),
+                    "root_id": root_id.to_string(), "last_id": root_id.to_string(),
+                    "max_slice_size": 64, "max_fan_out": 64
+                }
+            })
+        };
+        json!({ "fields": { "book": { "fields": { "bids": header(bids), "asks": header(asks), "lot_size": "1000" } } } })
+    }
+
+    /// An order key: the price in the high 64 bits. Bids count their
+    /// sequence down, so the oldest order at a price sorts last.
+    fn order_key(price: u64, sequence: u64, bid: bool) -> String {
+        let low = if bid { u64::MAX - sequence } else { sequence };
+        (((price as u128) << 64) | low as u128).to_string()
+    }
+
+    fn order(quantity: u64, filled: u64) -> Value {
+        json!({ "type": "0x2c8d::order::Order", "fields": {
+            "quantity": quantity.to_string(), "filled_quantity": filled.to_string(), "status": 0
+        }})
+    }
+
+    /// Mock node serving BigVector slices by `(parent, slice ID)`, counting
+    /// the requests it gets.
+    async fn spawn_slices(slices: Vec<(&'static str, u64, Value, Value)>) -> (String, Arc<AtomicUsize>) {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let url = spawn_mock_rpc(move |request| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let params = &request["params"];
+            let slice_id = params[1]["value"].as_str().and_then(|v| v.parse::<u64>().ok());
+            let result = slices
+                .iter()
+                .find(|(parent, id, _, _)| params[0] == *parent && slice_id == Some(*id))
+                .map(|(_, _, keys, vals)| {
+                    json!({ "data": { "content": { "fields": { "value": { "fields": {
+                        "keys": keys, "vals": vals, "prev": "0", "next": "0"
+                    }}}}}})
+                })
+                .unwrap_or_else(|| json!({ "error": { "code": "dynamicFieldNotFound" } }));
+            Reply::Json(json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+        })
+        .await;
+        (url, hits)
+    }
+
+    #[tokio::test]
+    async fn test_top_of_book_from_big_vectors() {
+        // Bids: one inner level over two leaves, the best prices in the
+        // right one. Asks: a single leaf.
+        let slices = vec![
+            ("0xb1d", 1, json!([order_key(3_480_000, 9, true)]), json!(["2", "3"])),
+            ("0xb1d", 2, json!([order_key(3_400_000, 1, true)]), json!([order(1_000, 0)])),
+            (
+                "0xb1d",
+                3,
+                json!([
+                    order_key(3_490_000, 5, true),
+                    order_key(3_500_000, 8, true),
+                    order_key(3_500_000, 4, true),
+                ]),
+                json!([order(7_000_000_000, 0), order(2_000_000_000, 0), order(10_000_000_000, 4_000_000_000)]),
+            ),
+            (
+                "0xa5c",
+                7,
+                json!([order_key(3_510_000, 2, false), order_key(3_520_000, 3, false)]),
+                json!([order(2_000_000_000, 500_000_000), order(9_000_000_000, 0)]),
+            ),
+        ];
+        let (url, hits) = spawn_slices(slices).await;
+        let fetcher = BookFetcher::new(&url, DEFAULT_DEEPBOOK_V3_PACKAGE);
+        let inner = pool_inner(("0xb1d", 1, 4, 1), ("0xa5c", 0, 2, 7));
+
+        let depth = fetcher.fetch_top_of_book(&inner).await.unwrap();
+        assert_eq!(depth.bids, [BookLevel { price: 0.0035, quantity: 8_000_000_000 }]);
+        assert_eq!(depth.asks, [BookLevel { price: 0.00351, quantity: 1_500_000_000 }]);
+        // Root and right leaf of the bids, the asks' root leaf: never the left bid leaf
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        let mut state = crate::fixtures::load_dex_for_tests("deepbook").unwrap().remove(0).parse(0).unwrap();
+        apply_book(&mut state, depth, &sui_usdc_decimals());
+        assert!((state.best_bid.unwrap() - 3.5).abs() < 1e-9);
+        assert!((state.best_ask.unwrap() - 3.51).abs() < 1e-9);
+        assert!((state.price_a_in_b().unwrap() - 0.003505).abs() < 1e-12);
+    }
+
+    #[tokio::test]
+    async fn test_top_of_book_empty_sides() {
+        let (url, hits) = spawn_slices(Vec::new()).await;
+        let fetcher = BookFetcher::new(&url, DEFAULT_DEEPBOOK_V3_PACKAGE);
+
+        // Nothing resting on either side: no slices to read, no price
+        let depth = fetcher.fetch_top_of_book(&pool_inner(("0xb1d", 0, 0, 0), ("0xa5c", 0, 0, 0))).await.unwrap();
+        assert_eq!(depth, BookDepth::default());
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+
+        let mut state = crate::fixtures::load_dex_for_tests("deepbook").unwrap().remove(0).parse(0).unwrap();
+        apply_book(&mut state, depth, &sui_usdc_decimals());
+        assert_eq!((state.best_bid, state.best_ask, state.price_a_in_b()), (None, None, None));
+
+        // A missing slice is an error, not an empty side
+        let inner = pool_inner(("0xb1d", 0, 3, 5), ("0xa5c", 0, 0, 0));
+        assert!(fetcher.fetch_top_of_book(&inner).await.is_err());
+        assert!(fetcher.fetch_top_of_book(&json!({ "fields": {} })).await.is_err());
+    }
+
+    #[test]
+    fn test_initial_shared_version() {
+        let data = json!({ "owner": { "Shared": { "initial_shared_version": 389750322 } } });
+        assert_eq!(initial_shared_version(&data), Some(389_750_322));
+        assert_eq!(initial_shared_version(&json!({ "owner": "Immutable" })), None);
+    }
+}
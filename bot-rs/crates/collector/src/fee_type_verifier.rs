@@ -0,0 +1,383 @@
+use anyhow::{Context, Result};
+use arb_types::config::Config;
+use arb_types::pool::Dex;
+use arb_types::rpc::RpcClient;
+use serde_json::{json, Value};
+use arb_types::Metrics;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time;
+use tracing::{debug, error, info, warn};
+
+use crate::pool_cache::PoolCache;
+use crate::pool_set::PoolSet;
+use crate::rpc_poller::{extract_third_type_param, PoolMeta};
+
+/// Default full-sweep period: every Turbos pool is re-verified once per hour.
+const DEFAULT_VERIFY_PERIOD: Duration = Duration::from_secs(3600);
+
+/// Per-request `sui_getObject` timeout.
+const RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Consecutive mismatches on the same pool before it is quarantined.
+const DEFAULT_QUARANTINE_AFTER: u32 = 3;
+
+/// Background check that each cached Turbos `fee_type` matches the pool's
+/// on-chain `Pool<A, B, Fee>` type.
+///
+/// A wrong fee type makes every Turbos call abort on-chain, so a mis-parse
+/// must not survive in the cache. Pools are verified one at a time, staggered
+/// evenly across `period`, so the sweep costs one `sui_getObject` per
+/// `period / n_pools` regardless of pool count. The Turbos pools are re-read
+/// from the pool list before each check, so reloads are followed.
+pub struct FeeTypeVerifier {
+    rpc: RpcClient,
+    pools: PoolSet,
+    period: Duration,
+    quarantine_after: u32,
+    /// Consecutive mismatches per pool (reset on a clean match).
+    mismatch_streak: HashMap<String, u32>,
+    /// Counts corrected mismatches (`arb_fee_type_mismatches_total`)
+    metrics: Arc<Metrics>,
+}
+
+/// Result of verifying one pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyOutcome {
+    /// Cached fee type matches on-chain.
+    Match,
+    /// Cache had no fee type yet; filled from on-chain.
+    Filled(String),
+    /// Cache was wrong and has been corrected.
+    Corrected { cached: String, actual: String },
+    /// Cache was corrected, and repeated mismatches quarantined the pool.
+    Quarantined { cached: String, actual: String },
+    /// Could not verify (pool not cached, object error, unparseable type).
+    Skipped(String),
+}
+
+impl FeeTypeVerifier {
+    /// Create a verifier for the Turbos pools in `config.monitored_pools`.
+    pub fn new(config: &Config) -> Self {
+        Self::for_pools(&config.rpc_url, config.monitored_pools.iter().map(PoolMeta::from).collect())
+            .with_rpc(RpcClient::from_config(config))
+    }
+
+    /// Create a verifier for the Turbos pools in an explicit list (other
+    /// DEXes are ignored).
+    pub fn for_pools(rpc_url: &str, pools: Vec<PoolMeta>) -> Self {
+        Self {
+            rpc: RpcClient::single(rpc_url).with_timeout(RPC_TIMEOUT),
+            pools: PoolSet::new(pools),
+            period: DEFAULT_VERIFY_PERIOD,
+            quarantine_after: DEFAULT_QUARANTINE_AFTER,
+            mismatch_streak: HashMap::new(),
+            metrics: Arc::new(Metrics::new()),
+        }
+    }
+
+    /// Verify through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(RPC_TIMEOUT);
+        self
+    }
+
+    /// Verify the Turbos pools of a shared pool list, so pools added by a
+    /// reload are verified too.
+    pub fn with_pools(mut self, pools: PoolSet) -> Self {
+        self.pools = pools;
+        self
+    }
+
+    /// Override the full-sweep period.
+    pub fn with_period(mut self, period: Duration) -> Self {
+        self.period = period;
+        self
+    }
+
+    /// Override the consecutive-mismatch threshold for quarantine.
+    pub fn with_quarantine_after(mut self, n: u32) -> Self {
+        self.quarantine_after = n.max(1);
+        self
+    }
+
+    /// Count mismatches in `metrics` (shared with the metrics and status
+    /// servers).
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// The IDs of the Turbos pools currently listed.
+    pub fn turbos_pool_ids(&self) -> Vec<String> {
+        self.pools
+            .snapshot()
+            .iter()
+            .filter(|m| m.dex.parse::<Dex>().is_ok_and(|d| d == Dex::Turbos))
+            .map(|m| m.object_id.clone())
+            .collect()
+    }
+
+    /// Run the staggered verification loop forever. The step between checks
+    /// follows the current Turbos pool count.
+    pub async fn run(&mut self, cache: PoolCache) -> Result<()> {
+        let mut changes = self.pools.watch();
+        info!(
+            pools = %self.turbos_pool_ids().len(),
+            period_secs = %self.period.as_secs(),
+            "Starting Turbos fee type verifier"
+        );
+
+        let mut idx = 0usize;
+        loop {
+            let pool_ids = self.turbos_pool_ids();
+            if pool_ids.is_empty() {
+                debug!("No Turbos pools listed — fee type verifier idle until the pool list changes");
+                if changes.changed().await.is_err() {
+                    std::future::pending::<()>().await;
+                }
+                continue;
+            }
+            // Pools were just seeded (or reloaded): wait a step before the first check
+            time::sleep(self.period / pool_ids.len() as u32).await;
+            let pool_id = pool_ids[idx % pool_ids.len()].clone();
+            idx = idx.wrapping_add(1);
+
+            match self.fetch_object(&pool_id).await {
+                Ok(result) => {
+                    let outcome = self.check(&cache, &pool_id, &result);
+                    debug!(pool = %pool_id, outcome = ?outcome, "Fee type verified");
+                }
+                Err(e) => warn!(pool = %pool_id, error = %e, "Fee type verification fetch failed"),
+            }
+        }
+    }
+
+    /// Compare one pool's cached fee type against a `sui_getObject` result
+    /// (the JSON-RPC `result` value), correcting the cache on mismatch.
+    pub fn check(&mut self, cache: &PoolCache, pool_id: &str, result: &Value) -> VerifyOutcome {
+        if let Some(obj_error) = result.get("error") {
+            return VerifyOutcome::Skipped(format!("object error: {}", obj_error));
+        }
+
+        let actual = match result
+            .get("data")
+            .and_then(|d| d.get("type"))
+            .and_then(|t| t.as_str())
+            .and_then(extract_third_type_param)
+        {
+            Some(t) => t,
+            None => return VerifyOutcome::Skipped("no fee type in object type".to_string()),
+        };
+
+        // Only the fee type is written: a price update landing meanwhile stays
+        let cached = match cache.set_fee_type(pool_id, &actual) {
+            None => return VerifyOutcome::Skipped("pool not cached".to_string()),
+            Some(Some(c)) if c == actual => {
+                self.mismatch_streak.remove(pool_id);
+                return VerifyOutcome::Match;
+            }
+            Some(Some(c)) => c,
+            Some(None) => return VerifyOutcome::Filled(actual),
+        };
+        self.metrics.record_fee_type_mismatch();
+
+        let streak = self.mismatch_streak.entry(pool_id.to_string()).or_insert(0);
+        *streak += 1;
+
+        if *streak >= self.quarantine_after {
+            error!(
+                pool = %pool_id,
+                cached = %cached,
+                actual = %actual,
+                streak = %streak,
+                "⛔ Repeated Turbos fee type mismatch — quarantining pool"
+            );
+            cache.quarantine(pool_id, "repeated Turbos fee type mismatch");
+            VerifyOutcome::Quarantined { cached, actual }
+        } else {
+            error!(
+                pool = %pool_id,
+                cached = %cached,
+                actual = %actual,
+                "Turbos fee type mismatch — cache corrected"
+            );
+            VerifyOutcome::Corrected { cached, actual }
+        }
+    }
+
+    /// Fetch a single object with its type string.
+    async fn fetch_object(&self, pool_id: &str) -> Result<Value> {
+        let body = self
+            .rpc
+            .call("sui_getObject", json!([pool_id, { "showType": true }]))
+            .await
+            .context("RPC request failed")?;
+
+        if let Some(error) = body.get("error") {
+            anyhow::bail!("RPC error: {}", error);
+        }
+
+        body.get("result").cloned().context("Missing result")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arb_types::pool::{Dex, PoolState};
+
+    const POOL: &str = "0xturbos";
+    const FEE_3000: &str = "0x91::fee3000bps::FEE3000BPS";
+    const FEE_500: &str = "0x91::fee500bps::FEE500BPS";
+
+    fn verifier() -> FeeTypeVerifier {
+        FeeTypeVerifier::for_pools("http://localhost:9000", vec![meta(POOL, "turbos")])
+    }
+
+    fn meta(id: &str, dex: &str) -> PoolMeta {
+        PoolMeta {
+            object_id: id.to_string(),
+            dex: dex.to_string(),
+            coin_type_a: "0x2::sui::SUI".to_string(),
+            coin_type_b: "0xusdc::usdc::USDC".to_string(),
+        }
+    }
+
+    fn cache_with(fee_type: Option<&str>) -> PoolCache {
+        let cache = PoolCache::new();
+        cache.upsert(
+            POOL.to_string(),
+            PoolState {
+                object_id: POOL.to_string(),
+                dex: Dex::Turbos,
+                coin_type_a: "0x2::sui::SUI".to_string(),
+                coin_type_b: "0xdba::usdc::USDC".to_string(),
+                sqrt_price: Some(1u128 << 64),
+                tick_index: Some(0),
+                liquidity: Some(1_000_000),
+                fee_rate_bps: Some(30),
+                reserve_a: None,
+                reserve_b: None,
+                best_bid: None,
+                best_ask: None,
+                book_depth: None,
+                last_updated_ms: 0,
+                fee_type: fee_type.map(String::from),
+                min_trade_size: None,
+                lot_size: None,
+                raw_balance_a: None,
+                raw_balance_b: None,
+            },
+        );
+        cache
+    }
+
+    /// Mocked `sui_getObject` result for a Turbos pool with the given fee type.
+    fn object_response(fee: &str) -> Value {
+        json!({
+            "data": {
+                "objectId": POOL,
+                "type": format!("0x91::pool::Pool<0x2::sui::SUI, 0xdba::usdc::USDC, {}>", fee),
+            }
+        })
+    }
+
+    #[test]
+    fn test_match_leaves_cache_untouched() {
+        let mut v = verifier();
+        let cache = cache_with(Some(FEE_3000));
+        assert_eq!(v.check(&cache, POOL, &object_response(FEE_3000)), VerifyOutcome::Match);
+        assert_eq!(v.metrics.snapshot().fee_type_mismatches, 0);
+        assert_eq!(cache.get(POOL).unwrap().fee_type.as_deref(), Some(FEE_3000));
+    }
+
+    #[test]
+    fn test_mismatch_corrects_cache_and_counts() {
+        let mut v = verifier();
+        let cache = cache_with(Some(FEE_500));
+        let outcome = v.check(&cache, POOL, &object_response(FEE_3000));
+        assert_eq!(
+            outcome,
+            VerifyOutcome::Corrected {
+                cached: FEE_500.to_string(),
+                actual: FEE_3000.to_string()
+            }
+        );
+        assert_eq!(cache.get(POOL).unwrap().fee_type.as_deref(), Some(FEE_3000));
+        assert_eq!(v.metrics.snapshot().fee_type_mismatches, 1);
+        assert!(!cache.is_quarantined(POOL));
+    }
+
+    #[test]
+    fn test_missing_fee_type_filled_without_counting() {
+        let mut v = verifier();
+        let cache = cache_with(None);
+        let outcome = v.check(&cache, POOL, &object_response(FEE_3000));
+        assert_eq!(outcome, VerifyOutcome::Filled(FEE_3000.to_string()));
+        assert_eq!(v.metrics.snapshot().fee_type_mismatches, 0);
+    }
+
+    #[test]
+    fn test_repeated_mismatch_escalates_to_quarantine() {
+        let mut v = verifier().with_quarantine_after(2);
+        let cache = cache_with(Some(FEE_500));
+
+        assert!(matches!(
+            v.check(&cache, POOL, &object_response(FEE_3000)),
+            VerifyOutcome::Corrected { .. }
+        ));
+
+        // Something re-poisons the cache before the next pass
+        let mut s = cache.get(POOL).unwrap();
+        s.fee_type = Some(FEE_500.to_string());
+        cache.upsert(POOL.to_string(), s);
+
+        assert!(matches!(
+            v.check(&cache, POOL, &object_response(FEE_3000)),
+            VerifyOutcome::Quarantined { .. }
+        ));
+        assert!(cache.is_quarantined(POOL));
+        assert!(cache.snapshot().is_empty());
+        assert_eq!(v.metrics.snapshot().fee_type_mismatches, 2);
+    }
+
+    #[test]
+    fn test_match_resets_streak() {
+        let mut v = verifier().with_quarantine_after(2);
+        let cache = cache_with(Some(FEE_500));
+
+        v.check(&cache, POOL, &object_response(FEE_3000)); // corrected, streak 1
+        v.check(&cache, POOL, &object_response(FEE_3000)); // match, streak reset
+
+        let mut s = cache.get(POOL).unwrap();
+        s.fee_type = Some(FEE_500.to_string());
+        cache.upsert(POOL.to_string(), s);
+
+        assert!(matches!(
+            v.check(&cache, POOL, &object_response(FEE_3000)),
+            VerifyOutcome::Corrected { .. }
+        ));
+        assert!(!cache.is_quarantined(POOL));
+    }
+
+    #[test]
+    fn test_object_error_skipped() {
+        let mut v = verifier();
+        let cache = cache_with(Some(FEE_3000));
+        let resp = json!({ "error": { "code": "notExists" } });
+        assert!(matches!(v.check(&cache, POOL, &resp), VerifyOutcome::Skipped(_)));
+    }
+
+    #[test]
+    fn test_follows_shared_pool_set() {
+        let pools = PoolSet::new(vec![meta(POOL, "turbos"), meta("0xcetus", "cetus")]);
+        let v = verifier().with_pools(pools.clone());
+        assert_eq!(v.turbos_pool_ids(), [POOL]);
+
+        // A reload adds a Turbos pool and drops the first one
+        pools.replace(vec![meta("0xcetus", "cetus"), meta("0xturbos2", "turbos")]);
+        assert_eq!(v.turbos_pool_ids(), ["0xturbos2"]);
+    }
+}
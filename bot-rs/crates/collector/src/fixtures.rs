@@ -0,0 +1,359 @@
+//! Chain-data fixtures for parser and mock-RPC tests.
+//!
+//! Layout: `<dir>/<dex>/<pool_id>.json`, one [`PoolFixture`] per file. Each
+//! fixture stores raw JSON-RPC `result` values — the `sui_getObject` result
+//! and, for DeepBook V3 Versioned pools, the `suix_getDynamicFieldObject`
+//! result for the PoolInner — so the same file can feed a parser directly or
+//! be served back by a mock RPC.
+//!
+//! The `record-fixtures` bin fetches real captures from a live RPC into
+//! [`RECORDED_FIXTURES_DIR`]; `record-fixtures --verify` re-fetches and
+//! reports structural drift without touching the files. Tests read them via
+//! [`load_for_tests`]. The hand-written fixtures under
+//! [`SYNTHETIC_FIXTURES_DIR`] (shaped like mainnet responses, with
+//! placeholder IDs, versions, and digests) stand in for a DEX until it has a
+//! recording, and back the tests that need exact, scripted pool values.
+
+use anyhow::{Context, Result};
+use arb_types::config::PoolConfig;
+use arb_types::pool::PoolState;
+use arb_types::rpc::RpcClient;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::parsers;
+use crate::rpc_poller::{
+    deepbook_inner_from_dynamic_field, deepbook_inner_id, extract_third_type_param,
+    fetch_deepbook_dynamic_field, is_deepbook_versioned, PoolMeta,
+};
+
+/// Hand-written fixtures checked into this crate.
+pub const SYNTHETIC_FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/synthetic");
+
+/// Default output of `record-fixtures`.
+pub const RECORDED_FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/recorded");
+
+/// One pool fixture.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolFixture {
+    pub dex: String,
+    pub pool_id: String,
+    pub coin_type_a: String,
+    pub coin_type_b: String,
+    /// `sui_getObject` result (`showType` + `showContent`).
+    pub object: Value,
+    /// `suix_getDynamicFieldObject` result for a DeepBook V3 PoolInner.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic_field: Option<Value>,
+}
+
+impl PoolFixture {
+    pub fn meta(&self) -> PoolMeta {
+        PoolMeta {
+            object_id: self.pool_id.clone(),
+            dex: self.dex.clone(),
+            coin_type_a: self.coin_type_a.clone(),
+            coin_type_b: self.coin_type_b.clone(),
+        }
+    }
+
+    /// On-chain object type string (e.g. `...::pool::Pool<A, B, Fee>`).
+    pub fn object_type(&self) -> Option<&str> {
+        self.object
+            .get("data")
+            .and_then(|d| d.get("type"))
+            .and_then(|t| t.as_str())
+    }
+
+    /// Content the parser sees — the PoolInner for Versioned DeepBook pools,
+    /// the object's own content otherwise.
+    pub fn content(&self) -> Result<Value> {
+        let raw = self
+            .object
+            .get("data")
+            .and_then(|d| d.get("content"))
+            .context("Fixture object has no data.content")?;
+
+        if self.dex.eq_ignore_ascii_case("deepbook") && is_deepbook_versioned(raw) {
+            let df = self
+                .dynamic_field
+                .as_ref()
+                .context("Versioned DeepBook fixture is missing its dynamic field")?;
+            deepbook_inner_from_dynamic_field(df)
+        } else {
+            Ok(raw.clone())
+        }
+    }
+
+    /// Parse the fixture the way the RPC poller does (including the Turbos fee type).
+    pub fn parse(&self, now_ms: u64) -> Result<PoolState> {
+        let mut state = parsers::parse_pool_object(&self.content()?, &self.dex, &self.meta(), now_ms)?;
+        if self.dex.eq_ignore_ascii_case("turbos") {
+            state.fee_type = self.object_type().and_then(extract_third_type_param);
+        }
+        Ok(state)
+    }
+
+    /// The pool entry in `MONITORED_POOLS` format, for re-recording.
+    pub fn pool_config(&self) -> PoolConfig {
+        PoolConfig {
+            dex: self.dex.clone(),
+            pool_id: self.pool_id.clone(),
+            coin_type_a: self.coin_type_a.clone(),
+            coin_type_b: self.coin_type_b.clone(),
+        }
+    }
+}
+
+/// Canonical path of a pool's fixture file.
+pub fn fixture_path(dir: &Path, dex: &str, pool_id: &str) -> PathBuf {
+    dir.join(dex.to_lowercase()).join(format!("{}.json", pool_id))
+}
+
+/// Load every fixture under `dir`, sorted by path.
+pub fn load_all(dir: &Path) -> Result<Vec<PoolFixture>> {
+    let mut paths = Vec::new();
+    for dex_dir in std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let dex_dir = dex_dir?.path();
+        if !dex_dir.is_dir() {
+            continue;
+        }
+        for entry in std::fs::read_dir(&dex_dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "json") {
+                paths.push(path);
+            }
+        }
+    }
+    paths.sort();
+    paths.iter().map(|p| load(p)).collect()
+}
+
+/// The fixtures tests run against: every recorded capture, plus the
+/// synthetic fixtures of each DEX with none recorded.
+pub fn load_for_tests() -> Result<Vec<PoolFixture>> {
+    let recorded_dir = Path::new(RECORDED_FIXTURES_DIR);
+    let mut fixtures = if recorded_dir.is_dir() { load_all(recorded_dir)? } else { Vec::new() };
+    let recorded: HashSet<String> = fixtures.iter().map(|f| f.dex.to_lowercase()).collect();
+    fixtures.extend(
+        load_all(Path::new(SYNTHETIC_FIXTURES_DIR))?
+            .into_iter()
+            .filter(|f| !recorded.contains(&f.dex.to_lowercase())),
+    );
+    fixtures.sort_by(|a, b| (&a.dex, &a.pool_id).cmp(&(&b.dex, &b.pool_id)));
+    Ok(fixtures)
+}
+
+/// [`load_for_tests`] for one DEX.
+pub fn load_dex_for_tests(dex: &str) -> Result<Vec<PoolFixture>> {
+    Ok(load_for_tests()?.into_iter().filter(|f| f.dex.eq_ignore_ascii_case(dex)).collect())
+}
+
+/// Load the fixtures for one DEX.
+pub fn load_dex(dir: &Path, dex: &str) -> Result<Vec<PoolFixture>> {
+    Ok(load_all(dir)?
+        .into_iter()
+        .filter(|f| f.dex.eq_ignore_ascii_case(dex))
+        .collect())
+}
+
+pub fn load(path: &Path) -> Result<PoolFixture> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read fixture {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("Invalid fixture {}", path.display()))
+}
+
+/// Write a fixture to its canonical path, pretty-printed with a trailing newline.
+pub fn save(dir: &Path, fixture: &PoolFixture) -> Result<PathBuf> {
+    let path = fixture_path(dir, &fixture.dex, &fixture.pool_id);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut text = serde_json::to_string_pretty(fixture)?;
+    text.push('\n');
+    std::fs::write(&path, text).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Fetch a pool from a live RPC, following the DeepBook Versioned unwrap path.
+pub async fn record(rpc: &RpcClient, pool: &PoolConfig) -> Result<PoolFixture> {
+    let body = rpc
+        .call("sui_getObject", json!([pool.pool_id, { "showType": true, "showContent": true }]))
+        .await
+        .context("RPC request failed")?;
+
+    if let Some(error) = body.get("error") {
+        anyhow::bail!("RPC error: {}", error);
+    }
+    let mut object = body.get("result").cloned().context("Missing result")?;
+    if let Some(obj_error) = object.get("error") {
+        anyhow::bail!("Object error for {}: {}", pool.pool_id, obj_error);
+    }
+
+    let content = object.get("data").and_then(|d| d.get("content"));
+    let mut dynamic_field = match content {
+        Some(c) if pool.dex.eq_ignore_ascii_case("deepbook") && is_deepbook_versioned(c) => {
+            let inner_id = deepbook_inner_id(c)?;
+            Some(fetch_deepbook_dynamic_field(rpc, inner_id).await?)
+        }
+        _ => None,
+    };
+
+    scrub_timestamps(&mut object);
+    if let Some(df) = dynamic_field.as_mut() {
+        scrub_timestamps(df);
+    }
+
+    Ok(PoolFixture {
+        dex: pool.dex.to_lowercase(),
+        pool_id: pool.pool_id.clone(),
+        coin_type_a: pool.coin_type_a.clone(),
+        coin_type_b: pool.coin_type_b.clone(),
+        object,
+        dynamic_field,
+    })
+}
+
+/// Zero out timestamp-like fields so re-recording an unchanged pool is a
+/// no-op diff. Keys are kept (with a zero of the same JSON type) so the
+/// structure is untouched.
+pub fn scrub_timestamps(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if is_timestamp_key(key) && !v.is_object() && !v.is_array() {
+                    match v {
+                        Value::String(s) => *s = "0".to_string(),
+                        Value::Number(_) => *v = json!(0),
+                        _ => {}
+                    }
+                } else {
+                    scrub_timestamps(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(scrub_timestamps),
+        _ => {}
+    }
+}
+
+fn is_timestamp_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.contains("timestamp")
+        || ["_ts", "_ts_ms", "_time", "_time_ms"].iter().any(|s| key.ends_with(s))
+}
+
+/// Shape of a JSON value: scalars become their type name, arrays collapse to
+/// the shape of their first element.
+pub fn structure(value: &Value) -> Value {
+    match value {
+        Value::Null => json!("null"),
+        Value::Bool(_) => json!("bool"),
+        Value::Number(_) => json!("number"),
+        Value::String(_) => json!("string"),
+        Value::Array(items) => Value::Array(items.first().map(structure).into_iter().collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), structure(v)))
+                .collect::<Map<_, _>>(),
+        ),
+    }
+}
+
+/// Structural differences between a recorded and a freshly fetched value,
+/// as `path: description` lines. Values are ignored; keys and types are not.
+pub fn diff_structure(recorded: &Value, fetched: &Value) -> Vec<String> {
+    let mut diffs = Vec::new();
+    diff_at("$", &structure(recorded), &structure(fetched), &mut diffs);
+    diffs
+}
+
+fn diff_at(path: &str, old: &Value, new: &Value, diffs: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (k, av) in a {
+                let child = format!("{}.{}", path, k);
+                match b.get(k) {
+                    Some(bv) => diff_at(&child, av, bv, diffs),
+                    None => diffs.push(format!("{}: removed", child)),
+                }
+            }
+            for k in b.keys().filter(|k| !a.contains_key(*k)) {
+                diffs.push(format!("{}.{}: added", path, k));
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            // Empty arrays carry no element shape to compare
+            if let (Some(av), Some(bv)) = (a.first(), b.first()) {
+                diff_at(&format!("{}[]", path), av, bv, diffs);
+            }
+        }
+        (a, b) if a != b => diffs.push(format!("{}: {} -> {}", path, kind(a), kind(b))),
+        _ => {}
+    }
+}
+
+fn kind(shape: &Value) -> &str {
+    match shape {
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::String(s) => s,
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_in_fixtures_parse() {
+        let fixtures = load_for_tests().unwrap();
+        assert!(!fixtures.is_empty());
+        for f in &fixtures {
+            let state = f
+                .parse(0)
+                .unwrap_or_else(|e| panic!("{} {}: {}", f.dex, f.pool_id, e));
+            assert_eq!(state.object_id, f.pool_id);
+            assert!(state.fee_rate_bps.is_some(), "{} {}: no fee", f.dex, f.pool_id);
+        }
+    }
+
+    #[test]
+    fn test_fixture_paths_are_canonical() {
+        for dir in [SYNTHETIC_FIXTURES_DIR, RECORDED_FIXTURES_DIR].map(Path::new).into_iter().filter(|d| d.is_dir()) {
+            for f in load_all(dir).unwrap() {
+                assert!(fixture_path(dir, &f.dex, &f.pool_id).exists());
+            }
+        }
+    }
+
+    #[test]
+    fn test_diff_structure_ignores_values() {
+        let a = json!({"fields": {"value": "1", "list": [{"x": 1}]}});
+        let b = json!({"fields": {"value": "999", "list": [{"x": 7}, {"x": 8}]}});
+        assert!(diff_structure(&a, &b).is_empty());
+    }
+
+    #[test]
+    fn test_diff_structure_reports_schema_drift() {
+        let a = json!({"fields": {"base_vault": {"fields": {"balance": "1"}}, "fee": "3"}});
+        let b = json!({"fields": {"base_vault": {"fields": {"value": "1"}}, "fee": 3}});
+        let diffs = diff_structure(&a, &b);
+        assert!(diffs.contains(&"$.fields.base_vault.fields.balance: removed".to_string()));
+        assert!(diffs.contains(&"$.fields.base_vault.fields.value: added".to_string()));
+        assert!(diffs.contains(&"$.fields.fee: string -> number".to_string()));
+    }
+
+    #[test]
+    fn test_scrub_timestamps_keeps_structure() {
+        let mut v = json!({"fields": {"last_update_timestamp": "1700000000000", "reserve": "5"}});
+        let before = structure(&v);
+        scrub_timestamps(&mut v);
+        assert_eq!(v["fields"]["last_update_timestamp"], "0");
+        assert_eq!(v["fields"]["reserve"], "5");
+        assert_eq!(structure(&v), before);
+    }
+}
@@ -0,0 +1,204 @@
+//! Per-pool swap flow from WebSocket swap events.
+//!
+//! A pool that just absorbed a burst of one-directional selling tends to be
+//! the cheap side of the next spread. Each observed swap is kept for the
+//! longest window, bounded per pool, and summarized over short windows
+//! (1m / 5m) as net token-A flow and trade counts.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Short window: the burst that is moving the price right now.
+pub const FLOW_WINDOW_SHORT_MS: u64 = 60_000;
+/// Long window: the trend the burst sits in.
+pub const FLOW_WINDOW_LONG_MS: u64 = 300_000;
+
+/// Swaps kept per pool; older ones are dropped even inside the window.
+pub const MAX_SWAPS_PER_POOL: usize = 512;
+
+/// One swap, in the pool's own A/B orientation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapFlow {
+    /// Token A in, token B out
+    pub a_to_b: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+impl SwapFlow {
+    /// Token A moved into the pool (negative when A was taken out).
+    pub fn net_a_in(&self) -> i128 {
+        if self.a_to_b {
+            self.amount_in as i128
+        } else {
+            -(self.amount_out as i128)
+        }
+    }
+
+    /// Token A moved in either direction.
+    pub fn gross_a(&self) -> u128 {
+        if self.a_to_b {
+            self.amount_in as u128
+        } else {
+            self.amount_out as u128
+        }
+    }
+}
+
+/// Flow summary for one pool over one window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlowWindow {
+    pub trades: u64,
+    pub a_to_b_trades: u64,
+    /// Net token A sold into the pool. Positive means A is being dumped
+    /// here, so A is likely cheap in this pool relative to others.
+    pub net_a_in: i128,
+    pub gross_a: u128,
+}
+
+impl FlowWindow {
+    /// How one-sided the flow was: 0 = balanced, 1 = all one direction.
+    pub fn imbalance(&self) -> f64 {
+        if self.gross_a == 0 {
+            0.0
+        } else {
+            self.net_a_in.unsigned_abs() as f64 / self.gross_a as f64
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PoolFlow {
+    swaps: VecDeque<(u64, SwapFlow)>,
+}
+
+impl PoolFlow {
+    fn prune(&mut self, cutoff: u64) {
+        while self.swaps.front().is_some_and(|&(t, _)| t < cutoff) {
+            self.swaps.pop_front();
+        }
+    }
+}
+
+/// Rolling per-pool swap flow. Cheap to clone (shared state).
+#[derive(Debug, Clone, Default)]
+pub struct FlowTracker {
+    inner: Arc<Mutex<HashMap<String, PoolFlow>>>,
+}
+
+impl FlowTracker {
+    /// Record a swap observed on `pool_id` at `now_ms`.
+    pub fn record(&self, pool_id: &str, flow: SwapFlow, now_ms: u64) {
+        if let Ok(mut map) = self.inner.lock() {
+            let p = map.entry(pool_id.to_string()).or_default();
+            p.swaps.push_back((now_ms, flow));
+            p.prune(now_ms.saturating_sub(FLOW_WINDOW_LONG_MS));
+            if p.swaps.len() > MAX_SWAPS_PER_POOL {
+                p.swaps.pop_front();
+            }
+        }
+    }
+
+    /// Flow on `pool_id` over the `window_ms` ending at `now_ms`.
+    pub fn window(&self, pool_id: &str, window_ms: u64, now_ms: u64) -> FlowWindow {
+        let Ok(map) = self.inner.lock() else {
+            return FlowWindow::default();
+        };
+        let cutoff = now_ms.saturating_sub(window_ms);
+        let mut w = FlowWindow::default();
+        for (_, s) in map
+            .get(pool_id)
+            .into_iter()
+            .flat_map(|p| p.swaps.iter())
+            .filter(|(t, _)| *t >= cutoff)
+        {
+            w.trades += 1;
+            w.a_to_b_trades += s.a_to_b as u64;
+            w.net_a_in += s.net_a_in();
+            w.gross_a += s.gross_a();
+        }
+        w
+    }
+
+    /// Direction of the most recent swap seen on `pool_id`.
+    pub fn last_direction(&self, pool_id: &str) -> Option<bool> {
+        let map = self.inner.lock().ok()?;
+        map.get(pool_id)?.swaps.back().map(|(_, s)| s.a_to_b)
+    }
+
+    /// Ordering prior for a route: short-window trades across its pools,
+    /// weighted by how one-sided each pool's flow was. Routes through pools
+    /// under a fresh one-directional burst come first.
+    pub fn pressure(&self, pool_ids: &[String], now_ms: u64) -> u64 {
+        pool_ids
+            .iter()
+            .map(|id| {
+                let w = self.window(id, FLOW_WINDOW_SHORT_MS, now_ms);
+                (w.trades as f64 * w.imbalance()).round() as u64
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sell_a(amount: u64) -> SwapFlow {
+        SwapFlow { a_to_b: true, amount_in: amount, amount_out: amount / 2 }
+    }
+
+    fn buy_a(amount: u64) -> SwapFlow {
+        SwapFlow { a_to_b: false, amount_in: amount * 2, amount_out: amount }
+    }
+
+    #[test]
+    fn test_window_accounting() {
+        let flow = FlowTracker::default();
+        flow.record("0x1", sell_a(1_000), 0);
+        flow.record("0x1", sell_a(500), 200_000);
+        flow.record("0x1", buy_a(300), 250_000);
+
+        let short = flow.window("0x1", FLOW_WINDOW_SHORT_MS, 260_000);
+        assert_eq!(short.trades, 2);
+        assert_eq!(short.a_to_b_trades, 1);
+        assert_eq!(short.net_a_in, 200);
+        assert_eq!(short.gross_a, 800);
+        assert!((short.imbalance() - 0.25).abs() < 1e-9);
+
+        let long = flow.window("0x1", FLOW_WINDOW_LONG_MS, 260_000);
+        assert_eq!(long.trades, 3);
+        assert_eq!(long.net_a_in, 1_200);
+        assert_eq!(flow.last_direction("0x1"), Some(false));
+        assert_eq!(flow.window("0x2", FLOW_WINDOW_LONG_MS, 260_000), FlowWindow::default());
+    }
+
+    #[test]
+    fn test_memory_bounded_per_pool() {
+        let flow = FlowTracker::default();
+        // Swaps older than the long window are dropped on the next record
+        flow.record("0x1", sell_a(1), 0);
+        flow.record("0x1", sell_a(1), FLOW_WINDOW_LONG_MS + 1);
+        assert_eq!(flow.window("0x1", u64::MAX, FLOW_WINDOW_LONG_MS + 1).trades, 1);
+
+        // A burst inside the window is capped
+        for i in 0..(MAX_SWAPS_PER_POOL as u64 + 10) {
+            flow.record("0x2", sell_a(1), 1_000 + i);
+        }
+        assert_eq!(flow.window("0x2", u64::MAX, 2_000).trades, MAX_SWAPS_PER_POOL as u64);
+    }
+
+    #[test]
+    fn test_pressure_favors_one_sided_bursts() {
+        let flow = FlowTracker::default();
+        for i in 0..4 {
+            flow.record("0xdump", sell_a(100), i);
+            // Balanced churn: as many trades, no net direction
+            flow.record("0xchurn", if i % 2 == 0 { sell_a(100) } else { buy_a(100) }, i);
+        }
+        let dump = flow.pressure(&["0xdump".to_string()], 10);
+        let churn = flow.pressure(&["0xchurn".to_string()], 10);
+        assert_eq!(dump, 4);
+        assert_eq!(churn, 0);
+    }
+}
@@ -1,8 +1,23 @@
+pub mod deepbook_book;
+pub mod fee_type_verifier;
+pub mod fixtures;
+pub mod flow;
 pub mod parsers;
 pub mod pool_cache;
+pub mod pool_set;
+pub mod pool_watcher;
 pub mod rpc_poller;
+pub mod subscriptions;
+pub mod ws_endpoints;
 pub mod ws_stream;
 
-pub use pool_cache::PoolCache;
-pub use rpc_poller::RpcPoller;
+pub use deepbook_book::BookFetcher;
+pub use fee_type_verifier::FeeTypeVerifier;
+pub use flow::FlowTracker;
+pub use pool_cache::{PoolCache, PoolWarmup};
+pub use pool_set::{PoolSet, PoolSetDiff};
+pub use pool_watcher::PoolWatcher;
+pub use rpc_poller::{PollBackoff, PollPacing, RpcPoller};
+pub use subscriptions::{PoolActivity, SubscriptionBudget};
+pub use ws_endpoints::WsEndpoints;
 pub use ws_stream::{DexPackage, TxEffectStream, WsStream};
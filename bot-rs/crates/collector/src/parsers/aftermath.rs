@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use arb_types::decimals::decimals_for_coin_type;
 use arb_types::pool::{Dex, PoolState};
 use serde_json::Value;
 
@@ -13,7 +14,10 @@ use super::PoolMeta;
 /// Aftermath's `normalized_balances` are stored as very large strings
 /// (scaled to 18 decimal fixed-point) so they overflow u64.
 /// We parse them as f64 and derive synthetic reserves that preserve
-/// the correct price ratio while fitting in u64.
+/// the correct price ratio while fitting in u64. The real depth goes in
+/// `raw_balance_a`/`raw_balance_b`: each normalized balance divided by the
+/// pool's `decimal_scalars` entry (`10^(18 - decimals)`), which gives the
+/// balance in the coin's base units.
 pub(crate) fn parse(content: &Value, meta: &PoolMeta, now_ms: u64) -> Result<PoolState> {
     let fields = content
         .get("fields")
@@ -36,6 +40,9 @@ pub(crate) fn parse(content: &Value, meta: &PoolMeta, now_ms: u64) -> Result<Poo
         _ => (None, None),
     };
 
+    let raw_balance_a = extract_raw_balance(fields, 0, &meta.coin_type_a);
+    let raw_balance_b = extract_raw_balance(fields, 1, &meta.coin_type_b);
+
     // Extract fee rate from fees_swap_in (Aftermath uses 18-decimal fixed-point bps)
     // e.g. "2500000000000000" = 0.0025 = 25 bps
     let fee_rate_bps = extract_fee_bps(fields);
@@ -53,8 +60,13 @@ pub(crate) fn parse(content: &Value, meta: &PoolMeta, now_ms: u64) -> Result<Poo
         reserve_b,
         best_bid: None,
         best_ask: None,
+        book_depth: None,
         last_updated_ms: now_ms,
         fee_type: None,
+        min_trade_size: None,
+        lot_size: None,
+        raw_balance_a,
+        raw_balance_b,
     })
 }
 
@@ -68,6 +80,26 @@ fn extract_normalized_balance(fields: &Value, index: usize) -> Option<f64> {
         .and_then(|s| s.parse::<f64>().ok())
 }
 
+/// Balance at index in the coin's base units: the normalized balance over
+/// the pool's decimal scalar, or over `10^(18 - decimals)` from the known
+/// decimals when the pool doesn't list its scalars.
+fn extract_raw_balance(fields: &Value, index: usize, coin_type: &str) -> Option<u128> {
+    let normalized: u128 = fields
+        .get("normalized_balances")
+        .and_then(|v| v.as_array())
+        .and_then(|arr| arr.get(index))
+        .and_then(|b| b.as_str())
+        .and_then(|s| s.parse().ok())?;
+    let scalar = fields
+        .get("decimal_scalars")
+        .and_then(|v| v.as_array())
+        .and_then(|arr| arr.get(index))
+        .and_then(|s| s.as_str())
+        .and_then(|s| s.parse::<u128>().ok())
+        .unwrap_or_else(|| 10u128.pow(18u32.saturating_sub(decimals_for_coin_type(coin_type) as u32)));
+    (scalar > 0).then(|| normalized / scalar)
+}
+
 /// Extract swap fee in basis points from Aftermath's fees_swap_in field.
 /// Aftermath stores fees as 18-decimal fixed-point: 2500000000000000 = 0.25% = 25 bps.
 fn extract_fee_bps(fields: &Value) -> Option<u64> {
@@ -0,0 +1,49 @@
+use anyhow::{Context, Result};
+use arb_types::pool::{Dex, PoolState};
+use serde_json::Value;
+
+use super::{event_i32, field_u128, field_u64, PoolMeta};
+
+/// Parse a Bluefin spot CLMM Pool object.
+///
+/// Bluefin Pool<A, B>:
+/// - current_sqrt_price: u128 (Q64.64)
+/// - liquidity: u128
+/// - current_tick_index: I32 { bits: u32 } (two's complement; older
+///   layouts call it current_tick)
+/// - fee_rate: u64 (in 1e6 units, e.g. 2000 = 0.2%)
+pub(crate) fn parse(content: &Value, meta: &PoolMeta, now_ms: u64) -> Result<PoolState> {
+    let fields = content
+        .get("fields")
+        .context("Missing fields in Bluefin pool")?;
+
+    let sqrt_price = field_u128(fields, "current_sqrt_price").ok();
+    let liquidity = field_u128(fields, "liquidity").ok();
+    let tick_index =
+        event_i32(fields, "current_tick_index").or_else(|| event_i32(fields, "current_tick"));
+
+    let fee_rate = field_u64(fields, "fee_rate").ok();
+    let fee_rate_bps = fee_rate.map(|f| f / 100);
+
+    Ok(PoolState {
+        object_id: meta.object_id.clone(),
+        dex: Dex::Bluefin,
+        coin_type_a: meta.coin_type_a.clone(),
+        coin_type_b: meta.coin_type_b.clone(),
+        sqrt_price,
+        tick_index,
+        liquidity,
+        fee_rate_bps,
+        reserve_a: None,
+        reserve_b: None,
+        best_bid: None,
+        best_ask: None,
+        book_depth: None,
+        last_updated_ms: now_ms,
+        fee_type: None,
+        min_trade_size: None,
+        lot_size: None,
+        raw_balance_a: None,
+        raw_balance_b: None,
+    })
+}
@@ -2,7 +2,8 @@ use anyhow::{Context, Result};
 use arb_types::pool::{Dex, PoolState};
 use serde_json::Value;
 
-use super::{field_u128, field_u64, PoolMeta};
+use super::{field_bool, field_u128, field_u64, tick_from_sqrt_price, PoolMeta};
+use crate::flow::SwapFlow;
 
 /// Parse a Cetus CLMM Pool object from `sui_getObject` content.
 ///
@@ -49,7 +50,43 @@ pub(crate) fn parse(content: &Value, meta: &PoolMeta, now_ms: u64) -> Result<Poo
         reserve_b: None,
         best_bid: None,
         best_ask: None,
+        book_depth: None,
         last_updated_ms: now_ms,
         fee_type: None,
+        min_trade_size: None,
+        lot_size: None,
+        raw_balance_a: None,
+        raw_balance_b: None,
+    })
+}
+
+/// Apply a Cetus `pool::SwapEvent` to the cached state.
+///
+/// The event carries `after_sqrt_price` and `steps` but not the liquidity.
+/// A single-step swap stayed inside one initialized tick range, so the
+/// cached liquidity still holds; multi-step swaps crossed ticks and need a
+/// re-fetch (`None`).
+pub(crate) fn apply_swap_event(parsed: &Value, prev: &PoolState, now_ms: u64) -> Option<PoolState> {
+    let sqrt_price = field_u128(parsed, "after_sqrt_price").ok()?;
+    if field_u64(parsed, "steps").ok()? > 1 {
+        return None;
+    }
+    prev.liquidity?;
+
+    Some(PoolState {
+        sqrt_price: Some(sqrt_price),
+        tick_index: Some(tick_from_sqrt_price(sqrt_price)),
+        last_updated_ms: now_ms,
+        ..prev.clone()
+    })
+}
+
+/// Amounts and direction of a Cetus `pool::SwapEvent`
+/// (`atob`, `amount_in`, `amount_out`).
+pub(crate) fn swap_flow(parsed: &Value) -> Option<SwapFlow> {
+    Some(SwapFlow {
+        a_to_b: field_bool(parsed, "atob")?,
+        amount_in: field_u64(parsed, "amount_in").ok()?,
+        amount_out: field_u64(parsed, "amount_out").ok()?,
     })
 }
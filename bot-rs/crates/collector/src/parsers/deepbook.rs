@@ -2,12 +2,14 @@ use anyhow::{Context, Result};
 use arb_types::pool::{Dex, PoolState};
 use serde_json::Value;
 
-use super::PoolMeta;
+use super::{field_bool, field_u64, PoolMeta};
+use crate::flow::SwapFlow;
 
 /// Parse a DeepBook V3 PoolInner object.
 ///
 /// DeepBook uses a CLOB model — no sqrt_price or liquidity.
-/// Key fields: base_vault, quote_vault balances, and order book state.
+/// Key fields: base_vault, quote_vault balances, and order book state
+/// (`book.lot_size` / `book.min_size` bound the tradeable amounts).
 ///
 /// Note: DeepBook V3 wraps pool data in `0x2::versioned::Versioned`.
 /// The RPC poller handles the two-step unwrap — by the time we get here,
@@ -25,6 +27,11 @@ pub(crate) fn parse(content: &Value, meta: &PoolMeta, now_ms: u64) -> Result<Poo
     // Extract taker fee in basis points if available
     let fee_rate_bps = extract_fee_bps(fields);
 
+    // Order size constraints from the book params (base-asset units)
+    let book = fields.get("book").and_then(|b| b.get("fields"));
+    let lot_size = book.and_then(|b| field_u64(b, "lot_size").ok());
+    let min_trade_size = book.and_then(|b| field_u64(b, "min_size").ok());
+
     Ok(PoolState {
         object_id: meta.object_id.clone(),
         dex: Dex::DeepBook,
@@ -38,8 +45,13 @@ pub(crate) fn parse(content: &Value, meta: &PoolMeta, now_ms: u64) -> Result<Poo
         reserve_b,
         best_bid: None,
         best_ask: None,
+        book_depth: None,
         last_updated_ms: now_ms,
         fee_type: None,
+        min_trade_size,
+        lot_size,
+        raw_balance_a: None,
+        raw_balance_b: None,
     })
 }
 
@@ -69,3 +81,17 @@ fn extract_fee_bps(fields: &Value) -> Option<u64> {
             .or_else(|| v.as_str().and_then(|s| s.parse::<u64>().ok()))
     })
 }
+
+/// Amounts and direction of a DeepBook `order_info::OrderFilled` event.
+/// Base is token A: a taker bid pays quote for base (B → A), a taker ask
+/// sells base for quote (A → B).
+pub(crate) fn swap_flow(parsed: &Value) -> Option<SwapFlow> {
+    let taker_is_bid = field_bool(parsed, "taker_is_bid")?;
+    let base = field_u64(parsed, "base_quantity").ok()?;
+    let quote = field_u64(parsed, "quote_quantity").ok()?;
+    Some(if taker_is_bid {
+        SwapFlow { a_to_b: false, amount_in: quote, amount_out: base }
+    } else {
+        SwapFlow { a_to_b: true, amount_in: base, amount_out: quote }
+    })
+}
@@ -2,7 +2,8 @@ use anyhow::{Context, Result};
 use arb_types::pool::{Dex, PoolState};
 use serde_json::Value;
 
-use super::{field_u128, field_u64, PoolMeta};
+use super::{event_i32, field_bool, field_u128, field_u64, PoolMeta};
+use crate::flow::SwapFlow;
 
 /// Parse a FlowX CLMM v3 Pool object.
 ///
@@ -46,7 +47,34 @@ pub(crate) fn parse(content: &Value, meta: &PoolMeta, now_ms: u64) -> Result<Poo
         reserve_b: None,
         best_bid: None,
         best_ask: None,
+        book_depth: None,
         last_updated_ms: now_ms,
         fee_type: None,
+        min_trade_size: None,
+        lot_size: None,
+        raw_balance_a: None,
+        raw_balance_b: None,
     })
 }
+
+/// Apply a FlowX CLMM `pool::Swap` event to the cached state. The event
+/// reports the complete post-swap `sqrt_price_after`, `liquidity`, and `tick_index`.
+pub(crate) fn apply_swap_event(parsed: &Value, prev: &PoolState, now_ms: u64) -> Option<PoolState> {
+    Some(PoolState {
+        sqrt_price: Some(field_u128(parsed, "sqrt_price_after").ok()?),
+        liquidity: Some(field_u128(parsed, "liquidity").ok()?),
+        tick_index: Some(event_i32(parsed, "tick_index")?),
+        last_updated_ms: now_ms,
+        ..prev.clone()
+    })
+}
+
+/// Amounts and direction of a FlowX CLMM `pool::Swap` event
+/// (`x_for_y`, `amount_x`, `amount_y`).
+pub(crate) fn swap_flow(parsed: &Value) -> Option<SwapFlow> {
+    let a_to_b = field_bool(parsed, "x_for_y")?;
+    let amount_x = field_u64(parsed, "amount_x").ok()?;
+    let amount_y = field_u64(parsed, "amount_y").ok()?;
+    let (amount_in, amount_out) = if a_to_b { (amount_x, amount_y) } else { (amount_y, amount_x) };
+    Some(SwapFlow { a_to_b, amount_in, amount_out })
+}
@@ -3,6 +3,7 @@ use arb_types::pool::{Dex, PoolState};
 use serde_json::Value;
 
 use super::{field_u64, PoolMeta};
+use crate::flow::SwapFlow;
 
 /// Parse a FlowX AMM v2 Pool object (constant-product / xy=k).
 ///
@@ -52,7 +53,27 @@ pub(crate) fn parse(content: &Value, meta: &PoolMeta, now_ms: u64) -> Result<Poo
         reserve_b,
         best_bid: None,
         best_ask: None,
+        book_depth: None,
         last_updated_ms: now_ms,
         fee_type: None,
+        min_trade_size: None,
+        lot_size: None,
+        raw_balance_a: None,
+        raw_balance_b: None,
     })
 }
+
+/// Amounts and direction of a FlowX AMM `pair::Swapped` event, which
+/// reports all four legs (`amount_x_in`, `amount_y_out`, ...) with the
+/// unused side zero.
+pub(crate) fn swap_flow(parsed: &Value) -> Option<SwapFlow> {
+    let x_in = field_u64(parsed, "amount_x_in").ok()?;
+    let y_in = field_u64(parsed, "amount_y_in").ok()?;
+    let x_out = field_u64(parsed, "amount_x_out").ok()?;
+    let y_out = field_u64(parsed, "amount_y_out").ok()?;
+    match (x_in > 0, y_in > 0) {
+        (true, false) => Some(SwapFlow { a_to_b: true, amount_in: x_in, amount_out: y_out }),
+        (false, true) => Some(SwapFlow { a_to_b: false, amount_in: y_in, amount_out: x_out }),
+        _ => None,
+    }
+}
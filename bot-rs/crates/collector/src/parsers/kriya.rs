@@ -0,0 +1,56 @@
+use anyhow::{Context, Result};
+use arb_types::pool::{Dex, PoolState};
+use serde_json::Value;
+
+use super::{field_u128, field_u64, PoolMeta};
+
+/// Parse a Kriya CLMM Pool object.
+///
+/// Kriya CLMM Pool<X, Y>:
+/// - sqrt_price: u128 (Q64.64)
+/// - liquidity: u128
+/// - tick_index: I32 { bits: u32 } (two's complement)
+/// - swap_fee_rate: u64 (parts per million, 3000 = 0.3%)
+pub(crate) fn parse(content: &Value, meta: &PoolMeta, now_ms: u64) -> Result<PoolState> {
+    let fields = content
+        .get("fields")
+        .context("Missing fields in Kriya pool")?;
+
+    let sqrt_price = field_u128(fields, "sqrt_price").ok();
+    let liquidity = field_u128(fields, "liquidity").ok();
+
+    let tick_index = fields
+        .get("tick_index")
+        .and_then(|v| v.get("fields"))
+        .and_then(|f| f.get("bits"))
+        .and_then(|b| {
+            b.as_u64()
+                .or_else(|| b.as_str().and_then(|s| s.parse().ok()))
+                .map(|bits| (bits as u32) as i32)
+        });
+
+    let fee_rate = field_u64(fields, "swap_fee_rate").ok();
+    let fee_rate_bps = fee_rate.map(|f| f / 100);
+
+    Ok(PoolState {
+        object_id: meta.object_id.clone(),
+        dex: Dex::Kriya,
+        coin_type_a: meta.coin_type_a.clone(),
+        coin_type_b: meta.coin_type_b.clone(),
+        sqrt_price,
+        tick_index,
+        liquidity,
+        fee_rate_bps,
+        reserve_a: None,
+        reserve_b: None,
+        best_bid: None,
+        best_ask: None,
+        book_depth: None,
+        last_updated_ms: now_ms,
+        fee_type: None,
+        min_trade_size: None,
+        lot_size: None,
+        raw_balance_a: None,
+        raw_balance_b: None,
+    })
+}
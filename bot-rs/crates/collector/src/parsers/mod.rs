@@ -1,29 +1,74 @@
 pub mod aftermath;
+pub mod bluefin;
 pub mod cetus;
 pub mod deepbook;
 pub mod flowx;
 pub mod flowx_amm;
+pub mod kriya;
 pub mod turbos;
 
 use anyhow::{Context, Result};
-use arb_types::pool::PoolState;
+use arb_types::pool::{Dex, PoolState};
 use serde_json::Value;
 
-/// Route to the correct parser based on DEX name.
+use crate::flow::SwapFlow;
+
+/// Route to the correct parser based on DEX name (any alias `Dex::from_str`
+/// accepts). The match is exhaustive, so every resolvable DEX has a parser.
 pub(crate) fn parse_pool_object(
     content: &Value,
     dex: &str,
     meta: &PoolMeta,
     now_ms: u64,
 ) -> Result<PoolState> {
-    match dex.to_lowercase().as_str() {
-        "cetus" => cetus::parse(content, meta, now_ms),
-        "turbos" => turbos::parse(content, meta, now_ms),
-        "deepbook" => deepbook::parse(content, meta, now_ms),
-        "aftermath" => aftermath::parse(content, meta, now_ms),
-        "flowx_clmm" | "flowx" => flowx::parse(content, meta, now_ms),
-        "flowx_amm" => flowx_amm::parse(content, meta, now_ms),
-        _ => anyhow::bail!("Unknown DEX: {dex}"),
+    match dex.parse::<Dex>()? {
+        Dex::Cetus => cetus::parse(content, meta, now_ms),
+        Dex::Turbos => turbos::parse(content, meta, now_ms),
+        Dex::DeepBook => deepbook::parse(content, meta, now_ms),
+        Dex::Aftermath => aftermath::parse(content, meta, now_ms),
+        Dex::FlowxClmm => flowx::parse(content, meta, now_ms),
+        Dex::FlowxAmm => flowx_amm::parse(content, meta, now_ms),
+        Dex::Kriya => kriya::parse(content, meta, now_ms),
+        Dex::Bluefin => bluefin::parse(content, meta, now_ms),
+    }
+}
+
+/// Build the post-swap state of `prev` directly from a swap event's
+/// `parsedJson`, without re-fetching the pool object.
+///
+/// Returns `None` if the event isn't a swap, the DEX doesn't report post-swap
+/// state in its events (DeepBook, Aftermath, FlowX AMM, Kriya, Bluefin), or the event lacks
+/// a needed field — the caller should then re-fetch.
+pub(crate) fn apply_swap_event(
+    event_type: &str,
+    parsed: &Value,
+    prev: &PoolState,
+    now_ms: u64,
+) -> Option<PoolState> {
+    let name = event_type.rsplit("::").next()?;
+    match (prev.dex, name) {
+        (Dex::Cetus, "SwapEvent") => cetus::apply_swap_event(parsed, prev, now_ms),
+        (Dex::Turbos, "SwapEvent") => turbos::apply_swap_event(parsed, prev, now_ms),
+        (Dex::FlowxClmm, "Swap" | "SwapEvent") => flowx::apply_swap_event(parsed, prev, now_ms),
+        _ => None,
+    }
+}
+
+/// Extract the amounts and direction of a swap event, in the pool's A/B
+/// orientation, for flow tracking.
+///
+/// Returns `None` for non-swap events, venues whose swap events don't map
+/// to a single A/B direction (Aftermath's multi-asset swaps), or events
+/// missing a needed field.
+pub(crate) fn parse_swap_flow(event_type: &str, parsed: &Value, dex: &str) -> Option<SwapFlow> {
+    let name = event_type.rsplit("::").next()?;
+    match (dex.parse::<Dex>().ok()?, name) {
+        (Dex::Cetus, "SwapEvent") => cetus::swap_flow(parsed),
+        (Dex::Turbos, "SwapEvent") => turbos::swap_flow(parsed),
+        (Dex::FlowxClmm, "Swap" | "SwapEvent") => flowx::swap_flow(parsed),
+        (Dex::FlowxAmm, "Swapped") => flowx_amm::swap_flow(parsed),
+        (Dex::DeepBook, "OrderFilled") => deepbook::swap_flow(parsed),
+        _ => None,
     }
 }
 
@@ -47,6 +92,12 @@ pub(crate) fn field_u128(fields: &Value, name: &str) -> Result<u128> {
         .with_context(|| format!("Missing or invalid u128 field: {name}"))
 }
 
+/// Helper: extract a bool field (native or string-encoded).
+pub(crate) fn field_bool(fields: &Value, name: &str) -> Option<bool> {
+    let v = fields.get(name)?;
+    v.as_bool().or_else(|| v.as_str()?.parse().ok())
+}
+
 /// Helper: extract a string field.
 #[allow(dead_code)]
 pub(crate) fn field_str<'a>(fields: &'a Value, name: &str) -> Result<&'a str> {
@@ -56,12 +107,31 @@ pub(crate) fn field_str<'a>(fields: &'a Value, name: &str) -> Result<&'a str> {
         .with_context(|| format!("Missing string field: {name}"))
 }
 
+/// Helper: extract a Move `I32 { bits: u32 }` from event JSON, which may be
+/// flattened (`{"bits": n}`) or wrapped like object content (`{"fields": {"bits": n}}`).
+pub(crate) fn event_i32(fields: &Value, name: &str) -> Option<i32> {
+    let v = fields.get(name)?;
+    let bits = v.get("bits").or_else(|| v.get("fields")?.get("bits"))?;
+    bits.as_u64()
+        .or_else(|| bits.as_str()?.parse().ok())
+        .map(|b| (b as u32) as i32)
+        .or_else(|| bits.as_i64().map(|b| b as i32))
+}
+
+/// Tick index for a Q64.64 sqrt price: `floor(log_1.0001(price))`.
+/// Computed in f64, so it can be off by one right at a tick boundary.
+pub(crate) fn tick_from_sqrt_price(sqrt_price: u128) -> i32 {
+    let sqrt = sqrt_price as f64 / 18_446_744_073_709_551_616.0; // 2^64
+    (2.0 * sqrt.ln() / 1.0001f64.ln()).floor() as i32
+}
+
 // Re-export PoolMeta for parser modules
 pub(crate) use crate::rpc_poller::PoolMeta;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures::{self, PoolFixture};
     use serde_json::json;
 
     fn test_meta() -> PoolMeta {
@@ -73,6 +143,141 @@ mod tests {
         }
     }
 
+    /// Pool fixtures for `dex`: recorded captures, or synthetic ones shaped
+    /// like mainnet RPC results until the DEX has a recording.
+    fn fixtures_for(dex: &str) -> Vec<PoolFixture> {
+        let f = fixtures::load_dex_for_tests(dex).unwrap();
+        assert!(!f.is_empty(), "no {dex} fixtures");
+        f
+    }
+
+    /// Parsed pool plus the raw Move fields it was parsed from.
+    fn parse_fixture(f: &PoolFixture) -> (PoolState, Value) {
+        let content = f.content().unwrap();
+        let state = parse_pool_object(&content, &f.dex, &f.meta(), 0).unwrap();
+        (state, content["fields"].clone())
+    }
+
+    fn raw_u64(v: &Value) -> u64 {
+        v.as_u64().or_else(|| v.as_str()?.parse().ok()).unwrap()
+    }
+
+    fn raw_u128(v: &Value) -> u128 {
+        v.as_str().unwrap().parse().unwrap()
+    }
+
+    // ── Fixture tests ──
+    // Expectations are derived from the fixture's own raw fields, so the same
+    // tests hold for fixtures re-recorded from mainnet.
+
+    #[test]
+    fn test_cetus_fixture() {
+        for f in fixtures_for("cetus") {
+            let (pool, raw) = parse_fixture(&f);
+            assert_eq!(pool.sqrt_price, Some(raw_u128(&raw["current_sqrt_price"])));
+            assert_eq!(pool.liquidity, Some(raw_u128(&raw["liquidity"])));
+            let bits = raw_u64(&raw["current_tick_index"]["fields"]["bits"]);
+            assert_eq!(pool.tick_index, Some(bits as u32 as i32));
+            assert_eq!(pool.fee_rate_bps, Some(raw_u64(&raw["fee_rate"]) / 100));
+        }
+    }
+
+    #[test]
+    fn test_turbos_fixture() {
+        for f in fixtures_for("turbos") {
+            let (pool, raw) = parse_fixture(&f);
+            assert_eq!(pool.sqrt_price, Some(raw_u128(&raw["sqrt_price"])));
+            assert_eq!(pool.liquidity, Some(raw_u128(&raw["liquidity"])));
+            let bits = raw_u64(&raw["tick_current_index"]["fields"]["bits"]);
+            assert_eq!(pool.tick_index, Some(bits as u32 as i32));
+            assert_eq!(pool.fee_rate_bps, Some(raw_u64(&raw["fee"]) / 100));
+
+            let fee_type = f.parse(0).unwrap().fee_type.expect("Turbos fee type");
+            assert!(f.object_type().unwrap().ends_with(&format!(", {}>", fee_type)));
+        }
+    }
+
+    #[test]
+    fn test_deepbook_fixture_versioned() {
+        for f in fixtures_for("deepbook") {
+            let (pool, raw) = parse_fixture(&f);
+            assert_eq!(pool.reserve_a, Some(raw_u64(&raw["base_vault"]["fields"]["value"])));
+            assert_eq!(pool.reserve_b, Some(raw_u64(&raw["quote_vault"]["fields"]["value"])));
+            assert_eq!(pool.fee_rate_bps, Some(raw_u64(&raw["taker_fee"])));
+            assert_eq!(pool.lot_size, Some(raw_u64(&raw["book"]["fields"]["lot_size"])));
+            assert_eq!(pool.min_trade_size, Some(raw_u64(&raw["book"]["fields"]["min_size"])));
+            assert_eq!(pool.sqrt_price, None);
+        }
+    }
+
+    #[test]
+    fn test_aftermath_fixture() {
+        for f in fixtures_for("aftermath") {
+            let (pool, raw) = parse_fixture(&f);
+            let na: f64 = raw["normalized_balances"][0].as_str().unwrap().parse().unwrap();
+            let nb: f64 = raw["normalized_balances"][1].as_str().unwrap().parse().unwrap();
+            // u128-scale balances → synthetic reserves at 1B virtual depth
+            assert_eq!(pool.reserve_a, Some(1_000_000_000));
+            assert_eq!(pool.reserve_b, Some((1e9 * nb / na) as u64));
+            // Real depth: normalized balance over the pool's decimal scalar
+            let scalar = |i: usize| raw_u128(&raw["decimal_scalars"][i]);
+            assert_eq!(pool.raw_balance_a, Some(raw_u128(&raw["normalized_balances"][0]) / scalar(0)));
+            assert_eq!(pool.raw_balance_b, Some(raw_u128(&raw["normalized_balances"][1]) / scalar(1)));
+            let fee: f64 = raw["fees_swap_in"][0].as_str().unwrap().parse().unwrap();
+            assert_eq!(pool.fee_rate_bps, Some((fee / 1e18 * 10_000.0) as u64));
+        }
+    }
+
+    #[test]
+    fn test_flowx_clmm_fixture() {
+        for f in fixtures_for("flowx_clmm") {
+            let (pool, raw) = parse_fixture(&f);
+            assert_eq!(pool.sqrt_price, Some(raw_u128(&raw["sqrt_price"])));
+            assert_eq!(pool.liquidity, Some(raw_u128(&raw["liquidity"])));
+            let bits = raw_u64(&raw["tick_index"]["fields"]["bits"]);
+            assert_eq!(pool.tick_index, Some(bits as u32 as i32));
+        }
+    }
+
+    #[test]
+    fn test_kriya_fixture() {
+        for f in fixtures_for("kriya") {
+            let (pool, raw) = parse_fixture(&f);
+            assert_eq!(pool.dex, arb_types::pool::Dex::Kriya);
+            assert_eq!(pool.sqrt_price, Some(raw_u128(&raw["sqrt_price"])));
+            assert_eq!(pool.liquidity, Some(raw_u128(&raw["liquidity"])));
+            let bits = raw_u64(&raw["tick_index"]["fields"]["bits"]);
+            assert_eq!(pool.tick_index, Some(bits as u32 as i32));
+            assert_eq!(pool.fee_rate_bps, Some(raw_u64(&raw["swap_fee_rate"]) / 100));
+            assert!(pool.price_a_in_b().is_some());
+        }
+    }
+
+    #[test]
+    fn test_bluefin_fixture() {
+        for f in fixtures_for("bluefin") {
+            let (pool, raw) = parse_fixture(&f);
+            assert_eq!(pool.dex, arb_types::pool::Dex::Bluefin);
+            assert_eq!(pool.sqrt_price, Some(raw_u128(&raw["current_sqrt_price"])));
+            assert_eq!(pool.liquidity, Some(raw_u128(&raw["liquidity"])));
+            let bits = raw_u64(&raw["current_tick_index"]["fields"]["bits"]);
+            assert_eq!(pool.tick_index, Some(bits as u32 as i32));
+            assert_eq!(pool.fee_rate_bps, Some(raw_u64(&raw["fee_rate"]) / 100));
+            assert!(pool.price_a_in_b().is_some());
+        }
+    }
+
+    #[test]
+    fn test_flowx_amm_fixture_dynamic_field() {
+        for f in fixtures_for("flowx_amm") {
+            let (pool, raw) = parse_fixture(&f);
+            let inner = &raw["value"]["fields"];
+            assert_eq!(pool.reserve_a, Some(raw_u64(&inner["reserve_x"])));
+            assert_eq!(pool.reserve_b, Some(raw_u64(&inner["reserve_y"])));
+            assert_eq!(pool.fee_rate_bps, Some(raw_u64(&inner["fee_rate"])));
+        }
+    }
+
     // ── field_u64 / field_u128 helper tests ──
 
     #[test]
@@ -245,6 +450,8 @@ mod tests {
         let pool = deepbook::parse(&content, &test_meta(), 0).unwrap();
         assert_eq!(pool.reserve_a, None);
         assert_eq!(pool.reserve_b, None);
+        assert_eq!(pool.lot_size, None);
+        assert_eq!(pool.min_trade_size, None);
     }
 
     // ── Aftermath parser tests ──
@@ -284,12 +491,37 @@ mod tests {
         assert_eq!(pool.fee_rate_bps, Some(25));
     }
 
+    #[test]
+    fn test_aftermath_parse_keeps_real_balances() {
+        let content = json!({
+            "fields": {
+                "normalized_balances": ["3000000000000000000000", "7500000000000000000000"],
+                "decimal_scalars": ["1000000000", "1000000000000"]
+            }
+        });
+        let pool = aftermath::parse(&content, &test_meta(), 0).unwrap();
+        // 3000 SUI and 7500 USDC in base units; the price ratio is unchanged
+        assert_eq!(pool.raw_balance_a, Some(3_000_000_000_000));
+        assert_eq!(pool.raw_balance_b, Some(7_500_000_000));
+        assert_eq!(pool.price_a_in_b(), Some(2.5));
+
+        // Without scalars, the known decimals give the same balances
+        let content = json!({
+            "fields": { "normalized_balances": ["3000000000000000000000", "7500000000000000000000"] }
+        });
+        let pool = aftermath::parse(&content, &test_meta(), 0).unwrap();
+        assert_eq!(pool.raw_balance_a, Some(3_000_000_000_000));
+        assert_eq!(pool.raw_balance_b, Some(7_500_000_000));
+    }
+
     #[test]
     fn test_aftermath_parse_empty_balances() {
         let content = json!({ "fields": { "normalized_balances": [] } });
         let pool = aftermath::parse(&content, &test_meta(), 0).unwrap();
         assert_eq!(pool.reserve_a, None);
         assert_eq!(pool.reserve_b, None);
+        assert_eq!(pool.raw_balance_a, None);
+        assert_eq!(pool.raw_balance_b, None);
     }
 
     // ── FlowX CLMM parser tests ──
@@ -311,6 +543,94 @@ mod tests {
         assert_eq!(pool.fee_rate_bps, Some(20));
     }
 
+    // ── Kriya CLMM parser tests ──
+
+    #[test]
+    fn test_kriya_parse_full() {
+        let content = json!({
+            "fields": {
+                "sqrt_price": "18446744073709551616",
+                "liquidity": "999999",
+                "tick_index": { "fields": { "bits": 4294967246u64 } },
+                "swap_fee_rate": "2500"
+            }
+        });
+        let pool = kriya::parse(&content, &test_meta(), 0).unwrap();
+        assert_eq!(pool.dex, arb_types::pool::Dex::Kriya);
+        assert_eq!(pool.sqrt_price, Some(1u128 << 64));
+        assert_eq!(pool.tick_index, Some(-50));
+        assert_eq!(pool.fee_rate_bps, Some(25));
+    }
+
+    #[test]
+    fn test_kriya_parse_missing_fields() {
+        assert!(kriya::parse(&json!({}), &test_meta(), 0).is_err());
+        let pool = kriya::parse(&json!({"fields": {}}), &test_meta(), 0).unwrap();
+        assert_eq!((pool.sqrt_price, pool.tick_index, pool.fee_rate_bps), (None, None, None));
+    }
+
+    // ── Bluefin CLMM parser tests ──
+
+    #[test]
+    fn test_bluefin_parse_full() {
+        let content = json!({
+            "fields": {
+                "current_sqrt_price": "18446744073709551616",
+                "liquidity": "1000000000",
+                "current_tick_index": {
+                    "fields": { "bits": 4294967196u64 }
+                },
+                "fee_rate": "2000"
+            }
+        });
+        let pool = bluefin::parse(&content, &test_meta(), 12345).unwrap();
+        assert_eq!(pool.dex, arb_types::pool::Dex::Bluefin);
+        assert_eq!(pool.sqrt_price, Some(1u128 << 64));
+        assert_eq!(pool.liquidity, Some(1_000_000_000));
+        // 4294967196 as u32 = 0xFFFFFF9C → as i32 = -100
+        assert_eq!(pool.tick_index, Some(-100));
+        assert_eq!(pool.fee_rate_bps, Some(20)); // 2000/100
+        assert_eq!(pool.last_updated_ms, 12345);
+        assert_eq!(pool.price_a_in_b(), Some(1.0));
+    }
+
+    #[test]
+    fn test_bluefin_parse_positive_tick() {
+        let content = json!({
+            "fields": {
+                "current_sqrt_price": "18446744073709551616",
+                "liquidity": "1000",
+                "current_tick_index": { "fields": { "bits": "100" } },
+                "fee_rate": 3000
+            }
+        });
+        let pool = bluefin::parse(&content, &test_meta(), 0).unwrap();
+        assert_eq!(pool.tick_index, Some(100));
+    }
+
+    #[test]
+    fn test_bluefin_parse_current_tick_alias() {
+        let content = json!({ "fields": { "current_tick": { "fields": { "bits": 4294967295u64 } } } });
+        let pool = bluefin::parse(&content, &test_meta(), 0).unwrap();
+        assert_eq!(pool.tick_index, Some(-1));
+    }
+
+    #[test]
+    fn test_bluefin_parse_missing_fields() {
+        assert!(bluefin::parse(&json!({}), &test_meta(), 0).is_err());
+    }
+
+    #[test]
+    fn test_bluefin_parse_partial_optional_none() {
+        let content = json!({ "fields": { "fee_rate": 2500 } });
+        let pool = bluefin::parse(&content, &test_meta(), 0).unwrap();
+        assert_eq!(pool.sqrt_price, None);
+        assert_eq!(pool.liquidity, None);
+        assert_eq!(pool.tick_index, None);
+        assert_eq!(pool.fee_rate_bps, Some(25));
+        assert_eq!(pool.price_a_in_b(), None);
+    }
+
     // ── FlowX AMM parser tests ──
 
     #[test]
@@ -398,4 +718,149 @@ mod tests {
         assert_eq!(p1.dex, arb_types::pool::Dex::FlowxClmm);
         assert_eq!(p2.dex, arb_types::pool::Dex::FlowxClmm);
     }
+
+    // ── Swap events with inline post-swap state ──
+
+    fn fixture_state(dex: &str) -> PoolState {
+        parse_fixture(&fixtures_for(dex)[0]).0
+    }
+
+    #[test]
+    fn test_turbos_swap_event_applied_inline() {
+        let prev = fixture_state("turbos");
+        let event = json!({
+            "pool": prev.object_id,
+            "sqrt_price": "18000000000000000000",
+            "liquidity": "123456789",
+            "tick_current_index": { "bits": 4294967196u64 }, // -100
+            "a_to_b": true,
+        });
+        let next = apply_swap_event("0x91bf::pool::SwapEvent", &event, &prev, 42).unwrap();
+        assert_eq!(next.sqrt_price, Some(18_000_000_000_000_000_000));
+        assert_eq!(next.liquidity, Some(123_456_789));
+        assert_eq!(next.tick_index, Some(-100));
+        assert_eq!(next.last_updated_ms, 42);
+        // Everything the event doesn't report is carried over
+        assert_eq!(next.fee_rate_bps, prev.fee_rate_bps);
+        assert_eq!(next.fee_type, prev.fee_type);
+        assert_eq!(next.coin_type_a, prev.coin_type_a);
+    }
+
+    #[test]
+    fn test_turbos_swap_event_missing_liquidity_falls_back() {
+        let prev = fixture_state("turbos");
+        let event = json!({ "sqrt_price": "18000000000000000000", "tick_current_index": { "bits": 0 } });
+        assert!(apply_swap_event("0x91bf::pool::SwapEvent", &event, &prev, 42).is_none());
+    }
+
+    #[test]
+    fn test_cetus_single_step_swap_keeps_liquidity() {
+        let prev = fixture_state("cetus");
+        let after = 1u128 << 64; // price 1.0 → tick 0
+        let event = json!({ "after_sqrt_price": after.to_string(), "steps": "1" });
+        let next = apply_swap_event("0x1eab::pool::SwapEvent", &event, &prev, 7).unwrap();
+        assert_eq!(next.sqrt_price, Some(after));
+        assert_eq!(next.tick_index, Some(0));
+        assert_eq!(next.liquidity, prev.liquidity);
+    }
+
+    #[test]
+    fn test_cetus_tick_crossing_swap_falls_back() {
+        let prev = fixture_state("cetus");
+        let event = json!({ "after_sqrt_price": "18446744073709551616", "steps": "3" });
+        assert!(apply_swap_event("0x1eab::pool::SwapEvent", &event, &prev, 7).is_none());
+    }
+
+    #[test]
+    fn test_flowx_clmm_swap_event_applied_inline() {
+        let prev = fixture_state("flowx_clmm");
+        let event = json!({
+            "pool_id": prev.object_id,
+            "sqrt_price_after": "18446744073709551616",
+            "liquidity": "5000",
+            "tick_index": { "bits": 0 },
+        });
+        let next = apply_swap_event("0x25929::pool::Swap", &event, &prev, 9).unwrap();
+        assert_eq!(next.liquidity, Some(5_000));
+        assert_eq!(next.tick_index, Some(0));
+    }
+
+    #[test]
+    fn test_non_swap_or_incomplete_dex_events_fall_back() {
+        let turbos = fixture_state("turbos");
+        let complete = json!({
+            "sqrt_price": "1", "liquidity": "1", "tick_current_index": { "bits": 0 }
+        });
+        // Liquidity events change state the swap fields don't describe
+        assert!(apply_swap_event("0x91bf::pool::MintEvent", &complete, &turbos, 0).is_none());
+        // Venues whose swap events don't carry post-swap state
+        for dex in ["deepbook", "aftermath", "flowx_amm"] {
+            let prev = fixture_state(dex);
+            assert!(apply_swap_event("0x1::pool::SwapEvent", &complete, &prev, 0).is_none());
+        }
+    }
+
+    // ── Swap flow extraction ──
+
+    #[test]
+    fn test_swap_flow_per_dex_fields() {
+        let cetus = json!({ "atob": true, "amount_in": "1000", "amount_out": "3500" });
+        assert_eq!(
+            parse_swap_flow("0x1eab::pool::SwapEvent", &cetus, "cetus"),
+            Some(SwapFlow { a_to_b: true, amount_in: 1_000, amount_out: 3_500 })
+        );
+
+        // Turbos reports per-token amounts; direction picks which is the input
+        let turbos = json!({ "a_to_b": false, "amount_a": "700", "amount_b": "2000" });
+        assert_eq!(
+            parse_swap_flow("0x91bf::pool::SwapEvent", &turbos, "turbos"),
+            Some(SwapFlow { a_to_b: false, amount_in: 2_000, amount_out: 700 })
+        );
+
+        let flowx = json!({ "x_for_y": true, "amount_x": 10, "amount_y": 30 });
+        assert_eq!(
+            parse_swap_flow("0x25929::pool::Swap", &flowx, "flowx_clmm"),
+            Some(SwapFlow { a_to_b: true, amount_in: 10, amount_out: 30 })
+        );
+
+        let amm = json!({
+            "amount_x_in": "0", "amount_y_in": "90", "amount_x_out": "30", "amount_y_out": "0"
+        });
+        assert_eq!(
+            parse_swap_flow("0xba15::pair::Swapped", &amm, "flowx_amm"),
+            Some(SwapFlow { a_to_b: false, amount_in: 90, amount_out: 30 })
+        );
+
+        // A taker bid buys base (A) with quote (B)
+        let fill = json!({ "taker_is_bid": true, "base_quantity": "5", "quote_quantity": "17" });
+        assert_eq!(
+            parse_swap_flow("0x2c8d::order_info::OrderFilled", &fill, "deepbook"),
+            Some(SwapFlow { a_to_b: false, amount_in: 17, amount_out: 5 })
+        );
+    }
+
+    #[test]
+    fn test_swap_flow_unmapped_or_incomplete_events() {
+        let cetus = json!({ "atob": true, "amount_in": "1000", "amount_out": "3500" });
+        // Not a swap
+        assert!(parse_swap_flow("0x1eab::pool::AddLiquidityEvent", &cetus, "cetus").is_none());
+        // Missing direction
+        let partial = json!({ "amount_in": "1000", "amount_out": "3500" });
+        assert!(parse_swap_flow("0x1eab::pool::SwapEvent", &partial, "cetus").is_none());
+        // Aftermath swaps are multi-asset; no single A/B direction
+        assert!(parse_swap_flow("0xefe1::events::SwapEvent", &cetus, "aftermath").is_none());
+        // Both AMM sides in (or neither) has no direction
+        let amm = json!({ "amount_x_in": "0", "amount_y_in": "0", "amount_x_out": "0", "amount_y_out": "0" });
+        assert!(parse_swap_flow("0xba15::pair::Swapped", &amm, "flowx_amm").is_none());
+    }
+
+    #[test]
+    fn test_tick_from_sqrt_price() {
+        assert_eq!(tick_from_sqrt_price(1u128 << 64), 0);
+        // Mid-tick prices (1.0001^100.5 and 1.0001^-99.5), away from the f64 edge
+        let sqrt = (1.0001f64.powf(50.25) * 18_446_744_073_709_551_616.0) as u128;
+        assert_eq!(tick_from_sqrt_price(sqrt), 100);
+        let sqrt = (1.0001f64.powf(-49.75) * 18_446_744_073_709_551_616.0) as u128;
+        assert_eq!(tick_from_sqrt_price(sqrt), -100);
+    }
 }
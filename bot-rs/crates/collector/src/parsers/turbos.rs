@@ -2,7 +2,8 @@ use anyhow::{Context, Result};
 use arb_types::pool::{Dex, PoolState};
 use serde_json::Value;
 
-use super::{field_u128, field_u64, PoolMeta};
+use super::{event_i32, field_bool, field_u128, field_u64, PoolMeta};
+use crate::flow::SwapFlow;
 
 /// Parse a Turbos CLMM Pool object.
 ///
@@ -48,8 +49,35 @@ pub(crate) fn parse(content: &Value, meta: &PoolMeta, now_ms: u64) -> Result<Poo
         reserve_b: None,
         best_bid: None,
         best_ask: None,
+        book_depth: None,
         last_updated_ms: now_ms,
         // Fee type is set by the RPC poller after parsing (extracted from object type string)
         fee_type: None,
+        min_trade_size: None,
+        lot_size: None,
+        raw_balance_a: None,
+        raw_balance_b: None,
     })
 }
+
+/// Apply a Turbos `pool::SwapEvent` to the cached state. The event reports
+/// the complete post-swap `sqrt_price`, `liquidity`, and `tick_current_index`.
+pub(crate) fn apply_swap_event(parsed: &Value, prev: &PoolState, now_ms: u64) -> Option<PoolState> {
+    Some(PoolState {
+        sqrt_price: Some(field_u128(parsed, "sqrt_price").ok()?),
+        liquidity: Some(field_u128(parsed, "liquidity").ok()?),
+        tick_index: Some(event_i32(parsed, "tick_current_index")?),
+        last_updated_ms: now_ms,
+        ..prev.clone()
+    })
+}
+
+/// Amounts and direction of a Turbos `pool::SwapEvent`. The event reports
+/// `amount_a` / `amount_b` rather than in/out, so `a_to_b` picks the side.
+pub(crate) fn swap_flow(parsed: &Value) -> Option<SwapFlow> {
+    let a_to_b = field_bool(parsed, "a_to_b")?;
+    let amount_a = field_u64(parsed, "amount_a").ok()?;
+    let amount_b = field_u64(parsed, "amount_b").ok()?;
+    let (amount_in, amount_out) = if a_to_b { (amount_a, amount_b) } else { (amount_b, amount_a) };
+    Some(SwapFlow { a_to_b, amount_in, amount_out })
+}
@@ -1,24 +1,149 @@
 use arb_types::pool::PoolState;
+use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::future::Future;
 use std::sync::Arc;
+use tokio::sync::Notify;
+use tracing::error;
+
+use crate::flow::FlowTracker;
+use crate::subscriptions::PoolActivity;
 
 /// Thread-safe cache of pool states, keyed by pool object ID.
 /// Updated by the collector, read by the strategy scanner.
+///
+/// Pools can be quarantined (e.g. on repeated data-quality failures). A
+/// quarantined pool keeps receiving updates but is excluded from `snapshot()`,
+/// so the scanner never routes through it.
+///
+/// Updates that actually change a pool's state are recorded in its
+/// `activity`, which ranks pools for WebSocket subscriptions. Swaps seen
+/// on the event stream are recorded in `flow`.
+///
+/// A pool new to the cache is also left out of `snapshot()` until it has
+/// warmed up (see [`PoolWarmup`]); by default its first observation counts.
+///
+/// Pools are also indexed by token pair (see [`pair_key`]), kept up to date
+/// on every upsert and remove, so per-pair lookups don't scan the cache.
+///
+/// An upsert that adds a pool or changes its state wakes [`changed`](Self::changed),
+/// so a consumer can act on new data without waiting for its next tick.
 #[derive(Debug, Clone)]
 pub struct PoolCache {
     inner: Arc<DashMap<String, PoolState>>,
+    /// Pool IDs by [`pair_key`]
+    pairs: Arc<DashMap<(String, String), BTreeSet<String>>>,
+    quarantined: Arc<DashMap<String, String>>,
+    /// Per pool: (updates stored, `last_updated_ms` of the first one)
+    observations: Arc<DashMap<String, (u32, u64)>>,
+    warmup: PoolWarmup,
+    activity: PoolActivity,
+    flow: FlowTracker,
+    updated: Arc<Notify>,
+}
+
+/// How much history a pool needs before it is tradeable. Both limits must
+/// be met; they apply to every pool that enters the cache, whether seeded
+/// at startup or discovered mid-run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolWarmup {
+    /// Updates stored for the pool, the first one included. Re-polls of an
+    /// unchanged state count: each confirms the data.
+    pub min_updates: u32,
+    /// Span between the pool's first and latest update timestamps (ms).
+    pub min_age_ms: u64,
+}
+
+impl Default for PoolWarmup {
+    /// Tradeable from the first observation.
+    fn default() -> Self {
+        Self { min_updates: 1, min_age_ms: 0 }
+    }
 }
 
 impl PoolCache {
     pub fn new() -> Self {
         Self {
             inner: Arc::new(DashMap::new()),
+            pairs: Arc::new(DashMap::new()),
+            quarantined: Arc::new(DashMap::new()),
+            observations: Arc::new(DashMap::new()),
+            warmup: PoolWarmup::default(),
+            activity: PoolActivity::default(),
+            flow: FlowTracker::default(),
+            updated: Arc::new(Notify::new()),
         }
     }
 
+    /// Require `warmup` of every pool before `snapshot()` includes it.
+    pub fn with_warmup(mut self, warmup: PoolWarmup) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
     /// Insert or update a pool state.
-    pub fn upsert(&self, pool_id: String, state: PoolState) {
-        self.inner.insert(pool_id, state);
+    ///
+    /// A pool's DEX never changes: an update that would relabel an existing
+    /// entry (e.g. a duplicate config under another DEX) is logged and
+    /// dropped, keeping the original. Returns whether the state was stored.
+    ///
+    /// An update without order book data (an object re-fetch that didn't
+    /// read the book) keeps the cached book and best bid/ask.
+    pub fn upsert(&self, pool_id: String, mut state: PoolState) -> bool {
+        let updated_ms = state.last_updated_ms;
+        let new_pair = pair_key(&state.coin_type_a, &state.coin_type_b);
+        // The entry lock is released before the pair index is touched, so
+        // the two maps are never locked in opposite orders
+        let mut changed = true;
+        let old_pair = match self.inner.entry(pool_id.clone()) {
+            Entry::Occupied(mut e) => {
+                if e.get().dex != state.dex {
+                    error!(
+                        pool = %e.key(),
+                        cached_dex = %e.get().dex,
+                        update_dex = %state.dex,
+                        "Rejected pool update that would change its DEX — keeping original"
+                    );
+                    return false;
+                }
+                if state.book_depth.is_none() && e.get().book_depth.is_some() {
+                    state.best_bid = e.get().best_bid;
+                    state.best_ask = e.get().best_ask;
+                    state.book_depth = e.get().book_depth.clone();
+                }
+                changed = e.get().significant_change(&state, 0.0);
+                if changed {
+                    self.activity.record_update(e.key(), state.last_updated_ms);
+                }
+                let old = e.insert(state);
+                Some(pair_key(&old.coin_type_a, &old.coin_type_b))
+            }
+            Entry::Vacant(e) => {
+                e.insert(state);
+                None
+            }
+        };
+        if old_pair.as_ref() != Some(&new_pair) {
+            if let Some(old_pair) = old_pair {
+                self.unindex(&old_pair, &pool_id);
+            }
+            self.pairs.entry(new_pair).or_default().insert(pool_id.clone());
+        }
+        let mut seen = self.observations.entry(pool_id).or_insert((0, updated_ms));
+        seen.0 = seen.0.saturating_add(1);
+        drop(seen);
+        if changed {
+            self.updated.notify_one();
+        }
+        true
+    }
+
+    /// Resolves once a pool is added or its state changes. An update made
+    /// while nobody is waiting is kept, so the next call resolves at once;
+    /// any number of updates in between collapse into that one wakeup.
+    pub fn changed(&self) -> impl Future<Output = ()> + '_ {
+        self.updated.notified()
     }
 
     /// Get a snapshot of a specific pool's state.
@@ -26,9 +151,67 @@ impl PoolCache {
         self.inner.get(pool_id).map(|r| r.value().clone())
     }
 
-    /// Get a snapshot of all pool states.
+    /// Get a snapshot of all non-quarantined, warmed-up pool states.
     pub fn snapshot(&self) -> Vec<PoolState> {
-        self.inner.iter().map(|r| r.value().clone()).collect()
+        self.inner
+            .iter()
+            .filter(|r| !self.quarantined.contains_key(r.key()))
+            .filter(|r| self.is_warm(r.key(), r.value().last_updated_ms))
+            .map(|r| r.value().clone())
+            .collect()
+    }
+
+    /// Whether a pool has met the warmup requirement.
+    fn is_warm(&self, pool_id: &str, last_updated_ms: u64) -> bool {
+        if self.warmup == PoolWarmup::default() {
+            return true;
+        }
+        self.observations.get(pool_id).is_some_and(|seen| {
+            let (updates, first_ms) = *seen;
+            updates >= self.warmup.min_updates
+                && last_updated_ms.saturating_sub(first_ms) >= self.warmup.min_age_ms
+        })
+    }
+
+    /// Pools still warming up: cached, but not yet in `snapshot()`.
+    pub fn warming(&self) -> usize {
+        self.inner
+            .iter()
+            .filter(|r| !self.is_warm(r.key(), r.value().last_updated_ms))
+            .count()
+    }
+
+    /// Exclude a pool from `snapshot()` until released.
+    pub fn quarantine(&self, pool_id: &str, reason: &str) {
+        self.quarantined.insert(pool_id.to_string(), reason.to_string());
+    }
+
+    /// Return a quarantined pool to the tradable set.
+    pub fn release(&self, pool_id: &str) -> bool {
+        self.quarantined.remove(pool_id).is_some()
+    }
+
+    /// Recent per-pool activity (state changes, opportunity participation).
+    pub fn activity(&self) -> &PoolActivity {
+        &self.activity
+    }
+
+    /// Recent per-pool swap flow (direction, net amounts).
+    pub fn flow(&self) -> &FlowTracker {
+        &self.flow
+    }
+
+    /// Whether a pool is currently quarantined.
+    pub fn is_quarantined(&self, pool_id: &str) -> bool {
+        self.quarantined.contains_key(pool_id)
+    }
+
+    /// Quarantined pool IDs with their reasons.
+    pub fn quarantined(&self) -> Vec<(String, String)> {
+        self.quarantined
+            .iter()
+            .map(|r| (r.key().clone(), r.value().clone()))
+            .collect()
     }
 
     /// Number of pools in the cache.
@@ -41,25 +224,104 @@ impl PoolCache {
         self.inner.is_empty()
     }
 
+    /// Set a pool's `fee_type` in place, returning the previous one, or
+    /// `None` if the pool isn't cached. Unlike re-upserting an edited copy,
+    /// this can't roll back a price update that lands in between, and it
+    /// isn't counted as an observation of the pool.
+    pub fn set_fee_type(&self, pool_id: &str, fee_type: &str) -> Option<Option<String>> {
+        let mut state = self.inner.get_mut(pool_id)?;
+        Some(state.fee_type.replace(fee_type.to_string()))
+    }
+
     /// Remove a pool from the cache.
+    /// A pool that returns later warms up again.
     pub fn remove(&self, pool_id: &str) -> Option<PoolState> {
-        self.inner.remove(pool_id).map(|(_, v)| v)
+        self.observations.remove(pool_id);
+        let (_, state) = self.inner.remove(pool_id)?;
+        self.unindex(&pair_key(&state.coin_type_a, &state.coin_type_b), pool_id);
+        Some(state)
+    }
+
+    /// Remove every pool whose last update is more than `max_age_ms` before
+    /// `now_ms`, returning how many were removed. Like [`remove`](Self::remove),
+    /// an evicted pool that comes back warms up again.
+    ///
+    /// Safe to run alongside `upsert` and `snapshot`: the cache is filtered
+    /// one shard at a time, and a pool re-inserted by a concurrent update
+    /// before it is unindexed keeps its index entry.
+    pub fn evict_stale(&self, now_ms: u64, max_age_ms: u64) -> usize {
+        let mut evicted = Vec::new();
+        self.inner.retain(|id, state| {
+            let stale = state.staleness_ms(now_ms) > max_age_ms;
+            if stale {
+                evicted.push((id.clone(), pair_key(&state.coin_type_a, &state.coin_type_b)));
+            }
+            !stale
+        });
+        for (id, pair) in &evicted {
+            if self.inner.contains_key(id) {
+                continue;
+            }
+            self.observations.remove(id);
+            self.unindex(pair, id);
+        }
+        evicted.len()
+    }
+
+    /// Drop `pool_id` from its pair's bucket, and the bucket once empty.
+    fn unindex(&self, pair: &(String, String), pool_id: &str) {
+        if let Some(mut ids) = self.pairs.get_mut(pair) {
+            ids.remove(pool_id);
+        }
+        self.pairs.remove_if(pair, |_, ids| ids.is_empty());
     }
 
     /// Get all pools for a specific token pair (in either order).
     pub fn pools_for_pair(&self, coin_a: &str, coin_b: &str) -> Vec<PoolState> {
-        self.inner
+        let ids: Vec<String> = match self.pairs.get(&pair_key(coin_a, coin_b)) {
+            Some(ids) => ids.iter().cloned().collect(),
+            None => return Vec::new(),
+        };
+        ids.iter().filter_map(|id| self.get(id)).collect()
+    }
+
+    /// Pool IDs by token pair, every cached pool included.
+    pub fn pair_index(&self) -> HashMap<(String, String), Vec<String>> {
+        self.pairs
             .iter()
-            .filter(|r| {
-                let p = r.value();
-                (p.coin_type_a == coin_a && p.coin_type_b == coin_b)
-                    || (p.coin_type_a == coin_b && p.coin_type_b == coin_a)
+            .map(|r| (r.key().clone(), r.value().iter().cloned().collect()))
+            .collect()
+    }
+
+    /// [`snapshot`](Self::snapshot) grouped by [`pair_key`]. Pairs with no
+    /// tradeable pool are left out.
+    pub fn snapshot_grouped(&self) -> HashMap<(String, String), Vec<PoolState>> {
+        self.pair_index()
+            .into_iter()
+            .map(|(pair, ids)| {
+                let pools: Vec<PoolState> = ids
+                    .iter()
+                    .filter(|id| !self.quarantined.contains_key(*id))
+                    .filter_map(|id| self.get(id))
+                    .filter(|p| self.is_warm(&p.object_id, p.last_updated_ms))
+                    .collect();
+                (pair, pools)
             })
-            .map(|r| r.value().clone())
+            .filter(|(_, pools)| !pools.is_empty())
             .collect()
     }
 }
 
+/// A token pair in canonical (sorted) order, so both orderings of a pool's
+/// coins map to the same key.
+pub fn pair_key(coin_a: &str, coin_b: &str) -> (String, String) {
+    if coin_a <= coin_b {
+        (coin_a.to_string(), coin_b.to_string())
+    } else {
+        (coin_b.to_string(), coin_a.to_string())
+    }
+}
+
 impl Default for PoolCache {
     fn default() -> Self {
         Self::new()
@@ -85,8 +347,13 @@ mod tests {
             reserve_b: None,
             best_bid: None,
             best_ask: None,
+            book_depth: None,
             last_updated_ms: 0,
             fee_type: None,
+            min_trade_size: None,
+            lot_size: None,
+            raw_balance_a: None,
+            raw_balance_b: None,
         }
     }
 
@@ -100,6 +367,79 @@ mod tests {
         assert_eq!(got.object_id, "0xabc");
     }
 
+    #[test]
+    fn test_set_fee_type_changes_only_the_fee_type() {
+        let cache = PoolCache::new().with_warmup(PoolWarmup { min_updates: 2, min_age_ms: 0 });
+        assert_eq!(cache.set_fee_type("0xabc", "0x91::fee::FEE"), None);
+
+        let mut pool = make_pool("0xabc", Dex::Turbos, "SUI", "USDC");
+        pool.fee_type = Some("0x91::fee500bps::FEE500BPS".to_string());
+        cache.upsert("0xabc".to_string(), pool.clone());
+        // A newer price lands after the verifier read the pool
+        pool.sqrt_price = Some(2u128 << 64);
+        pool.last_updated_ms = 500;
+        cache.upsert("0xabc".to_string(), pool);
+
+        let previous = cache.set_fee_type("0xabc", "0x91::fee3000bps::FEE3000BPS");
+        assert_eq!(previous, Some(Some("0x91::fee500bps::FEE500BPS".to_string())));
+        let got = cache.get("0xabc").unwrap();
+        assert_eq!(got.fee_type.as_deref(), Some("0x91::fee3000bps::FEE3000BPS"));
+        assert_eq!((got.sqrt_price, got.last_updated_ms), (Some(2u128 << 64), 500));
+        assert_eq!(cache.observations.get("0xabc").map(|seen| seen.0), Some(2));
+    }
+
+    #[test]
+    fn test_upsert_rejects_dex_change() {
+        let cache = PoolCache::new();
+        assert!(cache.upsert("0x1".to_string(), make_pool("0x1", Dex::Cetus, "SUI", "USDC")));
+
+        let mut update = make_pool("0x1", Dex::Cetus, "SUI", "USDC");
+        update.liquidity = Some(2_000_000);
+        assert!(cache.upsert("0x1".to_string(), update));
+
+        let relabeled = make_pool("0x1", Dex::FlowxClmm, "SUI", "USDC");
+        assert!(!cache.upsert("0x1".to_string(), relabeled));
+        let got = cache.get("0x1").unwrap();
+        assert_eq!(got.dex, Dex::Cetus);
+        assert_eq!(got.liquidity, Some(2_000_000));
+    }
+
+    #[test]
+    fn test_update_without_book_keeps_cached_book() {
+        let cache = PoolCache::new();
+        let mut pool = make_pool("0x1", Dex::DeepBook, "SUI", "USDC");
+        pool.best_bid = Some(3.5);
+        pool.book_depth = Some(Default::default());
+        cache.upsert("0x1".to_string(), pool);
+
+        // Object re-fetch: new vault state, no book read
+        let mut refetch = make_pool("0x1", Dex::DeepBook, "SUI", "USDC");
+        refetch.reserve_a = Some(42);
+        cache.upsert("0x1".to_string(), refetch);
+        let got = cache.get("0x1").unwrap();
+        assert_eq!((got.reserve_a, got.best_bid), (Some(42), Some(3.5)));
+
+        // A fetched (empty) book replaces it
+        let mut emptied = make_pool("0x1", Dex::DeepBook, "SUI", "USDC");
+        emptied.book_depth = Some(Default::default());
+        cache.upsert("0x1".to_string(), emptied);
+        assert_eq!(cache.get("0x1").unwrap().best_bid, None);
+    }
+
+    #[test]
+    fn test_only_state_changes_count_as_activity() {
+        let cache = PoolCache::new();
+        cache.upsert("0x1".to_string(), make_pool("0x1", Dex::Cetus, "SUI", "USDC"));
+        // Re-polling an unchanged pool is not activity
+        cache.upsert("0x1".to_string(), make_pool("0x1", Dex::Cetus, "SUI", "USDC"));
+        assert_eq!(cache.activity().score("0x1", 0), 0);
+
+        let mut swapped = make_pool("0x1", Dex::Cetus, "SUI", "USDC");
+        swapped.sqrt_price = Some(2u128 << 64);
+        cache.upsert("0x1".to_string(), swapped);
+        assert_eq!(cache.activity().score("0x1", 0), 1);
+    }
+
     #[test]
     fn test_pools_for_pair() {
         let cache = PoolCache::new();
@@ -123,4 +463,183 @@ mod tests {
         let pairs_rev = cache.pools_for_pair("USDC", "SUI");
         assert_eq!(pairs_rev.len(), 2);
     }
+
+    #[test]
+    fn test_pair_index_follows_upserts_and_removes() {
+        let cache = PoolCache::new();
+        cache.upsert("0x1".to_string(), make_pool("0x1", Dex::Cetus, "SUI", "USDC"));
+        // Listed the other way round: same bucket
+        cache.upsert("0x2".to_string(), make_pool("0x2", Dex::Turbos, "USDC", "SUI"));
+        cache.upsert("0x3".to_string(), make_pool("0x3", Dex::Cetus, "SUI", "WETH"));
+
+        let index = cache.pair_index();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index[&pair_key("SUI", "USDC")], ["0x1", "0x2"]);
+
+        // Coin ordering flipped by an update: still one entry in one bucket
+        cache.upsert("0x1".to_string(), make_pool("0x1", Dex::Cetus, "USDC", "SUI"));
+        assert_eq!(cache.pools_for_pair("SUI", "USDC").len(), 2);
+
+        // A pool whose coins change moves buckets
+        cache.upsert("0x3".to_string(), make_pool("0x3", Dex::Cetus, "SUI", "DEEP"));
+        assert!(cache.pools_for_pair("SUI", "WETH").is_empty());
+        assert_eq!(cache.pools_for_pair("DEEP", "SUI").len(), 1);
+
+        // Empty buckets are dropped
+        cache.remove("0x3");
+        assert_eq!(cache.pair_index().len(), 1);
+        cache.remove("0x1");
+        assert_eq!(cache.pair_index()[&pair_key("USDC", "SUI")], ["0x2"]);
+    }
+
+    #[tokio::test]
+    async fn test_upsert_wakes_changed() {
+        let cache = PoolCache::new();
+        let waiter = {
+            let cache = cache.clone();
+            tokio::spawn(async move { cache.changed().await })
+        };
+        tokio::task::yield_now().await;
+        cache.upsert("0x1".to_string(), make_pool("0x1", Dex::Cetus, "SUI", "USDC"));
+        tokio::time::timeout(std::time::Duration::from_millis(500), waiter)
+            .await
+            .expect("upsert did not wake the waiter")
+            .unwrap();
+
+        // An update with nobody waiting is kept for the next wait
+        let mut moved = make_pool("0x1", Dex::Cetus, "SUI", "USDC");
+        moved.sqrt_price = Some(2u128 << 64);
+        cache.upsert("0x1".to_string(), moved.clone());
+        tokio::time::timeout(std::time::Duration::from_millis(500), cache.changed())
+            .await
+            .expect("pending update was lost");
+
+        // Re-storing an unchanged state wakes nobody
+        cache.upsert("0x1".to_string(), moved);
+        assert!(tokio::time::timeout(std::time::Duration::from_millis(50), cache.changed())
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_evict_stale_removes_only_old_pools() {
+        let cache = PoolCache::new().with_warmup(PoolWarmup { min_updates: 2, min_age_ms: 0 });
+        let at = |id: &str, coin_b: &str, ms: u64| PoolState {
+            last_updated_ms: ms,
+            ..make_pool(id, Dex::Cetus, "SUI", coin_b)
+        };
+        cache.upsert("0x1".to_string(), at("0x1", "USDC", 1_000));
+        cache.upsert("0x2".to_string(), at("0x2", "USDC", 8_000));
+        cache.upsert("0x2".to_string(), at("0x2", "USDC", 9_000));
+        cache.upsert("0x3".to_string(), at("0x3", "WETH", 2_000));
+
+        assert_eq!(cache.evict_stale(10_000, 5_000), 2);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get("0x2").is_some());
+        assert_eq!(cache.pair_index().len(), 1);
+        assert_eq!(cache.pools_for_pair("SUI", "USDC").len(), 1);
+        // Exactly max_age old is kept
+        assert_eq!(cache.evict_stale(14_000, 5_000), 0);
+
+        // An evicted pool that returns warms up from scratch
+        cache.upsert("0x1".to_string(), at("0x1", "USDC", 14_000));
+        assert_eq!(cache.warming(), 1);
+    }
+
+    #[test]
+    fn test_evict_stale_alongside_upserts() {
+        let cache = PoolCache::new();
+        for i in 0..100 {
+            let id = format!("0x{i}");
+            cache.upsert(id.clone(), make_pool(&id, Dex::Cetus, "SUI", "USDC"));
+        }
+        let writer = {
+            let cache = cache.clone();
+            std::thread::spawn(move || {
+                for i in 0..100 {
+                    let id = format!("0x{i}");
+                    let fresh = PoolState { last_updated_ms: 100_000, ..make_pool(&id, Dex::Cetus, "SUI", "USDC") };
+                    cache.upsert(id, fresh);
+                    cache.snapshot();
+                }
+            })
+        };
+        let mut evicted = 0;
+        for _ in 0..10 {
+            evicted += cache.evict_stale(100_000, 50_000);
+        }
+        writer.join().unwrap();
+        evicted += cache.evict_stale(100_000, 50_000);
+
+        // Every pool is either cached and indexed, or evicted and unindexed
+        let indexed = cache.pools_for_pair("SUI", "USDC").len();
+        assert_eq!(indexed, cache.len());
+        assert!(evicted + cache.len() >= 100);
+    }
+
+    #[test]
+    fn test_snapshot_grouped_matches_snapshot() {
+        let cache = PoolCache::new();
+        cache.upsert("0x1".to_string(), make_pool("0x1", Dex::Cetus, "SUI", "USDC"));
+        cache.upsert("0x2".to_string(), make_pool("0x2", Dex::Turbos, "SUI", "USDC"));
+        cache.upsert("0x3".to_string(), make_pool("0x3", Dex::Cetus, "SUI", "WETH"));
+        cache.quarantine("0x3", "test");
+
+        let grouped = cache.snapshot_grouped();
+        assert_eq!(grouped.len(), 1, "a pair with only quarantined pools is left out");
+        let ids: Vec<_> = grouped[&pair_key("SUI", "USDC")].iter().map(|p| p.object_id.as_str()).collect();
+        assert_eq!(ids, ["0x1", "0x2"]);
+        assert_eq!(grouped.values().map(Vec::len).sum::<usize>(), cache.snapshot().len());
+    }
+
+    #[test]
+    fn test_quarantine_excluded_from_snapshot() {
+        let cache = PoolCache::new();
+        cache.upsert("0x1".to_string(), make_pool("0x1", Dex::Cetus, "SUI", "USDC"));
+        cache.upsert("0x2".to_string(), make_pool("0x2", Dex::Turbos, "SUI", "USDC"));
+
+        cache.quarantine("0x2", "fee type mismatch");
+        assert!(cache.is_quarantined("0x2"));
+        assert_eq!(cache.snapshot().len(), 1);
+        // Still retrievable directly and still counted
+        assert!(cache.get("0x2").is_some());
+        assert_eq!(cache.len(), 2);
+
+        assert!(cache.release("0x2"));
+        assert_eq!(cache.snapshot().len(), 2);
+    }
+
+    #[test]
+    fn test_warmup_requires_updates_and_age() {
+        let cache = PoolCache::new().with_warmup(PoolWarmup { min_updates: 3, min_age_ms: 1_000 });
+        let mut pool = make_pool("0x1", Dex::Cetus, "SUI", "USDC");
+        pool.last_updated_ms = 10_000;
+        cache.upsert("0x1".to_string(), pool.clone());
+        assert!(cache.snapshot().is_empty());
+        assert_eq!(cache.warming(), 1);
+
+        pool.last_updated_ms = 10_500;
+        cache.upsert("0x1".to_string(), pool.clone());
+        pool.last_updated_ms = 10_800;
+        cache.upsert("0x1".to_string(), pool.clone());
+        assert!(cache.snapshot().is_empty(), "3 updates but only 800ms of history");
+
+        pool.last_updated_ms = 11_000;
+        cache.upsert("0x1".to_string(), pool.clone());
+        assert_eq!(cache.snapshot().len(), 1);
+        assert_eq!(cache.warming(), 0);
+
+        // A pool that leaves and comes back starts over
+        cache.remove("0x1");
+        cache.upsert("0x1".to_string(), pool);
+        assert!(cache.snapshot().is_empty());
+    }
+
+    #[test]
+    fn test_default_warmup_trades_first_observation() {
+        let cache = PoolCache::new();
+        cache.upsert("0x1".to_string(), make_pool("0x1", Dex::Cetus, "SUI", "USDC"));
+        assert_eq!(cache.snapshot().len(), 1);
+        assert_eq!(cache.warming(), 0);
+    }
 }
@@ -0,0 +1,158 @@
+use arb_types::config::{Config, PoolConfig};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+use crate::rpc_poller::{object_key, PoolMeta};
+
+/// The monitored pool list, shared by the poller and the WebSocket streams
+/// so a reload reaches all of them. Clones share the list.
+///
+/// Readers take a [`snapshot`](Self::snapshot) per cycle; long-lived tasks
+/// can also [`watch`](Self::watch) for changes.
+#[derive(Debug, Clone)]
+pub struct PoolSet {
+    tx: Arc<watch::Sender<Arc<Vec<PoolMeta>>>>,
+}
+
+/// Pools added and removed by [`PoolSet::replace`].
+#[derive(Debug, Default)]
+pub struct PoolSetDiff {
+    pub added: Vec<PoolMeta>,
+    pub removed: Vec<PoolMeta>,
+}
+
+impl PoolSetDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl PoolSet {
+    pub fn new(pools: Vec<PoolMeta>) -> Self {
+        Self { tx: Arc::new(watch::Sender::new(Arc::new(pools))) }
+    }
+
+    /// The pools in `config.monitored_pools`.
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.monitored_pools.iter().map(PoolMeta::from).collect())
+    }
+
+    /// The current list.
+    pub fn snapshot(&self) -> Arc<Vec<PoolMeta>> {
+        self.tx.borrow().clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tx.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The monitored pool with `object_id` (compared as an object key).
+    pub fn find(&self, object_id: &str) -> Option<PoolMeta> {
+        let key = object_key(object_id);
+        self.tx.borrow().iter().find(|m| object_key(&m.object_id) == key).cloned()
+    }
+
+    /// A receiver that's notified on every change.
+    pub fn watch(&self) -> watch::Receiver<Arc<Vec<PoolMeta>>> {
+        self.tx.subscribe()
+    }
+
+    /// What [`replace`](Self::replace) with `pools` would change. A pool
+    /// whose ID is kept but whose metadata changed counts as removed and
+    /// re-added.
+    pub fn diff(&self, pools: &[PoolMeta]) -> PoolSetDiff {
+        let current = self.snapshot();
+        let old: HashSet<(String, &str, &str, &str)> = current.iter().map(identity).collect();
+        let new: HashSet<(String, &str, &str, &str)> = pools.iter().map(identity).collect();
+        PoolSetDiff {
+            added: pools.iter().filter(|m| !old.contains(&identity(m))).cloned().collect(),
+            removed: current.iter().filter(|m| !new.contains(&identity(m))).cloned().collect(),
+        }
+    }
+
+    /// Swap in `pools` and return what changed. Subscribers are only
+    /// notified when something did.
+    pub fn replace(&self, pools: Vec<PoolMeta>) -> PoolSetDiff {
+        let diff = self.diff(&pools);
+        if !diff.is_empty() {
+            self.tx.send_replace(Arc::new(pools));
+        }
+        diff
+    }
+}
+
+/// What makes two entries the same pool.
+fn identity(meta: &PoolMeta) -> (String, &str, &str, &str) {
+    (object_key(&meta.object_id), &meta.dex, &meta.coin_type_a, &meta.coin_type_b)
+}
+
+impl From<&PoolConfig> for PoolMeta {
+    fn from(p: &PoolConfig) -> Self {
+        PoolMeta {
+            object_id: p.pool_id.clone(),
+            dex: p.dex.clone(),
+            coin_type_a: p.coin_type_a.clone(),
+            coin_type_b: p.coin_type_b.clone(),
+        }
+    }
+}
+
+impl From<&PoolMeta> for PoolConfig {
+    fn from(m: &PoolMeta) -> Self {
+        PoolConfig {
+            dex: m.dex.clone(),
+            pool_id: m.object_id.clone(),
+            coin_type_a: m.coin_type_a.clone(),
+            coin_type_b: m.coin_type_b.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(id: &str, dex: &str) -> PoolMeta {
+        PoolMeta {
+            object_id: id.to_string(),
+            dex: dex.to_string(),
+            coin_type_a: "0x2::sui::SUI".to_string(),
+            coin_type_b: "0xa::usdc::USDC".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_replace_diffs_and_notifies() {
+        let set = PoolSet::new(vec![meta("0x1", "cetus"), meta("0x2", "turbos")]);
+        let mut rx = set.watch();
+
+        // Same pools (one ID spelled with leading zeros): no change
+        let diff = set.replace(vec![meta("0x01", "cetus"), meta("0x2", "turbos")]);
+        assert!(diff.is_empty());
+        assert!(!rx.has_changed().unwrap());
+
+        let diff = set.replace(vec![meta("0x1", "cetus"), meta("0x3", "deepbook")]);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].object_id, "0x3");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].object_id, "0x2");
+        assert!(rx.has_changed().unwrap());
+        assert_eq!(rx.borrow_and_update().len(), 2);
+
+        assert_eq!(set.find("0x03").unwrap().dex, "deepbook");
+        assert!(set.find("0x2").is_none());
+    }
+
+    #[test]
+    fn test_relabelled_pool_is_removed_and_readded() {
+        let set = PoolSet::new(vec![meta("0x1", "cetus")]);
+        let diff = set.replace(vec![meta("0x1", "turbos")]);
+        assert_eq!(diff.added[0].dex, "turbos");
+        assert_eq!(diff.removed[0].dex, "cetus");
+    }
+}
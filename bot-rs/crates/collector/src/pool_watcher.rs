@@ -0,0 +1,156 @@
+use anyhow::Result;
+use arb_types::config::{load_pools_file, Config};
+use arb_types::rpc::RpcClient;
+use arb_types::DecimalRegistry;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::time;
+use tracing::{error, info, warn};
+
+use crate::pool_cache::PoolCache;
+use crate::pool_set::{PoolSet, PoolSetDiff};
+use crate::rpc_poller::{self, object_key, PoolMeta};
+
+/// Default interval between pools file checks.
+pub const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Per-request timeout for seeding added pools.
+const SEED_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Re-reads the pools file (`POOLS_FILE`) whenever its modification time
+/// changes, so pools can be added and dropped without a restart.
+///
+/// Added pools are seeded into the cache before the poller and streams see
+/// them; removed ones are dropped from the cache once they no longer do. A
+/// file that fails to read or parse is logged and the current pools stay in
+/// effect.
+pub struct PoolWatcher {
+    path: PathBuf,
+    interval: Duration,
+    pools: PoolSet,
+    rpc: RpcClient,
+    chunk_size: usize,
+    /// Loads decimals for coin types the new pools bring in (None = skip)
+    decimals: Option<DecimalRegistry>,
+}
+
+impl PoolWatcher {
+    /// Watch `config.pools_file` and apply changes to `pools`.
+    pub fn new(config: &Config, pools: PoolSet) -> Self {
+        Self {
+            path: PathBuf::from(&config.pools_file),
+            interval: DEFAULT_RELOAD_INTERVAL,
+            pools,
+            rpc: RpcClient::from_config(config).with_timeout(SEED_TIMEOUT),
+            chunk_size: config.multi_get_chunk_size,
+            decimals: None,
+        }
+    }
+
+    /// Seed pools through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(SEED_TIMEOUT);
+        self
+    }
+
+    /// Check the file every `interval`.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Fetch on-chain decimals for coin types first seen in added pools.
+    pub fn with_decimals(mut self, decimals: DecimalRegistry) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Poll the file's modification time and reload on change. The file as
+    /// it is at startup is taken to be the loaded config. Runs forever.
+    pub async fn run(&self, cache: PoolCache) -> Result<()> {
+        info!(
+            path = %self.path.display(),
+            interval_ms = %self.interval.as_millis(),
+            "Watching pools file"
+        );
+        let mut last_modified = modified(&self.path);
+        let mut interval = time::interval(self.interval);
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            let current = modified(&self.path);
+            if current == last_modified {
+                continue;
+            }
+            last_modified = current;
+            if let Err(e) = self.reload(&cache).await {
+                error!(
+                    path = %self.path.display(),
+                    error = %format!("{:#}", e),
+                    "Pools file reload failed — keeping current pools"
+                );
+            }
+        }
+    }
+
+    /// Read the file and apply it: seed added pools, publish the new list,
+    /// then drop removed pools from the cache. A pool kept under changed
+    /// metadata is dropped before it is re-seeded.
+    pub async fn reload(&self, cache: &PoolCache) -> Result<PoolSetDiff> {
+        let metas: Vec<PoolMeta> = load_pools_file(&self.path)?.iter().map(PoolMeta::from).collect();
+        let diff = self.pools.diff(&metas);
+        if diff.is_empty() {
+            return Ok(diff);
+        }
+
+        let re_added: HashSet<String> = diff.added.iter().map(|m| object_key(&m.object_id)).collect();
+        let (changed, removed): (Vec<&PoolMeta>, Vec<&PoolMeta>) =
+            diff.removed.iter().partition(|m| re_added.contains(&object_key(&m.object_id)));
+        for meta in changed {
+            cache.remove(&meta.object_id);
+        }
+        if !diff.added.is_empty() {
+            if let Some(decimals) = &self.decimals {
+                let coin_types: Vec<String> = diff
+                    .added
+                    .iter()
+                    .flat_map(|m| [m.coin_type_a.clone(), m.coin_type_b.clone()])
+                    .collect();
+                decimals.seed(&self.rpc, &coin_types).await;
+            }
+            // The poller fetches every listed pool anyway, so a failed seed
+            // only delays the new pools by a cycle
+            match rpc_poller::seed_pools(&self.rpc, &diff.added, self.chunk_size, cache).await {
+                Ok(seeded) if seeded < diff.added.len() => warn!(
+                    added = %diff.added.len(),
+                    seeded = %seeded,
+                    "Some added pools could not be seeded — the poller keeps retrying them"
+                ),
+                Ok(_) => {}
+                Err(e) => warn!(error = %format!("{:#}", e), "Seeding added pools failed — left to the poller"),
+            }
+        }
+
+        // Readers still on the old list may look removed pools up until now
+        self.pools.replace(metas);
+        for meta in removed {
+            cache.remove(&meta.object_id);
+            info!(pool = %meta.object_id, dex = %meta.dex, "Removed pool");
+        }
+        info!(
+            added = %diff.added.len(),
+            removed = %diff.removed.len(),
+            pools = %self.pools.len(),
+            "Pools file reloaded"
+        );
+        Ok(diff)
+    }
+}
+
+/// The file's modification time, or None if it can't be read (a missing
+/// file, or one mid-replace).
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -1,23 +1,217 @@
 use anyhow::{Context, Result};
-use arb_types::config::Config;
-use reqwest::Client;
+use arb_types::clock::unix_now_ms;
+use arb_types::config::{dedup_pools, normalize_coin_type, Config, DEFAULT_MULTI_GET_CHUNK_SIZE};
+use arb_types::log_throttle::DEFAULT_INTERVAL;
+use arb_types::pool::PoolState;
+use arb_types::rpc::{RpcClient, RpcError};
+use arb_types::warn_throttled;
+use futures_util::{stream, StreamExt};
 use serde_json::{json, Value};
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::time;
 use tracing::{debug, error, info, warn};
 
+use crate::deepbook_book::{self, BookFetcher};
 use crate::parsers;
 use crate::pool_cache::PoolCache;
+use crate::pool_set::PoolSet;
+
+/// Per-request timeout for poll cycles.
+const POLL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest interval rate-limit backoff stretches polling to.
+pub const MAX_RATE_LIMIT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Successful cycles in a row that halve a backed-off interval.
+pub const RECOVERY_SUCCESSES: u32 = 3;
+
+/// Poll cycles over which [`PollPacing`] measures how many pools changed.
+pub const PACING_WINDOW: u32 = 5;
+
+/// Share of polled pools changing over a window that shortens the interval.
+pub const BUSY_CHANGE_RATIO: f64 = 0.25;
+
+/// Share of polled pools changing over a window, at or below which the
+/// interval lengthens.
+pub const QUIET_CHANGE_RATIO: f64 = 0.05;
 
 /// Polls Sui RPC for pool object state at a configurable interval.
 /// Parses the response into PoolState and updates the shared cache.
 pub struct RpcPoller {
-    client: Client,
-    rpc_url: String,
-    poll_interval: Duration,
-    pool_ids: Vec<PoolMeta>,
+    rpc: RpcClient,
+    chunk_size: usize,
+    pools: PoolSet,
+    /// Reads DeepBook order books after each pool fetch (None = vault state only)
+    book: Option<BookFetcher>,
+    backoff: PollBackoff,
+    pacing: PollPacing,
+}
+
+/// Rate-limit backoff of an [`RpcPoller`]. Clones share state, so the
+/// strategy loop and metrics can watch the poller's.
+///
+/// Each cycle the node rate-limits doubles the poll interval, up to
+/// [`MAX_RATE_LIMIT_INTERVAL`]; every [`RECOVERY_SUCCESSES`] successful
+/// cycles in a row halve it again. Other errors leave it alone.
+#[derive(Debug, Clone, Default)]
+pub struct PollBackoff {
+    state: Arc<BackoffState>,
+}
+
+#[derive(Debug, Default)]
+struct BackoffState {
+    /// Doublings of the poll interval in force (0 = not backing off)
+    level: AtomicU32,
+    successes: AtomicU32,
+    rate_limited: AtomicU64,
+}
+
+impl PollBackoff {
+    /// Factor the poll interval is currently stretched by (1 = none).
+    pub fn multiplier(&self) -> u32 {
+        1 << self.state.level.load(Ordering::Relaxed)
+    }
+
+    pub fn is_backing_off(&self) -> bool {
+        self.state.level.load(Ordering::Relaxed) > 0
+    }
+
+    /// Rate-limited cycles since startup.
+    pub fn rate_limited_total(&self) -> u64 {
+        self.state.rate_limited.load(Ordering::Relaxed)
+    }
+
+    /// The interval to poll at, given the configured `base`.
+    pub fn interval(&self, base: Duration) -> Duration {
+        (base * self.multiplier()).min(MAX_RATE_LIMIT_INTERVAL.max(base))
+    }
+
+    /// A cycle was rate-limited: double the interval unless it's at the cap.
+    pub fn rate_limited(&self, base: Duration) {
+        self.state.rate_limited.fetch_add(1, Ordering::Relaxed);
+        self.state.successes.store(0, Ordering::Relaxed);
+        if self.interval(base) < MAX_RATE_LIMIT_INTERVAL {
+            self.state.level.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// A cycle succeeded: halve the interval after enough in a row.
+    pub fn succeeded(&self) {
+        if !self.is_backing_off() {
+            return;
+        }
+        if self.state.successes.fetch_add(1, Ordering::Relaxed) + 1 >= RECOVERY_SUCCESSES {
+            self.state.successes.store(0, Ordering::Relaxed);
+            self.state.level.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Volatility-paced interval of an [`RpcPoller`]. Clones share state, so
+/// the strategy loop and metrics can watch the poller's.
+///
+/// Every [`PACING_WINDOW`] cycles, the share of polled pools whose parsed
+/// state changed sets the next interval: at least [`BUSY_CHANGE_RATIO`]
+/// shortens it by a quarter, down to the floor; at most
+/// [`QUIET_CHANGE_RATIO`] lengthens it by a quarter, up to the ceiling.
+/// Rate-limit backoff stretches whatever interval this picks.
+#[derive(Debug, Clone)]
+pub struct PollPacing {
+    state: Arc<PacingState>,
+}
+
+#[derive(Debug)]
+struct PacingState {
+    floor_ms: u64,
+    ceiling_ms: u64,
+    current_ms: AtomicU64,
+    window: Mutex<PacingWindow>,
+}
+
+#[derive(Debug, Default)]
+struct PacingWindow {
+    cycles: u32,
+    polled: usize,
+    changed: usize,
+}
+
+impl PollPacing {
+    /// Start at `base`, adapting between `floor` and `ceiling`.
+    pub fn new(base: Duration, floor: Duration, ceiling: Duration) -> Self {
+        let floor_ms = (floor.as_millis() as u64).max(1);
+        let ceiling_ms = (ceiling.as_millis() as u64).max(floor_ms);
+        let current_ms = (base.as_millis() as u64).clamp(floor_ms, ceiling_ms);
+        Self {
+            state: Arc::new(PacingState {
+                floor_ms,
+                ceiling_ms,
+                current_ms: AtomicU64::new(current_ms),
+                window: Mutex::new(PacingWindow::default()),
+            }),
+        }
+    }
+
+    /// Always `interval`.
+    pub fn fixed(interval: Duration) -> Self {
+        Self::new(interval, interval, interval)
+    }
+
+    pub fn is_adaptive(&self) -> bool {
+        self.state.floor_ms < self.state.ceiling_ms
+    }
+
+    pub fn current_interval_ms(&self) -> u64 {
+        self.state.current_ms.load(Ordering::Relaxed)
+    }
+
+    pub fn current_interval(&self) -> Duration {
+        Duration::from_millis(self.current_interval_ms())
+    }
+
+    /// A cycle polled `polled` pools, of which `changed` had a new state.
+    /// Re-paces at the end of each window.
+    pub fn record_cycle(&self, polled: usize, changed: usize) {
+        let (polled, changed) = {
+            let mut window = self.state.window.lock().unwrap_or_else(|e| e.into_inner());
+            window.cycles += 1;
+            window.polled += polled;
+            window.changed += changed;
+            if window.cycles < PACING_WINDOW {
+                return;
+            }
+            let done = std::mem::take(&mut *window);
+            (done.polled, done.changed)
+        };
+        if polled == 0 {
+            return;
+        }
+        let ratio = changed as f64 / polled as f64;
+        let current = self.current_interval_ms();
+        let next = if ratio >= BUSY_CHANGE_RATIO {
+            (current * 3 / 4).max(self.state.floor_ms)
+        } else if ratio <= QUIET_CHANGE_RATIO {
+            (current * 5 / 4).max(current + 1).min(self.state.ceiling_ms)
+        } else {
+            current
+        };
+        if next != current {
+            debug!(ratio = %format!("{:.2}", ratio), interval_ms = %next, "Poll interval re-paced");
+            self.state.current_ms.store(next, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Whether `e` is the node throttling us: HTTP 429, or a JSON-RPC error
+/// saying so.
+pub fn is_rate_limited(e: &anyhow::Error) -> bool {
+    if e.chain().any(|c| c.downcast_ref::<RpcError>().is_some_and(RpcError::is_rate_limited)) {
+        return true;
+    }
+    let message = format!("{:#}", e).to_lowercase();
+    message.contains("too many requests") || message.contains("rate limit")
 }
 
 /// Metadata for a pool to poll.
@@ -31,102 +225,142 @@ pub struct PoolMeta {
 
 impl RpcPoller {
     pub fn new(config: &Config) -> Self {
-        let pool_ids: Vec<PoolMeta> = config
-            .monitored_pools
-            .iter()
-            .map(|p| PoolMeta {
-                object_id: p.pool_id.clone(),
-                dex: p.dex.clone(),
-                coin_type_a: p.coin_type_a.clone(),
-                coin_type_b: p.coin_type_b.clone(),
-            })
-            .collect();
+        Self {
+            rpc: RpcClient::from_config(config).with_timeout(POLL_TIMEOUT),
+            chunk_size: config.multi_get_chunk_size,
+            pools: PoolSet::from_config(config),
+            book: None,
+            backoff: PollBackoff::default(),
+            pacing: PollPacing::fixed(Duration::from_millis(config.poll_interval_ms)),
+        }
+    }
 
+    /// Poll an explicit pool list every `poll_interval`, without a full
+    /// `Config` (WS fallback, reloads, tests).
+    pub fn for_pools(rpc_url: &str, poll_interval: Duration, pools: Vec<PoolMeta>) -> Self {
         Self {
-            client: Client::builder()
-                .timeout(Duration::from_secs(5))
-                .build()
-                .expect("Failed to create HTTP client"),
-            rpc_url: config.rpc_url.clone(),
-            poll_interval: Duration::from_millis(config.poll_interval_ms),
-            pool_ids,
+            rpc: RpcClient::single(rpc_url).with_timeout(POLL_TIMEOUT),
+            chunk_size: DEFAULT_MULTI_GET_CHUNK_SIZE,
+            pools: PoolSet::new(pools),
+            book: None,
+            backoff: PollBackoff::default(),
+            pacing: PollPacing::fixed(poll_interval),
         }
     }
 
+    /// Poll through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(POLL_TIMEOUT);
+        self
+    }
+
+    /// Poll a shared pool list, so pools added or removed by a reload are
+    /// picked up on the next cycle.
+    pub fn with_pools(mut self, pools: PoolSet) -> Self {
+        self.pools = pools;
+        self
+    }
+
+    /// Fetch each DeepBook pool's order book on every poll, which is where
+    /// its best bid/ask come from.
+    pub fn with_book_fetcher(mut self, book: BookFetcher) -> Self {
+        self.book = Some(book);
+        self
+    }
+
+    /// Track rate-limit backoff in `backoff` (keep a clone to watch it).
+    pub fn with_backoff(mut self, backoff: PollBackoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// The poller's rate-limit backoff state.
+    pub fn backoff(&self) -> &PollBackoff {
+        &self.backoff
+    }
+
+    /// Pace polling by how many pools change (keep a clone to watch it).
+    /// Without this the configured interval is fixed.
+    pub fn with_pacing(mut self, pacing: PollPacing) -> Self {
+        self.pacing = pacing;
+        self
+    }
+
+    /// The interval between polls before any rate-limit backoff.
+    pub fn current_interval_ms(&self) -> u64 {
+        self.pacing.current_interval_ms()
+    }
+
     /// Run the polling loop. Updates `cache` with fresh pool states.
-    /// Bumps `heartbeat` on every successful fetch so the strategy loop knows we're alive.
+    /// Bumps `heartbeat` after every fetch that updated at least one pool, so
+    /// the strategy loop knows we're alive; failed cycles leave it alone.
     /// This function runs forever (until the task is cancelled).
     ///
-    /// Uses `sui_multiGetObjects` to batch-fetch all pools in a single RPC call,
-    /// dramatically reducing rate-limit pressure vs individual fetches.
+    /// Uses `sui_multiGetObjects` to batch-fetch pools (chunked to the node's
+    /// per-call limit), dramatically reducing rate-limit pressure vs
+    /// individual fetches. Polls start one interval apart: paced by how many
+    /// pools change (see [`PollPacing`]), and stretched while the node
+    /// rate-limits us (see [`PollBackoff`]). Other failures are retried on
+    /// the next tick.
     pub async fn run(&self, cache: PoolCache, heartbeat: Arc<AtomicU64>) -> Result<()> {
         info!(
-            "Starting RPC poller: {} pools, {}ms interval (batch mode)",
-            self.pool_ids.len(),
-            self.poll_interval.as_millis()
+            "Starting RPC poller: {} pools, {}ms interval (batch mode{})",
+            self.pools.len(),
+            self.pacing.current_interval_ms(),
+            if self.pacing.is_adaptive() { ", paced by pool changes" } else { "" }
         );
 
-        let mut interval = time::interval(self.poll_interval);
-
         loop {
-            interval.tick().await;
+            let started = time::Instant::now();
 
             match self.batch_fetch_all(&cache).await {
-                Ok(updated) => {
+                Ok((updated, changed)) => {
+                    self.backoff.succeeded();
+                    self.pacing.record_cycle(updated, changed);
                     if updated > 0 {
-                        heartbeat.store(now_ms(), Ordering::Relaxed);
+                        heartbeat.store(unix_now_ms(), Ordering::Relaxed);
                     }
-                    debug!(updated = updated, total = self.pool_ids.len(), "Batch poll cycle complete");
+                    debug!(
+                        updated = updated,
+                        changed = changed,
+                        total = self.pools.len(),
+                        "Batch poll cycle complete"
+                    );
+                }
+                Err(e) if is_rate_limited(&e) => {
+                    let base = self.pacing.current_interval();
+                    self.backoff.rate_limited(base);
+                    warn!(
+                        error = %e,
+                        interval_ms = %self.backoff.interval(base).as_millis(),
+                        "RPC rate-limited the poller — backing off"
+                    );
                 }
                 Err(e) => {
                     warn!(error = %e, "Batch fetch failed, will retry next cycle");
                 }
             }
-        }
-    }
-
-    /// Batch-fetch all pool objects in a single `sui_multiGetObjects` RPC call.
-    /// Returns the number of pools successfully updated.
-    async fn batch_fetch_all(&self, cache: &PoolCache) -> Result<usize> {
-        let object_ids: Vec<&str> = self.pool_ids.iter().map(|m| m.object_id.as_str()).collect();
-
-        let response = self
-            .client
-            .post(&self.rpc_url)
-            .json(&json!({
-                "jsonrpc": "2.0",
-                "id": 1,
-                "method": "sui_multiGetObjects",
-                "params": [
-                    object_ids,
-                    {
-                        "showContent": true,
-                        "showType": true,
-                    }
-                ]
-            }))
-            .send()
-            .await
-            .context("Batch RPC request failed")?;
-
-        let body: Value = response.json().await.context("Failed to parse batch RPC response")?;
 
-        if let Some(error) = body.get("error") {
-            anyhow::bail!("RPC error: {}", error);
+            let wait = self.backoff.interval(self.pacing.current_interval());
+            time::sleep_until(started + wait).await;
         }
+    }
 
-        let results = body
-            .get("result")
-            .and_then(|r| r.as_array())
-            .context("Invalid multiGetObjects response")?;
+    /// Batch-fetch all pool objects via chunked `sui_multiGetObjects` calls.
+    /// Returns the number of pools successfully updated, and how many of
+    /// those changed since the cached state.
+    async fn batch_fetch_all(&self, cache: &PoolCache) -> Result<(usize, usize)> {
+        let pools = self.pools.snapshot();
+        let object_ids: Vec<&str> = pools.iter().map(|m| m.object_id.as_str()).collect();
+        let objects = multi_get_objects(&self.rpc, &object_ids, self.chunk_size).await?;
 
-        let ts = now_ms();
+        let ts = unix_now_ms();
         let mut updated = 0usize;
+        let mut changed = 0usize;
 
-        for (i, obj) in results.iter().enumerate() {
-            let meta = match self.pool_ids.get(i) {
-                Some(m) => m,
-                None => continue,
+        for meta in pools.iter() {
+            let Some(obj) = objects.get(&object_key(&meta.object_id)) else {
+                continue;
             };
 
             // Check for object-level error
@@ -153,7 +387,7 @@ impl RpcPoller {
             let content = if meta.dex.to_lowercase() == "deepbook"
                 && is_deepbook_versioned(raw_content)
             {
-                match unwrap_deepbook_versioned(&self.client, &self.rpc_url, raw_content).await {
+                match unwrap_deepbook_versioned(&self.rpc, raw_content).await {
                     Ok(inner) => inner,
                     Err(e) => {
                         warn!(pool = %meta.object_id, error = %e, "DeepBook V3 unwrap failed");
@@ -173,6 +407,17 @@ impl RpcPoller {
                             state.fee_type = extract_third_type_param(type_str);
                         }
                     }
+                    if meta.dex.to_lowercase() == "deepbook" {
+                        self.fetch_book(meta, data, &content, &mut state).await;
+                    }
+                    // Checked before the upsert wakes the strategy loop, so
+                    // an inverted pool never reaches a snapshot
+                    if guard_coin_ordering(cache, meta, data) == CoinOrdering::Flipped {
+                        continue;
+                    }
+                    if cache.get(&meta.object_id).is_none_or(|old| old.significant_change(&state, 0.0)) {
+                        changed += 1;
+                    }
                     cache.upsert(meta.object_id.clone(), state);
                     updated += 1;
                 }
@@ -182,69 +427,83 @@ impl RpcPoller {
             }
         }
 
-        Ok(updated)
+        Ok((updated, changed))
     }
 
+    /// Fill a DeepBook pool's bid/ask from its order book: the levels near
+    /// the mid via dev-inspect, else the top of each side from the
+    /// PoolInner (`inner`) BigVectors. On failure the state goes out
+    /// without book data, so the cache keeps the last book.
+    async fn fetch_book(&self, meta: &PoolMeta, data: &Value, inner: &Value, state: &mut PoolState) {
+        let Some(book) = &self.book else { return };
+        let inspected = match deepbook_book::initial_shared_version(data) {
+            Some(version) => book.fetch(meta, version).await,
+            None => Err(anyhow::anyhow!("DeepBook pool has no shared owner version")),
+        };
+        let depth = match inspected {
+            Ok(depth) => Ok(depth),
+            Err(e) => {
+                debug!(pool = %meta.object_id, error = %format!("{:#}", e), "Dev-inspect book read failed — reading BigVectors");
+                book.fetch_top_of_book(inner)
+                    .await
+                    .map_err(|fallback| anyhow::anyhow!("{:#}; BigVector read: {:#}", e, fallback))
+            }
+        };
+        match depth {
+            Ok(depth) => deepbook_book::apply_book(state, depth, book.decimals()),
+            Err(e) => {
+                warn_throttled!(
+                    format!("rpc_poller.book_failed:{}", meta.object_id),
+                    DEFAULT_INTERVAL,
+                    pool = %meta.object_id,
+                    error = %format!("{:#}", e),
+                    "DeepBook order book fetch failed"
+                );
+            }
+        }
+    }
 }
 
 /// Seed the cache with initial pool states via multi-get.
 pub async fn seed_cache(config: &Config, cache: &PoolCache) -> Result<()> {
-    let client = Client::builder()
-        .timeout(Duration::from_secs(10))
-        .build()?;
+    let rpc = RpcClient::from_config(config).with_timeout(Duration::from_secs(10));
 
-    let object_ids: Vec<&str> = config
-        .monitored_pools
+    // Config::from_env already de-duplicates; re-check for configs built elsewhere
+    let pools: Vec<PoolMeta> = dedup_pools(config.monitored_pools.clone())?
         .iter()
-        .map(|p| p.pool_id.as_str())
+        .map(PoolMeta::from)
         .collect();
 
-    if object_ids.is_empty() {
+    if pools.is_empty() {
         warn!("No pools configured for monitoring");
         return Ok(());
     }
 
-    info!("Seeding pool cache with {} pools...", object_ids.len());
-
-    // Use sui_multiGetObjects for batch fetching
-    let response = client
-        .post(&config.rpc_url)
-        .json(&json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "sui_multiGetObjects",
-            "params": [
-                object_ids,
-                {
-                    "showContent": true,
-                    "showType": true,
-                }
-            ]
-        }))
-        .send()
+    info!("Seeding pool cache with {} pools...", pools.len());
+    seed_pools(&rpc, &pools, config.multi_get_chunk_size, cache).await?;
+    info!("Pool cache seeded: {} pools", cache.len());
+    Ok(())
+}
+
+/// Fetch `pools` via multi-get and insert each one that parses. Pools that
+/// don't exist or fail to parse are logged and skipped. Returns the number
+/// seeded.
+pub async fn seed_pools(
+    rpc: &RpcClient,
+    pools: &[PoolMeta],
+    chunk_size: usize,
+    cache: &PoolCache,
+) -> Result<usize> {
+    let object_ids: Vec<&str> = pools.iter().map(|p| p.object_id.as_str()).collect();
+    let objects = multi_get_objects(rpc, &object_ids, chunk_size)
         .await
         .context("Failed to seed pool cache")?;
 
-    let body: Value = response.json().await?;
-    let results = body
-        .get("result")
-        .and_then(|r| r.as_array())
-        .context("Invalid multiGetObjects response")?;
-
-    let now_ms = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64;
-
-    for (i, obj) in results.iter().enumerate() {
-        if let Some(pool_config) = config.monitored_pools.get(i) {
-            let meta = PoolMeta {
-                object_id: pool_config.pool_id.clone(),
-                dex: pool_config.dex.clone(),
-                coin_type_a: pool_config.coin_type_a.clone(),
-                coin_type_b: pool_config.coin_type_b.clone(),
-            };
+    let now_ms = unix_now_ms();
+    let mut seeded = 0usize;
 
+    for meta in pools {
+        if let Some(obj) = objects.get(&object_key(&meta.object_id)) {
             // Check for object-level error (e.g. notExists)
             if let Some(obj_error) = obj.get("error") {
                 let code = obj_error
@@ -267,7 +526,7 @@ pub async fn seed_cache(config: &Config, cache: &PoolCache) -> Result<()> {
                         && is_deepbook_versioned(raw_content)
                     {
                         debug!(pool = %meta.object_id, "DeepBook V3 Versioned detected, fetching inner object");
-                        match unwrap_deepbook_versioned(&client, &config.rpc_url, raw_content)
+                        match unwrap_deepbook_versioned(rpc, raw_content)
                             .await
                         {
                             Ok(inner) => inner,
@@ -284,7 +543,7 @@ pub async fn seed_cache(config: &Config, cache: &PoolCache) -> Result<()> {
                         raw_content.clone()
                     };
 
-                    match parsers::parse_pool_object(&content, &meta.dex, &meta, now_ms) {
+                    match parsers::parse_pool_object(&content, &meta.dex, meta, now_ms) {
                         Ok(mut state) => {
                             // Extract Turbos fee type from on-chain object type
                             if meta.dex.to_lowercase() == "turbos" {
@@ -295,12 +554,16 @@ pub async fn seed_cache(config: &Config, cache: &PoolCache) -> Result<()> {
                                     }
                                 }
                             }
+                            if guard_coin_ordering(cache, meta, data) == CoinOrdering::Flipped {
+                                continue;
+                            }
                             info!(
                                 pool = %meta.object_id,
                                 dex = %meta.dex,
                                 "Seeded pool state"
                             );
                             cache.upsert(meta.object_id.clone(), state);
+                            seeded += 1;
                         }
                         Err(e) => {
                             error!(pool = %meta.object_id, error = %e, "Failed to parse pool");
@@ -311,8 +574,7 @@ pub async fn seed_cache(config: &Config, cache: &PoolCache) -> Result<()> {
         }
     }
 
-    info!("Pool cache seeded: {} pools", cache.len());
-    Ok(())
+    Ok(seeded)
 }
 
 /// Extract the 3rd type parameter from a Sui Move type string.
@@ -321,30 +583,223 @@ pub async fn seed_cache(config: &Config, cache: &PoolCache) -> Result<()> {
 ///   `0x91bfbc...::pool::Pool<CoinA, CoinB, 0x91bfbc...::fee3000bps::FEE3000BPS>`
 ///
 /// This function returns the 3rd parameter (the fee type).
-/// Splits on `, ` which works for non-nested generic types.
-fn extract_third_type_param(type_str: &str) -> Option<String> {
-    let open = type_str.find('<')?;
-    let close = type_str.rfind('>')?;
+pub(crate) fn extract_third_type_param(type_str: &str) -> Option<String> {
+    split_type_params(type_str).into_iter().nth(2)
+}
+
+/// Split the outermost generic parameters of a Move type string.
+///
+/// Depth-aware: commas inside nested generics (e.g. `Pool<Wrapped<A, B>, C, Fee>`)
+/// do not split. Whitespace around each parameter is trimmed.
+pub fn split_type_params(type_str: &str) -> Vec<String> {
+    let (open, close) = match (type_str.find('<'), type_str.rfind('>')) {
+        (Some(o), Some(c)) if o < c => (o, c),
+        _ => return Vec::new(),
+    };
     let inner = &type_str[open + 1..close];
-    // Split on ", " to separate type parameters
-    let parts: Vec<&str> = inner.split(", ").collect();
-    if parts.len() >= 3 {
-        Some(parts[2].to_string())
+
+    let mut params = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0usize;
+    for (i, ch) in inner.char_indices() {
+        match ch {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                params.push(inner[start..i].trim().to_string());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = inner[start..].trim();
+    if !last.is_empty() {
+        params.push(last.to_string());
+    }
+    params
+}
+
+/// Max concurrent `sui_multiGetObjects` chunk requests per fetch.
+const MULTI_GET_CONCURRENCY: usize = 4;
+
+/// Fetch `object_ids` with `sui_multiGetObjects`, at most `chunk_size` IDs
+/// per call (fullnodes reject larger requests outright), with up to
+/// `MULTI_GET_CONCURRENCY` calls in flight.
+///
+/// Results are keyed by [`object_key`] of the returned object ID, not by
+/// position, so they stay aligned however the chunks come back. A failed
+/// chunk only drops its own objects; the call fails only if every chunk did.
+pub async fn multi_get_objects(
+    rpc: &RpcClient,
+    object_ids: &[&str],
+    chunk_size: usize,
+) -> Result<HashMap<String, Value>> {
+    // Owned chunks: borrowed ones trip the `Send` check on spawned callers
+    let chunks: Vec<Vec<String>> = object_ids
+        .chunks(chunk_size.max(1))
+        .map(|c| c.iter().map(|id| id.to_string()).collect())
+        .collect();
+    let total = chunks.len();
+    let results: Vec<Result<Vec<(String, Value)>>> = stream::iter(chunks)
+        .map(|chunk| async move { multi_get_chunk(rpc, &chunk).await })
+        .buffer_unordered(MULTI_GET_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut objects = HashMap::with_capacity(object_ids.len());
+    let mut first_error = None;
+    let mut failed = 0usize;
+    for result in results {
+        match result {
+            Ok(items) => objects.extend(items),
+            Err(e) => {
+                failed += 1;
+                warn!(error = %e, "multiGetObjects chunk failed");
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    match first_error {
+        Some(e) if failed == total => Err(e),
+        _ => Ok(objects),
+    }
+}
+
+/// One `sui_multiGetObjects` call. Each result is keyed by the object ID it
+/// reports (`data.objectId`, or `error.object_id` for missing objects),
+/// falling back to the requested ID at the same position.
+async fn multi_get_chunk(rpc: &RpcClient, chunk: &[String]) -> Result<Vec<(String, Value)>> {
+    let params = json!([
+        chunk,
+        {
+            "showContent": true,
+            "showType": true,
+            "showOwner": true,
+        }
+    ]);
+    let body = rpc
+        .call("sui_multiGetObjects", params)
+        .await
+        .context("Batch RPC request failed")?;
+
+    if let Some(error) = body.get("error") {
+        anyhow::bail!("RPC error: {}", error);
+    }
+
+    let results = body
+        .get("result")
+        .and_then(|r| r.as_array())
+        .context("Invalid multiGetObjects response")?;
+
+    Ok(results
+        .iter()
+        .enumerate()
+        .filter_map(|(i, obj)| {
+            let reported = obj
+                .pointer("/data/objectId")
+                .or_else(|| obj.pointer("/error/object_id"))
+                .and_then(|id| id.as_str());
+            let id = reported.or_else(|| chunk.get(i).map(String::as_str))?;
+            Some((object_key(id), obj.clone()))
+        })
+        .collect())
+}
+
+/// Canonical object ID for lookups: lowercase hex without leading zeros, so
+/// the node's 64-hex form matches a short configured ID.
+pub fn object_key(object_id: &str) -> String {
+    let hex = object_id.trim_start_matches("0x").trim_start_matches("0X");
+    let hex = hex.trim_start_matches('0').to_lowercase();
+    format!("0x{}", if hex.is_empty() { "0" } else { &hex })
+}
+
+/// Quarantine reason prefix for pools whose on-chain coin order no longer
+/// matches the config. Only quarantines with this prefix are auto-released.
+const FLIPPED_ORDER_REASON: &str = "coin ordering flipped";
+
+/// How a pool object's on-chain `Pool<A, B, ..>` coin order relates to the
+/// configured `coin_type_a` / `coin_type_b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoinOrdering {
+    Matches,
+    /// On-chain type is `Pool<B, A, ..>` — a migrated or re-created pool.
+    Flipped,
+    /// The type doesn't carry both configured coins as its first two
+    /// parameters (e.g. LP-typed pools), so the order can't be checked.
+    Unknown,
+}
+
+/// Compare the first two type parameters of an on-chain object type with
+/// the configured coin pair. Addresses are normalized, so long and short
+/// forms of the same coin type compare equal.
+pub fn coin_ordering(type_str: &str, meta: &PoolMeta) -> CoinOrdering {
+    let params = split_type_params(type_str);
+    let (Some(first), Some(second)) = (params.first(), params.get(1)) else {
+        return CoinOrdering::Unknown;
+    };
+    let (first, second) = (normalize_coin_type(first), normalize_coin_type(second));
+    let a = normalize_coin_type(&meta.coin_type_a);
+    let b = normalize_coin_type(&meta.coin_type_b);
+
+    if first == a && second == b {
+        CoinOrdering::Matches
+    } else if first == b && second == a {
+        CoinOrdering::Flipped
     } else {
-        None
+        CoinOrdering::Unknown
     }
 }
 
-fn now_ms() -> u64 {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+/// Exclude a pool whose on-chain coin order is the reverse of the config.
+///
+/// Every parser and PTB builder assumes `coin_type_a` is the pool's first
+/// type parameter, so a flipped pool would be priced and traded inverted.
+/// Rather than guess at correcting the state, the pool is quarantined until
+/// the ordering matches again (config fixed, or the pool flips back).
+/// Callers must not upsert the state of a pool this reports as `Flipped`.
+fn guard_coin_ordering(cache: &PoolCache, meta: &PoolMeta, data: &Value) -> CoinOrdering {
+    let Some(type_str) = data.get("type").and_then(|t| t.as_str()) else {
+        return CoinOrdering::Unknown;
+    };
+
+    let ordering = coin_ordering(type_str, meta);
+    match ordering {
+        CoinOrdering::Flipped => {
+            if !cache.is_quarantined(&meta.object_id) {
+                error!(
+                    pool = %meta.object_id,
+                    dex = %meta.dex,
+                    on_chain = %type_str,
+                    config_a = %meta.coin_type_a,
+                    config_b = %meta.coin_type_b,
+                    "🚨 Pool coin ordering flipped on-chain — excluding pool; swap coin types in MONITORED_POOLS"
+                );
+            }
+            cache.quarantine(
+                &meta.object_id,
+                &format!(
+                    "{}: on-chain Pool<{}, {}>",
+                    FLIPPED_ORDER_REASON, meta.coin_type_b, meta.coin_type_a
+                ),
+            );
+        }
+        CoinOrdering::Matches => {
+            let flipped = cache
+                .quarantined()
+                .iter()
+                .any(|(id, reason)| id == &meta.object_id && reason.starts_with(FLIPPED_ORDER_REASON));
+            if flipped && cache.release(&meta.object_id) {
+                info!(pool = %meta.object_id, dex = %meta.dex, "Pool coin ordering matches config again — released");
+            }
+        }
+        CoinOrdering::Unknown => {}
+    }
+    ordering
 }
 
 /// Check if a DeepBook content object is a V3 Versioned wrapper.
 /// V3 pools have an `inner` field (the Versioned object) but no direct `base_vault`.
-fn is_deepbook_versioned(content: &Value) -> bool {
+pub(crate) fn is_deepbook_versioned(content: &Value) -> bool {
     content
         .get("fields")
         .map(|f| f.get("inner").is_some() && f.get("base_vault").is_none())
@@ -357,50 +812,44 @@ fn is_deepbook_versioned(content: &Value) -> bool {
 /// The outer pool has: content.fields.inner.fields.id.id → inner versioned object ID
 /// The PoolInner is stored as a dynamic field on that inner object with key {type: "u64", value: "1"}.
 /// The dynamic field response wraps the actual data: content.fields.value = PoolInner { fields: ... }
-async fn unwrap_deepbook_versioned(
-    client: &Client,
-    rpc_url: &str,
-    content: &Value,
-) -> Result<Value> {
-    let inner_id = content
+async fn unwrap_deepbook_versioned(rpc: &RpcClient, content: &Value) -> Result<Value> {
+    let inner_id = deepbook_inner_id(content)?;
+    let result = fetch_deepbook_dynamic_field(rpc, inner_id).await?;
+    deepbook_inner_from_dynamic_field(&result)
+}
+
+/// The Versioned inner object ID of a DeepBook V3 pool (`fields.inner.fields.id.id`).
+pub(crate) fn deepbook_inner_id(content: &Value) -> Result<&str> {
+    content
         .get("fields")
         .and_then(|f| f.get("inner"))
         .and_then(|i| i.get("fields"))
         .and_then(|f| f.get("id"))
         .and_then(|id| id.get("id"))
         .and_then(|id| id.as_str())
-        .context("Missing inner versioned object ID in DeepBook V3 pool")?;
+        .context("Missing inner versioned object ID in DeepBook V3 pool")
+}
 
+/// Fetch the raw `suix_getDynamicFieldObject` result for a DeepBook V3 PoolInner.
+pub(crate) async fn fetch_deepbook_dynamic_field(rpc: &RpcClient, inner_id: &str) -> Result<Value> {
     debug!(inner_id = %inner_id, "Fetching DeepBook V3 PoolInner dynamic field");
 
-    let response = client
-        .post(rpc_url)
-        .json(&json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "suix_getDynamicFieldObject",
-            "params": [
-                inner_id,
-                {
-                    "type": "u64",
-                    "value": "1"
-                }
-            ]
-        }))
-        .send()
+    let body = rpc
+        .call("suix_getDynamicFieldObject", json!([inner_id, { "type": "u64", "value": "1" }]))
         .await
         .context("Failed to fetch DeepBook V3 inner object")?;
 
-    let body: Value = response.json().await?;
-
     if let Some(error) = body.get("error") {
         anyhow::bail!("RPC error fetching DeepBook V3 inner: {}", error);
     }
 
-    let result = body
-        .get("result")
-        .context("Missing result for DeepBook V3 inner")?;
+    body.get("result")
+        .cloned()
+        .context("Missing result for DeepBook V3 inner")
+}
 
+/// Extract the PoolInner content from a `suix_getDynamicFieldObject` result.
+pub(crate) fn deepbook_inner_from_dynamic_field(result: &Value) -> Result<Value> {
     if let Some(obj_error) = result.get("error") {
         let code = obj_error
             .get("code")
@@ -425,3 +874,176 @@ async fn unwrap_deepbook_versioned(
         .cloned()
         .context("Missing value in DeepBook V3 dynamic field response")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_third_type_param_flat() {
+        let t = "0x91::pool::Pool<0x2::sui::SUI, 0xdba::usdc::USDC, 0x91::fee3000bps::FEE3000BPS>";
+        assert_eq!(
+            extract_third_type_param(t).as_deref(),
+            Some("0x91::fee3000bps::FEE3000BPS")
+        );
+    }
+
+    #[test]
+    fn test_extract_third_type_param_nested() {
+        // Naive ", " splitting would return "0xb::y::Y>" here
+        let t = "0x1::pool::Pool<0xa::w::Wrapped<0xa::x::X, 0xb::y::Y>, 0x2::sui::SUI, 0x1::fee::FEE>";
+        assert_eq!(extract_third_type_param(t).as_deref(), Some("0x1::fee::FEE"));
+    }
+
+    #[test]
+    fn test_object_key_matches_long_and_short_ids() {
+        let long = "0x00000000000000000000000000000000000000000000000000000000000000AB";
+        assert_eq!(object_key(long), object_key("0xab"));
+        assert_eq!(object_key("0x0"), "0x0");
+    }
+
+    fn meta(a: &str, b: &str) -> PoolMeta {
+        PoolMeta {
+            object_id: "0xpool".to_string(),
+            dex: "cetus".to_string(),
+            coin_type_a: a.to_string(),
+            coin_type_b: b.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_coin_ordering() {
+        let m = meta("0x2::sui::SUI", "0xdba::usdc::USDC");
+        let matches = "0x1eab::pool::Pool<0x2::sui::SUI, 0xdba::usdc::USDC>";
+        let flipped = "0x1eab::pool::Pool<0xdba::usdc::USDC, 0x2::sui::SUI>";
+        assert_eq!(coin_ordering(matches, &m), CoinOrdering::Matches);
+        assert_eq!(coin_ordering(flipped, &m), CoinOrdering::Flipped);
+
+        // Long-form addresses compare equal to short ones
+        let long = "0x1eab::pool::Pool<0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI, 0xDBA::usdc::USDC, 0x91::fee::FEE>";
+        assert_eq!(coin_ordering(long, &m), CoinOrdering::Matches);
+
+        // LP-typed pools and unrelated types can't be checked
+        assert_eq!(coin_ordering("0xefe::pool::Pool<0xabc::lp::LP>", &m), CoinOrdering::Unknown);
+        assert_eq!(coin_ordering("0x2::object::Object", &m), CoinOrdering::Unknown);
+    }
+
+    #[test]
+    fn test_flipped_pool_quarantined_then_released() {
+        let cache = PoolCache::new();
+        let m = meta("0x2::sui::SUI", "0xdba::usdc::USDC");
+        let flipped = json!({ "type": "0x1eab::pool::Pool<0xdba::usdc::USDC, 0x2::sui::SUI>" });
+        let matches = json!({ "type": "0x1eab::pool::Pool<0x2::sui::SUI, 0xdba::usdc::USDC>" });
+
+        assert_eq!(guard_coin_ordering(&cache, &m, &flipped), CoinOrdering::Flipped);
+        assert!(cache.is_quarantined("0xpool"));
+
+        assert_eq!(guard_coin_ordering(&cache, &m, &matches), CoinOrdering::Matches);
+        assert!(!cache.is_quarantined("0xpool"));
+
+        // Quarantines for other reasons are left alone
+        cache.quarantine("0xpool", "fee type mismatch");
+        assert_eq!(guard_coin_ordering(&cache, &m, &matches), CoinOrdering::Matches);
+        assert!(cache.is_quarantined("0xpool"));
+    }
+
+    #[test]
+    fn test_backoff_doubles_to_cap_and_recovers() {
+        let backoff = PollBackoff::default();
+        let base = Duration::from_secs(5);
+        assert_eq!(backoff.interval(base), base);
+
+        backoff.rate_limited(base);
+        assert_eq!(backoff.interval(base), Duration::from_secs(10));
+        for _ in 0..5 {
+            backoff.rate_limited(base);
+        }
+        assert_eq!(backoff.interval(base), MAX_RATE_LIMIT_INTERVAL);
+        assert_eq!(backoff.multiplier(), 8, "stops doubling once at the cap");
+        assert_eq!(backoff.rate_limited_total(), 6);
+
+        // Each run of RECOVERY_SUCCESSES halves it
+        for _ in 0..RECOVERY_SUCCESSES - 1 {
+            backoff.succeeded();
+        }
+        assert_eq!(backoff.multiplier(), 8);
+        backoff.succeeded();
+        assert_eq!(backoff.multiplier(), 4);
+        // A rate limit in between restarts the count
+        backoff.succeeded();
+        backoff.rate_limited(base);
+        for _ in 0..RECOVERY_SUCCESSES * 3 {
+            backoff.succeeded();
+        }
+        assert!(!backoff.is_backing_off());
+    }
+
+    #[test]
+    fn test_pacing_shrinks_when_busy_and_grows_when_quiet() {
+        let ms = Duration::from_millis;
+        let pacing = PollPacing::new(ms(400), ms(100), ms(1_000));
+        let window = |polled, changed| {
+            for _ in 0..PACING_WINDOW {
+                pacing.record_cycle(polled, changed);
+            }
+        };
+
+        // Nothing moves until a window completes
+        for _ in 0..PACING_WINDOW - 1 {
+            pacing.record_cycle(10, 10);
+        }
+        assert_eq!(pacing.current_interval_ms(), 400);
+        pacing.record_cycle(10, 10);
+        assert_eq!(pacing.current_interval_ms(), 300);
+
+        // Half the pools changing: down to the floor
+        for _ in 0..10 {
+            window(10, 5);
+        }
+        assert_eq!(pacing.current_interval_ms(), 100);
+
+        // In between the thresholds it holds
+        window(10, 1);
+        assert_eq!(pacing.current_interval_ms(), 100);
+
+        // Quiet pools: back up to the ceiling
+        window(10, 0);
+        assert_eq!(pacing.current_interval_ms(), 125);
+        for _ in 0..20 {
+            window(10, 0);
+        }
+        assert_eq!(pacing.current_interval_ms(), 1_000);
+
+        // A window with nothing polled (every fetch failed) says nothing
+        window(0, 0);
+        assert_eq!(pacing.current_interval_ms(), 1_000);
+    }
+
+    #[test]
+    fn test_fixed_pacing_never_moves() {
+        let pacing = PollPacing::fixed(Duration::from_millis(500));
+        assert!(!pacing.is_adaptive());
+        for changed in [10, 0] {
+            for _ in 0..PACING_WINDOW * 3 {
+                pacing.record_cycle(10, changed);
+            }
+            assert_eq!(pacing.current_interval_ms(), 500);
+        }
+    }
+
+    #[test]
+    fn test_is_rate_limited() {
+        assert!(is_rate_limited(&anyhow::anyhow!(
+            "RPC error: {}",
+            json!({ "code": -32000, "message": "Too Many Requests" })
+        )));
+        assert!(!is_rate_limited(&anyhow::anyhow!("Invalid multiGetObjects response")));
+    }
+
+    #[test]
+    fn test_split_type_params_no_space_and_missing() {
+        assert_eq!(split_type_params("P<A,B,C>"), vec!["A", "B", "C"]);
+        assert!(split_type_params("0x2::sui::SUI").is_empty());
+        assert_eq!(extract_third_type_param("P<A, B>"), None);
+    }
+}
@@ -0,0 +1,313 @@
+//! WebSocket subscription budgeting.
+//!
+//! RPC providers cap concurrent WS subscriptions, so only the most active
+//! pools get a precise per-pool `ChangedObject` subscription. The rest are
+//! covered by package-level event subscriptions plus polling. Pools are
+//! ranked by recent state changes and opportunity participation, and the
+//! allocation is re-balanced incrementally as activity shifts.
+
+use anyhow::Result;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// How far back activity counts towards a pool's rank.
+pub const DEFAULT_ACTIVITY_WINDOW_MS: u64 = 3_600_000;
+
+/// One opportunity participation counts as this many state updates.
+pub const PARTICIPATION_WEIGHT: u64 = 5;
+
+#[derive(Debug, Default)]
+struct Activity {
+    updates: VecDeque<u64>,
+    participations: VecDeque<u64>,
+}
+
+impl Activity {
+    fn prune(&mut self, cutoff: u64) {
+        while self.updates.front().is_some_and(|&t| t < cutoff) {
+            self.updates.pop_front();
+        }
+        while self.participations.front().is_some_and(|&t| t < cutoff) {
+            self.participations.pop_front();
+        }
+    }
+}
+
+/// Per-pool activity over a sliding window: observed state changes and
+/// appearances in detected opportunities. Cheap to clone (shared state).
+#[derive(Debug, Clone)]
+pub struct PoolActivity {
+    inner: Arc<Mutex<HashMap<String, Activity>>>,
+    window_ms: u64,
+}
+
+impl PoolActivity {
+    pub fn new(window_ms: u64) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(HashMap::new())),
+            window_ms,
+        }
+    }
+
+    /// Record that a pool's on-chain state changed at `now_ms`.
+    pub fn record_update(&self, pool_id: &str, now_ms: u64) {
+        if let Ok(mut map) = self.inner.lock() {
+            let a = map.entry(pool_id.to_string()).or_default();
+            a.updates.push_back(now_ms);
+            a.prune(now_ms.saturating_sub(self.window_ms));
+        }
+    }
+
+    /// Record that these pools were part of a detected opportunity.
+    pub fn record_participation(&self, pool_ids: &[String], now_ms: u64) {
+        if let Ok(mut map) = self.inner.lock() {
+            for id in pool_ids {
+                let a = map.entry(id.clone()).or_default();
+                a.participations.push_back(now_ms);
+                a.prune(now_ms.saturating_sub(self.window_ms));
+            }
+        }
+    }
+
+    /// Activity score within the window ending at `now_ms`.
+    pub fn score(&self, pool_id: &str, now_ms: u64) -> u64 {
+        let Ok(mut map) = self.inner.lock() else {
+            return 0;
+        };
+        match map.get_mut(pool_id) {
+            Some(a) => {
+                a.prune(now_ms.saturating_sub(self.window_ms));
+                a.updates.len() as u64 + PARTICIPATION_WEIGHT * a.participations.len() as u64
+            }
+            None => 0,
+        }
+    }
+
+    /// Rank `pool_ids` most-active first. Ties keep current `incumbents`
+    /// ahead of challengers (no churn between equally quiet pools), then
+    /// fall back to pool ID for a deterministic order.
+    pub fn rank(&self, pool_ids: &[String], incumbents: &HashSet<String>, now_ms: u64) -> Vec<String> {
+        let mut scored: Vec<(u64, bool, &String)> = pool_ids
+            .iter()
+            .map(|id| (self.score(id, now_ms), incumbents.contains(id), id))
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(b.2)));
+        scored.into_iter().map(|(_, _, id)| id.clone()).collect()
+    }
+}
+
+impl Default for PoolActivity {
+    fn default() -> Self {
+        Self::new(DEFAULT_ACTIVITY_WINDOW_MS)
+    }
+}
+
+/// Where per-pool subscriptions are actually opened and closed.
+pub trait SubscriptionSink {
+    fn subscribe(&mut self, pool_id: &str) -> Result<()>;
+    fn unsubscribe(&mut self, pool_id: &str) -> Result<()>;
+}
+
+/// Subscription changes made by one re-balance.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RebalanceDiff {
+    pub subscribe: Vec<String>,
+    pub unsubscribe: Vec<String>,
+}
+
+impl RebalanceDiff {
+    pub fn is_empty(&self) -> bool {
+        self.subscribe.is_empty() && self.unsubscribe.is_empty()
+    }
+}
+
+/// The set of pools holding a per-pool subscription, capped at `cap`.
+/// Cheap to clone; clones share the allocation (for status reporting).
+#[derive(Debug, Clone)]
+pub struct SubscriptionBudget {
+    cap: usize,
+    allocation: Arc<Mutex<BTreeSet<String>>>,
+}
+
+impl SubscriptionBudget {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            cap,
+            allocation: Arc::new(Mutex::new(BTreeSet::new())),
+        }
+    }
+
+    pub fn cap(&self) -> usize {
+        self.cap
+    }
+
+    /// Pools currently holding a per-pool subscription.
+    pub fn allocation(&self) -> Vec<String> {
+        self.allocation
+            .lock()
+            .map(|a| a.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Forget the allocation, e.g. after the connection (and with it every
+    /// subscription) was lost.
+    pub fn reset(&self) {
+        if let Ok(mut a) = self.allocation.lock() {
+            a.clear();
+        }
+    }
+
+    /// Changes needed to move from the current allocation to the top `cap`
+    /// of `ranked`.
+    pub fn diff(&self, ranked: &[String]) -> RebalanceDiff {
+        let current = self.allocation.lock().map(|a| a.clone()).unwrap_or_default();
+        let desired: BTreeSet<&String> = ranked.iter().take(self.cap).collect();
+        RebalanceDiff {
+            subscribe: ranked
+                .iter()
+                .take(self.cap)
+                .filter(|id| !current.contains(*id))
+                .cloned()
+                .collect(),
+            unsubscribe: current.iter().filter(|id| !desired.contains(id)).cloned().collect(),
+        }
+    }
+
+    /// Move the allocation to the top `cap` of `ranked` through `sink`.
+    ///
+    /// Unsubscribes go first so the live count never exceeds the cap. A
+    /// failed call leaves that pool's allocation unchanged, so the returned
+    /// diff lists only changes that went through.
+    pub fn rebalance(&self, ranked: &[String], sink: &mut impl SubscriptionSink) -> RebalanceDiff {
+        let planned = self.diff(ranked);
+        let mut applied = RebalanceDiff::default();
+        let Ok(mut allocation) = self.allocation.lock() else {
+            return applied;
+        };
+
+        for id in planned.unsubscribe {
+            if sink.unsubscribe(&id).is_ok() {
+                allocation.remove(&id);
+                applied.unsubscribe.push(id);
+            }
+        }
+        for id in planned.subscribe {
+            if allocation.len() >= self.cap {
+                break;
+            }
+            if sink.subscribe(&id).is_ok() {
+                allocation.insert(id.clone());
+                applied.subscribe.push(id);
+            }
+        }
+        applied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records calls and tracks the live subscription count.
+    #[derive(Default)]
+    struct MockSink {
+        live: BTreeSet<String>,
+        max_live: usize,
+        fail: HashSet<String>,
+    }
+
+    impl SubscriptionSink for MockSink {
+        fn subscribe(&mut self, pool_id: &str) -> Result<()> {
+            if self.fail.contains(pool_id) {
+                anyhow::bail!("subscribe rejected");
+            }
+            self.live.insert(pool_id.to_string());
+            self.max_live = self.max_live.max(self.live.len());
+            Ok(())
+        }
+
+        fn unsubscribe(&mut self, pool_id: &str) -> Result<()> {
+            self.live.remove(pool_id);
+            Ok(())
+        }
+    }
+
+    fn ids(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rank_by_updates_and_participation() {
+        let activity = PoolActivity::new(1_000);
+        for t in 0..3 {
+            activity.record_update("0xbusy", 100 + t);
+        }
+        activity.record_participation(&ids(&["0xarb"]), 100);
+        activity.record_update("0xold", 0);
+
+        let ranked = activity.rank(&ids(&["0xquiet", "0xbusy", "0xarb", "0xold"]), &HashSet::new(), 1_050);
+        // 0xarb: 5 (one participation), 0xbusy: 3, 0xold expired at 1_050
+        assert_eq!(ranked, ids(&["0xarb", "0xbusy", "0xold", "0xquiet"]));
+        assert_eq!(activity.score("0xold", 1_050), 0);
+    }
+
+    #[test]
+    fn test_rank_ties_prefer_incumbents() {
+        let activity = PoolActivity::default();
+        let incumbents: HashSet<String> = ids(&["0xb"]).into_iter().collect();
+        let ranked = activity.rank(&ids(&["0xa", "0xb", "0xc"]), &incumbents, 0);
+        assert_eq!(ranked, ids(&["0xb", "0xa", "0xc"]));
+    }
+
+    #[test]
+    fn test_rebalance_is_incremental() {
+        let budget = SubscriptionBudget::new(2);
+        let mut sink = MockSink::default();
+
+        let first = budget.rebalance(&ids(&["0xa", "0xb", "0xc"]), &mut sink);
+        assert_eq!(first.subscribe, ids(&["0xa", "0xb"]));
+        assert!(first.unsubscribe.is_empty());
+
+        // Only the pool that dropped out is swapped
+        let second = budget.rebalance(&ids(&["0xc", "0xa", "0xb"]), &mut sink);
+        assert_eq!(second.subscribe, ids(&["0xc"]));
+        assert_eq!(second.unsubscribe, ids(&["0xb"]));
+        assert_eq!(budget.allocation(), ids(&["0xa", "0xc"]));
+
+        // Unchanged ranking → no calls
+        assert!(budget.rebalance(&ids(&["0xc", "0xa", "0xb"]), &mut sink).is_empty());
+    }
+
+    #[test]
+    fn test_rebalance_enforces_cap() {
+        let budget = SubscriptionBudget::new(2);
+        let mut sink = MockSink::default();
+        budget.rebalance(&ids(&["0xa", "0xb"]), &mut sink);
+        budget.rebalance(&ids(&["0xc", "0xd", "0xa"]), &mut sink);
+
+        // Unsubscribes run first, so the live count never exceeded the cap
+        assert_eq!(sink.max_live, 2);
+        assert_eq!(sink.live.len(), 2);
+        assert_eq!(budget.allocation(), ids(&["0xc", "0xd"]));
+    }
+
+    #[test]
+    fn test_failed_subscribe_not_allocated() {
+        let budget = SubscriptionBudget::new(3);
+        let mut sink = MockSink {
+            fail: ids(&["0xb"]).into_iter().collect(),
+            ..Default::default()
+        };
+        let diff = budget.rebalance(&ids(&["0xa", "0xb", "0xc"]), &mut sink);
+        assert_eq!(diff.subscribe, ids(&["0xa", "0xc"]));
+        assert_eq!(budget.allocation(), ids(&["0xa", "0xc"]));
+
+        // Retried on the next re-balance once the sink accepts it
+        sink.fail.clear();
+        let diff = budget.rebalance(&ids(&["0xa", "0xb", "0xc"]), &mut sink);
+        assert_eq!(diff.subscribe, ids(&["0xb"]));
+
+        budget.reset();
+        assert!(budget.allocation().is_empty());
+    }
+}
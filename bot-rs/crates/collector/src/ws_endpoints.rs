@@ -0,0 +1,167 @@
+//! WebSocket endpoint selection and failover.
+//!
+//! `SUI_WS_URL` (one URL or a comma-separated list) takes precedence over the
+//! URL derived from `SUI_RPC_URL`. Each stream walks the list in order: the
+//! current URL is kept until it fails `max_failures` times in a row, then the
+//! stream reconnects to the next one (wrapping around).
+
+use anyhow::{Context, Result};
+use arb_types::Config;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio_tungstenite::connect_async;
+use tracing::warn;
+
+use crate::ws_stream::WsStream;
+
+/// Consecutive failures on one URL before moving to the next.
+pub const DEFAULT_MAX_FAILURES: u32 = 3;
+
+/// Failover list of WebSocket URLs for one stream.
+pub struct WsEndpoints {
+    urls: Vec<String>,
+    max_failures: u32,
+    reconnect_delay: Duration,
+    /// (index of the current URL, consecutive failures on it)
+    state: Mutex<(usize, u32)>,
+}
+
+impl WsEndpoints {
+    pub fn new(urls: Vec<String>) -> Self {
+        assert!(!urls.is_empty(), "WsEndpoints needs at least one URL");
+        Self {
+            urls,
+            max_failures: DEFAULT_MAX_FAILURES,
+            reconnect_delay: Duration::from_secs(3),
+            state: Mutex::new((0, 0)),
+        }
+    }
+
+    /// `config.ws_urls` if set, else one URL derived from each RPC endpoint.
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(Self::resolve(config))
+    }
+
+    /// The URLs a stream should use for `config`, in failover order.
+    pub fn resolve(config: &Config) -> Vec<String> {
+        if config.ws_urls.is_empty() {
+            let mut urls: Vec<String> =
+                config.rpc_endpoints().iter().map(|u| WsStream::ws_url_from_rpc(u)).collect();
+            urls.dedup();
+            urls
+        } else {
+            config.ws_urls.clone()
+        }
+    }
+
+    pub fn with_max_failures(mut self, max_failures: u32) -> Self {
+        self.max_failures = max_failures.max(1);
+        self
+    }
+
+    pub fn with_reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+
+    pub fn urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    pub fn reconnect_delay(&self) -> Duration {
+        self.reconnect_delay
+    }
+
+    /// The URL to connect to next.
+    pub fn current(&self) -> String {
+        self.urls[self.state.lock().unwrap().0].clone()
+    }
+
+    /// The current URL delivered data — reset its failure count.
+    pub fn record_success(&self) {
+        self.state.lock().unwrap().1 = 0;
+    }
+
+    /// The current URL failed. Moves to the next URL after `max_failures`
+    /// in a row and returns it.
+    pub fn record_failure(&self) -> Option<String> {
+        let mut state = self.state.lock().unwrap();
+        state.1 += 1;
+        if state.1 < self.max_failures || self.urls.len() == 1 {
+            return None;
+        }
+        let failed = state.0;
+        *state = ((failed + 1) % self.urls.len(), 0);
+        warn!(
+            from = %self.urls[failed],
+            to = %self.urls[state.0],
+            failures = %self.max_failures,
+            "WebSocket endpoint failing — switching to next URL"
+        );
+        Some(self.urls[state.0].clone())
+    }
+}
+
+impl Clone for WsEndpoints {
+    /// A fresh failover state over the same URLs and settings.
+    fn clone(&self) -> Self {
+        Self::new(self.urls.clone())
+            .with_max_failures(self.max_failures)
+            .with_reconnect_delay(self.reconnect_delay)
+    }
+}
+
+/// Complete a WebSocket handshake with every URL, so a bad `SUI_WS_URL`
+/// fails startup instead of leaving the stream in its reconnect loop.
+pub async fn verify_handshakes(urls: &[String], timeout: Duration) -> Result<()> {
+    for url in urls {
+        let (mut ws, _) = tokio::time::timeout(timeout, connect_async(url.as_str()))
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out after {}ms", timeout.as_millis()))
+            .and_then(|r| r.map_err(anyhow::Error::from))
+            .with_context(|| format!("WebSocket handshake with {} failed", url))?;
+        let _ = ws.close(None).await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("ws://node{}:9000", i)).collect()
+    }
+
+    #[test]
+    fn test_stays_until_max_failures() {
+        let e = WsEndpoints::new(urls(2)).with_max_failures(3);
+        assert_eq!(e.record_failure(), None);
+        assert_eq!(e.record_failure(), None);
+        assert_eq!(e.record_failure().as_deref(), Some("ws://node1:9000"));
+        assert_eq!(e.current(), "ws://node1:9000");
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let e = WsEndpoints::new(urls(2)).with_max_failures(2);
+        e.record_failure();
+        e.record_success();
+        assert_eq!(e.record_failure(), None);
+        assert_eq!(e.current(), "ws://node0:9000");
+    }
+
+    #[test]
+    fn test_wraps_around() {
+        let e = WsEndpoints::new(urls(2)).with_max_failures(1);
+        e.record_failure();
+        assert_eq!(e.record_failure().as_deref(), Some("ws://node0:9000"));
+    }
+
+    #[test]
+    fn test_single_url_never_switches() {
+        let e = WsEndpoints::new(urls(1)).with_max_failures(1);
+        assert_eq!(e.record_failure(), None);
+        assert_eq!(e.current(), "ws://node0:9000");
+    }
+}
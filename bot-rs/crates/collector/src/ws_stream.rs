@@ -1,28 +1,56 @@
 use anyhow::{Context, Result};
+use arb_types::clock::unix_now_ms;
+use arb_types::log_throttle::DEFAULT_INTERVAL;
+use arb_types::pool::Dex;
+use arb_types::rpc::RpcClient;
+use arb_types::warn_throttled;
 use futures_util::{SinkExt, StreamExt};
 use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::{Instant, Interval};
 use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::Message;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info};
 
 use crate::parsers;
 use crate::pool_cache::PoolCache;
-use crate::rpc_poller::PoolMeta;
+use crate::pool_set::PoolSet;
+use crate::rpc_poller::{extract_third_type_param, object_key, PoolMeta};
+use crate::subscriptions::{SubscriptionBudget, SubscriptionSink};
+use crate::ws_endpoints::WsEndpoints;
+
+/// Per-request timeout for pool re-fetches.
+const REFETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A connection silent (no message, not even a Pong) for this long is dead.
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Minimum gap between event-triggered re-fetches of one pool.
+pub const DEFAULT_REFETCH_DEBOUNCE: Duration = Duration::from_millis(200);
 
 /// Streams real-time pool state updates via Sui WebSocket subscriptions.
 ///
 /// Uses `suix_subscribeEvent` to listen for swap events from monitored DEX
-/// packages. When events arrive, we re-fetch the affected pool object via
-/// RPC to get the latest state, then update the cache.
+/// packages. Swap events that report the post-swap pool state are applied to
+/// the cache directly; for the rest we re-fetch the affected pool object via
+/// RPC to get the latest state.
 ///
 /// This provides ~400ms latency (Sui finality) vs ~500ms+ with polling.
 pub struct WsStream {
-    ws_url: String,
-    rpc_url: String,
+    endpoints: WsEndpoints,
+    rpc: RpcClient,
     /// DEX package IDs to subscribe to swap events from
     dex_packages: Vec<DexPackage>,
-    /// Pool metadata indexed by object ID for quick lookup
-    pool_metas: Vec<PoolMeta>,
+    /// Monitored pools (shared, so a reload reaches the stream)
+    pools: PoolSet,
+    /// Reconnect after this long without a message; pinged at half of it
+    idle_timeout: Duration,
+    /// Bumped on every processed event
+    heartbeat: Option<Arc<AtomicU64>>,
+    debounce: RefetchDebounce,
 }
 
 /// A DEX package to subscribe to events from.
@@ -40,13 +68,57 @@ impl WsStream {
         pool_metas: Vec<PoolMeta>,
     ) -> Self {
         Self {
-            ws_url: ws_url.to_string(),
-            rpc_url: rpc_url.to_string(),
+            endpoints: WsEndpoints::new(vec![ws_url.to_string()]),
+            rpc: RpcClient::single(rpc_url).with_timeout(REFETCH_TIMEOUT),
             dex_packages,
-            pool_metas,
+            pools: PoolSet::new(pool_metas),
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            heartbeat: None,
+            debounce: RefetchDebounce::new(DEFAULT_REFETCH_DEBOUNCE),
         }
     }
 
+    /// Look events up in a shared pool list. Subscriptions are per package,
+    /// so pools added by a reload are covered without resubscribing.
+    pub fn with_pools(mut self, pools: PoolSet) -> Self {
+        self.pools = pools;
+        self
+    }
+
+    /// Re-fetch pools through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(REFETCH_TIMEOUT);
+        self
+    }
+
+    /// Connect through `endpoints` (with failover) instead of the single URL.
+    pub fn with_endpoints(mut self, endpoints: WsEndpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    /// Treat the connection as dead after `timeout` without any message.
+    /// The stream pings the node every `timeout / 2`, so a quiet but live
+    /// connection keeps answering with Pongs.
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Bump `heartbeat` on every processed event.
+    pub fn with_heartbeat(mut self, heartbeat: Arc<AtomicU64>) -> Self {
+        self.heartbeat = Some(heartbeat);
+        self
+    }
+
+    /// Re-fetch a pool at most once per `window`: events inside it are
+    /// folded into one trailing fetch when it ends. Zero fetches on every
+    /// event.
+    pub fn with_refetch_debounce(mut self, window: Duration) -> Self {
+        self.debounce = RefetchDebounce::new(window);
+        self
+    }
+
     /// Derive the WebSocket URL from an HTTP RPC URL.
     /// e.g., `https://fullnode.mainnet.sui.io:443` → `wss://fullnode.mainnet.sui.io:443`
     pub fn ws_url_from_rpc(rpc_url: &str) -> String {
@@ -59,21 +131,29 @@ impl WsStream {
     /// Automatically reconnects on disconnect.
     pub async fn run(&self, cache: PoolCache) -> Result<()> {
         info!(
-            ws_url = %self.ws_url,
+            ws_urls = ?self.endpoints.urls(),
             packages = %self.dex_packages.len(),
-            pools = %self.pool_metas.len(),
+            pools = %self.pools.len(),
             "Starting WebSocket event stream"
         );
 
         loop {
-            match self.connect_and_stream(&cache).await {
+            let ws_url = self.endpoints.current();
+            match self.connect_and_stream(&ws_url, &cache).await {
                 Ok(()) => {
                     info!("WebSocket stream ended normally");
                     break;
                 }
                 Err(e) => {
-                    error!(error = %e, "WebSocket stream error, reconnecting in 3s...");
-                    tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+                    let delay = self.endpoints.reconnect_delay();
+                    error!(
+                        ws_url = %ws_url,
+                        error = %e,
+                        "WebSocket stream error, reconnecting in {}ms...",
+                        delay.as_millis()
+                    );
+                    self.endpoints.record_failure();
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
@@ -82,8 +162,8 @@ impl WsStream {
     }
 
     /// Connect to the WebSocket and process events until disconnected.
-    async fn connect_and_stream(&self, cache: &PoolCache) -> Result<()> {
-        let (ws_stream, _response) = connect_async(&self.ws_url)
+    async fn connect_and_stream(&self, ws_url: &str, cache: &PoolCache) -> Result<()> {
+        let (ws_stream, _response) = connect_async(ws_url)
             .await
             .context("Failed to connect to WebSocket")?;
 
@@ -114,15 +194,25 @@ impl WsStream {
             );
         }
 
-        // Create HTTP client for re-fetching pool objects
-        let http_client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(5))
-            .build()?;
-
         // Process incoming events
         let mut event_count = 0u64;
+        let mut ping = ping_timer(self.idle_timeout);
+        let mut last_message = Instant::now();
+
+        loop {
+            let msg = tokio::select! {
+                msg = tokio::time::timeout_at(last_message + self.idle_timeout, read.next()) => match msg {
+                    Ok(msg) => msg,
+                    Err(_) => anyhow::bail!("No message in {}ms — connection presumed dead", self.idle_timeout.as_millis()),
+                },
+                _ = ping.tick() => {
+                    write.send(Message::Ping(Default::default())).await.context("Failed to send ping")?;
+                    continue;
+                }
+            };
+            let Some(msg) = msg else { break };
+            last_message = Instant::now();
 
-        while let Some(msg) = read.next().await {
             match msg {
                 Ok(Message::Text(text)) => {
                     let text_str: &str = &text;
@@ -131,6 +221,7 @@ impl WsStream {
                             // Check if it's a subscription confirmation
                             if value.get("result").is_some() && value.get("id").is_some() {
                                 debug!("Subscription confirmed");
+                                self.endpoints.record_success();
                                 continue;
                             }
 
@@ -138,18 +229,18 @@ impl WsStream {
                             if let Some(params) = value.get("params") {
                                 if let Some(result) = params.get("result") {
                                     event_count += 1;
-                                    self.handle_event(
-                                        result,
-                                        cache,
-                                        &http_client,
-                                        event_count,
-                                    )
-                                    .await;
+                                    self.handle_event(result, cache, event_count).await;
+                                    bump(&self.heartbeat);
                                 }
                             }
                         }
                         Err(e) => {
-                            warn!(error = %e, "Failed to parse WebSocket message");
+                            warn_throttled!(
+                                "ws_stream.parse_failed",
+                                DEFAULT_INTERVAL,
+                                error = %e,
+                                "Failed to parse WebSocket message"
+                            );
                         }
                     }
                 }
@@ -173,13 +264,13 @@ impl WsStream {
 
     /// Handle a single event from the WebSocket stream.
     ///
-    /// When a DEX event is received, we identify the affected pool
-    /// and re-fetch its state via RPC to update the cache.
+    /// When a DEX event is received, we identify the affected pool and update
+    /// it from the event's own post-swap fields if they're sufficient,
+    /// otherwise re-fetch its state via RPC.
     async fn handle_event(
         &self,
         event: &Value,
         cache: &PoolCache,
-        http_client: &reqwest::Client,
         event_count: u64,
     ) {
         // Extract the event type to identify which DEX and pool
@@ -210,14 +301,15 @@ impl WsStream {
             }
         };
 
-        // Find the pool metadata
-        let meta = match self.pool_metas.iter().find(|m| m.object_id == pool_id) {
-            Some(m) => m.clone(),
+        // Find the pool metadata (events carry the 64-hex form of the ID)
+        let meta = match self.pools.find(&pool_id) {
+            Some(m) => m,
             None => {
                 debug!(pool_id = %pool_id, "Event for unmonitored pool");
                 return;
             }
         };
+        let pool_id = meta.object_id.clone();
 
         debug!(
             pool = %pool_id,
@@ -227,43 +319,57 @@ impl WsStream {
             "Pool update event received"
         );
 
-        // Re-fetch the pool object to get latest state
-        match self
-            .fetch_pool_state(http_client, &meta)
-            .await
+        if let Some(flow) = event
+            .get("parsedJson")
+            .and_then(|parsed| parsers::parse_swap_flow(event_type, parsed, &meta.dex))
         {
-            Ok(state) => {
-                cache.upsert(pool_id, state);
-                debug!(
-                    pool = %meta.object_id,
-                    dex = %meta.dex,
-                    "Pool state updated from event"
-                );
-            }
-            Err(e) => {
-                warn!(
-                    pool = %meta.object_id,
-                    error = %e,
-                    "Failed to re-fetch pool after event"
-                );
-            }
+            cache.flow().record(&pool_id, flow, unix_now_ms());
         }
+
+        // Fast path: build the new state from the swap event itself when it
+        // carries the complete post-swap state (Cetus single-step, Turbos,
+        // FlowX CLMM). Skips the re-fetch round trip entirely.
+        let inline = event.get("parsedJson").and_then(|parsed| {
+            let prev = cache.get(&pool_id)?;
+            parsers::apply_swap_event(event_type, parsed, &prev, unix_now_ms())
+        });
+        if let Some(state) = inline {
+            cache.upsert(pool_id, state);
+            debug!(
+                pool = %meta.object_id,
+                dex = %meta.dex,
+                "Pool state updated inline from swap event"
+            );
+            return;
+        }
+
+        // Re-fetch the pool object to get latest state
+        self.debounce.refetch(&self.rpc, meta, cache).await;
     }
 
-    /// Try to extract the pool object ID from an event's parsed JSON.
+    /// Extract the pool object ID from an event's parsed JSON, using the
+    /// field the emitting DEX puts it in.
     fn extract_pool_id(&self, event: &Value) -> Option<String> {
-        let parsed = event.get("parsedJson")?;
-
-        // Different DEXes use different field names for pool ID
-        // Try common patterns
-        for field in &["pool", "pool_id", "poolId", "pool_address"] {
-            if let Some(id) = parsed.get(field).and_then(|v| v.as_str()) {
-                return Some(id.to_string());
-            }
-        }
+        let event_type = event.get("type")?.as_str()?;
+        let field = pool_id_field(self.dex_for_event_type(event_type)?)?;
+        event
+            .get("parsedJson")?
+            .get(field)?
+            .as_str()
+            .map(str::to_string)
+    }
 
-        // Some events put pool ID in the object change sender
-        None
+    /// The DEX that emitted an event of type `type_str`
+    /// (`<package>::<module>::<Name>`), by matching its package against the
+    /// subscribed packages. `None` for our own package and unknown ones.
+    fn dex_for_event_type(&self, type_str: &str) -> Option<Dex> {
+        let package = object_key(type_str.split("::").next()?);
+        self.dex_packages
+            .iter()
+            .find(|p| object_key(&p.package_id) == package)?
+            .dex_name
+            .parse()
+            .ok()
     }
 
     /// Try to match a pool from the event's package/module info.
@@ -286,85 +392,133 @@ impl WsStream {
         // Fallback: refresh all pools from this DEX
         None
     }
+}
 
-    /// Fetch a single pool's current state via RPC.
-    async fn fetch_pool_state(
-        &self,
-        client: &reqwest::Client,
-        meta: &PoolMeta,
-    ) -> Result<arb_types::pool::PoolState> {
-        let response = client
-            .post(&self.rpc_url)
-            .json(&json!({
-                "jsonrpc": "2.0",
-                "id": 1,
-                "method": "sui_getObject",
-                "params": [
-                    meta.object_id,
-                    {
-                        "showContent": true,
-                        "showType": true,
-                    }
-                ]
-            }))
-            .send()
-            .await
-            .context("RPC request failed")?;
-
-        let body: Value = response.json().await.context("Failed to parse RPC response")?;
-
-        if let Some(error) = body.get("error") {
-            anyhow::bail!("RPC error: {}", error);
-        }
-
-        let content = body
-            .get("result")
-            .and_then(|r| r.get("data"))
-            .and_then(|d| d.get("content"))
-            .context("Missing result.data.content in response")?;
+/// Fires every `idle_timeout / 2`, starting one period from now.
+fn ping_timer(idle_timeout: Duration) -> Interval {
+    let period = (idle_timeout / 2).max(Duration::from_millis(1));
+    tokio::time::interval_at(Instant::now() + period, period)
+}
 
-        let now_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
+fn bump(heartbeat: &Option<Arc<AtomicU64>>) {
+    if let Some(heartbeat) = heartbeat {
+        heartbeat.store(unix_now_ms(), Ordering::Relaxed);
+    }
+}
 
-        parsers::parse_pool_object(content, &meta.dex, meta, now_ms)
+/// The `parsedJson` field holding the pool ID in `dex`'s swap events.
+/// FlowX AMM events name only the coin types, not the pair object.
+fn pool_id_field(dex: Dex) -> Option<&'static str> {
+    match dex {
+        Dex::Cetus | Dex::Turbos => Some("pool"),
+        Dex::FlowxClmm | Dex::Kriya | Dex::Bluefin | Dex::Aftermath | Dex::DeepBook => Some("pool_id"),
+        Dex::FlowxAmm => None,
     }
 }
 
 /// Subscribe to transaction effects for specific object IDs.
 /// This is an alternative subscription mode that watches for any
 /// transaction that modifies a monitored pool object.
+///
+/// With a `SubscriptionBudget`, only the most active pools (up to the cap)
+/// are subscribed; the allocation is re-ranked every `rebalance_interval`
+/// and changed incrementally on the live connection.
 pub struct TxEffectStream {
-    ws_url: String,
-    rpc_url: String,
-    pool_metas: Vec<PoolMeta>,
+    endpoints: WsEndpoints,
+    rpc: RpcClient,
+    pools: PoolSet,
+    budget: SubscriptionBudget,
+    rebalance_interval: Duration,
+    idle_timeout: Duration,
+    heartbeat: Option<Arc<AtomicU64>>,
+    debounce: RefetchDebounce,
 }
 
 impl TxEffectStream {
     pub fn new(ws_url: &str, rpc_url: &str, pool_metas: Vec<PoolMeta>) -> Self {
         Self {
-            ws_url: ws_url.to_string(),
-            rpc_url: rpc_url.to_string(),
-            pool_metas,
+            endpoints: WsEndpoints::new(vec![ws_url.to_string()]),
+            rpc: RpcClient::single(rpc_url).with_timeout(REFETCH_TIMEOUT),
+            budget: SubscriptionBudget::new(pool_metas.len()),
+            pools: PoolSet::new(pool_metas),
+            rebalance_interval: Duration::from_secs(300),
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            heartbeat: None,
+            debounce: RefetchDebounce::new(DEFAULT_REFETCH_DEBOUNCE),
         }
     }
 
+    /// Limit per-pool subscriptions to `budget`, re-ranking every `interval`.
+    pub fn with_budget(mut self, budget: SubscriptionBudget, interval: Duration) -> Self {
+        self.budget = budget;
+        self.rebalance_interval = interval;
+        self
+    }
+
+    /// Re-fetch pools through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(REFETCH_TIMEOUT);
+        self
+    }
+
+    /// Connect through `endpoints` (with failover) instead of the single URL.
+    pub fn with_endpoints(mut self, endpoints: WsEndpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    /// Subscribe from a shared pool list: a change re-balances the
+    /// subscriptions on the live connection, so added pools can be
+    /// subscribed and removed ones are unsubscribed.
+    pub fn with_pools(mut self, pools: PoolSet) -> Self {
+        self.pools = pools;
+        self
+    }
+
+    /// Treat the connection as dead after `timeout` without any message,
+    /// pinging every `timeout / 2` (see [`WsStream::with_idle_timeout`]).
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Bump `heartbeat` on every processed transaction.
+    pub fn with_heartbeat(mut self, heartbeat: Arc<AtomicU64>) -> Self {
+        self.heartbeat = Some(heartbeat);
+        self
+    }
+
+    /// Re-fetch a pool at most once per `window` (see
+    /// [`WsStream::with_refetch_debounce`]).
+    pub fn with_refetch_debounce(mut self, window: Duration) -> Self {
+        self.debounce = RefetchDebounce::new(window);
+        self
+    }
+
     /// Run the transaction effect stream using `suix_subscribeTransaction`.
     /// Watches for transactions that modify any monitored pool object.
     pub async fn run(&self, cache: PoolCache) -> Result<()> {
         info!(
-            ws_url = %self.ws_url,
-            pools = %self.pool_metas.len(),
+            ws_urls = ?self.endpoints.urls(),
+            pools = %self.pools.len(),
+            max_subscriptions = %self.budget.cap(),
             "Starting transaction effect stream"
         );
 
         loop {
-            match self.connect_and_stream(&cache).await {
+            let ws_url = self.endpoints.current();
+            match self.connect_and_stream(&ws_url, &cache).await {
                 Ok(()) => break,
                 Err(e) => {
-                    error!(error = %e, "TX stream error, reconnecting in 3s...");
-                    tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+                    let delay = self.endpoints.reconnect_delay();
+                    error!(
+                        ws_url = %ws_url,
+                        error = %e,
+                        "TX stream error, reconnecting in {}ms...",
+                        delay.as_millis()
+                    );
+                    self.endpoints.record_failure();
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
@@ -372,8 +526,26 @@ impl TxEffectStream {
         Ok(())
     }
 
-    async fn connect_and_stream(&self, cache: &PoolCache) -> Result<()> {
-        let (ws_stream, _) = connect_async(&self.ws_url)
+    /// Re-rank pools by activity and move the budget's allocation to match.
+    fn rebalance(&self, cache: &PoolCache, subs: &mut WsSubscriptions) {
+        let pool_ids: Vec<String> = self.pools.snapshot().iter().map(|m| m.object_id.clone()).collect();
+        let incumbents: HashSet<String> = self.budget.allocation().into_iter().collect();
+        let ranked = cache.activity().rank(&pool_ids, &incumbents, unix_now_ms());
+        let diff = self.budget.rebalance(&ranked, subs);
+        if !diff.is_empty() {
+            info!(
+                subscribed = %diff.subscribe.len(),
+                unsubscribed = %diff.unsubscribe.len(),
+                allocated = %self.budget.allocation().len(),
+                cap = %self.budget.cap(),
+                "Re-balanced pool subscriptions"
+            );
+            debug!(subscribe = ?diff.subscribe, unsubscribe = ?diff.unsubscribe, "Subscription changes");
+        }
+    }
+
+    async fn connect_and_stream(&self, ws_url: &str, cache: &PoolCache) -> Result<()> {
+        let (ws_stream, _) = connect_async(ws_url)
             .await
             .context("Failed to connect to WebSocket")?;
 
@@ -381,53 +553,76 @@ impl TxEffectStream {
 
         let (mut write, mut read) = ws_stream.split();
 
-        // Subscribe to transactions that change any monitored pool
-        // Use ChangedObject filter with pool IDs
-        let pool_ids: Vec<&str> = self
-            .pool_metas
-            .iter()
-            .map(|m| m.object_id.as_str())
-            .collect();
-
-        // Sui supports `TransactionFilter::ChangedObject` filter
-        // We subscribe once per pool for precise filtering
-        for (i, pool_id) in pool_ids.iter().enumerate() {
-            let subscribe_msg = json!({
-                "jsonrpc": "2.0",
-                "id": i + 1,
-                "method": "suix_subscribeTransaction",
-                "params": [{
-                    "ChangedObject": pool_id
-                }]
-            });
-
-            write
-                .send(Message::Text(subscribe_msg.to_string().into()))
-                .await
-                .context("Failed to send subscribe message")?;
+        // Subscriptions die with the connection — start from an empty allocation
+        self.budget.reset();
+        let mut subs = WsSubscriptions::default();
 
-            debug!(pool = %pool_id, "Subscribed to object changes");
-        }
-
-        let http_client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(5))
-            .build()?;
+        // Sui supports `TransactionFilter::ChangedObject` filter.
+        // We subscribe once per pool for precise filtering; the interval's
+        // first tick fires immediately and makes the initial allocation.
+        let mut rebalance = tokio::time::interval(self.rebalance_interval);
+        let mut pool_changes = self.pools.watch();
+        let mut ping = ping_timer(self.idle_timeout);
+        let mut last_message = Instant::now();
 
         // Process incoming transaction notifications
-        while let Some(msg) = read.next().await {
+        loop {
+            let msg = tokio::select! {
+                msg = tokio::time::timeout_at(last_message + self.idle_timeout, read.next()) => match msg {
+                    Ok(msg) => msg,
+                    Err(_) => anyhow::bail!("No message in {}ms — connection presumed dead", self.idle_timeout.as_millis()),
+                },
+                _ = ping.tick() => {
+                    write.send(Message::Ping(Default::default())).await.context("Failed to send ping")?;
+                    continue;
+                }
+                _ = rebalance.tick() => {
+                    self.rebalance(cache, &mut subs);
+                    for request in subs.outbox.drain(..) {
+                        write
+                            .send(Message::Text(request.to_string().into()))
+                            .await
+                            .context("Failed to send subscription request")?;
+                    }
+                    continue;
+                }
+                Ok(()) = pool_changes.changed() => {
+                    info!(pools = %self.pools.len(), "Pool list changed — re-balancing subscriptions");
+                    self.rebalance(cache, &mut subs);
+                    for request in subs.outbox.drain(..) {
+                        write
+                            .send(Message::Text(request.to_string().into()))
+                            .await
+                            .context("Failed to send subscription request")?;
+                    }
+                    continue;
+                }
+            };
+            let Some(msg) = msg else { break };
+            last_message = Instant::now();
+
             match msg {
                 Ok(Message::Text(text)) => {
                     let text_str: &str = &text;
                     if let Ok(value) = serde_json::from_str::<Value>(text_str) {
-                        // Skip subscription confirmations
-                        if value.get("result").is_some() && value.get("id").is_some() {
+                        // Subscription confirmations and unsubscribe replies
+                        if value.get("id").is_some() {
+                            self.endpoints.record_success();
+                            subs.on_response(&value);
+                            for request in subs.outbox.drain(..) {
+                                write
+                                    .send(Message::Text(request.to_string().into()))
+                                    .await
+                                    .context("Failed to send subscription request")?;
+                            }
                             continue;
                         }
 
                         // Handle transaction notification
                         if let Some(params) = value.get("params") {
                             if let Some(result) = params.get("result") {
-                                self.handle_tx_effect(result, cache, &http_client).await;
+                                self.handle_tx_effect(result, cache).await;
+                                bump(&self.heartbeat);
                             }
                         }
                     }
@@ -453,7 +648,6 @@ impl TxEffectStream {
         &self,
         tx_result: &Value,
         cache: &PoolCache,
-        http_client: &reqwest::Client,
     ) {
         // Extract the digest for logging
         let digest = tx_result
@@ -465,7 +659,7 @@ impl TxEffectStream {
         let changed_ids = self.extract_changed_objects(tx_result);
 
         for pool_id in changed_ids {
-            if let Some(meta) = self.pool_metas.iter().find(|m| m.object_id == pool_id) {
+            if let Some(meta) = self.pools.find(&pool_id) {
                 debug!(
                     pool = %pool_id,
                     dex = %meta.dex,
@@ -474,14 +668,7 @@ impl TxEffectStream {
                 );
 
                 // Re-fetch pool state
-                match fetch_pool(http_client, &self.rpc_url, meta).await {
-                    Ok(state) => {
-                        cache.upsert(pool_id, state);
-                    }
-                    Err(e) => {
-                        warn!(pool = %meta.object_id, error = %e, "Failed to re-fetch pool");
-                    }
-                }
+                self.debounce.refetch(&self.rpc, meta, cache).await;
             }
         }
     }
@@ -489,6 +676,7 @@ impl TxEffectStream {
     /// Extract changed object IDs from transaction effects.
     fn extract_changed_objects(&self, tx_result: &Value) -> Vec<String> {
         let mut ids = Vec::new();
+        let pools = self.pools.snapshot();
 
         // Check effects.mutated and effects.created
         if let Some(effects) = tx_result.get("effects") {
@@ -502,7 +690,7 @@ impl TxEffectStream {
                             .and_then(|id| id.as_str())
                         {
                             // Only include if it's a monitored pool
-                            if self.pool_metas.iter().any(|m| m.object_id == id) {
+                            if pools.iter().any(|m| m.object_id == id) {
                                 ids.push(id.to_string());
                             }
                         }
@@ -515,46 +703,350 @@ impl TxEffectStream {
     }
 }
 
-/// Fetch a single pool's current state via RPC (shared helper).
-async fn fetch_pool(
-    client: &reqwest::Client,
-    rpc_url: &str,
-    meta: &PoolMeta,
-) -> Result<arb_types::pool::PoolState> {
-    let response = client
-        .post(rpc_url)
-        .json(&json!({
+/// What to do about an event-triggered re-fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Refetch {
+    /// Fetch now
+    Now,
+    /// Fetch once this much later — the pool was fetched inside the window
+    After(u64),
+    /// A trailing fetch is already scheduled
+    Skip,
+}
+
+/// Per-pool debounce for event-triggered re-fetches.
+///
+/// A busy pool emits several events a second, but the strategy loop reads
+/// the cache far less often. The first event in a window fetches; later ones
+/// mark the pool pending, and one trailing fetch at the end of the window
+/// picks up everything they changed.
+///
+/// Cheap to clone; clones share the per-pool state.
+#[derive(Debug, Clone)]
+struct RefetchDebounce {
+    window_ms: u64,
+    /// Pool ID → (last fetch started, trailing fetch scheduled)
+    pools: Arc<Mutex<HashMap<String, (u64, bool)>>>,
+}
+
+impl RefetchDebounce {
+    fn new(window: Duration) -> Self {
+        Self {
+            window_ms: window.as_millis() as u64,
+            pools: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Decide on a re-fetch of `pool_id` for an event at `now_ms`.
+    fn admit(&self, pool_id: &str, now_ms: u64) -> Refetch {
+        let mut pools = self.pools.lock().unwrap_or_else(|e| e.into_inner());
+        match pools.get_mut(pool_id) {
+            Some((_, true)) => Refetch::Skip,
+            Some((last, pending)) if now_ms < *last + self.window_ms => {
+                *pending = true;
+                Refetch::After(*last + self.window_ms - now_ms)
+            }
+            _ => {
+                pools.insert(pool_id.to_string(), (now_ms, false));
+                Refetch::Now
+            }
+        }
+    }
+
+    /// Record the trailing fetch of `pool_id` starting at `now_ms`.
+    fn fire(&self, pool_id: &str, now_ms: u64) {
+        let mut pools = self.pools.lock().unwrap_or_else(|e| e.into_inner());
+        pools.insert(pool_id.to_string(), (now_ms, false));
+    }
+
+    /// Re-fetch `meta` into `cache` now, after the window, or not at all.
+    async fn refetch(&self, rpc: &RpcClient, meta: PoolMeta, cache: &PoolCache) {
+        match self.admit(&meta.object_id, unix_now_ms()) {
+            Refetch::Now => refetch_into(rpc, &meta, cache).await,
+            Refetch::After(delay_ms) => {
+                let (debounce, rpc, cache) = (self.clone(), rpc.clone(), cache.clone());
+                tokio::spawn(async move {
+                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                    debounce.fire(&meta.object_id, unix_now_ms());
+                    refetch_into(&rpc, &meta, &cache).await;
+                });
+            }
+            Refetch::Skip => {}
+        }
+    }
+}
+
+/// Re-fetch one pool and store it in `cache`.
+async fn refetch_into(rpc: &RpcClient, meta: &PoolMeta, cache: &PoolCache) {
+    match fetch_pool(rpc, meta).await {
+        Ok(state) => {
+            cache.upsert(meta.object_id.clone(), state);
+            debug!(pool = %meta.object_id, dex = %meta.dex, "Pool state re-fetched after event");
+        }
+        Err(e) => {
+            warn_throttled!(
+                format!("ws_stream.refetch_failed:{}", meta.object_id),
+                DEFAULT_INTERVAL,
+                pool = %meta.object_id,
+                error = %e,
+                "Failed to re-fetch pool after event"
+            );
+        }
+    }
+}
+
+/// Per-pool `suix_subscribeTransaction` bookkeeping for one connection.
+///
+/// Requests are queued in `outbox` for the caller to send. Unsubscribing
+/// needs the subscription ID from the confirmation, so a pool dropped while
+/// its subscribe is still in flight is unsubscribed when it's confirmed.
+#[derive(Debug, Default)]
+struct WsSubscriptions {
+    next_id: u64,
+    /// Subscribe request ID → pool ID, awaiting confirmation
+    pending: HashMap<u64, String>,
+    /// Pool ID → subscription ID
+    active: HashMap<String, u64>,
+    /// Pools dropped before their subscribe was confirmed
+    cancelled: HashSet<String>,
+    outbox: Vec<Value>,
+}
+
+impl WsSubscriptions {
+    fn request(&mut self, method: &str, params: Value) -> u64 {
+        self.next_id += 1;
+        self.outbox.push(json!({
             "jsonrpc": "2.0",
-            "id": 1,
-            "method": "sui_getObject",
-            "params": [
-                meta.object_id,
-                {
-                    "showContent": true,
-                    "showType": true,
-                }
-            ]
-        }))
-        .send()
+            "id": self.next_id,
+            "method": method,
+            "params": params,
+        }));
+        self.next_id
+    }
+
+    /// Handle a reply to one of our requests.
+    fn on_response(&mut self, value: &Value) {
+        let Some(pool_id) = value
+            .get("id")
+            .and_then(|id| id.as_u64())
+            .and_then(|id| self.pending.remove(&id))
+        else {
+            return;
+        };
+
+        match value.get("result").and_then(|r| r.as_u64()) {
+            Some(sub_id) if self.cancelled.remove(&pool_id) => {
+                self.request("suix_unsubscribeTransaction", json!([sub_id]));
+            }
+            Some(sub_id) => {
+                debug!(pool = %pool_id, subscription = %sub_id, "Subscribed to object changes");
+                self.active.insert(pool_id, sub_id);
+            }
+            None => {
+                self.cancelled.remove(&pool_id);
+                warn_throttled!(
+                    format!("ws_stream.subscribe_failed:{}", pool_id),
+                    DEFAULT_INTERVAL,
+                    pool = %pool_id,
+                    error = %value.get("error").cloned().unwrap_or_default(),
+                    "Pool subscription rejected — covered by polling"
+                );
+            }
+        }
+    }
+}
+
+impl SubscriptionSink for WsSubscriptions {
+    fn subscribe(&mut self, pool_id: &str) -> Result<()> {
+        self.cancelled.remove(pool_id);
+        let id = self.request("suix_subscribeTransaction", json!([{ "ChangedObject": pool_id }]));
+        self.pending.insert(id, pool_id.to_string());
+        Ok(())
+    }
+
+    fn unsubscribe(&mut self, pool_id: &str) -> Result<()> {
+        match self.active.remove(pool_id) {
+            Some(sub_id) => {
+                self.request("suix_unsubscribeTransaction", json!([sub_id]));
+            }
+            None => {
+                self.cancelled.insert(pool_id.to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fetch a single pool's current state via RPC (shared helper).
+async fn fetch_pool(rpc: &RpcClient, meta: &PoolMeta) -> Result<arb_types::pool::PoolState> {
+    let body = rpc
+        .call("sui_getObject", json!([meta.object_id, { "showContent": true, "showType": true }]))
         .await
         .context("RPC request failed")?;
 
-    let body: Value = response.json().await?;
-
     if let Some(error) = body.get("error") {
         anyhow::bail!("RPC error: {}", error);
     }
 
-    let content = body
-        .get("result")
-        .and_then(|r| r.get("data"))
-        .and_then(|d| d.get("content"))
-        .context("Missing content")?;
+    let data = body.get("result").and_then(|r| r.get("data")).context("Missing data")?;
+    let content = data.get("content").context("Missing content")?;
+
+    let mut state = parsers::parse_pool_object(content, &meta.dex, meta, unix_now_ms())?;
+    // Turbos calls need the fee type, which only the object type carries
+    if meta.dex.eq_ignore_ascii_case("turbos") {
+        state.fee_type = data.get("type").and_then(Value::as_str).and_then(extract_third_type_param);
+    }
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ws_subscriptions_lifecycle() {
+        let mut subs = WsSubscriptions::default();
+        subs.subscribe("0xa").unwrap();
+        subs.subscribe("0xb").unwrap();
+        assert_eq!(subs.outbox.len(), 2);
+        assert_eq!(subs.outbox[0]["params"][0]["ChangedObject"], "0xa");
+        subs.outbox.clear();
+
+        // 0xa confirmed, then dropped → unsubscribed by subscription ID
+        subs.on_response(&json!({ "jsonrpc": "2.0", "id": 1, "result": 77 }));
+        subs.unsubscribe("0xa").unwrap();
+        assert_eq!(subs.outbox[0]["method"], "suix_unsubscribeTransaction");
+        assert_eq!(subs.outbox[0]["params"], json!([77]));
+        subs.outbox.clear();
+
+        // 0xb dropped while in flight → unsubscribed once confirmed
+        subs.unsubscribe("0xb").unwrap();
+        assert!(subs.outbox.is_empty());
+        subs.on_response(&json!({ "jsonrpc": "2.0", "id": 2, "result": 78 }));
+        assert_eq!(subs.outbox[0]["params"], json!([78]));
+        assert!(subs.active.is_empty());
+    }
+
+    #[test]
+    fn test_rapid_events_fold_into_one_trailing_refetch() {
+        let debounce = RefetchDebounce::new(Duration::from_millis(200));
+        // 10 events for one pool, 10ms apart
+        let decisions: Vec<Refetch> = (0..10).map(|i| debounce.admit("0xa", 1_000 + i * 10)).collect();
+        assert_eq!(decisions[0], Refetch::Now);
+        assert_eq!(decisions[1], Refetch::After(190), "trailing fetch at the end of the window");
+        assert!(decisions[2..].iter().all(|d| *d == Refetch::Skip));
+        let fetches = decisions.iter().filter(|d| **d != Refetch::Skip).count();
+        assert_eq!(fetches, 2);
+
+        // Other pools aren't held back
+        assert_eq!(debounce.admit("0xb", 1_050), Refetch::Now);
+
+        // Once the trailing fetch has run, the next event inside its window
+        // schedules another; one after the window fetches straight away
+        debounce.fire("0xa", 1_200);
+        assert_eq!(debounce.admit("0xa", 1_250), Refetch::After(150));
+        debounce.fire("0xa", 1_400);
+        assert_eq!(debounce.admit("0xa", 1_700), Refetch::Now);
+    }
+
+    #[test]
+    fn test_zero_window_fetches_every_event() {
+        let debounce = RefetchDebounce::new(Duration::ZERO);
+        assert!((0..10).all(|_| debounce.admit("0xa", 1_000) == Refetch::Now));
+    }
+
+    const CETUS: &str = "0x1eabed72c53feb3805120a081dc15963c204dc8d091542592abaf7a35689b2fb";
+    const TURBOS: &str = "0x91bfbc386a41afcfd9b2533058d7e915a1d3829089cc268ff4333d54d6339ca1";
+    const FLOWX: &str = "0x25929e7f29e0a30eb4e692952ba1b5b65a3a4d65ab5f2a32e1ba3edcb587f26d";
+    const AFTERMATH: &str = "0xefe170ec0be4d762196bedecd7a065816576198a6527c99282a2551aaa7da38c";
+    const DEEPBOOK: &str = "0x2c8d603bc51326b8c13cef9dd07031a408a48dddb541963357661df5d3204809";
+    const POOL: &str = "0x0000000000000000000000000000000000000000000000000000000000000abc";
+
+    fn stream() -> WsStream {
+        let dexes = [("cetus", CETUS), ("turbos", TURBOS), ("flowx", FLOWX), ("aftermath", AFTERMATH), ("deepbook", DEEPBOOK)];
+        let packages = dexes
+            .into_iter()
+            .map(|(dex, pkg)| DexPackage { package_id: pkg.to_string(), dex_name: dex.to_string() })
+            .chain([DexPackage { package_id: "0xa4b".to_string(), dex_name: "arbmove".to_string() }])
+            .collect();
+        WsStream::new("ws://localhost:9001", "http://localhost:9000", packages, Vec::new())
+    }
 
-    let now_ms = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64;
+    fn event(event_type: String, parsed: Value) -> Value {
+        json!({ "type": event_type, "parsedJson": parsed })
+    }
 
-    parsers::parse_pool_object(content, &meta.dex, meta, now_ms)
+    #[test]
+    fn test_dex_for_event_type() {
+        let ws = stream();
+        assert_eq!(ws.dex_for_event_type(&format!("{}::pool::SwapEvent", CETUS)), Some(Dex::Cetus));
+        // Leading zeros don't matter
+        let padded = DEEPBOOK.replace("0x", "0x000");
+        assert_eq!(ws.dex_for_event_type(&format!("{}::pool::OrderFilled", padded)), Some(Dex::DeepBook));
+        assert_eq!(ws.dex_for_event_type("0x0000000000000a4b::events::ArbExecuted"), None);
+        assert_eq!(ws.dex_for_event_type("0x123::pool::SwapEvent"), None);
+    }
+
+    #[test]
+    fn test_extract_pool_id_per_dex() {
+        let ws = stream();
+        let cases = [
+            event(
+                format!("{}::pool::SwapEvent", CETUS),
+                json!({
+                    "atob": true, "pool": POOL, "partner": "0x639b5e433da31739e800cd085f356e64cae222966d0f1b11bd9dc76b322ff58b",
+                    "amount_in": "1000000000", "amount_out": "3512093", "ref_amount": "0", "fee_amount": "2500000",
+                    "vault_a_amount": "812345678901", "vault_b_amount": "2851234567",
+                    "before_sqrt_price": "1093260521802436543", "after_sqrt_price": "1093258811284410720", "steps": "1"
+                }),
+            ),
+            event(
+                format!("{}::pool::SwapEvent", TURBOS),
+                json!({
+                    "pool": POOL, "recipient": "0x8e2b1f0c9d9b4c3b8f5d7f6e1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d",
+                    "amount_a": "1000000000", "amount_b": "3512093", "liquidity": "45678901234",
+                    "tick_current_index": { "bits": 4294912345u64 }, "tick_pre_index": { "bits": 4294912350u64 },
+                    "sqrt_price": "1093258811284410720", "protocol_fee": "500000", "fee_amount": "2500000",
+                    "a_to_b": true, "is_exact_in": true
+                }),
+            ),
+            event(
+                format!("{}::pool::Swap", FLOWX),
+                json!({
+                    "sender": "0x8e2b1f0c9d9b4c3b8f5d7f6e1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d", "pool_id": POOL,
+                    "x_for_y": true, "amount_x": "1000000000", "amount_y": "3512093",
+                    "sqrt_price_before": "1093260521802436543", "sqrt_price_after": "1093258811284410720",
+                    "liquidity": "45678901234", "tick_index": { "bits": 4294912345u64 }, "fee": "2500000"
+                }),
+            ),
+            event(
+                format!("{}::events::SwapEventV2", AFTERMATH),
+                json!({
+                    "pool_id": POOL, "issuer": "0x8e2b1f0c9d9b4c3b8f5d7f6e1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d",
+                    "referrer": null, "types_in": ["0000000000000000000000000000000000000000000000000000000000000002::sui::SUI"],
+                    "amounts_in": ["1000000000"], "types_out": ["dba34672e30cb065b1f93e3ab55318768fd6fef66c15942c9f7cb846e2f900e7::usdc::USDC"],
+                    "amounts_out": ["3512093"], "reserves": ["812345678901", "2851234567"]
+                }),
+            ),
+            event(
+                format!("{}::order_info::OrderFilled", DEEPBOOK),
+                json!({
+                    "pool_id": POOL, "maker_order_id": "68160737799100866923792791", "taker_order_id": "170141183460469231731687303715884105727",
+                    "maker_client_order_id": "0", "taker_client_order_id": "0", "price": "3512093", "taker_is_bid": false,
+                    "taker_fee": "1000", "taker_fee_is_deep": true, "maker_fee": "0", "maker_fee_is_deep": true,
+                    "base_quantity": "1000000000", "quote_quantity": "3512093",
+                    "maker_balance_manager_id": "0x344c2734b1d211bd15212bfb7847c66a3b18803f3f5ab00f5ff6f87b6fe6d27d",
+                    "taker_balance_manager_id": "0x5b5b8d2f1f2c8c5e4e5f3f6a7b8c9d0e1f2a3b4c5d6e7f8091a2b3c4d5e6f708",
+                    "timestamp": "1718000000000"
+                }),
+            ),
+        ];
+        for e in &cases {
+            assert_eq!(ws.extract_pool_id(e).as_deref(), Some(POOL), "{}", e["type"]);
+        }
+
+        // Our own package's events carry no pool
+        let arb = event("0xa4b::events::ArbExecuted".to_string(), json!({ "pool": POOL, "profit": "1" }));
+        assert_eq!(ws.extract_pool_id(&arb), None);
+    }
 }
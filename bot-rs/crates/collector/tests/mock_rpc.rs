@@ -0,0 +1,460 @@
+//! Seed the pool cache from a mock JSON-RPC server that replays the synthetic
+//! fixtures, exercising the real request/unwrap path (multi-get, DeepBook
+//! Versioned dynamic field, Turbos fee type from the object type string,
+//! chunking under the node's per-call object limit, failover past an
+//! unavailable endpoint, pools file reloads, the poller's heartbeat, its
+//! rate-limit backoff and its pacing).
+
+use arb_collector::fixtures::{self, PoolFixture};
+use arb_collector::{rpc_poller, PollBackoff, PollPacing, PoolCache, PoolSet, PoolWatcher, RpcPoller};
+use arb_types::config::{Config, PoolConfig, DEFAULT_MULTI_GET_CHUNK_SIZE};
+use arb_types::rpc::RpcClient;
+use arb_types::test_support::{self, Reply};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Per-call object limit enforced by the mock, like a real fullnode's.
+const MAX_MULTI_GET: usize = 2;
+
+/// Answer one JSON-RPC request body from the fixtures, rejecting
+/// multi-gets of more than `max_multi_get` objects.
+fn respond(fixtures: &[PoolFixture], request: &Value, max_multi_get: usize) -> Value {
+    let params = &request["params"];
+    let result = match request["method"].as_str() {
+        Some("sui_multiGetObjects") if params[0].as_array().map_or(0, Vec::len) > max_multi_get => {
+            return json!({
+                "jsonrpc": "2.0", "id": 1,
+                "error": { "code": -32602, "message": format!("Too many objects (max {})", max_multi_get) }
+            });
+        }
+        // Reversed to check results are matched by objectId, not position
+        Some("sui_multiGetObjects") => Value::Array(
+            params[0]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .rev()
+                .map(|id| {
+                    fixtures
+                        .iter()
+                        .find(|f| Some(f.pool_id.as_str()) == id.as_str())
+                        .map(|f| f.object.clone())
+                        .unwrap_or_else(|| json!({ "error": { "code": "notExists", "object_id": id } }))
+                })
+                .collect(),
+        ),
+        Some("suix_getDynamicFieldObject") => fixtures
+            .iter()
+            .find(|f| {
+                f.object["data"]["content"]["fields"]["inner"]["fields"]["id"]["id"] == params[0]
+            })
+            .and_then(|f| f.dynamic_field.clone())
+            .unwrap_or_else(|| json!({ "error": { "code": "dynamicFieldNotFound" } })),
+        _ => return json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32601 } }),
+    };
+    json!({ "jsonrpc": "2.0", "id": 1, "result": result })
+}
+
+/// Mock node serving the fixtures.
+async fn spawn_mock_rpc(fixtures: Vec<PoolFixture>) -> String {
+    spawn_logged_mock_rpc(fixtures, MAX_MULTI_GET).await.0
+}
+
+/// [`spawn_mock_rpc`] with a `max_multi_get` object limit, also returning
+/// the object count of each `sui_multiGetObjects` request it received.
+async fn spawn_logged_mock_rpc(
+    fixtures: Vec<PoolFixture>,
+    max_multi_get: usize,
+) -> (String, Arc<Mutex<Vec<usize>>>) {
+    let multi_gets = Arc::new(Mutex::new(Vec::new()));
+    let log = multi_gets.clone();
+    let url = test_support::spawn_mock_rpc(move |request| {
+        if request["method"] == "sui_multiGetObjects" {
+            log.lock().unwrap().push(request["params"][0].as_array().map_or(0, Vec::len));
+        }
+        Reply::Json(respond(&fixtures, request, max_multi_get))
+    })
+    .await;
+    (url, multi_gets)
+}
+
+/// Mock node that answers its first `throttled` requests with HTTP 429,
+/// then serves the fixtures. Returns its URL and a count of requests.
+async fn spawn_throttling_mock_rpc(fixtures: Vec<PoolFixture>, throttled: usize) -> (String, Arc<AtomicUsize>) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    let url = test_support::spawn_mock_rpc(move |request| {
+        if counter.fetch_add(1, Ordering::SeqCst) < throttled {
+            Reply::status(429)
+        } else {
+            Reply::Json(respond(&fixtures, request, DEFAULT_MULTI_GET_CHUNK_SIZE))
+        }
+    })
+    .await;
+    (url, hits)
+}
+
+/// Server that answers every request with HTTP 503. Returns its URL and a
+/// count of requests it received.
+async fn spawn_unavailable() -> (String, Arc<AtomicUsize>) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    let url = test_support::spawn_mock_rpc(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Reply::status(503)
+    })
+    .await;
+    (url, hits)
+}
+
+fn config(rpc_url: String, pools: Vec<PoolConfig>) -> Config {
+    Config { multi_get_chunk_size: MAX_MULTI_GET, ..Config::for_tests(rpc_url, pools) }
+}
+
+#[tokio::test]
+async fn test_seed_cache_from_fixtures() {
+    let fixtures = fixtures::load_for_tests().unwrap();
+    let pools = fixtures.iter().map(PoolFixture::pool_config).collect();
+    let rpc_url = spawn_mock_rpc(fixtures.clone()).await;
+
+    let cache = PoolCache::new();
+    rpc_poller::seed_cache(&config(rpc_url, pools), &cache).await.unwrap();
+    assert_eq!(cache.len(), fixtures.len());
+
+    for f in &fixtures {
+        let seeded = cache.get(&f.pool_id).unwrap_or_else(|| panic!("{} not seeded", f.pool_id));
+        let expected = f.parse(seeded.last_updated_ms).unwrap();
+        assert_eq!(
+            serde_json::to_string(&seeded).unwrap(),
+            serde_json::to_string(&expected).unwrap(),
+            "{} {}",
+            f.dex,
+            f.pool_id
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_seed_cache_unwraps_deepbook_and_turbos_fee_type() {
+    let fixtures = fixtures::load_for_tests().unwrap();
+    let pools = fixtures.iter().map(PoolFixture::pool_config).collect();
+    let rpc_url = spawn_mock_rpc(fixtures.clone()).await;
+
+    let cache = PoolCache::new();
+    rpc_poller::seed_cache(&config(rpc_url, pools), &cache).await.unwrap();
+
+    let deepbook = fixtures.iter().find(|f| f.dex == "deepbook").unwrap();
+    assert!(deepbook.dynamic_field.is_some(), "fixture should be a Versioned pool");
+    let state = cache.get(&deepbook.pool_id).unwrap();
+    assert!(state.reserve_a.is_some() && state.reserve_b.is_some());
+
+    let turbos = fixtures.iter().find(|f| f.dex == "turbos").unwrap();
+    let fee_type = cache.get(&turbos.pool_id).unwrap().fee_type.unwrap();
+    assert!(turbos.object_type().unwrap().ends_with(&format!("{}>", fee_type)));
+}
+
+#[tokio::test]
+async fn test_flipped_pool_never_reaches_the_cache() {
+    let fixtures = fixtures::load_dex_for_tests("cetus").unwrap();
+    let mut pool = fixtures[0].pool_config();
+    std::mem::swap(&mut pool.coin_type_a, &mut pool.coin_type_b);
+    let rpc_url = spawn_mock_rpc(fixtures.clone()).await;
+
+    let cache = PoolCache::new();
+    rpc_poller::seed_cache(&config(rpc_url.clone(), vec![pool.clone()]), &cache).await.unwrap();
+    assert!(cache.get(&pool.pool_id).is_none());
+    assert!(cache.is_quarantined(&pool.pool_id));
+
+    // Polling quarantines it without ever upserting the inverted state
+    let mut meta = fixtures[0].meta();
+    std::mem::swap(&mut meta.coin_type_a, &mut meta.coin_type_b);
+    let every = Duration::from_millis(20);
+    let poller = RpcPoller::for_pools(&rpc_url, every, vec![meta]);
+    let (polled, heartbeat) = (PoolCache::new(), Arc::new(AtomicU64::new(0)));
+    let (c, hb) = (polled.clone(), heartbeat.clone());
+    let task = tokio::spawn(async move { poller.run(c, hb).await });
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !polled.is_quarantined(&pool.pool_id) {
+            tokio::time::sleep(every).await;
+        }
+    })
+    .await
+    .expect("pool should be quarantined");
+    tokio::time::sleep(every * 3).await;
+    task.abort();
+    assert!(polled.get(&pool.pool_id).is_none());
+    assert_eq!(heartbeat.load(Ordering::Relaxed), 0, "nothing was updated");
+}
+
+#[tokio::test]
+async fn test_seed_cache_fails_when_unchunked_request_exceeds_node_limit() {
+    let fixtures = fixtures::load_for_tests().unwrap();
+    assert!(fixtures.len() > MAX_MULTI_GET, "need more fixtures than the per-call limit");
+    let pools = fixtures.iter().map(PoolFixture::pool_config).collect();
+    let rpc_url = spawn_mock_rpc(fixtures.clone()).await;
+
+    // One call with every pool is rejected outright — nothing gets seeded
+    let mut unchunked = config(rpc_url, pools);
+    unchunked.multi_get_chunk_size = fixtures.len();
+    let cache = PoolCache::new();
+    assert!(rpc_poller::seed_cache(&unchunked, &cache).await.is_err());
+    assert!(cache.is_empty());
+}
+
+#[tokio::test]
+async fn test_multi_get_chunks_large_pool_sets() {
+    let fixtures = fixtures::load_for_tests().unwrap();
+    // A real fullnode's limit
+    let (rpc_url, multi_gets) = spawn_logged_mock_rpc(fixtures.clone(), 50).await;
+
+    // 120 pools: the fixture pools spread among made-up IDs the node
+    // reports as missing
+    let missing: Vec<String> = (0..120 - fixtures.len()).map(|i| format!("0x{:x}", 0xf000 + i)).collect();
+    let mut ids = missing.clone();
+    for (i, f) in fixtures.iter().enumerate() {
+        ids.insert(i * 110 / fixtures.len() + 5, f.pool_id.clone());
+    }
+    let id_refs: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+    let rpc = RpcClient::single(&rpc_url);
+    let objects = rpc_poller::multi_get_objects(&rpc, &id_refs, DEFAULT_MULTI_GET_CHUNK_SIZE).await.unwrap();
+
+    let mut sizes = multi_gets.lock().unwrap().clone();
+    sizes.sort_unstable();
+    assert_eq!(sizes, [20, 50, 50]);
+    assert_eq!(objects.len(), 120);
+
+    // Every pool gets its own object back, whichever chunk carried it
+    for f in &fixtures {
+        let obj = &objects[&rpc_poller::object_key(&f.pool_id)];
+        assert_eq!(obj, &f.object, "{}", f.pool_id);
+    }
+    for id in &missing {
+        let obj = &objects[&rpc_poller::object_key(id)];
+        assert_eq!(obj["error"]["object_id"], id.as_str());
+    }
+}
+
+#[tokio::test]
+async fn test_seed_cache_fails_over_past_unavailable_endpoint() {
+    let fixtures = fixtures::load_for_tests().unwrap();
+    let pools = fixtures.iter().map(PoolFixture::pool_config).collect();
+    let (down, hits) = spawn_unavailable().await;
+    let up = spawn_mock_rpc(fixtures.clone()).await;
+
+    let mut failover = config(down.clone(), pools);
+    failover.rpc_urls = vec![down, up];
+    let cache = PoolCache::new();
+    rpc_poller::seed_cache(&failover, &cache).await.unwrap();
+    assert_eq!(cache.len(), fixtures.len());
+    assert!(hits.load(Ordering::SeqCst) > 0, "the first endpoint should have been tried");
+}
+
+#[tokio::test]
+async fn test_failing_endpoint_backs_off() {
+    let (down, hits) = spawn_unavailable().await;
+    let up = spawn_mock_rpc(Vec::new()).await;
+    let rpc = RpcClient::new(&[down.clone(), up.clone()]).unwrap();
+    assert_eq!(rpc.preferred_url(), down);
+
+    // The 503 fails over within the call: the reply is the second node's
+    let reply = rpc.call("sui_multiGetObjects", json!([[], {}])).await.unwrap();
+    assert!(reply["result"].is_array(), "{reply}");
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+    // Backing off: later calls go straight to the healthy endpoint, and
+    // clones share that health
+    assert_eq!(rpc.preferred_url(), up);
+    rpc.clone().call("sui_multiGetObjects", json!([[], {}])).await.unwrap();
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+    // A JSON-RPC error is the call's problem, not the endpoint's
+    let reply = rpc.call("no_such_method", json!([])).await.unwrap();
+    assert!(reply.get("error").is_some());
+    assert_eq!(rpc.preferred_url(), up);
+}
+
+#[tokio::test]
+async fn test_poller_heartbeat_advances_only_on_successful_fetches() {
+    let fixtures = fixtures::load_for_tests().unwrap();
+    let metas: Vec<_> = fixtures.iter().map(PoolFixture::meta).collect();
+    let every = Duration::from_millis(20);
+
+    // Dead node: the poller keeps cycling, but never claims to be alive
+    let (down, hits) = spawn_unavailable().await;
+    let heartbeat = Arc::new(AtomicU64::new(0));
+    let poller = RpcPoller::for_pools(&down, every, metas.clone());
+    let (cache, hb) = (PoolCache::new(), heartbeat.clone());
+    let task = tokio::spawn(async move { poller.run(cache, hb).await });
+    tokio::time::sleep(every * 5).await;
+    task.abort();
+    assert!(hits.load(Ordering::SeqCst) > 1, "the poller should keep retrying");
+    assert_eq!(heartbeat.load(Ordering::Relaxed), 0);
+
+    // Live node: the first successful cycle bumps it
+    let (up, _) = spawn_logged_mock_rpc(fixtures.clone(), DEFAULT_MULTI_GET_CHUNK_SIZE).await;
+    let poller = RpcPoller::for_pools(&up, every, metas);
+    let (cache, hb) = (PoolCache::new(), heartbeat.clone());
+    let polled = cache.clone();
+    let task = tokio::spawn(async move { poller.run(cache, hb).await });
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while heartbeat.load(Ordering::Relaxed) == 0 {
+            tokio::time::sleep(every).await;
+        }
+    })
+    .await
+    .expect("heartbeat should advance");
+    task.abort();
+    assert_eq!(polled.len(), fixtures.len());
+}
+
+#[tokio::test]
+async fn test_poller_backs_off_on_429_and_recovers() {
+    let fixtures = fixtures::load_for_tests().unwrap();
+    let metas: Vec<_> = fixtures.iter().map(PoolFixture::meta).collect();
+    let every = Duration::from_millis(20);
+
+    let (url, hits) = spawn_throttling_mock_rpc(fixtures.clone(), 3).await;
+    let backoff = PollBackoff::default();
+    let poller = RpcPoller::for_pools(&url, every, metas).with_backoff(backoff.clone());
+    let heartbeat = Arc::new(AtomicU64::new(0));
+    let (cache, hb) = (PoolCache::new(), heartbeat.clone());
+    let task = tokio::spawn(async move { poller.run(cache, hb).await });
+
+    // 20ms, then 40ms, then 80ms apart: not a request every tick
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(backoff.is_backing_off());
+    assert!(hits.load(Ordering::SeqCst) <= 3, "{} requests while rate-limited", hits.load(Ordering::SeqCst));
+
+    // Once the node answers again, polling resumes and the interval
+    // works its way back down
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while heartbeat.load(Ordering::Relaxed) == 0 || backoff.is_backing_off() {
+            tokio::time::sleep(every).await;
+        }
+    })
+    .await
+    .expect("poller should recover");
+    task.abort();
+    assert_eq!(backoff.rate_limited_total(), 3);
+    assert_eq!(backoff.multiplier(), 1);
+}
+
+#[tokio::test]
+async fn test_poller_slows_down_on_unchanged_pools() {
+    let fixtures = fixtures::load_for_tests().unwrap();
+    let metas: Vec<_> = fixtures.iter().map(PoolFixture::meta).collect();
+    let every = Duration::from_millis(20);
+
+    // The fixtures never change: after the first cycle fills the cache,
+    // every poll finds the same states
+    let (url, _) = spawn_logged_mock_rpc(fixtures, DEFAULT_MULTI_GET_CHUNK_SIZE).await;
+    let pacing = PollPacing::new(every, Duration::from_millis(10), Duration::from_millis(100));
+    let poller = RpcPoller::for_pools(&url, every, metas).with_pacing(pacing.clone());
+    assert_eq!(poller.current_interval_ms(), 20);
+    let task = tokio::spawn(async move { poller.run(PoolCache::new(), Arc::new(AtomicU64::new(0))).await });
+
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while pacing.current_interval_ms() < 100 {
+            tokio::time::sleep(every).await;
+        }
+    })
+    .await
+    .expect("poller should back off to the ceiling");
+    task.abort();
+}
+
+/// `[[pools]]` entries for a pools file.
+fn pools_toml(fixtures: &[&PoolFixture]) -> String {
+    fixtures
+        .iter()
+        .map(|f| {
+            format!(
+                "[[pools]]\ndex = \"{}\"\npool_id = \"{}\"\ncoin_type_a = \"{}\"\ncoin_type_b = \"{}\"\n\n",
+                f.dex, f.pool_id, f.coin_type_a, f.coin_type_b
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_pools_file_reload_seeds_and_removes() {
+    let fixtures = fixtures::load_for_tests().unwrap();
+    let (first, second) = (&fixtures[0], &fixtures[1]);
+    let rpc_url = spawn_mock_rpc(fixtures.clone()).await;
+
+    let dir = std::env::temp_dir().join(format!("arb-pool-watcher-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("pools.toml");
+    std::fs::write(&path, pools_toml(&[first])).unwrap();
+
+    let mut cfg = config(rpc_url, vec![first.pool_config()]);
+    cfg.pools_file = path.to_str().unwrap().to_string();
+    let cache = PoolCache::new();
+    let pools = PoolSet::from_config(&cfg);
+    let watcher = PoolWatcher::new(&cfg, pools.clone());
+
+    // Unchanged file: nothing to do
+    assert!(watcher.reload(&cache).await.unwrap().is_empty());
+
+    // Swap the first pool for the second
+    std::fs::write(&path, pools_toml(&[second])).unwrap();
+    cache.upsert(first.pool_id.clone(), first.parse(0).unwrap());
+    let diff = watcher.reload(&cache).await.unwrap();
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.removed.len(), 1);
+    assert!(cache.get(&first.pool_id).is_none());
+    assert!(cache.get(&second.pool_id).is_some(), "added pool was not seeded");
+    assert_eq!(pools.len(), 1);
+    assert!(pools.find(&second.pool_id).is_some());
+
+    // A broken file is rejected and the current pools stay
+    std::fs::write(&path, "[[pools]]\ndex = \"cetus\"\n").unwrap();
+    assert!(watcher.reload(&cache).await.is_err());
+    assert!(pools.find(&second.pool_id).is_some());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn test_pools_file_reload_keeps_removed_pool_until_published() {
+    let fixtures = fixtures::load_for_tests().unwrap();
+    let (first, second) = (fixtures[0].clone(), fixtures[1].clone());
+    // A slow node, so the reload can be observed mid-seed
+    let served = fixtures.clone();
+    let rpc_url = test_support::spawn_mock_rpc(move |request| {
+        let reply = Reply::Json(respond(&served, request, DEFAULT_MULTI_GET_CHUNK_SIZE));
+        Reply::After(Duration::from_millis(300), Box::new(reply))
+    })
+    .await;
+
+    let dir = std::env::temp_dir().join(format!("arb-pool-watcher-order-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("pools.toml");
+    std::fs::write(&path, pools_toml(&[&second])).unwrap();
+
+    let mut cfg = config(rpc_url, vec![first.pool_config()]);
+    cfg.pools_file = path.to_str().unwrap().to_string();
+    let cache = PoolCache::new();
+    cache.upsert(first.pool_id.clone(), first.parse(0).unwrap());
+    let pools = PoolSet::from_config(&cfg);
+    let watcher = Arc::new(PoolWatcher::new(&cfg, pools.clone()));
+
+    let reload = {
+        let (watcher, cache) = (watcher.clone(), cache.clone());
+        tokio::spawn(async move { watcher.reload(&cache).await })
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    // Still listed, so still cached
+    assert!(pools.find(&first.pool_id).is_some());
+    assert!(cache.get(&first.pool_id).is_some(), "removed pool left the cache before the new list was published");
+
+    reload.await.unwrap().unwrap();
+    assert!(pools.find(&first.pool_id).is_none());
+    assert!(cache.get(&first.pool_id).is_none());
+    assert!(cache.get(&second.pool_id).is_some());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
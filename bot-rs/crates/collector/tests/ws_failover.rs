@@ -0,0 +1,234 @@
+//! WebSocket endpoint resolution, the startup handshake check, stream
+//! failover between mock servers (one that breaks every handshake, one that
+//! goes silent after it, and one that accepts subscriptions), and idle
+//! detection.
+
+use arb_collector::rpc_poller::PoolMeta;
+use arb_collector::ws_endpoints::{self, WsEndpoints};
+use arb_collector::{DexPackage, PoolCache, TxEffectStream, WsStream};
+use arb_types::config::Config;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Accepts TCP connections and drops them before the handshake. Returns its
+/// URL and a count of connection attempts.
+async fn spawn_broken_ws() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let counter = attempts.clone();
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            counter.fetch_add(1, Ordering::SeqCst);
+            drop(socket);
+        }
+    });
+    (format!("ws://{}", addr), attempts)
+}
+
+/// Completes the handshake, then never reads or writes again — not even a
+/// Pong. Returns its URL and a count of connections.
+async fn spawn_silent_ws() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = connections.clone();
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            counter.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let Ok(ws) = tokio_tungstenite::accept_async(socket).await else { return };
+                std::future::pending::<()>().await;
+                drop(ws);
+            });
+        }
+    });
+    (format!("ws://{}", addr), connections)
+}
+
+/// Confirms every subscription request, forwards its method name, and
+/// follows up with an (empty) notification.
+async fn spawn_ws() -> (String, mpsc::UnboundedReceiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let Ok(mut ws) = tokio_tungstenite::accept_async(socket).await else { return };
+                while let Some(Ok(msg)) = ws.next().await {
+                    let Message::Text(text) = msg else { continue };
+                    let request: Value = serde_json::from_str(&text).unwrap();
+                    let _ = tx.send(request["method"].as_str().unwrap_or_default().to_string());
+                    let reply = json!({ "jsonrpc": "2.0", "id": request["id"], "result": 7 });
+                    ws.send(Message::Text(reply.to_string().into())).await.unwrap();
+                    let notification = json!({ "jsonrpc": "2.0", "params": { "subscription": 7, "result": {} } });
+                    ws.send(Message::Text(notification.to_string().into())).await.unwrap();
+                }
+            });
+        }
+    });
+    (format!("ws://{}", addr), rx)
+}
+
+fn config(rpc_url: &str, ws_urls: Vec<String>) -> Config {
+    Config { ws_urls, ..Config::for_tests(rpc_url, Vec::new()) }
+}
+
+fn failover(urls: Vec<String>) -> WsEndpoints {
+    WsEndpoints::new(urls)
+        .with_max_failures(2)
+        .with_reconnect_delay(Duration::from_millis(20))
+}
+
+#[test]
+fn test_ws_url_override_takes_precedence() {
+    let derived = config("https://rpc.provider.example:443/v1/key", Vec::new());
+    assert_eq!(WsEndpoints::resolve(&derived), ["wss://rpc.provider.example:443/v1/key"]);
+
+    let urls = vec!["ws://10.0.0.5:9001/ws".to_string(), "wss://backup.example".to_string()];
+    let overridden = config("https://rpc.provider.example:443/v1/key", urls.clone());
+    assert_eq!(WsEndpoints::resolve(&overridden), urls);
+    assert_eq!(WsEndpoints::from_config(&overridden).current(), urls[0]);
+}
+
+#[test]
+fn test_ws_urls_derived_per_rpc_endpoint() {
+    let mut cfg = config("https://primary.example", Vec::new());
+    cfg.rpc_urls = vec!["https://primary.example".to_string(), "http://10.0.0.5:9000".to_string()];
+    assert_eq!(WsEndpoints::resolve(&cfg), ["wss://primary.example", "ws://10.0.0.5:9000"]);
+}
+
+#[tokio::test]
+async fn test_startup_handshake_validation() {
+    let (good, _rx) = spawn_ws().await;
+    let (broken, _) = spawn_broken_ws().await;
+    let timeout = Duration::from_secs(2);
+
+    ws_endpoints::verify_handshakes(std::slice::from_ref(&good), timeout).await.unwrap();
+
+    let err = ws_endpoints::verify_handshakes(&[good, broken.clone()], timeout)
+        .await
+        .unwrap_err();
+    assert!(format!("{:#}", err).contains(&broken), "{:#}", err);
+
+    let err = ws_endpoints::verify_handshakes(&["ws://127.0.0.1:1".to_string()], timeout)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("ws://127.0.0.1:1"));
+}
+
+#[tokio::test]
+async fn test_event_stream_fails_over_to_next_url() {
+    let (broken, attempts) = spawn_broken_ws().await;
+    let (good, mut subscriptions) = spawn_ws().await;
+
+    let packages = vec![DexPackage { package_id: "0x1eab".into(), dex_name: "cetus".into() }];
+    let stream = WsStream::new(&broken, "http://127.0.0.1:1", packages, Vec::new())
+        .with_endpoints(failover(vec![broken, good]));
+    tokio::spawn(async move { stream.run(PoolCache::new()).await });
+
+    let method = tokio::time::timeout(Duration::from_secs(5), subscriptions.recv())
+        .await
+        .expect("stream never reached the second URL")
+        .unwrap();
+    assert_eq!(method, "suix_subscribeEvent");
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_tx_stream_fails_over_to_next_url() {
+    let (broken, attempts) = spawn_broken_ws().await;
+    let (good, mut subscriptions) = spawn_ws().await;
+
+    let metas = vec![PoolMeta {
+        object_id: "0xp00l".into(),
+        dex: "cetus".into(),
+        coin_type_a: "0x2::sui::SUI".into(),
+        coin_type_b: "0xdba3::usdc::USDC".into(),
+    }];
+    let stream = TxEffectStream::new(&broken, "http://127.0.0.1:1", metas)
+        .with_endpoints(failover(vec![broken, good]));
+    tokio::spawn(async move { stream.run(PoolCache::new()).await });
+
+    let method = tokio::time::timeout(Duration::from_secs(5), subscriptions.recv())
+        .await
+        .expect("stream never reached the second URL")
+        .unwrap();
+    assert_eq!(method, "suix_subscribeTransaction");
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_event_stream_reconnects_when_server_goes_silent() {
+    let (silent, connections) = spawn_silent_ws().await;
+    let (good, mut subscriptions) = spawn_ws().await;
+
+    let packages = vec![DexPackage { package_id: "0x1eab".into(), dex_name: "cetus".into() }];
+    let stream = WsStream::new(&silent, "http://127.0.0.1:1", packages, Vec::new())
+        .with_endpoints(failover(vec![silent, good]))
+        .with_idle_timeout(Duration::from_millis(200));
+    tokio::spawn(async move { stream.run(PoolCache::new()).await });
+
+    let method = tokio::time::timeout(Duration::from_secs(5), subscriptions.recv())
+        .await
+        .expect("stream never gave up on the silent server")
+        .unwrap();
+    assert_eq!(method, "suix_subscribeEvent");
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_tx_stream_reconnects_when_server_goes_silent() {
+    let (silent, connections) = spawn_silent_ws().await;
+    let (good, mut subscriptions) = spawn_ws().await;
+
+    let metas = vec![PoolMeta {
+        object_id: "0xp00l".into(),
+        dex: "cetus".into(),
+        coin_type_a: "0x2::sui::SUI".into(),
+        coin_type_b: "0xdba3::usdc::USDC".into(),
+    }];
+    let stream = TxEffectStream::new(&silent, "http://127.0.0.1:1", metas)
+        .with_endpoints(failover(vec![silent, good]))
+        .with_idle_timeout(Duration::from_millis(200));
+    tokio::spawn(async move { stream.run(PoolCache::new()).await });
+
+    let method = tokio::time::timeout(Duration::from_secs(5), subscriptions.recv())
+        .await
+        .expect("stream never gave up on the silent server")
+        .unwrap();
+    assert_eq!(method, "suix_subscribeTransaction");
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_quiet_stream_kept_alive_by_pongs_and_bumps_heartbeat() {
+    let (good, mut subscriptions) = spawn_ws().await;
+    let heartbeat = Arc::new(AtomicU64::new(0));
+
+    let packages = vec![DexPackage { package_id: "0x1eab".into(), dex_name: "cetus".into() }];
+    let stream = WsStream::new(&good, "http://127.0.0.1:1", packages, Vec::new())
+        .with_idle_timeout(Duration::from_millis(200))
+        .with_heartbeat(heartbeat.clone());
+    tokio::spawn(async move { stream.run(PoolCache::new()).await });
+
+    let method = tokio::time::timeout(Duration::from_secs(5), subscriptions.recv())
+        .await
+        .expect("stream never subscribed")
+        .unwrap();
+    assert_eq!(method, "suix_subscribeEvent");
+
+    // Several idle windows pass with only Pongs coming back: no reconnect
+    // (which would resubscribe), and the one event bumped the heartbeat
+    tokio::time::sleep(Duration::from_millis(1_000)).await;
+    assert!(subscriptions.try_recv().is_err(), "stream reconnected a live connection");
+    assert!(heartbeat.load(Ordering::Relaxed) > 0);
+}
@@ -1,16 +1,23 @@
 use anyhow::{Context, Result};
-use reqwest::Client;
+use arb_types::rpc::RpcClient;
 use serde_json::{json, Value};
+use std::time::Duration;
 use tracing::{debug, error, info};
 
+use crate::tx_verify::{BuiltTx, ExpectedTx};
+
+/// Per-request RPC timeout.
+const RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Periodically merges fragmented `Coin<SUI>` objects to prevent
 /// hitting Sui's per-transaction object limits.
 ///
 /// After many trades, gas rebates and profit transfers create numerous
-/// small coin objects. This merger consolidates them via `unsafe_payAllSui`.
+/// small coin objects. This merger consolidates them via `unsafe_payAllSui`,
+/// or — when `target_coin_count > 1` — via `unsafe_paySui` into N coins of
+/// roughly equal size, so several transactions can each have their own gas coin.
 pub struct CoinMerger {
-    client: Client,
-    rpc_url: String,
+    rpc: RpcClient,
     owner_address: String,
     /// Merge when coin count exceeds this threshold.
     merge_threshold: usize,
@@ -20,33 +27,44 @@ pub struct CoinMerger {
     check_interval_cycles: u64,
     /// Gas budget for merge transaction (MIST).
     merge_gas_budget: u64,
+    /// Number of gas coins to leave after merging (1 = consolidate everything).
+    target_coin_count: usize,
 }
 
 impl CoinMerger {
     pub fn new(rpc_url: &str, owner_address: &str) -> Self {
         Self {
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(10))
-                .build()
-                .expect("Failed to create HTTP client"),
-            rpc_url: rpc_url.to_string(),
+            rpc: RpcClient::single(rpc_url).with_timeout(RPC_TIMEOUT),
             owner_address: owner_address.to_string(),
             merge_threshold: 20,
             cycle_count: 0,
             check_interval_cycles: 100, // ~50s at 500ms tick
             merge_gas_budget: 10_000_000, // 0.01 SUI
+            target_coin_count: 1,
         }
     }
 
-    /// Call this every strategy cycle. Returns `Some(tx_bytes_base64)` when
-    /// a merge is needed, or `None` if no action required.
+    /// Query through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(RPC_TIMEOUT);
+        self
+    }
+
+    /// Merge down to `n` roughly equal gas coins instead of one (min 1).
+    pub fn with_target_coin_count(mut self, n: usize) -> Self {
+        self.target_coin_count = n.max(1);
+        self
+    }
+
+    /// Call this every strategy cycle. Returns `Some(tx)` when a merge is
+    /// needed, or `None` if no action required.
     ///
     /// The caller is responsible for signing and submitting the returned tx.
-    pub async fn maybe_merge(&mut self) -> Result<Option<String>> {
+    pub async fn maybe_merge(&mut self) -> Result<Option<BuiltTx>> {
         self.cycle_count += 1;
 
         // Only check periodically to avoid spamming RPC
-        if self.cycle_count % self.check_interval_cycles != 0 {
+        if !self.cycle_count.is_multiple_of(self.check_interval_cycles) {
             return Ok(None);
         }
 
@@ -54,7 +72,9 @@ impl CoinMerger {
         let coins = self.fetch_sui_coins().await?;
         let coin_count = coins.len();
 
-        if coin_count <= self.merge_threshold {
+        // Too fragmented, or (in multi-coin mode) too few coins to go around
+        let under_target = self.target_coin_count > 1 && coin_count < self.target_coin_count;
+        if coin_count <= self.merge_threshold && !under_target {
             debug!(
                 coin_count = %coin_count,
                 threshold = %self.merge_threshold,
@@ -66,7 +86,8 @@ impl CoinMerger {
         info!(
             coin_count = %coin_count,
             threshold = %self.merge_threshold,
-            "Too many Coin<SUI> objects — merging"
+            target = %self.target_coin_count,
+            "Reshaping Coin<SUI> objects"
         );
 
         // Collect all coin object IDs
@@ -80,9 +101,44 @@ impl CoinMerger {
             return Ok(None);
         }
 
-        // Build merge transaction via unsafe_payAllSui
-        match self.build_merge_tx(&coin_ids).await {
-            Ok(tx_bytes) => Ok(Some(tx_bytes)),
+        let total_balance: u64 = coins
+            .iter()
+            .filter_map(|c| c.get("balance").and_then(|b| b.as_str()))
+            .filter_map(|b| b.parse::<u64>().ok())
+            .sum();
+
+        // Multi-coin target → unsafe_paySui with split outputs; otherwise payAllSui
+        let result = match split_amounts(total_balance, self.merge_gas_budget, self.target_coin_count)
+        {
+            Some(amounts) => self.build_split_tx(&coin_ids, &amounts).await,
+            // Merging the few coins there are reshapes nothing: only
+            // consolidate when there are too many
+            None if coin_count <= self.merge_threshold => {
+                debug!(
+                    total_balance = %total_balance,
+                    target = %self.target_coin_count,
+                    "Balance too small to split — leaving coins as they are"
+                );
+                return Ok(None);
+            }
+            None => {
+                if self.target_coin_count > 1 {
+                    debug!(
+                        total_balance = %total_balance,
+                        target = %self.target_coin_count,
+                        "Balance too small to split — consolidating into one coin"
+                    );
+                }
+                self.build_merge_tx(&coin_ids).await
+            }
+        };
+
+        match result {
+            Ok(tx_bytes) => Ok(Some(BuiltTx {
+                tx_bytes,
+                expected: ExpectedTx::native(&self.owner_address, self.merge_gas_budget),
+                gas_coin: None,
+            })),
             Err(e) => {
                 error!(error = %e, "Failed to build merge transaction");
                 Err(e)
@@ -112,24 +168,12 @@ impl CoinMerger {
                 ])
             };
 
-            let response = self
-                .client
-                .post(&self.rpc_url)
-                .json(&json!({
-                    "jsonrpc": "2.0",
-                    "id": 1,
-                    "method": "suix_getCoins",
-                    "params": params
-                }))
-                .send()
+            let body = self
+                .rpc
+                .call("suix_getCoins", params)
                 .await
                 .context("suix_getCoins request failed")?;
 
-            let body: Value = response
-                .json()
-                .await
-                .context("Failed to parse getCoins response")?;
-
             if let Some(error) = body.get("error") {
                 anyhow::bail!("suix_getCoins error: {}", error);
             }
@@ -162,29 +206,17 @@ impl CoinMerger {
     /// Build a merge transaction using unsafe_payAllSui.
     /// Returns base64-encoded tx_bytes ready for signing.
     async fn build_merge_tx(&self, coin_ids: &[String]) -> Result<String> {
-        let response = self
-            .client
-            .post(&self.rpc_url)
-            .json(&json!({
-                "jsonrpc": "2.0",
-                "id": 1,
-                "method": "unsafe_payAllSui",
-                "params": [
-                    self.owner_address,       // signer
-                    coin_ids,                 // input_coins (all SUI coins)
-                    self.owner_address,       // recipient (self — just merging)
-                    self.merge_gas_budget     // gas_budget
-                ]
-            }))
-            .send()
+        let body = self
+            .rpc
+            .call("unsafe_payAllSui", json!([
+                self.owner_address,       // signer
+                coin_ids,                 // input_coins (all SUI coins)
+                self.owner_address,       // recipient (self — just merging)
+                self.merge_gas_budget     // gas_budget
+            ]))
             .await
             .context("unsafe_payAllSui request failed")?;
 
-        let body: Value = response
-            .json()
-            .await
-            .context("Failed to parse payAllSui response")?;
-
         if let Some(error) = body.get("error") {
             anyhow::bail!("unsafe_payAllSui error: {}", error);
         }
@@ -198,11 +230,62 @@ impl CoinMerger {
 
         Ok(tx_bytes)
     }
+
+    /// Build a reshape transaction using unsafe_paySui: merge all `coin_ids`
+    /// and split off one self-owned coin per entry in `amounts`.
+    /// Returns base64-encoded tx_bytes ready for signing.
+    async fn build_split_tx(&self, coin_ids: &[String], amounts: &[u64]) -> Result<String> {
+        let recipients = vec![self.owner_address.as_str(); amounts.len()];
+        let amounts: Vec<String> = amounts.iter().map(|a| a.to_string()).collect();
+
+        let body = self
+            .rpc
+            .call("unsafe_paySui", json!([
+                self.owner_address,       // signer
+                coin_ids,                 // input_coins (first is also gas)
+                recipients,               // recipients (self — one per split)
+                amounts,                  // amounts per recipient
+                self.merge_gas_budget     // gas_budget
+            ]))
+            .await
+            .context("unsafe_paySui request failed")?;
+
+        if let Some(error) = body.get("error") {
+            anyhow::bail!("unsafe_paySui error: {}", error);
+        }
+
+        let tx_bytes = body
+            .get("result")
+            .and_then(|r| r.get("txBytes"))
+            .and_then(|b| b.as_str())
+            .context("Missing txBytes in paySui response")?
+            .to_string();
+
+        Ok(tx_bytes)
+    }
+}
+
+/// Amounts for the `n - 1` split outputs of a paySui reshape.
+///
+/// The merged remainder coin becomes the n-th coin, so each output gets an
+/// equal share of the balance after reserving `gas_reserve` for the merge tx.
+/// Returns `None` for `n <= 1` or when a share would be smaller than the
+/// reserve (coins that small can't back a transaction anyway).
+fn split_amounts(total_balance: u64, gas_reserve: u64, n: usize) -> Option<Vec<u64>> {
+    if n <= 1 {
+        return None;
+    }
+    let per_coin = total_balance.saturating_sub(gas_reserve) / n as u64;
+    if per_coin < gas_reserve.max(1) {
+        return None;
+    }
+    Some(vec![per_coin; n - 1])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arb_types::test_support::{spawn_mock_rpc, Reply};
 
     #[test]
     fn test_new_defaults() {
@@ -211,6 +294,35 @@ mod tests {
         assert_eq!(merger.check_interval_cycles, 100);
         assert_eq!(merger.merge_gas_budget, 10_000_000);
         assert_eq!(merger.cycle_count, 0);
+        assert_eq!(merger.target_coin_count, 1);
+    }
+
+    #[test]
+    fn test_target_coin_count_min_one() {
+        let merger = CoinMerger::new("http://localhost:9000", "0xabc").with_target_coin_count(0);
+        assert_eq!(merger.target_coin_count, 1);
+    }
+
+    #[test]
+    fn test_split_amounts_single_target_uses_pay_all() {
+        assert_eq!(split_amounts(10_000_000_000, 10_000_000, 1), None);
+    }
+
+    #[test]
+    fn test_split_amounts_equal_shares() {
+        // 4 SUI, 0.01 SUI reserve, 4 coins → 3 split outputs + remainder
+        let amounts = split_amounts(4_000_000_000, 10_000_000, 4).unwrap();
+        assert_eq!(amounts.len(), 3);
+        assert!(amounts.iter().all(|&a| a == 997_500_000));
+        // Remainder coin keeps at least one share plus the gas reserve
+        let remainder = 4_000_000_000 - amounts.iter().sum::<u64>();
+        assert!(remainder >= 997_500_000 + 10_000_000);
+    }
+
+    #[test]
+    fn test_split_amounts_too_small_falls_back() {
+        // 0.03 SUI across 4 coins → shares below the gas reserve
+        assert_eq!(split_amounts(30_000_000, 10_000_000, 4), None);
     }
 
     #[tokio::test]
@@ -238,6 +350,22 @@ mod tests {
         assert_eq!(merger.cycle_count, 100);
     }
 
+    #[tokio::test]
+    async fn test_under_target_without_balance_to_split_does_nothing() {
+        // 2 coins worth 0.03 SUI, 4 wanted: too little to split, too few to merge
+        let url = spawn_mock_rpc(|request| {
+            assert_eq!(request["method"], "suix_getCoins", "nothing should be built");
+            Reply::Json(json!({ "jsonrpc": "2.0", "id": 1, "result": { "data": [
+                { "coinObjectId": "0x1", "balance": "15000000" },
+                { "coinObjectId": "0x2", "balance": "15000000" },
+            ], "hasNextPage": false } }))
+        })
+        .await;
+        let mut merger = CoinMerger::new(&url, "0xabc").with_target_coin_count(4);
+        merger.cycle_count = 99;
+        assert!(merger.maybe_merge().await.unwrap().is_none());
+    }
+
     #[test]
     fn test_cycle_interval_logic() {
         let merger = CoinMerger::new("http://localhost:9000", "0xabc");
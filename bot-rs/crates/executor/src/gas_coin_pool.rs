@@ -0,0 +1,332 @@
+use anyhow::{Context, Result};
+use arb_types::rpc::RpcClient;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, info};
+
+use crate::tx_verify::{BuiltTx, ExpectedTx};
+
+/// Per-request RPC timeout.
+const RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Coins fetched per refresh.
+const COIN_PAGE: u64 = 50;
+
+/// A `Coin<SUI>` usable as a transaction's gas payment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasCoin {
+    pub object_id: String,
+    pub balance: u64,
+}
+
+/// Independent gas coins handed out one per transaction, so transactions
+/// in flight at the same time never contend for the same gas object.
+///
+/// [`acquire`](Self::acquire) leases the largest free coin; the lease
+/// returns it to the pool when dropped (or passed to
+/// [`release`](Self::release)). When the wallet holds fewer than
+/// `target_count` usable coins, [`maybe_split`](Self::maybe_split) builds a
+/// transaction splitting the largest one.
+///
+/// Cheap to clone; clones share the coins and leases.
+#[derive(Debug, Clone)]
+pub struct GasCoinPool {
+    rpc: RpcClient,
+    owner_address: String,
+    /// Coins to keep available
+    target_count: usize,
+    /// Coins below this can't cover a transaction and are left out (MIST)
+    min_coin_balance: u64,
+    /// Gas budget for the split transaction (MIST)
+    split_gas_budget: u64,
+    state: Arc<Mutex<Coins>>,
+}
+
+#[derive(Debug, Default)]
+struct Coins {
+    coins: Vec<GasCoin>,
+    leased: HashSet<String>,
+}
+
+/// A gas coin leased from a [`GasCoinPool`], returned to it on drop.
+#[derive(Debug)]
+pub struct GasCoinLease {
+    coin: GasCoin,
+    state: Arc<Mutex<Coins>>,
+}
+
+impl Deref for GasCoinLease {
+    type Target = GasCoin;
+
+    fn deref(&self) -> &GasCoin {
+        &self.coin
+    }
+}
+
+impl Drop for GasCoinLease {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.leased.remove(&self.coin.object_id);
+    }
+}
+
+impl GasCoinPool {
+    pub fn new(rpc_url: &str, owner_address: &str, target_count: usize, min_coin_balance: u64) -> Self {
+        Self {
+            rpc: RpcClient::single(rpc_url).with_timeout(RPC_TIMEOUT),
+            owner_address: owner_address.to_string(),
+            target_count: target_count.max(1),
+            min_coin_balance,
+            split_gas_budget: 10_000_000, // 0.01 SUI
+            state: Arc::new(Mutex::new(Coins::default())),
+        }
+    }
+
+    /// Query through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(RPC_TIMEOUT);
+        self
+    }
+
+    /// Coins the pool aims to keep available.
+    pub fn target_count(&self) -> usize {
+        self.target_count
+    }
+
+    /// Lease the largest free coin, or `None` if every coin is in use.
+    pub fn acquire(&self) -> Option<GasCoinLease> {
+        let mut state = self.lock();
+        let coin = state
+            .coins
+            .iter()
+            .filter(|c| !state.leased.contains(&c.object_id))
+            .max_by_key(|c| c.balance)?
+            .clone();
+        state.leased.insert(coin.object_id.clone());
+        Some(GasCoinLease { coin, state: self.state.clone() })
+    }
+
+    /// Return a leased coin to the pool (same as dropping the lease).
+    pub fn release(&self, lease: GasCoinLease) {
+        drop(lease);
+    }
+
+    /// Usable coins, leased or not.
+    pub fn len(&self) -> usize {
+        self.lock().coins.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().coins.is_empty()
+    }
+
+    /// Coins not currently leased.
+    pub fn free_count(&self) -> usize {
+        let state = self.lock();
+        state.coins.iter().filter(|c| !state.leased.contains(&c.object_id)).count()
+    }
+
+    /// Replace the known coins, dropping those below the minimum balance.
+    /// Leases outstanding stay leased.
+    pub fn set_coins(&self, coins: Vec<GasCoin>) {
+        let coins = coins.into_iter().filter(|c| c.balance >= self.min_coin_balance).collect();
+        self.lock().coins = coins;
+    }
+
+    /// Re-fetch the wallet's SUI coins.
+    pub async fn refresh(&self) -> Result<()> {
+        let body = self
+            .rpc
+            .call("suix_getCoins", json!([self.owner_address, "0x2::sui::SUI", null, COIN_PAGE]))
+            .await
+            .context("suix_getCoins request failed")?;
+        if let Some(error) = body.get("error") {
+            anyhow::bail!("suix_getCoins error: {}", error);
+        }
+        let coins: Vec<GasCoin> = body
+            .get("result")
+            .and_then(|r| r.get("data"))
+            .and_then(Value::as_array)
+            .context("suix_getCoins returned no data")?
+            .iter()
+            .filter_map(|c| {
+                Some(GasCoin {
+                    object_id: c.get("coinObjectId")?.as_str()?.to_string(),
+                    balance: c.get("balance")?.as_str()?.parse().ok()?,
+                })
+            })
+            .collect();
+        self.set_coins(coins);
+        debug!(coins = %self.len(), free = %self.free_count(), "Gas coin pool refreshed");
+        Ok(())
+    }
+
+    /// A transaction splitting the largest coin when the pool holds fewer
+    /// than `target_count` coins, or `None` if it doesn't need one (or the
+    /// balance is too small to split). Only splits while no coin is leased,
+    /// so it never touches a coin in flight.
+    ///
+    /// Built with `unsafe_paySui`, which pays gas from its first input coin:
+    /// `unsafe_splitCoin` needs a second coin for gas, which an
+    /// under-provisioned wallet may not have. The caller signs and submits
+    /// it, then refreshes the pool.
+    pub async fn maybe_split(&self) -> Result<Option<BuiltTx>> {
+        let (coin, amounts) = {
+            let state = self.lock();
+            if state.coins.len() >= self.target_count || !state.leased.is_empty() {
+                return Ok(None);
+            }
+            let Some(largest) = state.coins.iter().max_by_key(|c| c.balance) else {
+                return Ok(None);
+            };
+            let Some(amounts) = split_amounts(
+                largest.balance,
+                self.target_count - state.coins.len(),
+                self.split_gas_budget,
+                self.min_coin_balance,
+            ) else {
+                return Ok(None);
+            };
+            (largest.clone(), amounts)
+        };
+
+        info!(
+            coin = %coin.object_id,
+            balance = %coin.balance,
+            splits = %amounts.len(),
+            target = %self.target_count,
+            "Splitting gas coin"
+        );
+        let recipients = vec![self.owner_address.as_str(); amounts.len()];
+        let amounts: Vec<String> = amounts.iter().map(u64::to_string).collect();
+        let body = self
+            .rpc
+            .call(
+                "unsafe_paySui",
+                json!([
+                    self.owner_address,   // signer
+                    [coin.object_id],     // input_coins (also gas)
+                    recipients,           // recipients (self — one per split)
+                    amounts,              // amounts per recipient
+                    self.split_gas_budget // gas_budget
+                ]),
+            )
+            .await
+            .context("unsafe_paySui request failed")?;
+        if let Some(error) = body.get("error") {
+            anyhow::bail!("unsafe_paySui error: {}", error);
+        }
+        let tx_bytes = body
+            .get("result")
+            .and_then(|r| r.get("txBytes"))
+            .and_then(Value::as_str)
+            .context("Missing txBytes in paySui response")?
+            .to_string();
+        Ok(Some(BuiltTx {
+            tx_bytes,
+            expected: ExpectedTx::native(&self.owner_address, self.split_gas_budget),
+            gas_coin: None,
+        }))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Coins> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Amounts to split off a coin of `balance` to make `new_coins` more coins,
+/// each as large as what stays behind, after reserving `gas_reserve` for the
+/// split itself. `None` if any coin would fall below `min_coin_balance`.
+pub fn split_amounts(balance: u64, new_coins: usize, gas_reserve: u64, min_coin_balance: u64) -> Option<Vec<u64>> {
+    if new_coins == 0 {
+        return None;
+    }
+    let share = balance.checked_sub(gas_reserve)? / (new_coins as u64 + 1);
+    if share == 0 || share < min_coin_balance {
+        return None;
+    }
+    Some(vec![share; new_coins])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(coins: &[(&str, u64)]) -> GasCoinPool {
+        let pool = GasCoinPool::new("http://localhost:9000", "0xabc", 4, 100_000_000);
+        pool.set_coins(coins.iter().map(|&(id, balance)| GasCoin { object_id: id.into(), balance }).collect());
+        pool
+    }
+
+    #[test]
+    fn test_split_amounts_into_n_coins() {
+        // 10 SUI into 4 coins: 3 split off, each the same as what remains
+        let amounts = split_amounts(10_000_000_000, 3, 10_000_000, 100_000_000).unwrap();
+        assert_eq!(amounts, vec![2_497_500_000; 3]);
+        assert!(10_000_000_000 - 10_000_000 - amounts.iter().sum::<u64>() >= amounts[0]);
+
+        // Too small to give every coin the minimum
+        assert_eq!(split_amounts(300_000_000, 3, 10_000_000, 100_000_000), None);
+        assert_eq!(split_amounts(5_000_000, 1, 10_000_000, 0), None);
+        assert_eq!(split_amounts(10_000_000_000, 0, 10_000_000, 0), None);
+    }
+
+    #[test]
+    fn test_acquire_and_release() {
+        let pool = pool(&[("0x1", 1_000_000_000), ("0x2", 3_000_000_000), ("0x3", 50)]);
+        // Dust below the minimum isn't a gas coin
+        assert_eq!(pool.len(), 2);
+
+        let first = pool.acquire().unwrap();
+        assert_eq!(first.object_id, "0x2", "largest free coin first");
+        let second = pool.acquire().unwrap();
+        assert_eq!(second.object_id, "0x1");
+        assert!(pool.acquire().is_none());
+        assert_eq!(pool.free_count(), 0);
+
+        pool.release(first);
+        assert_eq!(pool.free_count(), 1);
+        assert_eq!(pool.acquire().unwrap().object_id, "0x2");
+
+        // A dropped lease is released too, and a refresh keeps live leases
+        pool.set_coins(vec![
+            GasCoin { object_id: "0x1".into(), balance: 900_000_000 },
+            GasCoin { object_id: "0x4".into(), balance: 200_000_000 },
+        ]);
+        assert_eq!(pool.free_count(), 1);
+        drop(second);
+        assert_eq!(pool.free_count(), 2);
+    }
+
+    #[test]
+    fn test_concurrent_acquires_get_distinct_coins() {
+        let ids: Vec<String> = (0..8).map(|i| format!("0x{:x}", i + 1)).collect();
+        let pool = pool(&ids.iter().map(|id| (id.as_str(), 1_000_000_000)).collect::<Vec<_>>());
+
+        let leases: Vec<GasCoinLease> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..16).map(|_| s.spawn(|| pool.acquire())).collect();
+            handles.into_iter().filter_map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(leases.len(), 8, "one lease per coin, the rest turned away");
+        let distinct: HashSet<&str> = leases.iter().map(|l| l.object_id.as_str()).collect();
+        assert_eq!(distinct.len(), 8);
+
+        drop(leases);
+        assert_eq!(pool.free_count(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_no_split_while_provisioned_or_leased() {
+        let full = pool(&[("0x1", 1_000_000_000), ("0x2", 1_000_000_000), ("0x3", 1_000_000_000), ("0x4", 1_000_000_000)]);
+        assert!(full.maybe_split().await.unwrap().is_none());
+
+        // Short of coins, but one is in flight: splitting could touch it
+        let short = pool(&[("0x1", 10_000_000_000)]);
+        let lease = short.acquire().unwrap();
+        assert!(short.maybe_split().await.unwrap().is_none());
+        drop(lease);
+    }
+}
@@ -1,15 +1,21 @@
 use anyhow::{Context, Result};
-use reqwest::Client;
-use serde_json::{json, Value};
-use tracing::{debug, error, warn};
+use arb_types::log_throttle::DEFAULT_INTERVAL;
+use arb_types::notifier::Notifier;
+use arb_types::rpc::RpcClient;
+use arb_types::{clear_throttled, warn_throttled};
+use serde_json::json;
+use std::time::Duration;
+use tracing::{debug, error};
+
+/// Per-request RPC timeout.
+const RPC_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Monitors the wallet's SUI gas balance via RPC.
 ///
 /// Checks balance before each trade attempt and warns/blocks when
 /// the balance is too low to cover gas costs.
 pub struct GasMonitor {
-    client: Client,
-    rpc_url: String,
+    rpc: RpcClient,
     owner_address: String,
     /// Minimum balance (in MIST) required to attempt a trade.
     /// Default: 100M MIST = 0.1 SUI (enough for ~2 trades)
@@ -20,31 +26,86 @@ pub struct GasMonitor {
     last_fetch_ms: u64,
     /// How often to re-fetch balance (ms).
     fetch_interval_ms: u64,
+    /// A cached balance within this many MIST of `min_balance_mist` (either
+    /// side) is re-fetched on every check instead of trusted for
+    /// `fetch_interval_ms`. 0 = always trust a fresh cache.
+    refresh_margin_mist: u64,
+    /// Webhook for low-balance alerts (disabled by default).
+    notifier: Notifier,
+    /// A fetched balance below this raises a low-balance alert.
+    alert_threshold_mist: u64,
+    /// When the last trade succeeded, for the alert.
+    last_trade_ms: Option<u64>,
 }
 
 impl GasMonitor {
     pub fn new(rpc_url: &str, owner_address: &str, min_balance_mist: u64) -> Self {
         Self {
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(5))
-                .build()
-                .expect("Failed to create HTTP client"),
-            rpc_url: rpc_url.to_string(),
+            rpc: RpcClient::single(rpc_url).with_timeout(RPC_TIMEOUT),
             owner_address: owner_address.to_string(),
             min_balance_mist,
             cached_balance: u64::MAX, // assume ok until first fetch
             last_fetch_ms: 0,
             fetch_interval_ms: 10_000, // re-check every 10s
+            refresh_margin_mist: 0,
+            notifier: Notifier::default(),
+            alert_threshold_mist: min_balance_mist,
+            last_trade_ms: None,
         }
     }
 
+    /// Query through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(RPC_TIMEOUT);
+        self
+    }
+
+    /// Force a fresh fetch whenever the cached balance is within `margin_mist`
+    /// of the minimum, so near-empty-wallet decisions use current data.
+    pub fn with_refresh_margin(mut self, margin_mist: u64) -> Self {
+        self.refresh_margin_mist = margin_mist;
+        self
+    }
+
+    /// Post a low-balance alert through `notifier` when a fetched balance is
+    /// below the alert threshold: on the first drop, then as often as the
+    /// notifier's rate limit allows while it stays low.
+    pub fn with_notifier(mut self, notifier: Notifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Alert below `threshold_mist` instead of `min_balance_mist` — e.g.
+    /// higher, to get a warning before trading pauses.
+    pub fn with_alert_threshold(mut self, threshold_mist: u64) -> Self {
+        self.alert_threshold_mist = threshold_mist;
+        self
+    }
+
+    /// Note a successful trade at `now_ms`; the low-balance alert reports
+    /// how long ago the last one was.
+    pub fn record_trade(&mut self, now_ms: u64) {
+        self.last_trade_ms = Some(now_ms);
+    }
+
+    /// Last known balance (after local gas deductions), or `None` before
+    /// the first successful fetch.
+    pub fn balance(&self) -> Option<u64> {
+        (self.cached_balance != u64::MAX).then_some(self.cached_balance)
+    }
+
+    /// Whether the cached balance can be trusted at `now_ms` without a fetch.
+    fn cache_usable(&self, now_ms: u64) -> bool {
+        self.cached_balance != u64::MAX
+            && now_ms.saturating_sub(self.last_fetch_ms) < self.fetch_interval_ms
+            && self.cached_balance.abs_diff(self.min_balance_mist) > self.refresh_margin_mist
+    }
+
     /// Check if gas balance is sufficient for trading.
     /// Returns `Ok(balance)` if sufficient, `Err` if insufficient or fetch failed.
     pub async fn check_balance(&mut self, now_ms: u64) -> Result<u64> {
-        // Use cached balance if fresh enough
-        if now_ms.saturating_sub(self.last_fetch_ms) < self.fetch_interval_ms
-            && self.cached_balance != u64::MAX
-        {
+        // Use cached balance if fresh enough and not close to the minimum
+        if self.cache_usable(now_ms) {
             return if self.cached_balance >= self.min_balance_mist {
                 Ok(self.cached_balance)
             } else {
@@ -61,9 +122,14 @@ impl GasMonitor {
             Ok(balance) => {
                 self.cached_balance = balance;
                 self.last_fetch_ms = now_ms;
+                if balance < self.alert_threshold_mist {
+                    self.alert_low_balance(balance, now_ms);
+                }
 
                 if balance < self.min_balance_mist {
-                    warn!(
+                    warn_throttled!(
+                        "gas_monitor.low_balance",
+                        DEFAULT_INTERVAL,
                         balance_mist = %balance,
                         balance_sui = %format!("{:.4}", balance as f64 / 1_000_000_000.0),
                         min_required = %self.min_balance_mist,
@@ -76,6 +142,7 @@ impl GasMonitor {
                         self.min_balance_mist
                     )
                 } else {
+                    clear_throttled!("gas_monitor.low_balance");
                     debug!(
                         balance_sui = %format!("{:.4}", balance as f64 / 1_000_000_000.0),
                         "Gas balance OK"
@@ -91,29 +158,42 @@ impl GasMonitor {
         }
     }
 
+    /// Notify that the balance is down to `balance` (rate-limited by the notifier).
+    fn alert_low_balance(&self, balance: u64, now_ms: u64) {
+        let since_trade_secs = self.last_trade_ms.map(|t| now_ms.saturating_sub(t) / 1000);
+        let since_trade = match since_trade_secs {
+            Some(secs) => format!("{}h {}m ago", secs / 3600, secs / 60 % 60),
+            None => "none since startup".to_string(),
+        };
+        let message = format!(
+            "Low gas balance on {}: {:.4} SUI (alert below {:.4} SUI, trading needs {:.4} SUI). Last successful trade: {}",
+            self.owner_address,
+            balance as f64 / 1_000_000_000.0,
+            self.alert_threshold_mist as f64 / 1_000_000_000.0,
+            self.min_balance_mist as f64 / 1_000_000_000.0,
+            since_trade
+        );
+        let details = json!({
+            "address": self.owner_address,
+            "balance_sui": balance as f64 / 1_000_000_000.0,
+            "balance_mist": balance,
+            "min_balance_mist": self.min_balance_mist,
+            "secs_since_last_trade": since_trade_secs,
+        });
+        self.notifier.notify("gas.low", &message, details, now_ms);
+    }
+
     /// Fetch the total SUI balance for the owner address.
-    async fn fetch_balance(&self) -> Result<u64> {
-        let response = self
-            .client
-            .post(&self.rpc_url)
-            .json(&json!({
-                "jsonrpc": "2.0",
-                "id": 1,
-                "method": "suix_getBalance",
-                "params": [
-                    self.owner_address,
-                    "0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI"
-                ]
-            }))
-            .send()
+    pub async fn fetch_balance(&self) -> Result<u64> {
+        let body = self
+            .rpc
+            .call("suix_getBalance", json!([
+                self.owner_address,
+                "0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI"
+            ]))
             .await
             .context("Balance RPC request failed")?;
 
-        let body: Value = response
-            .json()
-            .await
-            .context("Failed to parse balance response")?;
-
         if let Some(error) = body.get("error") {
             anyhow::bail!("Balance RPC error: {}", error);
         }
@@ -145,6 +225,42 @@ mod tests {
         assert_eq!(monitor.cached_balance, u64::MAX);
     }
 
+    #[test]
+    fn test_cache_bypassed_near_minimum() {
+        let mut monitor =
+            GasMonitor::new("http://localhost:9000", "0xabc", 100_000_000).with_refresh_margin(50_000_000);
+        monitor.last_fetch_ms = 1_000;
+
+        // Plenty of headroom: cached value is trusted until it goes stale
+        monitor.cached_balance = 500_000_000;
+        assert!(monitor.cache_usable(2_000));
+        assert!(!monitor.cache_usable(11_000));
+
+        // Within the margin on either side of the minimum: always re-fetch
+        monitor.cached_balance = 140_000_000;
+        assert!(!monitor.cache_usable(2_000));
+        monitor.cached_balance = 60_000_000;
+        assert!(!monitor.cache_usable(2_000));
+
+        // Far below the minimum: the cached "insufficient" answer stands
+        monitor.cached_balance = 10_000_000;
+        assert!(monitor.cache_usable(2_000));
+
+        // Spending gas can move a comfortable balance into the margin
+        monitor.cached_balance = 200_000_000;
+        assert!(monitor.cache_usable(2_000));
+        monitor.deduct_gas(60_000_000);
+        assert!(!monitor.cache_usable(2_000));
+    }
+
+    #[test]
+    fn test_zero_margin_keeps_interval_caching() {
+        let mut monitor = GasMonitor::new("http://localhost:9000", "0xabc", 100_000_000);
+        monitor.last_fetch_ms = 1_000;
+        monitor.cached_balance = 100_000_001;
+        assert!(monitor.cache_usable(2_000));
+    }
+
     #[test]
     fn test_deduct_gas() {
         let mut monitor = GasMonitor::new("http://localhost:9000", "0xabc", 100_000_000);
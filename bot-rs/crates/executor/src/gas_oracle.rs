@@ -0,0 +1,136 @@
+use anyhow::{Context, Result};
+use arb_types::opportunity::StrategyType;
+use arb_types::rpc::RpcClient;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+/// Per-request RPC timeout.
+const RPC_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Reference gas price (MIST per gas unit) assumed until the first refresh.
+pub const DEFAULT_REFERENCE_GAS_PRICE: u64 = 750;
+
+/// Gas units budgeted for a two-hop arb (flash or direct): computation for
+/// two swaps plus a flash repay, with headroom for storage.
+pub const TWO_HOP_GAS_UNITS: u64 = 20_000;
+
+/// Gas units budgeted for a tri-hop arb.
+pub const TRI_HOP_GAS_UNITS: u64 = 30_000;
+
+/// Gas units budgeted for a quad-hop arb.
+pub const QUAD_HOP_GAS_UNITS: u64 = 40_000;
+
+/// Live reference gas price, and gas budgets scaled by it.
+///
+/// A static budget runs out during congestion and over-reserves when the
+/// network is calm. The oracle polls `suix_getReferenceGasPrice` and
+/// budgets `units(strategy) × price`, capped at `ceiling_mist`.
+///
+/// Cheap to clone; clones share the price.
+#[derive(Debug, Clone)]
+pub struct GasOracle {
+    rpc: RpcClient,
+    ceiling_mist: u64,
+    price: Arc<AtomicU64>,
+}
+
+impl GasOracle {
+    pub fn new(rpc_url: &str, ceiling_mist: u64) -> Self {
+        Self {
+            rpc: RpcClient::single(rpc_url).with_timeout(RPC_TIMEOUT),
+            ceiling_mist,
+            price: Arc::new(AtomicU64::new(DEFAULT_REFERENCE_GAS_PRICE)),
+        }
+    }
+
+    /// Query through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(RPC_TIMEOUT);
+        self
+    }
+
+    /// Re-fetch the reference gas price. On failure the last known price stays.
+    pub async fn refresh(&self) -> Result<u64> {
+        let body = self
+            .rpc
+            .call("suix_getReferenceGasPrice", json!([]))
+            .await
+            .context("suix_getReferenceGasPrice request failed")?;
+        if let Some(error) = body.get("error") {
+            anyhow::bail!("suix_getReferenceGasPrice error: {}", error);
+        }
+        // The node returns the price as a string
+        let price = body
+            .get("result")
+            .and_then(|r| r.as_u64().or_else(|| r.as_str()?.parse().ok()))
+            .filter(|&p| p > 0)
+            .with_context(|| format!("Invalid reference gas price: {}", body.get("result").unwrap_or(&Value::Null)))?;
+        debug!(price = %price, "Reference gas price refreshed");
+        self.set_price(price);
+        Ok(price)
+    }
+
+    /// Reference gas price (MIST per gas unit).
+    pub fn current_gas_price(&self) -> u64 {
+        self.price.load(Ordering::Relaxed)
+    }
+
+    /// Override the price (tests, and callers that learn it some other way).
+    pub fn set_price(&self, price: u64) {
+        self.price.store(price, Ordering::Relaxed);
+    }
+
+    /// Gas budget (MIST) for one `strategy` transaction at the current price,
+    /// capped at the ceiling.
+    pub fn suggested_budget(&self, strategy: StrategyType) -> u64 {
+        let units = match strategy.hops() {
+            4 => QUAD_HOP_GAS_UNITS,
+            3 => TRI_HOP_GAS_UNITS,
+            _ => TWO_HOP_GAS_UNITS,
+        };
+        units.saturating_mul(self.current_gas_price()).min(self.ceiling_mist)
+    }
+
+    /// The cap on any suggested budget.
+    pub fn ceiling(&self) -> u64 {
+        self.ceiling_mist
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_scales_with_reference_price() {
+        let oracle = GasOracle::new("http://localhost:9000", 1_000_000_000);
+        let calm = oracle.suggested_budget(StrategyType::CetusToTurbos);
+        assert_eq!(calm, TWO_HOP_GAS_UNITS * DEFAULT_REFERENCE_GAS_PRICE);
+
+        oracle.set_price(DEFAULT_REFERENCE_GAS_PRICE * 3);
+        let congested = oracle.suggested_budget(StrategyType::CetusToTurbos);
+        assert!(congested > calm);
+        assert_eq!(congested, calm * 3);
+
+        // Tri-hop is budgeted more than two-hop at the same price
+        assert!(oracle.suggested_budget(StrategyType::TriCetusTurbosDeepBook) > congested);
+        assert!(
+            oracle.suggested_budget(StrategyType::QuadCetusCetusCetusCetus)
+                > oracle.suggested_budget(StrategyType::TriCetusTurbosDeepBook)
+        );
+    }
+
+    #[test]
+    fn test_budget_respects_ceiling() {
+        let oracle = GasOracle::new("http://localhost:9000", 50_000_000);
+        oracle.set_price(100_000);
+        assert_eq!(oracle.suggested_budget(StrategyType::CetusToTurbos), 50_000_000);
+        assert_eq!(oracle.suggested_budget(StrategyType::TriCetusTurbosDeepBook), 50_000_000);
+
+        oracle.set_price(u64::MAX);
+        assert_eq!(oracle.suggested_budget(StrategyType::TriCetusTurbosDeepBook), 50_000_000);
+    }
+}
@@ -0,0 +1,175 @@
+use anyhow::{Context, Result};
+use arb_types::config::normalize_coin_type;
+use arb_types::rpc::RpcClient;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::debug;
+
+/// SUI, as `normalize_coin_type` writes it.
+const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
+
+/// Per-request RPC timeout.
+const RPC_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A wallet coin usable as direct-arb inventory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InventoryCoin {
+    pub object_id: String,
+    pub balance: u64,
+}
+
+/// Wallet holdings of the configured inventory coin types, for
+/// flash-loan-free (direct) arbs funded by the bot's own capital.
+///
+/// Per coin type, the largest single coin is the inventory coin: the
+/// `direct` Move functions take one `&mut Coin<A>`, split the input from it,
+/// and merge the proceeds back, so its object ID stays stable across trades.
+/// For SUI the wallet must also hold another coin to pay gas.
+///
+/// Cheap to clone; clones share the holdings.
+#[derive(Debug, Clone)]
+pub struct Inventory {
+    rpc: RpcClient,
+    owner_address: String,
+    /// Normalized coin types to track
+    coin_types: Vec<String>,
+    holdings: Arc<RwLock<HashMap<String, InventoryCoin>>>,
+}
+
+impl Inventory {
+    pub fn new(rpc_url: &str, owner_address: &str, coin_types: &[String]) -> Self {
+        Self {
+            rpc: RpcClient::single(rpc_url).with_timeout(RPC_TIMEOUT),
+            owner_address: owner_address.to_string(),
+            coin_types: coin_types.iter().map(|c| normalize_coin_type(c)).collect(),
+            holdings: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Query through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(RPC_TIMEOUT);
+        self
+    }
+
+    /// Tracked coin types (normalized).
+    pub fn coin_types(&self) -> &[String] {
+        &self.coin_types
+    }
+
+    /// Re-fetch the inventory coin for every tracked type. A type whose
+    /// fetch fails keeps its previous holding.
+    pub async fn refresh(&self) -> Result<()> {
+        let mut failures = Vec::new();
+        for coin_type in &self.coin_types {
+            match self.fetch_coins(coin_type).await {
+                Ok(coins) => {
+                    let pick = pick_inventory_coin(coin_type, &coins);
+                    debug!(coin_type = %coin_type, coin = ?pick, "Inventory refreshed");
+                    let mut holdings = self.holdings.write().unwrap_or_else(|e| e.into_inner());
+                    match pick {
+                        Some(c) => holdings.insert(coin_type.clone(), c),
+                        None => holdings.remove(coin_type),
+                    };
+                }
+                Err(e) => failures.push(format!("{}: {:#}", coin_type, e)),
+            }
+        }
+        anyhow::ensure!(failures.is_empty(), "Inventory refresh failed for {}", failures.join("; "));
+        Ok(())
+    }
+
+    /// The inventory coin for `coin_type` if it holds at least `amount`.
+    pub fn covering(&self, coin_type: &str, amount: u64) -> Option<InventoryCoin> {
+        let holdings = self.holdings.read().unwrap_or_else(|e| e.into_inner());
+        holdings
+            .get(&normalize_coin_type(coin_type))
+            .filter(|c| c.balance >= amount)
+            .cloned()
+    }
+
+    /// Override the holding for `coin_type` (tests, and callers that learn
+    /// the balance some other way).
+    pub fn set_holding(&self, coin_type: &str, coin: InventoryCoin) {
+        self.holdings
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(normalize_coin_type(coin_type), coin);
+    }
+
+    /// All coins of `coin_type` owned by the wallet (first page of 50 —
+    /// the inventory coin is the largest, and a fragmented wallet is the
+    /// coin merger's job).
+    async fn fetch_coins(&self, coin_type: &str) -> Result<Vec<Value>> {
+        let body = self
+            .rpc
+            .call("suix_getCoins", json!([self.owner_address, coin_type, null, 50]))
+            .await
+            .context("suix_getCoins request failed")?;
+        if let Some(error) = body.get("error") {
+            anyhow::bail!("suix_getCoins error: {}", error);
+        }
+        Ok(body
+            .get("result")
+            .and_then(|r| r.get("data"))
+            .and_then(|d| d.as_array())
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+/// The largest coin in a `suix_getCoins` page. For SUI, the largest coin
+/// is only usable while another coin remains to pay gas.
+fn pick_inventory_coin(coin_type: &str, coins: &[Value]) -> Option<InventoryCoin> {
+    let parsed: Vec<InventoryCoin> = coins
+        .iter()
+        .filter_map(|c| {
+            Some(InventoryCoin {
+                object_id: c.get("coinObjectId")?.as_str()?.to_string(),
+                balance: c.get("balance")?.as_str()?.parse().ok()?,
+            })
+        })
+        .collect();
+    if coin_type == SUI_COIN_TYPE && parsed.len() < 2 {
+        return None;
+    }
+    parsed.into_iter().max_by_key(|c| c.balance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin(id: &str, balance: u64) -> Value {
+        json!({ "coinObjectId": id, "balance": balance.to_string() })
+    }
+
+    #[test]
+    fn test_picks_largest_coin() {
+        let usdc = "0xa::usdc::USDC";
+        let coins = [coin("0x1", 5), coin("0x2", 50), coin("0x3", 7)];
+        let pick = pick_inventory_coin(usdc, &coins).unwrap();
+        assert_eq!(pick, InventoryCoin { object_id: "0x2".into(), balance: 50 });
+        assert_eq!(pick_inventory_coin(usdc, &[]), None);
+    }
+
+    #[test]
+    fn test_sui_needs_a_separate_gas_coin() {
+        assert_eq!(pick_inventory_coin(SUI_COIN_TYPE, &[coin("0x1", 10)]), None);
+        let pick = pick_inventory_coin(SUI_COIN_TYPE, &[coin("0x1", 10), coin("0x2", 3)]).unwrap();
+        assert_eq!(pick.object_id, "0x1");
+    }
+
+    #[test]
+    fn test_covering_checks_balance_and_normalizes_type() {
+        let long = "0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI";
+        let inv = Inventory::new("http://localhost:9000", "0xabc", &[long.to_string()]);
+        assert_eq!(inv.coin_types(), [SUI_COIN_TYPE]);
+        inv.set_holding(long, InventoryCoin { object_id: "0x1".into(), balance: 100 });
+        assert!(inv.covering("0x2::sui::SUI", 100).is_some());
+        assert!(inv.covering(long, 101).is_none());
+        assert!(inv.covering("0xa::usdc::USDC", 1).is_none());
+    }
+}
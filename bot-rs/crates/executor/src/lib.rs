@@ -1,10 +1,20 @@
 pub mod coin_merger;
+pub mod gas_coin_pool;
 pub mod gas_monitor;
+pub mod gas_oracle;
+pub mod inventory;
+pub mod local_ptb;
 pub mod ptb_builder;
 pub mod signer;
 pub mod submitter;
+pub mod tx_verify;
 
 pub use coin_merger::CoinMerger;
+pub use gas_coin_pool::{GasCoin, GasCoinLease, GasCoinPool};
 pub use gas_monitor::GasMonitor;
+pub use gas_oracle::GasOracle;
+pub use inventory::{Inventory, InventoryCoin};
+pub use local_ptb::LocalTxBuilder;
 pub use signer::Signer;
-pub use submitter::{SubmitResult, Submitter};
+pub use submitter::{GasCharge, SubmitError, SubmitResult, Submitter};
+pub use tx_verify::{BuiltTx, ExpectedCall, ExpectedTx, TxMismatch};
@@ -0,0 +1,624 @@
+//! Local PTB construction: encode the strategy's `TransactionData` as BCS
+//! here instead of asking the node to build it with `unsafe_moveCall`.
+//!
+//! Saves a round trip on every candidate and works with providers that
+//! disable the `unsafe_` namespace. What the node would have looked up is
+//! resolved once and cached:
+//! - each Move function's parameters (which objects are taken `&mut`),
+//! - shared objects' initial shared versions (these never change),
+//! - owned objects' refs, the gas coin, and the reference gas price
+//!   (and the current epoch, when transactions carry an expiration).
+//!
+//! Owned refs change whenever a transaction uses them, so they are dropped
+//! after every submission ([`LocalTxBuilder::invalidate_owned`]) and at most
+//! `OWNED_MAX_AGE` after they were fetched.
+
+use anyhow::{Context, Result};
+use arb_types::encoding::{decode_base58, hex_addr, parse_address, string, type_tag, uleb128};
+use arb_types::rpc::RpcClient;
+use base64::Engine as _;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Owned object refs and the gas coin are re-fetched at least this often,
+/// in case something outside the bot moved them.
+const OWNED_MAX_AGE: Duration = Duration::from_secs(30);
+
+/// Coins fetched when picking the gas coin.
+const GAS_COIN_PAGE: u64 = 50;
+
+/// The current epoch as far as [`LocalTxBuilder::build_cached`] knows.
+#[cfg(test)]
+pub(crate) const TEST_EPOCH: u64 = 500;
+
+/// `(ObjectID, SequenceNumber, ObjectDigest)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectRef {
+    pub id: [u8; 32],
+    pub version: u64,
+    pub digest: [u8; 32],
+}
+
+/// How an object is passed into the transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ObjectInput {
+    Shared { id: [u8; 32], initial_shared_version: u64 },
+    /// Address-owned or immutable
+    Owned(ObjectRef),
+}
+
+/// One transaction input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Input {
+    Pure(Vec<u8>),
+    Object { object: ObjectInput, mutable: bool },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Gas {
+    coin: ObjectRef,
+    price: u64,
+    /// Current epoch; only fetched when transactions expire
+    epoch: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+struct Cache {
+    /// `(module, function)` → whether each parameter is taken mutably
+    /// (`&mut` or by value)
+    functions: HashMap<(String, String), Vec<bool>>,
+    shared: HashMap<[u8; 32], u64>,
+    owned: HashMap<[u8; 32], ObjectRef>,
+    gas_price: Option<u64>,
+    epoch: Option<u64>,
+    /// The sender's SUI coins, with balances
+    gas_coins: Option<Vec<(u64, ObjectRef)>>,
+    /// When the owned refs and gas were first fetched since the last invalidation
+    owned_since: Option<Instant>,
+}
+
+impl Cache {
+    fn expire_owned(&mut self, now: Instant) {
+        if self.owned_since.is_some_and(|t| now.duration_since(t) >= OWNED_MAX_AGE) {
+            self.invalidate_owned();
+        }
+    }
+
+    fn invalidate_owned(&mut self) {
+        self.owned.clear();
+        self.gas_price = None;
+        self.epoch = None;
+        self.gas_coins = None;
+        self.owned_since = None;
+    }
+
+    fn object(&self, id: &[u8; 32]) -> Option<ObjectInput> {
+        if let Some(&initial_shared_version) = self.shared.get(id) {
+            return Some(ObjectInput::Shared { id: *id, initial_shared_version });
+        }
+        self.owned.get(id).copied().map(ObjectInput::Owned)
+    }
+}
+
+/// Builds strategy transactions locally. See the module docs.
+#[derive(Debug)]
+pub struct LocalTxBuilder {
+    rpc: RpcClient,
+    package_id: String,
+    package: [u8; 32],
+    sender_id: String,
+    sender: [u8; 32],
+    gas_budget: u64,
+    /// Epochs after the current one a transaction stays valid (None = never expires)
+    expiration_epochs: Option<u64>,
+    cache: Mutex<Cache>,
+}
+
+impl LocalTxBuilder {
+    pub fn new(rpc: RpcClient, package_id: &str, sender: &str, gas_budget: u64) -> Result<Self> {
+        Ok(Self {
+            rpc,
+            package_id: package_id.to_string(),
+            package: parse_address(package_id).context("Invalid package ID")?,
+            sender_id: sender.to_string(),
+            sender: parse_address(sender).context("Invalid sender address")?,
+            gas_budget,
+            expiration_epochs: None,
+            cache: Mutex::new(Cache::default()),
+        })
+    }
+
+    /// Expire each transaction `epochs` after the epoch it was built in
+    /// (0 = at the end of the current epoch). None = never expires.
+    pub fn with_expiration_epochs(mut self, epochs: Option<u64>) -> Self {
+        self.expiration_epochs = epochs;
+        self
+    }
+
+    /// Resolve `object_ids` and the gas coin ahead of the first build.
+    pub async fn warm_up(&self, object_ids: &[String]) -> Result<()> {
+        let ids = object_ids.iter().map(|id| parse_address(id)).collect::<Result<Vec<_>>>()?;
+        self.resolve_objects(&ids).await?;
+        self.gas(&ids, None, self.gas_budget).await?;
+        Ok(())
+    }
+
+    /// Forget owned object refs and the gas coin. Call after every
+    /// submission: a transaction bumps the versions of what it touches.
+    pub fn invalidate_owned(&self) {
+        self.cache.lock().unwrap().invalidate_owned();
+    }
+
+    /// Build `package::module::function<type_args>(args)` and return the
+    /// base64 `TransactionData`. `args` are as for `unsafe_moveCall`:
+    /// `0x…` strings are objects, decimal strings `u64` values. `gas_budget`
+    /// is capped at the builder's budget, which the gas coin must cover.
+    /// `gas_coin` pays for it if given, else the largest free SUI coin.
+    pub async fn build(
+        &self,
+        module: &str,
+        function: &str,
+        type_args: &[String],
+        args: &[Value],
+        gas_budget: u64,
+        gas_coin: Option<&str>,
+    ) -> Result<String> {
+        self.function_params(module, function).await?;
+        let values = args.iter().map(parse_arg).collect::<Result<Vec<_>>>()?;
+        let object_ids: Vec<[u8; 32]> = values
+            .iter()
+            .filter_map(|v| match v {
+                Arg::Object(id) => Some(*id),
+                Arg::U64(_) => None,
+            })
+            .collect();
+        self.resolve_objects(&object_ids).await?;
+        let gas_coin = gas_coin.map(parse_address).transpose().context("Invalid gas coin ID")?;
+        let gas = self.gas(&object_ids, gas_coin, gas_budget.min(self.gas_budget)).await?;
+        let bytes = self.assemble(module, function, type_args, &values, gas, gas_budget)?;
+        Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+    }
+
+    /// Encode the call from what's cached. Everything must be resolved.
+    fn assemble(
+        &self,
+        module: &str,
+        function: &str,
+        type_args: &[String],
+        values: &[Arg],
+        gas: Gas,
+        gas_budget: u64,
+    ) -> Result<Vec<u8>> {
+        let cache = self.cache.lock().unwrap();
+        let mutability = cache
+            .functions
+            .get(&(module.to_string(), function.to_string()))
+            .with_context(|| format!("{}::{} not resolved", module, function))?;
+        anyhow::ensure!(
+            mutability.len() >= values.len(),
+            "{}::{} takes {} parameters, got {} arguments",
+            module,
+            function,
+            mutability.len(),
+            values.len()
+        );
+        let inputs = values
+            .iter()
+            .zip(mutability)
+            .map(|(value, &mutable)| match value {
+                Arg::U64(v) => Ok(Input::Pure(v.to_le_bytes().to_vec())),
+                Arg::Object(id) => cache
+                    .object(id)
+                    .map(|object| Input::Object { object, mutable })
+                    .with_context(|| format!("Object {} not resolved", hex_addr(id))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        MoveCallTx {
+            sender: self.sender,
+            package: self.package,
+            module,
+            function,
+            type_args,
+            inputs,
+            gas,
+            gas_budget: gas_budget.min(self.gas_budget),
+            expiration: gas.epoch.zip(self.expiration_epochs).map(|(epoch, n)| epoch.saturating_add(n)),
+        }
+        .encode()
+    }
+
+    /// Build from a pre-filled cache, without RPC: `normalized` stands in
+    /// for the `sui_getNormalizedMoveFunction` result, and every object in
+    /// `args` is taken as shared, except `owned`. The current epoch is
+    /// [`TEST_EPOCH`]. For tests.
+    #[cfg(test)]
+    pub(crate) fn build_cached(
+        &self,
+        module: &str,
+        function: &str,
+        normalized: &Value,
+        type_args: &[String],
+        args: &[Value],
+        owned: &[&str],
+    ) -> Result<Vec<u8>> {
+        let values = args.iter().map(parse_arg).collect::<Result<Vec<_>>>()?;
+        let owned = owned.iter().map(|id| parse_address(id)).collect::<Result<Vec<_>>>()?;
+        let gas = Gas {
+            coin: ObjectRef { id: parse_address("0x9a5")?, version: 4, digest: [7; 32] },
+            price: 750,
+            epoch: self.expiration_epochs.map(|_| TEST_EPOCH),
+        };
+        {
+            let mut cache = self.cache.lock().unwrap();
+            cache.functions.insert((module.to_string(), function.to_string()), param_mutability(normalized)?);
+            for value in &values {
+                if let Arg::Object(id) = value {
+                    if owned.contains(id) {
+                        cache.owned.insert(*id, ObjectRef { id: *id, version: 2, digest: [1; 32] });
+                    } else {
+                        cache.shared.insert(*id, 1);
+                    }
+                }
+            }
+        }
+        self.assemble(module, function, type_args, &values, gas, self.gas_budget)
+    }
+
+    /// Whether each parameter of `module::function` is taken mutably, from
+    /// the normalized Move function (cached for good).
+    async fn function_params(&self, module: &str, function: &str) -> Result<Vec<bool>> {
+        let key = (module.to_string(), function.to_string());
+        if let Some(params) = self.cache.lock().unwrap().functions.get(&key) {
+            return Ok(params.clone());
+        }
+        let body = self
+            .rpc
+            .call("sui_getNormalizedMoveFunction", json!([self.package_id, module, function]))
+            .await?;
+        let params = param_mutability(rpc_result(&body, "sui_getNormalizedMoveFunction")?)?;
+        self.cache.lock().unwrap().functions.insert(key, params.clone());
+        Ok(params)
+    }
+
+    /// Fetch whichever of `ids` aren't cached (or whose owned refs expired).
+    async fn resolve_objects(&self, ids: &[[u8; 32]]) -> Result<()> {
+        let missing: Vec<String> = {
+            let mut cache = self.cache.lock().unwrap();
+            cache.expire_owned(Instant::now());
+            let mut seen = HashSet::new();
+            ids.iter().filter(|id| cache.object(id).is_none() && seen.insert(**id)).map(hex_addr).collect()
+        };
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        debug!(objects = %missing.len(), "Resolving objects for local PTB build");
+        let body = self.rpc.call("sui_multiGetObjects", json!([missing, { "showOwner": true }])).await?;
+        let objects = rpc_result(&body, "sui_multiGetObjects")?
+            .as_array()
+            .context("sui_multiGetObjects result is not an array")?;
+        anyhow::ensure!(objects.len() == missing.len(), "sui_multiGetObjects returned {} of {} objects", objects.len(), missing.len());
+
+        let mut cache = self.cache.lock().unwrap();
+        for (id, object) in missing.iter().zip(objects) {
+            let data = object.get("data").with_context(|| format!("Object {} not found: {}", id, object))?;
+            match parse_object(data, &self.sender)? {
+                ObjectInput::Shared { id, initial_shared_version } => {
+                    cache.shared.insert(id, initial_shared_version);
+                }
+                ObjectInput::Owned(r) => {
+                    cache.owned.insert(r.id, r);
+                    cache.owned_since.get_or_insert_with(Instant::now);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The gas coin — `want`, or else the sender's largest SUI coin that
+    /// isn't a call argument — and reference gas price. The coin must cover
+    /// `budget`, the budget the transaction will carry.
+    async fn gas(&self, exclude: &[[u8; 32]], want: Option<[u8; 32]>, budget: u64) -> Result<Gas> {
+        let cached = {
+            let mut cache = self.cache.lock().unwrap();
+            cache.expire_owned(Instant::now());
+            let epoch_ready = self.expiration_epochs.is_none() || cache.epoch.is_some();
+            match (cache.gas_price, cache.gas_coins.clone()) {
+                (Some(price), Some(coins)) if epoch_ready => Some((price, cache.epoch, coins)),
+                _ => None,
+            }
+        };
+        let (price, epoch, coins) = match cached {
+            Some(cached) => cached,
+            None => {
+                let fetched = self.fetch_gas().await?;
+                let mut cache = self.cache.lock().unwrap();
+                cache.gas_price = Some(fetched.0);
+                cache.epoch = fetched.1;
+                cache.gas_coins = Some(fetched.2.clone());
+                cache.owned_since.get_or_insert_with(Instant::now);
+                fetched
+            }
+        };
+
+        let (balance, coin) = match want {
+            Some(id) => {
+                anyhow::ensure!(!exclude.contains(&id), "Gas coin {} is also a call argument", hex_addr(&id));
+                *coins
+                    .iter()
+                    .find(|(_, c)| c.id == id)
+                    .with_context(|| format!("Gas coin {} is not among the sender's SUI coins", hex_addr(&id)))?
+            }
+            None => *coins
+                .iter()
+                .filter(|(_, c)| !exclude.contains(&c.id))
+                .max_by_key(|(balance, _)| *balance)
+                .context("No SUI coin available to pay gas")?,
+        };
+        anyhow::ensure!(balance >= budget, "Gas coin holds {} MIST, below the {} MIST budget", balance, budget);
+        Ok(Gas { coin, price, epoch })
+    }
+
+    /// Reference gas price, the current epoch if transactions expire, and
+    /// the sender's SUI coins (first page).
+    async fn fetch_gas(&self) -> Result<(u64, Option<u64>, Vec<(u64, ObjectRef)>)> {
+        let (price, epoch) = if self.expiration_epochs.is_some() {
+            // Both from one call: the reference price is set per epoch
+            let body = self.rpc.call("suix_getLatestSuiSystemState", json!([])).await?;
+            let state = rpc_result(&body, "suix_getLatestSuiSystemState")?;
+            let price = state.get("referenceGasPrice").and_then(as_u64).context("Invalid reference gas price")?;
+            let epoch = state.get("epoch").and_then(as_u64).context("Invalid epoch")?;
+            (price, Some(epoch))
+        } else {
+            let body = self.rpc.call("suix_getReferenceGasPrice", json!([])).await?;
+            let price =
+                as_u64(rpc_result(&body, "suix_getReferenceGasPrice")?).context("Invalid reference gas price")?;
+            (price, None)
+        };
+
+        let body = self
+            .rpc
+            .call("suix_getCoins", json!([self.sender_id, "0x2::sui::SUI", null, GAS_COIN_PAGE]))
+            .await?;
+        let coins = rpc_result(&body, "suix_getCoins")?
+            .get("data")
+            .and_then(Value::as_array)
+            .context("suix_getCoins returned no data")?
+            .iter()
+            .map(|coin| Ok((coin.get("balance").and_then(as_u64).unwrap_or(0), object_ref(coin, "coinObjectId")?)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok((price, epoch, coins))
+    }
+}
+
+/// Whether each parameter of a normalized Move function is taken mutably.
+/// Only `&T` isn't: shared objects behind one are passed as immutable
+/// inputs, so the transaction doesn't queue behind others writing them
+/// (the Cetus `GlobalConfig`, the DEXes' version objects, the clock).
+fn param_mutability(normalized: &Value) -> Result<Vec<bool>> {
+    Ok(normalized
+        .get("parameters")
+        .and_then(Value::as_array)
+        .context("Normalized function has no parameters")?
+        .iter()
+        .map(|p| p.get("Reference").is_none())
+        .collect())
+}
+
+/// A call argument as passed to `unsafe_moveCall`.
+enum Arg {
+    Object([u8; 32]),
+    U64(u64),
+}
+
+fn parse_arg(arg: &Value) -> Result<Arg> {
+    let s = arg.as_str().with_context(|| format!("Unsupported argument {}", arg))?;
+    if s.starts_with("0x") {
+        Ok(Arg::Object(parse_address(s)?))
+    } else {
+        s.parse().map(Arg::U64).with_context(|| format!("Unsupported argument '{}'", s))
+    }
+}
+
+/// How a fetched object (`showOwner`) is passed: shared objects by their
+/// initial shared version, our own and immutable ones by ref.
+fn parse_object(data: &Value, sender: &[u8; 32]) -> Result<ObjectInput> {
+    let r = object_ref(data, "objectId")?;
+    let owner = data.get("owner").context("Object has no owner (fetched without showOwner?)")?;
+    if owner.as_str() == Some("Immutable") {
+        return Ok(ObjectInput::Owned(r));
+    }
+    if let Some(shared) = owner.get("Shared") {
+        let initial_shared_version = shared
+            .get("initial_shared_version")
+            .and_then(as_u64)
+            .context("Shared object has no initial_shared_version")?;
+        return Ok(ObjectInput::Shared { id: r.id, initial_shared_version });
+    }
+    match owner.get("AddressOwner").and_then(Value::as_str) {
+        Some(addr) if parse_address(addr)? == *sender => Ok(ObjectInput::Owned(r)),
+        _ => anyhow::bail!("Object {} is not ours to use (owner {})", hex_addr(&r.id), owner),
+    }
+}
+
+/// `{ <id_field>, version, digest }` from an RPC object or coin.
+fn object_ref(data: &Value, id_field: &str) -> Result<ObjectRef> {
+    let id = data.get(id_field).and_then(Value::as_str).with_context(|| format!("Missing {}", id_field))?;
+    let version = data.get("version").and_then(as_u64).context("Missing object version")?;
+    let digest = data.get("digest").and_then(Value::as_str).context("Missing object digest")?;
+    let digest: [u8; 32] = decode_base58(digest)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Object digest '{}' is not 32 bytes", digest))?;
+    Ok(ObjectRef { id: parse_address(id)?, version, digest })
+}
+
+/// A `u64` sent as a JSON number or decimal string.
+fn as_u64(v: &Value) -> Option<u64> {
+    v.as_u64().or_else(|| v.as_str()?.parse().ok())
+}
+
+fn rpc_result<'a>(body: &'a Value, method: &str) -> Result<&'a Value> {
+    if let Some(error) = body.get("error") {
+        anyhow::bail!("{} error: {}", method, error);
+    }
+    body.get("result").with_context(|| format!("Missing result in {} response", method))
+}
+
+// ── BCS encoding ──
+
+/// `TransactionData::V1` for a single Move call.
+struct MoveCallTx<'a> {
+    sender: [u8; 32],
+    package: [u8; 32],
+    module: &'a str,
+    function: &'a str,
+    type_args: &'a [String],
+    inputs: Vec<Input>,
+    gas: Gas,
+    gas_budget: u64,
+    /// Last epoch the transaction may execute in
+    expiration: Option<u64>,
+}
+
+impl MoveCallTx<'_> {
+    fn encode(&self) -> Result<Vec<u8>> {
+        let mut out = vec![0, 0]; // TransactionData::V1, TransactionKind::ProgrammableTransaction
+
+        uleb128(&mut out, self.inputs.len() as u64);
+        for input in &self.inputs {
+            match input {
+                Input::Pure(bytes) => {
+                    out.push(0); // CallArg::Pure
+                    uleb128(&mut out, bytes.len() as u64);
+                    out.extend_from_slice(bytes);
+                }
+                Input::Object { object: ObjectInput::Owned(r), .. } => {
+                    out.extend_from_slice(&[1, 0]); // CallArg::Object(ImmOrOwnedObject)
+                    object_ref_bytes(&mut out, r);
+                }
+                Input::Object { object: ObjectInput::Shared { id, initial_shared_version }, mutable } => {
+                    out.extend_from_slice(&[1, 1]); // CallArg::Object(SharedObject)
+                    out.extend_from_slice(id);
+                    out.extend_from_slice(&initial_shared_version.to_le_bytes());
+                    out.push(*mutable as u8);
+                }
+            }
+        }
+
+        uleb128(&mut out, 1);
+        out.push(0); // Command::MoveCall
+        out.extend_from_slice(&self.package);
+        string(&mut out, self.module);
+        string(&mut out, self.function);
+        uleb128(&mut out, self.type_args.len() as u64);
+        for ty in self.type_args {
+            type_tag(&mut out, ty)?;
+        }
+        uleb128(&mut out, self.inputs.len() as u64);
+        for i in 0..self.inputs.len() {
+            out.push(1); // Argument::Input
+            out.extend_from_slice(&(i as u16).to_le_bytes());
+        }
+
+        out.extend_from_slice(&self.sender);
+        // GasData { payment, owner, price, budget }
+        uleb128(&mut out, 1);
+        object_ref_bytes(&mut out, &self.gas.coin);
+        out.extend_from_slice(&self.sender);
+        out.extend_from_slice(&self.gas.price.to_le_bytes());
+        out.extend_from_slice(&self.gas_budget.to_le_bytes());
+        match self.expiration {
+            None => out.push(0), // TransactionExpiration::None
+            Some(epoch) => {
+                out.push(1); // TransactionExpiration::Epoch
+                out.extend_from_slice(&epoch.to_le_bytes());
+            }
+        }
+        Ok(out)
+    }
+}
+
+fn object_ref_bytes(out: &mut Vec<u8>, r: &ObjectRef) {
+    out.extend_from_slice(&r.id);
+    out.extend_from_slice(&r.version.to_le_bytes());
+    uleb128(out, r.digest.len() as u64);
+    out.extend_from_slice(&r.digest);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arb_types::test_support::{spawn_mock_rpc, Reply};
+
+    #[test]
+    fn test_parse_object_owners() {
+        let sender = parse_address("0xaa").unwrap();
+        let digest = "1".repeat(32);
+        let shared = json!({
+            "objectId": "0x5", "version": "12", "digest": digest,
+            "owner": { "Shared": { "initial_shared_version": 3 } }
+        });
+        assert_eq!(
+            parse_object(&shared, &sender).unwrap(),
+            ObjectInput::Shared { id: parse_address("0x5").unwrap(), initial_shared_version: 3 }
+        );
+
+        let owned = json!({ "objectId": "0x7", "version": "9", "digest": digest, "owner": { "AddressOwner": "0xaa" } });
+        let ObjectInput::Owned(r) = parse_object(&owned, &sender).unwrap() else {
+            panic!("owned object parsed as shared");
+        };
+        assert_eq!((r.version, r.digest), (9, [0; 32]));
+
+        let immutable = json!({ "objectId": "0x8", "version": "1", "digest": digest, "owner": "Immutable" });
+        assert!(matches!(parse_object(&immutable, &sender).unwrap(), ObjectInput::Owned(_)));
+
+        let theirs = json!({ "objectId": "0x9", "version": "1", "digest": digest, "owner": { "AddressOwner": "0xbb" } });
+        assert!(parse_object(&theirs, &sender).is_err());
+    }
+
+    #[test]
+    fn test_param_mutability() {
+        let sui_struct = |module: &str, name: &str| {
+            json!({ "Struct": { "address": "0x2", "module": module, "name": name, "typeArguments": [] } })
+        };
+        let normalized = json!({
+            "visibility": "Private",
+            "isEntry": true,
+            "typeParameters": [],
+            "parameters": [
+                { "Reference": sui_struct("clock", "Clock") },
+                { "MutableReference": sui_struct("coin", "Coin") },
+                sui_struct("coin", "Coin"),
+                "U64",
+                { "MutableReference": sui_struct("tx_context", "TxContext") }
+            ],
+            "return": []
+        });
+        assert_eq!(param_mutability(&normalized).unwrap(), [false, true, true, true, true]);
+        assert!(param_mutability(&json!({ "isEntry": true })).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_gas_coin_covers_the_capped_budget() {
+        // One coin holding 30M MIST: enough for a 20M budget, not the 50M cap
+        let url = spawn_mock_rpc(|request| {
+            Reply::Json(match request["method"].as_str().unwrap_or_default() {
+                "suix_getReferenceGasPrice" => json!({ "jsonrpc": "2.0", "id": 1, "result": "750" }),
+                _ => json!({ "jsonrpc": "2.0", "id": 1, "result": { "data": [{
+                    "coinObjectId": "0x9a5", "version": "4", "digest": "1".repeat(32), "balance": "30000000"
+                }] } }),
+            })
+        })
+        .await;
+        let builder = LocalTxBuilder::new(RpcClient::new(&[url]).unwrap(), "0xabc", "0xaa", 50_000_000).unwrap();
+
+        let gas = builder.gas(&[], None, 20_000_000).await.unwrap();
+        assert_eq!(gas.coin.id, parse_address("0x9a5").unwrap());
+        let err = builder.gas(&[], None, 50_000_000).await.unwrap_err();
+        assert!(err.to_string().contains("below the 50000000 MIST budget"), "{err}");
+    }
+}
@@ -1,26 +1,82 @@
 use anyhow::{Context, Result};
+use arb_types::log_throttle::DEFAULT_INTERVAL;
+use arb_types::warn_throttled;
 use arb_types::config::Config;
 use arb_types::opportunity::{ArbOpportunity, StrategyType};
-use reqwest::Client;
+use arb_types::rpc::RpcClient;
 use serde_json::{json, Value};
-use tracing::debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::gas_coin_pool::GasCoinPool;
+use crate::gas_oracle::GasOracle;
+use crate::inventory::Inventory;
+use crate::local_ptb::LocalTxBuilder;
+use crate::tx_verify::{BuiltTx, ExpectedCall, ExpectedTx};
+
+/// Delay before the first retry of a transient build failure; doubles per retry.
+const RETRY_BACKOFF_MS: u64 = 50;
+
+/// Per-request timeout for `unsafe_moveCall`.
+const RPC_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Why a PTB build failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildErrorKind {
+    /// HTTP transport or decode failure, or an RPC-side overload / rate
+    /// limit. Not the strategy's fault — worth retrying.
+    Transient,
+    /// `unsafe_moveCall` rejected the call (bad object, type arg, or value
+    /// argument), or the args couldn't be assembled. Retrying won't help.
+    Rejected,
+}
+
+/// A failed PTB build, classified for retry and failure tracking.
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct BuildError {
+    pub kind: BuildErrorKind,
+    pub message: String,
+}
+
+impl BuildError {
+    fn transient(e: impl std::fmt::Display) -> Self {
+        Self { kind: BuildErrorKind::Transient, message: e.to_string() }
+    }
+
+    fn rejected(e: impl std::fmt::Display) -> Self {
+        Self { kind: BuildErrorKind::Rejected, message: e.to_string() }
+    }
+
+    pub fn is_transient(&self) -> bool {
+        self.kind == BuildErrorKind::Transient
+    }
+}
 
 /// Builds Programmable Transaction Blocks (PTBs) for arb strategies.
 ///
 /// Each strategy maps to a specific Move entry function call with
 /// the correct object IDs, type arguments, and value arguments.
 pub struct PtbBuilder {
-    client: Client,
-    rpc_url: String,
+    rpc: RpcClient,
     package_id: String,
     admin_cap_id: String,
     pause_flag_id: String,
     sender: String,
+    /// Flat budget, and the ceiling on the oracle's suggestions
     gas_budget: u64,
+    /// Epochs a locally built transaction stays valid for (None = never expires)
+    tx_expiration_epochs: Option<u64>,
+    /// Scales each strategy's budget by the live reference gas price
+    gas_oracle: Option<GasOracle>,
+    /// Leases each transaction its own gas coin (None = node/builder picks)
+    gas_coins: Option<GasCoinPool>,
     // DEX shared objects
     cetus_global_config: String,
     turbos_versioned: String,
     flowx_versioned: String,
+    kriya_version: String,
     // Aftermath shared objects
     aftermath_registry: String,
     aftermath_fee_vault: String,
@@ -31,24 +87,32 @@ pub struct PtbBuilder {
     flowx_container: String,
     // DeepBook fee coin
     deep_fee_coin_id: String,
+    /// Extra attempts after a transient build failure.
+    max_retries: u32,
+    /// Fraction of expected profit enforced on-chain as `min_profit`.
+    min_profit_fraction: f64,
+    /// Wallet coins funding direct (flash-loan-free) strategies.
+    inventory: Option<Inventory>,
+    /// Encodes transactions locally; `unsafe_moveCall` is the fallback.
+    local: Option<LocalTxBuilder>,
 }
 
 impl PtbBuilder {
     pub fn new(config: &Config, sender: &str) -> Self {
         Self {
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(5))
-                .build()
-                .expect("Failed to create HTTP client"),
-            rpc_url: config.rpc_url.clone(),
+            rpc: RpcClient::from_config(config).with_timeout(RPC_TIMEOUT),
             package_id: config.package_id.clone(),
             admin_cap_id: config.admin_cap_id.clone(),
             pause_flag_id: config.pause_flag_id.clone(),
             sender: sender.to_string(),
             gas_budget: config.max_gas_budget,
+            tx_expiration_epochs: config.tx_expiration_epochs,
+            gas_oracle: None,
+            gas_coins: None,
             cetus_global_config: config.cetus_global_config.clone(),
             turbos_versioned: config.turbos_versioned.clone(),
             flowx_versioned: config.flowx_versioned.clone(),
+            kriya_version: config.kriya_version.clone(),
             flowx_container: config.flowx_container.clone(),
             aftermath_registry: config.aftermath_registry.clone(),
             aftermath_fee_vault: config.aftermath_fee_vault.clone(),
@@ -56,61 +120,245 @@ impl PtbBuilder {
             aftermath_insurance: config.aftermath_insurance.clone(),
             aftermath_referral: config.aftermath_referral.clone(),
             deep_fee_coin_id: config.deep_fee_coin_id.clone(),
+            max_retries: 1,
+            min_profit_fraction: config.min_profit_fraction,
+            inventory: None,
+            local: None,
+        }
+    }
+
+    /// Fraction of expected profit enforced on-chain as `min_profit`.
+    pub fn min_profit_fraction(&self) -> f64 {
+        self.min_profit_fraction
+    }
+
+    /// Retry transient build failures up to `n` times (0 = no retry).
+    pub fn with_max_retries(mut self, n: u32) -> Self {
+        self.max_retries = n;
+        self
+    }
+
+    /// Build through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(RPC_TIMEOUT);
+        self
+    }
+
+    /// Fund direct strategies from `inventory`.
+    pub fn with_inventory(mut self, inventory: Inventory) -> Self {
+        self.inventory = Some(inventory);
+        self
+    }
+
+    /// Budget gas per strategy from `oracle`'s reference price instead of
+    /// the flat `max_gas_budget`, which stays as the ceiling.
+    pub fn with_gas_oracle(mut self, oracle: GasOracle) -> Self {
+        self.gas_oracle = Some(oracle);
+        self
+    }
+
+    /// Pay for each transaction with a coin leased from `pool`, so
+    /// transactions in flight together don't share a gas coin. The lease
+    /// rides on the [`BuiltTx`] until it is dropped.
+    pub fn with_gas_coin_pool(mut self, pool: GasCoinPool) -> Self {
+        self.gas_coins = Some(pool);
+        self
+    }
+
+    /// Gas budget for one `strategy` transaction.
+    pub fn gas_budget_for(&self, strategy: StrategyType) -> u64 {
+        self.gas_oracle
+            .as_ref()
+            .map_or(self.gas_budget, |o| o.suggested_budget(strategy).min(self.gas_budget))
+    }
+
+    /// Encode transactions locally instead of calling `unsafe_moveCall`,
+    /// which stays as the fallback. Uses the client set by
+    /// [`with_rpc`](Self::with_rpc), so call that first. Only local builds
+    /// carry the configured transaction expiration, so with one set there
+    /// is no fallback.
+    pub fn with_local_build(mut self) -> Result<Self> {
+        self.local = Some(
+            LocalTxBuilder::new(self.rpc.clone(), &self.package_id, &self.sender, self.gas_budget)?
+                .with_expiration_epochs(self.tx_expiration_epochs),
+        );
+        Ok(self)
+    }
+
+    /// Resolve the configured shared objects, `pool_ids`, and the gas coin
+    /// for local builds ahead of the first candidate. No-op for RPC builds.
+    pub async fn warm_up(&self, pool_ids: &[String]) -> Result<()> {
+        let Some(local) = &self.local else {
+            return Ok(());
+        };
+        let mut ids: Vec<String> = [
+            &self.admin_cap_id,
+            &self.pause_flag_id,
+            &self.cetus_global_config,
+            &self.turbos_versioned,
+            &self.flowx_versioned,
+            &self.kriya_version,
+            &self.flowx_container,
+            &self.aftermath_registry,
+            &self.aftermath_fee_vault,
+            &self.aftermath_treasury,
+            &self.aftermath_insurance,
+            &self.aftermath_referral,
+            &self.deep_fee_coin_id,
+        ]
+        .into_iter()
+        .filter(|id| !id.is_empty())
+        .cloned()
+        .collect();
+        ids.push("0x6".to_string());
+        ids.extend(pool_ids.iter().cloned());
+        local.warm_up(&ids).await
+    }
+
+    /// A transaction was submitted: owned objects it used (admin cap, gas
+    /// coin, ..) have new versions, so local builds must re-fetch them.
+    pub fn invalidate_owned_objects(&self) {
+        if let Some(local) = &self.local {
+            local.invalidate_owned();
         }
     }
 
+    /// The inventory coin to fund a direct strategy with `opp`'s input.
+    fn inventory_coin(&self, opp: &ArbOpportunity) -> Result<Value> {
+        let coin_type = opp.type_args.first().context("Direct strategy needs type args")?;
+        let coin = self
+            .inventory
+            .as_ref()
+            .and_then(|inv| inv.covering(coin_type, opp.amount_in))
+            .with_context(|| format!("No inventory coin of {} covers {}", coin_type, opp.amount_in))?;
+        Ok(json!(coin.object_id))
+    }
+
     /// Build a transaction for the given opportunity.
-    /// Returns the serialized transaction bytes (base64).
-    pub async fn build(&self, opp: &ArbOpportunity) -> Result<String> {
+    /// Returns the serialized transaction bytes (base64) together with the
+    /// call they must contain, checked by the signer before signing.
+    ///
+    /// Transient failures are retried up to `max_retries` times with a short
+    /// backoff; rejected calls fail immediately. A failed local build falls
+    /// back to `unsafe_moveCall` unless a transaction expiration is set, in
+    /// which case it fails as transient.
+    pub async fn build(&self, opp: &ArbOpportunity) -> Result<BuiltTx, BuildError> {
         let module = opp.strategy.move_module();
         let function = opp.strategy.move_function_name();
 
-        let (args, type_args) = self.build_args(opp)?;
+        let (args, type_args) = self.build_args(opp).map_err(|e| BuildError::rejected(format!("{:#}", e)))?;
+        let gas_budget = self.gas_budget_for(opp.strategy);
+        // Not the strategy's fault: a coin frees up when a transaction settles
+        let gas_coin = match &self.gas_coins {
+            Some(pool) => Some(Arc::new(
+                pool.acquire().ok_or_else(|| BuildError::transient("No free gas coin in the pool"))?,
+            )),
+            None => None,
+        };
+        let gas_coin_id = gas_coin.as_ref().map(|c| c.object_id.as_str());
 
         debug!(
             module = %module,
             function = %function,
             amount = %opp.amount_in,
+            gas_budget = %gas_budget,
             "Building PTB"
         );
 
-        // Use unsafe_moveCall to build the transaction
-        let response = self
-            .client
-            .post(&self.rpc_url)
-            .json(&json!({
-                "jsonrpc": "2.0",
-                "id": 1,
-                "method": "unsafe_moveCall",
-                "params": [
-                    self.sender,
-                    self.package_id,
-                    module,
-                    function,
-                    type_args,
-                    args,
-                    null,  // gas object (auto-select)
-                    self.gas_budget.to_string(),
-                ]
-            }))
-            .send()
-            .await
-            .context("Failed to build PTB via RPC")?;
+        let built = |tx_bytes: String| {
+            let call = ExpectedCall {
+                package: self.package_id.clone(),
+                module: module.to_string(),
+                function: function.to_string(),
+                type_args: type_args.clone(),
+                args: args.clone(),
+            };
+            BuiltTx {
+                tx_bytes,
+                expected: ExpectedTx::move_call(&self.sender, gas_budget, call),
+                gas_coin: gas_coin.clone(),
+            }
+        };
 
-        let body: Value = response.json().await?;
+        if let Some(local) = &self.local {
+            match local.build(module, function, &type_args, &args, gas_budget, gas_coin_id).await {
+                Ok(tx_bytes) => return Ok(built(tx_bytes)),
+                // unsafe_moveCall can't set an expiration: rather skip the
+                // candidate than send one that could land epochs late
+                Err(e) if self.tx_expiration_epochs.is_some() => {
+                    return Err(BuildError::transient(format!(
+                        "Local PTB build failed, and unsafe_moveCall can't carry TX_EXPIRATION_EPOCHS: {e:#}"
+                    )));
+                }
+                Err(e) => warn_throttled!(
+                    "ptb_builder.local_fallback",
+                    DEFAULT_INTERVAL,
+                    strategy = %opp.strategy,
+                    error = %format!("{e:#}"),
+                    "Local PTB build failed — falling back to unsafe_moveCall"
+                ),
+            }
+        }
+
+        let mut attempt = 0;
+        loop {
+            match self.move_call(module, function, &type_args, &args, gas_budget, gas_coin_id).await {
+                Err(e) if e.is_transient() && attempt < self.max_retries => {
+                    attempt += 1;
+                    warn!(
+                        strategy = %opp.strategy,
+                        attempt = %attempt,
+                        error = %e,
+                        "Transient PTB build failure — retrying"
+                    );
+                    tokio::time::sleep(Duration::from_millis(RETRY_BACKOFF_MS << (attempt - 1))).await;
+                }
+                result => return Ok(built(result?)),
+            }
+        }
+    }
+
+    /// One `unsafe_moveCall` round trip.
+    async fn move_call(
+        &self,
+        module: &str,
+        function: &str,
+        type_args: &[String],
+        args: &[Value],
+        gas_budget: u64,
+        gas_coin: Option<&str>,
+    ) -> Result<String, BuildError> {
+        let params = json!([
+            self.sender,
+            self.package_id,
+            module,
+            function,
+            type_args,
+            args,
+            gas_coin, // gas object (null = auto-select)
+            gas_budget.to_string(),
+        ]);
+        // Every endpoint failing (timeout, 429, 5xx) is the nodes' problem
+        let body = self
+            .rpc
+            .call("unsafe_moveCall", params)
+            .await
+            .map_err(|e| BuildError::transient(format!("Failed to build PTB via RPC: {}", e)))?;
 
         if let Some(error) = body.get("error") {
-            anyhow::bail!("PTB build error: {}", error);
+            let message = format!("PTB build error: {}", error);
+            return Err(if is_transient_rpc_error(error) {
+                BuildError::transient(message)
+            } else {
+                BuildError::rejected(message)
+            });
         }
 
-        let tx_bytes = body
-            .get("result")
+        body.get("result")
             .and_then(|r| r.get("txBytes"))
             .and_then(|t| t.as_str())
-            .context("Missing txBytes in response")?
-            .to_string();
-
-        Ok(tx_bytes)
+            .map(str::to_string)
+            .ok_or_else(|| BuildError::transient("Missing txBytes in response"))
     }
 
     // ── Argument helpers ──
@@ -132,6 +380,32 @@ impl PtbBuilder {
         ]
     }
 
+    /// DeepBook fee coin argument. An unset `DEEP_FEE_COIN_ID` makes every
+    /// DeepBook build fail, so flag it (throttled — it fires every cycle).
+    fn deep_fee_coin(&self) -> Value {
+        if self.deep_fee_coin_id.is_empty() {
+            warn_throttled!(
+                "ptb_builder.deep_fee_coin_missing",
+                DEFAULT_INTERVAL,
+                "DEEP_FEE_COIN_ID not set — DeepBook strategies cannot build"
+            );
+        }
+        json!(self.deep_fee_coin_id)
+    }
+
+    /// Kriya version argument. Unset, every Kriya build fails the same way
+    /// as a missing DeepBook fee coin.
+    fn kriya_version(&self) -> Value {
+        if self.kriya_version.is_empty() {
+            warn_throttled!(
+                "ptb_builder.kriya_version_missing",
+                DEFAULT_INTERVAL,
+                "KRIYA_VERSION not set — Kriya strategies cannot build"
+            );
+        }
+        json!(self.kriya_version)
+    }
+
     /// Tail arguments: amount, min_profit, clock.
     fn tail_args(&self, amount: &str, min_profit: &str) -> Vec<Value> {
         vec![json!(amount), json!(min_profit), json!("0x6")]
@@ -140,7 +414,7 @@ impl PtbBuilder {
     /// Build the argument list for a specific strategy.
     fn build_args(&self, opp: &ArbOpportunity) -> Result<(Vec<Value>, Vec<String>)> {
         // Validate pool_ids length matches strategy requirements
-        let expected_pools = if opp.strategy.move_module() == "tri_hop" { 3 } else { 2 };
+        let expected_pools = opp.strategy.hops();
         anyhow::ensure!(
             opp.pool_ids.len() >= expected_pools,
             "Strategy {:?} requires {} pool IDs, got {}",
@@ -148,12 +422,22 @@ impl PtbBuilder {
             expected_pools,
             opp.pool_ids.len()
         );
+        // Type args must match the entry function's generics one for one: a
+        // missing or misplaced fee tier type aborts the call on-chain
+        let layout = opp.strategy.type_arg_layout();
+        anyhow::ensure!(
+            opp.type_args.len() == layout.len(),
+            "Strategy {:?} takes {} type args <{}>, got {}",
+            opp.strategy,
+            layout.len(),
+            layout.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", "),
+            opp.type_args.len()
+        );
 
         let amount = opp.amount_in.to_string();
-        // Use 90% of expected_profit as min_profit guard (tight but allows for minor slippage).
-        // Floor at 1 MIST so the on-chain assert_profit() check is never no-op.
-        let min_profit_raw = opp.expected_profit * 9 / 10;
-        let min_profit = min_profit_raw.max(1).to_string();
+        // Enforce a fraction (default 90%) of expected_profit on-chain — tight
+        // but allows for minor slippage.
+        let min_profit = opp.min_profit(self.min_profit_fraction).to_string();
 
         debug!(
             amount = %amount,
@@ -186,6 +470,31 @@ impl PtbBuilder {
                 a
             }
 
+            // ═══════════════════════════════════════
+            //  Direct (inventory-funded): Cetus ↔ Turbos
+            // ═══════════════════════════════════════
+            StrategyType::DirectCetusToTurbos => {
+                let mut a = self.base_args();
+                a.push(json!(self.cetus_global_config));
+                a.push(json!(opp.pool_ids[0])); // cetus_pool (buy leg)
+                a.push(json!(opp.pool_ids[1])); // turbos_pool
+                a.push(json!(self.turbos_versioned));
+                a.push(self.inventory_coin(opp)?);
+                a.extend(self.tail_args(&amount, &min_profit));
+                a
+            }
+
+            StrategyType::DirectTurbosToCetus => {
+                let mut a = self.base_args();
+                a.push(json!(self.cetus_global_config));
+                a.push(json!(opp.pool_ids[1])); // cetus_pool
+                a.push(json!(opp.pool_ids[0])); // turbos_pool (buy leg)
+                a.push(json!(self.turbos_versioned));
+                a.push(self.inventory_coin(opp)?);
+                a.extend(self.tail_args(&amount, &min_profit));
+                a
+            }
+
             // ═══════════════════════════════════════
             //  Two-hop: Cetus ↔ DeepBook
             // ═══════════════════════════════════════
@@ -194,7 +503,7 @@ impl PtbBuilder {
                 a.push(json!(self.cetus_global_config));
                 a.push(json!(opp.pool_ids[0])); // cetus_pool
                 a.push(json!(opp.pool_ids[1])); // deepbook_pool
-                a.push(json!(self.deep_fee_coin_id));
+                a.push(self.deep_fee_coin());
                 a.extend(self.tail_args(&amount, &min_profit));
                 a
             }
@@ -204,7 +513,7 @@ impl PtbBuilder {
                 a.push(json!(self.cetus_global_config));
                 a.push(json!(opp.pool_ids[1])); // cetus_pool
                 a.push(json!(opp.pool_ids[0])); // deepbook_pool (flash source)
-                a.push(json!(self.deep_fee_coin_id));
+                a.push(self.deep_fee_coin());
                 a.extend(self.tail_args(&amount, &min_profit));
                 a
             }
@@ -217,7 +526,7 @@ impl PtbBuilder {
                 a.push(json!(opp.pool_ids[0])); // turbos_pool
                 a.push(json!(self.turbos_versioned));
                 a.push(json!(opp.pool_ids[1])); // deepbook_pool
-                a.push(json!(self.deep_fee_coin_id));
+                a.push(self.deep_fee_coin());
                 a.extend(self.tail_args(&amount, &min_profit));
                 a
             }
@@ -227,7 +536,7 @@ impl PtbBuilder {
                 a.push(json!(opp.pool_ids[1])); // turbos_pool
                 a.push(json!(self.turbos_versioned));
                 a.push(json!(opp.pool_ids[0])); // deepbook_pool (flash source)
-                a.push(json!(self.deep_fee_coin_id));
+                a.push(self.deep_fee_coin());
                 a.extend(self.tail_args(&amount, &min_profit));
                 a
             }
@@ -262,7 +571,7 @@ impl PtbBuilder {
             StrategyType::DeepBookToAftermath => {
                 let mut a = self.base_args();
                 a.push(json!(opp.pool_ids[0])); // deepbook_pool
-                a.push(json!(self.deep_fee_coin_id));
+                a.push(self.deep_fee_coin());
                 a.extend(self.aftermath_args(&opp.pool_ids[1]));
                 a.extend(self.tail_args(&amount, &min_profit));
                 a
@@ -320,7 +629,7 @@ impl PtbBuilder {
             StrategyType::DeepBookToFlowxClmm => {
                 let mut a = self.base_args();
                 a.push(json!(opp.pool_ids[0])); // deepbook_pool
-                a.push(json!(self.deep_fee_coin_id));
+                a.push(self.deep_fee_coin());
                 a.push(json!(opp.pool_ids[1])); // flowx_pool
                 a.push(json!(self.flowx_versioned));
                 a.extend(self.tail_args(&amount, &min_profit));
@@ -330,13 +639,36 @@ impl PtbBuilder {
             StrategyType::FlowxClmmToDeepBook => {
                 let mut a = self.base_args();
                 a.push(json!(opp.pool_ids[1])); // deepbook_pool
-                a.push(json!(self.deep_fee_coin_id));
+                a.push(self.deep_fee_coin());
                 a.push(json!(opp.pool_ids[0])); // flowx_pool (flash source)
                 a.push(json!(self.flowx_versioned));
                 a.extend(self.tail_args(&amount, &min_profit));
                 a
             }
 
+            // ═══════════════════════════════════════
+            //  Two-hop: Cetus ↔ Kriya CLMM
+            // ═══════════════════════════════════════
+            StrategyType::CetusToKriya => {
+                let mut a = self.base_args();
+                a.push(json!(self.cetus_global_config));
+                a.push(json!(opp.pool_ids[0])); // cetus_pool
+                a.push(json!(opp.pool_ids[1])); // kriya_pool
+                a.push(self.kriya_version());
+                a.extend(self.tail_args(&amount, &min_profit));
+                a
+            }
+
+            StrategyType::KriyaToCetus => {
+                let mut a = self.base_args();
+                a.push(json!(self.cetus_global_config));
+                a.push(json!(opp.pool_ids[1])); // cetus_pool
+                a.push(json!(opp.pool_ids[0])); // kriya_pool (flash source)
+                a.push(self.kriya_version());
+                a.extend(self.tail_args(&amount, &min_profit));
+                a
+            }
+
             // ═══════════════════════════════════════
             //  Two-hop: Cetus → FlowX AMM
             // ═══════════════════════════════════════
@@ -367,7 +699,7 @@ impl PtbBuilder {
             StrategyType::DeepBookToFlowxAmm => {
                 let mut a = self.base_args();
                 a.push(json!(opp.pool_ids[0])); // deepbook_pool
-                a.push(json!(self.deep_fee_coin_id));
+                a.push(self.deep_fee_coin());
                 a.push(json!(self.flowx_container)); // flowx container
                 a.extend(self.tail_args(&amount, &min_profit));
                 a
@@ -424,7 +756,7 @@ impl PtbBuilder {
                 a.push(json!(opp.pool_ids[1])); // turbos_pool_bc
                 a.push(json!(self.turbos_versioned));
                 a.push(json!(opp.pool_ids[2])); // deepbook_pool_ca
-                a.push(json!(self.deep_fee_coin_id));
+                a.push(self.deep_fee_coin());
                 a.extend(self.tail_args(&amount, &min_profit));
                 a
             }
@@ -437,7 +769,7 @@ impl PtbBuilder {
                 a.push(json!(self.cetus_global_config));
                 a.push(json!(opp.pool_ids[0])); // cetus_pool_ab
                 a.push(json!(opp.pool_ids[1])); // deepbook_pool_bc
-                a.push(json!(self.deep_fee_coin_id));
+                a.push(self.deep_fee_coin());
                 a.push(json!(opp.pool_ids[2])); // turbos_pool_ca
                 a.push(json!(self.turbos_versioned));
                 a.extend(self.tail_args(&amount, &min_profit));
@@ -451,7 +783,7 @@ impl PtbBuilder {
                 let mut a = self.base_args();
                 a.push(json!(self.cetus_global_config));
                 a.push(json!(opp.pool_ids[0])); // deepbook_pool_ac
-                a.push(json!(self.deep_fee_coin_id));
+                a.push(self.deep_fee_coin());
                 a.push(json!(opp.pool_ids[1])); // cetus_pool_ab
                 a.push(json!(opp.pool_ids[2])); // turbos_pool_bc
                 a.push(json!(self.turbos_versioned));
@@ -529,8 +861,376 @@ impl PtbBuilder {
                 a.extend(self.tail_args(&amount, &min_profit));
                 a
             }
+
+            // ═══════════════════════════════════════
+            //  Quad-hop: Cetus × Cetus × Cetus × Cetus
+            // ═══════════════════════════════════════
+            StrategyType::QuadCetusCetusCetusCetus => {
+                let mut a = self.base_args();
+                a.push(json!(self.cetus_global_config));
+                a.push(json!(opp.pool_ids[0])); // pool_ab
+                a.push(json!(opp.pool_ids[1])); // pool_bc
+                a.push(json!(opp.pool_ids[2])); // pool_cd
+                a.push(json!(opp.pool_ids[3])); // pool_da
+                a.extend(self.tail_args(&amount, &min_profit));
+                a
+            }
         };
 
         Ok((args, opp.type_args.clone()))
     }
 }
+
+/// Whether a JSON-RPC error from `unsafe_moveCall` is the node's problem
+/// (overload, rate limit, internal error) rather than a bad call. HTTP
+/// 429/5xx never get here: [`RpcClient`] fails over on them.
+fn is_transient_rpc_error(error: &Value) -> bool {
+    // -32603: internal error; -32000..-32099 is reserved for server errors,
+    // but Sui reports rejected calls as -32002, so only match by message there.
+    if error.get("code").and_then(|c| c.as_i64()) == Some(-32603) {
+        return true;
+    }
+    let message = error
+        .get("message")
+        .and_then(|m| m.as_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    ["rate limit", "too many requests", "timeout", "timed out", "overloaded", "unavailable"]
+        .iter()
+        .any(|p| message.contains(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx_verify::{shared_inputs, verify_tx_bytes};
+    use arb_types::opportunity::TypeArgSlot;
+    use arb_types::test_support::{spawn_mock_rpc, Reply};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const SENDER: &str = "0xaa";
+
+    fn builder() -> PtbBuilder {
+        let id = |s: &str| s.to_string();
+        PtbBuilder {
+            rpc: RpcClient::single("http://127.0.0.1:9"),
+            package_id: id("0xbeef"),
+            admin_cap_id: id("0xca9"),
+            pause_flag_id: id("0xf1a9"),
+            sender: id(SENDER),
+            gas_budget: 50_000_000,
+            tx_expiration_epochs: None,
+            gas_oracle: None,
+            gas_coins: None,
+            cetus_global_config: id("0xce75"),
+            turbos_versioned: id("0x7b05"),
+            flowx_versioned: id("0xf10"),
+            kriya_version: id("0xf5"),
+            aftermath_registry: id("0xa1"),
+            aftermath_fee_vault: id("0xa2"),
+            aftermath_treasury: id("0xa3"),
+            aftermath_insurance: id("0xa4"),
+            aftermath_referral: id("0xa5"),
+            flowx_container: id("0xf11"),
+            deep_fee_coin_id: id("0xdee9"),
+            max_retries: 0,
+            min_profit_fraction: 0.9,
+            inventory: None,
+            local: None,
+        }
+        .with_local_build()
+        .unwrap()
+    }
+
+    fn opp(strategy: StrategyType) -> ArbOpportunity {
+        ArbOpportunity {
+            strategy,
+            amount_in: 1_000_000_000,
+            expected_profit: 5_000_000,
+            estimated_gas: 3_000_000,
+            net_profit: 2_000_000,
+            pool_ids: vec!["0x1001".into(), "0x1002".into(), "0x1003".into(), "0x1004".into()],
+            type_args: strategy
+                .type_arg_layout()
+                .iter()
+                .map(|slot| match slot {
+                    TypeArgSlot::Coin(0) => "0x2::sui::SUI".to_string(),
+                    TypeArgSlot::Coin(1) => "0xdba3::usdc::USDC".to_string(),
+                    TypeArgSlot::Coin(n) => format!("0xc{n}::coin::COIN"),
+                    TypeArgSlot::TurbosFee => "0x91b::fee3000bps::FEE3000BPS".to_string(),
+                    TypeArgSlot::AftermathLp => "0xaf::lp::LP".to_string(),
+                })
+                .collect(),
+            detected_at_ms: 0,
+        }
+    }
+
+    /// `strategy`'s entry point as `sui_getNormalizedMoveFunction` returns
+    /// it, down to what the local builder reads: each parameter's kind,
+    /// taken from its signature in `sources/strategies`.
+    fn normalized_function(strategy: StrategyType) -> Value {
+        let path = format!("{}/../../../sources/strategies/{}.move", env!("CARGO_MANIFEST_DIR"), strategy.move_module());
+        let source = std::fs::read_to_string(&path).unwrap();
+        let Some(start) = source.find(&format!("entry fun {}<", strategy.move_function_name())) else {
+            // The FlowX AMM legs have no entry point in the package yet:
+            // take every parameter mutably
+            assert!(
+                matches!(
+                    strategy,
+                    StrategyType::CetusToFlowxAmm | StrategyType::TurbosToFlowxAmm | StrategyType::DeepBookToFlowxAmm
+                ),
+                "{} not in {}",
+                strategy.move_function_name(),
+                path
+            );
+            return json!({ "parameters": vec![json!({ "MutableReference": {} }); 16] });
+        };
+        let signature = &source[start..];
+        let params = &signature[signature.find('(').unwrap() + 1..signature.find(')').unwrap()];
+        let parameters: Vec<Value> = params
+            .lines()
+            .filter_map(|line| line.trim().trim_end_matches(',').split_once(": "))
+            .map(|(_, ty)| {
+                let name = |ty: &str| json!({ "Struct": { "name": ty.split('<').next().unwrap() } });
+                match ty {
+                    "u64" => json!("U64"),
+                    _ if ty.starts_with("&mut ") => json!({ "MutableReference": name(&ty[5..]) }),
+                    _ if ty.starts_with('&') => json!({ "Reference": name(&ty[1..]) }),
+                    _ => name(ty),
+                }
+            })
+            .collect();
+        json!({ "isEntry": true, "parameters": parameters })
+    }
+
+    /// Build `opp` locally, taking the admin cap and the DeepBook fee coin
+    /// as owned objects.
+    fn build_local(builder: &PtbBuilder, opp: &ArbOpportunity, args: &[Value], type_args: &[String]) -> Vec<u8> {
+        let (module, function) = (opp.strategy.move_module(), opp.strategy.move_function_name());
+        let normalized = normalized_function(opp.strategy);
+        let local = builder.local.as_ref().unwrap();
+        local.build_cached(module, function, &normalized, type_args, args, &["0xca9", "0xdee9"]).unwrap()
+    }
+
+    /// A locally encoded transaction carries the same call, in the same
+    /// argument order, as the JSON sent to `unsafe_moveCall` — checked by
+    /// the signer's pre-signing verifier against that JSON.
+    fn assert_local_matches_json(strategy: StrategyType) {
+        let builder = builder();
+        let opp = opp(strategy);
+        let (args, type_args) = builder.build_args(&opp).unwrap();
+        let (module, function) = (strategy.move_module(), strategy.move_function_name());
+        let bytes = build_local(&builder, &opp, &args, &type_args);
+
+        let call = ExpectedCall {
+            package: "0xbeef".into(),
+            module: module.into(),
+            function: function.into(),
+            type_args,
+            args,
+        };
+        let expected = ExpectedTx::move_call(SENDER, builder.gas_budget, call);
+        if let Err(e) = verify_tx_bytes(&bytes, &expected) {
+            panic!("{:?}: {}", strategy, e);
+        }
+    }
+
+    #[test]
+    fn test_local_build_matches_json_args() {
+        // Direct strategies need an inventory coin, which this builder lacks
+        for strategy in StrategyType::all().filter(|s| !s.is_direct()) {
+            assert_local_matches_json(strategy);
+        }
+    }
+
+    #[test]
+    fn test_local_build_takes_read_only_objects_immutably() {
+        let padded = |id: &str| format!("0x{:0>64}", id.trim_start_matches("0x"));
+        // The pause flag, Cetus GlobalConfig, Turbos/FlowX/Kriya version
+        // objects, Aftermath registry/fee vault/referral vault and the clock
+        let read_only: Vec<String> =
+            ["0xf1a9", "0xce75", "0x7b05", "0xf10", "0xf5", "0xa1", "0xa2", "0xa5", "0x6"].map(padded).into();
+        let builder = builder();
+        for strategy in [
+            StrategyType::CetusToTurbos,
+            StrategyType::CetusToDeepBook,
+            StrategyType::CetusToAftermath,
+            StrategyType::FlowxClmmToCetus,
+            StrategyType::KriyaToCetus,
+            StrategyType::TriCetusCetusTurbos,
+        ] {
+            let opp = opp(strategy);
+            let (args, type_args) = builder.build_args(&opp).unwrap();
+            let shared = shared_inputs(&build_local(&builder, &opp, &args, &type_args)).unwrap();
+            assert!(shared.iter().any(|(id, _)| *id == padded("0x6")), "{:?} takes the clock", strategy);
+            for (id, mutable) in shared {
+                // Pools (and Aftermath's treasury and insurance fund) are written
+                assert_eq!(mutable, !read_only.contains(&id), "{:?}: {}", strategy, id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_local_build_sets_expiration_epoch() {
+        let opp = opp(StrategyType::CetusToTurbos);
+        let (module, function) = (opp.strategy.move_module(), opp.strategy.move_function_name());
+
+        let never = builder();
+        let (args, type_args) = never.build_args(&opp).unwrap();
+        let bytes = build_local(&never, &opp, &args, &type_args);
+        assert_eq!(bytes.last(), Some(&0), "TransactionExpiration::None");
+
+        let mut expiring = builder();
+        expiring.tx_expiration_epochs = Some(2);
+        let expiring = expiring.with_local_build().unwrap();
+        let bytes = build_local(&expiring, &opp, &args, &type_args);
+        let mut tail = vec![1]; // TransactionExpiration::Epoch
+        tail.extend_from_slice(&(crate::local_ptb::TEST_EPOCH + 2).to_le_bytes());
+        assert!(bytes.ends_with(&tail), "expires two epochs after the current one");
+
+        // Still the same call to the verifier
+        let call = ExpectedCall {
+            package: "0xbeef".into(),
+            module: module.into(),
+            function: function.into(),
+            type_args,
+            args,
+        };
+        verify_tx_bytes(&bytes, &ExpectedTx::move_call(SENDER, expiring.gas_budget, call)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_local_build_with_expiration_skips_unsafe_move_call() {
+        // Object lookups fail, so every local build does; unsafe_moveCall works
+        let move_calls = Arc::new(AtomicUsize::new(0));
+        let counter = move_calls.clone();
+        let url = spawn_mock_rpc(move |request| {
+            Reply::Json(match request["method"].as_str().unwrap_or_default() {
+                "unsafe_moveCall" => {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    json!({ "jsonrpc": "2.0", "id": 1, "result": { "txBytes": "AAEC" } })
+                }
+                _ => json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32000, "message": "unavailable" } }),
+            })
+        })
+        .await;
+        let rpc = RpcClient::new(&[url]).unwrap();
+
+        let fallback = builder().with_rpc(rpc.clone()).with_local_build().unwrap();
+        let built = fallback.build(&opp(StrategyType::CetusToTurbos)).await.unwrap();
+        assert_eq!(built.tx_bytes, "AAEC");
+        assert_eq!(move_calls.load(Ordering::SeqCst), 1);
+
+        let mut expiring = builder().with_rpc(rpc);
+        expiring.tx_expiration_epochs = Some(2);
+        let expiring = expiring.with_local_build().unwrap();
+        let err = expiring.build(&opp(StrategyType::CetusToTurbos)).await.unwrap_err();
+        assert!(err.is_transient(), "{}", err);
+        assert!(err.to_string().contains("TX_EXPIRATION_EPOCHS"), "{}", err);
+        assert_eq!(move_calls.load(Ordering::SeqCst), 1, "no expiration-less fallback");
+    }
+
+    #[test]
+    fn test_gas_budget_follows_oracle_under_ceiling() {
+        let flat = builder();
+        assert_eq!(flat.gas_budget_for(StrategyType::CetusToTurbos), 50_000_000);
+
+        // The oracle's own ceiling is looser than the builder's
+        let oracle = GasOracle::new("http://localhost:9000", u64::MAX);
+        let dynamic = builder().with_gas_oracle(oracle.clone());
+        oracle.set_price(750);
+        assert_eq!(dynamic.gas_budget_for(StrategyType::CetusToTurbos), 15_000_000);
+        oracle.set_price(10_000);
+        assert_eq!(dynamic.gas_budget_for(StrategyType::CetusToTurbos), 50_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_build_without_free_gas_coin_is_transient() {
+        let pool = GasCoinPool::new("http://localhost:9000", SENDER, 1, 0);
+        pool.set_coins(vec![crate::GasCoin { object_id: "0x9a5".into(), balance: 1_000_000_000 }]);
+        let builder = builder().with_gas_coin_pool(pool.clone());
+
+        // The only coin is in flight: transient, so the strategy isn't blamed
+        let lease = pool.acquire().unwrap();
+        let err = builder.build(&opp(StrategyType::CetusToTurbos)).await.unwrap_err();
+        assert!(err.is_transient(), "{}", err);
+        drop(lease);
+        assert_eq!(pool.free_count(), 1);
+    }
+
+    #[test]
+    fn test_reverse_strategies_keep_pool_order() {
+        // The reverse entry points take the same objects and the same
+        // <A, B, ...> as the forward ones: only the swap directions differ
+        let builder = builder();
+        for (forward, reverse) in [
+            (StrategyType::CetusToTurbos, StrategyType::CetusToTurbosRev),
+            (StrategyType::CetusToAftermath, StrategyType::CetusToAftermathRev),
+        ] {
+            let rev_opp = opp(reverse);
+            let mut fwd_opp = rev_opp.clone();
+            fwd_opp.strategy = forward;
+
+            let (rev_args, rev_types) = builder.build_args(&rev_opp).unwrap();
+            let (fwd_args, fwd_types) = builder.build_args(&fwd_opp).unwrap();
+            assert_eq!(rev_args, fwd_args, "{:?}", reverse);
+            assert_eq!(rev_types, fwd_types, "{:?}", reverse);
+            assert_eq!(rev_types[0], "0x2::sui::SUI");
+            assert_ne!(reverse.move_function_name(), forward.move_function_name());
+        }
+    }
+
+    #[test]
+    fn test_build_args_checks_type_arg_layout() {
+        let builder = builder();
+        // The scanner couldn't resolve the Turbos fee tier type
+        let mut missing_fee = opp(StrategyType::TriCetusCetusTurbos);
+        missing_fee.type_args.pop();
+        let err = builder.build_args(&missing_fee).unwrap_err().to_string();
+        assert!(err.contains("takes 4 type args <A, B, C, TurbosFee>, got 3"), "{}", err);
+
+        let mut extra = opp(StrategyType::CetusToDeepBook);
+        extra.type_args.push("0x91b::fee3000bps::FEE3000BPS".into());
+        assert!(builder.build_args(&extra).is_err());
+    }
+
+    #[test]
+    fn test_local_build_rejects_reordered_args() {
+        let builder = builder();
+        let opp = opp(StrategyType::CetusToTurbos);
+        let (mut args, type_args) = builder.build_args(&opp).unwrap();
+        let expected = ExpectedTx::move_call(
+            SENDER,
+            builder.gas_budget,
+            ExpectedCall {
+                package: "0xbeef".into(),
+                module: "two_hop".into(),
+                function: opp.strategy.move_function_name().into(),
+                type_args: type_args.clone(),
+                args: args.clone(),
+            },
+        );
+        // Swapping the two pools must not slip past the verifier
+        args.swap(3, 4);
+        let bytes = build_local(&builder, &opp, &args, &type_args);
+        assert!(verify_tx_bytes(&bytes, &expected).is_err());
+    }
+
+    #[test]
+    fn test_rate_limit_and_server_errors_are_transient() {
+        let err = json!({ "code": -32000, "message": "Too many requests" });
+        assert!(is_transient_rpc_error(&err));
+        assert!(is_transient_rpc_error(&json!({ "code": -32603, "message": "Internal error" })));
+    }
+
+    #[test]
+    fn test_move_call_parameter_errors_are_rejected() {
+        let err = json!({
+            "code": -32602,
+            "message": "Invalid params: Error checking transaction input objects: ObjectNotFound"
+        });
+        assert!(!is_transient_rpc_error(&err));
+        let err = json!({ "code": -32002, "message": "Type argument mismatch" });
+        assert!(!is_transient_rpc_error(&err));
+    }
+}
@@ -1,6 +1,11 @@
 use anyhow::{Context, Result};
+use arb_types::encoding::encode_base58;
+use arb_types::Config;
 use ed25519_dalek::{Signer as _, SigningKey, VerifyingKey};
 use base64::Engine as _;
+use std::path::Path;
+
+use crate::tx_verify::{verify_tx_bytes, ExpectedTx};
 
 /// Ed25519 transaction signer for Sui.
 ///
@@ -25,13 +30,63 @@ impl Signer {
             Self::decode_hex(key)?
         };
 
-        let signing_key = SigningKey::from_bytes(&key_bytes);
+        Ok(Self::from_key_bytes(&key_bytes))
+    }
+
+    /// Load the key for `address` from a Sui CLI keystore file
+    /// (`~/.sui/sui_config/sui.keystore`: a JSON array of base64-encoded
+    /// `flag || 32_byte_key` entries). Only Ed25519 entries are considered.
+    pub fn from_keystore(path: &Path, address: &str) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read keystore {}", path.display()))?;
+        let entries: Vec<String> = serde_json::from_str(&raw)
+            .with_context(|| format!("Keystore {} is not a JSON array of keys", path.display()))?;
+
+        let wanted = normalize_address(address);
+        let mut skipped = 0;
+        for (i, entry) in entries.iter().enumerate() {
+            let data = base64::engine::general_purpose::STANDARD
+                .decode(entry.trim())
+                .with_context(|| format!("Keystore entry {} is not valid base64", i))?;
+            let [0x00, key @ ..] = data.as_slice() else {
+                skipped += 1;
+                continue;
+            };
+            let key_bytes: [u8; 32] = key
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Keystore entry {} has a {}-byte key, expected 32", i, key.len()))?;
+            let signer = Self::from_key_bytes(&key_bytes);
+            if signer.address() == wanted {
+                return Ok(signer);
+            }
+        }
+        anyhow::bail!(
+            "No Ed25519 key for {} in keystore {} ({} entries, {} non-Ed25519 skipped)",
+            wanted,
+            path.display(),
+            entries.len(),
+            skipped
+        )
+    }
+
+    /// The wallet `config` selects: the keystore entry for
+    /// `keystore_address` if `keystore_path` is set, else `private_key_hex`.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        if config.keystore_path.is_empty() {
+            Self::from_hex(&config.private_key_hex)
+        } else {
+            Self::from_keystore(Path::new(&config.keystore_path), &config.keystore_address)
+        }
+    }
+
+    fn from_key_bytes(key_bytes: &[u8; 32]) -> Self {
+        let signing_key = SigningKey::from_bytes(key_bytes);
         let verifying_key = signing_key.verifying_key();
 
-        Ok(Self {
+        Self {
             signing_key,
             verifying_key,
-        })
+        }
     }
 
     /// Decode a hex-encoded private key (with or without "0x" prefix).
@@ -97,10 +152,15 @@ impl Signer {
 
     /// Sign transaction bytes and return the serialized signature.
     /// Format: base64(flag_byte || ed25519_signature || public_key)
-    pub fn sign_transaction(&self, tx_bytes_base64: &str) -> Result<String> {
+    ///
+    /// The bytes come from the RPC node, so they are decoded and checked
+    /// against `expected` first; a mismatch fails with a
+    /// [`TxMismatch`](crate::tx_verify::TxMismatch) and nothing is signed.
+    pub fn sign_transaction(&self, tx_bytes_base64: &str, expected: &ExpectedTx) -> Result<String> {
         let tx_bytes = base64::engine::general_purpose::STANDARD
-            .decode(tx_bytes_base64)
+            .decode(tx_bytes_base64.trim())
             .context("Invalid base64 tx bytes")?;
+        verify_tx_bytes(&tx_bytes, expected)?;
 
         // Sui signs blake2b_256(intent || tx_bytes)
         // Intent: [0, 0, 0] for TransactionData
@@ -125,12 +185,36 @@ impl Signer {
         Ok(base64::engine::general_purpose::STANDARD.encode(&sig_bytes))
     }
 
+    /// The digest Sui will assign a transaction, computed locally from its
+    /// base64 BCS `TransactionData`: Base58 of
+    /// `blake2b_256("TransactionData::" || bytes)`, as the RPC returns it.
+    /// Note the BCS type-name prefix: the intent prefix is only for signing.
+    pub fn tx_digest(tx_bytes_base64: &str) -> Result<String> {
+        let tx_bytes = base64::engine::general_purpose::STANDARD
+            .decode(tx_bytes_base64.trim())
+            .context("Invalid base64 tx bytes")?;
+        let hash = blake2b_simd::Params::new()
+            .hash_length(32)
+            .to_state()
+            .update(b"TransactionData::")
+            .update(&tx_bytes)
+            .finalize();
+        Ok(encode_base58(hash.as_bytes()))
+    }
+
     /// Get the public key bytes (32 bytes).
     pub fn public_key_bytes(&self) -> [u8; 32] {
         self.verifying_key.to_bytes()
     }
 }
 
+/// Lowercase, `0x`-prefixed, zero-padded to 32 bytes — the form
+/// [`Signer::address`] returns.
+fn normalize_address(address: &str) -> String {
+    let hex = address.trim().trim_start_matches("0x").to_lowercase();
+    format!("0x{:0>64}", hex)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,4 +267,63 @@ mod tests {
 
         assert!(Signer::from_hex(&encoded).is_err());
     }
+
+    fn keystore_entry(flag: u8, key: [u8; 32]) -> String {
+        let mut data = vec![flag];
+        data.extend_from_slice(&key);
+        base64::engine::general_purpose::STANDARD.encode(data)
+    }
+
+    fn write_keystore(name: &str, entries: &[String]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("arb-keystore-{}-{}.keystore", name, std::process::id()));
+        std::fs::write(&path, serde_json::to_string(entries).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_signer_from_keystore_selects_by_address() {
+        let wanted = Signer::from_hex(&hex::encode([7u8; 32])).unwrap().address();
+        let path = write_keystore(
+            "select",
+            &[
+                keystore_entry(0x01, [9u8; 32]), // secp256k1, skipped
+                keystore_entry(0x00, [42u8; 32]),
+                keystore_entry(0x00, [7u8; 32]),
+            ],
+        );
+
+        // Address matching ignores the 0x prefix and case
+        let signer = Signer::from_keystore(&path, &wanted[2..].to_uppercase()).unwrap();
+        assert_eq!(signer.address(), wanted);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_signer_from_keystore_missing_address() {
+        let path = write_keystore("missing", &[keystore_entry(0x00, [42u8; 32]), keystore_entry(0x02, [1u8; 32])]);
+        let other = Signer::from_hex(&hex::encode([7u8; 32])).unwrap().address();
+
+        let Err(err) = Signer::from_keystore(&path, &other) else { panic!("found a key for {}", other) };
+        let err = err.to_string();
+        assert!(err.contains(&other), "{}", err);
+        assert!(err.contains("1 non-Ed25519 skipped"), "{}", err);
+        assert!(Signer::from_keystore(&path.with_extension("absent"), &other).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_tx_digest() {
+        // Pinned so a change to the scheme can't go unnoticed
+        let digest = Signer::tx_digest("AAECAwQFBgc=").unwrap();
+        assert_eq!(digest, "DWov5GhpAHBrCazvpHatq2saCodqMhMDZAeVDtrunT8i");
+        assert_eq!(Signer::tx_digest(" AAECAwQFBgc=\n").unwrap(), digest);
+
+        // The digest hashes the type-name prefix, not the signing intent
+        let intent_hash = blake2b_simd::Params::new()
+            .hash_length(32)
+            .hash(&[0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_ne!(digest, encode_base58(intent_hash.as_bytes()));
+
+        assert!(Signer::tx_digest("not base64!").is_err());
+    }
 }
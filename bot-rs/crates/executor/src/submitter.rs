@@ -1,199 +1,454 @@
 use anyhow::{Context, Result};
-use reqwest::Client;
+use arb_types::rpc::RpcClient;
 use serde_json::{json, Value};
+use std::time::Duration;
 use tracing::{error, info, warn};
 
+use crate::signer::Signer;
+
+/// Delay before the first resubmission; grows linearly per attempt.
+const RETRY_BACKOFF_MS: u64 = 500;
+
+/// Digest lookups [`Submitter::confirm`] makes before giving up.
+const CONFIRM_ATTEMPTS: u32 = 3;
+
+/// Per-request timeout; execution waits for local finality.
+const SUBMIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Wait between [`Submitter::confirm`] lookups.
+const CONFIRM_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Submits signed transactions to the Sui network with retry logic.
 pub struct Submitter {
-    client: Client,
-    rpc_url: String,
+    rpc: RpcClient,
+    timeout: Duration,
     max_retries: u32,
 }
 
+/// Why a submission produced no execution result.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SubmitError {
+    /// The node rejected the transaction (bad signature, missing or locked
+    /// object, insufficient gas, ...). It did not execute, and resubmitting
+    /// the same bytes won't help.
+    #[error("Transaction rejected: {0}")]
+    NonRetryable(String),
+    /// The request timed out or the connection dropped after the node may
+    /// have received it, and looking up `digest` didn't settle whether it
+    /// executed.
+    #[error("Transaction {digest} may have executed: {message}")]
+    PossiblyExecuted { digest: String, message: String },
+    /// Failed before the node could have accepted it (connection refused,
+    /// rate limit, overload). Retries are exhausted.
+    #[error("Transaction submission failed: {0}")]
+    Retryable(String),
+}
+
 /// Result of a transaction submission.
 #[derive(Debug)]
 pub struct SubmitResult {
     pub digest: String,
     pub success: bool,
+    /// Net gas (computation + storage − rebate), floored at 0.
     pub gas_cost_mist: u64,
+    /// Gas components and who paid them.
+    pub gas: GasCharge,
     pub profit_mist: Option<u64>,
     pub error_message: Option<String>,
 }
 
+impl SubmitResult {
+    /// Net gas `address` paid: the whole charge if it owns the gas coin (or
+    /// the payer is unknown), nothing if someone else sponsored it. Negative
+    /// when the storage rebate exceeded the cost.
+    pub fn gas_paid_by(&self, address: &str) -> i64 {
+        match &self.gas.payer {
+            Some(payer) if !payer.eq_ignore_ascii_case(address) => 0,
+            _ => self.gas.net_mist(),
+        }
+    }
+}
+
+/// Gas charged by one transaction. Costs and the storage rebate all settle
+/// against the gas coin, so they belong to its owner — the sender, or a
+/// sponsor — while profit flows to the sender.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GasCharge {
+    /// Owner of the gas coin (`effects.gasObject`).
+    pub payer: Option<String>,
+    pub computation_mist: u64,
+    pub storage_mist: u64,
+    pub rebate_mist: u64,
+}
+
+impl GasCharge {
+    /// Cost minus rebate; negative if the transaction freed more storage
+    /// than it used.
+    pub fn net_mist(&self) -> i64 {
+        (self.computation_mist + self.storage_mist) as i64 - self.rebate_mist as i64
+    }
+}
+
 impl Submitter {
     pub fn new(rpc_url: &str) -> Self {
         Self {
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(30))
-                .build()
-                .expect("Failed to create HTTP client"),
-            rpc_url: rpc_url.to_string(),
+            rpc: RpcClient::single(rpc_url).with_timeout(SUBMIT_TIMEOUT),
+            timeout: SUBMIT_TIMEOUT,
             max_retries: 2,
         }
     }
 
+    /// Submit through a shared (possibly multi-endpoint) client. A
+    /// submission that fails over resubmits the same signed bytes, which
+    /// never execute twice.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(self.timeout);
+        self
+    }
+
+    /// Bound each RPC request. A submission that hits this is looked up by
+    /// digest before it is retried.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.rpc = self.rpc.with_timeout(timeout);
+        self.timeout = timeout;
+        self
+    }
+
     /// Submit a signed transaction and wait for execution.
-    /// On timeout/network errors, checks if the transaction already landed
-    /// before retrying to avoid double-counting in P&L.
-    pub async fn submit(
-        &self,
-        tx_bytes: &str,
-        signature: &str,
-    ) -> Result<SubmitResult> {
+    ///
+    /// Rejections fail fast. Failures before the node could have accepted
+    /// the transaction are retried. A timeout or dropped connection may
+    /// hide a transaction that executed, so its locally computed digest is
+    /// looked up first and the on-chain result returned if it landed.
+    /// Resubmitting the same signed bytes never executes them twice.
+    pub async fn submit(&self, tx_bytes: &str, signature: &str) -> Result<SubmitResult, SubmitError> {
+        let digest = Signer::tx_digest(tx_bytes).map_err(|e| SubmitError::NonRetryable(format!("{:#}", e)))?;
+        info!(digest = %digest, "Submitting transaction");
         let mut last_error = String::new();
+        // Once an attempt may have executed, the outcome stays unknown
+        // unless a lookup finds it
+        let mut unconfirmed = false;
 
         for attempt in 0..=self.max_retries {
             if attempt > 0 {
-                // Before retrying, check if the transaction already landed on-chain.
-                // Compute the expected digest from tx_bytes + signature to query.
-                warn!(attempt = %attempt, "Checking if tx already landed before retry...");
-                tokio::time::sleep(std::time::Duration::from_millis(500 * attempt as u64)).await;
+                tokio::time::sleep(Duration::from_millis(RETRY_BACKOFF_MS * attempt as u64)).await;
             }
 
-            match self.submit_once(tx_bytes, signature).await {
+            match self.submit_once(tx_bytes, signature, &digest).await {
                 Ok(result) => return Ok(result),
-                Err(e) => {
-                    last_error = e.to_string();
-                    // If the error mentions "already executed" or duplicate, treat as success
-                    // and try to fetch the result
-                    if last_error.contains("already") || last_error.contains("duplicate") {
-                        warn!("Transaction appears already executed — treating as success");
-                        // Return a partial result; the tx did land but we don't have details
-                        return Ok(SubmitResult {
-                            digest: "unknown-duplicate".to_string(),
-                            success: true,
-                            gas_cost_mist: 0,
-                            profit_mist: None,
-                            error_message: Some("Duplicate transaction detected".to_string()),
-                        });
+                Err(SubmitError::NonRetryable(message)) => {
+                    error!(digest = %digest, error = %message, "Transaction rejected");
+                    return Err(SubmitError::NonRetryable(message));
+                }
+                Err(SubmitError::Retryable(message)) => {
+                    warn!(attempt = %attempt, error = %message, "Submission failed");
+                    last_error = message;
+                }
+                Err(SubmitError::PossiblyExecuted { message, .. }) => {
+                    warn!(
+                        attempt = %attempt,
+                        digest = %digest,
+                        error = %message,
+                        "Submission outcome unknown — checking digest before retry"
+                    );
+                    match self.find_transaction(&digest).await {
+                        Ok(Some(result)) => return Ok(result),
+                        Ok(None) => last_error = message,
+                        Err(e) => last_error = format!("{}; digest lookup failed: {:#}", message, e),
                     }
-                    error!(attempt = %attempt, error = %last_error, "Submission failed");
+                    unconfirmed = true;
                 }
             }
         }
 
-        anyhow::bail!("Transaction submission failed after {} retries: {}", self.max_retries, last_error)
-    }
-
-    async fn submit_once(
-        &self,
-        tx_bytes: &str,
-        signature: &str,
-    ) -> Result<SubmitResult> {
-        let response = self
-            .client
-            .post(&self.rpc_url)
-            .json(&json!({
-                "jsonrpc": "2.0",
-                "id": 1,
-                "method": "sui_executeTransactionBlock",
-                "params": [
-                    tx_bytes,
-                    [signature],
-                    {
-                        "showEffects": true,
-                        "showEvents": true,
-                    },
-                    "WaitForLocalExecution"
-                ]
-            }))
-            .send()
-            .await
-            .context("Failed to submit transaction")?;
+        Err(if unconfirmed {
+            SubmitError::PossiblyExecuted { digest, message: last_error }
+        } else {
+            SubmitError::Retryable(format!("after {} retries: {}", self.max_retries, last_error))
+        })
+    }
 
-        let body: Value = response.json().await.context("Failed to parse submission response")?;
+    /// Look a transaction up by digest. `None` if the node doesn't know it.
+    pub async fn find_transaction(&self, digest: &str) -> Result<Option<SubmitResult>> {
+        let body = self
+            .rpc
+            .call("sui_getTransactionBlock", json!([digest, { "showEffects": true, "showEvents": true }]))
+            .await
+            .context("Failed to look up transaction")?;
 
         if let Some(error) = body.get("error") {
+            let message = error.get("message").and_then(Value::as_str).unwrap_or_default().to_lowercase();
+            if message.contains("could not find") || message.contains("not found") {
+                return Ok(None);
+            }
             anyhow::bail!("RPC error: {}", error);
         }
-
         let result = body.get("result").context("Missing result")?;
+        Ok(Some(parse_execution(result)))
+    }
 
-        let digest = result
-            .get("digest")
-            .and_then(|d| d.as_str())
-            .unwrap_or("unknown")
-            .to_string();
-
-        let effects = result.get("effects");
-        let status = effects
-            .and_then(|e| e.get("status"))
-            .and_then(|s| s.get("status"))
-            .and_then(|s| s.as_str())
-            .unwrap_or("unknown");
-
-        let gas_cost = effects
-            .and_then(|e| e.get("gasUsed"))
-            .map(|g| {
-                let comp = g.get("computationCost")
-                    .and_then(|v| v.as_str())
-                    .and_then(|s| s.parse::<u64>().ok())
-                    .unwrap_or(0);
-                let storage = g.get("storageCost")
-                    .and_then(|v| v.as_str())
-                    .and_then(|s| s.parse::<u64>().ok())
-                    .unwrap_or(0);
-                let rebate = g.get("storageRebate")
-                    .and_then(|v| v.as_str())
-                    .and_then(|s| s.parse::<u64>().ok())
-                    .unwrap_or(0);
-                comp + storage - rebate.min(comp + storage)
-            })
-            .unwrap_or(0);
-
-        // Parse ArbExecuted event for actual profit
-        let profit = result
-            .get("events")
-            .and_then(|e| e.as_array())
-            .and_then(|events| {
-                events.iter().find_map(|ev| {
-                    let event_type = ev.get("type")?.as_str()?;
-                    if event_type.contains("ArbExecuted") {
-                        ev.get("parsedJson")
-                            .and_then(|p| p.get("profit"))
-                            .and_then(|p| p.as_str())
-                            .and_then(|s| s.parse::<u64>().ok())
-                    } else {
-                        None
-                    }
-                })
-            });
-
-        let success = status == "success";
-
-        if success {
-            info!(
-                digest = %digest,
-                gas = %gas_cost,
-                profit = ?profit,
-                "Transaction executed successfully"
-            );
-        } else {
-            let error_msg = effects
-                .and_then(|e| e.get("status"))
-                .and_then(|s| s.get("error"))
-                .and_then(|e| e.as_str())
-                .unwrap_or("Unknown error");
-            warn!(digest = %digest, error = %error_msg, "Transaction failed on-chain");
+    /// Settle a [`SubmitError::PossiblyExecuted`]: look the digest up a few
+    /// times, a second apart, giving a slow node time to finish. `None` if
+    /// it's still not found; `Err` only if no lookup got an answer.
+    pub async fn confirm(&self, digest: &str) -> Result<Option<SubmitResult>> {
+        let mut answered = false;
+        let mut last_error = None;
+        for attempt in 0..CONFIRM_ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::sleep(CONFIRM_INTERVAL).await;
+            }
+            match self.find_transaction(digest).await {
+                Ok(Some(result)) => return Ok(Some(result)),
+                Ok(None) => answered = true,
+                Err(e) => last_error = Some(e),
+            }
         }
+        match last_error {
+            Some(e) if !answered => Err(e),
+            _ => Ok(None),
+        }
+    }
+
+    async fn submit_once(&self, tx_bytes: &str, signature: &str, digest: &str) -> Result<SubmitResult, SubmitError> {
+        let possibly_executed = |message: String| SubmitError::PossiblyExecuted { digest: digest.to_string(), message };
 
-        Ok(SubmitResult {
-            digest,
-            success,
-            gas_cost_mist: gas_cost,
-            profit_mist: profit,
-            error_message: if success {
-                None
+        let params = json!([
+            tx_bytes,
+            [signature],
+            {
+                "showEffects": true,
+                "showEvents": true,
+            },
+            "WaitForLocalExecution"
+        ]);
+        // A timeout or gateway error may have cut off a node that is still
+        // executing; only refused or rate-limited attempts certainly didn't
+        let body = self.rpc.call("sui_executeTransactionBlock", params).await.map_err(|e| {
+            if e.may_have_reached_node() {
+                possibly_executed(e.to_string())
             } else {
-                Some(
-                    effects
-                        .and_then(|e| e.get("status"))
-                        .and_then(|s| s.get("error"))
-                        .and_then(|e| e.as_str())
-                        .unwrap_or("Unknown error")
-                        .to_string(),
-                )
+                SubmitError::Retryable(e.to_string())
+            }
+        })?;
+
+        if let Some(error) = body.get("error") {
+            return Err(classify_rpc_error(error, digest));
+        }
+
+        let result = body.get("result").ok_or_else(|| possibly_executed("Missing result".to_string()))?;
+        Ok(parse_execution(result))
+    }
+}
+
+/// Classify a JSON-RPC error from `sui_executeTransactionBlock`. The node
+/// validated and rejected the transaction unless it says it's overloaded,
+/// timed out waiting for execution, or already executed it. Validators
+/// flag some rejections "non-retryable" — e.g. an input object whose
+/// version is no longer available — and resending the same bytes can't fix
+/// those, even where the message also says "unavailable".
+fn classify_rpc_error(error: &Value, digest: &str) -> SubmitError {
+    let text = format!("RPC error: {}", error);
+    let message = error
+        .get("message")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_lowercase();
+    let matches = |patterns: &[&str]| patterns.iter().any(|p| message.contains(p));
+
+    if matches(&["timeout", "timed out", "already executed", "already been executed", "duplicate"]) {
+        SubmitError::PossiblyExecuted { digest: digest.to_string(), message: text }
+    } else if matches(&["non-retryable", "unavailableforconsumption"]) {
+        SubmitError::NonRetryable(text)
+    } else if matches(&["rate limit", "too many requests", "overloaded", "unavailable"]) {
+        SubmitError::Retryable(text)
+    } else {
+        SubmitError::NonRetryable(text)
+    }
+}
+
+/// Read a `sui_executeTransactionBlock` result (with effects and events).
+pub fn parse_execution(result: &Value) -> SubmitResult {
+    let digest = result
+        .get("digest")
+        .and_then(|d| d.as_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let effects = result.get("effects");
+    let status = effects
+        .and_then(|e| e.get("status"))
+        .and_then(|s| s.get("status"))
+        .and_then(|s| s.as_str())
+        .unwrap_or("unknown");
+
+    let gas = effects.map(parse_gas_charge).unwrap_or_default();
+    let gas_cost = gas.net_mist().max(0) as u64;
+
+    // Parse ArbExecuted event for actual profit
+    let profit = result
+        .get("events")
+        .and_then(|e| e.as_array())
+        .and_then(|events| {
+            events.iter().find_map(|ev| {
+                let event_type = ev.get("type")?.as_str()?;
+                if event_type.contains("ArbExecuted") {
+                    ev.get("parsedJson")
+                        .and_then(|p| p.get("profit"))
+                        .and_then(|p| p.as_str())
+                        .and_then(|s| s.parse::<u64>().ok())
+                } else {
+                    None
+                }
+            })
+        });
+
+    let success = status == "success";
+    let error_message = (!success).then(|| {
+        effects
+            .and_then(|e| e.get("status"))
+            .and_then(|s| s.get("error"))
+            .and_then(|e| e.as_str())
+            .unwrap_or("Unknown error")
+            .to_string()
+    });
+
+    if success {
+        info!(
+            digest = %digest,
+            gas = %gas_cost,
+            payer = ?gas.payer,
+            profit = ?profit,
+            "Transaction executed successfully"
+        );
+    } else {
+        warn!(digest = %digest, error = ?error_message, "Transaction failed on-chain");
+    }
+
+    SubmitResult {
+        digest,
+        success,
+        gas_cost_mist: gas_cost,
+        gas,
+        profit_mist: profit,
+        error_message,
+    }
+}
+
+/// `effects.gasUsed` components and the gas coin's owner.
+fn parse_gas_charge(effects: &Value) -> GasCharge {
+    let used = |key: &str| {
+        effects
+            .get("gasUsed")
+            .and_then(|g| g.get(key))
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(0)
+    };
+    GasCharge {
+        payer: effects
+            .get("gasObject")
+            .and_then(|g| g.get("owner"))
+            .and_then(|o| o.get("AddressOwner"))
+            .and_then(|a| a.as_str())
+            .map(str::to_string),
+        computation_mist: used("computationCost"),
+        storage_mist: used("storageCost"),
+        rebate_mist: used("storageRebate"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENDER: &str = "0x00000000000000000000000000000000000000000000000000000000000000aa";
+    const SPONSOR: &str = "0x00000000000000000000000000000000000000000000000000000000000000bb";
+
+    fn execution(payer: &str, status: &str, rebate: u64) -> Value {
+        json!({
+            "digest": "9xQ",
+            "effects": {
+                "status": { "status": status, "error": "MoveAbort(.., 3)" },
+                "gasObject": { "owner": { "AddressOwner": payer }, "reference": { "objectId": "0x9a5" } },
+                "gasUsed": {
+                    "computationCost": "1000000",
+                    "storageCost": "2000000",
+                    "storageRebate": rebate.to_string(),
+                    "nonRefundableStorageFee": "0"
+                }
             },
+            "events": [{
+                "type": "0xa11ce::events::ArbExecuted",
+                "parsedJson": { "profit": "5000000" }
+            }]
         })
     }
+
+    #[test]
+    fn test_self_paid_gas_charged_to_sender() {
+        let r = parse_execution(&execution(SENDER, "success", 500_000));
+        assert!(r.success);
+        assert_eq!(r.gas_cost_mist, 2_500_000);
+        assert_eq!(r.gas.payer.as_deref(), Some(SENDER));
+        assert_eq!(r.gas_paid_by(SENDER), 2_500_000);
+        assert_eq!(r.profit_mist, Some(5_000_000));
+    }
+
+    #[test]
+    fn test_sponsored_gas_and_rebate_go_to_sponsor() {
+        let r = parse_execution(&execution(SPONSOR, "success", 500_000));
+        // Profit still credits the sender; gas and the rebate settle with the sponsor
+        assert_eq!(r.profit_mist, Some(5_000_000));
+        assert_eq!(r.gas_paid_by(SENDER), 0);
+        assert_eq!(r.gas_paid_by(SPONSOR), 2_500_000);
+    }
+
+    #[test]
+    fn test_rebate_exceeding_cost_is_a_credit() {
+        let r = parse_execution(&execution(SENDER, "success", 4_000_000));
+        assert_eq!(r.gas_cost_mist, 0);
+        assert_eq!(r.gas.net_mist(), -1_000_000);
+        assert_eq!(r.gas_paid_by(SENDER), -1_000_000);
+    }
+
+    #[test]
+    fn test_failed_execution() {
+        let r = parse_execution(&execution(SENDER, "failure", 0));
+        assert!(!r.success);
+        assert_eq!(r.error_message.as_deref(), Some("MoveAbort(.., 3)"));
+        assert_eq!(r.gas_paid_by(SENDER), 3_000_000);
+    }
+
+    #[test]
+    fn test_rpc_error_classification() {
+        let classify = |message: &str| classify_rpc_error(&json!({ "code": -32002, "message": message }), "D1");
+        assert!(matches!(classify("InsufficientGas"), SubmitError::NonRetryable(_)));
+        assert!(matches!(
+            classify("Error checking transaction input objects: ObjectNotFound"),
+            SubmitError::NonRetryable(_)
+        ));
+        assert!(matches!(classify("Invalid user signature"), SubmitError::NonRetryable(_)));
+        assert!(matches!(classify("Too many requests"), SubmitError::Retryable(_)));
+        assert!(matches!(classify("Service unavailable"), SubmitError::Retryable(_)));
+        assert!(matches!(
+            classify(
+                "Transaction is rejected as invalid by more than 1/3 of validators by stake (non-retryable). \
+                 Non-retryable errors: [ObjectVersionUnavailableForConsumption { current_version: SequenceNumber(3) }]"
+            ),
+            SubmitError::NonRetryable(_)
+        ));
+        assert!(matches!(
+            classify("Transaction timed out before reaching finality"),
+            SubmitError::PossiblyExecuted { .. }
+        ));
+    }
+
+    #[test]
+    fn test_unknown_payer_charged_to_caller() {
+        let mut v = execution(SENDER, "success", 0);
+        v["effects"].as_object_mut().unwrap().remove("gasObject");
+        let r = parse_execution(&v);
+        assert_eq!(r.gas.payer, None);
+        assert_eq!(r.gas_paid_by(SENDER), 3_000_000);
+    }
 }
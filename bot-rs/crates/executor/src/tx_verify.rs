@@ -0,0 +1,696 @@
+//! Pre-signing verification of node-built transactions.
+//!
+//! Every transaction we sign is built by the RPC node (`unsafe_moveCall`,
+//! `unsafe_payAllSui`, `unsafe_paySui`), and the signature covers whatever
+//! bytes came back. A misbehaving or malicious endpoint could return a
+//! transaction that spends our gas on someone else's call or transfers our
+//! coins away. Before signing, the BCS `TransactionData` is decoded — just
+//! the fields we check — and compared with what we asked for.
+
+use arb_types::config::normalize_coin_type;
+use arb_types::encoding::{self, hex_addr};
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::gas_coin_pool::GasCoinLease;
+
+/// A node-built transaction that doesn't match what we requested. Never sign it.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Refusing to sign node-built transaction: {0}")]
+pub struct TxMismatch(pub String);
+
+fn mismatch(msg: impl Into<String>) -> TxMismatch {
+    TxMismatch(msg.into())
+}
+
+/// The single Move call a strategy transaction must contain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectedCall {
+    pub package: String,
+    pub module: String,
+    pub function: String,
+    pub type_args: Vec<String>,
+    /// Call arguments as sent to `unsafe_moveCall`: `0x…` strings are object
+    /// IDs, decimal strings are `u64` pure values.
+    pub args: Vec<Value>,
+}
+
+/// What a transaction we are about to sign must look like.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectedTx {
+    pub sender: String,
+    pub max_gas_budget: u64,
+    /// `Some`: exactly this Move call and nothing else. `None`: native
+    /// commands only (coin merges), every transfer back to `sender`.
+    pub call: Option<ExpectedCall>,
+}
+
+impl ExpectedTx {
+    pub fn move_call(sender: &str, max_gas_budget: u64, call: ExpectedCall) -> Self {
+        Self { sender: sender.to_string(), max_gas_budget, call: Some(call) }
+    }
+
+    pub fn native(sender: &str, max_gas_budget: u64) -> Self {
+        Self { sender: sender.to_string(), max_gas_budget, call: None }
+    }
+}
+
+/// Node-built transaction bytes (base64) with the expectation to check them
+/// against before signing.
+#[derive(Debug, Clone)]
+pub struct BuiltTx {
+    pub tx_bytes: String,
+    pub expected: ExpectedTx,
+    /// The gas coin leased for this transaction, if it was built with one.
+    /// It goes back to the pool once every copy of the transaction is
+    /// dropped — after it has been submitted or discarded.
+    pub gas_coin: Option<Arc<GasCoinLease>>,
+}
+
+/// Check decoded `TransactionData` bytes against `expected`.
+pub fn verify_tx_bytes(tx_bytes: &[u8], expected: &ExpectedTx) -> Result<(), TxMismatch> {
+    let tx = decode_transaction_data(tx_bytes)?;
+
+    let sender = parse_address(&expected.sender)?;
+    if tx.sender != sender {
+        return Err(mismatch(format!("sender is {}, expected {}", hex_addr(&tx.sender), expected.sender)));
+    }
+    if tx.gas_owner != sender {
+        return Err(mismatch(format!("gas owner is {}, not our address", hex_addr(&tx.gas_owner))));
+    }
+    if tx.gas_budget > expected.max_gas_budget {
+        return Err(mismatch(format!(
+            "gas budget {} exceeds configured max {}",
+            tx.gas_budget, expected.max_gas_budget
+        )));
+    }
+
+    match &expected.call {
+        Some(call) => verify_move_call(&tx, call),
+        None => verify_native(&tx, &sender),
+    }
+}
+
+fn verify_move_call(tx: &TxData, expected: &ExpectedCall) -> Result<(), TxMismatch> {
+    let [Command::MoveCall(call)] = tx.commands.as_slice() else {
+        return Err(mismatch(format!(
+            "expected a single Move call, got {} command(s)",
+            tx.commands.len()
+        )));
+    };
+
+    let package = parse_address(&expected.package)?;
+    if call.package != package {
+        return Err(mismatch(format!("call targets package {}, expected {}", hex_addr(&call.package), expected.package)));
+    }
+    if call.module != expected.module || call.function != expected.function {
+        return Err(mismatch(format!(
+            "call targets {}::{}, expected {}::{}",
+            call.module, call.function, expected.module, expected.function
+        )));
+    }
+
+    let got: Vec<String> = call.type_args.iter().map(|t| canonical_type(t)).collect();
+    let want: Vec<String> = expected.type_args.iter().map(|t| canonical_type(t)).collect();
+    if got != want {
+        return Err(mismatch(format!("type args {:?}, expected {:?}", got, want)));
+    }
+
+    if call.arguments.len() != expected.args.len() {
+        return Err(mismatch(format!(
+            "call has {} arguments, expected {}",
+            call.arguments.len(),
+            expected.args.len()
+        )));
+    }
+    for (i, (arg, want)) in call.arguments.iter().zip(&expected.args).enumerate() {
+        let input = match arg {
+            Argument::Input(idx) => tx
+                .inputs
+                .get(*idx as usize)
+                .ok_or_else(|| mismatch(format!("argument {} refers to missing input {}", i, idx)))?,
+            other => return Err(mismatch(format!("argument {} is {:?}, expected an input", i, other))),
+        };
+        let want = want
+            .as_str()
+            .ok_or_else(|| mismatch(format!("argument {} has unsupported expected value {}", i, want)))?;
+        verify_input(i, input, want)?;
+    }
+    Ok(())
+}
+
+/// Object args must be the object we named; decimal args must be the same `u64`.
+fn verify_input(i: usize, input: &CallArg, want: &str) -> Result<(), TxMismatch> {
+    if want.starts_with("0x") {
+        let id = parse_address(want)?;
+        match input {
+            CallArg::Object(got) | CallArg::Shared(got, _) if *got == id => Ok(()),
+            CallArg::Object(got) | CallArg::Shared(got, _) => {
+                Err(mismatch(format!("argument {} is object {}, expected {}", i, hex_addr(got), want)))
+            }
+            CallArg::Pure(_) => Err(mismatch(format!("argument {} is a pure value, expected object {}", i, want))),
+        }
+    } else {
+        let value: u64 = want
+            .parse()
+            .map_err(|_| mismatch(format!("argument {} has unsupported expected value '{}'", i, want)))?;
+        match input {
+            CallArg::Pure(bytes) if bytes.as_slice() == value.to_le_bytes() => Ok(()),
+            CallArg::Pure(bytes) => Err(mismatch(format!("argument {} is pure 0x{}, expected u64 {}", i, hex::encode(bytes), value))),
+            CallArg::Object(got) | CallArg::Shared(got, _) => {
+                Err(mismatch(format!("argument {} is object {}, expected u64 {}", i, hex_addr(got), value)))
+            }
+        }
+    }
+}
+
+/// Coin merges: no Move code, no publishing, and every transfer goes to us.
+fn verify_native(tx: &TxData, owner: &[u8; 32]) -> Result<(), TxMismatch> {
+    for command in &tx.commands {
+        match command {
+            Command::MoveCall(c) => {
+                return Err(mismatch(format!("unexpected Move call {}::{}", c.module, c.function)))
+            }
+            Command::PublishOrUpgrade => return Err(mismatch("unexpected publish/upgrade")),
+            Command::TransferObjects(recipient) => {
+                let to = match recipient {
+                    Argument::Input(idx) => match tx.inputs.get(*idx as usize) {
+                        Some(CallArg::Pure(bytes)) => bytes.as_slice(),
+                        _ => return Err(mismatch("transfer recipient is not a pure address")),
+                    },
+                    _ => return Err(mismatch("transfer recipient is not a pure address")),
+                };
+                if to != owner {
+                    return Err(mismatch(format!("transfer to 0x{}, not our address", hex::encode(to))));
+                }
+            }
+            Command::Other => {}
+        }
+    }
+    Ok(())
+}
+
+// ── Decoded TransactionData (only what we verify) ──
+
+#[derive(Debug)]
+struct TxData {
+    sender: [u8; 32],
+    inputs: Vec<CallArg>,
+    commands: Vec<Command>,
+    gas_owner: [u8; 32],
+    gas_budget: u64,
+}
+
+#[derive(Debug)]
+enum CallArg {
+    Pure(Vec<u8>),
+    /// Owned or receiving — we only check the ID
+    Object([u8; 32]),
+    /// Shared, and whether it's taken mutably (checked by tests)
+    Shared([u8; 32], #[cfg_attr(not(test), allow(dead_code))] bool),
+}
+
+#[derive(Debug)]
+enum Argument {
+    GasCoin,
+    Input(u16),
+    /// Result (or nested result) of an earlier command
+    Result,
+}
+
+#[derive(Debug)]
+struct MoveCall {
+    package: [u8; 32],
+    module: String,
+    function: String,
+    /// Rendered as `0x<addr>::module::Name<...>`
+    type_args: Vec<String>,
+    arguments: Vec<Argument>,
+}
+
+#[derive(Debug)]
+enum Command {
+    MoveCall(MoveCall),
+    /// Recipient argument of a `TransferObjects`
+    TransferObjects(Argument),
+    PublishOrUpgrade,
+    /// SplitCoins, MergeCoins, MakeMoveVec
+    Other,
+}
+
+/// Minimal BCS reader over a byte slice.
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], TxMismatch> {
+        let end = self.pos.checked_add(n).filter(|&e| e <= self.buf.len()).ok_or_else(|| {
+            mismatch(format!("truncated transaction ({} bytes, needed {} at offset {})", self.buf.len(), n, self.pos))
+        })?;
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, TxMismatch> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, TxMismatch> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, TxMismatch> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn bool(&mut self) -> Result<bool, TxMismatch> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(mismatch(format!("invalid bool byte {}", b))),
+        }
+    }
+
+    /// ULEB128 length prefix, bounded by the bytes left so a hostile length
+    /// can't trigger a huge allocation.
+    fn len(&mut self) -> Result<usize, TxMismatch> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                let len = usize::try_from(value).map_err(|_| mismatch("length overflow"))?;
+                if len > self.buf.len() - self.pos {
+                    return Err(mismatch(format!("length {} exceeds remaining bytes", len)));
+                }
+                return Ok(len);
+            }
+        }
+        Err(mismatch("malformed ULEB128 length"))
+    }
+
+    fn address(&mut self) -> Result<[u8; 32], TxMismatch> {
+        Ok(self.take(32)?.try_into().unwrap())
+    }
+
+    fn bytes(&mut self) -> Result<Vec<u8>, TxMismatch> {
+        let n = self.len()?;
+        Ok(self.take(n)?.to_vec())
+    }
+
+    fn string(&mut self) -> Result<String, TxMismatch> {
+        String::from_utf8(self.bytes()?).map_err(|_| mismatch("identifier is not UTF-8"))
+    }
+
+    fn vec<T>(&mut self, mut item: impl FnMut(&mut Self) -> Result<T, TxMismatch>) -> Result<Vec<T>, TxMismatch> {
+        let n = self.len()?;
+        (0..n).map(|_| item(self)).collect()
+    }
+
+    /// `(ObjectID, SequenceNumber, ObjectDigest)`
+    fn object_ref(&mut self) -> Result<[u8; 32], TxMismatch> {
+        let id = self.address()?;
+        self.u64()?;
+        self.bytes()?;
+        Ok(id)
+    }
+
+    fn call_arg(&mut self) -> Result<CallArg, TxMismatch> {
+        match self.u8()? {
+            0 => Ok(CallArg::Pure(self.bytes()?)),
+            1 => match self.u8()? {
+                0 | 2 => Ok(CallArg::Object(self.object_ref()?)),
+                1 => {
+                    let id = self.address()?;
+                    self.u64()?; // initial_shared_version
+                    Ok(CallArg::Shared(id, self.bool()?))
+                }
+                t => Err(mismatch(format!("unknown ObjectArg variant {}", t))),
+            },
+            t => Err(mismatch(format!("unknown CallArg variant {}", t))),
+        }
+    }
+
+    fn argument(&mut self) -> Result<Argument, TxMismatch> {
+        match self.u8()? {
+            0 => Ok(Argument::GasCoin),
+            1 => Ok(Argument::Input(self.u16()?)),
+            2 => {
+                self.u16()?;
+                Ok(Argument::Result)
+            }
+            3 => {
+                self.u16()?;
+                self.u16()?;
+                Ok(Argument::Result)
+            }
+            t => Err(mismatch(format!("unknown Argument variant {}", t))),
+        }
+    }
+
+    fn type_tag(&mut self, depth: usize) -> Result<String, TxMismatch> {
+        if depth > 8 {
+            return Err(mismatch("type tag nested too deeply"));
+        }
+        Ok(match self.u8()? {
+            0 => "bool".into(),
+            1 => "u8".into(),
+            2 => "u64".into(),
+            3 => "u128".into(),
+            4 => "address".into(),
+            5 => "signer".into(),
+            6 => format!("vector<{}>", self.type_tag(depth + 1)?),
+            7 => {
+                let address = self.address()?;
+                let module = self.string()?;
+                let name = self.string()?;
+                let params = self.vec(|r| r.type_tag(depth + 1))?;
+                let base = format!("{}::{}::{}", hex_addr(&address), module, name);
+                if params.is_empty() { base } else { format!("{}<{}>", base, params.join(", ")) }
+            }
+            8 => "u16".into(),
+            9 => "u32".into(),
+            10 => "u256".into(),
+            t => return Err(mismatch(format!("unknown TypeTag variant {}", t))),
+        })
+    }
+
+    fn command(&mut self) -> Result<Command, TxMismatch> {
+        match self.u8()? {
+            0 => Ok(Command::MoveCall(MoveCall {
+                package: self.address()?,
+                module: self.string()?,
+                function: self.string()?,
+                type_args: self.vec(|r| r.type_tag(0))?,
+                arguments: self.vec(Self::argument)?,
+            })),
+            1 => {
+                self.vec(Self::argument)?;
+                Ok(Command::TransferObjects(self.argument()?))
+            }
+            2 | 3 => {
+                // SplitCoins(coin, amounts) / MergeCoins(target, sources)
+                self.argument()?;
+                self.vec(Self::argument)?;
+                Ok(Command::Other)
+            }
+            4 | 6 => Ok(Command::PublishOrUpgrade),
+            5 => {
+                // MakeMoveVec(Option<TypeTag>, args)
+                if self.u8()? == 1 {
+                    self.type_tag(0)?;
+                }
+                self.vec(Self::argument)?;
+                Ok(Command::Other)
+            }
+            t => Err(mismatch(format!("unknown Command variant {}", t))),
+        }
+    }
+}
+
+/// Decode `TransactionData::V1` with a programmable transaction kind.
+fn decode_transaction_data(bytes: &[u8]) -> Result<TxData, TxMismatch> {
+    let mut r = Reader { buf: bytes, pos: 0 };
+    if r.u8()? != 0 {
+        return Err(mismatch("unsupported TransactionData version"));
+    }
+    if r.u8()? != 0 {
+        return Err(mismatch("transaction kind is not a programmable transaction"));
+    }
+    let inputs = r.vec(Reader::call_arg)?;
+    let commands = r.vec(Reader::command)?;
+    let sender = r.address()?;
+    // GasData { payment, owner, price, budget }
+    r.vec(Reader::object_ref)?;
+    let gas_owner = r.address()?;
+    r.u64()?;
+    let gas_budget = r.u64()?;
+    // TransactionExpiration: None | Epoch(u64)
+    match r.u8()? {
+        0 => {}
+        1 => {
+            r.u64()?;
+        }
+        t => return Err(mismatch(format!("unknown expiration variant {}", t))),
+    }
+    if r.pos != bytes.len() {
+        return Err(mismatch(format!("{} trailing bytes after TransactionData", bytes.len() - r.pos)));
+    }
+    Ok(TxData { sender, inputs, commands, gas_owner, gas_budget })
+}
+
+/// Each shared input of `tx_bytes` (`0x`-padded ID) and whether it's
+/// taken mutably.
+#[cfg(test)]
+pub(crate) fn shared_inputs(tx_bytes: &[u8]) -> Result<Vec<(String, bool)>, TxMismatch> {
+    Ok(decode_transaction_data(tx_bytes)?
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            CallArg::Shared(id, mutable) => Some((hex_addr(id), *mutable)),
+            _ => None,
+        })
+        .collect())
+}
+
+/// [`encoding::parse_address`], as a mismatch.
+fn parse_address(s: &str) -> Result<[u8; 32], TxMismatch> {
+    encoding::parse_address(s).map_err(|_| mismatch(format!("invalid address '{}'", s)))
+}
+
+/// Normalize every address inside a (possibly generic) type string so
+/// `0x2::sui::SUI` and the 32-byte form compare equal.
+fn canonical_type(t: &str) -> String {
+    let mut out = String::with_capacity(t.len());
+    let mut token = String::new();
+    for c in t.chars() {
+        if matches!(c, '<' | '>' | ',') {
+            out.push_str(&normalize_coin_type(token.trim()));
+            token.clear();
+            out.push(c);
+        } else if !c.is_whitespace() {
+            token.push(c);
+        }
+    }
+    out.push_str(&normalize_coin_type(token.trim()));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arb_types::encoding::{string, uleb128};
+    use serde_json::json;
+
+    const SENDER: &str = "0x00000000000000000000000000000000000000000000000000000000000000aa";
+    const PACKAGE: &str = "0xbeef";
+    const POOL: &str = "0x1234";
+
+    /// BCS encoder for the subset of `TransactionData` the verifier reads.
+    struct Tx {
+        inputs: Vec<Vec<u8>>,
+        commands: Vec<Vec<u8>>,
+        sender: [u8; 32],
+        gas_owner: [u8; 32],
+        budget: u64,
+    }
+
+    fn pure(bytes: &[u8]) -> Vec<u8> {
+        let mut v = vec![0];
+        uleb128(&mut v, bytes.len() as u64);
+        v.extend_from_slice(bytes);
+        v
+    }
+
+    fn shared(id: &str) -> Vec<u8> {
+        let mut v = vec![1, 1];
+        v.extend_from_slice(&parse_address(id).unwrap());
+        v.extend_from_slice(&7u64.to_le_bytes());
+        v.push(1);
+        v
+    }
+
+    /// `0x2::sui::SUI` struct tag
+    fn sui_tag() -> Vec<u8> {
+        let mut v = vec![7];
+        v.extend_from_slice(&parse_address("0x2").unwrap());
+        string(&mut v, "sui");
+        string(&mut v, "SUI");
+        v.push(0);
+        v
+    }
+
+    fn move_call(package: &str, function: &str, inputs: usize) -> Vec<u8> {
+        let mut v = vec![0];
+        v.extend_from_slice(&parse_address(package).unwrap());
+        string(&mut v, "two_hop");
+        string(&mut v, function);
+        v.push(1);
+        v.extend(sui_tag());
+        uleb128(&mut v, inputs as u64);
+        for i in 0..inputs as u16 {
+            v.push(1);
+            v.extend_from_slice(&i.to_le_bytes());
+        }
+        v
+    }
+
+    impl Tx {
+        fn strategy() -> Self {
+            Self {
+                inputs: vec![shared(POOL), pure(&1_000u64.to_le_bytes()), shared("0x6")],
+                commands: vec![move_call(PACKAGE, "cetus_to_turbos", 3)],
+                sender: parse_address(SENDER).unwrap(),
+                gas_owner: parse_address(SENDER).unwrap(),
+                budget: 50_000_000,
+            }
+        }
+
+        fn encode(&self) -> Vec<u8> {
+            let mut v = vec![0, 0];
+            uleb128(&mut v, self.inputs.len() as u64);
+            self.inputs.iter().for_each(|i| v.extend(i));
+            uleb128(&mut v, self.commands.len() as u64);
+            self.commands.iter().for_each(|c| v.extend(c));
+            v.extend_from_slice(&self.sender);
+            // One gas payment coin
+            v.push(1);
+            v.extend_from_slice(&parse_address("0x99").unwrap());
+            v.extend_from_slice(&3u64.to_le_bytes());
+            v.push(32);
+            v.extend_from_slice(&[0xdd; 32]);
+            v.extend_from_slice(&self.gas_owner);
+            v.extend_from_slice(&1_000u64.to_le_bytes());
+            v.extend_from_slice(&self.budget.to_le_bytes());
+            v.push(0);
+            v
+        }
+    }
+
+    fn expected() -> ExpectedTx {
+        ExpectedTx::move_call(
+            "0xaa",
+            50_000_000,
+            ExpectedCall {
+                package: PACKAGE.into(),
+                module: "two_hop".into(),
+                function: "cetus_to_turbos".into(),
+                type_args: vec!["0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI".into()],
+                args: vec![json!(POOL), json!("1000"), json!("0x6")],
+            },
+        )
+    }
+
+    fn rejection(tx: &Tx, expected: &ExpectedTx) -> String {
+        verify_tx_bytes(&tx.encode(), expected).unwrap_err().0
+    }
+
+    #[test]
+    fn test_accepts_matching_move_call() {
+        verify_tx_bytes(&Tx::strategy().encode(), &expected()).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_wrong_sender_gas_owner_and_budget() {
+        let mut tx = Tx::strategy();
+        tx.sender = [0xbb; 32];
+        assert!(rejection(&tx, &expected()).contains("sender"));
+
+        let mut tx = Tx::strategy();
+        tx.gas_owner = [0xbb; 32];
+        assert!(rejection(&tx, &expected()).contains("gas owner"));
+
+        let mut tx = Tx::strategy();
+        tx.budget = 50_000_001;
+        assert!(rejection(&tx, &expected()).contains("gas budget"));
+    }
+
+    #[test]
+    fn test_rejects_wrong_call_target_and_type_args() {
+        let mut tx = Tx::strategy();
+        tx.commands = vec![move_call("0xbad", "cetus_to_turbos", 3)];
+        assert!(rejection(&tx, &expected()).contains("package"));
+
+        let mut tx = Tx::strategy();
+        tx.commands = vec![move_call(PACKAGE, "turbos_to_cetus", 3)];
+        assert!(rejection(&tx, &expected()).contains("turbos_to_cetus"));
+
+        let mut want = expected();
+        want.call.as_mut().unwrap().type_args = vec!["0xa::usdc::USDC".into()];
+        assert!(rejection(&Tx::strategy(), &want).contains("type args"));
+    }
+
+    #[test]
+    fn test_rejects_wrong_objects_and_values() {
+        let mut tx = Tx::strategy();
+        tx.inputs[0] = shared("0x9999");
+        assert!(rejection(&tx, &expected()).contains("expected 0x1234"));
+
+        let mut tx = Tx::strategy();
+        tx.inputs[1] = pure(&2_000u64.to_le_bytes());
+        assert!(rejection(&tx, &expected()).contains("expected u64 1000"));
+
+        let mut tx = Tx::strategy();
+        tx.commands = vec![move_call(PACKAGE, "cetus_to_turbos", 2)];
+        assert!(rejection(&tx, &expected()).contains("2 arguments"));
+    }
+
+    #[test]
+    fn test_decodes_shared_mutability() {
+        let mut tx = Tx::strategy();
+        // The clock taken by `&Clock`
+        *tx.inputs[2].last_mut().unwrap() = 0;
+        let bytes = tx.encode();
+        verify_tx_bytes(&bytes, &expected()).unwrap();
+        let padded = |id: &str| format!("0x{:0>64}", id.trim_start_matches("0x"));
+        assert_eq!(shared_inputs(&bytes).unwrap(), [(padded(POOL), true), (padded("0x6"), false)]);
+    }
+
+    #[test]
+    fn test_rejects_smuggled_extra_command() {
+        let mut tx = Tx::strategy();
+        // TransferObjects([GasCoin], Input(1))
+        tx.commands.push(vec![1, 1, 0, 1, 1, 0]);
+        assert!(rejection(&tx, &expected()).contains("2 command(s)"));
+    }
+
+    #[test]
+    fn test_rejects_malformed_bytes() {
+        let bytes = Tx::strategy().encode();
+        assert!(verify_tx_bytes(&bytes[..bytes.len() - 3], &expected()).unwrap_err().0.contains("truncated"));
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(verify_tx_bytes(&trailing, &expected()).unwrap_err().0.contains("trailing"));
+        assert!(verify_tx_bytes(&[], &expected()).is_err());
+        // Hostile length prefix on the inputs vector
+        assert!(verify_tx_bytes(&[0, 0, 0xff, 0xff, 0xff, 0xff, 0x0f], &expected()).unwrap_err().0.contains("exceeds"));
+    }
+
+    #[test]
+    fn test_native_transfers_must_return_to_sender() {
+        let mut tx = Tx::strategy();
+        tx.commands = vec![vec![1, 1, 0, 1, 0, 0]]; // TransferObjects([GasCoin], Input(0))
+        tx.inputs = vec![pure(&parse_address(SENDER).unwrap())];
+        verify_tx_bytes(&tx.encode(), &ExpectedTx::native(SENDER, 10_000_000)).unwrap_err();
+        tx.budget = 10_000_000;
+        verify_tx_bytes(&tx.encode(), &ExpectedTx::native(SENDER, 10_000_000)).unwrap();
+
+        tx.inputs = vec![pure(&[0xbb; 32])];
+        assert!(rejection(&tx, &ExpectedTx::native(SENDER, 10_000_000)).contains("not our address"));
+
+        let strategy = Tx::strategy();
+        assert!(rejection(&strategy, &ExpectedTx::native(SENDER, 50_000_000)).contains("unexpected Move call"));
+    }
+
+    #[test]
+    fn test_canonical_type_normalizes_nested_addresses() {
+        assert_eq!(
+            canonical_type("0x000002::coin::Coin<0x0002::sui::SUI, 0xA::x::Y>"),
+            "0x2::coin::Coin<0x2::sui::SUI,0xa::x::Y>"
+        );
+        assert_eq!(canonical_type("u64"), "u64");
+    }
+}
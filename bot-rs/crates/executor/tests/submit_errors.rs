@@ -0,0 +1,146 @@
+//! Submission error handling against a mock JSON-RPC server: rejections
+//! fail fast, rate limits are retried, and a timed-out submission is looked
+//! up by digest before anything is resubmitted.
+
+use arb_executor::{Signer, SubmitError, Submitter};
+use arb_types::test_support::{self, Reply};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const TX_BYTES: &str = "AAECAwQFBgc=";
+const SIGNATURE: &str = "c2ln";
+
+/// Replies for the `n`th call (0-based) of `method`.
+type Script = fn(method: &str, n: usize) -> Reply;
+
+/// Mock JSON-RPC server answering per `script`. Returns its URL and the
+/// methods called, in order.
+async fn spawn_mock_rpc(script: Script) -> (String, Arc<Mutex<Vec<String>>>) {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let log = calls.clone();
+    let url = test_support::spawn_mock_rpc(move |request| {
+        let method = request["method"].as_str().unwrap_or_default().to_string();
+        let n = {
+            let mut log = log.lock().unwrap();
+            let n = log.iter().filter(|m| **m == method).count();
+            log.push(method.clone());
+            n
+        };
+        script(&method, n)
+    })
+    .await;
+    (url, calls)
+}
+
+fn executed() -> Value {
+    json!({ "jsonrpc": "2.0", "id": 1, "result": {
+        "digest": Signer::tx_digest(TX_BYTES).unwrap(),
+        "effects": {
+            "status": { "status": "success" },
+            "gasUsed": { "computationCost": "1000000", "storageCost": "0", "storageRebate": "0" }
+        },
+        "events": [{ "type": "0xabc::events::ArbExecuted", "parsedJson": { "profit": "7000000" } }]
+    }})
+}
+
+fn rpc_error(code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": code, "message": message } })
+}
+
+fn not_found() -> Value {
+    rpc_error(-32602, "Could not find the referenced transaction")
+}
+
+fn calls(log: &Mutex<Vec<String>>) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for method in log.lock().unwrap().iter() {
+        *counts.entry(method.clone()).or_default() += 1;
+    }
+    counts
+}
+
+fn submitter(url: &str) -> Submitter {
+    Submitter::new(url).with_timeout(Duration::from_millis(200))
+}
+
+#[tokio::test]
+async fn test_rejection_fails_fast() {
+    let (url, log) = spawn_mock_rpc(|_, _| Reply::Json(rpc_error(-32002, "InsufficientGas"))).await;
+
+    let err = submitter(&url).submit(TX_BYTES, SIGNATURE).await.unwrap_err();
+    assert!(matches!(err, SubmitError::NonRetryable(ref m) if m.contains("InsufficientGas")), "{err:?}");
+    assert_eq!(*log.lock().unwrap(), ["sui_executeTransactionBlock"]);
+}
+
+#[tokio::test]
+async fn test_rate_limit_is_retried() {
+    let (url, log) = spawn_mock_rpc(|_, n| if n == 0 { Reply::status(429) } else { Reply::Json(executed()) }).await;
+
+    let result = submitter(&url).submit(TX_BYTES, SIGNATURE).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.profit_mist, Some(7_000_000));
+    assert_eq!(calls(&log)["sui_executeTransactionBlock"], 2);
+    assert!(!calls(&log).contains_key("sui_getTransactionBlock"));
+}
+
+#[tokio::test]
+async fn test_rate_limit_exhausts_retries() {
+    let (url, log) = spawn_mock_rpc(|_, _| Reply::status(429)).await;
+
+    let err = submitter(&url).submit(TX_BYTES, SIGNATURE).await.unwrap_err();
+    assert!(matches!(err, SubmitError::Retryable(_)), "{err:?}");
+    assert_eq!(calls(&log)["sui_executeTransactionBlock"], 3);
+}
+
+#[tokio::test]
+async fn test_timeout_finds_landed_transaction() {
+    let (url, log) = spawn_mock_rpc(|method, _| match method {
+        "sui_executeTransactionBlock" => Reply::Hang,
+        _ => Reply::Json(executed()),
+    })
+    .await;
+
+    // Landed despite the timeout: reported from the lookup, not resubmitted
+    let result = submitter(&url).submit(TX_BYTES, SIGNATURE).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.digest, Signer::tx_digest(TX_BYTES).unwrap());
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["sui_executeTransactionBlock", "sui_getTransactionBlock"]
+    );
+}
+
+#[tokio::test]
+async fn test_unconfirmed_timeout_is_possibly_executed() {
+    let (url, log) = spawn_mock_rpc(|method, _| match method {
+        "sui_executeTransactionBlock" => Reply::Hang,
+        _ => Reply::Json(not_found()),
+    })
+    .await;
+
+    let err = submitter(&url).submit(TX_BYTES, SIGNATURE).await.unwrap_err();
+    match err {
+        SubmitError::PossiblyExecuted { digest, .. } => assert_eq!(digest, Signer::tx_digest(TX_BYTES).unwrap()),
+        other => panic!("expected PossiblyExecuted, got {other:?}"),
+    }
+    // Every retry was preceded by a lookup
+    let calls = calls(&log);
+    assert_eq!(calls["sui_executeTransactionBlock"], 3);
+    assert_eq!(calls["sui_getTransactionBlock"], 3);
+}
+
+#[tokio::test]
+async fn test_confirm_waits_for_slow_node() {
+    let (url, _) =
+        spawn_mock_rpc(|_, n| if n == 0 { Reply::Json(not_found()) } else { Reply::Json(executed()) }).await;
+    let digest = Signer::tx_digest(TX_BYTES).unwrap();
+
+    let result = submitter(&url).confirm(&digest).await.unwrap().unwrap();
+    assert!(result.success);
+
+    // Nothing listening: no lookup gets an answer
+    let unreachable = submitter("http://127.0.0.1:1");
+    assert!(unreachable.find_transaction(&digest).await.is_err());
+}
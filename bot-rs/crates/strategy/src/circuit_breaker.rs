@@ -1,10 +1,17 @@
+use anyhow::{Context, Result};
+use arb_types::notifier::Notifier;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use tracing::{error, info, warn};
 
 /// Circuit breaker that halts trading when loss thresholds are exceeded.
 ///
 /// Two independent trip conditions:
 /// 1. **Consecutive failures** — N trades in a row that fail or lose money.
-/// 2. **Cumulative loss** — Total net loss exceeds a MIST threshold within a rolling window.
+/// 2. **Cumulative loss** — Total net loss exceeds a MIST threshold within a rolling window
+///    (`loss_window_ms`, the cooldown unless set with [`with_loss_window`](Self::with_loss_window)).
 ///
 /// Once tripped, the breaker enters a cooldown period before allowing trades again.
 #[derive(Debug)]
@@ -13,13 +20,20 @@ pub struct CircuitBreaker {
     max_consecutive_failures: u32,
     max_cumulative_loss_mist: i64,
     cooldown_ms: u64,
+    /// Trades older than this no longer count toward the loss limit. 0 = all-time.
+    loss_window_ms: u64,
 
     // ── State ──
     consecutive_failures: u32,
+    /// All-time P&L, for accounting.
     cumulative_pnl_mist: i64,
+    /// `(timestamp_ms, pnl)` of the trades inside the loss window.
+    window: VecDeque<(u64, i64)>,
     total_trades: u64,
     tripped_at_ms: Option<u64>,
     trip_reason: Option<String>,
+    /// Webhook notified on every trip (disabled by default).
+    notifier: Notifier,
 }
 
 impl CircuitBreaker {
@@ -38,11 +52,61 @@ impl CircuitBreaker {
             max_consecutive_failures,
             max_cumulative_loss_mist,
             cooldown_ms,
+            loss_window_ms: cooldown_ms,
             consecutive_failures: 0,
             cumulative_pnl_mist: 0,
+            window: VecDeque::new(),
             total_trades: 0,
             tripped_at_ms: None,
             trip_reason: None,
+            notifier: Notifier::default(),
+        }
+    }
+
+    /// Sum losses over the last `window_ms` instead of the cooldown. 0 sums
+    /// every trade since startup.
+    pub fn with_loss_window(mut self, window_ms: u64) -> Self {
+        self.loss_window_ms = window_ms;
+        self
+    }
+
+    /// Post each trip to `notifier` (subject to its rate limit).
+    pub fn with_notifier(mut self, notifier: Notifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Rebuild a breaker from state saved with [`to_snapshot`](Self::to_snapshot),
+    /// under the current thresholds.
+    ///
+    /// `tripped_at_ms` is wall-clock epoch ms, so a breaker that tripped
+    /// before a restart stays tripped until its cooldown has elapsed in
+    /// real time, however long the bot was down.
+    pub fn from_snapshot(
+        snapshot: CircuitBreakerSnapshot,
+        max_consecutive_failures: u32,
+        max_cumulative_loss_mist: i64,
+        cooldown_ms: u64,
+    ) -> Self {
+        let mut cb = Self::new(max_consecutive_failures, max_cumulative_loss_mist, cooldown_ms);
+        cb.consecutive_failures = snapshot.consecutive_failures;
+        cb.cumulative_pnl_mist = snapshot.cumulative_pnl_mist;
+        cb.window = snapshot.window.into();
+        cb.total_trades = snapshot.total_trades;
+        cb.tripped_at_ms = snapshot.tripped_at_ms;
+        cb.trip_reason = snapshot.trip_reason;
+        cb
+    }
+
+    /// The breaker's state (not its thresholds), for persisting across restarts.
+    pub fn to_snapshot(&self) -> CircuitBreakerSnapshot {
+        CircuitBreakerSnapshot {
+            consecutive_failures: self.consecutive_failures,
+            cumulative_pnl_mist: self.cumulative_pnl_mist,
+            window: self.window.iter().copied().collect(),
+            total_trades: self.total_trades,
+            tripped_at_ms: self.tripped_at_ms,
+            trip_reason: self.trip_reason.clone(),
         }
     }
 
@@ -78,14 +142,15 @@ impl CircuitBreaker {
     }
 
     /// Record a successful, profitable trade.
-    pub fn record_success(&mut self, profit_mist: i64) {
+    pub fn record_success(&mut self, profit_mist: i64, now_ms: u64) {
         self.total_trades += 1;
         self.consecutive_failures = 0;
-        self.cumulative_pnl_mist += profit_mist;
+        self.record_pnl(profit_mist, now_ms);
 
         info!(
             profit = %profit_mist,
             cumulative_pnl = %self.cumulative_pnl_mist,
+            window_pnl = %self.window_pnl(),
             total_trades = %self.total_trades,
             "Circuit breaker: trade succeeded"
         );
@@ -96,12 +161,13 @@ impl CircuitBreaker {
     pub fn record_failure(&mut self, loss_mist: i64, now_ms: u64) -> bool {
         self.total_trades += 1;
         self.consecutive_failures += 1;
-        self.cumulative_pnl_mist += loss_mist; // loss_mist should be negative
+        self.record_pnl(loss_mist, now_ms); // loss_mist should be negative
 
         warn!(
             consecutive = %self.consecutive_failures,
             loss = %loss_mist,
             cumulative_pnl = %self.cumulative_pnl_mist,
+            window_pnl = %self.window_pnl(),
             "Circuit breaker: trade failed/lost"
         );
 
@@ -117,12 +183,19 @@ impl CircuitBreaker {
             return true;
         }
 
-        if self.cumulative_pnl_mist <= -self.max_cumulative_loss_mist {
+        let window_pnl = self.window_pnl();
+        if window_pnl <= -self.max_cumulative_loss_mist {
+            let within = if self.loss_window_ms == 0 {
+                String::new()
+            } else {
+                format!(" within {}s", self.loss_window_ms / 1000)
+            };
             self.trip(
                 now_ms,
                 format!(
-                    "Cumulative loss {} MIST exceeds limit {} MIST",
-                    self.cumulative_pnl_mist.abs(),
+                    "Cumulative loss {} MIST{} exceeds limit {} MIST",
+                    window_pnl.abs(),
+                    within,
                     self.max_cumulative_loss_mist
                 ),
             );
@@ -132,6 +205,25 @@ impl CircuitBreaker {
         false
     }
 
+    /// Add a trade's P&L to the totals and drop trades that left the window.
+    fn record_pnl(&mut self, pnl_mist: i64, now_ms: u64) {
+        self.cumulative_pnl_mist += pnl_mist;
+        self.window.push_back((now_ms, pnl_mist));
+        if self.loss_window_ms > 0 {
+            while let Some(&(at, _)) = self.window.front() {
+                if now_ms.saturating_sub(at) <= self.loss_window_ms {
+                    break;
+                }
+                self.window.pop_front();
+            }
+        }
+    }
+
+    /// P&L of the trades inside the loss window.
+    fn window_pnl(&self) -> i64 {
+        self.window.iter().map(|(_, pnl)| pnl).sum()
+    }
+
     /// Manually trip the breaker.
     fn trip(&mut self, now_ms: u64, reason: String) {
         error!(
@@ -139,6 +231,21 @@ impl CircuitBreaker {
             cooldown_ms = %self.cooldown_ms,
             "🚨 CIRCUIT BREAKER TRIPPED — trading paused"
         );
+        self.notifier.notify(
+            "breaker.tripped",
+            &format!(
+                "Circuit breaker tripped: {} — trading paused for {}s",
+                reason,
+                self.cooldown_ms / 1000
+            ),
+            json!({
+                "reason": reason,
+                "consecutive_failures": self.consecutive_failures,
+                "cumulative_pnl_mist": self.cumulative_pnl_mist,
+                "cooldown_ms": self.cooldown_ms,
+            }),
+            now_ms,
+        );
         self.tripped_at_ms = Some(now_ms);
         self.trip_reason = Some(reason);
     }
@@ -156,6 +263,7 @@ impl CircuitBreaker {
         CircuitBreakerStats {
             consecutive_failures: self.consecutive_failures,
             cumulative_pnl_mist: self.cumulative_pnl_mist,
+            window_pnl_mist: self.window_pnl(),
             total_trades: self.total_trades,
             is_tripped: self.tripped_at_ms.is_some(),
             trip_reason: self.trip_reason.clone(),
@@ -167,11 +275,75 @@ impl CircuitBreaker {
 pub struct CircuitBreakerStats {
     pub consecutive_failures: u32,
     pub cumulative_pnl_mist: i64,
+    /// P&L inside the loss window — what the loss limit is checked against.
+    pub window_pnl_mist: i64,
     pub total_trades: u64,
     pub is_tripped: bool,
     pub trip_reason: Option<String>,
 }
 
+/// Serializable breaker state — see [`CircuitBreaker::to_snapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitBreakerSnapshot {
+    pub consecutive_failures: u32,
+    pub cumulative_pnl_mist: i64,
+    /// `(timestamp_ms, pnl)` of the trades inside the loss window.
+    #[serde(default)]
+    pub window: Vec<(u64, i64)>,
+    #[serde(default)]
+    pub total_trades: u64,
+    /// Epoch ms the breaker tripped at; `None` while trading is allowed.
+    pub tripped_at_ms: Option<u64>,
+    pub trip_reason: Option<String>,
+}
+
+/// Keeps a breaker's snapshot in a JSON file so a restart doesn't clear a
+/// trip. Writes go through a temporary file and a rename, and are skipped
+/// when nothing changed since the last one.
+#[derive(Debug)]
+pub struct BreakerStore {
+    path: PathBuf,
+    last: Option<CircuitBreakerSnapshot>,
+}
+
+impl BreakerStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().to_path_buf(), last: None }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The saved snapshot, or `None` if the file doesn't exist yet.
+    pub fn load(&mut self) -> Result<Option<CircuitBreakerSnapshot>> {
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", self.path.display())),
+        };
+        let snapshot: CircuitBreakerSnapshot =
+            serde_json::from_str(&text).with_context(|| format!("Invalid breaker state in {}", self.path.display()))?;
+        self.last = Some(snapshot.clone());
+        Ok(Some(snapshot))
+    }
+
+    /// Write `breaker`'s snapshot if it changed since the last load or save.
+    /// Returns whether the file was written.
+    pub fn save(&mut self, breaker: &CircuitBreaker) -> Result<bool> {
+        let snapshot = breaker.to_snapshot();
+        if self.last.as_ref() == Some(&snapshot) {
+            return Ok(false);
+        }
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_string(&snapshot)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &self.path).with_context(|| format!("Failed to replace {}", self.path.display()))?;
+        self.last = Some(snapshot);
+        Ok(true)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,7 +368,7 @@ mod tests {
         let mut cb = CircuitBreaker::new(3, 1_000_000_000, 60_000);
         cb.record_failure(-100_000, 1000);
         cb.record_failure(-100_000, 2000);
-        cb.record_success(500_000); // resets consecutive counter
+        cb.record_success(500_000, 3000); // resets consecutive counter
         assert!(!cb.record_failure(-100_000, 4000)); // only 1 now
         assert!(cb.is_trading_allowed(4000));
     }
@@ -205,7 +377,7 @@ mod tests {
     fn test_cumulative_loss_trip() {
         let mut cb = CircuitBreaker::new(100, 500_000, 60_000); // high consec limit
         cb.record_failure(-200_000, 1000);
-        cb.record_success(50_000); // resets consecutive but not cumulative
+        cb.record_success(50_000, 2000); // resets consecutive but not cumulative
         // cumulative = -200_000 + 50_000 = -150_000
         assert!(cb.is_trading_allowed(2000));
         cb.record_failure(-400_000, 3000);
@@ -213,6 +385,39 @@ mod tests {
         assert!(!cb.is_trading_allowed(3000));
     }
 
+    #[test]
+    fn test_old_losses_age_out_of_window() {
+        let mut cb = CircuitBreaker::new(100, 500_000, 60_000).with_loss_window(10_000);
+        cb.record_failure(-400_000, 1_000);
+        // 11s later the first loss has left the window
+        assert!(!cb.record_failure(-400_000, 12_000));
+        assert!(cb.is_trading_allowed(12_000));
+        let stats = cb.stats();
+        assert_eq!(stats.cumulative_pnl_mist, -800_000, "all-time P&L still kept");
+        assert_eq!(stats.window_pnl_mist, -400_000);
+
+        // A burst inside the window trips
+        assert!(cb.record_failure(-200_000, 15_000));
+        assert!(cb.stats().trip_reason.unwrap().contains("within 10s"));
+    }
+
+    #[test]
+    fn test_loss_window_defaults_to_cooldown() {
+        let mut cb = CircuitBreaker::new(100, 500_000, 5_000);
+        cb.record_failure(-300_000, 0);
+        assert!(!cb.record_failure(-300_000, 5_001));
+        cb.record_failure(-100_000, 6_000);
+        assert!(cb.record_failure(-150_000, 7_000));
+    }
+
+    #[test]
+    fn test_zero_window_counts_all_time() {
+        let mut cb = CircuitBreaker::new(100, 500_000, 5_000).with_loss_window(0);
+        cb.record_failure(-300_000, 0);
+        cb.record_success(0, 1_000_000);
+        assert!(cb.record_failure(-300_000, 86_400_000));
+    }
+
     #[test]
     fn test_cooldown_auto_resets() {
         let mut cb = CircuitBreaker::new(1, 1_000_000_000, 5_000); // 5s cooldown
@@ -230,6 +435,7 @@ mod tests {
         let stats = cb.stats();
         assert_eq!(stats.consecutive_failures, 2);
         assert_eq!(stats.cumulative_pnl_mist, -300);
+        assert_eq!(stats.window_pnl_mist, -300);
         assert_eq!(stats.total_trades, 2);
         assert!(!stats.is_tripped);
     }
@@ -259,4 +465,66 @@ mod tests {
         cb.record_failure(0, 2000);
         assert!(!cb.is_trading_allowed(2000));
     }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("arb-breaker-{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut cb = CircuitBreaker::new(5, 1_000_000, 60_000);
+        cb.record_failure(-100, 1_000);
+        cb.record_success(300, 2_000);
+        cb.record_failure(-50, 3_000);
+        let restored = CircuitBreaker::from_snapshot(cb.to_snapshot(), 5, 1_000_000, 60_000);
+        assert_eq!(restored.to_snapshot(), cb.to_snapshot());
+        assert_eq!(restored.stats().window_pnl_mist, 150);
+        assert_eq!(restored.stats().consecutive_failures, 1);
+    }
+
+    #[test]
+    fn test_restart_while_tripped_stays_tripped() {
+        let path = temp_path("tripped");
+        let mut cb = CircuitBreaker::new(1, 1_000_000_000, 60_000);
+        cb.record_failure(-100_000, 1_000_000);
+        let mut store = BreakerStore::new(&path);
+        assert!(store.save(&cb).unwrap());
+        assert!(!store.save(&cb).unwrap(), "unchanged state isn't rewritten");
+
+        // Restarted 30s later: still inside the 60s cooldown
+        let snapshot = BreakerStore::new(&path).load().unwrap().unwrap();
+        let mut restarted = CircuitBreaker::from_snapshot(snapshot, 1, 1_000_000_000, 60_000);
+        assert!(restarted.stats().is_tripped);
+        assert!(restarted.stats().trip_reason.unwrap().contains("consecutive failures"));
+        assert!(!restarted.is_trading_allowed(1_030_000));
+        assert_eq!(restarted.stats().cumulative_pnl_mist, -100_000);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_restart_after_cooldown_resets() {
+        let path = temp_path("cooled");
+        let mut cb = CircuitBreaker::new(1, 1_000_000_000, 60_000);
+        cb.record_failure(-100_000, 1_000_000);
+        BreakerStore::new(&path).save(&cb).unwrap();
+
+        // Down for longer than the cooldown: trading resumes, P&L is kept
+        let mut store = BreakerStore::new(&path);
+        let snapshot = store.load().unwrap().unwrap();
+        let mut restarted = CircuitBreaker::from_snapshot(snapshot, 1, 1_000_000_000, 60_000);
+        assert!(restarted.is_trading_allowed(1_060_000));
+        assert!(!restarted.stats().is_tripped);
+        assert_eq!(restarted.stats().cumulative_pnl_mist, -100_000);
+
+        // The reset is persisted
+        assert!(store.save(&restarted).unwrap());
+        assert_eq!(BreakerStore::new(&path).load().unwrap().unwrap().tripped_at_ms, None);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_store_missing_file_loads_none() {
+        let mut store = BreakerStore::new(temp_path("missing"));
+        assert!(store.load().unwrap().is_none());
+    }
 }
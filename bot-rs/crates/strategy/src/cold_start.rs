@@ -0,0 +1,134 @@
+//! Cold-start handling for adaptive components.
+//!
+//! A component that learns from history (observed gas, trade outcomes, ..)
+//! has nothing to learn from on a brand-new wallet. Until it has seen
+//! `min_samples` observations it reports itself cold and callers fall back
+//! to the static defaults below — the values the bot used before it had
+//! any adaptive logic — so first-run behavior is exactly the static one.
+//!
+//! Only the gas estimator's per-hop samples (`GasSamples`) implement
+//! [`Adaptive`], so only they appear in cold-start logs and `/status`. EV
+//! ranking (`OutcomeStats`) is deliberately not tracked: a route with no
+//! history is scored from the `PRIOR_COUNTS` pseudo-counts, which are its
+//! cold-start default and fade as outcomes accrue, so there is no cutover
+//! to report. Operator-set inputs (strategy weights) and ones that follow
+//! the latest reading (the gas oracle's reference price, poll pacing) have
+//! no history to warm up.
+
+use std::collections::BTreeSet;
+use tracing::info;
+
+// ── Static defaults ──
+
+/// Gas estimate for a two-hop arb before any actuals are observed (MIST).
+pub const TWO_HOP_GAS_ESTIMATE: u64 = 5_000_000;
+
+/// Gas estimate for a tri-hop arb before any actuals are observed (MIST).
+pub const TRI_HOP_GAS_ESTIMATE: u64 = 4_000_000;
+
+/// Gas estimate for a quad-hop arb before any actuals are observed (MIST).
+pub const QUAD_HOP_GAS_ESTIMATE: u64 = 5_500_000;
+
+/// Observed gas samples needed before a median replaces the static estimate.
+pub const MIN_GAS_SAMPLES: usize = 10;
+
+// ── Adaptive components ──
+
+/// A component whose output depends on accumulated samples.
+pub trait Adaptive {
+    /// Stable name used in logs and status output.
+    fn name(&self) -> &'static str;
+    /// Samples accumulated so far.
+    fn samples(&self) -> usize;
+    /// Samples needed before the component's own output is trusted.
+    fn min_samples(&self) -> usize;
+
+    /// Whether the component has enough history to replace its static default.
+    fn is_warm(&self) -> bool {
+        self.samples() >= self.min_samples()
+    }
+}
+
+/// Tracks which adaptive components are still in cold start and logs
+/// transitions in and out of it.
+#[derive(Debug, Default)]
+pub struct ColdStartMonitor {
+    seen: BTreeSet<&'static str>,
+    cold: BTreeSet<&'static str>,
+}
+
+impl ColdStartMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check `component`'s state. Returns `true` if it just left cold start.
+    pub fn observe(&mut self, component: &dyn Adaptive) -> bool {
+        let name = component.name();
+        let warm = component.is_warm();
+        let first = self.seen.insert(name);
+
+        if first && !warm {
+            info!(
+                component = %name,
+                samples = %component.samples(),
+                min_samples = %component.min_samples(),
+                "🧊 Cold start — using static defaults"
+            );
+            self.cold.insert(name);
+            false
+        } else if warm && self.cold.remove(name) {
+            info!(
+                component = %name,
+                samples = %component.samples(),
+                "🔥 Left cold start — using observed history"
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Components observed in cold start and not yet warm, sorted by name.
+    pub fn cold_components(&self) -> Vec<&'static str> {
+        self.cold.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter(usize);
+
+    impl Adaptive for Counter {
+        fn name(&self) -> &'static str {
+            "counter"
+        }
+        fn samples(&self) -> usize {
+            self.0
+        }
+        fn min_samples(&self) -> usize {
+            3
+        }
+    }
+
+    #[test]
+    fn test_monitor_reports_cold_then_transition() {
+        let mut m = ColdStartMonitor::new();
+        assert!(!m.observe(&Counter(0)));
+        assert_eq!(m.cold_components(), vec!["counter"]);
+        assert!(!m.observe(&Counter(2)));
+        assert!(m.observe(&Counter(3)));
+        assert!(m.cold_components().is_empty());
+        // Transition is reported once
+        assert!(!m.observe(&Counter(4)));
+    }
+
+    #[test]
+    fn test_monitor_warm_from_start_is_never_cold() {
+        let mut m = ColdStartMonitor::new();
+        assert!(!m.observe(&Counter(5)));
+        assert!(m.cold_components().is_empty());
+    }
+}
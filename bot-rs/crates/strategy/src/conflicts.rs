@@ -0,0 +1,276 @@
+use arb_types::opportunity::ArbOpportunity;
+use arb_types::pool::PoolState;
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Direction a leg trades through its pool, relative to the pool's `<A, B>` ordering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapDirection {
+    AToB,
+    BToA,
+}
+
+/// Per-leg `(pool_id, direction)`, or `None` if any leg couldn't be resolved.
+type Legs = Option<Vec<(String, SwapDirection)>>;
+
+/// Two opportunities in a batch that would trade the same pool against each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// Index (in the batch) of the earlier opportunity.
+    pub first: usize,
+    /// Index (in the batch) of the later opportunity.
+    pub second: usize,
+    pub pool_id: String,
+}
+
+/// Resolve the swap direction of each leg of `opp`.
+///
+/// The token path comes from the leading type args: two-hop `[A, B]` trades
+/// A→B then B→A; tri-hop `[A, B, C]` trades A→B, B→C, C→A. Reverse
+/// strategies keep the forward `<A, B>` args but start from B, so their path
+/// is rotated: B→A then A→B. Returns `None` if a pool is missing from
+/// `pools` or doesn't trade the expected pair.
+pub fn leg_directions(opp: &ArbOpportunity, pools: &[PoolState]) -> Legs {
+    let hops = opp.pool_ids.len();
+    // An n-hop cycle visits n distinct tokens (extra args like a fee type follow)
+    if hops < 2 || opp.type_args.len() < hops {
+        return None;
+    }
+    let mut tokens = opp.type_args[..hops].to_vec();
+    if opp.strategy.is_reverse() {
+        tokens.rotate_left(1);
+    }
+
+    opp.pool_ids
+        .iter()
+        .enumerate()
+        .map(|(i, pool_id)| {
+            let pool = pools.iter().find(|p| &p.object_id == pool_id)?;
+            let from = &tokens[i % tokens.len()];
+            let to = &tokens[(i + 1) % tokens.len()];
+            let dir = if &pool.coin_type_a == from && &pool.coin_type_b == to {
+                SwapDirection::AToB
+            } else if &pool.coin_type_b == from && &pool.coin_type_a == to {
+                SwapDirection::BToA
+            } else {
+                return None;
+            };
+            Some((pool_id.clone(), dir))
+        })
+        .collect()
+}
+
+/// Find self-sandwich conflicts in a batch of opportunities meant to execute
+/// concurrently: any pool traded in opposite directions by two of them, or
+/// shared by an opportunity whose directions can't be resolved.
+pub fn find_conflicts(batch: &[ArbOpportunity], pools: &[PoolState]) -> Vec<Conflict> {
+    let legs: Vec<Legs> = batch.iter().map(|o| leg_directions(o, pools)).collect();
+
+    let mut conflicts = Vec::new();
+    for i in 0..batch.len() {
+        for j in (i + 1)..batch.len() {
+            if let Some(pool_id) = conflicting_pool(&batch[i], &legs[i], &batch[j], &legs[j]) {
+                conflicts.push(Conflict {
+                    first: i,
+                    second: j,
+                    pool_id,
+                });
+            }
+        }
+    }
+    conflicts
+}
+
+/// Greedily pick up to `max` opportunities (in the given order, i.e. best
+/// first) such that the selected batch has no self-sandwich conflicts.
+pub fn select_conflict_free(
+    candidates: Vec<ArbOpportunity>,
+    pools: &[PoolState],
+    max: usize,
+) -> Vec<ArbOpportunity> {
+    let mut selected: Vec<(ArbOpportunity, Legs)> = Vec::new();
+
+    for opp in candidates {
+        if selected.len() >= max {
+            break;
+        }
+        let legs = leg_directions(&opp, pools);
+        let clash = selected
+            .iter()
+            .find_map(|(s, s_legs)| conflicting_pool(s, s_legs, &opp, &legs));
+        match clash {
+            Some(pool_id) => debug!(
+                strategy = %opp.strategy,
+                pool = %pool_id,
+                "Skipping opportunity — would trade against a selected one on the same pool"
+            ),
+            None => selected.push((opp, legs)),
+        }
+    }
+
+    selected.into_iter().map(|(o, _)| o).collect()
+}
+
+/// The first pool on which `a` and `b` conflict, if any.
+fn conflicting_pool(
+    a: &ArbOpportunity,
+    a_legs: &Legs,
+    b: &ArbOpportunity,
+    b_legs: &Legs,
+) -> Option<String> {
+    match (a_legs, b_legs) {
+        (Some(a_legs), Some(b_legs)) => {
+            let a_dirs: HashMap<&str, SwapDirection> =
+                a_legs.iter().map(|(id, d)| (id.as_str(), *d)).collect();
+            b_legs
+                .iter()
+                .find(|(id, d)| a_dirs.get(id.as_str()).is_some_and(|ad| ad != d))
+                .map(|(id, _)| id.clone())
+        }
+        // Unknown direction: any shared pool is treated as a conflict
+        _ => a.pool_ids.iter().find(|id| b.pool_ids.contains(id)).cloned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arb_types::opportunity::StrategyType;
+    use arb_types::pool::Dex;
+
+    fn pool(id: &str, dex: Dex, a: &str, b: &str) -> PoolState {
+        PoolState {
+            object_id: id.to_string(),
+            dex,
+            coin_type_a: a.to_string(),
+            coin_type_b: b.to_string(),
+            sqrt_price: Some(1u128 << 64),
+            tick_index: Some(0),
+            liquidity: Some(1_000_000),
+            fee_rate_bps: Some(30),
+            reserve_a: None,
+            reserve_b: None,
+            best_bid: None,
+            best_ask: None,
+            book_depth: None,
+            last_updated_ms: 0,
+            fee_type: None,
+            min_trade_size: None,
+            lot_size: None,
+            raw_balance_a: None,
+            raw_balance_b: None,
+        }
+    }
+
+    fn opp(pool_ids: &[&str], type_args: &[&str], profit: u64) -> ArbOpportunity {
+        ArbOpportunity {
+            strategy: StrategyType::CetusToTurbos,
+            amount_in: 1_000_000_000,
+            expected_profit: profit,
+            estimated_gas: 0,
+            net_profit: profit as i64,
+            pool_ids: pool_ids.iter().map(|s| s.to_string()).collect(),
+            type_args: type_args.iter().map(|s| s.to_string()).collect(),
+            detected_at_ms: 0,
+        }
+    }
+
+    fn pools() -> Vec<PoolState> {
+        vec![
+            pool("0x1", Dex::Cetus, "SUI", "USDC"),
+            pool("0x2", Dex::Turbos, "SUI", "USDC"),
+            pool("0x3", Dex::Cetus, "SUI", "USDC"),
+            pool("0x4", Dex::Cetus, "USDC", "CETUS"),
+            pool("0x5", Dex::Cetus, "CETUS", "SUI"),
+        ]
+    }
+
+    #[test]
+    fn test_leg_directions_two_hop() {
+        let o = opp(&["0x1", "0x2"], &["SUI", "USDC"], 1);
+        let legs = leg_directions(&o, &pools()).unwrap();
+        assert_eq!(legs[0], ("0x1".to_string(), SwapDirection::AToB));
+        assert_eq!(legs[1], ("0x2".to_string(), SwapDirection::BToA));
+    }
+
+    #[test]
+    fn test_leg_directions_reverse_two_hop() {
+        let mut o = opp(&["0x1", "0x2"], &["SUI", "USDC"], 1);
+        o.strategy = StrategyType::CetusToTurbosRev;
+        let legs = leg_directions(&o, &pools()).unwrap();
+        assert_eq!(legs[0], ("0x1".to_string(), SwapDirection::BToA));
+        assert_eq!(legs[1], ("0x2".to_string(), SwapDirection::AToB));
+    }
+
+    #[test]
+    fn test_leg_directions_tri_hop_ignores_fee_type_arg() {
+        let o = opp(&["0x1", "0x4", "0x5"], &["SUI", "USDC", "CETUS", "0x91::fee::FEE"], 1);
+        let legs = leg_directions(&o, &pools()).unwrap();
+        assert!(legs.iter().all(|(_, d)| *d == SwapDirection::AToB));
+    }
+
+    #[test]
+    fn test_opposite_direction_same_pool_conflicts() {
+        // A buys on 0x1 / sells on 0x2; B buys on 0x2 / sells on 0x1
+        let a = opp(&["0x1", "0x2"], &["SUI", "USDC"], 10);
+        let b = opp(&["0x2", "0x1"], &["SUI", "USDC"], 5);
+        let conflicts = find_conflicts(&[a, b], &pools());
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!((conflicts[0].first, conflicts[0].second), (0, 1));
+    }
+
+    #[test]
+    fn test_forward_and_reverse_on_same_pool_conflict() {
+        // Both flash on Cetus 0x1: the forward trade sells SUI into it, the
+        // reverse one sells USDC, each with the scanner's `<SUI, USDC>` args
+        let forward = opp(&["0x1", "0x2"], &["SUI", "USDC"], 10);
+        let mut reverse = opp(&["0x1", "0x3"], &["SUI", "USDC"], 5);
+        reverse.strategy = StrategyType::CetusToTurbosRev;
+        let conflicts = find_conflicts(&[forward.clone(), reverse.clone()], &pools());
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].pool_id, "0x1");
+        assert_eq!(select_conflict_free(vec![forward, reverse], &pools(), 2).len(), 1);
+    }
+
+    #[test]
+    fn test_reverse_same_direction_shared_pool_allowed() {
+        // Reverse on 0x1 trades it USDC→SUI, like a forward trade selling on it
+        let mut reverse = opp(&["0x1", "0x2"], &["SUI", "USDC"], 10);
+        reverse.strategy = StrategyType::CetusToTurbosRev;
+        let forward = opp(&["0x3", "0x1"], &["SUI", "USDC"], 5);
+        assert!(find_conflicts(&[reverse, forward], &pools()).is_empty());
+    }
+
+    #[test]
+    fn test_same_direction_shared_pool_allowed() {
+        let a = opp(&["0x1", "0x2"], &["SUI", "USDC"], 10);
+        let b = opp(&["0x1", "0x3"], &["SUI", "USDC"], 5);
+        assert!(find_conflicts(&[a, b], &pools()).is_empty());
+    }
+
+    #[test]
+    fn test_unresolvable_direction_shared_pool_conflicts() {
+        let a = opp(&["0x1", "0x2"], &["SUI", "USDC"], 10);
+        let b = opp(&["0x1", "0xmissing"], &["SUI", "USDC"], 5);
+        assert_eq!(find_conflicts(&[a, b], &pools()).len(), 1);
+    }
+
+    #[test]
+    fn test_select_conflict_free_keeps_best_and_skips_opposing() {
+        let best = opp(&["0x1", "0x2"], &["SUI", "USDC"], 10);
+        let opposing = opp(&["0x2", "0x3"], &["SUI", "USDC"], 8); // 0x2 a2b vs b2a
+        // Shares 0x1 with `best`, but both trade it SUI→USDC (a2b)
+        let compatible = opp(&["0x3", "0x1"], &["USDC", "SUI"], 6);
+        let selected = select_conflict_free(vec![best, opposing, compatible], &pools(), 3);
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected[0].expected_profit, 10);
+        assert_eq!(selected[1].expected_profit, 6);
+    }
+
+    #[test]
+    fn test_select_conflict_free_respects_max() {
+        let a = opp(&["0x1", "0x2"], &["SUI", "USDC"], 10);
+        let b = opp(&["0x1", "0x3"], &["SUI", "USDC"], 5);
+        assert_eq!(select_conflict_free(vec![a, b], &pools(), 1).len(), 1);
+    }
+}
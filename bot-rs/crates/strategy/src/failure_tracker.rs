@@ -0,0 +1,153 @@
+use arb_types::opportunity::StrategyType;
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+/// Per-strategy PTB build failure tracking.
+///
+/// A strategy whose builds keep getting rejected (stale object IDs, a type
+/// argument the Move function no longer accepts, ..) is suspended so it stops
+/// burning RPC calls every cycle. Transient RPC failures should not be
+/// recorded here — they aren't the strategy's fault.
+///
+/// After the suspension expires the strategy is half-open: one successful
+/// build clears it, one more failure suspends it again.
+#[derive(Debug)]
+pub struct FailureTracker {
+    // ── Config ──
+    max_consecutive: u32,
+    suspend_ms: u64,
+
+    // ── State ──
+    per_strategy: HashMap<StrategyType, FailureEntry>,
+}
+
+/// Build failure history for one strategy.
+#[derive(Debug, Clone, Default)]
+pub struct FailureEntry {
+    pub consecutive: u32,
+    pub total: u64,
+    pub suspended_until_ms: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+impl FailureTracker {
+    /// Suspend a strategy for `suspend_ms` after `max_consecutive` build
+    /// failures in a row (0 = never suspend).
+    pub fn new(max_consecutive: u32, suspend_ms: u64) -> Self {
+        Self {
+            max_consecutive,
+            suspend_ms,
+            per_strategy: HashMap::new(),
+        }
+    }
+
+    /// Record a build failure. Returns `true` if this failure suspended the strategy.
+    pub fn record_failure(&mut self, strategy: StrategyType, error: &str, now_ms: u64) -> bool {
+        let entry = self.per_strategy.entry(strategy).or_default();
+        entry.consecutive += 1;
+        entry.total += 1;
+        entry.last_error = Some(error.to_string());
+
+        let half_open = entry.suspended_until_ms.is_some_and(|until| now_ms >= until);
+        if self.max_consecutive == 0 || (entry.consecutive < self.max_consecutive && !half_open) {
+            return false;
+        }
+
+        let until = now_ms + self.suspend_ms;
+        entry.suspended_until_ms = Some(until);
+        warn!(
+            strategy = %strategy,
+            consecutive = %entry.consecutive,
+            suspend_ms = %self.suspend_ms,
+            error = %error,
+            "⏸️ Strategy suspended after repeated PTB build failures"
+        );
+        true
+    }
+
+    /// Record a successful build, clearing the failure streak and any suspension.
+    pub fn record_success(&mut self, strategy: StrategyType) {
+        if let Some(entry) = self.per_strategy.get_mut(&strategy) {
+            if entry.suspended_until_ms.take().is_some() {
+                info!(strategy = %strategy, "▶️ Strategy build recovered — suspension cleared");
+            }
+            entry.consecutive = 0;
+        }
+    }
+
+    /// Whether `strategy` is currently suspended.
+    pub fn is_suspended(&self, strategy: StrategyType, now_ms: u64) -> bool {
+        self.per_strategy
+            .get(&strategy)
+            .and_then(|e| e.suspended_until_ms)
+            .is_some_and(|until| now_ms < until)
+    }
+
+    /// Strategies currently suspended, with the time each resumes.
+    pub fn suspended(&self, now_ms: u64) -> Vec<(StrategyType, u64)> {
+        let mut out: Vec<(StrategyType, u64)> = self
+            .per_strategy
+            .iter()
+            .filter_map(|(s, e)| e.suspended_until_ms.filter(|&u| now_ms < u).map(|u| (*s, u)))
+            .collect();
+        out.sort_by_key(|(_, until)| *until);
+        out
+    }
+
+    /// Failure history for `strategy`, if it has ever failed.
+    pub fn entry(&self, strategy: StrategyType) -> Option<&FailureEntry> {
+        self.per_strategy.get(&strategy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const S: StrategyType = StrategyType::CetusToTurbos;
+
+    #[test]
+    fn test_suspends_after_max_consecutive() {
+        let mut t = FailureTracker::new(3, 1_000);
+        assert!(!t.record_failure(S, "bad arg", 0));
+        assert!(!t.record_failure(S, "bad arg", 1));
+        assert!(t.record_failure(S, "bad arg", 2));
+        assert!(t.is_suspended(S, 500));
+        assert!(!t.is_suspended(StrategyType::TurbosToCetus, 500));
+        assert_eq!(t.suspended(500), vec![(S, 1_002)]);
+        assert_eq!(t.entry(S).unwrap().total, 3);
+    }
+
+    #[test]
+    fn test_success_resets_streak() {
+        let mut t = FailureTracker::new(2, 1_000);
+        t.record_failure(S, "bad arg", 0);
+        t.record_success(S);
+        assert!(!t.record_failure(S, "bad arg", 1));
+        assert!(!t.is_suspended(S, 2));
+    }
+
+    #[test]
+    fn test_half_open_after_expiry() {
+        let mut t = FailureTracker::new(2, 1_000);
+        t.record_failure(S, "bad arg", 0);
+        t.record_failure(S, "bad arg", 0);
+        assert!(!t.is_suspended(S, 1_000));
+        // One failure after expiry suspends again
+        assert!(t.record_failure(S, "bad arg", 1_000));
+        assert!(t.is_suspended(S, 1_500));
+        // One success clears it
+        t.record_success(S);
+        assert!(!t.is_suspended(S, 1_500));
+        assert!(t.suspended(1_500).is_empty());
+    }
+
+    #[test]
+    fn test_zero_max_never_suspends() {
+        let mut t = FailureTracker::new(0, 1_000);
+        for i in 0..10 {
+            assert!(!t.record_failure(S, "bad arg", i));
+        }
+        assert!(!t.is_suspended(S, 10));
+    }
+}
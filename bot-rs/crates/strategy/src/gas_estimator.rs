@@ -0,0 +1,162 @@
+use arb_types::opportunity::ArbOpportunity;
+use std::collections::VecDeque;
+
+use crate::cold_start::{Adaptive, MIN_GAS_SAMPLES, TRI_HOP_GAS_ESTIMATE, TWO_HOP_GAS_ESTIMATE};
+
+/// Recent gas actuals kept per route shape.
+const GAS_SAMPLE_WINDOW: usize = 100;
+
+/// Rolling gas samples for one route shape, with a static fallback.
+#[derive(Debug)]
+pub struct GasSamples {
+    name: &'static str,
+    default_mist: u64,
+    min_samples: usize,
+    samples: VecDeque<u64>,
+}
+
+impl GasSamples {
+    fn new(name: &'static str, default_mist: u64) -> Self {
+        Self {
+            name,
+            default_mist,
+            min_samples: MIN_GAS_SAMPLES,
+            samples: VecDeque::with_capacity(GAS_SAMPLE_WINDOW),
+        }
+    }
+
+    fn record(&mut self, gas_mist: u64) {
+        if self.samples.len() == GAS_SAMPLE_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(gas_mist);
+    }
+
+    /// Median of the recent samples once warm, else the static default.
+    pub fn estimate(&self) -> u64 {
+        if !self.is_warm() || self.samples.is_empty() {
+            return self.default_mist;
+        }
+        let mut sorted: Vec<u64> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        sorted[sorted.len() / 2]
+    }
+}
+
+impl Adaptive for GasSamples {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn samples(&self) -> usize {
+        self.samples.len()
+    }
+
+    fn min_samples(&self) -> usize {
+        self.min_samples
+    }
+}
+
+/// Gas estimates learned from dry-run and on-chain actuals.
+///
+/// Until enough samples exist for a route shape, estimates are the scanner's
+/// static defaults, so a first run ranks and filters exactly as before.
+#[derive(Debug)]
+pub struct GasEstimator {
+    pub two_hop: GasSamples,
+    pub tri_hop: GasSamples,
+}
+
+impl Default for GasEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GasEstimator {
+    pub fn new() -> Self {
+        Self {
+            two_hop: GasSamples::new("gas_estimate.two_hop", TWO_HOP_GAS_ESTIMATE),
+            tri_hop: GasSamples::new("gas_estimate.tri_hop", TRI_HOP_GAS_ESTIMATE),
+        }
+    }
+
+    /// Override the sample threshold for leaving cold start.
+    pub fn with_min_samples(mut self, n: usize) -> Self {
+        self.two_hop.min_samples = n;
+        self.tri_hop.min_samples = n;
+        self
+    }
+
+    fn for_hops(&self, hops: usize) -> &GasSamples {
+        if hops >= 3 { &self.tri_hop } else { &self.two_hop }
+    }
+
+    /// Record the actual gas used by an arb over `hops` pools.
+    pub fn record(&mut self, hops: usize, gas_mist: u64) {
+        if hops >= 3 {
+            self.tri_hop.record(gas_mist);
+        } else {
+            self.two_hop.record(gas_mist);
+        }
+    }
+
+    /// Estimated gas for an arb over `hops` pools.
+    pub fn estimate(&self, hops: usize) -> u64 {
+        self.for_hops(hops).estimate()
+    }
+
+    /// Replace `opp`'s gas estimate with the learned one and recompute net
+    /// profit. A no-op while the route shape is in cold start.
+    pub fn apply(&self, opp: &mut ArbOpportunity) {
+        let samples = self.for_hops(opp.pool_ids.len());
+        if !samples.is_warm() {
+            return;
+        }
+        opp.estimated_gas = samples.estimate();
+        opp.net_profit = opp.expected_profit as i64 - opp.estimated_gas as i64;
+    }
+
+    /// Every adaptive component, for cold-start monitoring.
+    pub fn components(&self) -> [&dyn Adaptive; 2] {
+        [&self.two_hop, &self.tri_hop]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_history_uses_static_defaults() {
+        let g = GasEstimator::new();
+        assert_eq!(g.estimate(2), TWO_HOP_GAS_ESTIMATE);
+        assert_eq!(g.estimate(3), TRI_HOP_GAS_ESTIMATE);
+        assert!(g.components().iter().all(|c| !c.is_warm()));
+    }
+
+    #[test]
+    fn test_median_after_min_samples() {
+        let mut g = GasEstimator::new().with_min_samples(3);
+        g.record(2, 3_000_000);
+        g.record(2, 1_000_000);
+        assert_eq!(g.estimate(2), TWO_HOP_GAS_ESTIMATE);
+        g.record(2, 2_000_000);
+        assert_eq!(g.estimate(2), 2_000_000);
+        // Tri-hop still cold
+        assert_eq!(g.estimate(3), TRI_HOP_GAS_ESTIMATE);
+    }
+
+    #[test]
+    fn test_window_drops_oldest() {
+        let mut g = GasEstimator::new().with_min_samples(1);
+        for _ in 0..GAS_SAMPLE_WINDOW {
+            g.record(2, 9_000_000);
+        }
+        for _ in 0..=GAS_SAMPLE_WINDOW / 2 {
+            g.record(2, 1_000_000);
+        }
+        assert_eq!(g.two_hop.samples(), GAS_SAMPLE_WINDOW);
+        assert_eq!(g.estimate(2), 1_000_000);
+    }
+}
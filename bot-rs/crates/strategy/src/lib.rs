@@ -1,9 +1,30 @@
 pub mod circuit_breaker;
+pub mod cold_start;
+pub mod conflicts;
+pub mod failure_tracker;
+pub mod gas_estimator;
+pub mod opportunity_tracker;
 pub mod optimizer;
+pub mod outcomes;
+pub mod quarantine;
 pub mod scanner;
 pub mod simulator;
+pub mod trade_stats;
+pub mod weights;
 
-pub use circuit_breaker::CircuitBreaker;
-pub use optimizer::{build_local_simulator, ternary_search};
+pub use circuit_breaker::{BreakerStore, CircuitBreaker, CircuitBreakerSnapshot};
+pub use cold_start::ColdStartMonitor;
+pub use failure_tracker::FailureTracker;
+pub use gas_estimator::GasEstimator;
+pub use opportunity_tracker::OpportunityTracker;
+pub use outcomes::OutcomeStats;
+pub use optimizer::{
+    build_local_quad_simulator, build_local_simulator, build_opportunity_simulator, build_local_tri_simulator, build_strategy_simulator, flash_fee_bps, golden_section_search, optimize_amount,
+    optimize_amount_lots, simulate_clob_fill, strategy_flash_fee_bps, ternary_search, ternary_search_lots,
+    MissingFeePolicy, SearchMethod, MIN_SIM_AMOUNT,
+};
+pub use quarantine::StrategyQuarantine;
 pub use scanner::Scanner;
-pub use simulator::DryRunner;
+pub use simulator::{DryRunResult, DryRunner};
+pub use trade_stats::{TradeOutcome, TradeStats};
+pub use weights::StrategyWeights;
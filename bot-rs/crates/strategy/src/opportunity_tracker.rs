@@ -0,0 +1,167 @@
+use arb_types::opportunity::{ArbOpportunity, StrategyType};
+use std::collections::HashMap;
+
+/// Per-opportunity cooldown: stops the loop from re-attempting the same
+/// strategy over the same pools every tick while a spread persists.
+///
+/// Opportunities are keyed by strategy plus their pool IDs in sorted order,
+/// so the same pools visited in a different order are one entry. After a
+/// failed attempt (dry-run rejection, build or submit error, revert) the key
+/// is skipped for `failure_cooldown_ms`; after a landed trade for
+/// `success_cooldown_ms`, long enough for our own trade to reach the pool
+/// states. 0 disables either cooldown.
+#[derive(Debug)]
+pub struct OpportunityTracker {
+    // ── Config ──
+    failure_cooldown_ms: u64,
+    success_cooldown_ms: u64,
+
+    // ── State ──
+    attempts: HashMap<OpportunityKey, LastAttempt>,
+}
+
+/// Strategy plus sorted pool IDs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OpportunityKey {
+    pub strategy: StrategyType,
+    pub pool_ids: Vec<String>,
+}
+
+impl OpportunityKey {
+    pub fn of(opp: &ArbOpportunity) -> Self {
+        let mut pool_ids = opp.pool_ids.clone();
+        pool_ids.sort();
+        Self { strategy: opp.strategy, pool_ids }
+    }
+}
+
+/// The most recent attempt at one opportunity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LastAttempt {
+    pub at_ms: u64,
+    pub succeeded: bool,
+}
+
+impl OpportunityTracker {
+    pub fn new(failure_cooldown_ms: u64, success_cooldown_ms: u64) -> Self {
+        Self {
+            failure_cooldown_ms,
+            success_cooldown_ms,
+            attempts: HashMap::new(),
+        }
+    }
+
+    /// Record a failed attempt at `opp`.
+    pub fn record_failure(&mut self, opp: &ArbOpportunity, now_ms: u64) {
+        self.record(opp, false, now_ms);
+    }
+
+    /// Record a trade for `opp` that landed.
+    pub fn record_success(&mut self, opp: &ArbOpportunity, now_ms: u64) {
+        self.record(opp, true, now_ms);
+    }
+
+    /// Whether `opp` was attempted too recently to try again.
+    pub fn is_cooling_down(&self, opp: &ArbOpportunity, now_ms: u64) -> bool {
+        self.attempts
+            .get(&OpportunityKey::of(opp))
+            .is_some_and(|last| now_ms < last.at_ms + self.cooldown_for(last.succeeded))
+    }
+
+    /// The last attempt at `opp`, if any is remembered.
+    pub fn last_attempt(&self, opp: &ArbOpportunity) -> Option<LastAttempt> {
+        self.attempts.get(&OpportunityKey::of(opp)).copied()
+    }
+
+    /// Forget attempts whose cooldown has elapsed.
+    pub fn prune(&mut self, now_ms: u64) {
+        let (failure, success) = (self.failure_cooldown_ms, self.success_cooldown_ms);
+        self.attempts
+            .retain(|_, last| now_ms < last.at_ms + if last.succeeded { success } else { failure });
+    }
+
+    /// Opportunities currently remembered.
+    pub fn len(&self) -> usize {
+        self.attempts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attempts.is_empty()
+    }
+
+    fn record(&mut self, opp: &ArbOpportunity, succeeded: bool, now_ms: u64) {
+        self.attempts
+            .insert(OpportunityKey::of(opp), LastAttempt { at_ms: now_ms, succeeded });
+    }
+
+    fn cooldown_for(&self, succeeded: bool) -> u64 {
+        if succeeded {
+            self.success_cooldown_ms
+        } else {
+            self.failure_cooldown_ms
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opp(strategy: StrategyType, pools: &[&str]) -> ArbOpportunity {
+        ArbOpportunity {
+            strategy,
+            amount_in: 1_000_000_000,
+            expected_profit: 5_000_000,
+            estimated_gas: 3_000_000,
+            net_profit: 2_000_000,
+            pool_ids: pools.iter().map(|p| p.to_string()).collect(),
+            type_args: vec![],
+            detected_at_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_pool_order_collapses_to_one_key() {
+        let mut t = OpportunityTracker::new(10_000, 2_000);
+        let abc = opp(StrategyType::TriCetusTurbosDeepBook, &["0xa", "0xb", "0xc"]);
+        let cab = opp(StrategyType::TriCetusTurbosDeepBook, &["0xc", "0xa", "0xb"]);
+        t.record_failure(&abc, 0);
+        t.record_failure(&cab, 1);
+        assert_eq!(t.len(), 1);
+        assert!(t.is_cooling_down(&cab, 5_000));
+
+        // Another strategy over the same pools is a different opportunity
+        let other = opp(StrategyType::TriCetusDeepBookTurbos, &["0xa", "0xb", "0xc"]);
+        assert!(!t.is_cooling_down(&other, 5_000));
+    }
+
+    #[test]
+    fn test_cooldowns_expire() {
+        let mut t = OpportunityTracker::new(10_000, 2_000);
+        let failed = opp(StrategyType::CetusToTurbos, &["0x1", "0x2"]);
+        let landed = opp(StrategyType::TurbosToCetus, &["0x3", "0x4"]);
+        t.record_failure(&failed, 1_000);
+        t.record_success(&landed, 1_000);
+
+        assert!(t.is_cooling_down(&failed, 2_999));
+        assert!(t.is_cooling_down(&landed, 2_999));
+        assert!(!t.is_cooling_down(&landed, 3_000));
+        assert!(t.is_cooling_down(&failed, 10_999));
+        assert!(!t.is_cooling_down(&failed, 11_000));
+
+        t.prune(3_000);
+        assert_eq!(t.last_attempt(&failed), Some(LastAttempt { at_ms: 1_000, succeeded: false }));
+        assert_eq!(t.last_attempt(&landed), None);
+        t.prune(11_000);
+        assert!(t.is_empty());
+    }
+
+    #[test]
+    fn test_latest_outcome_wins() {
+        let mut t = OpportunityTracker::new(10_000, 2_000);
+        let o = opp(StrategyType::CetusToTurbos, &["0x1", "0x2"]);
+        t.record_failure(&o, 0);
+        t.record_success(&o, 1_000);
+        assert!(!t.is_cooling_down(&o, 3_000));
+    }
+}
@@ -0,0 +1,327 @@
+use arb_types::opportunity::ArbOpportunity;
+use std::collections::HashMap;
+
+/// Move module holding the on-chain min_profit guard (`profit::assert_profit`).
+pub const PROFIT_MODULE: &str = "profit";
+
+/// Abort code of `profit::assert_profit` (`E_NOT_PROFITABLE`).
+pub const E_NOT_PROFITABLE: u64 = 1;
+
+/// A failed transaction without a parseable abort is treated as a guard
+/// revert when it burned at least this fraction of the estimated gas (both
+/// swaps ran), and as an early abort otherwise.
+pub const FULL_GAS_FRACTION: f64 = 0.8;
+
+/// A fill realizing at least this fraction of the expected profit counts
+/// as at expectation.
+pub const FILL_TOLERANCE: f64 = 0.95;
+
+/// Pseudo-counts for each outcome on a route with no history, in
+/// `OutcomeKind` order.
+pub const PRIOR_COUNTS: [f64; 4] = [4.0, 2.0, 1.0, 1.0];
+
+/// Prior realized/expected profit ratio for below-expectation fills.
+pub const PRIOR_BELOW_RATIO: f64 = 0.5;
+
+/// Prior gas_used/estimated_gas ratio for early aborts.
+pub const PRIOR_EARLY_GAS_RATIO: f64 = 0.3;
+
+/// A parsed `MoveAbort(..)` from an execution error string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveAbort {
+    /// Aborting module name (e.g. `profit`), when the location is present
+    pub module: Option<String>,
+    pub function_name: Option<String>,
+    pub code: u64,
+}
+
+impl MoveAbort {
+    /// Whether this is our own min_profit guard tripping.
+    pub fn is_profit_guard(&self) -> bool {
+        self.code == E_NOT_PROFITABLE && self.module.as_deref() == Some(PROFIT_MODULE)
+    }
+}
+
+/// Parse the abort location and code out of a Sui execution error, e.g.
+/// `MoveAbort(MoveLocation { module: ModuleId { address: .., name:
+/// Identifier("profit") }, .., function_name: Some("assert_profit") }, 1) in
+/// command 2`.
+pub fn parse_move_abort(error: &str) -> Option<MoveAbort> {
+    let start = error.find("MoveAbort(")? + "MoveAbort(".len();
+    let rest = &error[start..];
+
+    // Arguments up to the matching close paren; the code is the last one
+    let mut depth = 0i32;
+    let mut end = None;
+    let mut last_comma = None;
+    for (i, ch) in rest.char_indices() {
+        match ch {
+            '(' | '{' | '[' => depth += 1,
+            ')' | '}' | ']' if depth == 0 => {
+                end = Some(i);
+                break;
+            }
+            ')' | '}' | ']' => depth -= 1,
+            ',' if depth == 0 => last_comma = Some(i),
+            _ => {}
+        }
+    }
+    let args = &rest[..end?];
+    let code = args[last_comma? + 1..].trim().parse().ok()?;
+
+    let quoted_after = |marker: &str| {
+        let s = &args[args.find(marker)? + marker.len()..];
+        Some(s[..s.find('"')?].to_string())
+    };
+    Some(MoveAbort {
+        module: quoted_after("name: Identifier(\""),
+        function_name: quoted_after("function_name: Some(\""),
+        code,
+    })
+}
+
+/// How an executed transaction turned out, by what it cost us.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutcomeKind {
+    /// Realized roughly the expected profit
+    Filled = 0,
+    /// Succeeded, but the price moved: profit below expectation, above the guard
+    FilledBelowExpectation = 1,
+    /// The min_profit guard aborted after both swaps ran — full gas lost
+    GuardRevert = 2,
+    /// Aborted before the guard (a leg's own check, a bad input) — partial gas
+    EarlyAbort = 3,
+}
+
+/// Classify an on-chain result for `opp` from its status, realized profit,
+/// gas used, and (for failures) the abort in `error`.
+pub fn classify(
+    opp: &ArbOpportunity,
+    success: bool,
+    profit_mist: Option<u64>,
+    gas_used: u64,
+    error: Option<&str>,
+) -> OutcomeKind {
+    if success {
+        let realized = profit_mist.unwrap_or(0) as f64;
+        return if realized >= opp.expected_profit as f64 * FILL_TOLERANCE {
+            OutcomeKind::Filled
+        } else {
+            OutcomeKind::FilledBelowExpectation
+        };
+    }
+
+    match error.and_then(parse_move_abort) {
+        Some(abort) if abort.is_profit_guard() => OutcomeKind::GuardRevert,
+        Some(_) => OutcomeKind::EarlyAbort,
+        None if gas_used as f64 >= opp.estimated_gas as f64 * FULL_GAS_FRACTION => OutcomeKind::GuardRevert,
+        None => OutcomeKind::EarlyAbort,
+    }
+}
+
+/// Outcome history for one route.
+#[derive(Debug, Clone, Default)]
+pub struct RouteOutcomes {
+    pub counts: [u64; 4],
+    /// Sum of realized/expected profit over below-expectation fills
+    below_ratio_sum: f64,
+    /// Sum of gas_used/estimated_gas over early aborts
+    early_gas_ratio_sum: f64,
+}
+
+impl RouteOutcomes {
+    pub fn count(&self, kind: OutcomeKind) -> u64 {
+        self.counts[kind as usize]
+    }
+
+    /// Posterior outcome probabilities (observed counts plus `PRIOR_COUNTS`).
+    pub fn probabilities(&self) -> [f64; 4] {
+        let weights: Vec<f64> = (0..4).map(|i| self.counts[i] as f64 + PRIOR_COUNTS[i]).collect();
+        let total: f64 = weights.iter().sum();
+        [weights[0] / total, weights[1] / total, weights[2] / total, weights[3] / total]
+    }
+
+    /// Mean of `sum` over `n` samples, shrunk towards `prior` by one pseudo-sample.
+    fn mean(sum: f64, n: u64, prior: f64) -> f64 {
+        (sum + prior) / (n as f64 + 1.0)
+    }
+}
+
+/// Per-route outcome frequencies and the expected value they imply.
+///
+/// A failed trade isn't one fixed cost: a min_profit guard revert burns the
+/// full gas of both swaps, an early abort only part of it, and a fill below
+/// expectation still nets a (smaller) profit. The EV score weighs each
+/// opportunity's expected profit and gas by its route's outcome mix.
+#[derive(Debug, Default)]
+pub struct OutcomeStats {
+    per_route: HashMap<String, RouteOutcomes>,
+}
+
+impl OutcomeStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route key: the pools traded, in order.
+    pub fn route_key(opp: &ArbOpportunity) -> String {
+        opp.pool_ids.join(">")
+    }
+
+    /// Record an on-chain outcome for `opp`.
+    pub fn record(&mut self, opp: &ArbOpportunity, kind: OutcomeKind, profit_mist: Option<u64>, gas_used: u64) {
+        let route = self.per_route.entry(Self::route_key(opp)).or_default();
+        route.counts[kind as usize] += 1;
+        match kind {
+            OutcomeKind::FilledBelowExpectation if opp.expected_profit > 0 => {
+                route.below_ratio_sum += profit_mist.unwrap_or(0) as f64 / opp.expected_profit as f64;
+            }
+            OutcomeKind::EarlyAbort if opp.estimated_gas > 0 => {
+                route.early_gas_ratio_sum += (gas_used as f64 / opp.estimated_gas as f64).min(1.0);
+            }
+            _ => {}
+        }
+    }
+
+    /// Outcome history for `opp`'s route, if any.
+    pub fn route(&self, opp: &ArbOpportunity) -> Option<&RouteOutcomes> {
+        self.per_route.get(&Self::route_key(opp))
+    }
+
+    /// Expected net value of executing `opp` (MIST), using category-specific
+    /// costs: a full fill nets `profit − gas`, a below-expectation fill nets
+    /// `ratio·profit − gas`, a guard revert loses full gas, and an early
+    /// abort loses its observed fraction of gas.
+    pub fn ev(&self, opp: &ArbOpportunity) -> i64 {
+        let default = RouteOutcomes::default();
+        let route = self.route(opp).unwrap_or(&default);
+        let [p_fill, p_below, p_guard, p_early] = route.probabilities();
+
+        let below_ratio = RouteOutcomes::mean(
+            route.below_ratio_sum,
+            route.count(OutcomeKind::FilledBelowExpectation),
+            PRIOR_BELOW_RATIO,
+        );
+        let early_gas_ratio = RouteOutcomes::mean(
+            route.early_gas_ratio_sum,
+            route.count(OutcomeKind::EarlyAbort),
+            PRIOR_EARLY_GAS_RATIO,
+        );
+
+        let profit = opp.expected_profit as f64;
+        let gas = opp.estimated_gas as f64;
+        let ev = p_fill * (profit - gas) + p_below * (below_ratio * profit - gas)
+            - p_guard * gas
+            - p_early * early_gas_ratio * gas;
+        ev.round() as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arb_types::opportunity::StrategyType;
+
+    const GUARD_ABORT: &str = "MoveAbort(MoveLocation { module: ModuleId { address: 5306f6, name: Identifier(\"profit\") }, function: 0, instruction: 12, function_name: Some(\"assert_profit\") }, 1) in command 2";
+    const LEG_ABORT: &str = "MoveAbort(MoveLocation { module: ModuleId { address: 1eabed, name: Identifier(\"pool\") }, function: 7, instruction: 40, function_name: Some(\"flash_swap\") }, 5) in command 0";
+
+    fn opp(pools: &[&str], expected_profit: u64, estimated_gas: u64) -> ArbOpportunity {
+        ArbOpportunity {
+            strategy: StrategyType::CetusToTurbos,
+            amount_in: 1_000_000_000,
+            expected_profit,
+            estimated_gas,
+            net_profit: expected_profit as i64 - estimated_gas as i64,
+            pool_ids: pools.iter().map(|p| p.to_string()).collect(),
+            type_args: vec![],
+            detected_at_ms: 0,
+        }
+    }
+
+    /// Success probability × net − failure probability × full gas, the
+    /// model used before outcomes were split into categories.
+    fn two_outcome_ev(route: &RouteOutcomes, o: &ArbOpportunity) -> f64 {
+        let ok = (route.count(OutcomeKind::Filled) + route.count(OutcomeKind::FilledBelowExpectation)) as f64;
+        let total = route.counts.iter().sum::<u64>() as f64;
+        let p = ok / total;
+        p * (o.expected_profit as f64 - o.estimated_gas as f64) - (1.0 - p) * o.estimated_gas as f64
+    }
+
+    #[test]
+    fn test_parse_move_abort() {
+        let guard = parse_move_abort(GUARD_ABORT).unwrap();
+        assert_eq!(guard.module.as_deref(), Some("profit"));
+        assert_eq!(guard.function_name.as_deref(), Some("assert_profit"));
+        assert_eq!(guard.code, 1);
+        assert!(guard.is_profit_guard());
+
+        let leg = parse_move_abort(LEG_ABORT).unwrap();
+        assert_eq!(leg.code, 5);
+        assert!(!leg.is_profit_guard());
+
+        // Location elided: code only
+        let bare = parse_move_abort("MoveAbort(.., 1)").unwrap();
+        assert_eq!((bare.module, bare.code), (None, 1));
+        assert!(parse_move_abort("InsufficientGas").is_none());
+    }
+
+    #[test]
+    fn test_classify() {
+        let o = opp(&["0x1", "0x2"], 10_000_000, 5_000_000);
+        assert_eq!(classify(&o, true, Some(9_800_000), 5_000_000, None), OutcomeKind::Filled);
+        assert_eq!(
+            classify(&o, true, Some(6_000_000), 5_000_000, None),
+            OutcomeKind::FilledBelowExpectation
+        );
+        assert_eq!(classify(&o, false, None, 5_000_000, Some(GUARD_ABORT)), OutcomeKind::GuardRevert);
+        assert_eq!(classify(&o, false, None, 5_000_000, Some(LEG_ABORT)), OutcomeKind::EarlyAbort);
+        // No parseable abort: fall back on how much gas burned
+        assert_eq!(classify(&o, false, None, 4_500_000, Some("InsufficientGas")), OutcomeKind::GuardRevert);
+        assert_eq!(classify(&o, false, None, 1_000_000, None), OutcomeKind::EarlyAbort);
+    }
+
+    #[test]
+    fn test_cheap_failures_outrank_guard_reverts() {
+        // Same success rate and expected profit; A fails early (10% gas),
+        // B trips the guard (full gas)
+        let a = opp(&["0xa1", "0xa2"], 10_000_000, 5_000_000);
+        let b = opp(&["0xb1", "0xb2"], 10_000_000, 5_000_000);
+        let mut stats = OutcomeStats::new();
+        for _ in 0..10 {
+            stats.record(&a, OutcomeKind::Filled, Some(10_000_000), 5_000_000);
+            stats.record(&a, OutcomeKind::EarlyAbort, None, 500_000);
+            stats.record(&b, OutcomeKind::Filled, Some(10_000_000), 5_000_000);
+            stats.record(&b, OutcomeKind::GuardRevert, None, 5_000_000);
+        }
+
+        // The two-outcome model can't tell them apart
+        let (ra, rb) = (stats.route(&a).unwrap(), stats.route(&b).unwrap());
+        assert_eq!(two_outcome_ev(ra, &a), two_outcome_ev(rb, &b));
+        assert!(stats.ev(&a) > stats.ev(&b));
+    }
+
+    #[test]
+    fn test_below_expectation_fills_discount_profit() {
+        // A always fills in full; B always succeeds but at 30% of expectation.
+        // Two-outcome sees both as 100% success and prefers B's bigger number.
+        let a = opp(&["0xa"], 10_000_000, 2_000_000);
+        let b = opp(&["0xb"], 12_000_000, 2_000_000);
+        let mut stats = OutcomeStats::new();
+        for _ in 0..20 {
+            stats.record(&a, OutcomeKind::Filled, Some(10_000_000), 2_000_000);
+            stats.record(&b, OutcomeKind::FilledBelowExpectation, Some(3_600_000), 2_000_000);
+        }
+
+        let (ra, rb) = (stats.route(&a).unwrap(), stats.route(&b).unwrap());
+        assert!(two_outcome_ev(rb, &b) > two_outcome_ev(ra, &a));
+        assert!(stats.ev(&a) > stats.ev(&b));
+    }
+
+    #[test]
+    fn test_unseen_route_uses_priors() {
+        let stats = OutcomeStats::new();
+        let o = opp(&["0x1"], 10_000_000, 2_000_000);
+        // fill 0.5·8M + below 0.25·(5M−2M) − guard 0.125·2M − early 0.125·0.3·2M
+        assert_eq!(stats.ev(&o), 4_000_000 + 750_000 - 250_000 - 75_000);
+    }
+}
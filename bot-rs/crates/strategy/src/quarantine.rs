@@ -0,0 +1,220 @@
+use arb_types::opportunity::StrategyType;
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+/// Per-strategy circuit breaker: quarantines a strategy that keeps failing
+/// dry-runs, reverting on-chain, or losing money, while the others trade on.
+///
+/// Same two trip conditions as [`CircuitBreaker`](crate::CircuitBreaker),
+/// counted per strategy:
+/// 1. **Consecutive failures** — N dry-run rejections or on-chain reverts in a row.
+/// 2. **Cumulative loss** — realized P&L since the strategy was last
+///    (re-)enabled falls below `-max_loss_mist`.
+///
+/// A quarantined strategy is re-enabled with a clean slate once `cooldown_ms`
+/// has elapsed. RPC transport errors are not the strategy's fault and should
+/// not be recorded here.
+#[derive(Debug)]
+pub struct StrategyQuarantine {
+    // ── Config ──
+    max_consecutive_failures: u32,
+    max_loss_mist: i64,
+    cooldown_ms: u64,
+
+    // ── State ──
+    per_strategy: HashMap<StrategyType, StrategyRecord>,
+}
+
+/// Session history for one strategy.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StrategyRecord {
+    /// Candidates built for dry-run or submission
+    pub attempts: u64,
+    /// Dry-runs that executed but came back unprofitable or aborted
+    pub dry_run_failures: u64,
+    /// Submissions that landed on-chain and reverted
+    pub onchain_failures: u64,
+    /// Realized profit minus gas over the whole session (MIST)
+    pub realized_pnl_mist: i64,
+    /// Failures in a row since the last success or release
+    pub consecutive_failures: u32,
+    /// Realized P&L since the strategy was last (re-)enabled (MIST)
+    pub pnl_since_release_mist: i64,
+    pub quarantined_until_ms: Option<u64>,
+    /// How many times the strategy has been quarantined
+    pub quarantines: u32,
+}
+
+impl StrategyQuarantine {
+    /// Quarantine a strategy for `cooldown_ms` after `max_consecutive_failures`
+    /// failures in a row or a loss of `max_loss_mist` (positive). 0 disables
+    /// either condition.
+    pub fn new(max_consecutive_failures: u32, max_loss_mist: i64, cooldown_ms: u64) -> Self {
+        Self {
+            max_consecutive_failures,
+            max_loss_mist,
+            cooldown_ms,
+            per_strategy: HashMap::new(),
+        }
+    }
+
+    /// Record a candidate for `strategy` reaching dry-run or submission.
+    pub fn record_attempt(&mut self, strategy: StrategyType) {
+        self.per_strategy.entry(strategy).or_default().attempts += 1;
+    }
+
+    /// Record a dry-run that rejected the candidate. Returns `true` if this
+    /// quarantined the strategy.
+    pub fn record_dry_run_failure(&mut self, strategy: StrategyType, now_ms: u64) -> bool {
+        self.per_strategy.entry(strategy).or_default().dry_run_failures += 1;
+        self.record_failure(strategy, 0, now_ms)
+    }
+
+    /// Record a submission that reverted on-chain, losing `loss_mist`
+    /// (negative). Returns `true` if this quarantined the strategy.
+    pub fn record_onchain_failure(&mut self, strategy: StrategyType, loss_mist: i64, now_ms: u64) -> bool {
+        self.per_strategy.entry(strategy).or_default().onchain_failures += 1;
+        self.record_failure(strategy, loss_mist, now_ms)
+    }
+
+    /// Record a landed trade netting `net_mist` (profit minus gas). A losing
+    /// trade still clears the failure streak but counts toward the loss limit.
+    /// Returns `true` if this quarantined the strategy.
+    pub fn record_success(&mut self, strategy: StrategyType, net_mist: i64, now_ms: u64) -> bool {
+        let entry = self.per_strategy.entry(strategy).or_default();
+        entry.consecutive_failures = 0;
+        entry.realized_pnl_mist += net_mist;
+        entry.pnl_since_release_mist += net_mist;
+        self.check_loss(strategy, now_ms)
+    }
+
+    /// Whether `strategy` is quarantined. Once the cooldown has elapsed the
+    /// strategy is released with its streak and loss counters reset.
+    pub fn is_quarantined(&mut self, strategy: StrategyType, now_ms: u64) -> bool {
+        let Some(entry) = self.per_strategy.get_mut(&strategy) else {
+            return false;
+        };
+        match entry.quarantined_until_ms {
+            Some(until) if now_ms < until => true,
+            Some(_) => {
+                info!(strategy = %strategy, "▶️ Strategy quarantine elapsed — re-enabled");
+                entry.quarantined_until_ms = None;
+                entry.consecutive_failures = 0;
+                entry.pnl_since_release_mist = 0;
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Per-strategy records, worst realized P&L first.
+    pub fn stats(&self) -> Vec<(StrategyType, StrategyRecord)> {
+        let mut rows: Vec<_> = self.per_strategy.iter().map(|(k, v)| (*k, v.clone())).collect();
+        rows.sort_by_key(|(_, r)| r.realized_pnl_mist);
+        rows
+    }
+
+    /// Record for one strategy, if it has been seen.
+    pub fn get(&self, strategy: StrategyType) -> Option<&StrategyRecord> {
+        self.per_strategy.get(&strategy)
+    }
+
+    fn record_failure(&mut self, strategy: StrategyType, loss_mist: i64, now_ms: u64) -> bool {
+        let entry = self.per_strategy.entry(strategy).or_default();
+        entry.consecutive_failures += 1;
+        entry.realized_pnl_mist += loss_mist;
+        entry.pnl_since_release_mist += loss_mist;
+
+        if self.max_consecutive_failures > 0 && entry.consecutive_failures >= self.max_consecutive_failures {
+            let reason = format!(
+                "{} consecutive failures (limit: {})",
+                entry.consecutive_failures, self.max_consecutive_failures
+            );
+            self.quarantine(strategy, reason, now_ms);
+            return true;
+        }
+        self.check_loss(strategy, now_ms)
+    }
+
+    /// Quarantine `strategy` if its loss since release exceeds the limit.
+    fn check_loss(&mut self, strategy: StrategyType, now_ms: u64) -> bool {
+        let pnl = self.per_strategy.get(&strategy).map_or(0, |e| e.pnl_since_release_mist);
+        if self.max_loss_mist <= 0 || pnl > -self.max_loss_mist {
+            return false;
+        }
+        let reason = format!("loss {} MIST exceeds limit {} MIST", pnl.abs(), self.max_loss_mist);
+        self.quarantine(strategy, reason, now_ms);
+        true
+    }
+
+    fn quarantine(&mut self, strategy: StrategyType, reason: String, now_ms: u64) {
+        let entry = self.per_strategy.entry(strategy).or_default();
+        entry.quarantined_until_ms = Some(now_ms + self.cooldown_ms);
+        entry.quarantines += 1;
+        warn!(
+            strategy = %strategy,
+            reason = %reason,
+            cooldown_ms = %self.cooldown_ms,
+            "⏸️ Strategy quarantined"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const S: StrategyType = StrategyType::FlowxClmmToDeepBook;
+
+    #[test]
+    fn test_quarantined_after_consecutive_failures_then_released() {
+        let mut q = StrategyQuarantine::new(3, 0, 10_000);
+        q.record_attempt(S);
+        assert!(!q.record_dry_run_failure(S, 0));
+        assert!(!q.record_dry_run_failure(S, 1));
+        assert!(q.record_onchain_failure(S, -2_000_000, 2));
+        assert!(q.is_quarantined(S, 5_000));
+        assert!(!q.is_quarantined(StrategyType::CetusToTurbos, 5_000));
+
+        // Cooldown elapsed: re-enabled with a clean streak
+        assert!(!q.is_quarantined(S, 10_002));
+        let r = q.get(S).unwrap();
+        assert_eq!((r.attempts, r.dry_run_failures, r.onchain_failures), (1, 2, 1));
+        assert_eq!(r.consecutive_failures, 0);
+        assert_eq!(r.realized_pnl_mist, -2_000_000);
+        assert_eq!(r.quarantines, 1);
+        assert!(!q.record_dry_run_failure(S, 10_003));
+    }
+
+    #[test]
+    fn test_success_resets_streak() {
+        let mut q = StrategyQuarantine::new(2, 0, 10_000);
+        q.record_dry_run_failure(S, 0);
+        q.record_success(S, 1_000, 1);
+        assert!(!q.record_dry_run_failure(S, 2));
+        assert!(!q.is_quarantined(S, 3));
+    }
+
+    #[test]
+    fn test_quarantined_on_cumulative_loss() {
+        let mut q = StrategyQuarantine::new(0, 5_000_000, 10_000);
+        assert!(!q.record_success(S, -3_000_000, 0));
+        assert!(q.record_onchain_failure(S, -2_000_000, 1));
+        assert!(q.is_quarantined(S, 2));
+
+        // The loss limit starts over after release; session P&L is kept
+        assert!(!q.is_quarantined(S, 10_001));
+        assert!(!q.record_success(S, -4_000_000, 10_002));
+        assert_eq!(q.get(S).unwrap().realized_pnl_mist, -9_000_000);
+        assert_eq!(q.stats()[0].0, S);
+    }
+
+    #[test]
+    fn test_zero_limits_never_quarantine() {
+        let mut q = StrategyQuarantine::new(0, 0, 10_000);
+        for i in 0..10 {
+            assert!(!q.record_onchain_failure(S, -1_000_000_000, i));
+        }
+        assert!(!q.is_quarantined(S, 10));
+    }
+}
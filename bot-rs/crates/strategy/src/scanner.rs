@@ -1,41 +1,365 @@
-use arb_types::decimals::normalize_price;
-use arb_types::opportunity::{ArbOpportunity, StrategyType};
-use arb_types::pool::{Dex, PoolState};
+use arb_types::clock::CycleClock;
+use arb_types::config::{coin_pair_key, normalize_coin_type};
+use arb_types::decimals::DecimalRegistry;
+use arb_types::log_throttle::DEFAULT_INTERVAL;
+use arb_types::metrics::Metrics;
+use arb_types::opportunity::{ArbOpportunity, StrategyType, TypeArgSlot, DEFAULT_MIN_TRADE_AMOUNT_MIST};
+use arb_types::pool::{Dex, PoolState, TradeSizeDefaults, TradeSizeLimits};
+use arb_types::warn_throttled;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use tracing::{debug, info};
 
+use crate::cold_start::{QUAD_HOP_GAS_ESTIMATE, TRI_HOP_GAS_ESTIMATE, TWO_HOP_GAS_ESTIMATE};
+use crate::optimizer::{build_opportunity_simulator, build_strategy_simulator, MissingFeePolicy, SearchMethod, MIN_SIM_AMOUNT};
+
 /// Maximum spread (as fraction) considered realistic.
 /// Anything above this is a price normalization bug, not a real arb.
 /// Real cross-DEX arbs on Sui mainnet are typically 0.01%–5%.
 const MAX_REALISTIC_SPREAD: f64 = 0.50; // 50%
 
+/// Default minimum cross-rate for a quad-hop: four swaps' fees and slippage
+/// need a wider edge than a tri-hop's 1%.
+pub const DEFAULT_QUAD_HOP_MIN_CROSS_RATE: f64 = 1.015;
+
 /// Scans pool states for arbitrage opportunities.
 /// Performs O(n²) pairwise comparison of pools sharing the same token pair.
 pub struct Scanner {
     /// Minimum profit threshold in MIST.
     pub min_profit_mist: u64,
+    /// Per-pair overrides of `min_profit_mist`, keyed by [`coin_pair_key`].
+    pair_min_profit: HashMap<(String, String), u64>,
     /// Maximum staleness in ms — skip pools older than this.
     pub max_staleness_ms: u64,
+    /// Both pools of a two-hop pair must be fresher than this (ms). Tighter
+    /// than `max_staleness_ms`: a fresh price against a slightly stale one
+    /// looks like a spread but is mostly lag.
+    pub pair_freshness_ms: u64,
+    /// Pairs with fewer pools fresh within `pair_freshness_ms` aren't traded.
+    pub min_fresh_pools_per_pair: usize,
+    /// Two-hop legs must have depths within this ratio (shallower ÷
+    /// deeper). 0 = no check.
+    pub min_leg_depth_ratio: f64,
+    /// Trade amounts below this are dust, whatever profit they report.
+    pub min_trade_amount_mist: u64,
+    /// Per-DEX minimum trade / lot size fallbacks for pools that don't report their own.
+    pub trade_size_defaults: TradeSizeDefaults,
+    /// How the local optimizer prices a pool with no parsed fee rate.
+    pub missing_fee: MissingFeePolicy,
+    /// Line search the local optimizer refines trade sizes with.
+    pub search_method: SearchMethod,
+    /// Normalized coin types allowed as the flash / profit currency. Empty = any.
+    allowed_flash_coins: Vec<String>,
+    /// Strategies opportunities may use. Empty = all.
+    strategy_allowlist: HashSet<StrategyType>,
+    /// Strategies never used; wins over the allowlist.
+    strategy_denylist: HashSet<StrategyType>,
+    /// Quad-hop cycles must multiply out above this.
+    pub quad_hop_min_cross_rate: f64,
     /// Cycle counter for periodic summary logging.
     scan_count: AtomicU64,
+    /// Opportunities found per strategy, for `/metrics`.
+    metrics: Arc<Metrics>,
+    /// Coin decimals for price normalization (on-chain, static fallback).
+    decimals: DecimalRegistry,
 }
 
 impl Scanner {
     pub fn new(min_profit_mist: u64) -> Self {
         Self {
             min_profit_mist,
+            pair_min_profit: HashMap::new(),
             max_staleness_ms: 5_000, // 5 seconds default
+            pair_freshness_ms: 5_000,
+            min_fresh_pools_per_pair: 2,
+            min_leg_depth_ratio: 0.0,
+            min_trade_amount_mist: DEFAULT_MIN_TRADE_AMOUNT_MIST,
+            trade_size_defaults: TradeSizeDefaults::default(),
+            missing_fee: MissingFeePolicy::default(),
+            search_method: SearchMethod::default(),
+            allowed_flash_coins: Vec::new(),
+            strategy_allowlist: HashSet::new(),
+            strategy_denylist: HashSet::new(),
+            quad_hop_min_cross_rate: DEFAULT_QUAD_HOP_MIN_CROSS_RATE,
             scan_count: AtomicU64::new(0),
+            metrics: Arc::new(Metrics::new()),
+            decimals: DecimalRegistry::new(),
+        }
+    }
+
+    /// Override `min_profit_mist` for specific coin pairs. Keys may name the
+    /// coins in either order.
+    pub fn with_pair_thresholds(mut self, thresholds: HashMap<(String, String), u64>) -> Self {
+        self.pair_min_profit = thresholds
+            .into_iter()
+            .map(|((a, b), mist)| (coin_pair_key(&a, &b), mist))
+            .collect();
+        self
+    }
+
+    /// Minimum profit (MIST) for an arb on the `coin_a`/`coin_b` pair: its
+    /// override if one is set, else `min_profit_mist`.
+    pub fn min_profit_for(&self, coin_a: &str, coin_b: &str) -> u64 {
+        if self.pair_min_profit.is_empty() {
+            return self.min_profit_mist;
+        }
+        self.pair_min_profit
+            .get(&coin_pair_key(coin_a, coin_b))
+            .copied()
+            .unwrap_or(self.min_profit_mist)
+    }
+
+    /// Normalize prices with `decimals` (seeded from coin metadata) instead
+    /// of the static table alone.
+    pub fn with_decimals(mut self, decimals: DecimalRegistry) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// Count found opportunities in `metrics` (shared with the metrics server).
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Set the per-DEX minimum trade / lot size fallbacks.
+    pub fn with_trade_size_defaults(mut self, defaults: TradeSizeDefaults) -> Self {
+        self.trade_size_defaults = defaults;
+        self
+    }
+
+    /// Set how the optimizer treats pools with no parsed fee rate.
+    pub fn with_missing_fee_policy(mut self, policy: MissingFeePolicy) -> Self {
+        self.missing_fee = policy;
+        self
+    }
+
+    /// Refine trade sizes with `method` (default: ternary search).
+    pub fn with_search_method(mut self, method: SearchMethod) -> Self {
+        self.search_method = method;
+        self
+    }
+
+    /// Require `min_fresh_pools` pools per pair, each fresher than
+    /// `freshness_ms`, before comparing that pair's prices.
+    pub fn with_pair_freshness(mut self, freshness_ms: u64, min_fresh_pools: usize) -> Self {
+        self.pair_freshness_ms = freshness_ms;
+        self.min_fresh_pools_per_pair = min_fresh_pools;
+        self
+    }
+
+    /// Skip two-hops whose legs' depths differ by more than `ratio`
+    /// (shallower ÷ deeper, e.g. 0.2). 0 disables the check.
+    pub fn with_min_leg_depth_ratio(mut self, ratio: f64) -> Self {
+        self.min_leg_depth_ratio = ratio;
+        self
+    }
+
+    /// Report quad-hop cycles whose cross-rate exceeds `rate` (e.g. 1.015).
+    pub fn with_quad_hop_min_cross_rate(mut self, rate: f64) -> Self {
+        self.quad_hop_min_cross_rate = rate;
+        self
+    }
+
+    /// Whether two legs of the same pair are deep enough relative to each
+    /// other: the shallow leg dominates slippage, so a deep-vs-dust pairing
+    /// can't execute at any meaningful size. Legs whose depth isn't known
+    /// (DeepBook) pass.
+    fn legs_balanced(&self, a: &PoolState, b: &PoolState) -> bool {
+        if self.min_leg_depth_ratio <= 0.0 {
+            return true;
+        }
+        match leg_depth_ratio(a, b) {
+            Some(ratio) if ratio < self.min_leg_depth_ratio => {
+                debug!(
+                    pool_a = %a.object_id,
+                    pool_b = %b.object_id,
+                    depth_ratio = %format!("{:.3}", ratio),
+                    min_ratio = %self.min_leg_depth_ratio,
+                    "Leg depths mismatched — skipping pair"
+                );
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// Whether `pool` is fresh enough to be one side of a two-hop pair.
+    fn pair_fresh(&self, pool: &PoolState, now_ms: u64) -> bool {
+        pool.staleness_ms(now_ms) <= self.pair_freshness_ms.min(self.max_staleness_ms)
+    }
+
+    /// Pairs with at least `min_fresh_pools_per_pair` fresh pools, keyed by
+    /// their sorted coin types. Pairs where only one of several pools is
+    /// fresh are logged: a partial coverage problem, not a quiet market.
+    fn tradable_pairs(&self, pools: &[PoolState], now_ms: u64) -> HashSet<(String, String)> {
+        let mut counts: HashMap<(String, String), (usize, usize)> = HashMap::new();
+        for p in pools {
+            let c = counts.entry(pair_key(p)).or_default();
+            c.0 += self.pair_fresh(p, now_ms) as usize;
+            c.1 += 1;
+        }
+        counts
+            .into_iter()
+            .filter_map(|(pair, (fresh, total))| {
+                if fresh == 1 && total > 1 {
+                    warn_throttled!(
+                        format!("scanner.one_fresh_pool:{}/{}", pair.0, pair.1),
+                        DEFAULT_INTERVAL,
+                        pair = %format!("{}/{}", pair.0, pair.1),
+                        pools = %total,
+                        freshness_ms = %self.pair_freshness_ms,
+                        "Only one fresh pool for pair — partial coverage, not trading it"
+                    );
+                }
+                (fresh >= self.min_fresh_pools_per_pair).then_some(pair)
+            })
+            .collect()
+    }
+
+    /// Set the absolute trade size floor (MIST).
+    pub fn with_min_trade_amount(mut self, mist: u64) -> Self {
+        self.min_trade_amount_mist = mist;
+        self
+    }
+
+    /// Whether `amount` is below the absolute trade size floor.
+    pub fn is_dust(&self, amount: u64) -> bool {
+        amount < self.min_trade_amount_mist
+    }
+
+    /// Restrict the flash / profit currency ([`ArbOpportunity::profit_coin`]) to `coins`.
+    /// An empty list allows any currency.
+    pub fn with_allowed_flash_coins(mut self, coins: Vec<String>) -> Self {
+        self.allowed_flash_coins = coins.iter().map(|c| normalize_coin_type(c)).collect();
+        self
+    }
+
+    /// Whether `opp` borrows and profits in an allowed currency.
+    pub fn flash_coin_allowed(&self, opp: &ArbOpportunity) -> bool {
+        if self.allowed_flash_coins.is_empty() {
+            return true;
+        }
+        opp.profit_coin()
+            .is_some_and(|c| self.allowed_flash_coins.contains(&normalize_coin_type(c)))
+    }
+
+    /// Only produce opportunities for strategies in `allow` (empty = all)
+    /// and not in `deny`.
+    pub fn with_strategy_filter(mut self, allow: Vec<StrategyType>, deny: Vec<StrategyType>) -> Self {
+        self.strategy_allowlist = allow.into_iter().collect();
+        self.strategy_denylist = deny.into_iter().collect();
+        self
+    }
+
+    /// Whether the allow / deny lists permit `strategy`.
+    pub fn strategy_allowed(&self, strategy: StrategyType) -> bool {
+        !self.strategy_denylist.contains(&strategy)
+            && (self.strategy_allowlist.is_empty() || self.strategy_allowlist.contains(&strategy))
+    }
+
+    /// Drop dust-sized opportunities, those whose flash currency isn't
+    /// allowed, and those for a filtered-out strategy.
+    fn retain_eligible(&self, opportunities: &mut Vec<ArbOpportunity>) {
+        let before = opportunities.len();
+        opportunities.retain(|o| !self.is_dust(o.amount_in));
+        let dust = before - opportunities.len();
+        if dust > 0 {
+            debug!(rejected = %dust, floor = %self.min_trade_amount_mist, "Dropped dust-sized opportunities");
+        }
+
+        let before = opportunities.len();
+        opportunities.retain(|o| self.flash_coin_allowed(o));
+        let rejected = before - opportunities.len();
+        if rejected > 0 {
+            debug!(rejected = %rejected, "Dropped opportunities with a disallowed flash currency");
+        }
+
+        let before = opportunities.len();
+        opportunities.retain(|o| self.strategy_allowed(o.strategy));
+        let rejected = before - opportunities.len();
+        if rejected > 0 {
+            debug!(rejected = %rejected, "Dropped opportunities for a filtered-out strategy");
         }
     }
 
+    /// Combined size limits across all of `opp`'s legs, or `None` if a leg's
+    /// pool is missing from `pools`.
+    pub fn trade_limits(&self, opp: &ArbOpportunity, pools: &[PoolState]) -> Option<TradeSizeLimits> {
+        let legs = opp
+            .pool_ids
+            .iter()
+            .map(|id| pools.iter().find(|p| &p.object_id == id))
+            .collect::<Option<Vec<_>>>()?;
+        Some(TradeSizeLimits::for_pools(&legs, &self.trade_size_defaults))
+    }
+
+    /// Probe amount for a rough estimate: `base`, raised to the venues'
+    /// minimum and rounded up to a whole lot.
+    fn probe_amount(&self, base: u64, legs: &[&PoolState]) -> u64 {
+        let limits = TradeSizeLimits::for_pools(legs, &self.trade_size_defaults);
+        limits.round_up(base.max(limits.min_trade_size))
+    }
+
+    /// The strategy for a spread where `cheap` prices coin A below `dear`,
+    /// with its `(flash, sell)` legs: borrow A on `cheap` (forward), or B on
+    /// `dear` (reverse). When the package has both, the one the local
+    /// simulator finds more profitable wins, compared in coin A.
+    fn pick_direction<'p>(
+        &self,
+        cheap: &'p PoolState,
+        dear: &'p PoolState,
+    ) -> Option<(StrategyType, &'p PoolState, &'p PoolState)> {
+        let forward = resolve_strategy(cheap.dex, dear.dex).map(|s| (s, cheap, dear));
+        let reverse = resolve_reverse_strategy(dear.dex, cheap.dex).map(|s| (s, dear, cheap));
+        let (Some(forward), Some(reverse)) = (forward, reverse) else {
+            return forward.or(reverse);
+        };
+        let forward_profit = self.simulated_profit(forward) as f64;
+        // Reverse profit is in B: convert at the flash pool's price
+        let reverse_profit = match dear.price_a_in_b() {
+            Some(price) if price > 0.0 => self.simulated_profit(reverse) as f64 / price,
+            _ => 0.0,
+        };
+        debug!(
+            forward = ?forward.0,
+            reverse = ?reverse.0,
+            forward_profit = %forward_profit,
+            reverse_profit_in_a = %reverse_profit,
+            "Compared arb directions"
+        );
+        Some(if reverse_profit > forward_profit { reverse } else { forward })
+    }
+
+    /// The local optimizer's best profit for `strategy` on `(flash, sell)`,
+    /// in the coin it borrows. 0 if the legs can't be simulated.
+    fn simulated_profit(&self, (strategy, flash, sell): (StrategyType, &PoolState, &PoolState)) -> u64 {
+        let limits = TradeSizeLimits::for_pools(&[flash, sell], &self.trade_size_defaults);
+        build_strategy_simulator(strategy, flash, sell, self.missing_fee)
+            .map(|(simulate, hi)| {
+                self.search_method.optimize_lots(MIN_SIM_AMOUNT, hi, 100_000, limits, &*simulate).1
+            })
+            .unwrap_or(0)
+    }
+
     /// Scan all pool states for two-hop arbitrage opportunities.
     /// Returns opportunities sorted by expected profit (descending).
-    pub fn scan_two_hop(&self, pools: &[PoolState]) -> Vec<ArbOpportunity> {
-        let now_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
+    pub fn scan_two_hop(&self, pools: &[PoolState], clock: CycleClock) -> Vec<ArbOpportunity> {
+        let opportunities = self.two_hop_candidates(pools, true, clock);
+        self.metrics.record_opportunities(&opportunities);
+        opportunities
+    }
+
+    /// Two-hop scan body. `count_cycle` is false for ad-hoc re-scans (e.g.
+    /// tri-hop degradation) so they don't skew the periodic scan summary.
+    fn two_hop_candidates(
+        &self,
+        pools: &[PoolState],
+        count_cycle: bool,
+        clock: CycleClock,
+    ) -> Vec<ArbOpportunity> {
+        let now_ms = clock.now_ms();
 
         let mut opportunities = Vec::new();
         let mut pairs_checked = 0u32;
@@ -43,152 +367,166 @@ impl Scanner {
         let mut near_misses = 0u32;
         let mut best_spread = 0.0f64;
         let mut best_pair_desc = String::new();
+        let tradable = self.tradable_pairs(pools, now_ms);
+
+        // Only pools on the same token pair are compared
+        for (i, j) in same_pair_candidates(pools) {
+            let pool_a = &pools[i];
+            let pool_b = &pools[j];
+
+            // Skip stale pools
+            if pool_a.staleness_ms(now_ms) > self.max_staleness_ms
+                || pool_b.staleness_ms(now_ms) > self.max_staleness_ms
+            {
+                continue;
+            }
 
-        // O(n²) pairwise comparison
-        for i in 0..pools.len() {
-            for j in (i + 1)..pools.len() {
-                let pool_a = &pools[i];
-                let pool_b = &pools[j];
+            // Compare only fresh prices, on pairs with enough coverage
+            if !tradable.contains(&pair_key(pool_a))
+                || !self.pair_fresh(pool_a, now_ms)
+                || !self.pair_fresh(pool_b, now_ms)
+            {
+                continue;
+            }
 
-                // Skip stale pools
-                if pool_a.staleness_ms(now_ms) > self.max_staleness_ms
-                    || pool_b.staleness_ms(now_ms) > self.max_staleness_ms
-                {
-                    continue;
+            pairs_checked += 1;
+
+            // Check for price divergence
+            if let (Some(price_a), Some(price_b)) =
+                (pool_a.price_a_in_b(), pool_b.price_a_in_b())
+            {
+                // Apply decimal normalization for cross-DEX-type comparison
+                let adj_a = self.decimals.normalize_price(
+                    price_a,
+                    &pool_a.coin_type_a,
+                    &pool_a.coin_type_b,
+                );
+                let adj_b = self.decimals.normalize_price(
+                    price_b,
+                    &pool_b.coin_type_a,
+                    &pool_b.coin_type_b,
+                );
+
+                // Ensure we compare A/B prices in the same direction
+                let (norm_a, norm_b) = if pool_a.coin_type_a == pool_b.coin_type_a {
+                    (adj_a, adj_b)
+                } else {
+                    // Pools have reversed ordering
+                    (adj_a, 1.0 / adj_b)
+                };
+
+                let spread = (norm_a - norm_b).abs() / norm_a.min(norm_b);
+
+                // Track best spread for summary logging
+                if spread > best_spread {
+                    best_spread = spread;
+                    best_pair_desc = format!("{}/{}", pool_a.dex, pool_b.dex);
                 }
 
-                // Check if pools share the same token pair
-                if !same_pair(pool_a, pool_b) {
-                    continue;
-                }
+                if spread > 0.001 {
+                    // >0.1% spread — potential opportunity
+                    divergences += 1;
 
-                pairs_checked += 1;
-
-                // Check for price divergence
-                if let (Some(price_a), Some(price_b)) =
-                    (pool_a.price_a_in_b(), pool_b.price_a_in_b())
-                {
-                    // Apply decimal normalization for cross-DEX-type comparison
-                    let adj_a = normalize_price(
-                        price_a,
-                        &pool_a.coin_type_a,
-                        &pool_a.coin_type_b,
-                    );
-                    let adj_b = normalize_price(
-                        price_b,
-                        &pool_b.coin_type_a,
-                        &pool_b.coin_type_b,
+                    // Sanity check: reject impossible spreads (normalization bugs)
+                    if spread > MAX_REALISTIC_SPREAD {
+                        warn_throttled!(
+                            format!("scanner.bogus_spread:{}:{}", pool_a.object_id, pool_b.object_id),
+                            DEFAULT_INTERVAL,
+                            dex_a = %pool_a.dex,
+                            dex_b = %pool_b.dex,
+                            spread = %format!("{:.2}%", spread * 100.0),
+                            pair = %format!("{}/{}",
+                                pool_a.coin_type_a.rsplit("::").next().unwrap_or("?"),
+                                pool_a.coin_type_b.rsplit("::").next().unwrap_or("?")),
+                            "Bogus spread rejected (likely decimal mismatch)"
+                        );
+                        continue;
+                    }
+
+                    debug!(
+                        dex_a = %pool_a.dex,
+                        dex_b = %pool_b.dex,
+                        spread = %format!("{:.4}%", spread * 100.0),
+                        pair = %format!("{}/{}",
+                            pool_a.coin_type_a.rsplit("::").next().unwrap_or("?"),
+                            pool_a.coin_type_b.rsplit("::").next().unwrap_or("?")),
+                        "Price divergence"
                     );
 
-                    // Ensure we compare A/B prices in the same direction
-                    let (norm_a, norm_b) = if pool_a.coin_type_a == pool_b.coin_type_a {
-                        (adj_a, adj_b)
+                    // Determine direction: buy cheap, sell expensive
+                    let (cheap, dear) = if norm_a < norm_b {
+                        (pool_a, pool_b)
                     } else {
-                        // Pools have reversed ordering
-                        (adj_a, 1.0 / adj_b)
+                        (pool_b, pool_a)
                     };
 
-                    let spread = (norm_a - norm_b).abs() / norm_a.min(norm_b);
-
-                    // Track best spread for summary logging
-                    if spread > best_spread {
-                        best_spread = spread;
-                        best_pair_desc = format!("{}/{}", pool_a.dex, pool_b.dex);
+                    if !self.legs_balanced(cheap, dear) {
+                        continue;
                     }
 
-                    if spread > 0.001 {
-                        // >0.1% spread — potential opportunity
-                        divergences += 1;
+                    if let Some((strategy, flash_pool, sell_pool)) = self.pick_direction(cheap, dear) {
+                        // Rough profit estimate (will be refined by optimizer)
+                        // 1 SUI as starting estimate (or the venues' minimum, if larger)
+                        let mut est_amount =
+                            self.probe_amount(1_000_000_000, &[flash_pool, sell_pool]);
+                        if strategy.is_reverse() {
+                            // Reverse strategies borrow B: the same size, in B
+                            let price = flash_pool.price_a_in_b().unwrap_or(1.0);
+                            est_amount = (est_amount as f64 * price) as u64;
+                        }
+                        let est_profit =
+                            (est_amount as f64 * spread * 0.5) as u64; // conservative
+                        let min_profit =
+                            self.min_profit_for(&flash_pool.coin_type_a, &flash_pool.coin_type_b);
 
-                        // Sanity check: reject impossible spreads (normalization bugs)
-                        if spread > MAX_REALISTIC_SPREAD {
+                        if est_profit > min_profit {
                             debug!(
-                                dex_a = %pool_a.dex,
-                                dex_b = %pool_b.dex,
-                                spread = %format!("{:.2}%", spread * 100.0),
-                                pair = %format!("{}/{}",
-                                    pool_a.coin_type_a.rsplit("::").next().unwrap_or("?"),
-                                    pool_a.coin_type_b.rsplit("::").next().unwrap_or("?")),
-                                "Bogus spread rejected (likely decimal mismatch)"
+                                strategy = %strategy,
+                                spread = %format!("{:.4}%", spread * 100.0),
+                                est_profit = %est_profit,
+                                "Arb opportunity detected"
                             );
-                            continue;
-                        }
-
-                        debug!(
-                            dex_a = %pool_a.dex,
-                            dex_b = %pool_b.dex,
-                            spread = %format!("{:.4}%", spread * 100.0),
-                            pair = %format!("{}/{}",
-                                pool_a.coin_type_a.rsplit("::").next().unwrap_or("?"),
-                                pool_a.coin_type_b.rsplit("::").next().unwrap_or("?")),
-                            "Price divergence"
-                        );
 
-                        // Determine direction: buy cheap, sell expensive
-                        let (flash_pool, sell_pool) = if norm_a < norm_b {
-                            (pool_a, pool_b)
+                            // Reverse entry points take the same <A, B> as
+                            // the forward ones; only the swap directions differ
+                            let coins = [flash_pool.coin_type_a.clone(), flash_pool.coin_type_b.clone()];
+                            let type_args = layout_type_args(strategy, &coins, &[flash_pool, sell_pool]);
+
+                            opportunities.push(ArbOpportunity {
+                                strategy,
+                                amount_in: est_amount,
+                                expected_profit: est_profit,
+                                estimated_gas: TWO_HOP_GAS_ESTIMATE,
+                                net_profit: est_profit as i64 - TWO_HOP_GAS_ESTIMATE as i64,
+                                pool_ids: vec![
+                                    flash_pool.object_id.clone(),
+                                    sell_pool.object_id.clone(),
+                                ],
+                                type_args,
+                                detected_at_ms: now_ms,
+                            });
                         } else {
-                            (pool_b, pool_a)
-                        };
-
-                        if let Some(strategy) =
-                            resolve_strategy(flash_pool.dex, sell_pool.dex)
-                        {
-                            // Rough profit estimate (will be refined by optimizer)
-                            let est_amount = 1_000_000_000u64; // 1 SUI as starting estimate
-                            let est_profit =
-                                (est_amount as f64 * spread * 0.5) as u64; // conservative
-
-                            if est_profit > self.min_profit_mist {
-                                debug!(
-                                    strategy = ?strategy,
-                                    spread = %format!("{:.4}%", spread * 100.0),
-                                    est_profit = %est_profit,
-                                    "Arb opportunity detected"
-                                );
-
-                                let mut type_args = vec![
-                                    flash_pool.coin_type_a.clone(),
-                                    flash_pool.coin_type_b.clone(),
-                                ];
-                                // Turbos pools need their fee tier type as an extra type arg
-                                if let Some(ft) = find_turbos_fee_type(&[flash_pool, sell_pool]) {
-                                    type_args.push(ft);
-                                }
-
-                                opportunities.push(ArbOpportunity {
-                                    strategy,
-                                    amount_in: est_amount,
-                                    expected_profit: est_profit,
-                                    estimated_gas: 5_000_000, // ~5M MIST default
-                                    net_profit: est_profit as i64 - 5_000_000,
-                                    pool_ids: vec![
-                                        flash_pool.object_id.clone(),
-                                        sell_pool.object_id.clone(),
-                                    ],
-                                    type_args,
-                                    detected_at_ms: now_ms,
-                                });
-                            } else {
-                                near_misses += 1;
-                                debug!(
-                                    dex_a = %flash_pool.dex,
-                                    dex_b = %sell_pool.dex,
-                                    spread = %format!("{:.4}%", spread * 100.0),
-                                    est_profit = %est_profit,
-                                    min_profit = %self.min_profit_mist,
-                                    "Near miss — spread found but below threshold"
-                                );
-                            }
+                            near_misses += 1;
+                            debug!(
+                                dex_a = %flash_pool.dex,
+                                dex_b = %sell_pool.dex,
+                                spread = %format!("{:.4}%", spread * 100.0),
+                                est_profit = %est_profit,
+                                min_profit = %min_profit,
+                                "Near miss — spread found but below threshold"
+                            );
                         }
                     }
                 }
-            }
-        }
+            }        }
 
         // Periodic summary log (every ~20 scans ≈ 30s at 1.5s interval)
-        let cycle = self.scan_count.fetch_add(1, Ordering::Relaxed);
-        if cycle % 20 == 0 {
+        let cycle = if count_cycle {
+            self.scan_count.fetch_add(1, Ordering::Relaxed)
+        } else {
+            1
+        };
+        if count_cycle && cycle.is_multiple_of(20) {
             info!(
                 cycle = cycle,
                 pairs_checked = pairs_checked,
@@ -201,8 +539,10 @@ impl Scanner {
             );
         }
 
+        self.retain_eligible(&mut opportunities);
+
         // Sort by expected profit descending
-        opportunities.sort_by(|a, b| b.expected_profit.cmp(&a.expected_profit));
+        opportunities.sort_by_key(|o| std::cmp::Reverse(o.expected_profit));
         opportunities
     }
 
@@ -214,11 +554,8 @@ impl Scanner {
     /// - Pool 3 trades C/A (swap C for A, repay flash)
     ///
     /// Returns opportunities sorted by expected profit (descending).
-    pub fn scan_tri_hop(&self, pools: &[PoolState]) -> Vec<ArbOpportunity> {
-        let now_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
+    pub fn scan_tri_hop(&self, pools: &[PoolState], clock: CycleClock) -> Vec<ArbOpportunity> {
+        let now_ms = clock.now_ms();
 
         let mut opportunities = Vec::new();
 
@@ -228,88 +565,73 @@ impl Scanner {
             .filter(|p| p.staleness_ms(now_ms) <= self.max_staleness_ms)
             .collect();
 
-        // O(n³) — fine for small pool counts (<50 pools)
-        for p1 in &fresh {
-            for p2 in &fresh {
-                if std::ptr::eq(*p1, *p2) {
-                    continue;
-                }
-
-                // Find shared token between p1 and p2 (the "B" in A→B→C)
-                let shared = shared_token(p1, p2);
-                if shared.is_none() {
-                    continue;
-                }
-                let (token_b, token_a_from_p1, token_c_from_p2) = shared.unwrap();
-
-                for p3 in &fresh {
-                    if std::ptr::eq(*p1, *p3) || std::ptr::eq(*p2, *p3) {
-                        continue;
-                    }
-
-                    // p3 must trade C/A to close the triangle
-                    if !pool_has_pair(p3, &token_c_from_p2, &token_a_from_p1) {
-                        continue;
-                    }
+        // Each pool's price is normalized once, not once per triangle
+        let prices: Vec<Option<f64>> = fresh.iter().map(|p| normalized_price(p, &self.decimals)).collect();
+
+        // Walk the token graph rather than every triple of pools
+        for (i, j, k) in tri_hop_candidates(&fresh) {
+            let (p1, p2, p3) = (fresh[i], fresh[j], fresh[k]);
+            let Some((token_b, token_a_from_p1, token_c_from_p2)) = shared_token(p1, p2) else {
+                continue;
+            };
+            // We have a triangle: A→B (p1) → C (p2) → A (p3)
+            // Check if price loop creates an arbitrage
+            let price_ab = prices[i].and_then(|p| directed_price(p1, p, &token_a_from_p1, &token_b));
+            let price_bc = prices[j].and_then(|p| directed_price(p2, p, &token_b, &token_c_from_p2));
+            let price_ca = prices[k].and_then(|p| directed_price(p3, p, &token_c_from_p2, &token_a_from_p1));
+
+            if let (Some(pab), Some(pbc), Some(pca)) = (price_ab, price_bc, price_ca) {
+                // Cross-rate: if pab * pbc * pca > 1.0, there's an arb
+                let cross_rate = pab * pbc * pca;
+
+                if cross_rate > 1.01 && cross_rate < (1.0 + MAX_REALISTIC_SPREAD) {
+                    // >1.0% edge for tri-hops (3 sequential swaps need larger edge
+                    // Try to find a valid pool ordering + strategy for these 3 pools
+                    if let Some((strategy, ordered_pools, coins)) =
+                        resolve_tri_with_ordering(p1, p2, p3)
+                    {
+                        let spread = cross_rate - 1.0;
+                        // 5 SUI (or the venues' minimum, if larger)
+                        let est_amount =
+                            self.probe_amount(5_000_000_000, &[p1, p2, p3]);
+                        // Tri-hop slippage factor: use 0.15 (not 0.5) because
+                        // 3 sequential swaps compound price impact significantly.
+                        // 2-hop uses 0.5; tri-hop needs much more conservative estimate.
+                        let est_profit =
+                            (est_amount as f64 * spread * 0.15) as u64;
+                        // The flash loan comes from the first leg's pool
+                        let flash_pool = ordered_pools[0];
+                        let min_profit = self
+                            .min_profit_for(&flash_pool.coin_type_a, &flash_pool.coin_type_b);
+
+                        if est_profit > min_profit {
+                            debug!(
+                                strategy = %strategy,
+                                cross_rate = %format!("{:.6}", cross_rate),
+                                est_profit = %est_profit,
+                                path = %format!("{} → {} → {} → {}",
+                                    coins[0].rsplit("::").next().unwrap_or("?"),
+                                    coins[1].rsplit("::").next().unwrap_or("?"),
+                                    coins[2].rsplit("::").next().unwrap_or("?"),
+                                    coins[0].rsplit("::").next().unwrap_or("?")),
+                                "Tri-hop opportunity detected"
+                            );
 
-                    // We have a triangle: A→B (p1) → C (p2) → A (p3)
-                    // Check if price loop creates an arbitrage
-                    let price_ab = pool_price_for_direction(p1, &token_a_from_p1, &token_b);
-                    let price_bc = pool_price_for_direction(p2, &token_b, &token_c_from_p2);
-                    let price_ca = pool_price_for_direction(p3, &token_c_from_p2, &token_a_from_p1);
-
-                    if let (Some(pab), Some(pbc), Some(pca)) = (price_ab, price_bc, price_ca) {
-                        // Cross-rate: if pab * pbc * pca > 1.0, there's an arb
-                        let cross_rate = pab * pbc * pca;
-
-                        if cross_rate > 1.01 && cross_rate < (1.0 + MAX_REALISTIC_SPREAD) {
-                            // >1.0% edge for tri-hops (3 sequential swaps need larger edge
-                            // Try to find a valid pool ordering + strategy for these 3 pools
-                            if let Some((strategy, ordered_pools, mut type_args)) =
-                                resolve_tri_with_ordering(p1, p2, p3)
-                            {
-                                let spread = cross_rate - 1.0;
-                                let est_amount = 5_000_000_000u64; // 5 SUI
-                                // Tri-hop slippage factor: use 0.15 (not 0.5) because
-                                // 3 sequential swaps compound price impact significantly.
-                                // 2-hop uses 0.5; tri-hop needs much more conservative estimate.
-                                let est_profit =
-                                    (est_amount as f64 * spread * 0.15) as u64;
-                                let tri_gas_estimate: u64 = 4_000_000;
-
-                                if est_profit > self.min_profit_mist {
-                                    debug!(
-                                        strategy = ?strategy,
-                                        cross_rate = %format!("{:.6}", cross_rate),
-                                        est_profit = %est_profit,
-                                        path = %format!("{} → {} → {} → {}",
-                                            type_args[0].rsplit("::").next().unwrap_or("?"),
-                                            type_args[1].rsplit("::").next().unwrap_or("?"),
-                                            type_args[2].rsplit("::").next().unwrap_or("?"),
-                                            type_args[0].rsplit("::").next().unwrap_or("?")),
-                                        "Tri-hop opportunity detected"
-                                    );
-
-                                    // Turbos pools need their fee tier type as extra type arg
-                                    if let Some(ft) = find_turbos_fee_type(&[p1, p2, p3]) {
-                                        type_args.push(ft);
-                                    }
-
-                                    opportunities.push(ArbOpportunity {
-                                        strategy,
-                                        amount_in: est_amount,
-                                        expected_profit: est_profit,
-                                        estimated_gas: tri_gas_estimate,
-                                        net_profit: est_profit as i64 - tri_gas_estimate as i64,
-                                        pool_ids: ordered_pools
-                                            .iter()
-                                            .map(|p| p.object_id.clone())
-                                            .collect(),
-                                        type_args,
-                                        detected_at_ms: now_ms,
-                                    });
-                                }
-                            }
+                            let type_args = layout_type_args(strategy, &coins, &ordered_pools);
+
+                            opportunities.push(ArbOpportunity {
+                                strategy,
+                                amount_in: est_amount,
+                                expected_profit: est_profit,
+                                estimated_gas: TRI_HOP_GAS_ESTIMATE,
+                                net_profit: est_profit as i64 - TRI_HOP_GAS_ESTIMATE as i64,
+                                pool_ids: ordered_pools
+                                    .iter()
+                                    .map(|p| p.object_id.clone())
+                                    .collect(),
+                                type_args,
+                                detected_at_ms: now_ms,
+                            });
                         }
                     }
                 }
@@ -324,10 +646,262 @@ impl Scanner {
             ids_b.sort();
             ids_a == ids_b
         });
+        self.retain_eligible(&mut opportunities);
 
-        opportunities.sort_by(|a, b| b.expected_profit.cmp(&a.expected_profit));
+        opportunities.sort_by_key(|o| std::cmp::Reverse(o.expected_profit));
+        self.metrics.record_opportunities(&opportunities);
         opportunities
     }
+
+    /// Scan for quad-hop arbitrage opportunities: A→B→C→D→A.
+    ///
+    /// Like [`scan_tri_hop`](Self::scan_tri_hop) over four pools: p1 trades
+    /// A/B, p2 B/C, p3 C/D and p4 D/A. A cycle is reported when its
+    /// cross-rate exceeds `quad_hop_min_cross_rate` and a strategy exists for
+    /// its pools in that order. The same four pools found in another order
+    /// are one opportunity.
+    ///
+    /// Returns opportunities sorted by expected profit (descending).
+    pub fn scan_quad_hop(&self, pools: &[PoolState], clock: CycleClock) -> Vec<ArbOpportunity> {
+        let now_ms = clock.now_ms();
+
+        let mut opportunities = Vec::new();
+        let mut seen: HashSet<Vec<String>> = HashSet::new();
+
+        // Filter to fresh pools only
+        let fresh: Vec<&PoolState> = pools
+            .iter()
+            .filter(|p| p.staleness_ms(now_ms) <= self.max_staleness_ms)
+            .collect();
+
+        let prices: Vec<Option<f64>> = fresh.iter().map(|p| normalized_price(p, &self.decimals)).collect();
+
+        for (legs, tokens) in quad_hop_candidates(&fresh) {
+            let cross_rate = legs.iter().enumerate().try_fold(1.0, |rate, (n, &i)| {
+                let price = prices[i]?;
+                Some(rate * directed_price(fresh[i], price, &tokens[n], &tokens[(n + 1) % 4])?)
+            });
+            let Some(cross_rate) = cross_rate else {
+                continue;
+            };
+            if cross_rate <= self.quad_hop_min_cross_rate || cross_rate >= 1.0 + MAX_REALISTIC_SPREAD {
+                continue;
+            }
+
+            let ordered = legs.map(|i| fresh[i]);
+            let Some(strategy) = resolve_quad_strategy(&ordered, &tokens) else {
+                continue;
+            };
+            let mut key: Vec<String> = ordered.iter().map(|p| p.object_id.clone()).collect();
+            key.sort();
+            if !seen.insert(key) {
+                continue;
+            }
+
+            let spread = cross_rate - 1.0;
+            // 5 SUI (or the venues' minimum, if larger)
+            let est_amount = self.probe_amount(5_000_000_000, &ordered);
+            // Four sequential swaps compound price impact further than
+            // tri-hop's three: 0.1 against its 0.15.
+            let est_profit = (est_amount as f64 * spread * 0.1) as u64;
+            let min_profit = self.min_profit_for(&ordered[0].coin_type_a, &ordered[0].coin_type_b);
+            if est_profit <= min_profit {
+                continue;
+            }
+
+            debug!(
+                strategy = %strategy,
+                cross_rate = %format!("{:.6}", cross_rate),
+                est_profit = %est_profit,
+                path = %format!("{} → {} → {} → {} → {}",
+                    tokens[0].rsplit("::").next().unwrap_or("?"),
+                    tokens[1].rsplit("::").next().unwrap_or("?"),
+                    tokens[2].rsplit("::").next().unwrap_or("?"),
+                    tokens[3].rsplit("::").next().unwrap_or("?"),
+                    tokens[0].rsplit("::").next().unwrap_or("?")),
+                "Quad-hop opportunity detected"
+            );
+
+            opportunities.push(ArbOpportunity {
+                strategy,
+                amount_in: est_amount,
+                expected_profit: est_profit,
+                estimated_gas: QUAD_HOP_GAS_ESTIMATE,
+                net_profit: est_profit as i64 - QUAD_HOP_GAS_ESTIMATE as i64,
+                pool_ids: ordered.iter().map(|p| p.object_id.clone()).collect(),
+                type_args: layout_type_args(strategy, &tokens, &ordered),
+                detected_at_ms: now_ms,
+            });
+        }
+        self.retain_eligible(&mut opportunities);
+
+        opportunities.sort_by_key(|o| std::cmp::Reverse(o.expected_profit));
+        self.metrics.record_opportunities(&opportunities);
+        opportunities
+    }
+
+    /// Re-price the combined scans on each candidate's own pools and order
+    /// them best first.
+    ///
+    /// The scans' estimates come from fixed probe sizes and per-scan
+    /// slippage factors, so they don't compare across scans: an inflated
+    /// tri-hop spread on shallow pools outranks a solid two-hop. Here each
+    /// candidate is sized with the local optimizer instead, and dropped if
+    /// it finds no profit. Then, by net profit, a candidate is dropped when
+    /// a better one with a different hop count trades one of its pools: both
+    /// feed on the same dislocation there, and once either lands the other's
+    /// edge is gone.
+    pub fn rank(&self, pools: &[PoolState], opportunities: Vec<ArbOpportunity>) -> Vec<ArbOpportunity> {
+        let found = opportunities.len();
+        let mut ranked: Vec<ArbOpportunity> = opportunities
+            .into_iter()
+            .filter_map(|mut opp| {
+                let legs = opp
+                    .pool_ids
+                    .iter()
+                    .map(|id| pools.iter().find(|p| &p.object_id == id))
+                    .collect::<Option<Vec<_>>>()?;
+                let (simulate, hi) = build_opportunity_simulator(opp.strategy, &legs, self.missing_fee)?;
+                let limits = TradeSizeLimits::for_pools(&legs, &self.trade_size_defaults);
+                let (amount, profit) =
+                    self.search_method.optimize_lots(MIN_SIM_AMOUNT, hi, 100_000, limits, &*simulate);
+                if profit == 0 {
+                    return None;
+                }
+                opp.amount_in = amount;
+                opp.expected_profit = profit;
+                opp.net_profit = profit as i64 - opp.estimated_gas as i64;
+                Some(opp)
+            })
+            .collect();
+        let unprofitable = found - ranked.len();
+        ranked.sort_by_key(|o| std::cmp::Reverse(o.net_profit));
+
+        let mut kept: Vec<ArbOpportunity> = Vec::with_capacity(ranked.len());
+        for opp in ranked {
+            let better = kept.iter().find(|k| {
+                k.pool_ids.len() != opp.pool_ids.len() && k.pool_ids.iter().any(|id| opp.pool_ids.contains(id))
+            });
+            match better {
+                Some(better) => debug!(
+                    strategy = %opp.strategy,
+                    pools = ?opp.pool_ids,
+                    kept = %better.strategy,
+                    "Dropping opportunity — a better one trades the same pool"
+                ),
+                None => kept.push(opp),
+            }
+        }
+        if kept.len() < found {
+            debug!(
+                found = %found,
+                unprofitable = %unprofitable,
+                overlapping = %(found - unprofitable - kept.len()),
+                "Ranked opportunities"
+            );
+        }
+        kept
+    }
+
+    /// Indices of `opp`'s legs whose pool is missing from `pools` (evicted or
+    /// quarantined) or older than `max_staleness_ms`.
+    pub fn unusable_legs(&self, opp: &ArbOpportunity, pools: &[PoolState], now_ms: u64) -> Vec<usize> {
+        opp.pool_ids
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| {
+                pools
+                    .iter()
+                    .find(|p| &p.object_id == *id)
+                    .is_none_or(|p| p.staleness_ms(now_ms) > self.max_staleness_ms)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Re-resolve a tri-hop whose third leg became unusable as a two-hop.
+    ///
+    /// Candidates are the first two legs' pools, other cached pools on those
+    /// legs' pairs, and other cached pools on the closing pair (the third leg's
+    /// pair). The failed third pool is excluded. The best candidate is sized
+    /// with the local optimizer and returned only if it still clears its
+    /// pair's minimum profit after gas.
+    pub fn degrade_tri_hop(
+        &self,
+        tri: &ArbOpportunity,
+        pools: &[PoolState],
+        clock: CycleClock,
+    ) -> Option<ArbOpportunity> {
+        if tri.pool_ids.len() != 3 {
+            return None;
+        }
+        let failed_id = &tri.pool_ids[2];
+        let leg1 = pools.iter().find(|p| p.object_id == tri.pool_ids[0])?;
+        let leg2 = pools.iter().find(|p| p.object_id == tri.pool_ids[1])?;
+
+        // Closing pair: the token each leg doesn't share with the other.
+        // Derived from the legs so it works even if the third pool is gone.
+        let (_, token_a, token_c) = shared_token(leg1, leg2)?;
+
+        let candidates: Vec<PoolState> = pools
+            .iter()
+            .filter(|p| &p.object_id != failed_id)
+            .filter(|p| {
+                same_pair(p, leg1) || same_pair(p, leg2) || pool_has_pair(p, &token_a, &token_c)
+            })
+            .cloned()
+            .collect();
+
+        let best = self
+            .two_hop_candidates(&candidates, false, clock)
+            .into_iter()
+            .next()?;
+
+        // Size with the same local optimizer the strategy loop uses
+        let flash_pool = candidates.iter().find(|p| p.object_id == best.pool_ids[0])?;
+        let sell_pool = candidates.iter().find(|p| p.object_id == best.pool_ids[1])?;
+        let limits = TradeSizeLimits::for_pools(&[flash_pool, sell_pool], &self.trade_size_defaults);
+        let (simulate, hi) = build_strategy_simulator(best.strategy, flash_pool, sell_pool, self.missing_fee)?;
+        let (optimal_amount, max_profit) =
+            self.search_method.optimize_lots(MIN_SIM_AMOUNT, hi, 100_000, limits, &*simulate);
+
+        // Like the strategy loop: keep the scanner estimate if the local
+        // model finds nothing (it may not model this pool pair well).
+        let (amount, profit) = if max_profit > 0 {
+            (optimal_amount, max_profit)
+        } else {
+            (best.amount_in, best.expected_profit)
+        };
+
+        let net_profit = profit as i64 - best.estimated_gas as i64;
+        let min_profit = self.min_profit_for(&flash_pool.coin_type_a, &flash_pool.coin_type_b);
+        if profit <= min_profit || net_profit <= 0 || self.is_dust(amount) {
+            debug!(
+                tri_strategy = %tri.strategy,
+                two_hop_strategy = %best.strategy,
+                amount = %amount,
+                profit = %profit,
+                "Tri-hop degradation candidate below threshold"
+            );
+            return None;
+        }
+
+        Some(ArbOpportunity {
+            amount_in: amount,
+            expected_profit: profit,
+            net_profit,
+            ..best
+        })
+    }
+}
+
+/// Shallower ÷ deeper depth of two pools on the same pair, compared in
+/// `a`'s coin A. `None` if either depth is unknown.
+fn leg_depth_ratio(a: &PoolState, b: &PoolState) -> Option<f64> {
+    let (depth_a, _) = a.virtual_reserves()?;
+    let (b_a, b_b) = b.virtual_reserves()?;
+    let depth_b = if b.coin_type_a == a.coin_type_a { b_a } else { b_b };
+    (depth_a > 0.0 && depth_b > 0.0).then(|| depth_a.min(depth_b) / depth_a.max(depth_b))
 }
 
 /// Check if two pools trade the same token pair (in either order).
@@ -336,6 +910,144 @@ fn same_pair(a: &PoolState, b: &PoolState) -> bool {
         || (a.coin_type_a == b.coin_type_b && a.coin_type_b == b.coin_type_a)
 }
 
+/// Index pairs `(i, j)`, `i < j`, of pools trading the same token pair, in
+/// the order a full pairwise scan would visit them. Bucketing by pair makes
+/// this O(n + Σ bucket²) rather than comparing every pool with every other.
+fn same_pair_candidates(pools: &[PoolState]) -> Vec<(usize, usize)> {
+    let mut buckets: HashMap<(String, String), Vec<usize>> = HashMap::new();
+    for (i, pool) in pools.iter().enumerate() {
+        buckets.entry(pair_key(pool)).or_default().push(i);
+    }
+    let mut candidates: Vec<(usize, usize)> = buckets
+        .values()
+        .flat_map(|bucket| {
+            bucket
+                .iter()
+                .enumerate()
+                .flat_map(move |(k, &i)| bucket[k + 1..].iter().map(move |&j| (i, j)))
+        })
+        .collect();
+    candidates.sort_unstable();
+    candidates
+}
+
+/// Candidate triangles for [`Scanner::scan_tri_hop`], as `(p1, p2, p3)`
+/// indices into `pools`: p2 shares a token with p1, and p3 trades the two
+/// tokens left over. Same triples, in the same order, as looping over every
+/// p1, p2, p3 — but p2 only comes from p1's tokens' pools and p3 only from
+/// one pair bucket, so the cost follows the token graph's degree rather
+/// than n³.
+fn tri_hop_candidates(pools: &[&PoolState]) -> Vec<(usize, usize, usize)> {
+    let mut by_token: HashMap<&str, Vec<usize>> = HashMap::new();
+    let mut by_pair: HashMap<(String, String), Vec<usize>> = HashMap::new();
+    for (i, pool) in pools.iter().enumerate() {
+        by_token.entry(&pool.coin_type_a).or_default().push(i);
+        if pool.coin_type_b != pool.coin_type_a {
+            by_token.entry(&pool.coin_type_b).or_default().push(i);
+        }
+        by_pair.entry(pair_key(pool)).or_default().push(i);
+    }
+
+    let mut candidates = Vec::new();
+    for (i, p1) in pools.iter().enumerate() {
+        let mut neighbours: Vec<usize> = [&p1.coin_type_a, &p1.coin_type_b]
+            .iter()
+            .filter_map(|token| by_token.get(token.as_str()))
+            .flatten()
+            .copied()
+            .filter(|&j| j != i)
+            .collect();
+        neighbours.sort_unstable();
+        neighbours.dedup();
+
+        for j in neighbours {
+            let Some((_, token_a, token_c)) = shared_token(p1, pools[j]) else {
+                continue;
+            };
+            let Some(closing) = by_pair.get(&token_pair(&token_c, &token_a)) else {
+                continue;
+            };
+            candidates.extend(closing.iter().filter(|&&k| k != i && k != j).map(|&k| (i, j, k)));
+        }
+    }
+    candidates
+}
+
+/// Candidate 4-cycles for [`Scanner::scan_quad_hop`], as pool indices
+/// `[p1, p2, p3, p4]` with the token path `[A, B, C, D]`: p1 trades A/B, p2
+/// B/C, p3 C/D and p4 D/A, all four tokens distinct. Like
+/// [`tri_hop_candidates`], each leg only comes from the previous token's
+/// pools, so the cost follows the token graph rather than n⁴.
+fn quad_hop_candidates(pools: &[&PoolState]) -> Vec<([usize; 4], [String; 4])> {
+    let mut by_token: HashMap<&str, Vec<usize>> = HashMap::new();
+    let mut by_pair: HashMap<(String, String), Vec<usize>> = HashMap::new();
+    for (i, pool) in pools.iter().enumerate() {
+        by_token.entry(&pool.coin_type_a).or_default().push(i);
+        if pool.coin_type_b != pool.coin_type_a {
+            by_token.entry(&pool.coin_type_b).or_default().push(i);
+        }
+        by_pair.entry(pair_key(pool)).or_default().push(i);
+    }
+    let other = |pool: &PoolState, token: &str| -> Option<String> {
+        if pool.coin_type_a == token {
+            Some(pool.coin_type_b.clone())
+        } else if pool.coin_type_b == token {
+            Some(pool.coin_type_a.clone())
+        } else {
+            None
+        }
+    };
+
+    let mut candidates = Vec::new();
+    for (i, p1) in pools.iter().enumerate() {
+        for (token_a, token_b) in [(&p1.coin_type_a, &p1.coin_type_b), (&p1.coin_type_b, &p1.coin_type_a)] {
+            if token_a == token_b {
+                continue;
+            }
+            for &j in by_token.get(token_b.as_str()).into_iter().flatten() {
+                if j == i {
+                    continue;
+                }
+                let Some(token_c) = other(pools[j], token_b).filter(|c| c != token_a && c != token_b) else {
+                    continue;
+                };
+                for &k in by_token.get(token_c.as_str()).into_iter().flatten() {
+                    if k == i || k == j {
+                        continue;
+                    }
+                    let Some(token_d) =
+                        other(pools[k], &token_c).filter(|d| d != token_a && d != token_b && d != &token_c)
+                    else {
+                        continue;
+                    };
+                    let Some(closing) = by_pair.get(&token_pair(&token_d, token_a)) else {
+                        continue;
+                    };
+                    for &l in closing.iter().filter(|&&l| l != i && l != j && l != k) {
+                        let tokens = [token_a.clone(), token_b.clone(), token_c.clone(), token_d.clone()];
+                        candidates.push(([i, j, k, l], tokens));
+                    }
+                }
+            }
+        }
+    }
+    candidates
+}
+
+/// A pool's token pair, order-independent.
+fn pair_key(p: &PoolState) -> (String, String) {
+    token_pair(&p.coin_type_a, &p.coin_type_b)
+}
+
+/// Two tokens in sorted order.
+fn token_pair(x: &str, y: &str) -> (String, String) {
+    if x <= y {
+        (x.to_string(), y.to_string())
+    } else {
+        (y.to_string(), x.to_string())
+    }
+}
+
 /// Map a (flash_source_dex, sell_dex) pair to the correct StrategyType.
 fn resolve_strategy(flash_dex: Dex, sell_dex: Dex) -> Option<StrategyType> {
     match (flash_dex, sell_dex) {
@@ -354,6 +1066,8 @@ fn resolve_strategy(flash_dex: Dex, sell_dex: Dex) -> Option<StrategyType> {
         (Dex::FlowxClmm, Dex::Turbos) => Some(StrategyType::FlowxClmmToTurbos),
         (Dex::DeepBook, Dex::FlowxClmm) => Some(StrategyType::DeepBookToFlowxClmm),
         (Dex::FlowxClmm, Dex::DeepBook) => Some(StrategyType::FlowxClmmToDeepBook),
+        (Dex::Cetus, Dex::Kriya) => Some(StrategyType::CetusToKriya),
+        (Dex::Kriya, Dex::Cetus) => Some(StrategyType::KriyaToCetus),
         // FlowX AMM — NO on-chain Move implementation exists.
         // These would burn gas with MoveAbort. Disabled until Move code ships.
         (Dex::Cetus, Dex::FlowxAmm)
@@ -368,6 +1082,16 @@ fn resolve_strategy(flash_dex: Dex, sell_dex: Dex) -> Option<StrategyType> {
     }
 }
 
+/// Map a (flash_source_dex, sell_dex) pair to the strategy that flash-borrows
+/// coin B instead of A, where the package has one.
+fn resolve_reverse_strategy(flash_dex: Dex, sell_dex: Dex) -> Option<StrategyType> {
+    match (flash_dex, sell_dex) {
+        (Dex::Cetus, Dex::Turbos) => Some(StrategyType::CetusToTurbosRev),
+        (Dex::Cetus, Dex::Aftermath) => Some(StrategyType::CetusToAftermathRev),
+        _ => None,
+    }
+}
+
 /// Map a (dex1, dex2, dex3) triple to the correct tri-hop StrategyType.
 fn resolve_tri_strategy(dex1: Dex, dex2: Dex, dex3: Dex) -> Option<StrategyType> {
     match (dex1, dex2, dex3) {
@@ -385,19 +1109,48 @@ fn resolve_tri_strategy(dex1: Dex, dex2: Dex, dex3: Dex) -> Option<StrategyType>
     }
 }
 
-/// Find the Turbos fee type from a set of pools.
-/// Returns the fee_type of the first Turbos pool found (strategies have at most one).
-fn find_turbos_fee_type(pools: &[&PoolState]) -> Option<String> {
-    pools
+/// The quad-hop strategy for `pools` traded along `tokens` (pool n swaps
+/// `tokens[n]` for `tokens[n + 1]`). Each leg is an a2b swap, so every pool
+/// must list its input coin as coin A.
+fn resolve_quad_strategy(pools: &[&PoolState; 4], tokens: &[String; 4]) -> Option<StrategyType> {
+    let a2b = pools
         .iter()
-        .find(|p| p.dex == Dex::Turbos)
-        .and_then(|p| p.fee_type.clone())
+        .enumerate()
+        .all(|(n, p)| p.coin_type_a == tokens[n] && p.coin_type_b == tokens[(n + 1) % 4]);
+    if !a2b {
+        return None;
+    }
+    match pools.map(|p| p.dex) {
+        [Dex::Cetus, Dex::Cetus, Dex::Cetus, Dex::Cetus] => Some(StrategyType::QuadCetusCetusCetusCetus),
+        _ => None,
+    }
+}
+
+/// Build `strategy`'s type arguments in the order its Move entry function
+/// declares them ([`StrategyType::type_arg_layout`]): `coins` along the path,
+/// then the Turbos fee tier and Aftermath LP types, each taken from the first
+/// pool of that DEX (strategies have at most one of each).
+///
+/// A slot whose pool doesn't know its extra type is left out, so the PTB
+/// builder's layout check rejects the opportunity instead of the chain.
+fn layout_type_args(strategy: StrategyType, coins: &[String], pools: &[&PoolState]) -> Vec<String> {
+    let extra_type = |dex: Dex| pools.iter().find(|p| p.dex == dex).and_then(|p| p.fee_type.clone());
+    strategy
+        .type_arg_layout()
+        .iter()
+        .filter_map(|slot| match slot {
+            TypeArgSlot::Coin(n) => coins.get(*n).cloned(),
+            TypeArgSlot::TurbosFee => extra_type(Dex::Turbos),
+            TypeArgSlot::AftermathLp => extra_type(Dex::Aftermath),
+        })
+        .collect()
 }
 
 /// Resolve tri-hop strategy with validated pool ordering.
 ///
 /// Tries to find a valid assignment of 3 pools to move function parameters.
-/// Returns `(strategy, ordered_pools, type_args)` if a valid ordering exists.
+/// Returns `(strategy, ordered_pools, coins)` if a valid ordering exists,
+/// `coins` being the path's `[A, B, C]`.
 ///
 /// **a2b cycle** (v1): pool_ab<A,B> → pool_bc<B,C> → pool_ca<C,A> all using swap_a2b.
 /// **b2a third leg** (v2): pool_ab<A,B> → pool_bc<B,C> → pool_ac<A,C> where third leg uses swap_b2a.
@@ -423,12 +1176,12 @@ fn resolve_tri_with_ordering<'a>(
             && pc.coin_type_b == pa.coin_type_a
         {
             if let Some(strategy) = resolve_tri_strategy(pa.dex, pb.dex, pc.dex) {
-                let type_args = vec![
+                let coins = vec![
                     pa.coin_type_a.clone(), // A
                     pa.coin_type_b.clone(), // B
                     pb.coin_type_b.clone(), // C
                 ];
-                return Some((strategy, vec![pa, pb, pc], type_args));
+                return Some((strategy, vec![pa, pb, pc], coins));
             }
         }
     }
@@ -448,12 +1201,12 @@ fn resolve_tri_with_ordering<'a>(
             && pc.coin_type_b == pb.coin_type_b
         {
             if let Some(strategy) = resolve_tri_strategy_v2(pa.dex, pb.dex, pc.dex) {
-                let type_args = vec![
+                let coins = vec![
                     pa.coin_type_a.clone(), // A
                     pa.coin_type_b.clone(), // B
                     pb.coin_type_b.clone(), // C
                 ];
-                return Some((strategy, vec![pa, pb, pc], type_args));
+                return Some((strategy, vec![pa, pb, pc], coins));
             }
         }
     }
@@ -501,10 +1254,24 @@ fn pool_has_pair(pool: &PoolState, token_x: &str, token_y: &str) -> bool {
 
 /// Get the effective price for swapping `from` → `to` on a pool.
 /// Returns None if the pool doesn't have price data or doesn't trade the pair.
-fn pool_price_for_direction(pool: &PoolState, from: &str, to: &str) -> Option<f64> {
+#[cfg(test)]
+fn pool_price_for_direction(
+    pool: &PoolState,
+    from: &str,
+    to: &str,
+    decimals: &DecimalRegistry,
+) -> Option<f64> {
+    directed_price(pool, normalized_price(pool, decimals)?, from, to)
+}
+
+/// A pool's A-in-B price, decimal-normalized.
+fn normalized_price(pool: &PoolState, decimals: &DecimalRegistry) -> Option<f64> {
     let base_price = pool.price_a_in_b()?;
-    let normalized = normalize_price(base_price, &pool.coin_type_a, &pool.coin_type_b);
+    Some(decimals.normalize_price(base_price, &pool.coin_type_a, &pool.coin_type_b))
+}
 
+/// `pool`'s `normalized` A-in-B price, turned into the price of `from` in `to`.
+fn directed_price(pool: &PoolState, normalized: f64, from: &str, to: &str) -> Option<f64> {
     if pool.coin_type_a == from && pool.coin_type_b == to {
         // a→b: price is already A-in-B
         Some(normalized)
@@ -523,6 +1290,7 @@ fn pool_price_for_direction(pool: &PoolState, from: &str, to: &str) -> Option<f6
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arb_types::clock::unix_now_ms;
 
     fn make_pool(id: &str, dex: Dex, sqrt_price: u128) -> PoolState {
         PoolState {
@@ -538,11 +1306,13 @@ mod tests {
             reserve_b: None,
             best_bid: None,
             best_ask: None,
-            last_updated_ms: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis() as u64,
+            book_depth: None,
+            last_updated_ms: unix_now_ms(),
             fee_type: None,
+            min_trade_size: None,
+            lot_size: None,
+            raw_balance_a: None,
+            raw_balance_b: None,
         }
     }
 
@@ -587,6 +1357,8 @@ mod tests {
             (Dex::FlowxClmm, Dex::Turbos, StrategyType::FlowxClmmToTurbos),
             (Dex::DeepBook, Dex::FlowxClmm, StrategyType::DeepBookToFlowxClmm),
             (Dex::FlowxClmm, Dex::DeepBook, StrategyType::FlowxClmmToDeepBook),
+            (Dex::Cetus, Dex::Kriya, StrategyType::CetusToKriya),
+            (Dex::Kriya, Dex::Cetus, StrategyType::KriyaToCetus),
             // FlowX AMM strategies are intentionally disabled (no on-chain code)
         ];
 
@@ -623,6 +1395,30 @@ mod tests {
         assert_eq!(resolve_strategy(Dex::Turbos, Dex::Turbos), None);
         assert_eq!(resolve_strategy(Dex::DeepBook, Dex::DeepBook), None);
         assert_eq!(resolve_strategy(Dex::FlowxClmm, Dex::FlowxClmm), None);
+        assert_eq!(resolve_strategy(Dex::Kriya, Dex::Kriya), None);
+    }
+
+    #[test]
+    fn test_kriya_spread_flashes_on_the_cheap_pool() {
+        let mut kriya = make_pool("0x1", Dex::Kriya, (1u128 << 64) * 95 / 100);
+        let mut cetus = make_pool("0x2", Dex::Cetus, (1u128 << 64) * 105 / 100);
+        kriya.liquidity = Some(1_000_000_000_000);
+        cetus.liquidity = Some(1_000_000_000_000);
+
+        let opps = Scanner::new(0).scan_two_hop(&[kriya, cetus], CycleClock::now());
+        assert_eq!(opps.len(), 1);
+        assert_eq!(opps[0].strategy, StrategyType::KriyaToCetus);
+        assert_eq!(opps[0].pool_ids, vec!["0x1".to_string(), "0x2".to_string()]);
+        assert_eq!(opps[0].type_args, vec!["SUI".to_string(), "USDC".to_string()]);
+    }
+
+    #[test]
+    fn test_resolve_kriya_only_against_cetus() {
+        // The package only pairs Kriya with Cetus
+        for other in [Dex::Turbos, Dex::DeepBook, Dex::FlowxClmm, Dex::Aftermath] {
+            assert_eq!(resolve_strategy(Dex::Kriya, other), None, "Kriya → {other:?}");
+            assert_eq!(resolve_strategy(other, Dex::Kriya), None, "{other:?} → Kriya");
+        }
     }
 
     // ── same_pair tests ──
@@ -649,14 +1445,14 @@ mod tests {
     #[test]
     fn test_scan_empty_pools() {
         let scanner = Scanner::new(1_000);
-        assert!(scanner.scan_two_hop(&[]).is_empty());
+        assert!(scanner.scan_two_hop(&[], CycleClock::now()).is_empty());
     }
 
     #[test]
     fn test_scan_single_pool_no_opportunities() {
         let scanner = Scanner::new(1_000);
         let pools = vec![make_pool("0x1", Dex::Cetus, 1 << 64)];
-        assert!(scanner.scan_two_hop(&pools).is_empty());
+        assert!(scanner.scan_two_hop(&pools, CycleClock::now()).is_empty());
     }
 
     #[test]
@@ -666,7 +1462,7 @@ mod tests {
             make_pool("0x1", Dex::Cetus, 1 << 64),
             make_pool("0x2", Dex::Turbos, 1 << 64),
         ];
-        assert!(scanner.scan_two_hop(&pools).is_empty());
+        assert!(scanner.scan_two_hop(&pools, CycleClock::now()).is_empty());
     }
 
     #[test]
@@ -676,47 +1472,255 @@ mod tests {
             make_pool("0x1", Dex::Cetus, (1u128 << 64) * 90 / 100), // price=0.81
             make_pool("0x2", Dex::Turbos, (1u128 << 64) * 110 / 100), // price=1.21
         ];
-        let opps = scanner.scan_two_hop(&pools);
+        let opps = scanner.scan_two_hop(&pools, CycleClock::now());
         assert!(!opps.is_empty(), "Should detect ~40% spread");
         assert_eq!(opps[0].pool_ids.len(), 2);
     }
 
+    #[test]
+    fn test_pair_index_matches_pairwise_scan() {
+        let coins = ["SUI", "USDC", "DEEP", "WETH", "CETUS", "USDT"];
+        let pools: Vec<PoolState> = (0..200)
+            .map(|i| {
+                let (a, b) = (coins[i % 5], coins[i % 5 + 1]);
+                let price = 1.0 + (i % 7) as f64 * 0.004;
+                let dex = if i % 2 == 0 { Dex::Cetus } else { Dex::Turbos };
+                // Every third pool lists the pair the other way round
+                if i % 3 == 0 {
+                    make_tri_pool(&format!("0x{:x}", i), dex, b, a, 1.0 / price)
+                } else {
+                    make_tri_pool(&format!("0x{:x}", i), dex, a, b, price)
+                }
+            })
+            .collect();
+
+        let naive: Vec<(usize, usize)> = (0..pools.len())
+            .flat_map(|i| ((i + 1)..pools.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| same_pair(&pools[i], &pools[j]))
+            .collect();
+        assert_eq!(same_pair_candidates(&pools), naive);
+
+        // Same opportunities as scanning each pair's pools on their own
+        let clock = CycleClock::now();
+        let scanner = Scanner::new(0);
+        let ids = |opps: Vec<ArbOpportunity>| {
+            let mut ids: Vec<Vec<String>> = opps.into_iter().map(|o| o.pool_ids).collect();
+            ids.sort();
+            ids
+        };
+        let all = ids(scanner.scan_two_hop(&pools, clock));
+        let mut per_pair = Vec::new();
+        for k in 0..5 {
+            let subset: Vec<PoolState> = pools.iter().skip(k).step_by(5).cloned().collect();
+            per_pair.extend(scanner.scan_two_hop(&subset, clock));
+        }
+        assert!(!all.is_empty());
+        assert_eq!(all, ids(per_pair));
+    }
+
+    /// `count` tokens, each paired with SUI and USDC and with the next
+    /// token, plus SUI/USDC pools, at roughly consistent prices.
+    fn token_graph_pools(count: usize) -> Vec<PoolState> {
+        let mut pools = Vec::new();
+        let mut add = |a: String, b: String, price: f64| {
+            let dex = if pools.len() % 2 == 0 { Dex::Cetus } else { Dex::Turbos };
+            pools.push(make_tri_pool(&format!("0x{:x}", pools.len() + 1), dex, &a, &b, price));
+        };
+        for t in 0..count {
+            let price = 1.0 + t as f64 * 0.01;
+            add(format!("T{}", t), "SUI".to_string(), price);
+            add(format!("T{}", t), "USDC".to_string(), price * (1.0 + (t % 3) as f64 * 0.001));
+            add(format!("T{}", t), format!("T{}", (t + 1) % count), price / (1.0 + ((t + 1) % count) as f64 * 0.01));
+        }
+        for k in 0..5 {
+            add("SUI".to_string(), "USDC".to_string(), 1.0 + k as f64 * 0.002);
+        }
+        pools
+    }
+
+    #[test]
+    fn test_tri_hop_candidates_match_triple_loop() {
+        let pools = token_graph_pools(12);
+        let refs: Vec<&PoolState> = pools.iter().collect();
+        let n = refs.len();
+        let naive: Vec<(usize, usize, usize)> = (0..n)
+            .flat_map(|i| (0..n).flat_map(move |j| (0..n).map(move |k| (i, j, k))))
+            .filter(|&(i, j, k)| i != j && i != k && j != k)
+            .filter(|&(i, j, k)| match shared_token(refs[i], refs[j]) {
+                Some((_, a, c)) => pool_has_pair(refs[k], &c, &a),
+                None => false,
+            })
+            .collect();
+        assert!(!naive.is_empty());
+        assert_eq!(tri_hop_candidates(&refs), naive);
+    }
+
+    #[test]
+    fn test_scan_200_pools_is_fast() {
+        let pools = token_graph_pools(65);
+        assert_eq!(pools.len(), 200);
+        let scanner = Scanner::new(0);
+        let clock = CycleClock::now();
+
+        // Plenty of triangles to price, so the timing means something
+        let refs: Vec<&PoolState> = pools.iter().collect();
+        assert!(tri_hop_candidates(&refs).len() > 1_000);
+
+        let start = std::time::Instant::now();
+        scanner.scan_two_hop(&pools, clock);
+        scanner.scan_tri_hop(&pools, clock);
+        let elapsed = start.elapsed();
+        assert!(elapsed.as_millis() < 50, "200-pool scan took {:?}", elapsed);
+    }
+
+    #[test]
+    fn test_resolve_reverse_strategy() {
+        assert_eq!(resolve_reverse_strategy(Dex::Cetus, Dex::Turbos), Some(StrategyType::CetusToTurbosRev));
+        assert_eq!(resolve_reverse_strategy(Dex::Cetus, Dex::Aftermath), Some(StrategyType::CetusToAftermathRev));
+        assert_eq!(resolve_reverse_strategy(Dex::Turbos, Dex::Cetus), None);
+        assert_eq!(resolve_reverse_strategy(Dex::DeepBook, Dex::Aftermath), None);
+    }
+
+    #[test]
+    fn test_reversed_spread_yields_rev_strategy() {
+        // SUI is dearer on Cetus than on Aftermath. Borrowing SUI on the
+        // Aftermath pool isn't possible; borrowing USDC on Cetus is.
+        let mut cetus = make_pool("0x1", Dex::Cetus, (1u128 << 64) * 110 / 100);
+        cetus.liquidity = Some(1_000_000_000_000);
+        let mut aftermath = make_pool("0x2", Dex::Aftermath, 0);
+        aftermath.sqrt_price = None;
+        aftermath.liquidity = None;
+        aftermath.reserve_a = Some(1_000_000_000_000);
+        aftermath.reserve_b = Some(1_000_000_000_000);
+
+        let opps = Scanner::new(0).scan_two_hop(&[cetus, aftermath], CycleClock::now());
+        assert_eq!(opps.len(), 1);
+        let opp = &opps[0];
+        assert_eq!(opp.strategy, StrategyType::CetusToAftermathRev);
+        assert_eq!(opp.pool_ids, vec!["0x1".to_string(), "0x2".to_string()]);
+        assert_eq!(opp.type_args, vec!["SUI".to_string(), "USDC".to_string()]);
+        assert_eq!(opp.profit_coin(), Some("USDC"));
+        // Sized in USDC at the Cetus price
+        assert_eq!(opp.amount_in, (1_000_000_000f64 * 1.21) as u64);
+    }
+
+    #[test]
+    fn test_pick_direction_takes_more_profitable_simulation() {
+        let mut dear = make_pool("0x1", Dex::Cetus, (1u128 << 64) * 105 / 100);
+        let mut cheap = make_pool("0x2", Dex::Turbos, (1u128 << 64) * 95 / 100);
+        dear.liquidity = Some(1_000_000_000_000);
+        cheap.liquidity = Some(1_000_000_000_000);
+        let scanner = Scanner::new(0);
+
+        let forward = (StrategyType::TurbosToCetus, &cheap, &dear);
+        let reverse = (StrategyType::CetusToTurbosRev, &dear, &cheap);
+        let forward_profit = scanner.simulated_profit(forward) as f64;
+        let reverse_profit = scanner.simulated_profit(reverse) as f64 / dear.price_a_in_b().unwrap();
+        let expected = if reverse_profit > forward_profit { reverse.0 } else { forward.0 };
+
+        let (picked, flash, _) = scanner.pick_direction(&cheap, &dear).unwrap();
+        assert_eq!(picked, expected);
+        assert_eq!(flash.dex, picked.flash_source());
+
+        // Only one direction exists for Turbos → Aftermath
+        let mut amm = cheap.clone();
+        amm.dex = Dex::Aftermath;
+        amm.reserve_a = Some(1_000_000_000_000);
+        amm.reserve_b = Some(1_000_000_000_000);
+        let (picked, _, _) = scanner.pick_direction(&amm, &dear).unwrap();
+        assert_eq!(picked, StrategyType::CetusToAftermathRev);
+    }
+
+    #[test]
+    fn test_min_leg_depth_ratio_skips_thin_leg() {
+        let shallow = make_pool("0x1", Dex::Cetus, (1u128 << 64) * 90 / 100);
+        let mut deep = make_pool("0x2", Dex::Turbos, (1u128 << 64) * 110 / 100);
+        deep.liquidity = Some(100_000_000_000); // 100× the other leg
+        let pools = vec![shallow, deep];
+
+        assert!(!Scanner::new(0).scan_two_hop(&pools, CycleClock::now()).is_empty(), "off by default");
+        let scanner = Scanner::new(0).with_min_leg_depth_ratio(0.2);
+        assert!(scanner.scan_two_hop(&pools, CycleClock::now()).is_empty());
+
+        // Comparable depths still trade
+        let mut balanced = pools.clone();
+        balanced[1].liquidity = Some(3_000_000_000);
+        assert!(!scanner.scan_two_hop(&balanced, CycleClock::now()).is_empty());
+    }
+
+    #[test]
+    fn test_leg_depth_ratio_across_models_and_orientation() {
+        // CLMM at price 1 with L = 1e9 → 1e9 of each coin, virtually
+        let clmm = make_pool("0x1", Dex::Cetus, 1u128 << 64);
+        let mut amm = make_pool("0x2", Dex::Aftermath, 0);
+        amm.sqrt_price = None;
+        amm.liquidity = None;
+        // Reversed ordering: USDC/SUI, 250M SUI deep
+        amm.coin_type_a = "USDC".into();
+        amm.coin_type_b = "SUI".into();
+        amm.reserve_a = Some(4_000_000_000);
+        amm.reserve_b = Some(250_000_000);
+        let ratio = leg_depth_ratio(&clmm, &amm).unwrap();
+        assert!((ratio - 0.25).abs() < 1e-9, "got {ratio}");
+
+        let mut book = make_pool("0x3", Dex::DeepBook, 0);
+        book.reserve_a = Some(1);
+        book.reserve_b = Some(1);
+        assert_eq!(leg_depth_ratio(&clmm, &book), None);
+        assert!(Scanner::new(0).with_min_leg_depth_ratio(0.9).legs_balanced(&clmm, &book));
+    }
+
     #[test]
     fn test_scan_skips_stale_pools() {
         let scanner = Scanner::new(0);
         let mut fresh = make_pool("0x1", Dex::Cetus, (1u128 << 64) * 80 / 100);
         let mut stale = make_pool("0x2", Dex::Turbos, (1u128 << 64) * 120 / 100);
         stale.last_updated_ms = 0; // epoch = very stale
-        fresh.last_updated_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
+        fresh.last_updated_ms = unix_now_ms();
 
-        let opps = scanner.scan_two_hop(&[fresh, stale]);
+        let opps = scanner.scan_two_hop(&[fresh, stale], CycleClock::now());
         assert!(opps.is_empty(), "Should skip stale pool");
     }
 
+    #[test]
+    fn test_pair_freshness_window() {
+        let now = unix_now_ms();
+        let mut a = make_pool("0x1", Dex::Cetus, (1u128 << 64) * 90 / 100);
+        let mut b = make_pool("0x2", Dex::Turbos, (1u128 << 64) * 110 / 100);
+        a.last_updated_ms = now;
+        // Within the global staleness limit, but not the per-pair window
+        b.last_updated_ms = now - 3_000;
+
+        let lenient = Scanner::new(0);
+        assert!(!lenient.scan_two_hop(&[a.clone(), b.clone()], CycleClock::now()).is_empty());
+
+        let strict = Scanner::new(0).with_pair_freshness(1_000, 2);
+        assert!(strict.scan_two_hop(&[a.clone(), b.clone()], CycleClock::now()).is_empty());
+        assert!(strict.tradable_pairs(&[a.clone(), b.clone()], now).is_empty());
+
+        b.last_updated_ms = now;
+        assert!(!strict.scan_two_hop(&[a.clone(), b.clone()], CycleClock::now()).is_empty());
+
+        // Two fresh pools aren't enough when three are required
+        let three = Scanner::new(0).with_pair_freshness(1_000, 3);
+        assert!(three.scan_two_hop(&[a, b], CycleClock::now()).is_empty());
+    }
+
     #[test]
     fn test_scan_different_pairs_no_match() {
         let scanner = Scanner::new(0);
         let mut a = make_pool("0x1", Dex::Cetus, (1u128 << 64) * 80 / 100);
         let mut b = make_pool("0x2", Dex::Turbos, (1u128 << 64) * 120 / 100);
         b.coin_type_b = "WETH".to_string();
-        a.last_updated_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
+        a.last_updated_ms = unix_now_ms();
         b.last_updated_ms = a.last_updated_ms;
-        assert!(scanner.scan_two_hop(&[a, b]).is_empty());
+        assert!(scanner.scan_two_hop(&[a, b], CycleClock::now()).is_empty());
     }
 
     #[test]
     fn test_scan_sorted_by_profit() {
         let scanner = Scanner::new(0);
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
+        let now = unix_now_ms();
 
         let mut small_spread = make_pool("0x1", Dex::Cetus, (1u128 << 64) * 98 / 100);
         let mut small_other = make_pool("0x2", Dex::Turbos, (1u128 << 64) * 103 / 100);
@@ -729,7 +1733,7 @@ mod tests {
             p.last_updated_ms = now;
         }
 
-        let opps = scanner.scan_two_hop(&[small_spread, small_other, big_spread, big_other]);
+        let opps = scanner.scan_two_hop(&[small_spread, small_other, big_spread, big_other], CycleClock::now());
         if opps.len() >= 2 {
             assert!(
                 opps[0].expected_profit >= opps[1].expected_profit,
@@ -741,11 +1745,8 @@ mod tests {
     // ── Tri-hop helper tests ──
 
     fn make_tri_pool(id: &str, dex: Dex, coin_a: &str, coin_b: &str, price: f64) -> PoolState {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
-        let is_clmm = matches!(dex, Dex::Cetus | Dex::Turbos | Dex::FlowxClmm);
+        let now = unix_now_ms();
+        let is_clmm = matches!(dex, Dex::Cetus | Dex::Turbos | Dex::FlowxClmm | Dex::Kriya | Dex::Bluefin);
 
         let (sqrt_price, liquidity, reserve_a, reserve_b) = if is_clmm {
             // CLMM: set sqrt_price from desired price (Q64.64)
@@ -772,8 +1773,13 @@ mod tests {
             reserve_b,
             best_bid: None,
             best_ask: None,
+            book_depth: None,
             last_updated_ms: now,
             fee_type: None,
+            min_trade_size: None,
+            lot_size: None,
+            raw_balance_a: None,
+            raw_balance_b: None,
         }
     }
 
@@ -817,6 +1823,23 @@ mod tests {
         assert!(!pool_has_pair(&p, "SUI", "DEEP"));
     }
 
+    #[test]
+    fn test_pool_price_uses_registry_decimals() {
+        let sui = "0x2::sui::SUI";
+        let token = "0xabc::token::TOKEN";
+        let p = make_tri_pool("0x1", Dex::FlowxAmm, sui, token, 0.002);
+        let decimals = DecimalRegistry::new();
+        // Unknown coin: static table (9 decimals), so no adjustment
+        let table = pool_price_for_direction(&p, sui, token, &decimals).unwrap();
+        assert!((table - 0.002).abs() < 1e-9);
+
+        decimals.insert(token, 6);
+        let fetched = pool_price_for_direction(&p, sui, token, &decimals).unwrap();
+        assert!((fetched - 2.0).abs() < 1e-6, "{}", fetched);
+        let inverse = pool_price_for_direction(&p, token, sui, &decimals).unwrap();
+        assert!((inverse - 0.5).abs() < 1e-6, "{}", inverse);
+    }
+
     #[test]
     fn test_resolve_tri_strategy_valid() {
         assert_eq!(
@@ -844,7 +1867,7 @@ mod tests {
     #[test]
     fn test_scan_tri_hop_empty() {
         let scanner = Scanner::new(0);
-        assert!(scanner.scan_tri_hop(&[]).is_empty());
+        assert!(scanner.scan_tri_hop(&[], CycleClock::now()).is_empty());
     }
 
     #[test]
@@ -854,7 +1877,7 @@ mod tests {
         let p1 = make_tri_pool("0x1", Dex::Cetus, "SUI", "CETUS", 3.0);
         let p2 = make_tri_pool("0x2", Dex::Cetus, "CETUS", "NAVX", 0.5);
         // Only 2 pools — can't form triangle
-        assert!(scanner.scan_tri_hop(&[p1, p2]).is_empty());
+        assert!(scanner.scan_tri_hop(&[p1, p2], CycleClock::now()).is_empty());
     }
 
     #[test]
@@ -867,12 +1890,169 @@ mod tests {
         let p3 = make_tri_pool("0x3", Dex::Cetus, "NAVX", "SUI", 0.2);    // 1 NAVX = 0.2 SUI
         // Cross rate: 3.5 * 2.0 * 0.2 = 1.4 (40% edge)
 
-        let opps = scanner.scan_tri_hop(&[p1, p2, p3]);
+        let opps = scanner.scan_tri_hop(&[p1, p2, p3], CycleClock::now());
         assert!(!opps.is_empty(), "Should find triangular arb");
         assert_eq!(opps[0].pool_ids.len(), 3);
         assert_eq!(opps[0].type_args.len(), 3);
     }
 
+    #[test]
+    fn test_allowed_flash_coins_filters_profit_currency() {
+        let pools = vec![
+            make_pool("0x1", Dex::Cetus, (1u128 << 64) * 90 / 100),
+            make_pool("0x2", Dex::Turbos, (1u128 << 64) * 110 / 100),
+        ];
+        // Unset: any currency
+        assert!(!Scanner::new(0).scan_two_hop(&pools, CycleClock::now()).is_empty());
+
+        let sui_only = Scanner::new(0).with_allowed_flash_coins(vec!["SUI".to_string()]);
+        assert!(sui_only.scan_two_hop(&pools, CycleClock::now()).iter().all(|o| o.type_args[0] == "SUI"));
+        assert!(!sui_only.scan_two_hop(&pools, CycleClock::now()).is_empty());
+
+        let usdt_only = Scanner::new(0).with_allowed_flash_coins(vec!["USDT".to_string()]);
+        assert!(usdt_only.scan_two_hop(&pools, CycleClock::now()).is_empty());
+
+        let p1 = make_tri_pool("0x1", Dex::Cetus, "SUI", "CETUS", 3.5);
+        let p2 = make_tri_pool("0x2", Dex::Cetus, "CETUS", "NAVX", 2.0);
+        let p3 = make_tri_pool("0x3", Dex::Cetus, "NAVX", "SUI", 0.2);
+        let tri = [p1, p2, p3];
+        assert!(usdt_only.scan_tri_hop(&tri, CycleClock::now()).is_empty());
+        assert!(sui_only.scan_tri_hop(&tri, CycleClock::now()).iter().all(|o| o.type_args[0] == "SUI"));
+    }
+
+    #[test]
+    fn test_pair_thresholds_override_global_min_profit() {
+        let pools = vec![
+            make_pool("0x1", Dex::Cetus, (1u128 << 64) * 90 / 100),
+            make_pool("0x2", Dex::Turbos, (1u128 << 64) * 110 / 100),
+        ];
+        // Either key order resolves to the same threshold
+        for key in [("USDC", "SUI"), ("SUI", "USDC")] {
+            let strict = Scanner::new(0)
+                .with_pair_thresholds(HashMap::from([((key.0.to_string(), key.1.to_string()), u64::MAX)]));
+            assert_eq!(strict.min_profit_for("SUI", "USDC"), u64::MAX);
+            assert_eq!(strict.min_profit_for("USDC", "SUI"), u64::MAX);
+            assert_eq!(strict.min_profit_for("SUI", "CETUS"), 0);
+            assert!(strict.scan_two_hop(&pools, CycleClock::now()).is_empty());
+        }
+
+        // An override can also be looser than the global minimum
+        let loose = Scanner::new(u64::MAX)
+            .with_pair_thresholds(HashMap::from([(("USDC".to_string(), "SUI".to_string()), 0)]));
+        assert!(!loose.scan_two_hop(&pools, CycleClock::now()).is_empty());
+    }
+
+    #[test]
+    fn test_tri_hop_uses_flash_pair_threshold() {
+        let tri = [
+            make_tri_pool("0x1", Dex::Cetus, "SUI", "CETUS", 3.5),
+            make_tri_pool("0x2", Dex::Cetus, "CETUS", "NAVX", 2.0),
+            make_tri_pool("0x3", Dex::Cetus, "NAVX", "SUI", 0.2),
+        ];
+        let opps = Scanner::new(0).scan_tri_hop(&tri, CycleClock::now());
+        assert!(!opps.is_empty());
+        let flash = tri.iter().find(|p| p.object_id == opps[0].pool_ids[0]).unwrap();
+        let others: Vec<&PoolState> = tri.iter().filter(|p| p.object_id != flash.object_id).collect();
+
+        // Strict threshold on the other legs' pairs changes nothing...
+        let thresholds = others
+            .iter()
+            .map(|p| ((p.coin_type_b.clone(), p.coin_type_a.clone()), u64::MAX))
+            .collect();
+        let lenient = Scanner::new(0).with_pair_thresholds(thresholds);
+        assert!(!lenient.scan_tri_hop(&tri, CycleClock::now()).is_empty());
+
+        // ...on the flash pool's pair (given reversed) it rejects the loop
+        let flash_key = (flash.coin_type_b.clone(), flash.coin_type_a.clone());
+        let strict = Scanner::new(0).with_pair_thresholds(HashMap::from([(flash_key, u64::MAX)]));
+        assert!(strict
+            .scan_tri_hop(&tri, CycleClock::now())
+            .iter()
+            .all(|o| o.pool_ids[0] != flash.object_id));
+    }
+
+    #[test]
+    fn test_dust_floor_rejects_small_amounts() {
+        let pools = vec![
+            make_pool("0x1", Dex::Cetus, (1u128 << 64) * 90 / 100),
+            make_pool("0x2", Dex::Turbos, (1u128 << 64) * 110 / 100),
+        ];
+        // Default floor (0.01 SUI) is below the 1 SUI probe amount
+        let opps = Scanner::new(0).scan_two_hop(&pools, CycleClock::now());
+        assert!(!opps.is_empty());
+        assert!(opps.iter().all(|o| o.amount_in >= DEFAULT_MIN_TRADE_AMOUNT_MIST));
+
+        // Raise the floor above the probe: rejected regardless of reported profit
+        let strict = Scanner::new(0).with_min_trade_amount(2_000_000_000);
+        assert!(strict.is_dust(1_000_000_000));
+        assert!(strict.scan_two_hop(&pools, CycleClock::now()).is_empty());
+    }
+
+    #[test]
+    fn test_strategy_allow_and_deny_lists_filter_opportunities() {
+        let pools = vec![
+            make_pool("0x1", Dex::Cetus, (1u128 << 64) * 90 / 100),
+            make_pool("0x2", Dex::Turbos, (1u128 << 64) * 110 / 100),
+        ];
+        let found = |scanner: Scanner| -> Vec<StrategyType> {
+            scanner.scan_two_hop(&pools, CycleClock::now()).iter().map(|o| o.strategy).collect()
+        };
+        let cetus_turbos = StrategyType::CetusToTurbos;
+        let turbos_cetus = StrategyType::TurbosToCetus;
+        assert_eq!(found(Scanner::new(0)), [cetus_turbos]);
+
+        assert_eq!(found(Scanner::new(0).with_strategy_filter(vec![cetus_turbos, turbos_cetus], vec![])), [cetus_turbos]);
+        assert!(found(Scanner::new(0).with_strategy_filter(vec![turbos_cetus], vec![])).is_empty());
+        assert!(found(Scanner::new(0).with_strategy_filter(vec![], vec![cetus_turbos])).is_empty());
+        assert_eq!(found(Scanner::new(0).with_strategy_filter(vec![], vec![turbos_cetus])), [cetus_turbos]);
+        // Deny wins over allow
+        assert!(found(Scanner::new(0).with_strategy_filter(vec![cetus_turbos], vec![cetus_turbos])).is_empty());
+
+        // Tri-hop opportunities are filtered the same way
+        let tri = [
+            make_tri_pool("0x1", Dex::Cetus, "SUI", "CETUS", 3.5),
+            make_tri_pool("0x2", Dex::Cetus, "CETUS", "NAVX", 2.0),
+            make_tri_pool("0x3", Dex::Cetus, "NAVX", "SUI", 0.2),
+        ];
+        let tri_strategy = Scanner::new(0).scan_tri_hop(&tri, CycleClock::now())[0].strategy;
+        let denied = Scanner::new(0).with_strategy_filter(vec![], vec![tri_strategy]);
+        assert!(denied.scan_tri_hop(&tri, CycleClock::now()).iter().all(|o| o.strategy != tri_strategy));
+    }
+
+    #[test]
+    fn test_flash_coin_allowed_normalizes_address() {
+        let scanner = Scanner::new(0).with_allowed_flash_coins(vec![format!("0x{:0>64}::sui::SUI", "2")]);
+        let mut opp = ArbOpportunity {
+            strategy: StrategyType::CetusToTurbos,
+            amount_in: 1,
+            expected_profit: 1,
+            estimated_gas: 0,
+            net_profit: 1,
+            pool_ids: vec![],
+            type_args: vec!["0x2::sui::SUI".into(), "0xa::usdc::USDC".into()],
+            detected_at_ms: 0,
+        };
+        assert!(scanner.flash_coin_allowed(&opp));
+        opp.type_args.swap(0, 1);
+        assert!(!scanner.flash_coin_allowed(&opp));
+    }
+
+    #[test]
+    fn test_tri_hop_type_args_follow_layout() {
+        let scanner = Scanner::new(0);
+        // The Turbos leg listed first: its fee tier still goes after the coins
+        let mut turbos = make_tri_pool("0x3", Dex::Turbos, "NAVX", "SUI", 0.2);
+        turbos.fee_type = Some("0x91b::fee3000bps::FEE3000BPS".to_string());
+        let p1 = make_tri_pool("0x1", Dex::Cetus, "SUI", "CETUS", 3.5);
+        let p2 = make_tri_pool("0x2", Dex::Cetus, "CETUS", "NAVX", 2.0);
+
+        let opps = scanner.scan_tri_hop(&[turbos, p1, p2], CycleClock::now());
+        assert_eq!(opps.len(), 1);
+        assert_eq!(opps[0].strategy, StrategyType::TriCetusCetusTurbos);
+        assert_eq!(opps[0].type_args, ["SUI", "CETUS", "NAVX", "0x91b::fee3000bps::FEE3000BPS"]);
+        assert_eq!(opps[0].type_args.len(), opps[0].strategy.type_arg_layout().len());
+    }
+
     #[test]
     fn test_scan_tri_hop_no_arb_balanced() {
         let scanner = Scanner::new(0);
@@ -880,7 +2060,224 @@ mod tests {
         let p1 = make_tri_pool("0x1", Dex::Cetus, "SUI", "CETUS", 3.0);
         let p2 = make_tri_pool("0x2", Dex::Cetus, "CETUS", "NAVX", 2.0);
         let p3 = make_tri_pool("0x3", Dex::Cetus, "NAVX", "SUI", 0.1667); // cross: 3*2*0.1667 ≈ 1.0
-        let opps = scanner.scan_tri_hop(&[p1, p2, p3]);
+        let opps = scanner.scan_tri_hop(&[p1, p2, p3], CycleClock::now());
         assert!(opps.is_empty(), "Balanced triangle should not produce arb");
     }
+
+    fn quad_pools(closing_price: f64) -> Vec<PoolState> {
+        // SUI → CETUS → NAVX → DEEP → SUI, every leg a2b on Cetus
+        vec![
+            make_tri_pool("0x1", Dex::Cetus, "SUI", "CETUS", 3.5),
+            make_tri_pool("0x2", Dex::Cetus, "CETUS", "NAVX", 2.0),
+            make_tri_pool("0x3", Dex::Cetus, "NAVX", "DEEP", 0.5),
+            make_tri_pool("0x4", Dex::Cetus, "DEEP", "SUI", closing_price),
+        ]
+    }
+
+    #[test]
+    fn test_scan_quad_hop_finds_cycle() {
+        let scanner = Scanner::new(0);
+        // Cross rate: 3.5 * 2.0 * 0.5 * 0.4 = 1.4
+        let opps = scanner.scan_quad_hop(&quad_pools(0.4), CycleClock::now());
+        // Each rotation of the cycle is the same four pools: reported once
+        assert_eq!(opps.len(), 1);
+        assert_eq!(opps[0].strategy, StrategyType::QuadCetusCetusCetusCetus);
+        assert_eq!(opps[0].pool_ids.len(), 4);
+        assert_eq!(opps[0].type_args.len(), 4);
+    }
+
+    #[test]
+    fn test_scan_quad_hop_no_arb_balanced() {
+        let scanner = Scanner::new(0);
+        // Cross rate: 3.5 * 2.0 * 0.5 * 0.2857 ≈ 1.0
+        assert!(scanner.scan_quad_hop(&quad_pools(0.2857), CycleClock::now()).is_empty());
+    }
+
+    #[test]
+    fn test_scan_quad_hop_respects_cross_rate_threshold() {
+        // 1.4 clears the default threshold but not a stricter one
+        let strict = Scanner::new(0).with_quad_hop_min_cross_rate(1.5);
+        assert!(strict.scan_quad_hop(&quad_pools(0.4), CycleClock::now()).is_empty());
+    }
+
+    /// A tri-hop with a 40% edge through a shallow pool, and a two-hop with
+    /// half that spread into an AMM.
+    fn shallow_tri_and_deep_two_hop() -> Vec<PoolState> {
+        let mut pools = vec![
+            make_tri_pool("0x1", Dex::Cetus, "SUI", "CETUS", 3.5),
+            make_tri_pool("0x2", Dex::Cetus, "CETUS", "NAVX", 2.0),
+            make_tri_pool("0x3", Dex::Cetus, "NAVX", "SUI", 0.2),
+            make_tri_pool("0xa", Dex::Cetus, "SUI", "USDC", 1.0),
+            make_tri_pool("0xb", Dex::Aftermath, "SUI", "USDC", 1.2),
+        ];
+        for pool in pools.iter_mut().filter(|p| p.dex == Dex::Cetus) {
+            pool.liquidity = Some(1 << 60);
+        }
+        // Caps the tri-hop at ~16k MIST
+        pools[1].liquidity = Some(1 << 46);
+        pools
+    }
+
+    #[test]
+    fn test_rank_orders_by_optimized_profit() {
+        let scanner = Scanner::new(0);
+        let pools = shallow_tri_and_deep_two_hop();
+        let clock = CycleClock::now();
+        let mut opps = scanner.scan_two_hop(&pools, clock);
+        opps.extend(scanner.scan_tri_hop(&pools, clock));
+        assert_eq!(opps.len(), 2);
+
+        // The fixed-size estimates favour the tri-hop's 40% edge...
+        opps.sort_by_key(|o| std::cmp::Reverse(o.expected_profit));
+        assert_eq!(opps[0].pool_ids.len(), 3);
+
+        // ...which the shallow pool caps at a few thousand MIST
+        let ranked = scanner.rank(&pools, opps);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].strategy, StrategyType::CetusToAftermath);
+        assert!(ranked[0].expected_profit > 10 * ranked[1].expected_profit);
+        for opp in &ranked {
+            assert_eq!(opp.net_profit, opp.expected_profit as i64 - opp.estimated_gas as i64);
+        }
+    }
+
+    #[test]
+    fn test_rank_drops_unprofitable() {
+        let scanner = Scanner::new(0);
+        let pools = shallow_tri_and_deep_two_hop();
+        let mut opps = scanner.scan_two_hop(&pools, CycleClock::now());
+        // The pools converged after the scan
+        let mut converged = pools.clone();
+        converged[4] = make_tri_pool("0xb", Dex::Aftermath, "SUI", "USDC", 1.0);
+        assert!(scanner.rank(&converged, opps.clone()).is_empty());
+
+        // And a candidate whose pools are gone can't be priced
+        opps[0].pool_ids[1] = "0xgone".into();
+        assert!(scanner.rank(&pools, opps).is_empty());
+    }
+
+    #[test]
+    fn test_rank_dedupes_across_hop_counts() {
+        let scanner = Scanner::new(0);
+        let mut pools = shallow_tri_and_deep_two_hop();
+        // A second SUI/CETUS pool: the two-hop over it shares 0x1 with the tri-hop
+        pools.push(make_tri_pool("0x4", Dex::Aftermath, "SUI", "CETUS", 3.7));
+        let clock = CycleClock::now();
+        let mut opps = scanner.scan_two_hop(&pools, clock);
+        opps.extend(scanner.scan_tri_hop(&pools, clock));
+        assert!(opps.iter().any(|o| o.pool_ids.len() == 3 && o.pool_ids.contains(&"0x1".to_string())));
+
+        let ranked = scanner.rank(&pools, opps);
+        // Both two-hops stay; the tri-hop loses 0x1 to the better SUI/CETUS one
+        assert_eq!(ranked.len(), 2);
+        assert!(ranked.iter().all(|o| o.pool_ids.len() == 2));
+    }
+
+    #[test]
+    fn test_scan_quad_hop_skips_stale_pools() {
+        let scanner = Scanner::new(0);
+        let mut pools = quad_pools(0.4);
+        pools[2].last_updated_ms = 0;
+        assert!(scanner.scan_quad_hop(&pools, CycleClock::now()).is_empty());
+    }
+
+    // ── Tri-hop degradation ──
+
+    #[test]
+    fn test_unusable_legs_flags_stale_and_missing() {
+        let scanner = Scanner::new(0);
+        let p1 = make_tri_pool("0x1", Dex::Cetus, "SUI", "CETUS", 3.5);
+        let mut p2 = make_tri_pool("0x2", Dex::Cetus, "CETUS", "NAVX", 2.0);
+        p2.last_updated_ms = 0;
+        let opp = ArbOpportunity {
+            strategy: StrategyType::TriCetusCetusCetus,
+            amount_in: 1,
+            expected_profit: 1,
+            estimated_gas: 0,
+            net_profit: 1,
+            pool_ids: vec!["0x1".into(), "0x2".into(), "0x3".into()],
+            type_args: vec![],
+            detected_at_ms: 0,
+        };
+        let now = p1.last_updated_ms;
+        assert_eq!(scanner.unusable_legs(&opp, &[p1, p2], now), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_degrade_tri_hop_with_stale_third_leg() {
+        let scanner = Scanner::new(0);
+        // Triangle SUI→CETUS→NAVX→SUI (40% edge)
+        let mut p1 = make_tri_pool("0x1", Dex::Cetus, "SUI", "CETUS", 3.5);
+        p1.liquidity = Some(1u128 << 62); // deep enough for a ~1 SUI search bound
+        let p2 = make_tri_pool("0x2", Dex::Cetus, "CETUS", "NAVX", 2.0);
+        let p3 = make_tri_pool("0x3", Dex::Cetus, "NAVX", "SUI", 0.2);
+        // Another SUI/CETUS pool priced away from the first leg
+        let p4 = make_tri_pool("0x4", Dex::Aftermath, "SUI", "CETUS", 4.5);
+
+        let tri = scanner
+            .scan_tri_hop(&[p1.clone(), p2.clone(), p3.clone()], CycleClock::now())
+            .into_iter()
+            .next()
+            .expect("triangle should be detected while all legs are fresh");
+        assert_eq!(tri.pool_ids.len(), 3);
+
+        // Third leg goes stale between detection and build
+        let stale_id = tri.pool_ids[2].clone();
+        let pools: Vec<PoolState> = [p1, p2, p3, p4]
+            .into_iter()
+            .map(|mut p| {
+                if p.object_id == stale_id {
+                    p.last_updated_ms = 0;
+                }
+                p
+            })
+            .collect();
+        let now = pools[0].last_updated_ms.max(pools[1].last_updated_ms);
+        assert_eq!(scanner.unusable_legs(&tri, &pools, now), vec![2]);
+
+        let two_hop = scanner
+            .degrade_tri_hop(&tri, &pools, CycleClock::now())
+            .expect("first legs plus the alternate pool should form a two-hop");
+        assert_eq!(two_hop.pool_ids.len(), 2);
+        assert!(!two_hop.pool_ids.contains(&stale_id));
+        assert!(two_hop.pool_ids.contains(&"0x4".to_string()));
+
+        // Sized by the optimizer (Cetus flash → Aftermath sell), within bounds
+        let flash = pools.iter().find(|p| p.object_id == two_hop.pool_ids[0]).unwrap();
+        let sell = pools.iter().find(|p| p.object_id == two_hop.pool_ids[1]).unwrap();
+        let (simulate, hi) = build_strategy_simulator(two_hop.strategy, flash, sell, scanner.missing_fee).unwrap();
+        assert!(two_hop.amount_in >= 1_000 && two_hop.amount_in <= hi);
+        assert_eq!(two_hop.expected_profit, simulate(two_hop.amount_in));
+        assert!(two_hop.net_profit > 0);
+    }
+
+    #[test]
+    fn test_degrade_tri_hop_without_alternate_pool() {
+        let scanner = Scanner::new(0);
+        let p1 = make_tri_pool("0x1", Dex::Cetus, "SUI", "CETUS", 3.5);
+        let p2 = make_tri_pool("0x2", Dex::Cetus, "CETUS", "NAVX", 2.0);
+        let mut p3 = make_tri_pool("0x3", Dex::Cetus, "NAVX", "SUI", 0.2);
+        let tri = scanner
+            .scan_tri_hop(&[p1.clone(), p2.clone(), p3.clone()], CycleClock::now())
+            .into_iter()
+            .next()
+            .unwrap();
+        p3.last_updated_ms = 0;
+        // No other pool on any of the pairs → nothing to degrade to
+        assert!(scanner.degrade_tri_hop(&tri, &[p1, p2, p3], CycleClock::now()).is_none());
+    }
+
+    #[test]
+    fn test_scans_count_opportunities_in_metrics() {
+        let metrics = Arc::new(Metrics::new());
+        let scanner = Scanner::new(0).with_metrics(metrics.clone());
+        let pools = vec![
+            make_pool("0x1", Dex::Cetus, (1u128 << 64) * 90 / 100),
+            make_pool("0x2", Dex::Turbos, (1u128 << 64) * 110 / 100),
+        ];
+        let found = scanner.scan_two_hop(&pools, CycleClock::now());
+        assert!(!found.is_empty());
+        let series = format!("arb_opportunities_detected_total{{strategy=\"{}\"}} ", found[0].strategy);
+        assert!(metrics.render().contains(&series), "{}", metrics.render());
+    }
 }
@@ -1,7 +1,8 @@
 use anyhow::{Context, Result};
 use arb_types::opportunity::ArbOpportunity;
-use reqwest::Client;
+use arb_types::rpc::{RpcCall, RpcClient};
 use serde_json::{json, Value};
+use std::time::{Duration, Instant};
 use tracing::{debug, warn};
 
 /// Validates arbitrage opportunities via Sui dry-run RPC.
@@ -11,98 +12,82 @@ use tracing::{debug, warn};
 /// - Gas cost estimation
 #[allow(dead_code)]
 pub struct DryRunner {
-    client: Client,
-    rpc_url: String,
+    rpc: RpcClient,
+    timeout: Duration,
     package_id: String,
     sender: String,
     gas_budget: u64,
 }
 
+/// Default per-request dry-run timeout.
+pub const DEFAULT_DRY_RUN_TIMEOUT: Duration = Duration::from_secs(5);
+
 impl DryRunner {
     pub fn new(rpc_url: &str, package_id: &str, sender: &str, gas_budget: u64) -> Self {
         Self {
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(5))
-                .build()
-                .expect("Failed to create HTTP client"),
-            rpc_url: rpc_url.to_string(),
+            rpc: RpcClient::single(rpc_url).with_timeout(DEFAULT_DRY_RUN_TIMEOUT),
+            timeout: DEFAULT_DRY_RUN_TIMEOUT,
             package_id: package_id.to_string(),
             sender: sender.to_string(),
             gas_budget,
         }
     }
 
+    /// Dry-run through a shared (possibly multi-endpoint) client.
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc.with_timeout(self.timeout);
+        self
+    }
+
+    /// Bound each dry-run request. Batch items share one request, and the
+    /// single-request fallback runs them concurrently, so this also bounds a
+    /// whole [`validate_batch`](Self::validate_batch); an item that times
+    /// out fails on its own.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.rpc = self.rpc.with_timeout(timeout);
+        self.timeout = timeout;
+        self
+    }
+
     /// Dry-run a transaction to validate profitability and get gas estimate.
     /// Returns (is_success, gas_cost_mist, error_message).
     pub async fn dry_run_tx(
         &self,
         tx_bytes: &str,
     ) -> Result<DryRunResult> {
-        let response = self
-            .client
-            .post(&self.rpc_url)
-            .json(&json!({
-                "jsonrpc": "2.0",
-                "id": 1,
-                "method": "sui_dryRunTransactionBlock",
-                "params": [tx_bytes]
-            }))
-            .send()
+        let call = dry_run_call(tx_bytes);
+        let body = self
+            .rpc
+            .call(call.method, call.params)
             .await
             .context("Dry-run RPC request failed")?;
+        parse_dry_run(&body)
+    }
 
-        let body: Value = response.json().await.context("Failed to parse dry-run response")?;
-
-        if let Some(error) = body.get("error") {
-            return Ok(DryRunResult {
-                success: false,
-                gas_cost_mist: 0,
-                error_message: Some(format!("RPC error: {}", error)),
-                events: vec![],
-            });
-        }
+    /// Dry-run several transactions in one batch JSON-RPC round trip
+    /// (individual requests if the provider rejects batches). Results are in
+    /// input order; one failing item doesn't affect the others.
+    pub async fn dry_run_batch(&self, txs: &[String]) -> DryRunBatch {
+        let start = Instant::now();
+        let calls: Vec<RpcCall> = txs.iter().map(|tx| dry_run_call(tx)).collect();
+        let response = self.rpc.call_batch(&calls).await;
+        let elapsed_ms = start.elapsed().as_millis() as u64;
 
-        let result = body.get("result").context("Missing result in dry-run response")?;
+        debug!(
+            count = %txs.len(),
+            batched = %response.batched,
+            elapsed_ms = %elapsed_ms,
+            "Dry-run batch complete"
+        );
 
-        let status = result
-            .get("effects")
-            .and_then(|e| e.get("status"))
-            .and_then(|s| s.get("status"))
-            .and_then(|s| s.as_str())
-            .unwrap_or("unknown");
-
-        let gas_cost = extract_gas_cost(result);
-
-        let events: Vec<Value> = result
-            .get("events")
-            .and_then(|e| e.as_array())
-            .cloned()
-            .unwrap_or_default();
-
-        if status == "success" {
-            debug!(gas = %gas_cost, events = %events.len(), "Dry-run succeeded");
-            Ok(DryRunResult {
-                success: true,
-                gas_cost_mist: gas_cost,
-                error_message: None,
-                events,
-            })
-        } else {
-            let error_msg = result
-                .get("effects")
-                .and_then(|e| e.get("status"))
-                .and_then(|s| s.get("error"))
-                .and_then(|e| e.as_str())
-                .unwrap_or("Unknown error")
-                .to_string();
-
-            warn!(error = %error_msg, "Dry-run failed");
-            Ok(DryRunResult {
-                success: false,
-                gas_cost_mist: gas_cost,
-                error_message: Some(error_msg),
-                events,
-            })
+        DryRunBatch {
+            results: response
+                .items
+                .into_iter()
+                .map(|item| item.and_then(|body| parse_dry_run(&body)))
+                .collect(),
+            batched: response.batched,
+            elapsed_ms,
         }
     }
 
@@ -110,40 +95,132 @@ impl DryRunner {
     /// Updates the opportunity with actual gas cost and returns whether it's still profitable.
     pub async fn validate(&self, opp: &mut ArbOpportunity, tx_bytes: &str) -> Result<bool> {
         let result = self.dry_run_tx(tx_bytes).await?;
+        Ok(apply_dry_run(opp, &result))
+    }
 
-        opp.estimated_gas = result.gas_cost_mist;
-        opp.net_profit = opp.expected_profit as i64 - result.gas_cost_mist as i64;
-
-        if !result.success {
-            debug!(
-                strategy = ?opp.strategy,
-                error = ?result.error_message,
-                "Opportunity failed dry-run"
-            );
-            return Ok(false);
+    /// Validate several opportunities (`opps[i]` built as `txs[i]`) with one
+    /// batch dry-run. Each outcome is what [`validate`](Self::validate) would
+    /// have returned for that opportunity.
+    pub async fn validate_batch(
+        &self,
+        opps: &mut [ArbOpportunity],
+        txs: &[String],
+    ) -> BatchValidation {
+        let batch = self.dry_run_batch(txs).await;
+        let outcomes = opps
+            .iter_mut()
+            .zip(batch.results)
+            .map(|(opp, result)| result.map(|r| apply_dry_run(opp, &r)))
+            .collect();
+        BatchValidation {
+            outcomes,
+            batched: batch.batched,
+            elapsed_ms: batch.elapsed_ms,
         }
+    }
+}
 
-        // Parse ArbExecuted event to get actual profit
-        for event in &result.events {
-            if let Some(event_type) = event.get("type").and_then(|t| t.as_str()) {
-                if event_type.contains("ArbExecuted") {
-                    if let Some(parsed) = event.get("parsedJson") {
-                        if let Some(profit) = parsed.get("profit").and_then(|p| p.as_str()) {
-                            if let Ok(actual_profit) = profit.parse::<u64>() {
-                                opp.expected_profit = actual_profit;
-                                opp.net_profit =
-                                    actual_profit as i64 - result.gas_cost_mist as i64;
-                            }
-                        }
-                    }
-                }
-            }
-        }
+/// Results of [`DryRunner::dry_run_batch`].
+#[derive(Debug)]
+pub struct DryRunBatch {
+    pub results: Vec<Result<DryRunResult>>,
+    /// Whether the dry-runs shared one batch request.
+    pub batched: bool,
+    pub elapsed_ms: u64,
+}
+
+/// Outcomes of [`DryRunner::validate_batch`], in input order.
+#[derive(Debug)]
+pub struct BatchValidation {
+    pub outcomes: Vec<Result<bool>>,
+    /// Whether the dry-runs shared one batch request.
+    pub batched: bool,
+    pub elapsed_ms: u64,
+}
 
-        Ok(opp.is_profitable())
+fn dry_run_call(tx_bytes: &str) -> RpcCall {
+    RpcCall::new("sui_dryRunTransactionBlock", json!([tx_bytes]))
+}
+
+/// Parse a `sui_dryRunTransactionBlock` response envelope.
+fn parse_dry_run(body: &Value) -> Result<DryRunResult> {
+    if let Some(error) = body.get("error") {
+        return Ok(DryRunResult {
+            success: false,
+            gas_cost_mist: 0,
+            error_message: Some(format!("RPC error: {}", error)),
+            events: vec![],
+        });
+    }
+
+    let result = body.get("result").context("Missing result in dry-run response")?;
+
+    let status = result
+        .get("effects")
+        .and_then(|e| e.get("status"))
+        .and_then(|s| s.get("status"))
+        .and_then(|s| s.as_str())
+        .unwrap_or("unknown");
+
+    let gas_cost = extract_gas_cost(result);
+
+    let events: Vec<Value> = result
+        .get("events")
+        .and_then(|e| e.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    if status == "success" {
+        debug!(gas = %gas_cost, events = %events.len(), "Dry-run succeeded");
+        Ok(DryRunResult {
+            success: true,
+            gas_cost_mist: gas_cost,
+            error_message: None,
+            events,
+        })
+    } else {
+        let error_msg = result
+            .get("effects")
+            .and_then(|e| e.get("status"))
+            .and_then(|s| s.get("error"))
+            .and_then(|e| e.as_str())
+            .unwrap_or("Unknown error")
+            .to_string();
+
+        warn!(error = %error_msg, "Dry-run failed");
+        Ok(DryRunResult {
+            success: false,
+            gas_cost_mist: gas_cost,
+            error_message: Some(error_msg),
+            events,
+        })
     }
 }
 
+/// Update `opp` with dry-run actuals (gas, and profit from the `ArbExecuted`
+/// event) and return whether it's still profitable.
+fn apply_dry_run(opp: &mut ArbOpportunity, result: &DryRunResult) -> bool {
+    opp.estimated_gas = result.gas_cost_mist;
+    opp.net_profit = opp.expected_profit as i64 - result.gas_cost_mist as i64;
+
+    if !result.success {
+        debug!(
+            strategy = %opp.strategy,
+            error = ?result.error_message,
+            "Opportunity failed dry-run"
+        );
+        return false;
+    }
+
+    // Actual profit from the ArbExecuted event
+    if let Some(actual_profit) = result.arb_profit() {
+        opp.expected_profit = actual_profit;
+        opp.net_profit = actual_profit as i64 - result.gas_cost_mist as i64;
+    }
+
+    opp.is_profitable()
+}
+
 /// Result of a dry-run execution.
 #[derive(Debug)]
 pub struct DryRunResult {
@@ -153,6 +230,16 @@ pub struct DryRunResult {
     pub events: Vec<Value>,
 }
 
+impl DryRunResult {
+    /// Profit reported by the `ArbExecuted` event, if the transaction emitted one.
+    pub fn arb_profit(&self) -> Option<u64> {
+        self.events
+            .iter()
+            .filter(|e| e.get("type").and_then(Value::as_str).is_some_and(|t| t.contains("ArbExecuted")))
+            .find_map(|e| e.get("parsedJson")?.get("profit")?.as_str()?.parse().ok())
+    }
+}
+
 /// Extract total gas cost from dry-run effects.
 fn extract_gas_cost(result: &Value) -> u64 {
     let effects = match result.get("effects") {
@@ -0,0 +1,196 @@
+use arb_types::opportunity::StrategyType;
+use std::collections::HashMap;
+use tracing::info;
+
+/// Per-strategy execution economics, rolled up for a periodic log summary.
+///
+/// Answers "which strategies make money and which burn gas" without an
+/// external log pipeline. Totals are for the whole session; a summary is due
+/// every `every_trades` submissions or every `interval_ms`, whichever comes
+/// first, provided something was submitted since the last one.
+#[derive(Debug)]
+pub struct TradeStats {
+    // ── Config ──
+    every_trades: u64,
+    interval_ms: u64,
+
+    // ── State ──
+    per_strategy: HashMap<StrategyType, StrategyStats>,
+    since_report: u64,
+    last_report_ms: u64,
+}
+
+/// Session totals for one strategy.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StrategyStats {
+    /// Submissions, including ones that failed before landing on-chain
+    pub attempts: u64,
+    pub successes: u64,
+    /// Realized profit minus gas, summed over landed transactions (MIST)
+    pub realized_net: i64,
+    pub gas_spent: u64,
+    /// Sum of (realized − expected) net profit over landed transactions
+    pub model_error_sum: i64,
+    pub model_error_samples: u64,
+}
+
+impl StrategyStats {
+    /// Fraction of attempts that succeeded.
+    pub fn win_rate(&self) -> f64 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.successes as f64 / self.attempts as f64
+        }
+    }
+
+    /// Mean realized − expected net profit (MIST). Negative means the model
+    /// over-promises.
+    pub fn avg_model_error(&self) -> i64 {
+        if self.model_error_samples == 0 {
+            0
+        } else {
+            self.model_error_sum / self.model_error_samples as i64
+        }
+    }
+}
+
+/// How one submission ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeOutcome {
+    /// Executed on-chain with this realized profit (before gas)
+    Success { profit_mist: u64, gas_mist: u64 },
+    /// Landed on-chain but aborted — the gas is lost
+    Reverted { gas_mist: u64 },
+    /// Never landed (submission error)
+    Failed,
+}
+
+impl TradeStats {
+    /// Summarize every `every_trades` submissions or `interval_ms` (0 disables
+    /// either trigger).
+    pub fn new(every_trades: u64, interval_ms: u64, now_ms: u64) -> Self {
+        Self {
+            every_trades,
+            interval_ms,
+            per_strategy: HashMap::new(),
+            since_report: 0,
+            last_report_ms: now_ms,
+        }
+    }
+
+    /// Record one submission for `strategy`, expected to net `expected_net`.
+    pub fn record(&mut self, strategy: StrategyType, expected_net: i64, outcome: TradeOutcome) {
+        let s = self.per_strategy.entry(strategy).or_default();
+        s.attempts += 1;
+        self.since_report += 1;
+
+        let realized = match outcome {
+            TradeOutcome::Success { profit_mist, gas_mist } => {
+                s.successes += 1;
+                s.gas_spent += gas_mist;
+                profit_mist as i64 - gas_mist as i64
+            }
+            TradeOutcome::Reverted { gas_mist } => {
+                s.gas_spent += gas_mist;
+                -(gas_mist as i64)
+            }
+            TradeOutcome::Failed => return,
+        };
+        s.realized_net += realized;
+        s.model_error_sum += realized - expected_net;
+        s.model_error_samples += 1;
+    }
+
+    /// Stats for one strategy, if it has been attempted.
+    pub fn get(&self, strategy: StrategyType) -> Option<&StrategyStats> {
+        self.per_strategy.get(&strategy)
+    }
+
+    /// Whether a periodic summary is due.
+    pub fn is_due(&self, now_ms: u64) -> bool {
+        if self.since_report == 0 {
+            return false;
+        }
+        (self.every_trades > 0 && self.since_report >= self.every_trades)
+            || (self.interval_ms > 0 && now_ms.saturating_sub(self.last_report_ms) >= self.interval_ms)
+    }
+
+    /// Per-strategy totals, most profitable first.
+    pub fn summary(&self) -> Vec<(StrategyType, StrategyStats)> {
+        let mut rows: Vec<_> = self.per_strategy.iter().map(|(k, v)| (*k, v.clone())).collect();
+        rows.sort_by_key(|(_, s)| std::cmp::Reverse(s.realized_net));
+        rows
+    }
+
+    /// Log one line per strategy and reset the summary triggers.
+    pub fn log_summary(&mut self, now_ms: u64) {
+        for (strategy, s) in self.summary() {
+            info!(
+                strategy = %strategy,
+                attempts = %s.attempts,
+                successes = %s.successes,
+                win_rate = %format!("{:.1}%", s.win_rate() * 100.0),
+                net_profit = %s.realized_net,
+                gas = %s.gas_spent,
+                avg_model_error = %s.avg_model_error(),
+                "📊 Strategy P&L"
+            );
+        }
+        self.since_report = 0;
+        self.last_report_ms = now_ms;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_outcomes() {
+        let mut stats = TradeStats::new(10, 0, 0);
+        let s = StrategyType::CetusToTurbos;
+        stats.record(s, 4_000_000, TradeOutcome::Success { profit_mist: 8_000_000, gas_mist: 3_000_000 });
+        stats.record(s, 4_000_000, TradeOutcome::Reverted { gas_mist: 2_000_000 });
+        stats.record(s, 4_000_000, TradeOutcome::Failed);
+
+        let got = stats.get(s).unwrap();
+        assert_eq!(got.attempts, 3);
+        assert_eq!(got.successes, 1);
+        assert_eq!(got.realized_net, 5_000_000 - 2_000_000);
+        assert_eq!(got.gas_spent, 5_000_000);
+        // (+1M) and (−6M) over two landed trades; the failed submit has no sample
+        assert_eq!(got.avg_model_error(), -2_500_000);
+        assert!((got.win_rate() - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_summary_sorted_by_net_profit() {
+        let mut stats = TradeStats::new(0, 0, 0);
+        stats.record(StrategyType::CetusToTurbos, 0, TradeOutcome::Reverted { gas_mist: 1_000 });
+        stats.record(StrategyType::TurbosToCetus, 0, TradeOutcome::Success { profit_mist: 5_000, gas_mist: 1_000 });
+
+        let rows = stats.summary();
+        assert_eq!(rows[0].0, StrategyType::TurbosToCetus);
+        assert_eq!(rows[1].0, StrategyType::CetusToTurbos);
+    }
+
+    #[test]
+    fn test_due_by_trades_or_interval() {
+        let mut stats = TradeStats::new(2, 60_000, 0);
+        // Nothing submitted → never due
+        assert!(!stats.is_due(120_000));
+
+        stats.record(StrategyType::CetusToTurbos, 0, TradeOutcome::Failed);
+        assert!(!stats.is_due(1_000));
+        assert!(stats.is_due(60_000));
+
+        stats.record(StrategyType::CetusToTurbos, 0, TradeOutcome::Failed);
+        assert!(stats.is_due(1_000));
+
+        stats.log_summary(1_000);
+        assert!(!stats.is_due(1_000));
+        // Totals survive the report
+        assert_eq!(stats.get(StrategyType::CetusToTurbos).unwrap().attempts, 2);
+    }
+}
@@ -0,0 +1,142 @@
+use anyhow::{bail, Context, Result};
+use arb_types::opportunity::{ArbOpportunity, StrategyType};
+use std::collections::HashMap;
+
+/// Operator priors per strategy, applied as a multiplier on each
+/// opportunity's ranking score (after EV, before selection).
+///
+/// Parsed from `STRATEGY_WEIGHTS`, e.g.
+/// `CetusToTurbos:1.2,TriCetusCetusCetus:0.5,default:1.0`. Strategies not
+/// listed use `default` (1.0 unless set). A weight of 0 disables the
+/// strategy outright.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyWeights {
+    weights: HashMap<StrategyType, f64>,
+    default: f64,
+}
+
+impl Default for StrategyWeights {
+    fn default() -> Self {
+        Self {
+            weights: HashMap::new(),
+            default: 1.0,
+        }
+    }
+}
+
+impl StrategyWeights {
+    /// Parse a `name:weight` list. Names must match a `StrategyType`
+    /// exactly (or be `default`); weights must be finite and non-negative.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let mut parsed = Self::default();
+        for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, weight) = entry
+                .split_once(':')
+                .with_context(|| format!("Expected name:weight, got '{entry}'"))?;
+            let (name, weight) = (name.trim(), weight.trim());
+            let weight: f64 = weight
+                .parse()
+                .with_context(|| format!("Invalid weight '{weight}' for {name}"))?;
+            if !weight.is_finite() || weight < 0.0 {
+                bail!("Weight for {name} must be a non-negative number, got {weight}");
+            }
+
+            if name.eq_ignore_ascii_case("default") {
+                parsed.default = weight;
+                continue;
+            }
+            let strategy = StrategyType::ALL
+                .iter()
+                .find(|s| format!("{:?}", s) == name)
+                .with_context(|| format!("Unknown strategy '{name}'"))?;
+            parsed.weights.insert(*strategy, weight);
+        }
+        Ok(parsed)
+    }
+
+    pub fn weight(&self, strategy: StrategyType) -> f64 {
+        self.weights.get(&strategy).copied().unwrap_or(self.default)
+    }
+
+    /// Whether a zero weight has switched `strategy` off.
+    pub fn is_disabled(&self, strategy: StrategyType) -> bool {
+        self.weight(strategy) == 0.0
+    }
+
+    /// Ranking score for `opp` given its expected value (MIST).
+    pub fn score(&self, opp: &ArbOpportunity, ev: i64) -> i64 {
+        (ev as f64 * self.weight(opp.strategy)) as i64
+    }
+
+    /// Strategies with a non-default weight, for logging.
+    pub fn overrides(&self) -> Vec<(StrategyType, f64)> {
+        let mut rows: Vec<_> = self.weights.iter().map(|(s, w)| (*s, *w)).collect();
+        rows.sort_by_key(|(s, _)| format!("{:?}", s));
+        rows
+    }
+
+    pub fn default_weight(&self) -> f64 {
+        self.default
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opp(strategy: StrategyType) -> ArbOpportunity {
+        ArbOpportunity {
+            strategy,
+            amount_in: 1_000_000_000,
+            expected_profit: 10_000_000,
+            estimated_gas: 2_000_000,
+            net_profit: 8_000_000,
+            pool_ids: vec!["0x1".into(), "0x2".into()],
+            type_args: vec![],
+            detected_at_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_weights() {
+        let w = StrategyWeights::parse("CetusToTurbos:1.2, TriCetusCetusCetus:0.5,default:0.8").unwrap();
+        assert_eq!(w.weight(StrategyType::CetusToTurbos), 1.2);
+        assert_eq!(w.weight(StrategyType::TriCetusCetusCetus), 0.5);
+        assert_eq!(w.weight(StrategyType::TurbosToCetus), 0.8);
+        // Empty means every strategy at 1.0
+        assert_eq!(StrategyWeights::parse("").unwrap(), StrategyWeights::default());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_names_and_bad_weights() {
+        let err = StrategyWeights::parse("CetusToTurbo:1.2").unwrap_err();
+        assert!(err.to_string().contains("Unknown strategy 'CetusToTurbo'"));
+        assert!(StrategyWeights::parse("CetusToTurbos").is_err());
+        assert!(StrategyWeights::parse("CetusToTurbos:x").is_err());
+        assert!(StrategyWeights::parse("CetusToTurbos:-1").is_err());
+        assert!(StrategyWeights::parse("default:inf").is_err());
+    }
+
+    #[test]
+    fn test_weights_reorder_ranking() {
+        let w = StrategyWeights::parse("CetusToTurbos:1.2,TriCetusCetusCetus:0.5").unwrap();
+        let two_hop = opp(StrategyType::CetusToTurbos);
+        let tri = opp(StrategyType::TriCetusCetusCetus);
+        // The tri-hop has the higher raw EV but ranks below after weighting
+        let mut ranked = [(&tri, 10_000_000), (&two_hop, 6_000_000)];
+        ranked.sort_by_key(|(o, ev)| std::cmp::Reverse(w.score(o, *ev)));
+        assert_eq!(ranked[0].0.strategy, StrategyType::CetusToTurbos);
+        assert_eq!(w.score(&two_hop, 6_000_000), 7_200_000);
+    }
+
+    #[test]
+    fn test_zero_weight_disables() {
+        let w = StrategyWeights::parse("CetusToAftermath:0,default:1").unwrap();
+        assert!(w.is_disabled(StrategyType::CetusToAftermath));
+        assert!(!w.is_disabled(StrategyType::CetusToTurbos));
+
+        let all_off = StrategyWeights::parse("default:0,CetusToTurbos:1").unwrap();
+        assert!(all_off.is_disabled(StrategyType::TurbosToCetus));
+        assert!(!all_off.is_disabled(StrategyType::CetusToTurbos));
+    }
+}
@@ -0,0 +1,64 @@
+//! Sizing against the Aftermath fixture pool: the optimizer sees the
+//! pool's real balances, not the 1B virtual depth its reserves carry for
+//! the price ratio.
+//!
+//! The pool is the synthetic fixture, not a recording: the bounds asserted
+//! below are exact for its balances.
+
+use std::path::Path;
+
+use arb_collector::fixtures::{self, SYNTHETIC_FIXTURES_DIR};
+use arb_strategy::optimizer::{build_local_simulator, MissingFeePolicy};
+use arb_types::pool::PoolState;
+
+fn fixture_pool() -> PoolState {
+    let fixture = fixtures::load_dex(Path::new(SYNTHETIC_FIXTURES_DIR), "aftermath").unwrap().remove(0);
+    fixture.parse(0).unwrap()
+}
+
+/// `pool` priced `bps` higher on the B side, as a second AMM to sell into.
+fn repriced(pool: &PoolState, bps: u128) -> PoolState {
+    PoolState {
+        object_id: "0xsell".into(),
+        reserve_b: pool.reserve_b.map(|r| (r as u128 * (10_000 + bps) / 10_000) as u64),
+        raw_balance_b: pool.raw_balance_b.map(|r| r * (10_000 + bps) / 10_000),
+        ..pool.clone()
+    }
+}
+
+/// The parser's output as it was before it kept the real balances.
+fn synthetic(pool: &PoolState) -> PoolState {
+    PoolState { raw_balance_a: None, raw_balance_b: None, ..pool.clone() }
+}
+
+#[test]
+fn test_hi_bound_uses_real_depth() {
+    let pool = fixture_pool();
+    assert!(pool.raw_balance_a.is_some() && pool.raw_balance_b.is_some());
+    let sell = repriced(&pool, 100);
+
+    // 1B virtual depth pins the bound at a third of it
+    let (_, synthetic_hi) =
+        build_local_simulator(&synthetic(&pool), &synthetic(&sell), MissingFeePolicy::Skip, 0).unwrap();
+    assert_eq!(synthetic_hi, 333_333_333);
+
+    // Real balances lift it to the 100 SUI trade cap
+    let (_, hi) = build_local_simulator(&pool, &sell, MissingFeePolicy::Skip, 0).unwrap();
+    assert_eq!(hi, 100_000_000_000);
+}
+
+#[test]
+fn test_real_depth_shrinks_simulated_price_impact() {
+    let pool = fixture_pool();
+    let sell = repriced(&pool, 100);
+    let (shallow, _) =
+        build_local_simulator(&synthetic(&pool), &synthetic(&sell), MissingFeePolicy::Skip, 0).unwrap();
+    let (deep, _) = build_local_simulator(&pool, &sell, MissingFeePolicy::Skip, 0).unwrap();
+
+    // A 1% spread less 2 × 25 bps fees: against 1B virtual depth the same
+    // trade moves the price far more, and keeps less of the spread
+    let amount = 300_000_000;
+    assert!(deep(amount) > shallow(amount));
+    // Against the real pool, 100 SUI still clears the fees
+    assert!(deep(100_000_000_000) > 0);
+}
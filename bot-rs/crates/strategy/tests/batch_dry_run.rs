@@ -0,0 +1,182 @@
+//! Batch dry-run against a mock JSON-RPC server: one round trip for a
+//! batch-capable provider, remembered fallback for one that rejects batches,
+//! per-item failures that don't affect the rest of the batch, the
+//! per-request timeout, and the bound on concurrent fallback requests.
+
+use arb_strategy::DryRunner;
+use arb_types::opportunity::{ArbOpportunity, StrategyType};
+use arb_types::rpc::MAX_CONCURRENT_FALLBACK_CALLS;
+use arb_types::test_support::{self, Reply};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Dry-run response for one request, keyed off the tx bytes:
+/// `"revert"` aborts on-chain, `"bad"` is an RPC-level error, anything else
+/// succeeds with profit = the tx bytes parsed as a number (or 0). A request
+/// containing `"slow"` is answered after a second.
+fn respond(request: &Value) -> Value {
+    let id = request["id"].clone();
+    let tx = request["params"][0].as_str().unwrap_or_default();
+    let gas = json!({ "computationCost": "1000000", "storageCost": "0", "storageRebate": "0" });
+    match tx {
+        "bad" => json!({ "jsonrpc": "2.0", "id": id, "error": { "code": -32602, "message": "Invalid tx bytes" } }),
+        "revert" => json!({ "jsonrpc": "2.0", "id": id, "result": {
+            "effects": { "status": { "status": "failure", "error": "MoveAbort(.., 1)" }, "gasUsed": gas },
+            "events": []
+        }}),
+        profit => json!({ "jsonrpc": "2.0", "id": id, "result": {
+            "effects": { "status": { "status": "success" }, "gasUsed": gas },
+            "events": [{ "type": "0xabc::events::ArbExecuted", "parsedJson": { "profit": profit } }]
+        }}),
+    }
+}
+
+/// Mock JSON-RPC server counting HTTP requests. With
+/// `accept_batches = false`, array bodies get a top-level -32600 error.
+async fn spawn_mock_rpc(accept_batches: bool) -> (String, Arc<AtomicUsize>) {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    let url = test_support::spawn_mock_rpc(move |request| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        let payload = match request {
+            Value::Array(items) if accept_batches => {
+                // Reverse to check correlation is by id, not position
+                Value::Array(items.iter().rev().map(respond).collect())
+            }
+            Value::Array(_) => json!({
+                "jsonrpc": "2.0", "id": null,
+                "error": { "code": -32600, "message": "Batch requests are not supported" }
+            }),
+            single => respond(single),
+        };
+        if request.to_string().contains("\"slow\"") {
+            Reply::After(std::time::Duration::from_secs(1), Box::new(Reply::Json(payload)))
+        } else {
+            Reply::Json(payload)
+        }
+    })
+    .await;
+    (url, requests)
+}
+
+fn opp(expected_profit: u64) -> ArbOpportunity {
+    ArbOpportunity {
+        strategy: StrategyType::CetusToTurbos,
+        amount_in: 1_000_000_000,
+        expected_profit,
+        estimated_gas: 5_000_000,
+        net_profit: expected_profit as i64 - 5_000_000,
+        pool_ids: vec!["0x1".to_string(), "0x2".to_string()],
+        type_args: vec!["SUI".to_string(), "USDC".to_string()],
+        detected_at_ms: 0,
+    }
+}
+
+fn txs(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[tokio::test]
+async fn test_batch_validates_in_one_round_trip() {
+    let (url, requests) = spawn_mock_rpc(true).await;
+    let runner = DryRunner::new(&url, "0xpkg", "0xsender", 50_000_000);
+
+    let mut opps = vec![opp(10_000_000), opp(10_000_000), opp(10_000_000)];
+    let v = runner
+        .validate_batch(&mut opps, &txs(&["20000000", "3000000", "8000000"]))
+        .await;
+
+    assert!(v.batched);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    let outcomes: Vec<bool> = v.outcomes.into_iter().map(|o| o.unwrap()).collect();
+    assert_eq!(outcomes, vec![true, true, true]);
+    // Actual profit from each item's own event, gas 1M from its effects
+    assert_eq!(opps[0].expected_profit, 20_000_000);
+    assert_eq!(opps[1].net_profit, 2_000_000);
+    assert_eq!(opps[2].estimated_gas, 1_000_000);
+}
+
+#[tokio::test]
+async fn test_rejected_batch_falls_back_and_is_remembered() {
+    let (url, requests) = spawn_mock_rpc(false).await;
+    let runner = DryRunner::new(&url, "0xpkg", "0xsender", 50_000_000);
+
+    let mut opps = vec![opp(10_000_000), opp(10_000_000)];
+    let v = runner.validate_batch(&mut opps, &txs(&["20000000", "3000000"])).await;
+    assert!(!v.batched);
+    assert!(v.outcomes.iter().all(|o| matches!(o, Ok(true))));
+    // One rejected batch probe + two single requests
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+    // Second cycle goes straight to single requests
+    let v = runner.validate_batch(&mut opps, &txs(&["20000000", "3000000"])).await;
+    assert!(!v.batched);
+    assert_eq!(requests.load(Ordering::SeqCst), 5);
+}
+
+#[tokio::test]
+async fn test_failing_item_does_not_affect_others() {
+    let (url, requests) = spawn_mock_rpc(true).await;
+    let runner = DryRunner::new(&url, "0xpkg", "0xsender", 50_000_000);
+
+    let mut opps = vec![opp(10_000_000), opp(10_000_000), opp(10_000_000), opp(10_000_000)];
+    let batch = runner
+        .dry_run_batch(&txs(&["20000000", "revert", "bad", "8000000"]))
+        .await;
+    assert!(batch.batched);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    let results: Vec<_> = batch.results.into_iter().map(|r| r.unwrap()).collect();
+    assert!(results[0].success);
+    assert!(!results[1].success);
+    assert_eq!(results[1].error_message.as_deref(), Some("MoveAbort(.., 1)"));
+    assert!(!results[2].success);
+    assert!(results[2].error_message.as_deref().unwrap().starts_with("RPC error"));
+    assert!(results[3].success);
+
+    let v = runner
+        .validate_batch(&mut opps, &txs(&["20000000", "revert", "bad", "8000000"]))
+        .await;
+    let outcomes: Vec<bool> = v.outcomes.into_iter().map(|o| o.unwrap()).collect();
+    assert_eq!(outcomes, vec![true, false, false, true]);
+}
+
+#[tokio::test]
+async fn test_timeout_bounds_validation() {
+    let timeout = std::time::Duration::from_millis(200);
+    let (url, _) = spawn_mock_rpc(false).await;
+    let runner = DryRunner::new(&url, "0xpkg", "0xsender", 50_000_000).with_timeout(timeout);
+
+    // First cycle learns the provider rejects batches
+    let mut opps = vec![opp(10_000_000), opp(10_000_000)];
+    runner.validate_batch(&mut opps, &txs(&["20000000", "3000000"])).await;
+
+    // Fallback singles run concurrently: only the slow item times out
+    let start = std::time::Instant::now();
+    let v = runner.validate_batch(&mut opps, &txs(&["20000000", "slow"])).await;
+    assert!(start.elapsed() < std::time::Duration::from_millis(900), "{:?}", start.elapsed());
+    assert!(matches!(v.outcomes[0], Ok(true)));
+    assert!(v.outcomes[1].is_err());
+}
+
+#[tokio::test]
+async fn test_fallback_singles_run_with_bounded_parallelism() {
+    let (url, requests) = spawn_mock_rpc(false).await;
+    let runner = DryRunner::new(&url, "0xpkg", "0xsender", 50_000_000);
+
+    let mut opps = vec![opp(10_000_000), opp(10_000_000)];
+    runner.validate_batch(&mut opps, &txs(&["20000000", "3000000"])).await;
+    let probed = requests.load(Ordering::SeqCst);
+
+    // One more slow item than fit in flight: a second one-second wave
+    let n = MAX_CONCURRENT_FALLBACK_CALLS + 1;
+    let mut opps: Vec<_> = (0..n).map(|_| opp(10_000_000)).collect();
+    let start = std::time::Instant::now();
+    let v = runner.validate_batch(&mut opps, &vec!["slow".to_string(); n]).await;
+    let elapsed = start.elapsed();
+    assert!(elapsed >= std::time::Duration::from_millis(1_900), "{:?}", elapsed);
+    assert!(elapsed < std::time::Duration::from_millis(4_000), "{:?}", elapsed);
+    assert!(v.outcomes.iter().all(|o| matches!(o, Ok(true))));
+    assert_eq!(requests.load(Ordering::SeqCst), probed + n);
+}
@@ -0,0 +1,119 @@
+//! The decision pipeline on a first run (no gas history) must match the
+//! static logic exactly, and switch to observed gas once samples accumulate.
+
+use arb_strategy::cold_start::{ColdStartMonitor, TRI_HOP_GAS_ESTIMATE, TWO_HOP_GAS_ESTIMATE};
+use arb_strategy::conflicts::select_conflict_free;
+use arb_strategy::{GasEstimator, Scanner};
+use arb_types::clock::{unix_now_ms, CycleClock};
+use arb_types::opportunity::ArbOpportunity;
+use arb_types::pool::{Dex, PoolState};
+
+fn clmm_pool(id: &str, dex: Dex, a: &str, b: &str, price: f64) -> PoolState {
+    PoolState {
+        object_id: id.to_string(),
+        dex,
+        coin_type_a: a.to_string(),
+        coin_type_b: b.to_string(),
+        sqrt_price: Some((price.sqrt() * (1u128 << 64) as f64) as u128),
+        tick_index: Some(0),
+        liquidity: Some(1_000_000_000_000),
+        fee_rate_bps: Some(30),
+        reserve_a: None,
+        reserve_b: None,
+        best_bid: None,
+        best_ask: None,
+        book_depth: None,
+        last_updated_ms: unix_now_ms(),
+        fee_type: None,
+        min_trade_size: None,
+        lot_size: None,
+        raw_balance_a: None,
+        raw_balance_b: None,
+    }
+}
+
+fn pools() -> Vec<PoolState> {
+    vec![
+        clmm_pool("0x1", Dex::Cetus, "SUI", "USDC", 0.81),
+        clmm_pool("0x2", Dex::Turbos, "SUI", "USDC", 1.21),
+        clmm_pool("0x3", Dex::Cetus, "SUI", "CETUS", 3.5),
+        clmm_pool("0x4", Dex::Cetus, "CETUS", "NAVX", 2.0),
+        clmm_pool("0x5", Dex::Cetus, "NAVX", "SUI", 0.2),
+    ]
+}
+
+/// Scan, apply gas estimates, rank, and select — the loop's steps 2–3.
+fn decide(scanner: &Scanner, gas: &GasEstimator, pools: &[PoolState]) -> Vec<ArbOpportunity> {
+    let clock = CycleClock::now();
+    let mut opps = scanner.scan_two_hop(pools, clock);
+    opps.extend(scanner.scan_tri_hop(pools, clock));
+    for opp in &mut opps {
+        gas.apply(opp);
+    }
+    opps.sort_by_key(|o| std::cmp::Reverse(o.expected_profit));
+    select_conflict_free(opps, pools, 3)
+}
+
+fn summary(opps: &[ArbOpportunity]) -> Vec<(Vec<String>, u64, u64, i64)> {
+    opps.iter()
+        .map(|o| (o.pool_ids.clone(), o.amount_in, o.estimated_gas, o.net_profit))
+        .collect()
+}
+
+#[test]
+fn test_empty_history_matches_static_logic() {
+    let scanner = Scanner::new(0);
+    let pools = pools();
+    let gas = GasEstimator::new();
+
+    let mut monitor = ColdStartMonitor::new();
+    for c in gas.components() {
+        monitor.observe(c);
+    }
+    assert_eq!(
+        monitor.cold_components(),
+        vec!["gas_estimate.tri_hop", "gas_estimate.two_hop"]
+    );
+
+    let clock = CycleClock::now();
+    let mut static_opps = scanner.scan_two_hop(&pools, clock);
+    static_opps.extend(scanner.scan_tri_hop(&pools, clock));
+    static_opps.sort_by_key(|o| std::cmp::Reverse(o.expected_profit));
+    let static_opps = select_conflict_free(static_opps, &pools, 3);
+
+    let decided = decide(&scanner, &gas, &pools);
+    assert!(!decided.is_empty());
+    assert_eq!(summary(&decided), summary(&static_opps));
+    for o in &decided {
+        let expected = if o.pool_ids.len() == 3 { TRI_HOP_GAS_ESTIMATE } else { TWO_HOP_GAS_ESTIMATE };
+        assert_eq!(o.estimated_gas, expected);
+        assert_eq!(o.net_profit, o.expected_profit as i64 - expected as i64);
+    }
+}
+
+#[test]
+fn test_transition_to_observed_gas() {
+    let scanner = Scanner::new(0);
+    let pools = pools();
+    let mut gas = GasEstimator::new().with_min_samples(3);
+    let mut monitor = ColdStartMonitor::new();
+    for c in gas.components() {
+        monitor.observe(c);
+    }
+
+    for _ in 0..3 {
+        gas.record(2, 1_500_000);
+    }
+    let left: Vec<bool> = gas.components().into_iter().map(|c| monitor.observe(c)).collect();
+    assert_eq!(left, vec![true, false]);
+    assert_eq!(monitor.cold_components(), vec!["gas_estimate.tri_hop"]);
+
+    for o in decide(&scanner, &gas, &pools) {
+        if o.pool_ids.len() == 2 {
+            assert_eq!(o.estimated_gas, 1_500_000);
+            assert_eq!(o.net_profit, o.expected_profit as i64 - 1_500_000);
+        } else {
+            assert_eq!(o.estimated_gas, TRI_HOP_GAS_ESTIMATE);
+        }
+    }
+}
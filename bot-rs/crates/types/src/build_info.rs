@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Which code and configuration produced a record: attached to trade
+/// results, opportunity traces, session stats, and the alert digest so
+/// results can be segmented across deploys and config changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    /// Crate version (`CARGO_PKG_VERSION`).
+    pub version: String,
+    /// Git commit the binary was built from.
+    pub commit: String,
+    /// [`Config::config_hash`](crate::Config::config_hash) of the running configuration.
+    pub config_hash: String,
+}
+
+impl std::fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "v{} ({}) config {}", self.version, self.commit, self.config_hash)
+    }
+}
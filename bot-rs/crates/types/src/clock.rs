@@ -0,0 +1,115 @@
+//! Wall-clock access.
+//!
+//! The strategy loop reads the clock once per cycle into a [`CycleClock`]
+//! and passes it down, so every guard in a cycle agrees on "now" and tests
+//! can pin it with [`CycleClock::at`]. The loop takes its readings from a
+//! [`Clock`]: the system clock in the bot, a manual one that a test
+//! advances in the loop's integration tests. The strategy and collector
+//! crates don't read `SystemTime` directly (their `clippy.toml` disallows
+//! it); anything that needs the current time outside a cycle calls
+//! [`unix_now_ms`].
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// "Now" for one strategy cycle or scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleClock {
+    now_ms: u64,
+}
+
+impl CycleClock {
+    /// Read the system clock once.
+    pub fn now() -> Self {
+        Self::at(unix_now_ms())
+    }
+
+    /// A fixed instant, for tests and replay.
+    pub fn at(now_ms: u64) -> Self {
+        Self { now_ms }
+    }
+
+    /// Unix time in milliseconds.
+    pub fn now_ms(&self) -> u64 {
+        self.now_ms
+    }
+}
+
+/// Where "now" comes from: the system clock, or a manual clock that only
+/// moves when told to. Clones share the manual clock's time.
+#[derive(Debug, Clone, Default)]
+pub struct Clock {
+    manual: Option<Arc<AtomicU64>>,
+}
+
+impl Clock {
+    /// The system clock.
+    pub fn system() -> Self {
+        Self::default()
+    }
+
+    /// A manual clock reading `start_ms` until [`advance`](Self::advance)d.
+    pub fn manual(start_ms: u64) -> Self {
+        Self { manual: Some(Arc::new(AtomicU64::new(start_ms))) }
+    }
+
+    /// Move a manual clock forward by `ms` (no-op on the system clock).
+    pub fn advance(&self, ms: u64) {
+        if let Some(now) = &self.manual {
+            now.fetch_add(ms, Ordering::Relaxed);
+        }
+    }
+
+    /// Unix time in milliseconds.
+    pub fn now_ms(&self) -> u64 {
+        match &self.manual {
+            Some(now) => now.load(Ordering::Relaxed),
+            None => unix_now_ms(),
+        }
+    }
+
+    /// Read the clock once for a cycle.
+    pub fn cycle(&self) -> CycleClock {
+        CycleClock::at(self.now_ms())
+    }
+}
+
+/// Current Unix time in milliseconds.
+pub fn unix_now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_is_read_once() {
+        let clock = CycleClock::now();
+        let first = clock.now_ms();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert_eq!(clock.now_ms(), first);
+        assert!(unix_now_ms() >= first + 5);
+    }
+
+    #[test]
+    fn test_pinned_clock() {
+        assert_eq!(CycleClock::at(1_700_000_000_000).now_ms(), 1_700_000_000_000);
+    }
+
+    #[test]
+    fn test_manual_clock_moves_only_when_advanced() {
+        let clock = Clock::manual(1_000);
+        let shared = clock.clone();
+        assert_eq!(clock.cycle(), CycleClock::at(1_000));
+        shared.advance(250);
+        assert_eq!(clock.now_ms(), 1_250);
+
+        let system = Clock::system();
+        system.advance(60_000);
+        assert!(system.now_ms() <= unix_now_ms());
+    }
+}
@@ -10,6 +10,125 @@
 //! When comparing prices from CLMM pools (sqrt_price in Q64.64) vs AMM pools
 //! (reserve_b / reserve_a), the decimal difference between token A and B must
 //! be factored in to get a real-world price comparison.
+//!
+//! [`DecimalRegistry`] holds the real values, fetched with
+//! `suix_getCoinMetadata` at startup. The static table below is only the
+//! fallback for coins whose metadata couldn't be fetched.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+use tracing::{info, warn};
+
+use crate::config::normalize_coin_type;
+use crate::rpc::{RpcCall, RpcClient};
+
+/// On-chain coin decimals by coin type, shared by clones.
+#[derive(Debug, Clone, Default)]
+pub struct DecimalRegistry {
+    inner: Arc<RegistryInner>,
+}
+
+#[derive(Debug, Default)]
+struct RegistryInner {
+    /// Normalized coin type → decimals from `CoinMetadata`
+    known: RwLock<HashMap<String, u8>>,
+    /// Coin types a fallback warning has been logged for
+    warned: Mutex<HashSet<String>>,
+}
+
+impl DecimalRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `coin_type`'s decimals.
+    pub fn insert(&self, coin_type: &str, decimals: u8) {
+        self.inner
+            .known
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(normalize_coin_type(coin_type), decimals);
+    }
+
+    /// Fetch `CoinMetadata.decimals` for every coin type (one batch request).
+    /// Coins whose metadata can't be fetched keep using the static table;
+    /// each is warned about. Returns how many were fetched.
+    pub async fn seed(&self, rpc: &RpcClient, coin_types: &[String]) -> usize {
+        let mut unique: Vec<String> = coin_types.iter().map(|c| normalize_coin_type(c)).collect();
+        unique.sort();
+        unique.dedup();
+        let calls: Vec<RpcCall> = unique
+            .iter()
+            .map(|c| RpcCall::new("suix_getCoinMetadata", json!([c])))
+            .collect();
+        let response = rpc.call_batch(&calls).await;
+
+        let mut fetched = 0;
+        for (coin_type, item) in unique.iter().zip(response.items) {
+            match item.and_then(|r| parse_metadata_decimals(&r)) {
+                Ok(decimals) => {
+                    let fallback = decimals_for_coin_type(coin_type);
+                    if decimals != fallback {
+                        info!(coin = %coin_type, decimals = %decimals, table = %fallback, "On-chain decimals differ from the static table");
+                    }
+                    self.insert(coin_type, decimals);
+                    fetched += 1;
+                }
+                Err(e) => {
+                    self.inner.warned.lock().unwrap_or_else(|e| e.into_inner()).insert(coin_type.clone());
+                    warn!(
+                        coin = %coin_type,
+                        fallback = %decimals_for_coin_type(coin_type),
+                        error = %format!("{:#}", e),
+                        "Coin metadata unavailable — using static decimals table"
+                    );
+                }
+            }
+        }
+        fetched
+    }
+
+    /// Decimals for `coin_type`: the fetched value, else the static table
+    /// (warned about once per coin type).
+    pub fn decimals(&self, coin_type: &str) -> u8 {
+        let key = normalize_coin_type(coin_type);
+        if let Some(d) = self.inner.known.read().unwrap_or_else(|e| e.into_inner()).get(&key) {
+            return *d;
+        }
+        let fallback = decimals_for_coin_type(coin_type);
+        if self.inner.warned.lock().unwrap_or_else(|e| e.into_inner()).insert(key) {
+            warn!(
+                coin = %coin_type,
+                fallback = %fallback,
+                "No on-chain decimals for coin — using static decimals table"
+            );
+        }
+        fallback
+    }
+
+    /// [`decimal_adjustment_factor`] using this registry's decimals.
+    pub fn adjustment_factor(&self, coin_type_a: &str, coin_type_b: &str) -> f64 {
+        let diff = self.decimals(coin_type_a) as i32 - self.decimals(coin_type_b) as i32;
+        10f64.powi(diff)
+    }
+
+    /// [`normalize_price`] using this registry's decimals.
+    pub fn normalize_price(&self, raw_price: f64, coin_type_a: &str, coin_type_b: &str) -> f64 {
+        raw_price * self.adjustment_factor(coin_type_a, coin_type_b)
+    }
+}
+
+/// `decimals` from a `suix_getCoinMetadata` response envelope.
+fn parse_metadata_decimals(response: &Value) -> Result<u8> {
+    if let Some(err) = response.get("error") {
+        anyhow::bail!("RPC error: {}", err);
+    }
+    let metadata = response.get("result").filter(|r| !r.is_null()).context("no CoinMetadata object")?;
+    let decimals = metadata.get("decimals").and_then(Value::as_u64).context("metadata has no decimals")?;
+    u8::try_from(decimals).context("decimals out of range")
+}
 
 /// Known mainnet decimal counts, keyed by the last segment of the coin type.
 /// e.g. `0x2::sui::SUI` → `SUI` → 9
@@ -33,10 +152,10 @@ pub fn decimals_for_coin_type(coin_type: &str) -> u8 {
             // e.g., "0xaf8cd...::coin::COIN" is wETH (8 decimals)
             if coin_type.contains("af8cd5edc19c4512") {
                 8 // wETH on Sui
-            } else if coin_type.contains("c060006111016b8a") {
-                6 // USDT on Sui (wrapped)
-            } else if coin_type.contains("5d4b302506645c37ff") {
-                6 // wUSDC on Sui (Wormhole bridged USDC)
+            } else if coin_type.contains("c060006111016b8a")
+                || coin_type.contains("5d4b302506645c37ff")
+            {
+                6 // USDT on Sui (wrapped) / wUSDC (Wormhole bridged USDC)
             } else {
                 9 // unknown wrapped — assume 9
             }
@@ -172,4 +291,23 @@ mod tests {
         let normalized = normalize_price(raw, "0x2::sui::SUI", "0xabc::cetus::CETUS");
         assert!((normalized - 1.5).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_registry_prefers_fetched_decimals() {
+        let registry = DecimalRegistry::new();
+        // Not in the static table: would default to 9
+        assert_eq!(registry.decimals("0xabc::token::TOKEN"), 9);
+        registry.insert("0x0abc::token::TOKEN", 6);
+        assert_eq!(registry.clone().decimals("0xabc::token::TOKEN"), 6);
+        assert_eq!(registry.normalize_price(0.002, "0x2::sui::SUI", "0xabc::token::TOKEN"), 2.0);
+    }
+
+    #[test]
+    fn test_parse_metadata_decimals() {
+        let ok = serde_json::json!({"result": {"decimals": 6, "symbol": "USDC"}});
+        assert_eq!(parse_metadata_decimals(&ok).unwrap(), 6);
+        assert!(parse_metadata_decimals(&serde_json::json!({"result": null})).is_err());
+        assert!(parse_metadata_decimals(&serde_json::json!({"error": {"code": -32602}})).is_err());
+        assert!(parse_metadata_decimals(&serde_json::json!({"result": {"decimals": 300}})).is_err());
+    }
 }
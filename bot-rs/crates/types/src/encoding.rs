@@ -0,0 +1,210 @@
+//! Sui wire encodings shared by the transaction builders and decoders:
+//! BCS primitives (ULEB128 lengths, strings, Move type tags), 32-byte
+//! addresses, and the Base58 alphabet Sui uses for digests.
+
+use anyhow::{Context, Result};
+
+/// Bitcoin-alphabet Base58, as used for Sui digests.
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// A BCS length or enum tag.
+pub fn uleb128(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// A BCS string: its length, then its UTF-8 bytes.
+pub fn string(out: &mut Vec<u8>, s: &str) {
+    uleb128(out, s.len() as u64);
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// A Move type tag: primitives, `vector<T>`, or `addr::module::Name<T..>`.
+pub fn type_tag(out: &mut Vec<u8>, ty: &str) -> Result<()> {
+    let ty = ty.trim();
+    let primitive = match ty {
+        "bool" => Some(0),
+        "u8" => Some(1),
+        "u64" => Some(2),
+        "u128" => Some(3),
+        "address" => Some(4),
+        "u16" => Some(8),
+        "u32" => Some(9),
+        "u256" => Some(10),
+        _ => None,
+    };
+    if let Some(tag) = primitive {
+        out.push(tag);
+        return Ok(());
+    }
+    if let Some(inner) = ty.strip_prefix("vector<").and_then(|t| t.strip_suffix('>')) {
+        out.push(6);
+        return type_tag(out, inner);
+    }
+
+    let (base, params) = match ty.find('<') {
+        Some(open) => {
+            let inner = ty[open + 1..].strip_suffix('>').with_context(|| format!("Invalid type '{}'", ty))?;
+            (&ty[..open], split_top_level(inner))
+        }
+        None => (ty, Vec::new()),
+    };
+    let parts: Vec<&str> = base.split("::").collect();
+    let [addr, module, name] = parts.as_slice() else {
+        anyhow::bail!("Invalid type '{}'", ty);
+    };
+    out.push(7); // TypeTag::Struct
+    out.extend_from_slice(&parse_address(addr)?);
+    string(out, module);
+    string(out, name);
+    uleb128(out, params.len() as u64);
+    for param in params {
+        type_tag(out, param)?;
+    }
+    Ok(())
+}
+
+/// Split `A, B<C, D>` on its top-level commas.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[start..].trim());
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+/// A `0x`-prefixed hex address or object ID, left-padded to 32 bytes
+/// (`0x6` → 31 zero bytes then `0x06`).
+pub fn parse_address(s: &str) -> Result<[u8; 32]> {
+    let hex_str = s.trim().trim_start_matches("0x");
+    anyhow::ensure!(
+        !hex_str.is_empty() && hex_str.len() <= 64 && hex_str.chars().all(|c| c.is_ascii_hexdigit()),
+        "Invalid address '{}'",
+        s
+    );
+    let bytes = hex::decode(format!("{:0>64}", hex_str)).expect("validated hex");
+    Ok(bytes.try_into().expect("32 bytes"))
+}
+
+/// `addr` as `0x` and 64 lowercase hex digits.
+pub fn hex_addr(addr: &[u8; 32]) -> String {
+    format!("0x{}", hex::encode(addr))
+}
+
+/// Encode `bytes` as Base58.
+pub fn encode_base58(bytes: &[u8]) -> String {
+    // Little-endian base-58 digits
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in bytes {
+        let mut carry = byte as u32;
+        for d in digits.iter_mut() {
+            carry += (*d as u32) << 8;
+            *d = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    std::iter::repeat_n('1', zeros)
+        .chain(digits.iter().rev().map(|&d| BASE58_ALPHABET[d as usize] as char))
+        .collect()
+}
+
+/// Decode Base58 (Sui object and transaction digests).
+pub fn decode_base58(s: &str) -> Result<Vec<u8>> {
+    // Little-endian base-256 accumulator
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in s.bytes() {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .with_context(|| format!("Invalid base58 character '{}' in '{}'", c as char, s))? as u32;
+        for b in bytes.iter_mut() {
+            carry += *b as u32 * 58;
+            *b = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    // Each leading '1' is a leading zero byte
+    bytes.extend(s.bytes().take_while(|&c| c == b'1').map(|_| 0));
+    bytes.reverse();
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_tag_encoding() {
+        let mut out = Vec::new();
+        type_tag(&mut out, "0x2::sui::SUI").unwrap();
+        let mut expected = vec![7];
+        expected.extend_from_slice(&[0; 31]);
+        expected.push(2);
+        expected.extend_from_slice(b"\x03sui\x03SUI\x00");
+        assert_eq!(out, expected);
+
+        let mut nested = Vec::new();
+        type_tag(&mut nested, "0xa::w::Wrapped<0x2::sui::SUI, vector<u8>>").unwrap();
+        assert_eq!(&nested[nested.len() - 3..], &[0, 6, 1]); // SUI's empty params, vector<u8>
+        assert!(type_tag(&mut Vec::new(), "SUI").is_err());
+
+        let mut coin = Vec::new();
+        type_tag(&mut coin, "0x2::coin::Coin<0x2::sui::SUI>").unwrap();
+        assert!(coin.ends_with(&out), "type param encoded last");
+        assert_eq!(split_top_level("0x2::a::A<u8, u64>, 0x2::b::B"), vec!["0x2::a::A<u8, u64>", "0x2::b::B"]);
+    }
+
+    #[test]
+    fn test_parse_address() {
+        let addr = parse_address("0x6").unwrap();
+        assert_eq!(&addr[..31], &[0; 31]);
+        assert_eq!(addr[31], 6);
+        assert_eq!(hex_addr(&addr), format!("0x{:0>64}", "6"));
+        assert!(parse_address("0x").is_err());
+        assert!(parse_address("0xzz").is_err());
+        assert!(parse_address(&format!("0x{}", "1".repeat(65))).is_err());
+    }
+
+    #[test]
+    fn test_base58() {
+        assert_eq!(encode_base58(b"hello world"), "StV1DL6CwTryKyV");
+        assert_eq!(encode_base58(&[0, 0, 1]), "112");
+        assert_eq!(encode_base58(&[]), "");
+
+        assert_eq!(decode_base58("").unwrap(), Vec::<u8>::new());
+        assert_eq!(decode_base58("1").unwrap(), vec![0]);
+        assert_eq!(decode_base58("5Q").unwrap(), vec![0xff]);
+        assert_eq!(decode_base58("StV1DL6CwTryKyV").unwrap(), b"hello world");
+        assert_eq!(decode_base58("118Qq").unwrap(), vec![0, 0, 0x61, 0x62]);
+        // 32 zero bytes, the shortest-string digest
+        assert_eq!(decode_base58(&"1".repeat(32)).unwrap(), vec![0; 32]);
+        assert!(decode_base58("0OIl").is_err());
+    }
+}
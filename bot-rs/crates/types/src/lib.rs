@@ -1,9 +1,23 @@
+pub mod build_info;
+pub mod clock;
 pub mod config;
 pub mod decimals;
+pub mod encoding;
+pub mod log_throttle;
+pub mod metrics;
+pub mod notifier;
 pub mod opportunity;
 pub mod pool;
+pub mod rotating_file;
+pub mod rpc;
+#[cfg(feature = "test-support")]
+pub mod test_support;
 
+pub use build_info::BuildInfo;
+pub use clock::CycleClock;
 pub use config::Config;
-pub use decimals::{decimal_adjustment_factor, decimals_for_coin_type, normalize_price};
+pub use decimals::{decimal_adjustment_factor, decimals_for_coin_type, normalize_price, DecimalRegistry};
+pub use metrics::{BreakerStatus, LastTrade, Metrics, MetricsSnapshot};
+pub use notifier::Notifier;
 pub use opportunity::{ArbOpportunity, StrategyType};
 pub use pool::PoolState;
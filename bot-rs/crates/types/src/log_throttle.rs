@@ -0,0 +1,204 @@
+//! Keyed rate limiting for repeated log lines.
+//!
+//! Warnings raised while a condition persists (stale pools, low gas, a dead
+//! collector) would otherwise repeat every cycle. The first occurrence of a
+//! key is emitted immediately; repeats within the interval are counted and
+//! suppressed, and the count is reported with the next emission or when the
+//! condition clears. Timing uses `Instant`, so wall-clock jumps don't
+//! unthrottle or silence anything.
+//!
+//! Use through [`warn_throttled!`](crate::warn_throttled) and
+//! [`clear_throttled!`](crate::clear_throttled).
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Independent shards, so unrelated keys logged from different tasks don't
+/// contend on one lock.
+const SHARDS: usize = 16;
+
+/// Interval for warnings about persistent conditions.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Repeats suppressed since a key was last emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Suppressed {
+    pub count: u64,
+    /// Time since the key was last emitted.
+    pub window: Duration,
+}
+
+#[derive(Debug)]
+struct Entry {
+    last_emit: Instant,
+    suppressed: u64,
+}
+
+/// Per-key emission state, sharded by key hash.
+#[derive(Debug)]
+pub struct LogThrottle {
+    shards: [Mutex<HashMap<String, Entry>>; SHARDS],
+}
+
+impl Default for LogThrottle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogThrottle {
+    pub fn new() -> Self {
+        Self {
+            shards: std::array::from_fn(|_| Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn shard(&self, key: &str) -> &Mutex<HashMap<String, Entry>> {
+        let mut h = DefaultHasher::new();
+        key.hash(&mut h);
+        &self.shards[h.finish() as usize % SHARDS]
+    }
+
+    /// Whether `key` may be logged now. `Some` means emit, carrying the
+    /// number of repeats suppressed since the last emission; `None` means
+    /// suppress (the repeat is counted).
+    pub fn check(&self, key: &str, interval: Duration) -> Option<Suppressed> {
+        self.check_at(key, interval, Instant::now())
+    }
+
+    pub fn check_at(&self, key: &str, interval: Duration, now: Instant) -> Option<Suppressed> {
+        let Ok(mut shard) = self.shard(key).lock() else {
+            return Some(Suppressed { count: 0, window: Duration::ZERO });
+        };
+        match shard.get_mut(key) {
+            None => {
+                shard.insert(key.to_string(), Entry { last_emit: now, suppressed: 0 });
+                Some(Suppressed { count: 0, window: Duration::ZERO })
+            }
+            Some(e) => {
+                let window = now.saturating_duration_since(e.last_emit);
+                if window >= interval {
+                    let count = std::mem::take(&mut e.suppressed);
+                    e.last_emit = now;
+                    Some(Suppressed { count, window })
+                } else {
+                    e.suppressed += 1;
+                    None
+                }
+            }
+        }
+    }
+
+    /// The condition behind `key` cleared: forget it, so the next occurrence
+    /// logs immediately. Returns the repeats still unreported, if any.
+    pub fn clear(&self, key: &str) -> Option<Suppressed> {
+        self.clear_at(key, Instant::now())
+    }
+
+    pub fn clear_at(&self, key: &str, now: Instant) -> Option<Suppressed> {
+        let e = self.shard(key).lock().ok()?.remove(key)?;
+        (e.suppressed > 0).then(|| Suppressed {
+            count: e.suppressed,
+            window: now.saturating_duration_since(e.last_emit),
+        })
+    }
+}
+
+static GLOBAL: LazyLock<LogThrottle> = LazyLock::new(LogThrottle::new);
+
+/// Process-wide throttle used by the logging macros.
+pub fn global() -> &'static LogThrottle {
+    &GLOBAL
+}
+
+/// `tracing::warn!` at most once per `interval` for `key`; suppressed
+/// repeats are summarized on the next emission.
+///
+/// ```ignore
+/// warn_throttled!("pools.stale", Duration::from_secs(60), count = %n, "All pool data is stale");
+/// ```
+#[macro_export]
+macro_rules! warn_throttled {
+    ($key:expr, $interval:expr, $($arg:tt)+) => {{
+        let key: &str = &$key;
+        if let Some(s) = $crate::log_throttle::global().check(key, $interval) {
+            if s.count > 0 {
+                ::tracing::warn!(
+                    key = %key,
+                    "Repeated {}× in the last {}s",
+                    s.count,
+                    s.window.as_secs()
+                );
+            }
+            ::tracing::warn!($($arg)+);
+        }
+    }};
+}
+
+/// Mark the condition behind a [`warn_throttled!`] key as cleared, logging
+/// any repeats that were suppressed since its last emission.
+#[macro_export]
+macro_rules! clear_throttled {
+    ($key:expr) => {{
+        let key: &str = &$key;
+        if let Some(s) = $crate::log_throttle::global().clear(key) {
+            ::tracing::info!(
+                key = %key,
+                "Condition cleared — repeated {}× in the last {}s",
+                s.count,
+                s.window.as_secs()
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_first_emits_then_counts_repeats() {
+        let t = LogThrottle::new();
+        let t0 = Instant::now();
+        assert_eq!(t.check_at("k", MIN, t0), Some(Suppressed { count: 0, window: Duration::ZERO }));
+        for i in 1..=240 {
+            assert!(t.check_at("k", MIN, t0 + Duration::from_millis(i * 200)).is_none());
+        }
+        let s = t.check_at("k", MIN, t0 + MIN).unwrap();
+        assert_eq!(s, Suppressed { count: 240, window: MIN });
+        // Counter reset after reporting
+        assert!(t.check_at("k", MIN, t0 + MIN + Duration::from_secs(1)).is_none());
+        assert_eq!(t.check_at("k", MIN, t0 + MIN * 2).unwrap().count, 1);
+    }
+
+    #[test]
+    fn test_keys_are_independent() {
+        let t = LogThrottle::new();
+        let t0 = Instant::now();
+        assert!(t.check_at("a", MIN, t0).is_some());
+        assert!(t.check_at("b", MIN, t0).is_some());
+        assert!(t.check_at("a", MIN, t0).is_none());
+    }
+
+    #[test]
+    fn test_clear_reports_unsummarized_repeats() {
+        let t = LogThrottle::new();
+        let t0 = Instant::now();
+        t.check_at("k", MIN, t0);
+        t.check_at("k", MIN, t0 + Duration::from_secs(1));
+        t.check_at("k", MIN, t0 + Duration::from_secs(2));
+        let s = t.clear_at("k", t0 + Duration::from_secs(10)).unwrap();
+        assert_eq!(s, Suppressed { count: 2, window: Duration::from_secs(10) });
+
+        // Cleared: the next occurrence emits immediately
+        assert!(t.check_at("k", MIN, t0 + Duration::from_secs(11)).is_some());
+        // Nothing suppressed → nothing to summarize
+        assert!(t.clear_at("k", t0 + Duration::from_secs(12)).is_none());
+        assert!(t.clear_at("unknown", t0).is_none());
+    }
+}
@@ -0,0 +1,426 @@
+//! Counters and gauges for the `/metrics` endpoint.
+//!
+//! One [`Metrics`] is shared (behind an `Arc`) by the strategy loop and the
+//! scanner; the bot's metrics server renders it in the Prometheus text
+//! exposition format on every scrape, and its status endpoint serves a
+//! [`MetricsSnapshot`] as JSON. Everything is in-process and resets on
+//! restart — Prometheus handles counter resets itself.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::opportunity::ArbOpportunity;
+use crate::pool::PoolState;
+
+/// Trade, scanner, and pool-cache statistics.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    cycles: AtomicU64,
+    trades: AtomicU64,
+    trades_succeeded: AtomicU64,
+    trades_failed: AtomicU64,
+    profit_mist: AtomicU64,
+    gas_mist: AtomicU64,
+    breaker_trips: AtomicU64,
+    pool_cache_size: AtomicU64,
+    collector_heartbeat_age_ms: AtomicU64,
+    /// Factor the RPC poller's interval is stretched by (1 = no backoff)
+    poll_backoff_multiplier: AtomicU64,
+    /// Poll cycles the RPC node rate-limited
+    poll_rate_limited: AtomicU64,
+    /// The RPC poller's paced interval, before backoff (ms)
+    poll_interval_ms: AtomicU64,
+    /// Cached Turbos fee types found wrong on-chain (and corrected)
+    fee_type_mismatches: AtomicU64,
+    /// Wallet gas balance, once known
+    gas_balance_mist: Mutex<Option<u64>>,
+    /// Opportunities found, by strategy name
+    opportunities: Mutex<BTreeMap<String, u64>>,
+    /// Staleness (ms) of each pool in the last snapshot, by (pool, dex)
+    pool_staleness: Mutex<BTreeMap<(String, String), u64>>,
+    /// Circuit breaker state as of the last cycle
+    breaker: Mutex<BreakerStatus>,
+    last_trade: Mutex<Option<LastTrade>>,
+    /// Adaptive components still on their static defaults, as of the last cycle
+    cold_components: Mutex<Vec<String>>,
+}
+
+/// Circuit breaker state, as reported by the strategy loop.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BreakerStatus {
+    pub tripped: bool,
+    pub trip_reason: Option<String>,
+    pub consecutive_failures: u32,
+    pub cumulative_pnl_mist: i64,
+    pub window_pnl_mist: i64,
+}
+
+/// The most recent submission.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LastTrade {
+    /// `None` if the RPC rejected it before it got a digest
+    pub digest: Option<String>,
+    pub strategy: String,
+    /// `success`, `reverted` or `submit_failed`
+    pub outcome: String,
+    pub profit_mist: u64,
+    pub gas_mist: u64,
+    pub at_ms: u64,
+}
+
+/// Point-in-time copy of the metrics, for the status endpoint.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsSnapshot {
+    pub cycles: u64,
+    pub trades: u64,
+    pub trades_succeeded: u64,
+    pub trades_failed: u64,
+    pub profit_mist: u64,
+    pub gas_mist: u64,
+    pub net_profit_mist: i64,
+    pub breaker_trips: u64,
+    pub pool_cache_size: u64,
+    pub poll_backoff_multiplier: u64,
+    pub poll_rate_limited: u64,
+    pub poll_interval_ms: u64,
+    pub fee_type_mismatches: u64,
+    /// Staleness (ms) of each tradeable pool at the last cycle, by pool ID
+    pub pool_staleness_ms: BTreeMap<String, u64>,
+    pub gas_balance_mist: Option<u64>,
+    pub circuit_breaker: BreakerStatus,
+    pub last_trade: Option<LastTrade>,
+    pub cold_components: Vec<String>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// One strategy-loop cycle started.
+    pub fn record_cycle(&self) {
+        self.cycles.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A submission that reached the chain. `profit_mist` is the arb
+    /// event's profit (0 for a revert); `gas_mist` is the gas charged.
+    pub fn record_trade(&self, success: bool, profit_mist: u64, gas_mist: u64) {
+        self.trades.fetch_add(1, Ordering::Relaxed);
+        if success {
+            self.trades_succeeded.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.trades_failed.fetch_add(1, Ordering::Relaxed);
+        }
+        self.profit_mist.fetch_add(profit_mist, Ordering::Relaxed);
+        self.gas_mist.fetch_add(gas_mist, Ordering::Relaxed);
+    }
+
+    /// A submission the RPC rejected (nothing landed, no gas charged).
+    pub fn record_submit_error(&self) {
+        self.trades.fetch_add(1, Ordering::Relaxed);
+        self.trades_failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_breaker_trip(&self) {
+        self.breaker_trips.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A cached Turbos fee type didn't match the pool's on-chain type.
+    pub fn record_fee_type_mismatch(&self) {
+        self.fee_type_mismatches.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count the opportunities one scan found.
+    pub fn record_opportunities(&self, opps: &[ArbOpportunity]) {
+        if opps.is_empty() {
+            return;
+        }
+        let mut counts = self.opportunities.lock().unwrap_or_else(|e| e.into_inner());
+        for opp in opps {
+            *counts.entry(opp.strategy.to_string()).or_default() += 1;
+        }
+    }
+
+    /// Replace the pool gauges: `cache_size` pools cached, and the
+    /// staleness of each pool in `pools` at `now_ms`.
+    pub fn set_pools(&self, cache_size: usize, pools: &[PoolState], now_ms: u64) {
+        self.pool_cache_size.store(cache_size as u64, Ordering::Relaxed);
+        let staleness = pools
+            .iter()
+            .map(|p| ((p.object_id.clone(), format!("{:?}", p.dex)), p.staleness_ms(now_ms)))
+            .collect();
+        *self.pool_staleness.lock().unwrap_or_else(|e| e.into_inner()) = staleness;
+    }
+
+    pub fn set_gas_balance(&self, balance_mist: u64) {
+        *self.gas_balance_mist.lock().unwrap_or_else(|e| e.into_inner()) = Some(balance_mist);
+    }
+
+    /// Time since the collectors last reported in.
+    pub fn set_collector_heartbeat_age(&self, age_ms: u64) {
+        self.collector_heartbeat_age_ms.store(age_ms, Ordering::Relaxed);
+    }
+
+    /// The RPC poller's rate-limit backoff: its interval multiplier and the
+    /// rate-limited cycles so far.
+    pub fn set_poll_backoff(&self, multiplier: u32, rate_limited_total: u64) {
+        self.poll_backoff_multiplier.store(multiplier as u64, Ordering::Relaxed);
+        self.poll_rate_limited.store(rate_limited_total, Ordering::Relaxed);
+    }
+
+    /// The RPC poller's interval as paced by pool changes, before backoff.
+    pub fn set_poll_interval(&self, interval_ms: u64) {
+        self.poll_interval_ms.store(interval_ms, Ordering::Relaxed);
+    }
+
+    pub fn set_breaker(&self, status: BreakerStatus) {
+        *self.breaker.lock().unwrap_or_else(|e| e.into_inner()) = status;
+    }
+
+    pub fn set_last_trade(&self, trade: LastTrade) {
+        *self.last_trade.lock().unwrap_or_else(|e| e.into_inner()) = Some(trade);
+    }
+
+    /// The adaptive components still in cold start (see `ColdStartMonitor`).
+    pub fn set_cold_components(&self, components: &[&str]) {
+        *self.cold_components.lock().unwrap_or_else(|e| e.into_inner()) =
+            components.iter().map(|c| c.to_string()).collect();
+    }
+
+    /// Whether the circuit breaker was tripped at the last cycle.
+    pub fn breaker_tripped(&self) -> bool {
+        self.breaker.lock().unwrap_or_else(|e| e.into_inner()).tripped
+    }
+
+    /// Copy of every metric.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let profit_mist = self.profit_mist.load(Ordering::Relaxed);
+        let gas_mist = self.gas_mist.load(Ordering::Relaxed);
+        MetricsSnapshot {
+            cycles: self.cycles.load(Ordering::Relaxed),
+            trades: self.trades.load(Ordering::Relaxed),
+            trades_succeeded: self.trades_succeeded.load(Ordering::Relaxed),
+            trades_failed: self.trades_failed.load(Ordering::Relaxed),
+            profit_mist,
+            gas_mist,
+            net_profit_mist: profit_mist as i64 - gas_mist as i64,
+            breaker_trips: self.breaker_trips.load(Ordering::Relaxed),
+            pool_cache_size: self.pool_cache_size.load(Ordering::Relaxed),
+            poll_backoff_multiplier: self.poll_backoff_multiplier.load(Ordering::Relaxed).max(1),
+            poll_rate_limited: self.poll_rate_limited.load(Ordering::Relaxed),
+            poll_interval_ms: self.poll_interval_ms.load(Ordering::Relaxed),
+            fee_type_mismatches: self.fee_type_mismatches.load(Ordering::Relaxed),
+            pool_staleness_ms: self
+                .pool_staleness
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .map(|((pool, _), ms)| (pool.clone(), *ms))
+                .collect(),
+            gas_balance_mist: *self.gas_balance_mist.lock().unwrap_or_else(|e| e.into_inner()),
+            circuit_breaker: self.breaker.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+            last_trade: self.last_trade.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+            cold_components: self.cold_components.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        }
+    }
+
+    /// Prometheus text exposition format (version 0.0.4).
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let counters = [
+            ("arb_scan_cycles_total", "Strategy loop cycles.", &self.cycles),
+            ("arb_trades_total", "Transactions submitted.", &self.trades),
+            ("arb_trades_succeeded_total", "Submitted transactions that executed successfully.", &self.trades_succeeded),
+            ("arb_trades_failed_total", "Submitted transactions that reverted or were rejected.", &self.trades_failed),
+            ("arb_profit_mist_total", "Arb profit reported by successful trades (MIST).", &self.profit_mist),
+            ("arb_gas_mist_total", "Gas charged for submitted transactions (MIST).", &self.gas_mist),
+            ("arb_circuit_breaker_trips_total", "Circuit breaker trips.", &self.breaker_trips),
+            ("arb_poll_rate_limited_total", "RPC poll cycles the node rate-limited.", &self.poll_rate_limited),
+            ("arb_fee_type_mismatches_total", "Cached Turbos fee types corrected from on-chain.", &self.fee_type_mismatches),
+        ];
+        for (name, help, value) in counters {
+            header(&mut out, name, help, "counter");
+            let _ = writeln!(out, "{} {}", name, value.load(Ordering::Relaxed));
+        }
+
+        let net = self.profit_mist.load(Ordering::Relaxed) as i64 - self.gas_mist.load(Ordering::Relaxed) as i64;
+        header(&mut out, "arb_net_profit_mist", "Arb profit minus gas charged since start (MIST).", "gauge");
+        let _ = writeln!(out, "arb_net_profit_mist {}", net);
+
+        header(&mut out, "arb_opportunities_detected_total", "Opportunities found by the scanner.", "counter");
+        for (strategy, n) in self.opportunities.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+            let _ = writeln!(out, "arb_opportunities_detected_total{{strategy=\"{}\"}} {}", escape(strategy), n);
+        }
+
+        header(&mut out, "arb_pool_cache_size", "Pools in the cache.", "gauge");
+        let _ = writeln!(out, "arb_pool_cache_size {}", self.pool_cache_size.load(Ordering::Relaxed));
+
+        header(&mut out, "arb_collector_heartbeat_age_ms", "Time since the collectors last reported in (ms).", "gauge");
+        let _ = writeln!(out, "arb_collector_heartbeat_age_ms {}", self.collector_heartbeat_age_ms.load(Ordering::Relaxed));
+
+        header(&mut out, "arb_poll_backoff_multiplier", "Factor the RPC poll interval is backed off by (1 = none).", "gauge");
+        let _ = writeln!(out, "arb_poll_backoff_multiplier {}", self.poll_backoff_multiplier.load(Ordering::Relaxed).max(1));
+
+        header(&mut out, "arb_poll_interval_ms", "RPC poll interval as paced by pool changes, before backoff (ms).", "gauge");
+        let _ = writeln!(out, "arb_poll_interval_ms {}", self.poll_interval_ms.load(Ordering::Relaxed));
+
+        // Left out until the first balance fetch rather than reported as 0
+        if let Some(balance) = *self.gas_balance_mist.lock().unwrap_or_else(|e| e.into_inner()) {
+            header(&mut out, "arb_gas_balance_mist", "Wallet gas balance (MIST).", "gauge");
+            let _ = writeln!(out, "arb_gas_balance_mist {}", balance);
+        }
+
+        header(&mut out, "arb_pool_staleness_ms", "Age of each tradeable pool's state at the last cycle (ms).", "gauge");
+        for ((pool, dex), ms) in self.pool_staleness.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+            let _ = writeln!(
+                out,
+                "arb_pool_staleness_ms{{pool=\"{}\",dex=\"{}\"}} {}",
+                escape(pool),
+                escape(dex),
+                ms
+            );
+        }
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Escape a label value (backslash, quote, newline).
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opportunity::StrategyType;
+    use crate::pool::Dex;
+
+    fn pool(id: &str, dex: Dex, last_updated_ms: u64) -> PoolState {
+        PoolState {
+            object_id: id.to_string(),
+            dex,
+            coin_type_a: "SUI".to_string(),
+            coin_type_b: "USDC".to_string(),
+            sqrt_price: None,
+            tick_index: None,
+            liquidity: None,
+            fee_rate_bps: None,
+            reserve_a: None,
+            reserve_b: None,
+            best_bid: None,
+            best_ask: None,
+            book_depth: None,
+            last_updated_ms,
+            fee_type: None,
+            min_trade_size: None,
+            lot_size: None,
+            raw_balance_a: None,
+            raw_balance_b: None,
+        }
+    }
+
+    fn opp(strategy: StrategyType) -> ArbOpportunity {
+        ArbOpportunity {
+            strategy,
+            amount_in: 0,
+            expected_profit: 0,
+            estimated_gas: 0,
+            net_profit: 0,
+            pool_ids: Vec::new(),
+            type_args: Vec::new(),
+            detected_at_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_trade_counters() {
+        let m = Metrics::new();
+        m.record_trade(true, 500_000, 20_000);
+        m.record_trade(false, 0, 15_000);
+        m.record_submit_error();
+        m.record_breaker_trip();
+
+        let text = m.render();
+        assert!(text.contains("arb_trades_total 3\n"), "{}", text);
+        assert!(text.contains("arb_trades_succeeded_total 1\n"));
+        assert!(text.contains("arb_trades_failed_total 2\n"));
+        assert!(text.contains("arb_profit_mist_total 500000\n"));
+        assert!(text.contains("arb_gas_mist_total 35000\n"));
+        assert!(text.contains("arb_circuit_breaker_trips_total 1\n"));
+        assert!(text.contains("arb_fee_type_mismatches_total 0\n"));
+        assert!(text.contains("# TYPE arb_trades_total counter\n"));
+        assert!(text.contains("arb_net_profit_mist 465000\n"));
+    }
+
+    #[test]
+    fn test_loop_gauges() {
+        let m = Metrics::new();
+        assert!(!m.render().contains("arb_gas_balance_mist"));
+
+        m.record_cycle();
+        m.record_cycle();
+        m.set_gas_balance(2_500_000_000);
+        m.set_collector_heartbeat_age(750);
+        assert!(m.render().contains("arb_poll_backoff_multiplier 1\n"));
+        m.set_poll_backoff(4, 2);
+        m.set_poll_interval(375);
+        let text = m.render();
+        assert!(text.contains("arb_scan_cycles_total 2\n"), "{}", text);
+        assert!(text.contains("arb_gas_balance_mist 2500000000\n"));
+        assert!(text.contains("arb_collector_heartbeat_age_ms 750\n"));
+        assert!(text.contains("arb_poll_backoff_multiplier 4\n"));
+        assert!(text.contains("arb_poll_rate_limited_total 2\n"));
+        assert!(text.contains("arb_poll_interval_ms 375\n"));
+    }
+
+    #[test]
+    fn test_labelled_series() {
+        let m = Metrics::new();
+        m.record_opportunities(&[opp(StrategyType::CetusToTurbos), opp(StrategyType::CetusToTurbos)]);
+        m.record_opportunities(&[opp(StrategyType::TurbosToCetus)]);
+        m.set_pools(3, &[pool("0x1", Dex::Cetus, 9_000), pool("0x2", Dex::Turbos, 10_000)], 10_000);
+
+        let text = m.render();
+        assert!(text.contains("arb_opportunities_detected_total{strategy=\"arb_cetus_to_turbos\"} 2\n"), "{}", text);
+        assert!(text.contains("arb_opportunities_detected_total{strategy=\"arb_turbos_to_cetus\"} 1\n"));
+        assert!(text.contains("arb_pool_cache_size 3\n"));
+        assert!(text.contains("arb_pool_staleness_ms{pool=\"0x1\",dex=\"Cetus\"} 1000\n"));
+
+        // A pool gone from the next snapshot drops out of the gauge
+        m.set_pools(1, &[pool("0x2", Dex::Turbos, 10_000)], 10_000);
+        assert!(!m.render().contains("pool=\"0x1\""));
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let m = Metrics::new();
+        m.record_trade(true, 500_000, 20_000);
+        m.set_pools(2, &[pool("0x1", Dex::Cetus, 9_000)], 10_000);
+        m.set_breaker(BreakerStatus { tripped: true, consecutive_failures: 3, ..Default::default() });
+        m.set_last_trade(LastTrade {
+            digest: Some("Dg1".to_string()),
+            strategy: "arb_cetus_to_turbos".to_string(),
+            outcome: "success".to_string(),
+            profit_mist: 500_000,
+            gas_mist: 20_000,
+            at_ms: 10_000,
+        });
+        m.set_cold_components(&["gas_estimate.tri_hop"]);
+
+        let snap = m.snapshot();
+        assert_eq!((snap.trades, snap.net_profit_mist, snap.pool_cache_size), (1, 480_000, 2));
+        assert_eq!(snap.pool_staleness_ms["0x1"], 1_000);
+        assert_eq!(snap.gas_balance_mist, None);
+        assert!(m.breaker_tripped());
+        assert_eq!(snap.last_trade.unwrap().digest.as_deref(), Some("Dg1"));
+        assert_eq!(snap.cold_components, ["gas_estimate.tri_hop"]);
+    }
+}
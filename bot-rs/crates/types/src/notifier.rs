@@ -0,0 +1,170 @@
+//! Webhook notifications for conditions an operator has to act on (an empty
+//! gas wallet, a tripped circuit breaker).
+//!
+//! Messages are POSTed as JSON carrying both `content` (Discord) and `text`
+//! (Slack), so either kind of incoming webhook accepts them, plus the event
+//! key and structured `details`. Each key is sent at most once per
+//! `min_interval`, so a condition that persists or flaps doesn't spam.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::warn;
+
+/// Default minimum time between two notifications with the same key.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Per-request timeout for webhook delivery.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Shared, rate-limited webhook sender. Clones share the rate-limit state;
+/// a disabled notifier (no URL) accepts and drops everything.
+#[derive(Clone, Default)]
+pub struct Notifier {
+    inner: Option<Arc<Inner>>,
+}
+
+struct Inner {
+    client: reqwest::Client,
+    url: String,
+    min_interval_ms: u64,
+    /// When each key was last sent (ms since epoch).
+    last_sent: Mutex<HashMap<String, u64>>,
+}
+
+impl std::fmt::Debug for Notifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The URL embeds the webhook's secret token
+        f.debug_struct("Notifier").field("enabled", &self.is_enabled()).finish()
+    }
+}
+
+impl Notifier {
+    /// POST to `url`. An empty URL gives a disabled notifier.
+    pub fn new(url: &str) -> Self {
+        if url.trim().is_empty() {
+            return Self::default();
+        }
+        Self {
+            inner: Some(Arc::new(Inner {
+                client: reqwest::Client::builder()
+                    .timeout(WEBHOOK_TIMEOUT)
+                    .build()
+                    .expect("Failed to create HTTP client"),
+                url: url.trim().to_string(),
+                min_interval_ms: DEFAULT_MIN_INTERVAL.as_millis() as u64,
+                last_sent: Mutex::new(HashMap::new()),
+            })),
+        }
+    }
+
+    /// Send each key at most once per `interval`. Call before cloning.
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        if let Some(inner) = self.inner.as_mut().and_then(Arc::get_mut) {
+            inner.min_interval_ms = interval.as_millis() as u64;
+        }
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Post `message` with `details` under `key`, unless `key` was sent
+    /// within the minimum interval. Delivery runs in the background; returns
+    /// whether a notification was sent.
+    pub fn notify(&self, key: &str, message: &str, details: Value, now_ms: u64) -> bool {
+        let Some(inner) = &self.inner else {
+            return false;
+        };
+        if !self.claim(key, now_ms) {
+            return false;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            warn!(key = %key, "No async runtime — webhook notification dropped");
+            return false;
+        };
+        let client = inner.client.clone();
+        let url = inner.url.clone();
+        let body = payload(key, message, details);
+        let key = key.to_string();
+        runtime.spawn(async move {
+            match client.post(&url).json(&body).send().await {
+                Ok(response) if !response.status().is_success() => {
+                    warn!(key = %key, status = %response.status(), "Webhook rejected notification")
+                }
+                Ok(_) => {}
+                Err(e) => warn!(key = %key, error = %e, "Failed to deliver webhook notification"),
+            }
+        });
+        true
+    }
+
+    /// When `key` was last sent, if ever.
+    pub fn last_sent(&self, key: &str) -> Option<u64> {
+        let inner = self.inner.as_ref()?;
+        inner.last_sent.lock().unwrap().get(key).copied()
+    }
+
+    /// Record a send of `key` at `now_ms` if the rate limit allows one.
+    fn claim(&self, key: &str, now_ms: u64) -> bool {
+        let Some(inner) = &self.inner else {
+            return false;
+        };
+        let mut last_sent = inner.last_sent.lock().unwrap();
+        if let Some(&at) = last_sent.get(key) {
+            if now_ms.saturating_sub(at) < inner.min_interval_ms {
+                return false;
+            }
+        }
+        last_sent.insert(key.to_string(), now_ms);
+        true
+    }
+}
+
+/// The JSON body posted for a notification.
+pub fn payload(key: &str, message: &str, details: Value) -> Value {
+    json!({
+        "content": message,
+        "text": message,
+        "event": key,
+        "details": details,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limited_per_key() {
+        let notifier = Notifier::new("http://127.0.0.1:9/hook").with_min_interval(Duration::from_secs(60));
+        assert!(notifier.claim("gas.low", 1_000));
+        assert!(!notifier.claim("gas.low", 30_000));
+        assert!(notifier.claim("breaker.tripped", 30_000), "keys are limited independently");
+        assert!(notifier.claim("gas.low", 61_000));
+        assert_eq!(notifier.last_sent("gas.low"), Some(61_000));
+
+        // Clones share the limit
+        assert!(!notifier.clone().claim("gas.low", 62_000));
+    }
+
+    #[test]
+    fn test_disabled_without_url() {
+        let notifier = Notifier::new("  ");
+        assert!(!notifier.is_enabled());
+        assert!(!notifier.notify("gas.low", "low", json!({}), 0));
+        assert_eq!(notifier.last_sent("gas.low"), None);
+        assert_eq!(format!("{:?}", Notifier::new("https://hooks.example/secret")), "Notifier { enabled: true }");
+    }
+
+    #[test]
+    fn test_payload_suits_discord_and_slack() {
+        let body = payload("gas.low", "Low gas", json!({ "balance_sui": 0.05 }));
+        assert_eq!(body["content"], "Low gas");
+        assert_eq!(body["text"], "Low gas");
+        assert_eq!(body["event"], "gas.low");
+        assert_eq!(body["details"]["balance_sui"], 0.05);
+    }
+}
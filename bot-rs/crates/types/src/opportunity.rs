@@ -2,7 +2,7 @@ use crate::pool::Dex;
 use serde::{Deserialize, Serialize};
 
 /// Describes which on-chain strategy entry function to call.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum StrategyType {
     // ── Two-hop ──
     CetusToTurbos,
@@ -28,6 +28,13 @@ pub enum StrategyType {
     CetusToFlowxAmm,
     TurbosToFlowxAmm,
     DeepBookToFlowxAmm,
+    // Kriya CLMM
+    CetusToKriya,
+    KriyaToCetus,
+
+    // ── Direct (inventory-funded, no flash loan) ──
+    DirectCetusToTurbos,
+    DirectTurbosToCetus,
 
     // ── Tri-hop ──
     TriCetusCetusCetus,
@@ -42,9 +49,188 @@ pub enum StrategyType {
     TriCetusCetusFlowxClmm,
     TriCetusFlowxClmmTurbos,
     TriFlowxClmmCetusTurbos,
+
+    // ── Quad-hop ──
+    QuadCetusCetusCetusCetus,
+}
+
+/// One generic type parameter of a strategy's Move entry function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeArgSlot {
+    /// The `n`th coin along the path: 0 is `A`, 1 is `B`, and so on.
+    Coin(usize),
+    /// The Turbos pool's fee tier type (`Pool<A, B, Fee>`).
+    TurbosFee,
+    /// The Aftermath pool's LP coin type (`Pool<LP>`).
+    AftermathLp,
+}
+
+/// Prints the slot as the Move generic is named (`A`, `TurbosFee`, `LP`).
+impl std::fmt::Display for TypeArgSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Coin(n) => write!(f, "{}", (b'A' + *n as u8) as char),
+            Self::TurbosFee => write!(f, "TurbosFee"),
+            Self::AftermathLp => write!(f, "LP"),
+        }
+    }
 }
 
 impl StrategyType {
+    /// Every strategy, in declaration order.
+    pub const ALL: [StrategyType; 36] = [
+        Self::CetusToTurbos,
+        Self::CetusToTurbosRev,
+        Self::TurbosToCetus,
+        Self::CetusToDeepBook,
+        Self::DeepBookToCetus,
+        Self::TurbosToDeepBook,
+        Self::DeepBookToTurbos,
+        Self::CetusToAftermath,
+        Self::CetusToAftermathRev,
+        Self::TurbosToAftermath,
+        Self::DeepBookToAftermath,
+        Self::CetusToFlowxClmm,
+        Self::FlowxClmmToCetus,
+        Self::TurbosToFlowxClmm,
+        Self::FlowxClmmToTurbos,
+        Self::DeepBookToFlowxClmm,
+        Self::FlowxClmmToDeepBook,
+        Self::CetusToFlowxAmm,
+        Self::TurbosToFlowxAmm,
+        Self::DeepBookToFlowxAmm,
+        Self::CetusToKriya,
+        Self::KriyaToCetus,
+        Self::DirectCetusToTurbos,
+        Self::DirectTurbosToCetus,
+        Self::TriCetusCetusCetus,
+        Self::TriCetusCetusCetusV2,
+        Self::TriCetusCetusTurbos,
+        Self::TriCetusTurbosDeepBook,
+        Self::TriCetusDeepBookTurbos,
+        Self::TriDeepBookCetusTurbos,
+        Self::TriCetusCetusAftermath,
+        Self::TriCetusTurbosAftermath,
+        Self::TriCetusCetusFlowxClmm,
+        Self::TriCetusFlowxClmmTurbos,
+        Self::TriFlowxClmmCetusTurbos,
+        Self::QuadCetusCetusCetusCetus,
+    ];
+
+    /// Iterate over every strategy.
+    pub fn all() -> impl Iterator<Item = StrategyType> {
+        Self::ALL.into_iter()
+    }
+
+    /// Every strategy whose flash loan / flash swap comes from `dex`.
+    pub fn all_with_flash_source(dex: Dex) -> Vec<StrategyType> {
+        Self::all().filter(|s| s.flash_source() == dex).collect()
+    }
+
+    /// Every tri-hop strategy.
+    pub fn all_tri_hop() -> Vec<StrategyType> {
+        Self::all().filter(Self::is_tri_hop).collect()
+    }
+
+    /// Whether this strategy trades the bot's own inventory instead of a
+    /// flash loan (the `direct` module).
+    pub fn is_direct(&self) -> bool {
+        self.move_module() == "direct"
+    }
+
+    /// The inventory-funded equivalent of a flash-loan strategy, if the
+    /// `direct` module implements it.
+    pub fn direct_variant(&self) -> Option<StrategyType> {
+        match self {
+            Self::CetusToTurbos => Some(Self::DirectCetusToTurbos),
+            Self::TurbosToCetus => Some(Self::DirectTurbosToCetus),
+            _ => None,
+        }
+    }
+
+    /// Whether this strategy flash-borrows coin B and profits in B (the
+    /// `*_reverse` / `*_rev` entry points) rather than coin A.
+    pub fn is_reverse(&self) -> bool {
+        matches!(self, Self::CetusToTurbosRev | Self::CetusToAftermathRev)
+    }
+
+    /// Whether this strategy lives in the `tri_hop` module.
+    pub fn is_tri_hop(&self) -> bool {
+        self.move_module() == "tri_hop"
+    }
+
+    /// Whether this strategy lives in the `quad_hop` module.
+    pub fn is_quad_hop(&self) -> bool {
+        self.move_module() == "quad_hop"
+    }
+
+    /// Pools the strategy swaps through.
+    pub fn hops(&self) -> usize {
+        if self.is_quad_hop() {
+            4
+        } else if self.is_tri_hop() {
+            3
+        } else {
+            2
+        }
+    }
+
+    /// The generic parameters of the Move entry function, in declaration
+    /// order: the path's coins first, then any fee tier / LP types.
+    pub fn type_arg_layout(&self) -> &'static [TypeArgSlot] {
+        use TypeArgSlot::{AftermathLp, Coin, TurbosFee};
+        const AB: &[TypeArgSlot] = &[Coin(0), Coin(1)];
+        const AB_FEE: &[TypeArgSlot] = &[Coin(0), Coin(1), TurbosFee];
+        const AB_LP: &[TypeArgSlot] = &[Coin(0), Coin(1), AftermathLp];
+        const AB_FEE_LP: &[TypeArgSlot] = &[Coin(0), Coin(1), TurbosFee, AftermathLp];
+        const ABC: &[TypeArgSlot] = &[Coin(0), Coin(1), Coin(2)];
+        const ABC_FEE: &[TypeArgSlot] = &[Coin(0), Coin(1), Coin(2), TurbosFee];
+        const ABC_LP: &[TypeArgSlot] = &[Coin(0), Coin(1), Coin(2), AftermathLp];
+        const ABC_FEE_LP: &[TypeArgSlot] = &[Coin(0), Coin(1), Coin(2), TurbosFee, AftermathLp];
+        const ABCD: &[TypeArgSlot] = &[Coin(0), Coin(1), Coin(2), Coin(3)];
+
+        match self {
+            Self::CetusToDeepBook
+            | Self::DeepBookToCetus
+            | Self::CetusToFlowxClmm
+            | Self::FlowxClmmToCetus
+            | Self::DeepBookToFlowxClmm
+            | Self::FlowxClmmToDeepBook
+            | Self::CetusToFlowxAmm
+            | Self::DeepBookToFlowxAmm
+            | Self::CetusToKriya
+            | Self::KriyaToCetus => AB,
+
+            Self::CetusToTurbos
+            | Self::CetusToTurbosRev
+            | Self::TurbosToCetus
+            | Self::TurbosToDeepBook
+            | Self::DeepBookToTurbos
+            | Self::TurbosToFlowxClmm
+            | Self::FlowxClmmToTurbos
+            | Self::TurbosToFlowxAmm
+            | Self::DirectCetusToTurbos
+            | Self::DirectTurbosToCetus => AB_FEE,
+
+            Self::CetusToAftermath | Self::CetusToAftermathRev | Self::DeepBookToAftermath => AB_LP,
+            Self::TurbosToAftermath => AB_FEE_LP,
+
+            Self::TriCetusCetusCetus | Self::TriCetusCetusCetusV2 | Self::TriCetusCetusFlowxClmm => ABC,
+
+            Self::TriCetusCetusTurbos
+            | Self::TriCetusTurbosDeepBook
+            | Self::TriCetusDeepBookTurbos
+            | Self::TriDeepBookCetusTurbos
+            | Self::TriCetusFlowxClmmTurbos
+            | Self::TriFlowxClmmCetusTurbos => ABC_FEE,
+
+            Self::TriCetusCetusAftermath => ABC_LP,
+            Self::TriCetusTurbosAftermath => ABC_FEE_LP,
+
+            Self::QuadCetusCetusCetusCetus => ABCD,
+        }
+    }
+
     /// The Move entry function name for this strategy.
     pub fn move_function_name(&self) -> &'static str {
         match self {
@@ -68,6 +254,10 @@ impl StrategyType {
             Self::CetusToFlowxAmm => "arb_cetus_to_flowx_amm",
             Self::TurbosToFlowxAmm => "arb_turbos_to_flowx_amm",
             Self::DeepBookToFlowxAmm => "arb_deepbook_to_flowx_amm",
+            Self::CetusToKriya => "arb_cetus_to_kriya",
+            Self::KriyaToCetus => "arb_kriya_to_cetus",
+            Self::DirectCetusToTurbos => "direct_cetus_to_turbos",
+            Self::DirectTurbosToCetus => "direct_turbos_to_cetus",
             Self::TriCetusCetusCetus => "tri_cetus_cetus_cetus",
             Self::TriCetusCetusCetusV2 => "tri_cetus_cetus_cetus_v2",
             Self::TriCetusCetusTurbos => "tri_cetus_cetus_turbos",
@@ -79,12 +269,14 @@ impl StrategyType {
             Self::TriCetusCetusFlowxClmm => "tri_cetus_cetus_flowx_clmm",
             Self::TriCetusFlowxClmmTurbos => "tri_cetus_flowx_clmm_turbos",
             Self::TriFlowxClmmCetusTurbos => "tri_flowx_clmm_cetus_turbos",
+            Self::QuadCetusCetusCetusCetus => "quad_cetus_cetus_cetus_cetus",
         }
     }
 
-    /// The Move module containing this strategy ("two_hop" or "tri_hop").
+    /// The Move module containing this strategy ("two_hop", "direct", "tri_hop" or "quad_hop").
     pub fn move_module(&self) -> &'static str {
         match self {
+            Self::DirectCetusToTurbos | Self::DirectTurbosToCetus => "direct",
             Self::TriCetusCetusCetus
             | Self::TriCetusCetusCetusV2
             | Self::TriCetusCetusTurbos
@@ -96,11 +288,13 @@ impl StrategyType {
             | Self::TriCetusCetusFlowxClmm
             | Self::TriCetusFlowxClmmTurbos
             | Self::TriFlowxClmmCetusTurbos => "tri_hop",
+            Self::QuadCetusCetusCetusCetus => "quad_hop",
             _ => "two_hop",
         }
     }
 
     /// Which DEX provides the flash loan / flash swap for this strategy.
+    /// Direct strategies have no flash loan; this is their buy-leg venue.
     pub fn flash_source(&self) -> Dex {
         match self {
             Self::CetusToTurbos
@@ -109,13 +303,16 @@ impl StrategyType {
             | Self::CetusToAftermath
             | Self::CetusToAftermathRev
             | Self::CetusToFlowxClmm
-            | Self::CetusToFlowxAmm => Dex::Cetus,
+            | Self::CetusToFlowxAmm
+            | Self::CetusToKriya
+            | Self::DirectCetusToTurbos => Dex::Cetus,
 
             Self::TurbosToCetus
             | Self::TurbosToDeepBook
             | Self::TurbosToAftermath
             | Self::TurbosToFlowxClmm
-            | Self::TurbosToFlowxAmm => Dex::Turbos,
+            | Self::TurbosToFlowxAmm
+            | Self::DirectTurbosToCetus => Dex::Turbos,
 
             Self::DeepBookToCetus
             | Self::DeepBookToTurbos
@@ -127,6 +324,8 @@ impl StrategyType {
             | Self::FlowxClmmToTurbos
             | Self::FlowxClmmToDeepBook => Dex::FlowxClmm,
 
+            Self::KriyaToCetus => Dex::Kriya,
+
             Self::TriCetusCetusCetus
             | Self::TriCetusCetusCetusV2
             | Self::TriCetusCetusTurbos
@@ -135,7 +334,8 @@ impl StrategyType {
             | Self::TriCetusCetusAftermath
             | Self::TriCetusTurbosAftermath
             | Self::TriCetusCetusFlowxClmm
-            | Self::TriCetusFlowxClmmTurbos => Dex::Cetus,
+            | Self::TriCetusFlowxClmmTurbos
+            | Self::QuadCetusCetusCetusCetus => Dex::Cetus,
 
             Self::TriDeepBookCetusTurbos => Dex::DeepBook,
             Self::TriFlowxClmmCetusTurbos => Dex::FlowxClmm,
@@ -143,6 +343,29 @@ impl StrategyType {
     }
 }
 
+/// Prints the Move entry function name (`arb_cetus_to_turbos`).
+impl std::fmt::Display for StrategyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.move_function_name())
+    }
+}
+
+/// Parses a Move entry function name, case-insensitively — the form
+/// `Display` prints and `STRATEGY_ALLOWLIST` / `STRATEGY_DENYLIST` take.
+impl std::str::FromStr for StrategyType {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let name = name.trim();
+        Self::all()
+            .find(|s| s.move_function_name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                let known: Vec<&str> = Self::all().map(|s| s.move_function_name()).collect();
+                anyhow::anyhow!("Unknown strategy '{}' (expected one of: {})", name, known.join(", "))
+            })
+    }
+}
+
 /// A detected arbitrage opportunity, ready for execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArbOpportunity {
@@ -164,11 +387,41 @@ pub struct ArbOpportunity {
     pub detected_at_ms: u64,
 }
 
+/// Default fraction of expected profit enforced on-chain as `min_profit`.
+pub const DEFAULT_MIN_PROFIT_FRACTION: f64 = 0.9;
+
+/// Default floor on trade size (0.01 SUI): smaller candidates waste a full
+/// execution attempt for negligible gain, whatever profit they report.
+pub const DEFAULT_MIN_TRADE_AMOUNT_MIST: u64 = 10_000_000;
+
+/// Fixed-point scale for the min-profit fraction (parts per million).
+const FRACTION_SCALE: u128 = 1_000_000;
+
 impl ArbOpportunity {
     /// Returns true if the opportunity is profitable after gas.
     pub fn is_profitable(&self) -> bool {
         self.net_profit > 0
     }
+
+    /// The coin borrowed and profited in: coin B (`type_args[1]`) for
+    /// reverse strategies, otherwise coin A (`type_args[0]`).
+    pub fn profit_coin(&self) -> Option<&str> {
+        let index = if self.strategy.is_reverse() { 1 } else { 0 };
+        self.type_args.get(index).map(String::as_str)
+    }
+
+    /// On-chain `min_profit` guard: `expected_profit × fraction`, rounded to
+    /// nearest (not truncated) and floored at 1 MIST so the on-chain
+    /// `assert_profit()` check is never a no-op.
+    ///
+    /// `fraction` is clamped to `[0, 1]` and applied as parts per million in
+    /// `u128`, so large estimates can't overflow.
+    pub fn min_profit(&self, fraction: f64) -> u64 {
+        let ppm = (fraction.clamp(0.0, 1.0) * FRACTION_SCALE as f64).round() as u128;
+        let scaled = (self.expected_profit as u128 * ppm + FRACTION_SCALE / 2) / FRACTION_SCALE;
+        // ppm ≤ scale, so the result never exceeds expected_profit
+        (scaled as u64).max(1)
+    }
 }
 
 #[cfg(test)]
@@ -208,6 +461,16 @@ mod tests {
         assert!(!opp.is_profitable());
     }
 
+    #[test]
+    fn test_profit_coin_follows_direction() {
+        let forward = make_opp(StrategyType::CetusToTurbos, 2, 10_000_000);
+        assert_eq!(forward.profit_coin(), Some("SUI"));
+        let reverse = make_opp(StrategyType::CetusToTurbosRev, 2, 10_000_000);
+        assert_eq!(reverse.profit_coin(), Some("USDC"));
+        assert!(StrategyType::CetusToAftermathRev.is_reverse());
+        assert!(!StrategyType::CetusToAftermath.is_reverse());
+    }
+
     // ── StrategyType tests ──
 
     #[test]
@@ -216,6 +479,7 @@ mod tests {
         assert_eq!(StrategyType::DeepBookToAftermath.move_module(), "two_hop");
         assert_eq!(StrategyType::FlowxClmmToCetus.move_module(), "two_hop");
         assert_eq!(StrategyType::CetusToFlowxAmm.move_module(), "two_hop");
+        assert_eq!(StrategyType::KriyaToCetus.move_module(), "two_hop");
     }
 
     #[test]
@@ -225,11 +489,76 @@ mod tests {
         assert_eq!(StrategyType::TriFlowxClmmCetusTurbos.move_module(), "tri_hop");
     }
 
+    #[test]
+    fn test_move_module_quad_hop_and_hops() {
+        let quad = StrategyType::QuadCetusCetusCetusCetus;
+        assert_eq!(quad.move_module(), "quad_hop");
+        assert!(quad.is_quad_hop() && !quad.is_tri_hop());
+        assert_eq!(quad.hops(), 4);
+        assert_eq!(StrategyType::TriCetusCetusCetus.hops(), 3);
+        assert_eq!(StrategyType::CetusToTurbos.hops(), 2);
+        assert_eq!(StrategyType::DirectCetusToTurbos.hops(), 2);
+    }
+
+    #[test]
+    fn test_type_arg_layout_turbos_fee_position() {
+        // (strategy, layout length, TurbosFee index) per the Move signatures
+        let turbos = [
+            (StrategyType::CetusToTurbos, 3, 2),
+            (StrategyType::CetusToTurbosRev, 3, 2),
+            (StrategyType::TurbosToCetus, 3, 2),
+            (StrategyType::TurbosToDeepBook, 3, 2),
+            (StrategyType::DeepBookToTurbos, 3, 2),
+            (StrategyType::TurbosToAftermath, 4, 2),
+            (StrategyType::TurbosToFlowxClmm, 3, 2),
+            (StrategyType::FlowxClmmToTurbos, 3, 2),
+            (StrategyType::TurbosToFlowxAmm, 3, 2),
+            (StrategyType::DirectCetusToTurbos, 3, 2),
+            (StrategyType::DirectTurbosToCetus, 3, 2),
+            (StrategyType::TriCetusCetusTurbos, 4, 3),
+            (StrategyType::TriCetusTurbosDeepBook, 4, 3),
+            (StrategyType::TriCetusDeepBookTurbos, 4, 3),
+            (StrategyType::TriDeepBookCetusTurbos, 4, 3),
+            (StrategyType::TriCetusTurbosAftermath, 5, 3),
+            (StrategyType::TriCetusFlowxClmmTurbos, 4, 3),
+            (StrategyType::TriFlowxClmmCetusTurbos, 4, 3),
+        ];
+        for (strategy, len, fee_at) in turbos {
+            let layout = strategy.type_arg_layout();
+            assert_eq!(layout.len(), len, "{strategy:?}");
+            assert_eq!(layout.iter().position(|s| *s == TypeArgSlot::TurbosFee), Some(fee_at), "{strategy:?}");
+        }
+
+        // Every other strategy has no fee tier slot
+        for strategy in StrategyType::all().filter(|s| !turbos.iter().any(|(t, ..)| t == s)) {
+            assert!(!strategy.type_arg_layout().contains(&TypeArgSlot::TurbosFee), "{strategy:?}");
+        }
+    }
+
+    #[test]
+    fn test_type_arg_layout_leads_with_path_coins() {
+        for strategy in StrategyType::all() {
+            let layout = strategy.type_arg_layout();
+            let coins: Vec<TypeArgSlot> = (0..strategy.hops()).map(TypeArgSlot::Coin).collect();
+            assert_eq!(&layout[..coins.len()], &coins[..], "{strategy:?}");
+            assert!(layout[coins.len()..].iter().all(|s| !matches!(s, TypeArgSlot::Coin(_))), "{strategy:?}");
+        }
+        // The LP type comes last, after any fee tier
+        assert_eq!(
+            StrategyType::TriCetusTurbosAftermath.type_arg_layout().last(),
+            Some(&TypeArgSlot::AftermathLp)
+        );
+        let names: Vec<String> = StrategyType::TurbosToAftermath.type_arg_layout().iter().map(|s| s.to_string()).collect();
+        assert_eq!(names, ["A", "B", "TurbosFee", "LP"]);
+    }
+
     #[test]
     fn test_move_function_names() {
         assert_eq!(StrategyType::CetusToTurbos.move_function_name(), "arb_cetus_to_turbos");
         assert_eq!(StrategyType::DeepBookToCetus.move_function_name(), "arb_deepbook_to_cetus");
         assert_eq!(StrategyType::TriCetusCetusCetus.move_function_name(), "tri_cetus_cetus_cetus");
+        assert_eq!(StrategyType::CetusToKriya.move_function_name(), "arb_cetus_to_kriya");
+        assert_eq!(StrategyType::KriyaToCetus.move_function_name(), "arb_kriya_to_cetus");
     }
 
     #[test]
@@ -239,6 +568,8 @@ mod tests {
         assert_eq!(StrategyType::DeepBookToCetus.flash_source(), Dex::DeepBook);
         assert_eq!(StrategyType::FlowxClmmToCetus.flash_source(), Dex::FlowxClmm);
         assert_eq!(StrategyType::CetusToFlowxAmm.flash_source(), Dex::Cetus);
+        assert_eq!(StrategyType::CetusToKriya.flash_source(), Dex::Cetus);
+        assert_eq!(StrategyType::KriyaToCetus.flash_source(), Dex::Kriya);
     }
 
     #[test]
@@ -248,19 +579,130 @@ mod tests {
         assert_eq!(StrategyType::TriFlowxClmmCetusTurbos.flash_source(), Dex::FlowxClmm);
     }
 
+    #[test]
+    fn test_all_lists_each_variant_once() {
+        let mut names: Vec<&str> = StrategyType::all().map(|s| s.move_function_name()).collect();
+        let total = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), total, "duplicate variant in StrategyType::ALL");
+        // Exhaustive match: adding a variant without updating ALL fails to compile here
+        for s in StrategyType::all() {
+            match s {
+                StrategyType::CetusToTurbos
+                | StrategyType::CetusToTurbosRev
+                | StrategyType::TurbosToCetus
+                | StrategyType::CetusToDeepBook
+                | StrategyType::DeepBookToCetus
+                | StrategyType::TurbosToDeepBook
+                | StrategyType::DeepBookToTurbos
+                | StrategyType::CetusToAftermath
+                | StrategyType::CetusToAftermathRev
+                | StrategyType::TurbosToAftermath
+                | StrategyType::DeepBookToAftermath
+                | StrategyType::CetusToFlowxClmm
+                | StrategyType::FlowxClmmToCetus
+                | StrategyType::TurbosToFlowxClmm
+                | StrategyType::FlowxClmmToTurbos
+                | StrategyType::DeepBookToFlowxClmm
+                | StrategyType::FlowxClmmToDeepBook
+                | StrategyType::CetusToFlowxAmm
+                | StrategyType::TurbosToFlowxAmm
+                | StrategyType::DeepBookToFlowxAmm
+                | StrategyType::CetusToKriya
+                | StrategyType::KriyaToCetus
+                | StrategyType::DirectCetusToTurbos
+                | StrategyType::DirectTurbosToCetus
+                | StrategyType::TriCetusCetusCetus
+                | StrategyType::TriCetusCetusCetusV2
+                | StrategyType::TriCetusCetusTurbos
+                | StrategyType::TriCetusTurbosDeepBook
+                | StrategyType::TriCetusDeepBookTurbos
+                | StrategyType::TriDeepBookCetusTurbos
+                | StrategyType::TriCetusCetusAftermath
+                | StrategyType::TriCetusTurbosAftermath
+                | StrategyType::TriCetusCetusFlowxClmm
+                | StrategyType::TriCetusFlowxClmmTurbos
+                | StrategyType::TriFlowxClmmCetusTurbos
+                | StrategyType::QuadCetusCetusCetusCetus => {}
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_with_flash_source() {
+        let deepbook = StrategyType::all_with_flash_source(Dex::DeepBook);
+        assert_eq!(deepbook.len(), 6);
+        assert!(deepbook.contains(&StrategyType::TriDeepBookCetusTurbos));
+        assert!(deepbook.iter().all(|s| s.flash_source() == Dex::DeepBook));
+
+        // Sell-only venues never provide the flash leg
+        assert!(StrategyType::all_with_flash_source(Dex::Aftermath).is_empty());
+        assert!(StrategyType::all_with_flash_source(Dex::FlowxAmm).is_empty());
+
+        // Flash sources partition the full set
+        let total: usize = [Dex::Cetus, Dex::Turbos, Dex::DeepBook, Dex::FlowxClmm, Dex::Kriya]
+            .into_iter()
+            .map(|d| StrategyType::all_with_flash_source(d).len())
+            .sum();
+        assert_eq!(total, StrategyType::ALL.len());
+    }
+
+    #[test]
+    fn test_direct_variants() {
+        let direct = StrategyType::CetusToTurbos.direct_variant().unwrap();
+        assert_eq!(direct, StrategyType::DirectCetusToTurbos);
+        assert!(direct.is_direct());
+        assert_eq!(direct.move_module(), "direct");
+        // Same buy leg as the flash strategy it replaces
+        assert_eq!(direct.flash_source(), StrategyType::CetusToTurbos.flash_source());
+        assert_eq!(
+            StrategyType::TurbosToCetus.direct_variant(),
+            Some(StrategyType::DirectTurbosToCetus)
+        );
+        assert_eq!(StrategyType::CetusToDeepBook.direct_variant(), None);
+        assert!(!StrategyType::CetusToTurbos.is_direct());
+    }
+
+    #[test]
+    fn test_all_tri_hop() {
+        let tri = StrategyType::all_tri_hop();
+        assert_eq!(tri.len(), 11);
+        assert!(tri.iter().all(|s| s.move_function_name().starts_with("tri_")));
+        assert!(!StrategyType::CetusToTurbos.is_tri_hop());
+    }
+
     #[test]
     fn test_min_profit_calculation() {
         // 90% of expected profit
         let opp = make_opp(StrategyType::CetusToTurbos, 2, 100_000);
-        let min_profit = opp.expected_profit * 9 / 10;
-        assert_eq!(min_profit, 90_000);
+        assert_eq!(opp.min_profit(DEFAULT_MIN_PROFIT_FRACTION), 90_000);
+        assert_eq!(opp.min_profit(0.95), 95_000);
     }
 
     #[test]
     fn test_min_profit_zero() {
+        // Floored at 1 MIST so the on-chain check is never a no-op
         let opp = make_opp(StrategyType::CetusToTurbos, 2, 0);
-        let min_profit = opp.expected_profit * 9 / 10;
-        assert_eq!(min_profit, 0);
+        assert_eq!(opp.min_profit(DEFAULT_MIN_PROFIT_FRACTION), 1);
+    }
+
+    #[test]
+    fn test_min_profit_rounds_to_nearest() {
+        // 9 × 0.9 = 8.1 → 8; 15 × 0.9 = 13.5 → 14 (truncation gave 13)
+        assert_eq!(make_opp(StrategyType::CetusToTurbos, 2, 9).min_profit(0.9), 8);
+        assert_eq!(make_opp(StrategyType::CetusToTurbos, 2, 15).min_profit(0.9), 14);
+        assert_eq!(make_opp(StrategyType::CetusToTurbos, 2, 19).min_profit(0.9), 17);
+    }
+
+    #[test]
+    fn test_min_profit_no_overflow_and_clamped() {
+        let opp = make_opp(StrategyType::CetusToTurbos, 2, u64::MAX);
+        let expected = (u64::MAX as u128 * 9 / 10) as u64;
+        assert!(opp.min_profit(0.9).abs_diff(expected) <= 1);
+        assert_eq!(opp.min_profit(1.0), u64::MAX);
+        assert_eq!(opp.min_profit(2.0), u64::MAX);
+        assert_eq!(make_opp(StrategyType::CetusToTurbos, 2, 100).min_profit(-1.0), 1);
     }
 
     #[test]
@@ -283,4 +725,23 @@ mod tests {
         let expected_pools = if opp.strategy.move_module() == "tri_hop" { 3 } else { 2 };
         assert!(opp.pool_ids.len() < expected_pools, "Should detect insufficient pool IDs");
     }
+
+    #[test]
+    fn test_strategy_name_round_trip() {
+        for strategy in StrategyType::all() {
+            let name = strategy.to_string();
+            assert_eq!(name, strategy.move_function_name());
+            assert_eq!(name.parse::<StrategyType>().unwrap(), strategy);
+            assert_eq!(name.to_uppercase().parse::<StrategyType>().unwrap(), strategy);
+        }
+        assert_eq!(" Arb_Cetus_To_Turbos ".parse::<StrategyType>().unwrap(), StrategyType::CetusToTurbos);
+    }
+
+    #[test]
+    fn test_unknown_strategy_lists_valid_names() {
+        let err = "CetusToTurbos".parse::<StrategyType>().unwrap_err().to_string();
+        assert!(err.contains("Unknown strategy 'CetusToTurbos'"));
+        assert!(err.contains("arb_cetus_to_turbos"));
+        assert!(err.contains("tri_flowx_clmm_cetus_turbos"));
+    }
 }
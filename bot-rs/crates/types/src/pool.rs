@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Unique identifier for a pool across all DEXes.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
@@ -13,6 +14,8 @@ pub enum Dex {
     Aftermath,
     FlowxClmm,
     FlowxAmm,
+    Kriya,
+    Bluefin,
 }
 
 impl std::fmt::Display for Dex {
@@ -24,10 +27,73 @@ impl std::fmt::Display for Dex {
             Dex::Aftermath => write!(f, "Aftermath"),
             Dex::FlowxClmm => write!(f, "FlowX CLMM"),
             Dex::FlowxAmm => write!(f, "FlowX AMM"),
+            Dex::Kriya => write!(f, "Kriya"),
+            Dex::Bluefin => write!(f, "Bluefin"),
         }
     }
 }
 
+impl Dex {
+    /// Every name accepted for each DEX in pool config (`MONITORED_POOLS`,
+    /// `DEX_TRADE_SIZES`), compared case-insensitively. The single source of
+    /// truth for DEX names: the parser router and the startup validator both
+    /// resolve through [`Dex::from_str`], so they can't disagree. Add an
+    /// alias here and every consumer accepts it.
+    pub const ALIASES: &'static [(&'static str, Dex)] = &[
+        ("cetus", Dex::Cetus),
+        ("turbos", Dex::Turbos),
+        ("deepbook", Dex::DeepBook),
+        ("aftermath", Dex::Aftermath),
+        ("flowx_clmm", Dex::FlowxClmm),
+        ("flowx", Dex::FlowxClmm),
+        ("flowx_amm", Dex::FlowxAmm),
+        ("kriya", Dex::Kriya),
+        ("bluefin", Dex::Bluefin),
+    ];
+}
+
+impl std::str::FromStr for Dex {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let name = name.trim();
+        Self::ALIASES
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+            .map(|(_, dex)| *dex)
+            .ok_or_else(|| {
+                let known: Vec<&str> = Self::ALIASES.iter().map(|(a, _)| *a).collect();
+                anyhow::anyhow!("Unknown DEX '{}' (expected one of: {})", name, known.join(", "))
+            })
+    }
+}
+
+/// One price level of a CLOB order book.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookLevel {
+    /// Raw quote units per raw base unit — the scale of [`PoolState::price_a_in_b`].
+    pub price: f64,
+    /// Base-coin quantity resting at this price (raw units).
+    pub quantity: u64,
+}
+
+/// Top of a CLOB order book, best level first on each side.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BookDepth {
+    pub bids: Vec<BookLevel>,
+    pub asks: Vec<BookLevel>,
+}
+
+impl BookDepth {
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.first().map(|l| l.price)
+    }
+
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.first().map(|l| l.price)
+    }
+}
+
 /// Normalized pool state — extracted from on-chain data, used by strategy scanner.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolState {
@@ -54,10 +120,17 @@ pub struct PoolState {
     /// Reserve of coin B.
     pub reserve_b: Option<u64>,
 
-    /// Best bid price for CLOB (DeepBook) — None for AMMs.
+    /// Best bid price for CLOB (DeepBook) in human units (whole quote coins
+    /// per whole base coin, decimal-normalized) — None for AMMs and an
+    /// empty side. Raw prices are in [`book_depth`](Self::book_depth).
     pub best_bid: Option<f64>,
-    /// Best ask price for CLOB.
+    /// Best ask price for CLOB, in human units.
     pub best_ask: Option<f64>,
+    /// Levels nearest the mid for CLOB pools, as last fetched, in raw
+    /// units. `None` if the book hasn't been fetched; an empty book is
+    /// `Some` with no levels.
+    #[serde(default)]
+    pub book_depth: Option<BookDepth>,
 
     /// Epoch timestamp of last update (ms since Unix epoch).
     pub last_updated_ms: u64,
@@ -66,6 +139,22 @@ pub struct PoolState {
     /// Turbos pools have a `TurbosFee` phantom type in Pool<A, B, Fee>.
     /// Must be passed as an additional type argument in Move calls.
     pub fee_type: Option<String>,
+
+    /// Venue minimum trade size (e.g. DeepBook `min_size`), in base-coin units.
+    #[serde(default)]
+    pub min_trade_size: Option<u64>,
+    /// Venue lot size — trade amounts must be a multiple of this (e.g. DeepBook `lot_size`).
+    #[serde(default)]
+    pub lot_size: Option<u64>,
+
+    /// Real balance of coin A in its base units, for pools whose
+    /// `reserve_a`/`reserve_b` only carry the price ratio (Aftermath, whose
+    /// balances overflow u64). Preferred over the reserves for depth.
+    #[serde(default)]
+    pub raw_balance_a: Option<u128>,
+    /// Real balance of coin B in its base units.
+    #[serde(default)]
+    pub raw_balance_b: Option<u128>,
 }
 
 impl PoolState {
@@ -78,10 +167,10 @@ impl PoolState {
     /// Compute the effective price of A in terms of B.
     /// For CLMM: derived from sqrt_price (only if liquidity is above minimum).
     /// For AMM: reserve_b / reserve_a.
-    /// For CLOB: midpoint of bid/ask.
+    /// For CLOB: midpoint of the top bid/ask levels.
     pub fn price_a_in_b(&self) -> Option<f64> {
         match self.dex {
-            Dex::Cetus | Dex::Turbos | Dex::FlowxClmm => {
+            Dex::Cetus | Dex::Turbos | Dex::FlowxClmm | Dex::Kriya | Dex::Bluefin => {
                 // Skip pools with zero or negligible liquidity — their sqrt_price
                 // is meaningless and creates phantom spreads in the scanner.
                 let liq = self.liquidity.unwrap_or(0);
@@ -105,8 +194,10 @@ impl PoolState {
                 // from all limit orders and have NO relationship to market price.
                 // Using reserve_b/reserve_a here would produce garbage prices that create
                 // phantom million-percent spreads against accurate CLMM pool prices.
-                // Only return a price if we have actual bid/ask data.
-                match (self.best_bid, self.best_ask) {
+                // Only return a price if we have actual bid/ask data: the
+                // raw levels, as best_bid/best_ask are decimal-normalized.
+                let book = self.book_depth.as_ref()?;
+                match (book.best_bid(), book.best_ask()) {
                     (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
                     (Some(bid), None) => Some(bid),
                     (None, Some(ask)) => Some(ask),
@@ -116,17 +207,190 @@ impl PoolState {
         }
     }
 
-    /// Returns true if this pool can be used as a flash swap source (hot-potato pattern).
+    /// Depth as `(reserve_a, reserve_b)` in raw units: actual reserves for
+    /// AMMs, virtual reserves at the current price for CLMMs
+    /// (`L / √P`, `L · √P`). `None` for DeepBook — its depth is in the
+    /// order book, which isn't modelled — and for unusable CLMM pools.
+    pub fn virtual_reserves(&self) -> Option<(f64, f64)> {
+        match self.dex {
+            Dex::Cetus | Dex::Turbos | Dex::FlowxClmm | Dex::Kriya | Dex::Bluefin => {
+                let liq = self.liquidity.filter(|&l| l >= Self::MIN_CLMM_LIQUIDITY)?;
+                let sqrt_p = self.sqrt_price.filter(|&sp| sp > 0)? as f64 / (1u128 << 64) as f64;
+                Some((liq as f64 / sqrt_p, liq as f64 * sqrt_p))
+            }
+            Dex::Aftermath | Dex::FlowxAmm => {
+                let (a, b) = self.depth()?;
+                Some((a as f64, b as f64))
+            }
+            Dex::DeepBook => None,
+        }
+    }
+
+    /// AMM depth as `(balance_a, balance_b)` in raw units: the real balances
+    /// when the parser kept them, else the reserves.
+    pub fn depth(&self) -> Option<(u128, u128)> {
+        match (self.raw_balance_a, self.raw_balance_b) {
+            (Some(a), Some(b)) => Some((a, b)),
+            _ => Some((self.reserve_a? as u128, self.reserve_b? as u128)),
+        }
+    }
+
     /// Returns true if this pool can be used as a flash swap source (hot-potato pattern).
     /// Aftermath and FlowX AMM do NOT support flash swaps (sell leg only).
     pub fn supports_flash_swap(&self) -> bool {
-        matches!(self.dex, Dex::Cetus | Dex::Turbos | Dex::DeepBook | Dex::FlowxClmm)
+        matches!(self.dex, Dex::Cetus | Dex::Turbos | Dex::DeepBook | Dex::FlowxClmm | Dex::Kriya | Dex::Bluefin)
     }
 
     /// How stale this data is (ms since last update).
     pub fn staleness_ms(&self, now_ms: u64) -> u64 {
         now_ms.saturating_sub(self.last_updated_ms)
     }
+
+    /// Trade size limits for this pool: venue-reported values, falling back
+    /// to the configured per-DEX defaults.
+    pub fn trade_limits(&self, defaults: &TradeSizeDefaults) -> TradeSizeLimits {
+        let fallback = defaults.for_dex(self.dex);
+        TradeSizeLimits {
+            min_trade_size: self.min_trade_size.unwrap_or(fallback.min_trade_size),
+            lot_size: self.lot_size.unwrap_or(fallback.lot_size).max(1),
+        }
+    }
+
+    /// Whether `other` differs materially from `self` for pricing purposes.
+    ///
+    /// Compares the price-relevant fields — sqrt price, reserves, liquidity,
+    /// bid/ask, and fee rate — and ignores bookkeeping (`last_updated_ms`,
+    /// `tick_index`, which follows sqrt price). `price_tolerance` is a
+    /// relative fraction (e.g. `0.0001` = 1 bp): a field changes materially
+    /// when it moves by more than that, appears, or disappears. A different
+    /// pool or token pair is always a material change.
+    pub fn significant_change(&self, other: &Self, price_tolerance: f64) -> bool {
+        if self.object_id != other.object_id
+            || self.dex != other.dex
+            || self.coin_type_a != other.coin_type_a
+            || self.coin_type_b != other.coin_type_b
+            || self.fee_rate_bps != other.fee_rate_bps
+        {
+            return true;
+        }
+
+        let tol = price_tolerance.max(0.0);
+        // Price is sqrt_price², so compare the squared ratio
+        let sqrt_price_moved = match (self.sqrt_price, other.sqrt_price) {
+            (Some(a), Some(b)) if a != b => {
+                a == 0 || {
+                    let ratio = b as f64 / a as f64;
+                    (ratio * ratio - 1.0).abs() > tol
+                }
+            }
+            (a, b) => a.is_some() != b.is_some(),
+        };
+
+        sqrt_price_moved
+            || moved(self.reserve_a.map(|v| v as f64), other.reserve_a.map(|v| v as f64), tol)
+            || moved(self.reserve_b.map(|v| v as f64), other.reserve_b.map(|v| v as f64), tol)
+            || moved(self.raw_balance_a.map(|v| v as f64), other.raw_balance_a.map(|v| v as f64), tol)
+            || moved(self.raw_balance_b.map(|v| v as f64), other.raw_balance_b.map(|v| v as f64), tol)
+            || moved(self.liquidity.map(|v| v as f64), other.liquidity.map(|v| v as f64), tol)
+            || moved(self.best_bid, other.best_bid, tol)
+            || moved(self.best_ask, other.best_ask, tol)
+    }
+}
+
+/// Whether an optional value appeared, disappeared, or moved by more than
+/// `tol` relative to `before`.
+fn moved(before: Option<f64>, after: Option<f64>, tol: f64) -> bool {
+    match (before, after) {
+        (Some(a), Some(b)) if a == b => false,
+        (Some(a), Some(b)) => a == 0.0 || ((b - a) / a).abs() > tol,
+        (a, b) => a.is_some() != b.is_some(),
+    }
+}
+
+/// Minimum size and lot size a trade amount must satisfy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradeSizeLimits {
+    pub min_trade_size: u64,
+    /// Always ≥ 1.
+    pub lot_size: u64,
+}
+
+impl Default for TradeSizeLimits {
+    fn default() -> Self {
+        Self { min_trade_size: 0, lot_size: 1 }
+    }
+}
+
+impl TradeSizeLimits {
+    /// Limits satisfying both `self` and `other`: the larger minimum and the
+    /// least common multiple of the lot sizes.
+    pub fn combine(self, other: Self) -> Self {
+        let (a, b) = (self.lot_size.max(1), other.lot_size.max(1));
+        let lcm = a / gcd(a, b) * b;
+        Self {
+            min_trade_size: self.min_trade_size.max(other.min_trade_size),
+            lot_size: lcm,
+        }
+    }
+
+    /// Combined limits of every pool in a route.
+    pub fn for_pools(pools: &[&PoolState], defaults: &TradeSizeDefaults) -> Self {
+        pools
+            .iter()
+            .map(|p| p.trade_limits(defaults))
+            .fold(Self::default(), Self::combine)
+    }
+
+    /// Largest multiple of the lot size ≤ `amount`.
+    pub fn round_down(&self, amount: u64) -> u64 {
+        amount - amount % self.lot_size.max(1)
+    }
+
+    /// Smallest multiple of the lot size ≥ `amount` (saturating).
+    pub fn round_up(&self, amount: u64) -> u64 {
+        let down = self.round_down(amount);
+        if down == amount {
+            amount
+        } else {
+            down.saturating_add(self.lot_size)
+        }
+    }
+
+    /// Whether `amount` is a whole number of lots at or above the minimum.
+    pub fn allows(&self, amount: u64) -> bool {
+        amount >= self.min_trade_size && amount.is_multiple_of(self.lot_size.max(1))
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Configured per-DEX trade size limits for pools whose venue doesn't report them.
+#[derive(Debug, Clone, Default)]
+pub struct TradeSizeDefaults {
+    per_dex: HashMap<Dex, TradeSizeLimits>,
+}
+
+impl TradeSizeDefaults {
+    pub fn new(per_dex: HashMap<Dex, TradeSizeLimits>) -> Self {
+        Self { per_dex }
+    }
+
+    /// Configured entries, sorted by DEX name.
+    pub fn entries(&self) -> Vec<(Dex, TradeSizeLimits)> {
+        let mut entries: Vec<_> = self.per_dex.iter().map(|(d, l)| (*d, *l)).collect();
+        entries.sort_by_key(|(d, _)| d.to_string());
+        entries
+    }
+
+    /// Defaults for `dex` (no minimum, lot size 1 if unconfigured).
+    pub fn for_dex(&self, dex: Dex) -> TradeSizeLimits {
+        self.per_dex.get(&dex).copied().unwrap_or_default()
+    }
 }
 
 /// A pair of pools trading the same token pair on different DEXes.
@@ -140,6 +404,19 @@ pub struct PoolPair {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_dex_from_str_resolves_aliases() {
+        assert_eq!("cetus".parse::<Dex>().unwrap(), Dex::Cetus);
+        assert_eq!(" DeepBook ".parse::<Dex>().unwrap(), Dex::DeepBook);
+        assert_eq!("flowx".parse::<Dex>().unwrap(), Dex::FlowxClmm);
+        assert_eq!("FLOWX_CLMM".parse::<Dex>().unwrap(), Dex::FlowxClmm);
+        assert_eq!("flowx_amm".parse::<Dex>().unwrap(), Dex::FlowxAmm);
+        assert_eq!("Kriya".parse::<Dex>().unwrap(), Dex::Kriya);
+        assert_eq!("BLUEFIN".parse::<Dex>().unwrap(), Dex::Bluefin);
+        let err = "momentum".parse::<Dex>().unwrap_err().to_string();
+        assert!(err.contains("Unknown DEX 'momentum'") && err.contains("bluefin"));
+    }
+
     fn base_pool(dex: Dex) -> PoolState {
         PoolState {
             object_id: "0x1".into(),
@@ -154,11 +431,52 @@ mod tests {
             reserve_b: None,
             best_bid: None,
             best_ask: None,
+            book_depth: None,
             last_updated_ms: 1000,
             fee_type: None,
+            min_trade_size: None,
+            lot_size: None,
+            raw_balance_a: None,
+            raw_balance_b: None,
         }
     }
 
+    // ── virtual_reserves tests ──
+
+    #[test]
+    fn test_clmm_virtual_reserves() {
+        let mut p = base_pool(Dex::Cetus);
+        p.sqrt_price = Some(2u128 << 64); // price 4
+        p.liquidity = Some(1_000_000_000);
+        let (x, y) = p.virtual_reserves().unwrap();
+        assert!((x - 500_000_000.0).abs() < 1.0);
+        assert!((y - 2_000_000_000.0).abs() < 1.0);
+        assert!((y / x - 4.0).abs() < 1e-9, "virtual reserves must imply the pool price");
+
+        p.liquidity = Some(1_000);
+        assert_eq!(p.virtual_reserves(), None);
+    }
+
+    #[test]
+    fn test_amm_and_clob_virtual_reserves() {
+        let mut p = base_pool(Dex::Aftermath);
+        p.reserve_a = Some(10);
+        assert_eq!(p.virtual_reserves(), None);
+        p.reserve_b = Some(20);
+        assert_eq!(p.virtual_reserves(), Some((10.0, 20.0)));
+
+        // Real balances give the depth; the reserves still give the price
+        p.raw_balance_a = Some(30_000_000_000_000_000_000);
+        p.raw_balance_b = Some(60_000_000_000_000_000_000);
+        assert_eq!(p.virtual_reserves(), Some((3e19, 6e19)));
+        assert_eq!(p.price_a_in_b(), Some(2.0));
+
+        let mut book = base_pool(Dex::DeepBook);
+        book.reserve_a = Some(10);
+        book.reserve_b = Some(20);
+        assert_eq!(book.virtual_reserves(), None);
+    }
+
     // ── price_a_in_b tests ──
 
     #[test]
@@ -224,28 +542,46 @@ mod tests {
         assert!(base_pool(Dex::Aftermath).price_a_in_b().is_none());
     }
 
+    /// A DeepBook pool with one level at each given raw price.
+    fn book_pool(bid: Option<f64>, ask: Option<f64>) -> PoolState {
+        let level = |price| BookLevel { price, quantity: 1_000 };
+        PoolState {
+            book_depth: Some(BookDepth {
+                bids: bid.map(level).into_iter().collect(),
+                asks: ask.map(level).into_iter().collect(),
+            }),
+            ..base_pool(Dex::DeepBook)
+        }
+    }
+
     #[test]
     fn test_deepbook_price_midpoint() {
-        let mut p = base_pool(Dex::DeepBook);
-        p.best_bid = Some(2.0);
-        p.best_ask = Some(3.0);
+        let p = book_pool(Some(2.0), Some(3.0));
         assert!((p.price_a_in_b().unwrap() - 2.5).abs() < 0.001);
     }
 
     #[test]
     fn test_deepbook_price_bid_only() {
-        let mut p = base_pool(Dex::DeepBook);
-        p.best_bid = Some(2.5);
+        let p = book_pool(Some(2.5), None);
         assert!((p.price_a_in_b().unwrap() - 2.5).abs() < 0.001);
     }
 
     #[test]
     fn test_deepbook_price_ask_only() {
-        let mut p = base_pool(Dex::DeepBook);
-        p.best_ask = Some(3.0);
+        let p = book_pool(None, Some(3.0));
         assert!((p.price_a_in_b().unwrap() - 3.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_deepbook_price_ignores_human_top_of_book() {
+        // best_bid/best_ask are decimal-normalized: the raw price is the book's
+        let mut p = book_pool(Some(0.0035), Some(0.0036));
+        p.best_bid = Some(3.5);
+        p.best_ask = Some(3.6);
+        assert!((p.price_a_in_b().unwrap() - 0.00355).abs() < 1e-12);
+        assert!(book_pool(None, None).price_a_in_b().is_none());
+    }
+
     #[test]
     fn test_deepbook_no_fallback_to_reserves() {
         // DeepBook is a CLOB — vault reserves don't reflect market price.
@@ -272,6 +608,8 @@ mod tests {
         assert!(base_pool(Dex::Turbos).supports_flash_swap());
         assert!(base_pool(Dex::DeepBook).supports_flash_swap());
         assert!(base_pool(Dex::FlowxClmm).supports_flash_swap());
+        assert!(base_pool(Dex::Kriya).supports_flash_swap());
+        assert!(base_pool(Dex::Bluefin).supports_flash_swap());
         assert!(!base_pool(Dex::Aftermath).supports_flash_swap());
         assert!(!base_pool(Dex::FlowxAmm).supports_flash_swap());
     }
@@ -286,6 +624,118 @@ mod tests {
         assert_eq!(p.staleness_ms(500), 0); // saturating_sub
     }
 
+    // ── trade_limits ──
+
+    #[test]
+    fn test_trade_limits_prefers_venue_values_over_defaults() {
+        let defaults = TradeSizeDefaults::new(HashMap::from([(
+            Dex::DeepBook,
+            TradeSizeLimits { min_trade_size: 5, lot_size: 10 },
+        )]));
+        let mut pool = base_pool(Dex::DeepBook);
+        assert_eq!(pool.trade_limits(&defaults), TradeSizeLimits { min_trade_size: 5, lot_size: 10 });
+
+        pool.min_trade_size = Some(1_000);
+        pool.lot_size = Some(100);
+        assert_eq!(pool.trade_limits(&defaults), TradeSizeLimits { min_trade_size: 1_000, lot_size: 100 });
+        assert_eq!(base_pool(Dex::Cetus).trade_limits(&defaults), TradeSizeLimits::default());
+    }
+
+    #[test]
+    fn test_trade_limits_combine_and_round() {
+        let a = TradeSizeLimits { min_trade_size: 1_000, lot_size: 4 };
+        let b = TradeSizeLimits { min_trade_size: 500, lot_size: 6 };
+        let c = a.combine(b);
+        assert_eq!(c, TradeSizeLimits { min_trade_size: 1_000, lot_size: 12 });
+        assert_eq!(c.round_down(1_234_567), 1_234_560);
+        assert_eq!(c.round_up(1_234_567), 1_234_572);
+        assert_eq!(c.round_up(1_200), 1_200);
+        assert!(c.allows(1_200));
+        assert!(!c.allows(1_201));
+        assert!(!c.allows(996)); // whole lots but below minimum
+    }
+
+    // ── significant_change ──
+
+    fn clmm_pool() -> PoolState {
+        let mut p = base_pool(Dex::Cetus);
+        p.sqrt_price = Some(1u128 << 64);
+        p.liquidity = Some(1_000_000_000);
+        p.fee_rate_bps = Some(25);
+        p
+    }
+
+    #[test]
+    fn test_significant_change_ignores_timestamp_and_tick() {
+        let a = clmm_pool();
+        let mut b = a.clone();
+        b.last_updated_ms = 99_999;
+        b.tick_index = Some(7);
+        assert!(!a.significant_change(&b, 0.0));
+    }
+
+    #[test]
+    fn test_significant_change_clmm_noise_vs_material() {
+        let a = clmm_pool();
+        let mut b = a.clone();
+        // sqrt +0.001% → price ≈ +0.002%, below a 1 bp tolerance
+        b.sqrt_price = Some((1u128 << 64) + (1u128 << 64) / 100_000);
+        assert!(!a.significant_change(&b, 0.0001));
+        assert!(a.significant_change(&b, 0.00001));
+
+        // sqrt +0.1% → price ≈ +0.2%
+        b.sqrt_price = Some((1u128 << 64) + (1u128 << 64) / 1_000);
+        assert!(a.significant_change(&b, 0.0001));
+
+        let mut c = a.clone();
+        c.liquidity = Some(1_000_050_000); // +0.005%
+        assert!(!a.significant_change(&c, 0.0001));
+        c.liquidity = Some(1_500_000_000);
+        assert!(a.significant_change(&c, 0.0001));
+    }
+
+    #[test]
+    fn test_significant_change_amm_and_clob() {
+        let mut a = base_pool(Dex::Aftermath);
+        a.reserve_a = Some(1_000_000);
+        a.reserve_b = Some(2_000_000);
+        let mut b = a.clone();
+        b.reserve_b = Some(2_000_010); // +0.0005%
+        assert!(!a.significant_change(&b, 0.0001));
+        b.reserve_b = Some(2_100_000);
+        assert!(a.significant_change(&b, 0.0001));
+
+        let mut c = base_pool(Dex::DeepBook);
+        c.best_bid = Some(1.0000);
+        c.best_ask = Some(1.0010);
+        let mut d = c.clone();
+        d.best_ask = Some(1.00101);
+        assert!(!c.significant_change(&d, 0.0001));
+        d.best_bid = Some(0.99);
+        assert!(c.significant_change(&d, 0.0001));
+    }
+
+    #[test]
+    fn test_significant_change_presence_identity_and_fee() {
+        let a = clmm_pool();
+        let mut b = a.clone();
+        b.liquidity = None;
+        assert!(a.significant_change(&b, 1.0));
+
+        let mut c = a.clone();
+        c.fee_rate_bps = Some(30);
+        assert!(a.significant_change(&c, 1.0));
+
+        let mut d = a.clone();
+        d.object_id = "0x2".into();
+        assert!(a.significant_change(&d, 1.0));
+
+        let mut e = a.clone();
+        e.sqrt_price = Some(0);
+        assert!(a.significant_change(&e, 0.5));
+        assert!(e.significant_change(&a, 0.5));
+    }
+
     // ── Dex Display ──
 
     #[test]
@@ -294,5 +744,7 @@ mod tests {
         assert_eq!(format!("{}", Dex::DeepBook), "DeepBook");
         assert_eq!(format!("{}", Dex::FlowxClmm), "FlowX CLMM");
         assert_eq!(format!("{}", Dex::FlowxAmm), "FlowX AMM");
+        assert_eq!(format!("{}", Dex::Kriya), "Kriya");
+        assert_eq!(format!("{}", Dex::Bluefin), "Bluefin");
     }
 }
@@ -0,0 +1,326 @@
+//! Append-only line files with rotation, compression, and retention.
+//!
+//! The live file keeps its configured name. On rotation it is renamed to
+//! `<name>.<rotated_at_ms>` (gzipped to `<name>.<rotated_at_ms>.gz` when
+//! compression is on) and a fresh live file is started. Segments older than
+//! the retention period are pruned after each rotation; the timestamp in the
+//! segment name, not the file's mtime, decides its age.
+//!
+//! Each line goes out in a single write, and a torn last line left by an
+//! abrupt stop is terminated on open, so earlier lines are never corrupted
+//! and the next line always starts cleanly.
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+const DAY_MS: u64 = 86_400_000;
+
+/// When to rotate and what to keep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RotationPolicy {
+    /// Roll once the live file reaches this many bytes (0 = no size limit)
+    pub max_bytes: u64,
+    /// Roll at each UTC day boundary
+    pub daily: bool,
+    /// Gzip rotated segments
+    pub compress: bool,
+    /// Delete segments older than this many days (0 = keep forever)
+    pub retention_days: u64,
+}
+
+impl Default for RotationPolicy {
+    fn default() -> Self {
+        Self {
+            max_bytes: 0,
+            daily: true,
+            compress: true,
+            retention_days: 0,
+        }
+    }
+}
+
+/// An append-only file that rotates per its [`RotationPolicy`].
+#[derive(Debug)]
+pub struct RotatingFile {
+    path: PathBuf,
+    policy: RotationPolicy,
+    file: File,
+    size: u64,
+    /// UTC day (ms / DAY_MS) the live file was started on
+    day: u64,
+}
+
+impl RotatingFile {
+    /// Open (or create) the live file at `path`, appending to existing content.
+    pub fn open(path: impl Into<PathBuf>, policy: RotationPolicy, now_ms: u64) -> Result<Self> {
+        let path = path.into();
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let mut file = open_append(&path)?;
+        if ends_mid_line(&path)? {
+            file.write_all(b"\n")?;
+        }
+        let meta = file.metadata()?;
+        // An existing file belongs to the day it was last written
+        let last_write_ms = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(now_ms, |d| d.as_millis() as u64);
+        Ok(Self {
+            path,
+            policy,
+            size: meta.len(),
+            day: if meta.len() > 0 { last_write_ms / DAY_MS } else { now_ms / DAY_MS },
+            file,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `line` plus a newline, rotating first if the policy says so.
+    pub fn append_line(&mut self, line: &str, now_ms: u64) -> Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.should_rotate(len, now_ms) {
+            self.rotate(now_ms)?;
+        }
+        let mut buf = Vec::with_capacity(len as usize);
+        buf.extend_from_slice(line.as_bytes());
+        buf.push(b'\n');
+        self.file.write_all(&buf)?;
+        self.size += len;
+        Ok(())
+    }
+
+    fn should_rotate(&self, incoming: u64, now_ms: u64) -> bool {
+        (self.policy.max_bytes > 0 && self.size + incoming > self.policy.max_bytes)
+            || (self.policy.daily && now_ms / DAY_MS != self.day)
+    }
+
+    /// Close the live file as a segment and start a new one. Returns the
+    /// segment's path.
+    pub fn rotate(&mut self, now_ms: u64) -> Result<PathBuf> {
+        self.file.flush()?;
+        let mut segment = self.segment_path(now_ms, 0);
+        let mut n = 0;
+        while segment.exists() || gz_path(&segment).exists() {
+            n += 1;
+            segment = self.segment_path(now_ms, n);
+        }
+        fs::rename(&self.path, &segment)
+            .with_context(|| format!("Failed to rotate {}", self.path.display()))?;
+        self.file = open_append(&self.path)?;
+        self.size = 0;
+        self.day = now_ms / DAY_MS;
+
+        let segment = if self.policy.compress { compress(&segment)? } else { segment };
+        self.prune(now_ms)?;
+        Ok(segment)
+    }
+
+    /// Delete segments older than the retention period. Returns how many.
+    pub fn prune(&self, now_ms: u64) -> Result<usize> {
+        if self.policy.retention_days == 0 {
+            return Ok(0);
+        }
+        let cutoff = now_ms.saturating_sub(self.policy.retention_days * DAY_MS);
+        let mut removed = 0;
+        for (path, rotated_at) in self.segments()? {
+            if rotated_at < cutoff {
+                fs::remove_file(&path).with_context(|| format!("Failed to prune {}", path.display()))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Rotated segments of this file with their rotation timestamps, oldest first.
+    pub fn segments(&self) -> Result<Vec<(PathBuf, u64)>> {
+        let dir = match self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            Some(d) => d.to_path_buf(),
+            None => PathBuf::from("."),
+        };
+        let prefix = format!("{}.", self.file_name());
+        let mut segments: Vec<(PathBuf, u64)> = fs::read_dir(&dir)?
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let name = e.file_name().into_string().ok()?;
+                let stamp = name.strip_prefix(&prefix)?;
+                let stamp = stamp.strip_suffix(".gz").unwrap_or(stamp);
+                let rotated_at = stamp.split('-').next()?.parse().ok()?;
+                Some((e.path(), rotated_at))
+            })
+            .collect();
+        segments.sort_by_key(|(_, t)| *t);
+        Ok(segments)
+    }
+
+    /// Every line in the rotated segments (oldest first, decompressing as
+    /// needed) and then the live file.
+    pub fn read_lines(&self) -> Result<Vec<String>> {
+        let mut lines = Vec::new();
+        for (path, _) in self.segments()? {
+            let file = File::open(&path).with_context(|| format!("Failed to open {}", path.display()))?;
+            let reader: Box<dyn Read> = if path.extension().is_some_and(|e| e == "gz") {
+                Box::new(GzDecoder::new(file))
+            } else {
+                Box::new(file)
+            };
+            for line in BufReader::new(reader).lines() {
+                lines.push(line.with_context(|| format!("Failed to read {}", path.display()))?);
+            }
+        }
+        let live = File::open(&self.path).with_context(|| format!("Failed to open {}", self.path.display()))?;
+        for line in BufReader::new(live).lines() {
+            lines.push(line?);
+        }
+        Ok(lines)
+    }
+
+    fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    fn segment_path(&self, now_ms: u64, n: u32) -> PathBuf {
+        let name = if n == 0 {
+            format!("{}.{}", self.file_name(), now_ms)
+        } else {
+            format!("{}.{}-{}", self.file_name(), now_ms, n)
+        };
+        self.path.with_file_name(name)
+    }
+}
+
+fn open_append(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))
+}
+
+/// Whether a non-empty file's last byte isn't a newline (a torn write).
+fn ends_mid_line(path: &Path) -> Result<bool> {
+    let mut file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(false);
+    }
+    file.seek(SeekFrom::End(-1))?;
+    let mut last = [0u8; 1];
+    file.read_exact(&mut last)?;
+    Ok(last[0] != b'\n')
+}
+
+fn gz_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".gz");
+    PathBuf::from(name)
+}
+
+/// Gzip `path` to `<path>.gz` and remove the original.
+fn compress(path: &Path) -> Result<PathBuf> {
+    let out = gz_path(path);
+    let mut encoder = GzEncoder::new(File::create(&out)?, Compression::default());
+    io::copy(&mut File::open(path)?, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+    fs::remove_file(path)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("arb-rotating-{}-{}", name, nanos));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_rotates_at_size_limit() {
+        let dir = temp_dir("size");
+        let policy = RotationPolicy { max_bytes: 10, daily: false, compress: false, retention_days: 0 };
+        let mut f = RotatingFile::open(dir.join("trades.jsonl"), policy, 0).unwrap();
+        f.append_line("aaaa", 1).unwrap();
+        f.append_line("bbbb", 2).unwrap();
+        // 10 bytes written; the next line would exceed the limit
+        f.append_line("cccc", 3).unwrap();
+
+        let segments = f.segments().unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(fs::read_to_string(&segments[0].0).unwrap(), "aaaa\nbbbb\n");
+        assert_eq!(fs::read_to_string(f.path()).unwrap(), "cccc\n");
+    }
+
+    #[test]
+    fn test_daily_rotation_compresses_segment() {
+        let dir = temp_dir("daily");
+        let mut f = RotatingFile::open(dir.join("trades.jsonl"), RotationPolicy::default(), 0).unwrap();
+        f.append_line("day0", 1_000).unwrap();
+        f.append_line("day0 again", DAY_MS - 1).unwrap();
+        f.append_line("day1", DAY_MS).unwrap();
+
+        let segments = f.segments().unwrap();
+        assert_eq!(segments.len(), 1);
+        assert!(segments[0].0.to_string_lossy().ends_with(".gz"));
+        let mut text = String::new();
+        GzDecoder::new(File::open(&segments[0].0).unwrap())
+            .read_to_string(&mut text)
+            .unwrap();
+        assert_eq!(text, "day0\nday0 again\n");
+        assert_eq!(fs::read_to_string(f.path()).unwrap(), "day1\n");
+    }
+
+    #[test]
+    fn test_retention_prunes_old_segments() {
+        let dir = temp_dir("retention");
+        let policy = RotationPolicy { max_bytes: 0, daily: true, compress: false, retention_days: 2 };
+        let mut f = RotatingFile::open(dir.join("replay.jsonl"), policy, 0).unwrap();
+        for day in 0..5 {
+            f.append_line(&format!("day{}", day), day * DAY_MS).unwrap();
+        }
+        // Segments rotated on days 1..4; day 1's is past retention by day 4
+        let days: Vec<u64> = f.segments().unwrap().iter().map(|(_, t)| t / DAY_MS).collect();
+        assert_eq!(days, vec![2, 3, 4]);
+
+        // Unrelated files in the directory are left alone
+        fs::write(dir.join("other.log.123"), "x").unwrap();
+        assert_eq!(f.prune(10 * DAY_MS).unwrap(), 3);
+        assert!(dir.join("other.log.123").exists());
+    }
+
+    #[test]
+    fn test_torn_line_terminated_and_history_read_back() {
+        let dir = temp_dir("torn");
+        let path = dir.join("trades.jsonl");
+        let policy = RotationPolicy { max_bytes: 12, daily: false, compress: true, retention_days: 0 };
+        let mut f = RotatingFile::open(&path, policy.clone(), 0).unwrap();
+        f.append_line("one", 1).unwrap();
+        f.append_line("two", 2).unwrap();
+        f.append_line("three", 3).unwrap();
+        f.append_line("four", 4).unwrap();
+        drop(f);
+        // Killed mid-write
+        fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"partial").unwrap();
+
+        let mut f = RotatingFile::open(&path, policy, 5).unwrap();
+        f.append_line("five", 5).unwrap();
+        assert_eq!(f.read_lines().unwrap(), vec!["one", "two", "three", "four", "{\"partial", "five"]);
+    }
+}
@@ -0,0 +1,419 @@
+//! Shared JSON-RPC client: [`RpcClient`] spreads single calls over several
+//! fullnodes with failover, and [`BatchRpc`] sends batch calls (an array of
+//! requests in one HTTP round trip), with a per-endpoint fallback for
+//! providers that reject batches.
+
+use anyhow::{Context, Result};
+use futures_util::{stream, StreamExt};
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::config::Config;
+
+/// Most single requests in flight at once when a batch falls back to them,
+/// so a large batch doesn't open a burst of connections to the provider.
+pub const MAX_CONCURRENT_FALLBACK_CALLS: usize = 8;
+
+/// One JSON-RPC method call.
+#[derive(Debug, Clone)]
+pub struct RpcCall {
+    pub method: &'static str,
+    pub params: Value,
+}
+
+impl RpcCall {
+    pub fn new(method: &'static str, params: Value) -> Self {
+        Self { method, params }
+    }
+
+    fn body(&self, id: usize) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": self.method,
+            "params": self.params,
+        })
+    }
+}
+
+/// Responses to a batch, in call order.
+///
+/// Each item is the full response envelope (`{"result": ..}` or
+/// `{"error": ..}`), so callers parse it exactly like a single response.
+/// An `Err` item means no usable response arrived for that call.
+#[derive(Debug)]
+pub struct BatchResponse {
+    pub items: Vec<Result<Value>>,
+    /// Whether the calls went out as a single batch request.
+    pub batched: bool,
+}
+
+/// Send a single JSON-RPC call to one endpoint and return its response
+/// envelope (the batch fallback; everything else goes through [`RpcClient`]).
+async fn call(client: &Client, url: &str, call: &RpcCall) -> Result<Value> {
+    client
+        .post(url)
+        .json(&call.body(1))
+        .send()
+        .await
+        .with_context(|| format!("{} request failed", call.method))?
+        .json()
+        .await
+        .with_context(|| format!("Failed to parse {} response", call.method))
+}
+
+/// Batch JSON-RPC client state: remembers which endpoints reject batches so
+/// the probe happens once per endpoint, not once per cycle.
+#[derive(Debug, Default)]
+pub struct BatchRpc {
+    rejected: Mutex<HashSet<String>>,
+}
+
+impl BatchRpc {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `url` has rejected a batch before.
+    pub fn is_rejected(&self, url: &str) -> bool {
+        self.rejected.lock().map(|r| r.contains(url)).unwrap_or(false)
+    }
+
+    /// Send `calls` as one batch request, falling back to concurrent single
+    /// requests (at most [`MAX_CONCURRENT_FALLBACK_CALLS`] at a time) if the
+    /// endpoint is known to (or now does) reject batches.
+    ///
+    /// Per-item RPC errors are returned in their item's envelope and don't
+    /// affect the other items. Only a transport failure on the batch itself
+    /// fails every item.
+    pub async fn call_batch(&self, client: &Client, url: &str, calls: &[RpcCall]) -> BatchResponse {
+        if calls.len() > 1 && !self.is_rejected(url) {
+            match send_batch(client, url, calls).await {
+                Ok(Some(items)) => return BatchResponse { items, batched: true },
+                Ok(None) => {
+                    warn!(url = %url, "RPC endpoint rejected batch request — using single requests");
+                    if let Ok(mut r) = self.rejected.lock() {
+                        r.insert(url.to_string());
+                    }
+                }
+                Err(e) => {
+                    let msg = format!("{:#}", e);
+                    return BatchResponse {
+                        items: calls.iter().map(|_| Err(anyhow::anyhow!("{}", msg))).collect(),
+                        batched: true,
+                    };
+                }
+            }
+        }
+
+        // Futures collected up front: a lazy `map` closure in the stream
+        // makes callers' futures fail the `Send` check
+        let requests: Vec<_> = calls.iter().map(|c| call(client, url, c)).collect();
+        let items = stream::iter(requests)
+            .buffered(MAX_CONCURRENT_FALLBACK_CALLS)
+            .collect()
+            .await;
+        BatchResponse { items, batched: false }
+    }
+}
+
+/// POST `calls` as a JSON array. `Ok(None)` if the endpoint doesn't support
+/// batches (non-2xx status or a non-array reply).
+async fn send_batch(client: &Client, url: &str, calls: &[RpcCall]) -> Result<Option<Vec<Result<Value>>>> {
+    let body: Vec<Value> = calls.iter().enumerate().map(|(id, c)| c.body(id)).collect();
+    let response = client
+        .post(url)
+        .json(&body)
+        .send()
+        .await
+        .context("Batch RPC request failed")?;
+    if !response.status().is_success() {
+        return Ok(None);
+    }
+
+    let reply: Value = match response.json().await {
+        Ok(v) => v,
+        Err(_) => return Ok(None),
+    };
+    Ok(correlate(reply, calls.len()))
+}
+
+/// Match batch reply items to request ids `0..n`. Providers may reorder
+/// items, so correlation is by id, never by position. `None` if the reply
+/// isn't an array (i.e. the batch itself was rejected).
+fn correlate(reply: Value, n: usize) -> Option<Vec<Result<Value>>> {
+    let Value::Array(reply) = reply else {
+        return None;
+    };
+
+    let mut by_id: HashMap<u64, Value> = reply
+        .into_iter()
+        .filter_map(|item| Some((item.get("id")?.as_u64()?, item)))
+        .collect();
+
+    Some(
+        (0..n as u64)
+            .map(|id| {
+                by_id
+                    .remove(&id)
+                    .with_context(|| format!("No response for batch item {}", id))
+            })
+            .collect(),
+    )
+}
+
+/// Default per-request timeout for an [`RpcClient`].
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Backoff after an endpoint's first failure; doubles per consecutive
+/// failure up to [`MAX_ENDPOINT_BACKOFF`].
+const ENDPOINT_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_ENDPOINT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// JSON-RPC client over one or more fullnodes, in failover order.
+///
+/// A call goes to the first endpoint that isn't backing off. A timeout,
+/// transport error, 429/5xx, or unreadable reply marks that endpoint down,
+/// with exponential backoff reset by its next success, and the call moves
+/// on to the next endpoint. Endpoints that are backing off are still
+/// tried, last, so a single-endpoint client always makes its attempt. A
+/// JSON-RPC `error` reply is the call's problem, not the endpoint's, and
+/// comes back in the envelope like any other reply.
+///
+/// Clones share endpoint health, so one client handed to every component
+/// steers all of them off a failing node at once.
+#[derive(Debug, Clone)]
+pub struct RpcClient {
+    client: Client,
+    endpoints: Arc<[Endpoint]>,
+    batch: Arc<BatchRpc>,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: String,
+    health: Mutex<Health>,
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    /// Skip (try last) until then
+    retry_at: Option<Instant>,
+}
+
+/// A call that no endpoint answered.
+#[derive(Debug, thiserror::Error)]
+#[error("{method} failed on every RPC endpoint: {last_error}")]
+pub struct RpcError {
+    pub method: String,
+    pub last_error: String,
+    reached_node: bool,
+    rate_limited: bool,
+}
+
+impl RpcError {
+    /// Whether any attempt may have been processed by a node (a timeout, a
+    /// dropped connection, a 5xx, an unreadable reply), as opposed to every
+    /// attempt being refused outright (connection refused, rate limited).
+    pub fn may_have_reached_node(&self) -> bool {
+        self.reached_node
+    }
+
+    /// Whether any endpoint refused the call with HTTP 429.
+    pub fn is_rate_limited(&self) -> bool {
+        self.rate_limited
+    }
+}
+
+/// One failed attempt against one endpoint.
+struct Attempt {
+    error: String,
+    reached_node: bool,
+    rate_limited: bool,
+}
+
+impl RpcClient {
+    /// Client over `urls`, in failover order.
+    pub fn new(urls: &[String]) -> Result<Self> {
+        anyhow::ensure!(!urls.is_empty(), "At least one RPC URL is required");
+        let endpoints = urls
+            .iter()
+            .map(|url| Endpoint { url: url.clone(), health: Mutex::new(Health::default()) })
+            .collect();
+        Ok(Self { client: http_client(DEFAULT_RPC_TIMEOUT), endpoints, batch: Arc::new(BatchRpc::new()) })
+    }
+
+    /// Client over `config`'s endpoints (`SUI_RPC_URLS`, else `SUI_RPC_URL`).
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(&config.rpc_endpoints()).expect("rpc_endpoints is never empty")
+    }
+
+    /// Client over a single endpoint.
+    pub fn single(url: &str) -> Self {
+        Self::new(&[url.to_string()]).expect("one URL")
+    }
+
+    /// Bound each request (per endpoint attempt). The returned client still
+    /// shares endpoint health with `self`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.client = http_client(timeout);
+        self
+    }
+
+    /// All endpoints, in failover order.
+    pub fn urls(&self) -> impl Iterator<Item = &str> {
+        self.endpoints.iter().map(|e| e.url.as_str())
+    }
+
+    /// The endpoint a call would try first right now.
+    pub fn preferred_url(&self) -> &str {
+        &self.endpoints[self.order(Instant::now())[0]].url
+    }
+
+    /// Endpoint indices to try: healthy ones in list order, then those
+    /// backing off, soonest retry first.
+    fn order(&self, now: Instant) -> Vec<usize> {
+        let retry_at = |i: usize| self.endpoints[i].health.lock().map(|h| h.retry_at).unwrap_or(None);
+        let (mut ready, mut waiting): (Vec<usize>, Vec<usize>) =
+            (0..self.endpoints.len()).partition(|&i| retry_at(i).is_none_or(|t| t <= now));
+        ready.sort_unstable();
+        waiting.sort_by_key(|&i| retry_at(i));
+        ready.extend(waiting);
+        ready
+    }
+
+    /// Send one call and return its response envelope (`{"result": ..}` or
+    /// `{"error": ..}`), failing over across endpoints.
+    pub async fn call(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut last_error = String::new();
+        let mut reached_node = false;
+        let mut rate_limited = false;
+
+        for i in self.order(Instant::now()) {
+            let endpoint = &self.endpoints[i];
+            match send(&self.client, &endpoint.url, &body).await {
+                Ok(reply) => {
+                    endpoint.succeeded();
+                    return Ok(reply);
+                }
+                Err(attempt) => {
+                    endpoint.failed(&attempt.error, Instant::now());
+                    reached_node |= attempt.reached_node;
+                    rate_limited |= attempt.rate_limited;
+                    last_error = attempt.error;
+                }
+            }
+        }
+        Err(RpcError { method: method.to_string(), last_error, reached_node, rate_limited })
+    }
+
+    /// Send `calls` as one batch (see [`BatchRpc::call_batch`]). If no item
+    /// got a response the endpoint is marked down and the batch moves on to
+    /// the next one.
+    pub async fn call_batch(&self, calls: &[RpcCall]) -> BatchResponse {
+        let mut response = BatchResponse { items: Vec::new(), batched: false };
+        for i in self.order(Instant::now()) {
+            let endpoint = &self.endpoints[i];
+            response = self.batch.call_batch(&self.client, &endpoint.url, calls).await;
+            match response.items.iter().find_map(|item| item.as_ref().err()) {
+                Some(e) if response.items.iter().all(Result::is_err) => {
+                    endpoint.failed(&format!("{:#}", e), Instant::now());
+                }
+                _ => {
+                    endpoint.succeeded();
+                    break;
+                }
+            }
+        }
+        response
+    }
+}
+
+impl Endpoint {
+    fn succeeded(&self) {
+        let Ok(mut health) = self.health.lock() else { return };
+        if health.consecutive_failures > 0 {
+            info!(url = %self.url, failures = %health.consecutive_failures, "RPC endpoint recovered");
+        }
+        *health = Health::default();
+    }
+
+    fn failed(&self, error: &str, now: Instant) {
+        let Ok(mut health) = self.health.lock() else { return };
+        health.consecutive_failures += 1;
+        let backoff = ENDPOINT_BACKOFF
+            .saturating_mul(1 << (health.consecutive_failures - 1).min(16))
+            .min(MAX_ENDPOINT_BACKOFF);
+        health.retry_at = Some(now + backoff);
+        if health.consecutive_failures == 1 {
+            warn!(url = %self.url, error = %error, "RPC endpoint failed — failing over");
+        }
+    }
+}
+
+/// POST one JSON-RPC body. Errors say whether the node may have seen it.
+async fn send(client: &Client, url: &str, body: &Value) -> Result<Value, Attempt> {
+    let response = client.post(url).json(body).send().await.map_err(|e| Attempt {
+        // A refused connection never reached the node
+        reached_node: !e.is_connect() || e.is_timeout(),
+        rate_limited: false,
+        error: format!("Request to {} failed: {}", url, e),
+    })?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+        return Err(Attempt {
+            reached_node: status.is_server_error(),
+            rate_limited: status == reqwest::StatusCode::TOO_MANY_REQUESTS,
+            error: format!("{} returned HTTP {}", url, status),
+        });
+    }
+    response.json().await.map_err(|e| Attempt {
+        reached_node: true,
+        rate_limited: false,
+        error: format!("Unreadable reply from {} (HTTP {}): {}", url, status, e),
+    })
+}
+
+fn http_client(timeout: Duration) -> Client {
+    Client::builder()
+        .timeout(timeout)
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correlate_by_id_not_position() {
+        let reply = json!([
+            { "jsonrpc": "2.0", "id": 1, "result": "b" },
+            { "jsonrpc": "2.0", "id": 0, "result": "a" },
+        ]);
+        let items = correlate(reply, 2).unwrap();
+        assert_eq!(items[0].as_ref().unwrap()["result"], "a");
+        assert_eq!(items[1].as_ref().unwrap()["result"], "b");
+    }
+
+    #[test]
+    fn test_correlate_missing_item_fails_only_that_item() {
+        let reply = json!([{ "jsonrpc": "2.0", "id": 0, "result": "a" }]);
+        let items = correlate(reply, 2).unwrap();
+        assert!(items[0].is_ok());
+        assert!(items[1].is_err());
+    }
+
+    #[test]
+    fn test_correlate_non_array_is_rejection() {
+        let reply = json!({ "jsonrpc": "2.0", "id": null, "error": { "code": -32600 } });
+        assert!(correlate(reply, 2).is_none());
+    }
+}
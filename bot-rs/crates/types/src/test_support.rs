@@ -0,0 +1,114 @@
+//! Helpers shared by the workspace's integration tests (`test-support`
+//! feature): a mock HTTP/JSON-RPC server. See also [`Config::for_tests`].
+//!
+//! [`Config::for_tests`]: crate::Config::for_tests
+
+use serde_json::Value;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// How the mock answers one request.
+#[derive(Debug, Clone)]
+pub enum Reply {
+    /// 200 with a JSON body.
+    Json(Value),
+    /// Any status with a raw body (e.g. a truncated JSON document).
+    Raw(u16, String),
+    /// `reply`, after a delay.
+    After(Duration, Box<Reply>),
+    /// Never answer (the client's timeout fires).
+    Hang,
+}
+
+impl Reply {
+    /// A bodiless response with `code`.
+    pub fn status(code: u16) -> Self {
+        Reply::Raw(code, String::new())
+    }
+}
+
+/// Minimal HTTP/1.1 server: one request per connection, its JSON body
+/// answered by `handler`. Returns the server's URL.
+pub async fn spawn_mock_rpc<F>(handler: F) -> String
+where
+    F: Fn(&Value) -> Reply + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handler = std::sync::Arc::new(handler);
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let handler = handler.clone();
+            tokio::spawn(async move {
+                let Some(request) = read_request(&mut socket).await else {
+                    return;
+                };
+                let reply = handler(&request);
+                write_reply(&mut socket, reply).await;
+            });
+        }
+    });
+
+    format!("http://{}", addr)
+}
+
+/// Read one HTTP request and parse its body as JSON (`None` if the
+/// connection closed first).
+async fn read_request(socket: &mut TcpStream) -> Option<Value> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let body = loop {
+        let n = socket.read(&mut chunk).await.unwrap_or(0);
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        let text = String::from_utf8_lossy(&buf);
+        if let Some(end) = text.find("\r\n\r\n") {
+            let len = text[..end]
+                .lines()
+                .find_map(|l| {
+                    let (k, v) = l.split_once(':')?;
+                    k.eq_ignore_ascii_case("content-length")
+                        .then(|| v.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            if buf.len() >= end + 4 + len {
+                break buf[end + 4..end + 4 + len].to_vec();
+            }
+        }
+    };
+    Some(serde_json::from_slice(&body).unwrap())
+}
+
+async fn write_reply(socket: &mut TcpStream, mut reply: Reply) {
+    let (status, payload) = loop {
+        match reply {
+            Reply::Json(body) => break (200, body.to_string()),
+            Reply::Raw(status, body) => break (status, body),
+            Reply::After(delay, next) => {
+                tokio::time::sleep(delay).await;
+                reply = *next;
+            }
+            Reply::Hang => {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                return;
+            }
+        }
+    };
+    let reason = reqwest::StatusCode::from_u16(status)
+        .ok()
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or("Unknown");
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        payload.len(),
+        payload
+    );
+    let _ = socket.write_all(response.as_bytes()).await;
+}
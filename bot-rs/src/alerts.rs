@@ -0,0 +1,540 @@
+//! Operator alerting with severity routing, batching, and rate limiting.
+//!
+//! Every alert carries a stable `key` (e.g. `"breaker.tripped"`) and a
+//! [`Severity`]. Routing by severity:
+//!
+//! | Severity   | Delivery                                                    |
+//! |------------|-------------------------------------------------------------|
+//! | `Critical` | Sent immediately; repeats of the key within a batch window   |
+//! |            | are coalesced like warnings                                 |
+//! | `Warning`  | Same-key alerts coalesced into one message per batch window |
+//! | `Info`     | Collected into the periodic digest only                     |
+//!
+//! All outgoing messages pass through a global rate limit. Messages over the
+//! limit are counted, and the next message that gets through is prefixed with
+//! an explicit "N alerts suppressed" notice — nothing is dropped silently.
+//!
+//! The router is synchronous and clock-driven (`now_ms` is passed in), so the
+//! strategy loop calls [`AlertRouter::tick`] once per cycle to flush expired
+//! batches and the digest.
+
+use std::collections::{BTreeMap, VecDeque};
+use tracing::{error, info, warn};
+
+/// Alert severity — determines routing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "INFO"),
+            Severity::Warning => write!(f, "WARN"),
+            Severity::Critical => write!(f, "CRITICAL"),
+        }
+    }
+}
+
+/// Destination for rendered alert messages (Telegram, webhook, test recorder).
+pub trait AlertTransport: Send {
+    /// Deliver one rendered message. Must not block the caller.
+    fn send(&self, text: &str);
+}
+
+/// Tuning knobs for the router.
+#[derive(Debug, Clone)]
+pub struct AlertConfig {
+    /// How long same-key warnings are coalesced before one message is sent.
+    pub batch_window_ms: u64,
+    /// How often the info digest is sent.
+    pub digest_interval_ms: u64,
+    /// Maximum messages delivered per `rate_window_ms`.
+    pub max_per_window: usize,
+    /// Length of the rate-limit window.
+    pub rate_window_ms: u64,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            batch_window_ms: 300_000,       // 5 minutes
+            digest_interval_ms: 86_400_000, // daily
+            max_per_window: 20,
+            rate_window_ms: 3_600_000, // per hour
+        }
+    }
+}
+
+/// Pending same-key warnings inside the current batch window.
+#[derive(Debug)]
+struct PendingBatch {
+    first_at_ms: u64,
+    count: u64,
+    last_message: String,
+}
+
+/// Info alerts accumulated for the next digest.
+#[derive(Debug, Default)]
+struct DigestEntry {
+    count: u64,
+    last_message: String,
+}
+
+/// Digest entries raised under one build tag.
+#[derive(Debug)]
+struct DigestSection {
+    build_tag: Option<String>,
+    entries: BTreeMap<String, DigestEntry>,
+}
+
+/// Severity-aware alert router. See module docs for the routing matrix.
+pub struct AlertRouter {
+    config: AlertConfig,
+    transport: Box<dyn AlertTransport>,
+    batches: BTreeMap<String, PendingBatch>,
+    /// When each critical key was last sent immediately
+    critical_sent_ms: BTreeMap<String, u64>,
+    /// One section per build tag seen since the last digest, oldest first
+    digest: Vec<DigestSection>,
+    last_digest_ms: u64,
+    sent_at: VecDeque<u64>,
+    suppressed: u64,
+    total_suppressed: u64,
+    /// Build / config identity shown in the digest header.
+    build_tag: Option<String>,
+}
+
+impl AlertRouter {
+    pub fn new(config: AlertConfig, transport: Box<dyn AlertTransport>, now_ms: u64) -> Self {
+        Self {
+            config,
+            transport,
+            batches: BTreeMap::new(),
+            critical_sent_ms: BTreeMap::new(),
+            digest: Vec::new(),
+            last_digest_ms: now_ms,
+            sent_at: VecDeque::new(),
+            suppressed: 0,
+            total_suppressed: 0,
+            build_tag: None,
+        }
+    }
+
+    /// Tag digests with the build / config identity (see `BuildInfo`), so
+    /// reports from different deploys or configs can be told apart. Info
+    /// alerts already collected stay under the tag they were raised with.
+    pub fn set_build_tag(&mut self, tag: &str) {
+        self.build_tag = Some(tag.to_string());
+    }
+
+    /// Raise an alert. Always logged; delivery depends on severity.
+    ///
+    /// A critical key already sent within the batch window is batched as a
+    /// warning instead, so one persistent condition can't spend the rate
+    /// limit that other criticals need.
+    pub fn raise(&mut self, severity: Severity, key: &str, message: &str, now_ms: u64) {
+        let repeat = severity == Severity::Critical
+            && self
+                .critical_sent_ms
+                .get(key)
+                .is_some_and(|&at| now_ms.saturating_sub(at) < self.config.batch_window_ms);
+        match severity {
+            Severity::Critical if !repeat => {
+                error!(key = %key, "🚨 ALERT: {}", message);
+                self.critical_sent_ms.insert(key.to_string(), now_ms);
+                let text = format!("[{}] {}: {}", severity, key, message);
+                self.deliver(&text, now_ms);
+            }
+            Severity::Critical | Severity::Warning => {
+                warn!(key = %key, "ALERT: {}", message);
+                let batch = self
+                    .batches
+                    .entry(key.to_string())
+                    .or_insert_with(|| PendingBatch {
+                        first_at_ms: now_ms,
+                        count: 0,
+                        last_message: String::new(),
+                    });
+                batch.count += 1;
+                batch.last_message = message.to_string();
+            }
+            Severity::Info => {
+                info!(key = %key, "Alert (digest): {}", message);
+                if self.digest.last().is_none_or(|s| s.build_tag != self.build_tag) {
+                    self.digest.push(DigestSection {
+                        build_tag: self.build_tag.clone(),
+                        entries: BTreeMap::new(),
+                    });
+                }
+                let section = self.digest.last_mut().expect("section pushed above");
+                let entry = section.entries.entry(key.to_string()).or_default();
+                entry.count += 1;
+                entry.last_message = message.to_string();
+            }
+        }
+    }
+
+    /// Flush warning batches whose window has elapsed and, if due, the digest.
+    /// Call once per strategy cycle.
+    pub fn tick(&mut self, now_ms: u64) {
+        let window = self.config.batch_window_ms;
+        let due: Vec<String> = self
+            .batches
+            .iter()
+            .filter(|(_, b)| now_ms.saturating_sub(b.first_at_ms) >= window)
+            .map(|(k, _)| k.clone())
+            .collect();
+
+        for key in due {
+            if let Some(batch) = self.batches.remove(&key) {
+                let text = if batch.count > 1 {
+                    format!(
+                        "[{}] {}: {} (×{} in {}s)",
+                        Severity::Warning,
+                        key,
+                        batch.last_message,
+                        batch.count,
+                        now_ms.saturating_sub(batch.first_at_ms) / 1000
+                    )
+                } else {
+                    format!("[{}] {}: {}", Severity::Warning, key, batch.last_message)
+                };
+                self.deliver(&text, now_ms);
+            }
+        }
+
+        if now_ms.saturating_sub(self.last_digest_ms) >= self.config.digest_interval_ms {
+            self.last_digest_ms = now_ms;
+            if !self.digest.is_empty() {
+                let mut text = String::new();
+                for section in std::mem::take(&mut self.digest) {
+                    if !text.is_empty() {
+                        text.push_str("\n\n");
+                    }
+                    match &section.build_tag {
+                        Some(tag) => text.push_str(&format!("[DIGEST] {}", tag)),
+                        None => text.push_str("[DIGEST]"),
+                    }
+                    for (key, entry) in section.entries {
+                        text.push_str(&format!("\n• {} ×{}: {}", key, entry.count, entry.last_message));
+                    }
+                }
+                if self.total_suppressed() > 0 {
+                    text.push_str(&format!(
+                        "\n({} alerts suppressed by rate limit since startup)",
+                        self.total_suppressed()
+                    ));
+                }
+                self.deliver(&text, now_ms);
+            }
+        }
+    }
+
+    /// Number of alerts dropped by the rate limit since startup.
+    pub fn total_suppressed(&self) -> u64 {
+        self.total_suppressed
+    }
+
+    /// Send through the global rate limit, prefixing any suppression notice.
+    fn deliver(&mut self, text: &str, now_ms: u64) {
+        while let Some(&oldest) = self.sent_at.front() {
+            if now_ms.saturating_sub(oldest) >= self.config.rate_window_ms {
+                self.sent_at.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.sent_at.len() >= self.config.max_per_window {
+            self.suppressed += 1;
+            self.total_suppressed += 1;
+            return;
+        }
+
+        self.sent_at.push_back(now_ms);
+        if self.suppressed > 0 {
+            let notice = format!(
+                "⚠️ {} alerts suppressed by rate limit\n{}",
+                self.suppressed, text
+            );
+            self.suppressed = 0;
+            self.transport.send(&notice);
+        } else {
+            self.transport.send(text);
+        }
+    }
+}
+
+/// Sends alert messages to a Telegram chat via the Bot API.
+/// Each send is spawned so the strategy loop never waits on Telegram.
+pub struct TelegramTransport {
+    client: reqwest::Client,
+    url: String,
+    chat_id: String,
+}
+
+impl TelegramTransport {
+    pub fn new(bot_token: &str, chat_id: &str) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(5))
+                .build()
+                .expect("Failed to create HTTP client"),
+            url: format!("https://api.telegram.org/bot{}/sendMessage", bot_token),
+            chat_id: chat_id.to_string(),
+        }
+    }
+}
+
+impl AlertTransport for TelegramTransport {
+    fn send(&self, text: &str) {
+        let client = self.client.clone();
+        let url = self.url.clone();
+        let body = serde_json::json!({ "chat_id": self.chat_id, "text": text });
+        tokio::spawn(async move {
+            if let Err(e) = client.post(&url).json(&body).send().await {
+                warn!(error = %e, "Failed to deliver Telegram alert");
+            }
+        });
+    }
+}
+
+/// Fallback transport when no notification channel is configured.
+/// Alerts are still logged by the router; this just notes the delivery.
+pub struct LogTransport;
+
+impl AlertTransport for LogTransport {
+    fn send(&self, text: &str) {
+        info!(target: "alerts", "{}", text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Records every message that would have been sent.
+    #[derive(Clone, Default)]
+    struct MockTransport {
+        sent: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl AlertTransport for MockTransport {
+        fn send(&self, text: &str) {
+            self.sent.lock().unwrap().push(text.to_string());
+        }
+    }
+
+    fn router(config: AlertConfig) -> (AlertRouter, Arc<Mutex<Vec<String>>>) {
+        let mock = MockTransport::default();
+        let sent = mock.sent.clone();
+        (AlertRouter::new(config, Box::new(mock), 0), sent)
+    }
+
+    fn test_config() -> AlertConfig {
+        AlertConfig {
+            batch_window_ms: 60_000,
+            digest_interval_ms: 600_000,
+            max_per_window: 100,
+            rate_window_ms: 3_600_000,
+        }
+    }
+
+    // ── Routing matrix ──
+
+    #[test]
+    fn test_critical_sent_immediately() {
+        let (mut r, sent) = router(test_config());
+        r.raise(Severity::Critical, "breaker.tripped", "5 failures", 1_000);
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].contains("CRITICAL"));
+        assert!(sent[0].contains("breaker.tripped"));
+    }
+
+    #[test]
+    fn test_repeated_critical_batched_until_window_elapses() {
+        let (mut r, sent) = router(test_config());
+        for i in 0..5 {
+            r.raise(Severity::Critical, "collector.dead", "no heartbeat", 1_000 + i * 1_000);
+        }
+        assert_eq!(sent.lock().unwrap().len(), 1, "repeats wait for the batch window");
+        r.tick(62_000);
+        r.raise(Severity::Critical, "collector.dead", "no heartbeat", 70_000);
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 3);
+        assert!(sent[1].starts_with("[WARN] collector.dead") && sent[1].contains("×4"), "got: {}", sent[1]);
+        assert!(sent[2].starts_with("[CRITICAL] collector.dead"), "got: {}", sent[2]);
+    }
+
+    #[test]
+    fn test_persistent_critical_does_not_starve_other_keys() {
+        let mut config = test_config();
+        config.max_per_window = 3;
+        let (mut r, sent) = router(config);
+        // A dead collector raising every 100ms cycle for a minute
+        for i in 0..600 {
+            r.raise(Severity::Critical, "collector.dead", "no heartbeat", i * 100);
+        }
+        r.raise(Severity::Critical, "breaker.tripped", "5 failures", 60_000);
+        let sent = sent.lock().unwrap();
+        assert!(sent.iter().any(|m| m.starts_with("[CRITICAL] breaker.tripped")), "got: {:?}", sent);
+        assert_eq!(r.total_suppressed(), 0);
+    }
+
+    #[test]
+    fn test_warning_not_sent_until_window_elapses() {
+        let (mut r, sent) = router(test_config());
+        r.raise(Severity::Warning, "gas.low", "0.05 SUI", 1_000);
+        r.tick(30_000);
+        assert!(sent.lock().unwrap().is_empty());
+        r.tick(61_000);
+        assert_eq!(sent.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_info_only_in_digest() {
+        let (mut r, sent) = router(test_config());
+        r.raise(Severity::Info, "trade.ok", "profit 1000", 1_000);
+        r.tick(120_000); // past the warning window, before the digest
+        assert!(sent.lock().unwrap().is_empty());
+        r.tick(600_000);
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].starts_with("[DIGEST]"));
+        assert!(sent[0].contains("trade.ok ×1"));
+    }
+
+    #[test]
+    fn test_digest_tagged_with_build() {
+        let (mut r, sent) = router(test_config());
+        r.set_build_tag("v0.1.0 (abc123) config 0011223344556677");
+        r.raise(Severity::Info, "trade.ok", "profit 1000", 1_000);
+        r.tick(600_000);
+        let sent = sent.lock().unwrap();
+        assert!(sent[0].starts_with("[DIGEST] v0.1.0 (abc123) config 0011223344556677\n"));
+    }
+
+    #[test]
+    fn test_digest_keeps_entries_under_the_tag_they_were_raised_with() {
+        let (mut r, sent) = router(test_config());
+        r.set_build_tag("v0.1.0 (abc123) config 0011223344556677");
+        r.raise(Severity::Info, "trade.ok", "profit 1000", 1_000);
+        // Config reloaded mid-window
+        r.set_build_tag("v0.1.0 (abc123) config 8899aabbccddeeff");
+        r.raise(Severity::Info, "trade.ok", "profit 2000", 2_000);
+        r.raise(Severity::Info, "pools.added", "3 pools", 3_000);
+        r.tick(600_000);
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(
+            sent[0],
+            "[DIGEST] v0.1.0 (abc123) config 0011223344556677\n• trade.ok ×1: profit 1000\n\n\
+             [DIGEST] v0.1.0 (abc123) config 8899aabbccddeeff\n• pools.added ×1: 3 pools\n• trade.ok ×1: profit 2000"
+        );
+    }
+
+    #[test]
+    fn test_empty_digest_not_sent() {
+        let (mut r, sent) = router(test_config());
+        r.tick(600_000);
+        assert!(sent.lock().unwrap().is_empty());
+    }
+
+    // ── Batching window ──
+
+    #[test]
+    fn test_same_key_warnings_coalesced_with_count() {
+        let (mut r, sent) = router(test_config());
+        for i in 0..5 {
+            r.raise(Severity::Warning, "pools.stale", "all stale", 1_000 + i * 1_000);
+        }
+        r.tick(61_000);
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 1, "5 same-key warnings should be one message");
+        assert!(sent[0].contains("×5"), "got: {}", sent[0]);
+    }
+
+    #[test]
+    fn test_different_keys_batched_separately() {
+        let (mut r, sent) = router(test_config());
+        r.raise(Severity::Warning, "gas.low", "low", 1_000);
+        r.raise(Severity::Warning, "pools.stale", "stale", 1_000);
+        r.raise(Severity::Warning, "gas.low", "low", 2_000);
+        r.tick(61_000);
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert!(sent.iter().any(|m| m.contains("gas.low") && m.contains("×2")));
+        assert!(sent.iter().any(|m| m.contains("pools.stale") && !m.contains('×')));
+    }
+
+    #[test]
+    fn test_batch_window_restarts_after_flush() {
+        let (mut r, sent) = router(test_config());
+        r.raise(Severity::Warning, "gas.low", "low", 0);
+        r.tick(60_000);
+        r.raise(Severity::Warning, "gas.low", "low", 70_000);
+        r.tick(100_000); // new window started at 70s — not due yet
+        assert_eq!(sent.lock().unwrap().len(), 1);
+        r.tick(130_000);
+        assert_eq!(sent.lock().unwrap().len(), 2);
+    }
+
+    // ── Suppression accounting ──
+
+    #[test]
+    fn test_rate_limit_suppresses_and_reports() {
+        let mut config = test_config();
+        config.max_per_window = 2;
+        config.rate_window_ms = 10_000;
+        let (mut r, sent) = router(config);
+
+        for i in 0..5 {
+            r.raise(Severity::Critical, &format!("security.{}", i), "mismatch", 1_000 + i);
+        }
+        assert_eq!(sent.lock().unwrap().len(), 2);
+        assert_eq!(r.total_suppressed(), 3);
+
+        // Window rolls over — next message carries the suppression notice
+        r.raise(Severity::Critical, "collector.dead", "still dead", 12_000);
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 3);
+        assert!(sent[2].contains("3 alerts suppressed"), "got: {}", sent[2]);
+        assert!(sent[2].contains("still dead"));
+    }
+
+    #[test]
+    fn test_suppression_notice_only_once() {
+        let mut config = test_config();
+        config.max_per_window = 1;
+        config.rate_window_ms = 10_000;
+        let (mut r, sent) = router(config);
+
+        r.raise(Severity::Critical, "a", "1", 0);
+        r.raise(Severity::Critical, "b", "2", 1); // suppressed
+        r.raise(Severity::Critical, "c", "3", 10_000); // notice + message
+        r.raise(Severity::Critical, "d", "4", 20_000); // plain
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 3);
+        assert!(sent[1].contains("1 alerts suppressed"));
+        assert!(!sent[2].contains("suppressed"));
+    }
+
+    #[test]
+    fn test_batched_warnings_count_against_rate_limit() {
+        let mut config = test_config();
+        config.max_per_window = 1;
+        let (mut r, sent) = router(config);
+
+        r.raise(Severity::Warning, "a", "x", 0);
+        r.raise(Severity::Warning, "b", "y", 0);
+        r.tick(60_000);
+        assert_eq!(sent.lock().unwrap().len(), 1);
+        assert_eq!(r.total_suppressed(), 1);
+    }
+}
@@ -0,0 +1,88 @@
+//! Operator CLI.
+//!
+//! ```text
+//! arbctl doctor [--json] [--skip rpc,ws] [--timeout-ms 3000]
+//! ```
+//!
+//! `doctor` runs the support checklist against the current `.env` and exits
+//! non-zero if any critical check fails.
+
+use anyhow::{bail, Context, Result};
+use arb_bot::doctor::{self, CheckResult, DoctorOptions, Report, Status, CHECKS};
+use arb_bot::settings::RuntimeSettings;
+use arb_types::Config;
+use std::time::Duration;
+
+const USAGE: &str = "usage: arbctl doctor [--json] [--skip CHECK[,CHECK...]] [--timeout-ms MS]";
+
+#[tokio::main]
+async fn main() {
+    match run().await {
+        Ok(healthy) => std::process::exit(if healthy { 0 } else { 1 }),
+        Err(e) => {
+            eprintln!("arbctl: {:#}\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+async fn run() -> Result<bool> {
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("doctor") => {}
+        Some(other) => bail!("unknown command '{}'", other),
+        None => bail!("missing command"),
+    }
+
+    dotenvy::dotenv().ok();
+    let mut opts = DoctorOptions::default();
+    let mut json = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--skip" => {
+                let list = args.next().context("--skip needs a check list")?;
+                for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+                    if !CHECKS.contains(&name) {
+                        bail!("unknown check '{}' (checks: {})", name, CHECKS.join(", "));
+                    }
+                    opts.skip.insert(name.to_string());
+                }
+            }
+            "--timeout-ms" => {
+                let ms: u64 = args
+                    .next()
+                    .context("--timeout-ms needs a value")?
+                    .parse()
+                    .context("invalid --timeout-ms")?;
+                opts.check_timeout = Duration::from_millis(ms);
+            }
+            other => bail!("unknown flag '{}'", other),
+        }
+    }
+
+    // Without a loadable config (and valid runtime settings) nothing else can run
+    let loaded = Config::load().and_then(|config| {
+        let settings = RuntimeSettings::from_env(&config)?;
+        Ok((config, settings))
+    });
+    let report = match loaded {
+        Ok((config, settings)) => doctor::run(&config, &opts.with_settings(&settings)).await,
+        Err(e) => Report {
+            checks: vec![CheckResult {
+                name: "config",
+                status: Status::Fail,
+                critical: true,
+                message: format!("{:#} — copy .env.example to .env and fill it in", e),
+                elapsed_ms: 0,
+            }],
+        },
+    };
+
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        print!("{}", report.render_text());
+    }
+    Ok(report.healthy())
+}
@@ -0,0 +1,498 @@
+//! `arbctl doctor`: the support checklist as one command.
+//!
+//! Runs each check in order — config, RPC reachability, wallet key and
+//! funding, configured objects and their types, the deployed package's entry
+//! functions, pool parsing, inventory coins, WebSocket delivery — and reports
+//! pass / warn / fail / skip with an actionable message. Every check is
+//! independently skippable and time-bounded, so a healthy setup finishes in
+//! well under 30 seconds and a hung endpoint can't stall the run.
+
+use anyhow::{Context, Result};
+use arb_collector::{rpc_poller, PoolCache, WsEndpoints};
+use arb_executor::{GasMonitor, Inventory, Signer};
+use arb_types::rpc::RpcClient;
+use arb_types::{Config, StrategyType};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::settings::RuntimeSettings;
+use crate::startup::{build_dex_packages, check_config, Level};
+
+/// Every check, in run order. Names are what `--skip` accepts.
+pub const CHECKS: &[&str] = &["config", "rpc", "wallet", "objects", "abi", "pools", "inventory", "ws"];
+
+/// Checks whose failure makes `doctor` exit non-zero. The rest degrade
+/// the bot (no inventory mode, polling instead of streaming) but don't stop it.
+const CRITICAL: &[&str] = &["config", "rpc", "wallet", "objects", "abi", "pools"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "PASS"),
+            Status::Warn => write!(f, "WARN"),
+            Status::Fail => write!(f, "FAIL"),
+            Status::Skip => write!(f, "SKIP"),
+        }
+    }
+}
+
+/// Outcome of one check.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: Status,
+    pub critical: bool,
+    pub message: String,
+    pub elapsed_ms: u64,
+}
+
+/// All check outcomes, in run order.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Report {
+    pub checks: Vec<CheckResult>,
+}
+
+impl Report {
+    /// The result for `name`, if it ran.
+    pub fn check(&self, name: &str) -> Option<&CheckResult> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+
+    /// Critical checks that failed.
+    pub fn critical_failures(&self) -> Vec<&'static str> {
+        self.checks
+            .iter()
+            .filter(|c| c.critical && c.status == Status::Fail)
+            .map(|c| c.name)
+            .collect()
+    }
+
+    pub fn healthy(&self) -> bool {
+        self.critical_failures().is_empty()
+    }
+
+    /// One line per check, then a verdict.
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        for c in &self.checks {
+            out.push_str(&format!("{:<4}  {:<9}  {:>5}ms  {}\n", c.status, c.name, c.elapsed_ms, c.message));
+        }
+        let failed = self.critical_failures();
+        if failed.is_empty() {
+            out.push_str("\nHealthy: no critical check failed.\n");
+        } else {
+            out.push_str(&format!("\nUnhealthy: critical check(s) failed: {}\n", failed.join(", ")));
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "healthy": self.healthy(),
+            "critical_failures": self.critical_failures(),
+            "checks": self.checks,
+        })
+    }
+}
+
+/// Doctor settings. [`with_settings`](Self::with_settings) takes the knobs
+/// the bot reads from its [`RuntimeSettings`].
+#[derive(Debug, Clone)]
+pub struct DoctorOptions {
+    /// Checks not to run (reported as skipped)
+    pub skip: HashSet<String>,
+    /// Time limit per check
+    pub check_timeout: Duration,
+    /// How long the WS check waits for a first event
+    pub ws_listen: Duration,
+    /// `MIN_GAS_BALANCE_MIST`
+    pub min_gas_balance: u64,
+    /// `USE_WEBSOCKET` — the WS check is skipped when streaming is off
+    pub use_ws: bool,
+    /// `INVENTORY_COINS` — the inventory check is skipped when empty
+    pub inventory_coins: Vec<String>,
+}
+
+impl Default for DoctorOptions {
+    fn default() -> Self {
+        Self {
+            skip: HashSet::new(),
+            check_timeout: Duration::from_secs(3),
+            ws_listen: Duration::from_secs(6),
+            min_gas_balance: 100_000_000,
+            use_ws: false,
+            inventory_coins: Vec::new(),
+        }
+    }
+}
+
+impl DoctorOptions {
+    /// Take the gas floor, streaming, and inventory knobs from `settings`,
+    /// so the checks judge the setup the bot would run with.
+    pub fn with_settings(mut self, settings: &RuntimeSettings) -> Self {
+        self.min_gas_balance = settings.min_gas_balance_mist;
+        self.use_ws = settings.use_websocket;
+        self.inventory_coins = settings.inventory_coins.clone();
+        self
+    }
+}
+
+/// What a check found, before timing and criticality are attached.
+struct Outcome {
+    status: Status,
+    message: String,
+}
+
+impl Outcome {
+    fn pass(message: impl Into<String>) -> Self {
+        Self { status: Status::Pass, message: message.into() }
+    }
+    fn warn(message: impl Into<String>) -> Self {
+        Self { status: Status::Warn, message: message.into() }
+    }
+    fn fail(message: impl Into<String>) -> Self {
+        Self { status: Status::Fail, message: message.into() }
+    }
+    fn skip(message: impl Into<String>) -> Self {
+        Self { status: Status::Skip, message: message.into() }
+    }
+}
+
+/// Run every check not in `opts.skip`, in order.
+pub async fn run(config: &Config, opts: &DoctorOptions) -> Report {
+    let rpc = RpcClient::from_config(config).with_timeout(opts.check_timeout);
+
+    let mut checks = Vec::with_capacity(CHECKS.len());
+    for &name in CHECKS {
+        let started = Instant::now();
+        let outcome = if opts.skip.contains(name) {
+            Outcome::skip("skipped (--skip)")
+        } else {
+            let limit = if name == "ws" { opts.ws_listen + opts.check_timeout } else { opts.check_timeout };
+            match tokio::time::timeout(limit, run_check(name, config, opts, &rpc)).await {
+                Ok(outcome) => outcome,
+                Err(_) => Outcome::fail(format!("timed out after {}ms", limit.as_millis())),
+            }
+        };
+        checks.push(CheckResult {
+            name,
+            status: outcome.status,
+            critical: CRITICAL.contains(&name),
+            message: outcome.message,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        });
+    }
+    Report { checks }
+}
+
+async fn run_check(name: &str, config: &Config, opts: &DoctorOptions, rpc: &RpcClient) -> Outcome {
+    match name {
+        "config" => check_config_findings(config),
+        "rpc" => check_rpc(config, opts).await,
+        "wallet" => check_wallet(config, opts).await,
+        "objects" => check_objects(config, rpc).await,
+        "abi" => check_abi(config, rpc).await,
+        "pools" => check_pools(config).await,
+        "inventory" => check_inventory(config, opts).await,
+        "ws" => check_ws(config, opts).await,
+        _ => Outcome::skip("unknown check"),
+    }
+}
+
+// ── Checks ──
+
+fn check_config_findings(config: &Config) -> Outcome {
+    let findings = check_config(config);
+    let errors: Vec<&str> = findings
+        .iter()
+        .filter(|f| f.level == Level::Error)
+        .map(|f| f.message.as_str())
+        .collect();
+    let warnings: Vec<&str> = findings
+        .iter()
+        .filter(|f| f.level == Level::Warning)
+        .map(|f| f.message.as_str())
+        .collect();
+    if !errors.is_empty() {
+        Outcome::fail(errors.join("; "))
+    } else if !warnings.is_empty() {
+        Outcome::warn(warnings.join("; "))
+    } else {
+        Outcome::pass("configuration OK")
+    }
+}
+
+/// Every endpoint is probed on its own: the failover client would hide a
+/// dead backup behind a healthy primary. A dead backup is a warning.
+async fn check_rpc(config: &Config, opts: &DoctorOptions) -> Outcome {
+    let urls = config.rpc_endpoints();
+    let mut reachable = Vec::new();
+    let mut unreachable = Vec::new();
+    for url in &urls {
+        let rpc = RpcClient::single(url).with_timeout(opts.check_timeout);
+        match rpc_result(&rpc, "sui_getChainIdentifier", json!([])).await {
+            Ok(chain) => reachable.push(format!("{} (chain {})", host(url), chain.as_str().unwrap_or("?"))),
+            Err(e) => unreachable.push(format!("{} unreachable: {:#}", host(url), e)),
+        }
+    }
+    if reachable.is_empty() {
+        Outcome::fail(format!(
+            "{} — check SUI_RPC_URL / SUI_RPC_URLS and the provider's status",
+            unreachable.join("; ")
+        ))
+    } else if !unreachable.is_empty() {
+        Outcome::warn(format!("{} reachable; {}", reachable.join(", "), unreachable.join("; ")))
+    } else {
+        Outcome::pass(format!("{} reachable", reachable.join(", ")))
+    }
+}
+
+async fn check_wallet(config: &Config, opts: &DoctorOptions) -> Outcome {
+    let signer = match Signer::from_config(config) {
+        Ok(s) => s,
+        Err(e) if config.keystore_path.is_empty() => {
+            return Outcome::fail(format!("SUI_PRIVATE_KEY is invalid: {:#}", e))
+        }
+        Err(e) => return Outcome::fail(format!("SUI_KEYSTORE_PATH: {:#}", e)),
+    };
+    let address = signer.address();
+    let monitor = GasMonitor::new(&config.rpc_url, &address, opts.min_gas_balance);
+    match monitor.fetch_balance().await {
+        Ok(balance) if balance >= opts.min_gas_balance => {
+            Outcome::pass(format!("{} holds {:.4} SUI", address, sui(balance)))
+        }
+        Ok(balance) => Outcome::fail(format!(
+            "{} holds {:.4} SUI, below MIN_GAS_BALANCE_MIST ({:.4} SUI) — fund the wallet",
+            address,
+            sui(balance),
+            sui(opts.min_gas_balance)
+        )),
+        Err(e) => Outcome::fail(format!("balance of {} unavailable: {:#}", address, e)),
+    }
+}
+
+/// Configured shared/owned objects and the type each must have.
+fn expected_objects(config: &Config) -> Vec<(&'static str, &str, &'static str)> {
+    let mut objects = vec![
+        ("PACKAGE_ID", config.package_id.as_str(), "package"),
+        ("ADMIN_CAP_ID", config.admin_cap_id.as_str(), "::admin::AdminCap"),
+        ("PAUSE_FLAG_ID", config.pause_flag_id.as_str(), "::admin::PauseFlag"),
+        ("CETUS_GLOBAL_CONFIG", config.cetus_global_config.as_str(), "::config::GlobalConfig"),
+        ("TURBOS_VERSIONED", config.turbos_versioned.as_str(), "::pool::Versioned"),
+        ("DEEP_FEE_COIN_ID", config.deep_fee_coin_id.as_str(), "::deep::DEEP>"),
+    ];
+    objects.retain(|(_, id, _)| id.starts_with("0x") && *id != "0x...");
+    objects
+}
+
+async fn check_objects(config: &Config, rpc: &RpcClient) -> Outcome {
+    let objects = expected_objects(config);
+    let ids: Vec<&str> = objects.iter().map(|(_, id, _)| *id).collect();
+    let fetched = match rpc_poller::multi_get_objects(rpc, &ids, config.multi_get_chunk_size).await {
+        Ok(f) => f,
+        Err(e) => return Outcome::fail(format!("could not fetch configured objects: {:#}", e)),
+    };
+
+    let mut problems = Vec::new();
+    for (setting, id, want) in &objects {
+        let object = fetched.get(&rpc_poller::object_key(id));
+        let ty = object.and_then(|o| o.pointer("/data/type")).and_then(Value::as_str);
+        match ty {
+            None => problems.push(format!("{} {} does not exist", setting, id)),
+            Some(ty) if *want == "package" && ty != "package" => {
+                problems.push(format!("{} {} is a {}, not a package", setting, id, ty))
+            }
+            Some(ty) if *want != "package" && !ty.contains(want) => {
+                problems.push(format!("{} {} has type {}, expected *{}", setting, id, ty, want))
+            }
+            Some(_) => {}
+        }
+    }
+    if problems.is_empty() {
+        Outcome::pass(format!("{} configured objects exist with the expected types", objects.len()))
+    } else {
+        Outcome::fail(problems.join("; "))
+    }
+}
+
+/// Every strategy's entry function must exist in the deployed package.
+/// Only direct or quad-hop strategies missing means the package predates
+/// the `direct` / `quad_hop` modules: a warning, since the other flash-loan
+/// strategies still work.
+async fn check_abi(config: &Config, rpc: &RpcClient) -> Outcome {
+    let modules = match rpc_result(
+        rpc,
+        "sui_getNormalizedMoveModulesByPackage",
+        json!([config.package_id]),
+    )
+    .await
+    {
+        Ok(m) => m,
+        Err(e) => return Outcome::fail(format!("could not read package {}: {:#}", config.package_id, e)),
+    };
+
+    let missing: Vec<StrategyType> = StrategyType::ALL
+        .iter()
+        .copied()
+        .filter(|s| {
+            modules
+                .get(s.move_module())
+                .and_then(|m| m.get("exposedFunctions"))
+                .and_then(|f| f.get(s.move_function_name()))
+                .is_none()
+        })
+        .collect();
+    let names: Vec<String> = missing
+        .iter()
+        .map(|s| format!("{}::{}", s.move_module(), s.move_function_name()))
+        .collect();
+
+    if missing.is_empty() {
+        Outcome::pass(format!("all {} strategy entry functions present", StrategyType::ALL.len()))
+    } else if missing.iter().all(|s| s.is_direct() || s.is_quad_hop()) {
+        Outcome::warn(format!(
+            "package lacks {} — upgrade it to enable inventory-funded direct arbs and quad-hops",
+            names.join(", ")
+        ))
+    } else {
+        Outcome::fail(format!(
+            "package {} lacks {} — PACKAGE_ID may point at an old or different deployment",
+            config.package_id,
+            names.join(", ")
+        ))
+    }
+}
+
+async fn check_pools(config: &Config) -> Outcome {
+    let cache = PoolCache::new();
+    if let Err(e) = rpc_poller::seed_cache(config, &cache).await {
+        return Outcome::fail(format!("could not fetch pools: {:#}", e));
+    }
+    let configured = config.monitored_pools.len();
+    let unparsed: Vec<&str> = config
+        .monitored_pools
+        .iter()
+        .filter(|p| cache.get(&p.pool_id).is_none())
+        .map(|p| p.pool_id.as_str())
+        .collect();
+    if configured == 0 {
+        Outcome::fail("MONITORED_POOLS is empty")
+    } else if unparsed.len() == configured {
+        Outcome::fail(format!("none of {} pools parsed — check pool IDs and DEX names", configured))
+    } else if !unparsed.is_empty() {
+        Outcome::warn(format!(
+            "{}/{} pools parsed; missing or unparseable: {}",
+            configured - unparsed.len(),
+            configured,
+            unparsed.join(", ")
+        ))
+    } else {
+        Outcome::pass(format!("all {} pools fetched and parsed", configured))
+    }
+}
+
+async fn check_inventory(config: &Config, opts: &DoctorOptions) -> Outcome {
+    if opts.inventory_coins.is_empty() {
+        return Outcome::skip("INVENTORY_COINS not set");
+    }
+    let address = match Signer::from_config(config) {
+        Ok(s) => s.address(),
+        Err(_) => return Outcome::skip("no valid wallet key"),
+    };
+    let inventory = Inventory::new(&config.rpc_url, &address, &opts.inventory_coins);
+    if let Err(e) = inventory.refresh().await {
+        return Outcome::fail(format!("{:#}", e));
+    }
+    let (held, empty): (Vec<&String>, Vec<&String>) =
+        inventory.coin_types().iter().partition(|c| inventory.covering(c, 1).is_some());
+    if empty.is_empty() {
+        Outcome::pass(format!("inventory coin held for {}", held.len()))
+    } else {
+        Outcome::warn(format!(
+            "no usable inventory coin for {} — direct arbs in these coins fall back to flash loans",
+            empty.iter().map(|c| c.as_str()).collect::<Vec<_>>().join(", ")
+        ))
+    }
+}
+
+/// Subscribe as the bot does and wait for one event.
+async fn check_ws(config: &Config, opts: &DoctorOptions) -> Outcome {
+    if !opts.use_ws {
+        return Outcome::skip("USE_WEBSOCKET is off — the bot polls");
+    }
+    let ws_url = WsEndpoints::resolve(config).swap_remove(0);
+    match ws_first_event(&ws_url, config, opts.ws_listen).await {
+        Ok(Some(event_type)) => Outcome::pass(format!("subscribed; first event {}", event_type)),
+        Ok(None) => Outcome::warn(format!(
+            "subscribed but no event in {}s — the endpoint may not deliver events (or the pools are quiet)",
+            opts.ws_listen.as_secs()
+        )),
+        Err(e) => Outcome::fail(format!("{:#} — the bot will fall back to polling", e)),
+    }
+}
+
+/// Connect, subscribe to every DEX package, and return the type of the
+/// first event delivered within `listen`.
+async fn ws_first_event(ws_url: &str, config: &Config, listen: Duration) -> Result<Option<String>> {
+    let (ws, _) = connect_async(ws_url).await.context("WebSocket connect failed")?;
+    let (mut write, mut read) = ws.split();
+    for (i, pkg) in build_dex_packages(config).iter().enumerate() {
+        let subscribe = json!({
+            "jsonrpc": "2.0",
+            "id": i + 1,
+            "method": "suix_subscribeEvent",
+            "params": [{ "Package": pkg.package_id }]
+        });
+        write.send(Message::Text(subscribe.to_string().into())).await.context("subscribe failed")?;
+    }
+
+    let deadline = tokio::time::Instant::now() + listen;
+    loop {
+        let msg = match tokio::time::timeout_at(deadline, read.next()).await {
+            Err(_) => return Ok(None),
+            Ok(None) => anyhow::bail!("WebSocket closed by the server"),
+            Ok(Some(msg)) => msg.context("WebSocket read failed")?,
+        };
+        let Message::Text(text) = msg else { continue };
+        let value: Value = serde_json::from_str(&text).context("non-JSON WebSocket message")?;
+        if let Some(error) = value.get("error") {
+            anyhow::bail!("subscription rejected: {}", error);
+        }
+        if let Some(event) = value.pointer("/params/result") {
+            return Ok(Some(event.get("type").and_then(Value::as_str).unwrap_or("?").to_string()));
+        }
+    }
+}
+
+// ── Helpers ──
+
+/// `result` of one JSON-RPC call, with RPC errors as `Err`.
+async fn rpc_result(rpc: &RpcClient, method: &str, params: Value) -> Result<Value> {
+    let body = rpc.call(method, params).await?;
+    if let Some(error) = body.get("error") {
+        anyhow::bail!("{} error: {}", method, error);
+    }
+    body.get("result").cloned().with_context(|| format!("{} returned no result", method))
+}
+
+/// Host part of a URL — provider URLs often embed an API key in the path.
+fn host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, r)| r);
+    rest.split('/').next().unwrap_or(rest)
+}
+
+fn sui(mist: u64) -> f64 {
+    mist as f64 / 1_000_000_000.0
+}
@@ -0,0 +1,11 @@
+//! Operator-facing pieces shared by the `arb-bot` and `arbctl` binaries,
+//! and the strategy loop `arb-bot` runs.
+
+pub mod alerts;
+pub mod doctor;
+pub mod profiling;
+pub mod reconcile;
+pub mod settings;
+pub mod startup;
+pub mod strategy_loop;
+pub mod trade_log;
@@ -1,41 +1,70 @@
-use anyhow::Result;
-use arb_collector::{rpc_poller, DexPackage, PoolCache, RpcPoller, TxEffectStream, WsStream};
-use arb_executor::{CoinMerger, GasMonitor, Signer, Submitter};
-use arb_strategy::{CircuitBreaker, DryRunner, Scanner, build_local_simulator, ternary_search};
-use arb_types::Config;
+mod metrics_server;
+mod status_server;
+mod telemetry;
+
+use anyhow::{Context, Result};
+use arb_collector::ws_endpoints;
+use arb_collector::{
+    rpc_poller, BookFetcher, FeeTypeVerifier, PollBackoff, PollPacing, PoolCache, PoolSet, PoolWatcher, RpcPoller, SubscriptionBudget,
+    TxEffectStream, WsEndpoints, WsStream,
+};
+use arb_executor::ptb_builder::PtbBuilder;
+use arb_executor::{CoinMerger, GasCoinPool, GasMonitor, GasOracle, Inventory, Signer, Submitter};
+use arb_strategy::{
+    BreakerStore, CircuitBreaker, ColdStartMonitor, DryRunner, FailureTracker, GasEstimator, OpportunityTracker,
+    OutcomeStats, Scanner, StrategyQuarantine, StrategyWeights, TradeStats,
+};
+use arb_types::clock::Clock;
+use arb_types::log_throttle::DEFAULT_INTERVAL;
+use arb_types::notifier::Notifier;
+use arb_types::{clear_throttled, warn_throttled, BuildInfo, Config, DecimalRegistry, Metrics};
+use arb_types::config::PoolConfig;
+use arb_types::rotating_file::RotationPolicy;
+use arb_types::rpc::RpcClient;
+use arb_bot::alerts::{AlertConfig, AlertRouter, AlertTransport, LogTransport, Severity, TelegramTransport};
+use arb_bot::profiling::{self, Profiler};
+use arb_bot::reconcile::Reconciler;
+use arb_bot::settings::{PtbBuildMode, RuntimeSettings};
+use arb_bot::startup::{build_dex_packages, validate_startup};
+use arb_bot::strategy_loop::{run_strategy_loop, save_breaker, StrategyState, MAX_POOL_STALENESS_MS};
+use arb_bot::trade_log::TradeLog;
+use status_server::StatusState;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use tokio::signal;
-use tracing::{debug, error, info, warn};
+use tokio::sync::watch;
+use tracing::{error, info, warn};
 
-/// Maximum allowed staleness (ms) for pool data before strategy loop skips a cycle.
-const MAX_POOL_STALENESS_MS: u64 = 10_000; // 10 seconds
+/// Git commit the binary was built from (set by build.rs).
+const GIT_COMMIT: &str = env!("GIT_COMMIT");
+
+#[cfg(feature = "alloc-counter")]
+#[global_allocator]
+static GLOBAL: profiling::alloc::CountingAllocator = profiling::alloc::CountingAllocator;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // ── Setup ──
-    dotenvy::dotenv().ok();
+    // The path is kept so SIGHUP can re-read STRATEGY_WEIGHTS from it
+    let env_file = dotenvy::dotenv().ok();
 
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
-        )
-        .with_target(true)
-        .with_thread_ids(false)
-        .init();
+    let otel_endpoint = std::env::var("OTEL_ENDPOINT").ok().filter(|s| !s.is_empty());
+    let _telemetry = telemetry::init(otel_endpoint)?;
 
     info!("╔══════════════════════════════════════╗");
     info!("║     ArbMove Bot v0.1.0 — Sui MEV    ║");
     info!("╚══════════════════════════════════════╝");
 
-    let config = Config::from_env()?;
-    let signer = Signer::from_hex(&config.private_key_hex)?;
+    let config = Config::load()?;
+    let settings = RuntimeSettings::from_env(&config)?;
+    let signer = Signer::from_config(&config)?;
     let sender_address = signer.address();
 
     info!(address = %sender_address, "Wallet loaded");
-    info!(rpc = %config.rpc_url, "Connecting to Sui");
+    // One client for every JSON-RPC caller, so all of them fail over together
+    let rpc = RpcClient::from_config(&config);
+    info!(rpc = %config.rpc_url, endpoints = %rpc.urls().count(), "Connecting to Sui");
     info!(
         pools = %config.monitored_pools.len(),
         min_profit = %config.min_profit_mist,
@@ -43,57 +72,271 @@ async fn main() -> Result<()> {
         "Configuration loaded"
     );
 
+    // ── Alerts ──
+    let mut alerts = build_alert_router(&settings);
+
     // ── Startup validation ──
-    validate_startup(&config);
+    let startup_errors = validate_startup(&config);
+    if startup_errors > 0 {
+        alerts.raise(
+            Severity::Critical,
+            "startup.validation",
+            &format!("{} critical config error(s) at startup", startup_errors),
+            now_ms(),
+        );
+    }
 
     // ── Initialize components ──
-    let cache = PoolCache::new();
+    // Per-pool warmup: history a pool needs before it's tradeable
+    let cache = PoolCache::new().with_warmup(settings.pool_warmup());
 
     // Seed cache with initial pool states
     rpc_poller::seed_cache(&config, &cache).await?;
-    info!(cached = %cache.len(), "Pool cache ready");
+    info!(cached = %cache.len(), warming = %cache.warming(), "Pool cache ready");
+
+    // Drop pools that have stopped updating (delisted, or no longer
+    // subscribed) so scans stop iterating over them
+    let evict_every_ms = settings.pool_evict_interval_ms;
+    if evict_every_ms > 0 {
+        let max_age_ms = settings.pool_evict_max_age_ms;
+        tokio::spawn(run_pool_eviction(cache.clone(), Duration::from_millis(evict_every_ms), max_age_ms));
+        info!(every_ms = %evict_every_ms, max_age_ms = %max_age_ms, "Stale pool eviction enabled");
+    }
+
+    // Coin decimals from on-chain CoinMetadata (static table as fallback)
+    let decimals = DecimalRegistry::new();
+    let coin_types: Vec<String> = config
+        .monitored_pools
+        .iter()
+        .flat_map(|p| [p.coin_type_a.clone(), p.coin_type_b.clone()])
+        .collect();
+    let fetched = decimals.seed(&rpc, &coin_types).await;
+    info!(fetched = %fetched, "Coin decimals loaded");
+
+    // ── Metrics endpoint (Prometheus text format; METRICS_PORT=0 disables) ──
+    let metrics = Arc::new(Metrics::new());
+    if settings.metrics_port > 0 {
+        let listener = metrics_server::bind(&format!("{}:{}", settings.metrics_bind, settings.metrics_port)).await?;
+        info!(addr = %listener.local_addr()?, "Metrics endpoint listening on /metrics");
+        tokio::spawn(metrics_server::serve(listener, metrics.clone()));
+    }
 
     // Create components
-    let poller = RpcPoller::new(&config);
-    let scanner = Scanner::new(config.min_profit_mist);
+    // DeepBook prices come from the order book, read via dev-inspect each poll
+    let book_fetcher = BookFetcher::new(&config.rpc_url, &settings.deepbook_v3_package_id)
+        .with_rpc(rpc.clone())
+        .with_ticks(settings.deepbook_book_ticks)
+        .with_decimals(decimals.clone());
+    // Monitored pools, shared by every collector so a pools file reload
+    // reaches all of them
+    let pools = PoolSet::from_config(&config);
+    // Rate-limit backoff of whichever poller runs, watched by the strategy loop
+    let poll_backoff = PollBackoff::default();
+    // Poll faster while pools are moving and slower while they're quiet,
+    // within these bounds (both default to POLL_INTERVAL_MS: fixed)
+    if settings.poll_interval_max_ms > config.pair_freshness_ms {
+        warn!(
+            max_ms = %settings.poll_interval_max_ms,
+            pair_freshness_ms = %config.pair_freshness_ms,
+            "POLL_INTERVAL_MAX_MS exceeds PAIR_FRESHNESS_MS — quiet pools will age out of two-hop scans between polls"
+        );
+    }
+    let poll_pacing = PollPacing::new(
+        Duration::from_millis(config.poll_interval_ms),
+        Duration::from_millis(settings.poll_interval_min_ms),
+        Duration::from_millis(settings.poll_interval_max_ms),
+    );
+    let poller = RpcPoller::new(&config)
+        .with_rpc(rpc.clone())
+        .with_pools(pools.clone())
+        .with_book_fetcher(book_fetcher.clone())
+        .with_backoff(poll_backoff.clone())
+        .with_pacing(poll_pacing.clone());
+    let scanner = Scanner::new(config.min_profit_mist)
+        .with_pair_thresholds(config.pair_min_profit.clone())
+        .with_trade_size_defaults(config.trade_size_defaults.clone())
+        .with_allowed_flash_coins(config.allowed_flash_coins.clone())
+        .with_strategy_filter(config.strategy_allowlist.clone(), config.strategy_denylist.clone())
+        .with_min_trade_amount(config.min_trade_amount_mist)
+        .with_pair_freshness(config.pair_freshness_ms, config.min_fresh_pools_per_pair)
+        // Pools the parser returned no fee for: skip them, or price at a fixed (high) fee
+        .with_missing_fee_policy(settings.missing_fee_policy)
+        // Line search refining trade sizes: ternary, or golden (fewer simulations)
+        .with_search_method(settings.search_method)
+        .with_min_leg_depth_ratio(config.min_leg_depth_ratio)
+        .with_quad_hop_min_cross_rate(settings.quad_hop_min_cross_rate)
+        .with_metrics(metrics.clone())
+        .with_decimals(decimals.clone());
+    // Per-request bound on dry-runs, so validating the top-K stays bounded
     let dry_runner = DryRunner::new(
         &config.rpc_url,
         &config.package_id,
         &sender_address,
         config.max_gas_budget,
-    );
-    let submitter = Submitter::new(&config.rpc_url);
-    let ptb_builder = arb_executor::ptb_builder::PtbBuilder::new(&config, &sender_address);
+    )
+    .with_rpc(rpc.clone())
+    .with_timeout(Duration::from_millis(settings.dry_run_timeout_ms));
+    let submitter = Submitter::new(&config.rpc_url).with_rpc(rpc.clone());
+
+    // ── Inventory (direct, flash-loan-free arbs from the wallet's own coins) ──
+    let inventory = if settings.inventory_coins.is_empty() {
+        None
+    } else {
+        let inventory =
+            Inventory::new(&config.rpc_url, &sender_address, &settings.inventory_coins).with_rpc(rpc.clone());
+        let refresh_ms = settings.inventory_refresh_ms;
+        tokio::spawn(run_inventory_refresh(inventory.clone(), Duration::from_millis(refresh_ms)));
+        info!(
+            coins = ?inventory.coin_types(),
+            refresh_ms = %refresh_ms,
+            "Inventory mode enabled — direct arbs when the wallet covers the input"
+        );
+        Some(inventory)
+    };
+
+    let mut ptb_builder = PtbBuilder::new(&config, &sender_address)
+        .with_rpc(rpc.clone())
+        .with_max_retries(settings.ptb_build_max_retries);
+    // Per-strategy gas budgets from the live reference gas price, capped
+    // at MAX_GAS_BUDGET (0 = flat MAX_GAS_BUDGET)
+    let gas_price_refresh_ms = settings.gas_price_refresh_ms;
+    if gas_price_refresh_ms > 0 {
+        let oracle = GasOracle::new(&config.rpc_url, config.max_gas_budget).with_rpc(rpc.clone());
+        match oracle.refresh().await {
+            Ok(price) => info!(price = %price, refresh_ms = %gas_price_refresh_ms, "Gas price oracle ready"),
+            Err(e) => warn!(error = %format!("{e:#}"), "Reference gas price fetch failed — using the default until refreshed"),
+        }
+        tokio::spawn(run_gas_price_refresh(oracle.clone(), Duration::from_millis(gas_price_refresh_ms)));
+        ptb_builder = ptb_builder.with_gas_oracle(oracle);
+    }
+    // One gas coin per transaction in flight (0 or 1 = let the builder pick)
+    let gas_coin_pool_size = settings.gas_coin_pool_size;
+    let gas_coins = if gas_coin_pool_size > 1 {
+        // A coin must cover the largest budget to be worth leasing
+        let pool = GasCoinPool::new(&config.rpc_url, &sender_address, gas_coin_pool_size, config.max_gas_budget)
+            .with_rpc(rpc.clone());
+        if let Err(e) = pool.refresh().await {
+            warn!(error = %format!("{e:#}"), "Gas coin fetch failed — retrying in the background");
+        }
+        let refresh_ms = settings.gas_coin_refresh_ms;
+        tokio::spawn(run_gas_coin_refresh(pool.clone(), Duration::from_millis(refresh_ms)));
+        info!(target = %gas_coin_pool_size, coins = %pool.len(), "Gas coin pool enabled");
+        ptb_builder = ptb_builder.with_gas_coin_pool(pool.clone());
+        Some(pool)
+    } else {
+        None
+    };
+    if let Some(inv) = &inventory {
+        ptb_builder = ptb_builder.with_inventory(inv.clone());
+    }
+    // `local`: encode transactions here instead of via `unsafe_moveCall`
+    // (kept as the fallback)
+    match settings.ptb_build_mode {
+        PtbBuildMode::Rpc => {
+            if config.tx_expiration_epochs.is_some() {
+                warn!("TX_EXPIRATION_EPOCHS needs PTB_BUILD_MODE=local — transactions built by the node never expire");
+            }
+        }
+        PtbBuildMode::Local => {
+            ptb_builder = ptb_builder.with_local_build()?;
+            let pool_ids: Vec<String> = config.monitored_pools.iter().map(|p| p.pool_id.clone()).collect();
+            match ptb_builder.warm_up(&pool_ids).await {
+                Ok(()) => info!(objects = %pool_ids.len(), "Local PTB builder ready"),
+                Err(e) => warn!(error = %format!("{e:#}"), "Local PTB builder warm-up failed — resolving on first build"),
+            }
+        }
+    }
 
-    // ── Determine collector mode ──
-    let use_ws = std::env::var("USE_WEBSOCKET")
-        .unwrap_or_else(|_| "false".to_string())
-        .parse::<bool>()
-        .unwrap_or(false);
+    // Per-strategy PTB build failure tracking: suspend a strategy whose
+    // builds keep getting rejected instead of retrying it every cycle.
+    let build_failures = FailureTracker::new(settings.build_max_consecutive_failures, settings.build_suspend_ms);
 
-    let ws_mode = std::env::var("WS_MODE")
-        .unwrap_or_else(|_| "event".to_string());
+    // Per-strategy quarantine: skip a strategy that keeps failing dry-runs,
+    // reverting, or losing money while the others trade on.
+    let quarantine = StrategyQuarantine::new(
+        settings.strategy_quarantine_failures,
+        settings.strategy_quarantine_loss_mist,
+        settings.strategy_quarantine_cooldown_ms,
+    );
+
+    // Per-opportunity cooldown: don't hammer the same pools every tick
+    // while a spread persists
+    let opportunity_tracker = OpportunityTracker::new(
+        settings.opportunity_failure_cooldown_ms,
+        settings.opportunity_success_cooldown_ms,
+    );
 
     // ── Spawn collector task(s) with supervision ──
     // Shared counter: collectors bump this on every successful update so the
     // strategy loop can detect when all collectors have died.
     let collector_heartbeat = Arc::new(AtomicU64::new(now_ms()));
 
-    if use_ws {
-        let ws_url = WsStream::ws_url_from_rpc(&config.rpc_url);
-        let pool_metas: Vec<_> = config
-            .monitored_pools
-            .iter()
-            .map(|p| arb_collector::rpc_poller::PoolMeta {
-                object_id: p.pool_id.clone(),
-                dex: p.dex.clone(),
-                coin_type_a: p.coin_type_a.clone(),
-                coin_type_b: p.coin_type_b.clone(),
-            })
-            .collect();
-
-        if ws_mode == "tx" {
-            let tx_stream = TxEffectStream::new(&ws_url, &config.rpc_url, pool_metas);
+    // ── Status endpoint (JSON health for probes; STATUS_PORT=0 disables) ──
+    if settings.status_port > 0 {
+        let listener = status_server::bind(&format!("{}:{}", settings.status_bind, settings.status_port)).await?;
+        info!(addr = %listener.local_addr()?, "Status endpoint listening on /status");
+        let state = StatusState::new(metrics.clone(), collector_heartbeat.clone(), MAX_POOL_STALENESS_MS * 3);
+        tokio::spawn(status_server::serve(listener, state));
+    }
+
+    // ── Determine collector mode ──
+    if settings.use_websocket {
+        // Fail fast on an unreachable endpoint rather than leaving the
+        // streams in their reconnect loop
+        let ws_urls = WsEndpoints::resolve(&config);
+        ws_endpoints::verify_handshakes(&ws_urls, Duration::from_secs(10))
+            .await
+            .context("WebSocket endpoint unreachable — fix SUI_WS_URL or set USE_WEBSOCKET=false")?;
+        let endpoints = WsEndpoints::new(ws_urls)
+            .with_max_failures(settings.ws_failover_after);
+        let ws_url = endpoints.current();
+        info!(ws_urls = ?endpoints.urls(), "WebSocket endpoints verified");
+        // A node can stop sending without closing the socket
+        let idle_timeout = Duration::from_millis(settings.ws_idle_timeout_ms);
+        // Events on a busy pool fold into one re-fetch per window
+        let refetch_debounce = Duration::from_millis(settings.ws_refetch_debounce_ms);
+        let pool_metas = pools.snapshot().to_vec();
+
+        if settings.ws_mode == "tx" {
+            // Providers cap concurrent subscriptions: the most active pools
+            // get per-pool subscriptions, the rest fall back to package
+            // events (which take a subscription each) plus polling.
+            let dex_packages = build_dex_packages(&config);
+            let max_subscriptions = settings.ws_max_subscriptions;
+            // Under the cap, leave room for pools a reload adds
+            let pool_cap = if pool_metas.len() <= max_subscriptions {
+                max_subscriptions
+            } else {
+                max_subscriptions.saturating_sub(dex_packages.len())
+            };
+            let budget = SubscriptionBudget::new(pool_cap);
+            let rebalance_interval = Duration::from_millis(settings.ws_rebalance_interval_ms);
+
+            if pool_cap < pool_metas.len() {
+                info!(
+                    pools = %pool_metas.len(),
+                    per_pool = %pool_cap,
+                    packages = %dex_packages.len(),
+                    "Subscription budget exceeded — remaining pools covered by package events"
+                );
+                let ws = WsStream::new(&ws_url, &config.rpc_url, dex_packages, pool_metas.clone())
+                    .with_rpc(rpc.clone())
+                    .with_pools(pools.clone())
+                    .with_endpoints(endpoints.clone())
+                    .with_idle_timeout(idle_timeout)
+                    .with_refetch_debounce(refetch_debounce)
+                    .with_heartbeat(collector_heartbeat.clone());
+                spawn_event_stream(ws, cache.clone(), collector_heartbeat.clone());
+            }
+
+            let tx_stream = TxEffectStream::new(&ws_url, &config.rpc_url, pool_metas)
+                .with_rpc(rpc.clone())
+                .with_pools(pools.clone())
+                .with_budget(budget, rebalance_interval)
+                .with_endpoints(endpoints.clone())
+                .with_idle_timeout(idle_timeout)
+                .with_refetch_debounce(refetch_debounce)
+                .with_heartbeat(collector_heartbeat.clone());
             let ws_cache = cache.clone();
             let hb = collector_heartbeat.clone();
             info!(mode = "tx_effects", "Using WebSocket streaming");
@@ -114,30 +357,25 @@ async fn main() -> Result<()> {
             });
         } else {
             let dex_packages = build_dex_packages(&config);
-            let ws = WsStream::new(&ws_url, &config.rpc_url, dex_packages, pool_metas);
-            let ws_cache = cache.clone();
-            let hb = collector_heartbeat.clone();
+            let ws = WsStream::new(&ws_url, &config.rpc_url, dex_packages, pool_metas)
+                .with_rpc(rpc.clone())
+                .with_pools(pools.clone())
+                .with_endpoints(endpoints)
+                .with_idle_timeout(idle_timeout)
+                .with_refetch_debounce(refetch_debounce)
+                .with_heartbeat(collector_heartbeat.clone());
             info!(mode = "event", "Using WebSocket streaming");
-
-            tokio::spawn(async move {
-                loop {
-                    match ws.run(ws_cache.clone()).await {
-                        Ok(()) => {
-                            warn!("WebSocket event stream ended cleanly — restarting in 3s");
-                        }
-                        Err(e) => {
-                            error!(error = %e, "WebSocket event stream failed — restarting in 3s");
-                        }
-                    }
-                    tokio::time::sleep(Duration::from_secs(3)).await;
-                    hb.store(now_ms(), Ordering::Relaxed);
-                }
-            });
+            spawn_event_stream(ws, cache.clone(), collector_heartbeat.clone());
         }
 
         // Also run RPC poller as supervised fallback
         let fallback_cache = cache.clone();
-        let poller = RpcPoller::new(&config);
+        let poller = RpcPoller::new(&config)
+            .with_rpc(rpc.clone())
+            .with_pools(pools.clone())
+            .with_book_fetcher(book_fetcher)
+            .with_backoff(poll_backoff.clone())
+            .with_pacing(poll_pacing.clone());
         let hb = collector_heartbeat.clone();
         info!("RPC poller running as fallback");
 
@@ -167,498 +405,595 @@ async fn main() -> Result<()> {
         });
     }
 
+    // ── Pools file hot reload (POOLS_FILE) ──
+    if !config.pools_file.is_empty() {
+        let reload_ms = settings.pools_reload_interval_ms;
+        let watcher = PoolWatcher::new(&config, pools.clone())
+            .with_rpc(rpc.clone())
+            .with_interval(Duration::from_millis(reload_ms))
+            .with_decimals(decimals.clone());
+        let watcher_cache = cache.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = watcher.run(watcher_cache.clone()).await {
+                    error!(error = %e, "Pools file watcher failed — restarting in 5s");
+                }
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+        });
+    }
+
+    // ── Turbos fee type verifier (hourly, staggered per pool) ──
+    {
+        let verifier_cache = cache.clone();
+        let config = config.clone();
+        let rpc = rpc.clone();
+        let pools = pools.clone();
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            let mut verifier = FeeTypeVerifier::new(&config).with_rpc(rpc).with_pools(pools).with_metrics(metrics);
+            loop {
+                if let Err(e) = verifier.run(verifier_cache.clone()).await {
+                    error!(error = %e, "Fee type verifier failed — restarting in 60s");
+                }
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+        });
+    }
+
     // ── Strategy loop ──
     let poll_interval = Duration::from_millis(config.poll_interval_ms);
-    let dry_run_enabled = config.dry_run_before_submit;
+    // Paper trading needs the dry-run: it's what stands in for the fill
+    let dry_run_enabled = config.dry_run_before_submit || config.paper_trading;
+    if config.paper_trading {
+        warn!("PAPER TRADING — trades are dry-run, never signed or submitted; P&L is simulated");
+    }
+    // Webhook shared by the gas monitor and circuit breaker
+    let notifier = build_notifier(&settings);
 
     // Gas balance monitor (min 0.1 SUI = 100M MIST to allow trading)
-    let min_gas_balance: u64 = env_var_or_default("MIN_GAS_BALANCE_MIST", 100_000_000);
-    let mut gas_monitor = GasMonitor::new(&config.rpc_url, &sender_address, min_gas_balance);
+    let min_gas_balance = settings.min_gas_balance_mist;
+    let gas_monitor = GasMonitor::new(&config.rpc_url, &sender_address, min_gas_balance)
+        .with_rpc(rpc.clone())
+        .with_refresh_margin(settings.gas_refresh_margin_mist)
+        .with_notifier(notifier.clone())
+        .with_alert_threshold(settings.gas_alert_threshold_mist);
     info!(
         min_balance_sui = %format!("{:.2}", min_gas_balance as f64 / 1_000_000_000.0),
         "Gas balance monitor initialized"
     );
 
     // Coin dust merger (consolidates fragmented Coin<SUI> objects)
-    let mut coin_merger = CoinMerger::new(&config.rpc_url, &sender_address);
-    info!("Coin merger initialized (threshold: 20 coins, check every ~50s)");
-
-    // Circuit breaker
-    let mut circuit_breaker = CircuitBreaker::new(
-        config.cb_max_consecutive_failures,
-        config.cb_max_cumulative_loss_mist,
-        config.cb_cooldown_ms,
+    let target_gas_coins = settings.target_gas_coin_count;
+    let coin_merger = CoinMerger::new(&config.rpc_url, &sender_address)
+        .with_rpc(rpc.clone())
+        .with_target_coin_count(target_gas_coins);
+    info!(
+        target_coins = %target_gas_coins,
+        "Coin merger initialized (threshold: 20 coins, check every ~50s)"
     );
+
+    // Circuit breaker, restored from BREAKER_STATE_PATH (empty = not persisted)
+    // so a restart doesn't clear a trip before its cooldown
+    let breaker_state_path = &settings.breaker_state_path;
+    let mut breaker_store = (!breaker_state_path.is_empty()).then(|| BreakerStore::new(breaker_state_path));
+    let saved_breaker = match breaker_store.as_mut() {
+        Some(store) => store.load()?,
+        None => None,
+    };
+    let circuit_breaker = match saved_breaker {
+        Some(snapshot) => {
+            info!(
+                path = %breaker_state_path,
+                tripped_at_ms = ?snapshot.tripped_at_ms,
+                reason = ?snapshot.trip_reason,
+                cumulative_pnl = %snapshot.cumulative_pnl_mist,
+                "Circuit breaker state restored"
+            );
+            CircuitBreaker::from_snapshot(
+                snapshot,
+                config.cb_max_consecutive_failures,
+                config.cb_max_cumulative_loss_mist,
+                config.cb_cooldown_ms,
+            )
+        }
+        None => CircuitBreaker::new(
+            config.cb_max_consecutive_failures,
+            config.cb_max_cumulative_loss_mist,
+            config.cb_cooldown_ms,
+        ),
+    }
+    .with_loss_window(config.cb_loss_window_ms)
+    .with_notifier(notifier);
     info!(
         max_consec = %config.cb_max_consecutive_failures,
         max_loss = %config.cb_max_cumulative_loss_mist,
         cooldown_ms = %config.cb_cooldown_ms,
+        loss_window_ms = %config.cb_loss_window_ms,
         "Circuit breaker initialized"
     );
 
-    let strategy_handle = tokio::spawn(async move {
-        let mut interval = tokio::time::interval(poll_interval);
-        let mut total_trades = 0u64;
-        let mut total_profit = 0i64;
-        let mut total_gas = 0u64;
-
-        info!("Strategy loop started ({}ms tick)", poll_interval.as_millis());
-
-        loop {
-            interval.tick().await;
-
-            // 0a. Circuit breaker check
-            if !circuit_breaker.is_trading_allowed(now_ms()) {
-                continue;
-            }
-
-            // 0b. Gas balance check
-            if let Err(e) = gas_monitor.check_balance(now_ms()).await {
-                warn!(error = %e, "Gas balance insufficient — skipping cycle");
-                continue;
-            }
-
-            // 0c. Periodic coin dust merge
-            if let Ok(Some(merge_tx)) = coin_merger.maybe_merge().await {
-                match signer.sign_transaction(&merge_tx) {
-                    Ok(sig) => {
-                        match submitter.submit(&merge_tx, &sig).await {
-                            Ok(result) => {
-                                if result.success {
-                                    info!(
-                                        digest = %result.digest,
-                                        gas = %result.gas_cost_mist,
-                                        "Coin merge successful"
-                                    );
-                                    gas_monitor.deduct_gas(result.gas_cost_mist);
-                                } else {
-                                    warn!(error = ?result.error_message, "Coin merge failed on-chain");
-                                }
-                            }
-                            Err(e) => warn!(error = %e, "Coin merge submission failed"),
-                        }
-                    }
-                    Err(e) => warn!(error = %e, "Failed to sign merge transaction"),
-                }
-            }
-
-            // 0d. Check collector liveness via heartbeat
-            let hb_age = now_ms().saturating_sub(
-                collector_heartbeat.load(Ordering::Relaxed),
-            );
-            if hb_age > MAX_POOL_STALENESS_MS * 3 {
-                warn!(
-                    stale_ms = %hb_age,
-                    "All collectors appear dead — skipping cycle"
-                );
-                continue;
-            }
-
-            // 1. Read pool states from cache
-            let pools = cache.snapshot();
-            if pools.is_empty() {
-                continue;
-            }
-
-            // 1b. Staleness guard: skip if ALL pools are too old
-            let now = now_ms();
-            let fresh_count = pools
-                .iter()
-                .filter(|p| p.staleness_ms(now) <= MAX_POOL_STALENESS_MS)
-                .count();
-            if fresh_count == 0 {
-                warn!("All pool data is stale — skipping cycle");
-                continue;
-            }
-
-            // 2. Scan for opportunities (two-hop + tri-hop)
-            let mut opportunities = scanner.scan_two_hop(&pools);
-            let tri_opps = scanner.scan_tri_hop(&pools);
-            opportunities.extend(tri_opps);
-
-            if opportunities.is_empty() {
-                continue;
-            }
+    // ── Profiling (off until toggled with SIGUSR1 / SIGUSR2) ──
+    let profiler = Arc::new(Profiler::new(
+        &settings.profile_dir,
+        settings.profile_cpu_secs,
+    ));
+    let profile_sample_ms = settings.profile_sample_interval_ms;
+    tokio::spawn(profiler.clone().run_sampler(Duration::from_millis(profile_sample_ms)));
+    profiling::spawn_signal_controls(profiler.clone());
+    info!(
+        dir = %profiler.dir().display(),
+        sample_ms = %profile_sample_ms,
+        "Profiler ready (SIGUSR1: toggle task sampling, SIGUSR2: CPU profile)"
+    );
 
-            // Re-sort combined opportunities by expected profit
-            opportunities.sort_by(|a, b| b.expected_profit.cmp(&a.expected_profit));
+    // ── Strategy weights (operator priors; SIGHUP re-reads them from .env) ──
+    let strategy_weights = Arc::new(RwLock::new(
+        StrategyWeights::parse(&settings.strategy_weights)
+            .map_err(|e| e.context("Invalid STRATEGY_WEIGHTS"))?,
+    ));
+    log_strategy_weights(&strategy_weights.read().unwrap_or_else(|e| e.into_inner()));
+    let (weights_tx, weights_rx) = watch::channel(settings.strategy_weights.clone());
+    spawn_weights_reload(strategy_weights.clone(), env_file, weights_tx);
+
+    // ── Build metadata ──
+    // Tags trade results, opportunity traces, and the alert digest so results
+    // can be segmented by code version and effective configuration.
+    let build = BuildInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        commit: GIT_COMMIT.to_string(),
+        config_hash: config.config_hash(&settings),
+    };
+    alerts.set_build_tag(&build.to_string());
+    info!(
+        version = %build.version,
+        commit = %build.commit,
+        config_hash = %build.config_hash,
+        "Build info"
+    );
+    let config_hash = spawn_config_hash_updates(&config, &settings, &pools, weights_rx, &build.config_hash);
 
-            // 3. Process best opportunity (safe: we checked is_empty above)
-            let mut best = match opportunities.into_iter().next() {
-                Some(opp) => opp,
-                None => continue,
-            };
+    // ── Trade log (JSONL, one line per submission; empty path disables) ──
+    let trade_log_path = &settings.trade_log_path;
+    let trade_log = if trade_log_path.is_empty() {
+        None
+    } else {
+        let policy = RotationPolicy {
+            max_bytes: settings.log_rotate_max_bytes,
+            daily: settings.log_rotate_daily,
+            compress: settings.log_compress,
+            retention_days: settings.log_retention_days,
+        };
+        info!(
+            path = %trade_log_path,
+            max_bytes = %policy.max_bytes,
+            daily = %policy.daily,
+            compress = %policy.compress,
+            retention_days = %policy.retention_days,
+            "Trade log enabled"
+        );
+        let log = TradeLog::open(trade_log_path, policy, config.paper_trading, now_ms())?;
+        let history = log.history();
+        info!(
+            trades = %history.trades,
+            succeeded = %history.succeeded,
+            failed = %history.failed,
+            profit = %history.profit_mist,
+            gas = %history.gas_mist,
+            net = %history.net_mist(),
+            "Trade log history loaded (lifetime before this session)"
+        );
+        Some(log)
+    };
+
+    // ── P&L reconciliation (wallet balance delta vs. trade-log P&L; 0 disables) ──
+    let reconcile_interval_ms = settings.pnl_reconcile_interval_ms;
+    // Paper fills never move the wallet, so there is nothing to reconcile
+    let reconciler = (reconcile_interval_ms > 0 && !config.paper_trading).then(|| {
+        let tolerance = settings.pnl_reconcile_tolerance_mist;
+        info!(
+            interval_ms = %reconcile_interval_ms,
+            tolerance_mist = %tolerance,
+            "P&L reconciliation enabled"
+        );
+        Reconciler::new(reconcile_interval_ms, tolerance)
+    });
 
-            // 4. Run optimizer via ternary search (local simulation)
-            // Tri-hop: optimizer only handles 2-pool arbs, so skip for tri-hop.
-            // The scanner's estimate + dry-runner validation is sufficient.
-            if best.pool_ids.len() == 2 {
-                let flash_pool = pools.iter().find(|p| p.object_id == best.pool_ids[0]);
-                let sell_pool = pools.iter().find(|p| p.object_id == best.pool_ids[1]);
-
-                if let (Some(fp), Some(sp)) = (flash_pool, sell_pool) {
-                    let (simulate, hi) = build_local_simulator(fp, sp);
-                    let (optimal_amount, max_profit) =
-                        ternary_search(1_000, hi, 100_000, &*simulate);
-
-                    if max_profit > 0 {
-                        debug!(
-                            prev_amount = %best.amount_in,
-                            new_amount = %optimal_amount,
-                            prev_profit = %best.expected_profit,
-                            new_profit = %max_profit,
-                            "Ternary search optimized"
-                        );
-                        best.amount_in = optimal_amount;
-                        best.expected_profit = max_profit;
-                        best.net_profit = max_profit as i64 - best.estimated_gas as i64;
-                    }
-                }
-            } else {
-                debug!(
-                    strategy = ?best.strategy,
-                    pools = %best.pool_ids.len(),
-                    est_profit = %best.expected_profit,
-                    "Tri-hop: skipping 2-pool optimizer, using scanner estimate"
-                );
-            }
+    let strategy_state = Arc::new(tokio::sync::Mutex::new(StrategyState {
+        cache,
+        collector_heartbeat,
+        poll_backoff,
+        poll_pacing,
+        scanner,
+        dry_runner,
+        submitter,
+        ptb_builder,
+        signer,
+        gas_monitor,
+        coin_merger,
+        gas_coins,
+        circuit_breaker,
+        breaker_store,
+        build_failures,
+        quarantine,
+        opportunity_tracker,
+        gas_estimator: GasEstimator::new(),
+        cold_start: ColdStartMonitor::new(),
+        outcome_stats: OutcomeStats::new(),
+        trade_stats: TradeStats::new(
+            settings.pnl_summary_every_trades,
+            settings.pnl_summary_interval_ms,
+            now_ms(),
+        ),
+        trade_log,
+        reconciler,
+        strategy_weights,
+        inventory,
+        alerts,
+        metrics,
+        profiler: profiler.clone(),
+        clock: Clock::system(),
+        poll_interval,
+        enable_quad_hop: settings.enable_quad_hop,
+        dry_run_enabled,
+        paper_trading: config.paper_trading,
+        dry_run_batch_size: settings.dry_run_batch_size,
+        max_opportunities: settings.max_opportunities_per_cycle,
+        build: build.clone(),
+        config_hash,
+        total_trades: 0,
+        total_profit: 0,
+        total_gas: 0,
+    }));
+
+    // ── Strategy watchdog ──
+    // The loop bumps this every cycle. If it stops ticking (hung RPC call, a
+    // future that never resolves) or the task exits, abort and respawn it.
+    let strategy_heartbeat = Arc::new(AtomicU64::new(now_ms()));
+    let watchdog_timeout_ms = settings.strategy_watchdog_timeout_ms;
+    info!(timeout_ms = %watchdog_timeout_ms, "Strategy watchdog initialized");
+
+    let mut strategy_handle = tokio::spawn(profiler.monitor().instrument(run_strategy_loop(
+        strategy_state.clone(),
+        strategy_heartbeat.clone(),
+        None,
+    )));
+    let mut strategy_restarts = 0u64;
 
-            // 4b. Post-optimization guards
-            // Guard: skip if optimizer couldn't find a profitable trade
-            if best.expected_profit == 0 {
-                debug!("Optimizer found no profitable amount — skipping");
-                continue;
-            }
+    // ── Graceful shutdown ──
+    info!("Bot running. Press Ctrl+C to stop.");
 
-            // Guard: check opportunity staleness (prices may have moved)
-            let opp_age_ms = now_ms().saturating_sub(best.detected_at_ms);
-            if opp_age_ms > 3_000 {
-                debug!(
-                    age_ms = %opp_age_ms,
-                    "Opportunity too stale (>3s) — skipping"
-                );
-                continue;
-            }
+    let mut watchdog = tokio::time::interval(poll_interval.max(Duration::from_secs(1)));
+    let shutdown = signal::ctrl_c();
+    tokio::pin!(shutdown);
 
-            // Guard: net profit must still be positive after gas
-            best.net_profit = best.expected_profit as i64 - best.estimated_gas as i64;
-            if best.net_profit <= 0 {
-                debug!(
-                    expected_profit = %best.expected_profit,
-                    estimated_gas = %best.estimated_gas,
-                    "Net profit non-positive after optimization — skipping"
-                );
-                continue;
+    loop {
+        tokio::select! {
+            res = &mut shutdown => {
+                res?;
+                break;
             }
-
-            info!(
-                strategy = ?best.strategy,
-                amount = %best.amount_in,
-                expected_profit = %best.expected_profit,
-                net_profit = %best.net_profit,
-                min_profit_onchain = %(best.expected_profit * 9 / 10).max(1),
-                pools = ?best.pool_ids,
-                "Processing opportunity"
-            );
-
-            // 5. Build PTB
-            let tx_bytes = match ptb_builder.build(&best).await {
-                Ok(bytes) => bytes,
-                Err(e) => {
-                    warn!(error = %e, "Failed to build PTB");
-                    continue;
-                }
-            };
-
-            // 6. Dry-run validation
-            if dry_run_enabled {
-                match dry_runner.validate(&mut best, &tx_bytes).await {
-                    Ok(true) => {
-                        info!(
-                            gas = %best.estimated_gas,
-                            net_profit = %best.net_profit,
-                            "Dry-run passed"
-                        );
-                    }
-                    Ok(false) => {
-                        warn!("Opportunity no longer profitable after dry-run");
-                        circuit_breaker.record_failure(0, now_ms());
-                        continue;
-                    }
-                    Err(e) => {
-                        warn!(error = %e, "Dry-run failed");
-                        circuit_breaker.record_failure(0, now_ms());
-                        continue;
-                    }
-                }
-
-                // 6b. Rebuild PTB with tighter min_profit from dry-run actuals
-                let tx_bytes_final = match ptb_builder.build(&best).await {
-                    Ok(bytes) => bytes,
-                    Err(e) => {
-                        warn!(error = %e, "Failed to rebuild PTB after dry-run");
-                        continue;
-                    }
-                };
-
-                // 7. Sign and submit (dry-run path with rebuilt PTB)
-                let signature = match signer.sign_transaction(&tx_bytes_final) {
-                    Ok(sig) => sig,
-                    Err(e) => {
-                        error!(error = %e, "Failed to sign transaction");
-                        continue;
-                    }
+            _ = watchdog.tick() => {
+                let stalled_ms = now_ms().saturating_sub(strategy_heartbeat.load(Ordering::Relaxed));
+                let reason = if strategy_handle.is_finished() {
+                    Some("strategy task exited unexpectedly".to_string())
+                } else if stalled_ms > watchdog_timeout_ms {
+                    Some(format!("strategy loop hung — no tick for {}ms", stalled_ms))
+                } else {
+                    None
                 };
 
-                match submitter.submit(&tx_bytes_final, &signature).await {
-                    Ok(result) => {
-                        total_trades += 1;
-                        total_gas += result.gas_cost_mist;
-                        gas_monitor.deduct_gas(result.gas_cost_mist);
-                        log_trade_result(&result, &mut total_profit, total_trades, total_gas);
-                        // Report to circuit breaker
-                        if result.success {
-                            let net = result.profit_mist.unwrap_or(0) as i64
-                                - result.gas_cost_mist as i64;
-                            circuit_breaker.record_success(net);
-                        } else {
-                            circuit_breaker
-                                .record_failure(-(result.gas_cost_mist as i64), now_ms());
-                        }
-                    }
-                    Err(e) => {
-                        error!(error = %e, "Transaction submission failed");
-                        circuit_breaker.record_failure(0, now_ms());
-                    }
-                }
-            } else {
-                // 7. Sign and submit (no dry-run path)
-                let signature = match signer.sign_transaction(&tx_bytes) {
-                    Ok(sig) => sig,
-                    Err(e) => {
-                        error!(error = %e, "Failed to sign transaction");
-                        continue;
-                    }
-                };
-
-                match submitter.submit(&tx_bytes, &signature).await {
-                    Ok(result) => {
-                        total_trades += 1;
-                        total_gas += result.gas_cost_mist;
-                        gas_monitor.deduct_gas(result.gas_cost_mist);
-                        log_trade_result(&result, &mut total_profit, total_trades, total_gas);
-                        // Report to circuit breaker
-                        if result.success {
-                            let net = result.profit_mist.unwrap_or(0) as i64
-                                - result.gas_cost_mist as i64;
-                            circuit_breaker.record_success(net);
-                        } else {
-                            circuit_breaker
-                                .record_failure(-(result.gas_cost_mist as i64), now_ms());
-                        }
-                    }
-                    Err(e) => {
-                        error!(error = %e, "Transaction submission failed");
-                        circuit_breaker.record_failure(0, now_ms());
-                    }
+                if let Some(reason) = reason {
+                    strategy_restarts += 1;
+                    error!(
+                        restarts = %strategy_restarts,
+                        "{} — aborting and respawning",
+                        reason
+                    );
+                    strategy_handle.abort();
+                    strategy_heartbeat.store(now_ms(), Ordering::Relaxed);
+                    strategy_handle = tokio::spawn(profiler.monitor().instrument(run_strategy_loop(
+                        strategy_state.clone(),
+                        strategy_heartbeat.clone(),
+                        Some(reason),
+                    )));
                 }
             }
         }
-    });
-
-    // ── Graceful shutdown ──
-    info!("Bot running. Press Ctrl+C to stop.");
+    }
 
-    signal::ctrl_c().await?;
     info!("\nShutting down...");
 
     strategy_handle.abort();
+    let _ = strategy_handle.await;
+    let mut final_state = strategy_state.lock().await;
+    let final_state = &mut *final_state;
+    save_breaker(&mut final_state.breaker_store, &final_state.circuit_breaker);
+    let cold_components = final_state.cold_start.cold_components();
 
     info!("╔══════════════════════════════════════╗");
     info!("║         Session Summary              ║");
     info!("╚══════════════════════════════════════╝");
+    info!(
+        version = %build.version,
+        commit = %build.commit,
+        config_hash = %build.config_hash,
+        "Session build"
+    );
+    if !cold_components.is_empty() {
+        info!(components = ?cold_components, "Still in cold start (static defaults)");
+    }
+    final_state.trade_stats.log_summary(now_ms());
+    let mode = if final_state.paper_trading { "PAPER " } else { "" };
+    info!(
+        trades = %final_state.total_trades,
+        net_profit = %final_state.total_profit,
+        gas = %final_state.total_gas,
+        "{}Session totals",
+        mode
+    );
+    if let Some(log) = final_state.trade_log.as_ref() {
+        log.flush().await;
+        let lifetime = log.lifetime();
+        info!(
+            trades = %lifetime.trades,
+            succeeded = %lifetime.succeeded,
+            failed = %lifetime.failed,
+            profit = %lifetime.profit_mist,
+            gas = %lifetime.gas_mist,
+            net = %lifetime.net_mist(),
+            "{}Lifetime totals (trade log)",
+            mode
+        );
+    }
     info!("Bot stopped gracefully.");
 
     Ok(())
 }
 
-/// Read an environment variable with a default, parsing to the target type.
-fn env_var_or_default<T: std::str::FromStr>(name: &str, default: T) -> T {
-    std::env::var(name)
-        .ok()
-        .and_then(|v| v.parse().ok())
-        .unwrap_or(default)
+/// Keep the inventory holdings fresh.
+async fn run_inventory_refresh(inventory: Inventory, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+        match inventory.refresh().await {
+            Ok(()) => clear_throttled!("main.inventory_refresh"),
+            Err(e) => warn_throttled!(
+                "main.inventory_refresh",
+                DEFAULT_INTERVAL,
+                error = %format!("{e:#}"),
+                "Inventory refresh failed — using last known holdings"
+            ),
+        }
+    }
 }
 
-/// Get current time in milliseconds since Unix epoch.
-fn now_ms() -> u64 {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+/// Refresh the gas coin pool's coins and balances every `every`.
+async fn run_gas_coin_refresh(pool: GasCoinPool, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    // The first tick is immediate, and startup just fetched them
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        match pool.refresh().await {
+            Ok(()) => clear_throttled!("main.gas_coin_refresh"),
+            Err(e) => warn_throttled!(
+                "main.gas_coin_refresh",
+                DEFAULT_INTERVAL,
+                error = %format!("{e:#}"),
+                "Gas coin refresh failed — using last known coins"
+            ),
+        }
+    }
 }
 
-/// Log a trade result and update running totals.
-fn log_trade_result(
-    result: &arb_executor::SubmitResult,
-    total_profit: &mut i64,
-    total_trades: u64,
-    total_gas: u64,
-) {
-    if result.success {
-        let profit = result.profit_mist.unwrap_or(0);
-        *total_profit += profit as i64 - result.gas_cost_mist as i64;
-
-        info!(
-            digest = %result.digest,
-            profit = %profit,
-            gas = %result.gas_cost_mist,
-            total_trades = %total_trades,
-            total_profit = %total_profit,
-            total_gas = %total_gas,
-            "✅ Arb executed successfully"
-        );
-    } else {
-        warn!(
-            digest = %result.digest,
-            error = ?result.error_message,
-            "❌ Transaction failed on-chain"
-        );
+/// Refresh the reference gas price every `every`.
+async fn run_gas_price_refresh(oracle: GasOracle, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    // The first tick is immediate, and startup just fetched it
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        match oracle.refresh().await {
+            Ok(_) => clear_throttled!("main.gas_price_refresh"),
+            Err(e) => warn_throttled!(
+                "main.gas_price_refresh",
+                DEFAULT_INTERVAL,
+                error = %format!("{e:#}"),
+                "Reference gas price refresh failed — using last known price"
+            ),
+        }
     }
 }
 
-/// Build the list of DEX package IDs to subscribe to from config.
-fn build_dex_packages(config: &Config) -> Vec<DexPackage> {
-    let mut packages = Vec::new();
-
-    // Add package IDs from environment if set
-    let dex_names = ["CETUS", "TURBOS", "DEEPBOOK", "AFTERMATH", "FLOWX"];
-
-    for name in &dex_names {
-        let env_key = format!("{}_PACKAGE_ID", name);
-        if let Ok(pkg_id) = std::env::var(&env_key) {
-            if !pkg_id.is_empty() {
-                packages.push(DexPackage {
-                    package_id: pkg_id,
-                    dex_name: name.to_lowercase(),
-                });
-            }
+/// Evict pools not updated for `max_age_ms` from `cache` every `every`.
+async fn run_pool_eviction(cache: PoolCache, every: Duration, max_age_ms: u64) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+        let evicted = cache.evict_stale(now_ms(), max_age_ms);
+        if evicted > 0 {
+            info!(evicted = %evicted, remaining = %cache.len(), "Evicted stale pools from cache");
         }
     }
+}
 
-    // Always include the arb package itself for ArbExecuted events
-    packages.push(DexPackage {
-        package_id: config.package_id.clone(),
-        dex_name: "arbmove".to_string(),
-    });
+/// Log the effective strategy weights.
+fn log_strategy_weights(weights: &StrategyWeights) {
+    let overrides: Vec<String> = weights
+        .overrides()
+        .iter()
+        .map(|(s, w)| format!("{:?}:{}", s, w))
+        .collect();
+    info!(
+        default = %weights.default_weight(),
+        overrides = %overrides.join(","),
+        "Strategy weights"
+    );
+}
 
-    packages
+/// Re-read `STRATEGY_WEIGHTS` from `env_file` (the `.env` loaded at startup)
+/// on SIGHUP. A value that fails to parse is logged and the current weights
+/// stay in effect; one that parses is also sent on `raw_tx` for the config
+/// hash.
+#[cfg(unix)]
+fn spawn_weights_reload(
+    weights: Arc<RwLock<StrategyWeights>>,
+    env_file: Option<std::path::PathBuf>,
+    raw_tx: watch::Sender<String>,
+) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    tokio::spawn(async move {
+        let mut hup = match signal(SignalKind::hangup()) {
+            Ok(s) => s,
+            Err(e) => return warn!(error = %e, "Failed to install SIGHUP handler"),
+        };
+        while hup.recv().await.is_some() {
+            let raw = match read_strategy_weights(env_file.as_deref()) {
+                Ok(raw) => raw,
+                Err(e) => {
+                    error!(error = %format!("{e:#}"), "Failed to re-read .env — keeping current weights");
+                    continue;
+                }
+            };
+            match StrategyWeights::parse(&raw) {
+                Ok(parsed) => {
+                    log_strategy_weights(&parsed);
+                    *weights.write().unwrap_or_else(|e| e.into_inner()) = parsed;
+                    raw_tx.send_replace(raw);
+                }
+                Err(e) => error!(error = %format!("{e:#}"), "Invalid STRATEGY_WEIGHTS — keeping current weights"),
+            }
+        }
+    });
 }
 
-/// Validate critical configuration at startup.
-/// Warns on non-fatal issues, errors on blockers.
-fn validate_startup(config: &Config) {
-    let mut warnings = 0u32;
-    let mut errors = 0u32;
-
-    // 1. Package ID must be set (not placeholder)
-    if config.package_id == "0x0" || config.package_id == "0x..." || config.package_id.is_empty() {
-        error!(
-            "PACKAGE_ID is not set ({}) — deploy the Move package first with `sui client publish`",
-            config.package_id
-        );
-        errors += 1;
-    }
+/// Reloading is Unix-only; elsewhere the startup weights stay in effect.
+#[cfg(not(unix))]
+fn spawn_weights_reload(
+    _weights: Arc<RwLock<StrategyWeights>>,
+    _env_file: Option<std::path::PathBuf>,
+    _raw_tx: watch::Sender<String>,
+) {
+    warn!("Strategy weight reload (SIGHUP) is only available on Unix");
+}
 
-    // 2. AdminCap and PauseFlag
-    if config.admin_cap_id == "0x..." || config.admin_cap_id.is_empty() {
-        error!("ADMIN_CAP_ID is not set — required for admin operations");
-        errors += 1;
-    }
-    if config.pause_flag_id == "0x..." || config.pause_flag_id.is_empty() {
-        error!("PAUSE_FLAG_ID is not set — required for all strategy calls");
-        errors += 1;
+/// The last `STRATEGY_WEIGHTS` line in `env_file`, parsed without touching
+/// the process environment (`set_var` isn't safe once the runtime's threads
+/// are up). With no file, or no such line, the startup value stands.
+#[cfg(unix)]
+fn read_strategy_weights(env_file: Option<&std::path::Path>) -> Result<String> {
+    let mut raw = None;
+    if let Some(path) = env_file {
+        for item in dotenvy::from_path_iter(path).with_context(|| format!("Failed to open {}", path.display()))? {
+            let (key, value) = item.with_context(|| format!("Invalid line in {}", path.display()))?;
+            if key == "STRATEGY_WEIGHTS" {
+                raw = Some(value);
+            }
+        }
     }
+    Ok(raw.unwrap_or_else(|| std::env::var("STRATEGY_WEIGHTS").unwrap_or_default()))
+}
 
-    // 3. Monitored pools
-    if config.monitored_pools.is_empty() {
-        error!("MONITORED_POOLS is empty — no pools to monitor. Add pool configs to start trading.");
-        errors += 1;
-    } else {
-        // Validate pool config format
-        for (i, pool) in config.monitored_pools.iter().enumerate() {
-            let valid_dexes = ["cetus", "turbos", "deepbook", "aftermath", "flowx_clmm", "flowx_amm", "flowx"];
-            if !valid_dexes.contains(&pool.dex.to_lowercase().as_str()) {
-                warn!(
-                    pool = %i,
-                    dex = %pool.dex,
-                    "Unknown DEX in pool config — may not be parseable"
-                );
-                warnings += 1;
+/// Recompute the config hash whenever a hot reload changes what it covers:
+/// the pools file (`pools`) or `STRATEGY_WEIGHTS` (`weights`). Each new
+/// hash is logged, and the strategy loop picks it up from the returned
+/// receiver, so trade rows and alerts carry the hash in effect.
+fn spawn_config_hash_updates(
+    config: &Config,
+    settings: &RuntimeSettings,
+    pools: &PoolSet,
+    mut weights: watch::Receiver<String>,
+    initial: &str,
+) -> watch::Receiver<String> {
+    let (tx, rx) = watch::channel(initial.to_string());
+    let mut config = config.clone();
+    let mut settings = settings.clone();
+    let mut pools = pools.watch();
+    let mut weights_open = true;
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                changed = pools.changed() => {
+                    if changed.is_err() {
+                        return;
+                    }
+                    config.monitored_pools = pools.borrow_and_update().iter().map(PoolConfig::from).collect();
+                }
+                changed = weights.changed(), if weights_open => match changed {
+                    Ok(()) => settings.strategy_weights = weights.borrow_and_update().clone(),
+                    Err(_) => weights_open = false,
+                },
             }
-            if !pool.pool_id.starts_with("0x") {
-                warn!(pool = %i, id = %pool.pool_id, "Pool ID doesn't start with 0x");
-                warnings += 1;
+            let hash = config.config_hash(&settings);
+            if *tx.borrow() != hash {
+                info!(config_hash = %hash, "Config hash changed after reload");
+                tx.send_replace(hash);
             }
         }
+    });
+    rx
+}
 
-        // Check for DeepBook pools without DEEP fee coin
-        let has_deepbook = config
-            .monitored_pools
-            .iter()
-            .any(|p| p.dex.to_lowercase() == "deepbook");
-        if has_deepbook
-            && (config.deep_fee_coin_id.is_empty()
-                || config.deep_fee_coin_id == "0x..."
-                || config.deep_fee_coin_id == "0x0")
-        {
-            warn!(
-                "DeepBook pools configured but DEEP_FEE_COIN_ID is not set — \
-                 DeepBook strategies will abort. Get a Coin<DEEP> object: \
-                 `sui client gas --coin-type 0xdeeb...::deep::DEEP`"
-            );
-            warnings += 1;
-        }
-    }
+/// Build the alert router from the `ALERT_*` settings.
+/// Falls back to log-only delivery when Telegram is not configured.
+fn build_alert_router(settings: &RuntimeSettings) -> AlertRouter {
+    let config = AlertConfig {
+        batch_window_ms: settings.alert_batch_window_ms,
+        digest_interval_ms: settings.alert_digest_interval_ms,
+        max_per_window: settings.alert_max_per_hour,
+        ..AlertConfig::default()
+    };
+
+    let token = &settings.alert_telegram_bot_token;
+    let chat_id = &settings.alert_telegram_chat_id;
+    let transport: Box<dyn AlertTransport> = if !token.is_empty() && !chat_id.is_empty() {
+        info!("Alerts: Telegram delivery enabled");
+        Box::new(TelegramTransport::new(token, chat_id))
+    } else {
+        info!("Alerts: log-only (set ALERT_TELEGRAM_BOT_TOKEN and ALERT_TELEGRAM_CHAT_ID)");
+        Box::new(LogTransport)
+    };
 
-    // 4. DEX shared objects
-    if config.cetus_global_config.is_empty() {
-        warn!("CETUS_GLOBAL_CONFIG not set — Cetus strategies will fail");
-        warnings += 1;
-    }
-    if config.turbos_versioned.is_empty() {
-        warn!("TURBOS_VERSIONED not set — Turbos strategies will fail");
-        warnings += 1;
-    }
+    info!(
+        batch_window_ms = %config.batch_window_ms,
+        digest_interval_ms = %config.digest_interval_ms,
+        max_per_hour = %config.max_per_window,
+        "Alert router initialized"
+    );
+    AlertRouter::new(config, transport, now_ms())
+}
 
-    // 5. Strategy params sanity
-    if config.min_profit_mist == 0 {
-        warn!("MIN_PROFIT_MIST is 0 — bot will attempt tiny unprofitable trades");
-        warnings += 1;
-    }
-    if config.max_gas_budget < 10_000_000 {
-        warn!(
-            budget = %config.max_gas_budget,
-            "MAX_GAS_BUDGET is very low — transactions may run out of gas"
-        );
-        warnings += 1;
+/// Build the low-gas / circuit-breaker webhook from the `ALERT_WEBHOOK_*` settings.
+fn build_notifier(settings: &RuntimeSettings) -> Notifier {
+    let interval_ms = settings.alert_webhook_interval_ms;
+    let notifier = Notifier::new(&settings.alert_webhook_url).with_min_interval(Duration::from_millis(interval_ms));
+    if notifier.is_enabled() {
+        info!(interval_ms = %interval_ms, "Webhook alerts enabled (low gas, circuit breaker)");
     }
+    notifier
+}
 
-    // Summary
-    if errors > 0 {
-        error!(
-            errors = %errors,
-            warnings = %warnings,
-            "⛔ Startup validation found {} critical error(s) — bot will likely fail",
-            errors
-        );
-    } else if warnings > 0 {
-        warn!(
-            warnings = %warnings,
-            "⚠️  Startup validation passed with {} warning(s)",
-            warnings
-        );
-    } else {
-        info!("✅ Startup validation passed — all checks OK");
-    }
+/// Get current time in milliseconds since Unix epoch.
+fn now_ms() -> u64 {
+    arb_types::clock::unix_now_ms()
+}
+
+/// Run the package event stream under supervision, restarting on exit.
+fn spawn_event_stream(ws: WsStream, cache: PoolCache, hb: Arc<AtomicU64>) {
+    tokio::spawn(async move {
+        loop {
+            match ws.run(cache.clone()).await {
+                Ok(()) => {
+                    warn!("WebSocket event stream ended cleanly — restarting in 3s");
+                }
+                Err(e) => {
+                    error!(error = %e, "WebSocket event stream failed — restarting in 3s");
+                }
+            }
+            tokio::time::sleep(Duration::from_secs(3)).await;
+            hb.store(now_ms(), Ordering::Relaxed);
+        }
+    });
 }
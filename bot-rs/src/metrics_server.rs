@@ -0,0 +1,113 @@
+//! HTTP `/metrics` endpoint for Prometheus.
+//!
+//! Off unless `METRICS_PORT` is set. Serves [`Metrics::render`] to
+//! `GET /metrics` and 404s anything else. This is a single scrape target,
+//! so it's a minimal HTTP/1.1 responder on the tokio listener (one request
+//! per connection) rather than a web framework.
+
+use anyhow::{Context, Result};
+use arb_types::Metrics;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::debug;
+
+/// Largest request head read; a scrape's is a few hundred bytes.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// How long a client gets to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Bind the endpoint, failing startup if the address is taken.
+pub async fn bind(addr: &str) -> Result<TcpListener> {
+    TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind metrics endpoint on {}", addr))
+}
+
+/// Accept scrapes until the process exits.
+pub async fn serve(listener: TcpListener, metrics: Arc<Metrics>) {
+    loop {
+        let Ok((socket, peer)) = listener.accept().await else { continue };
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            if let Err(e) = respond(socket, &metrics).await {
+                debug!(peer = %peer, error = %e, "Metrics request failed");
+            }
+        });
+    }
+}
+
+async fn respond(mut socket: TcpStream, metrics: &Metrics) -> std::io::Result<()> {
+    let (method, path) = read_request(&mut socket).await?;
+    let (status, content_type, body) = if method == "GET" && path == "/metrics" {
+        ("200 OK", "text/plain; version=0.0.4; charset=utf-8", metrics.render())
+    } else {
+        ("404 Not Found", "text/plain; charset=utf-8", "not found\n".to_string())
+    };
+    write_response(socket, status, content_type, &body).await
+}
+
+/// Read a request head and return its method and path (query dropped).
+pub(crate) async fn read_request(socket: &mut TcpStream) -> std::io::Result<(String, String)> {
+    let mut buf = Vec::with_capacity(512);
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < MAX_REQUEST_BYTES {
+        let n = tokio::time::timeout(READ_TIMEOUT, socket.read(&mut chunk))
+            .await
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "request timed out"))??;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let head = String::from_utf8_lossy(&buf);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default().to_string();
+    Ok((method, path))
+}
+
+/// Write a complete response and close the connection.
+pub(crate) async fn write_response(
+    mut socket: TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    socket.write_all(response.as_bytes()).await?;
+    socket.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_serves_metrics_and_404s_other_paths() {
+        let listener = bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let metrics = Arc::new(Metrics::new());
+        metrics.record_trade(true, 1_000, 100);
+        tokio::spawn(serve(listener, metrics));
+
+        let client = reqwest::Client::new();
+        let resp = client.get(format!("http://{}/metrics", addr)).send().await.unwrap();
+        assert_eq!(resp.status(), 200);
+        assert!(resp.headers()["content-type"].to_str().unwrap().starts_with("text/plain; version=0.0.4"));
+        assert!(resp.text().await.unwrap().contains("arb_profit_mist_total 1000\n"));
+
+        let resp = client.get(format!("http://{}/", addr)).send().await.unwrap();
+        assert_eq!(resp.status(), 404);
+    }
+}
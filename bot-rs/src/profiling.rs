@@ -0,0 +1,365 @@
+//! Runtime-toggleable profiling for production diagnosis.
+//!
+//! Everything defaults off and is switched without a restart via Unix signals:
+//!
+//! - `SIGUSR1` toggles strategy-task sampling: `tokio-metrics` poll/idle/schedule
+//!   stats appended to `<PROFILE_DIR>/task_metrics.jsonl`, plus (with the
+//!   `alloc-counter` feature) a "Cycle allocations" debug record per strategy
+//!   cycle ([`CycleAllocations`]).
+//! - `SIGUSR2` captures a CPU profile for `PROFILE_CPU_SECS` and writes a
+//!   flamegraph SVG to `PROFILE_DIR` (requires the `cpu-profile` feature).
+//!
+//! Signals rather than an HTTP route next to `/status`: the status port is
+//! bound for liveness probes, often on an address the whole cluster can
+//! reach, and carries no authentication, while a CPU capture costs the
+//! strategy task real time. Sending a signal takes a shell on the host or
+//! pod, which collecting the artifacts from `PROFILE_DIR` needs anyway.
+//!
+//! When sampling is off the only cost is the `TaskMonitor` instrumentation on
+//! the strategy task (a few atomic adds per poll) and one relaxed atomic load
+//! per cycle.
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_metrics::{TaskIntervals, TaskMonitor};
+use tracing::{debug, info, warn};
+
+/// File (inside the profile dir) that task-metric samples are appended to.
+pub const TASK_METRICS_FILE: &str = "task_metrics.jsonl";
+
+/// Shared profiling state. Cheap to clone via `Arc`.
+pub struct Profiler {
+    enabled: AtomicBool,
+    monitor: TaskMonitor,
+    intervals: Mutex<TaskIntervals>,
+    dir: PathBuf,
+    cpu_profile_secs: u64,
+}
+
+impl Profiler {
+    pub fn new(dir: impl Into<PathBuf>, cpu_profile_secs: u64) -> Self {
+        let monitor = TaskMonitor::new();
+        let intervals = monitor.intervals();
+        Self {
+            enabled: AtomicBool::new(false),
+            monitor,
+            intervals: Mutex::new(intervals),
+            dir: dir.into(),
+            cpu_profile_secs,
+        }
+    }
+
+    /// Monitor to instrument the strategy task with (`monitor.instrument(fut)`).
+    pub fn monitor(&self) -> &TaskMonitor {
+        &self.monitor
+    }
+
+    /// Whether sampling is currently on.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Turn sampling on or off. Turning it on discards the interval that
+    /// accumulated while off, so the first sample covers only enabled time.
+    pub fn set_enabled(&self, on: bool) {
+        if on && !self.is_enabled() {
+            if let Ok(mut it) = self.intervals.lock() {
+                it.next();
+            }
+        }
+        self.enabled.store(on, Ordering::Relaxed);
+        info!(enabled = %on, dir = %self.dir.display(), "Profiling sampling toggled");
+    }
+
+    /// Append one task-metrics sample covering the time since the last one.
+    /// Returns the artifact path, or `None` if sampling is off.
+    pub fn sample(&self) -> Result<Option<PathBuf>> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+        let metrics = match self.intervals.lock().ok().and_then(|mut it| it.next()) {
+            Some(m) => m,
+            None => return Ok(None),
+        };
+
+        let record = serde_json::json!({
+            "ts_ms": now_ms(),
+            "polls": metrics.total_poll_count,
+            "mean_poll_us": metrics.mean_poll_duration().as_micros() as u64,
+            "slow_polls": metrics.total_slow_poll_count,
+            "total_poll_us": metrics.total_poll_duration.as_micros() as u64,
+            "total_idle_us": metrics.total_idle_duration.as_micros() as u64,
+            "total_scheduled_us": metrics.total_scheduled_duration.as_micros() as u64,
+            "mean_scheduled_us": metrics.mean_scheduled_duration().as_micros() as u64,
+        });
+
+        std::fs::create_dir_all(&self.dir).context("Failed to create profile dir")?;
+        let path = self.dir.join(TASK_METRICS_FILE);
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .context("Failed to open task metrics file")?;
+        writeln!(file, "{}", record)?;
+        Ok(Some(path))
+    }
+
+    /// Sample every `every` while enabled. Runs forever.
+    pub async fn run_sampler(self: Arc<Self>, every: Duration) {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.sample() {
+                warn!(error = %e, "Failed to write task metrics sample");
+            }
+        }
+    }
+
+    /// Capture a CPU profile for the configured duration and write a
+    /// flamegraph SVG to the profile dir.
+    #[cfg(feature = "cpu-profile")]
+    pub async fn capture_cpu_profile(&self) -> Result<PathBuf> {
+        let guard = pprof::ProfilerGuardBuilder::default()
+            .frequency(99)
+            .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+            .build()
+            .context("Failed to start CPU profiler")?;
+        info!(secs = %self.cpu_profile_secs, "CPU profile capture started");
+        tokio::time::sleep(Duration::from_secs(self.cpu_profile_secs)).await;
+
+        let report = guard.report().build().context("Failed to build CPU profile")?;
+        std::fs::create_dir_all(&self.dir).context("Failed to create profile dir")?;
+        let path = self.dir.join(format!("cpu_{}.svg", now_ms()));
+        let file = std::fs::File::create(&path).context("Failed to create flamegraph file")?;
+        report.flamegraph(file).context("Failed to write flamegraph")?;
+        info!(path = %path.display(), "CPU profile written");
+        Ok(path)
+    }
+
+    /// Capture a CPU profile (unavailable without the `cpu-profile` feature).
+    #[cfg(not(feature = "cpu-profile"))]
+    pub async fn capture_cpu_profile(&self) -> Result<PathBuf> {
+        anyhow::bail!(
+            "CPU profiling not compiled in ({}s requested) — rebuild with --features cpu-profile",
+            self.cpu_profile_secs
+        )
+    }
+
+    /// Directory artifacts are written to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+/// Listen for SIGUSR1 (toggle sampling) and SIGUSR2 (capture CPU profile).
+#[cfg(unix)]
+pub fn spawn_signal_controls(profiler: Arc<Profiler>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let p = profiler.clone();
+    tokio::spawn(async move {
+        let mut usr1 = match signal(SignalKind::user_defined1()) {
+            Ok(s) => s,
+            Err(e) => return warn!(error = %e, "Failed to install SIGUSR1 handler"),
+        };
+        while usr1.recv().await.is_some() {
+            p.set_enabled(!p.is_enabled());
+        }
+    });
+
+    tokio::spawn(async move {
+        let mut usr2 = match signal(SignalKind::user_defined2()) {
+            Ok(s) => s,
+            Err(e) => return warn!(error = %e, "Failed to install SIGUSR2 handler"),
+        };
+        while usr2.recv().await.is_some() {
+            if let Err(e) = profiler.capture_cpu_profile().await {
+                warn!(error = %e, "CPU profile capture failed");
+            }
+        }
+    });
+}
+
+/// Signal controls are Unix-only; elsewhere profiling stays off.
+#[cfg(not(unix))]
+pub fn spawn_signal_controls(_profiler: Arc<Profiler>) {
+    warn!("Profiling signal controls are only available on Unix");
+}
+
+/// Counting global allocator for per-cycle allocation stats.
+#[cfg(feature = "alloc-counter")]
+pub mod alloc {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static BYTES: AtomicU64 = AtomicU64::new(0);
+
+    /// Wraps the system allocator, counting allocations and bytes.
+    pub struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    /// Total `(allocations, bytes)` since process start.
+    pub fn snapshot() -> (u64, u64) {
+        (ALLOCATIONS.load(Ordering::Relaxed), BYTES.load(Ordering::Relaxed))
+    }
+}
+
+/// Total `(allocations, bytes)` since process start, or `None` when the
+/// `alloc-counter` feature is off.
+pub fn allocation_snapshot() -> Option<(u64, u64)> {
+    #[cfg(feature = "alloc-counter")]
+    return Some(alloc::snapshot());
+    #[cfg(not(feature = "alloc-counter"))]
+    None
+}
+
+/// Allocations over one strategy cycle, from the start of its body to
+/// whichever way it exits: logged as the cycle's "Cycle allocations" record
+/// when dropped. The counter is process-wide, so allocations other tasks
+/// make while the cycle awaits are included.
+pub struct CycleAllocations {
+    cycle_ms: u64,
+    started: Instant,
+    start: (u64, u64),
+}
+
+impl CycleAllocations {
+    /// Start counting for the cycle stamped `cycle_ms`. `None` unless
+    /// sampling is on and the `alloc-counter` feature is compiled in.
+    pub fn start(profiler: &Profiler, cycle_ms: u64) -> Option<Self> {
+        if !profiler.is_enabled() {
+            return None;
+        }
+        Some(Self { cycle_ms, started: Instant::now(), start: allocation_snapshot()? })
+    }
+}
+
+impl Drop for CycleAllocations {
+    fn drop(&mut self) {
+        let Some((allocs, bytes)) = allocation_snapshot() else { return };
+        debug!(
+            cycle_ms = %self.cycle_ms,
+            elapsed_us = %self.started.elapsed().as_micros(),
+            allocs = %(allocs - self.start.0),
+            bytes = %(bytes - self.start.1),
+            "Cycle allocations (process-wide, cycle start to end)"
+        );
+    }
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("arb-profile-{}-{}", name, now_ms()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// A few synthetic strategy cycles on an instrumented task.
+    async fn run_cycles(profiler: &Profiler, n: usize) {
+        profiler
+            .monitor()
+            .instrument(async move {
+                for _ in 0..n {
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                    std::hint::black_box((0..1_000u64).sum::<u64>());
+                }
+            })
+            .await;
+    }
+
+    fn line_count(path: &Path) -> usize {
+        std::fs::read_to_string(path).map(|s| s.lines().count()).unwrap_or(0)
+    }
+
+    #[test]
+    fn test_cycle_allocations_counted_only_while_sampling() {
+        let profiler = Profiler::new(temp_dir("cycle-allocs"), 1);
+        assert!(CycleAllocations::start(&profiler, 0).is_none());
+        profiler.set_enabled(true);
+        let counting = CycleAllocations::start(&profiler, 0);
+        assert_eq!(counting.is_some(), cfg!(feature = "alloc-counter"));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_by_default_writes_nothing() {
+        let dir = temp_dir("off");
+        let profiler = Profiler::new(&dir, 1);
+        run_cycles(&profiler, 3).await;
+        assert!(profiler.sample().unwrap().is_none());
+        assert!(!dir.join(TASK_METRICS_FILE).exists());
+    }
+
+    #[tokio::test]
+    async fn test_toggle_on_produces_artifact_and_off_stops() {
+        let dir = temp_dir("toggle");
+        let profiler = Profiler::new(&dir, 1);
+
+        profiler.set_enabled(true);
+        run_cycles(&profiler, 5).await;
+        let path = profiler.sample().unwrap().expect("sampling on should write");
+        assert_eq!(line_count(&path), 1);
+
+        let record: serde_json::Value =
+            serde_json::from_str(std::fs::read_to_string(&path).unwrap().trim()).unwrap();
+        assert!(record["polls"].as_u64().unwrap() >= 5, "got {}", record);
+
+        profiler.set_enabled(false);
+        run_cycles(&profiler, 5).await;
+        assert!(profiler.sample().unwrap().is_none());
+        assert_eq!(line_count(&path), 1, "toggle-off must stop collection");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_reenable_discards_disabled_interval() {
+        let dir = temp_dir("reenable");
+        let profiler = Profiler::new(&dir, 1);
+
+        run_cycles(&profiler, 10).await; // while off
+        profiler.set_enabled(true);
+        run_cycles(&profiler, 2).await;
+        let path = profiler.sample().unwrap().unwrap();
+        let record: serde_json::Value =
+            serde_json::from_str(std::fs::read_to_string(&path).unwrap().trim()).unwrap();
+        assert!(record["polls"].as_u64().unwrap() < 10, "got {}", record);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(feature = "cpu-profile")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cpu_profile_written() {
+        let dir = temp_dir("cpu");
+        let profiler = Profiler::new(&dir, 1);
+        let path = profiler.capture_cpu_profile().await.unwrap();
+        assert!(path.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,204 @@
+//! On-chain P&L reconciliation.
+//!
+//! The trade log's P&L is what each submission reported: profit from the
+//! arb event, minus gas. The ground truth is the wallet's SUI balance.
+//! [`Reconciler`] samples the balance at window boundaries and reports the
+//! actual delta next to the P&L the bot accounted for over the same window,
+//! so costs the accounting never saw (untracked gas, failed-but-charged
+//! transactions) show up as a discrepancy.
+//!
+//! Deposits, withdrawals, coin-merge gas, and profit taken in non-SUI coins
+//! also land in the discrepancy — the report says how far apart the two
+//! numbers are, not why.
+//!
+//! Sampling happens at the top of a strategy cycle, when no submission of
+//! ours is in flight, so a window never splits a trade.
+
+use tracing::{info, warn};
+
+/// How long to wait before retrying after a failed balance fetch.
+const RETRY_MS: u64 = 30_000;
+
+/// One closed reconciliation window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub start_balance: u64,
+    pub end_balance: u64,
+    /// Net P&L the bot recorded for submissions inside the window.
+    pub accounted_mist: i64,
+}
+
+impl Window {
+    /// Actual wallet balance change over the window.
+    pub fn onchain_delta(&self) -> i64 {
+        self.end_balance as i64 - self.start_balance as i64
+    }
+
+    /// On-chain delta minus accounted P&L. Negative = the wallet lost more
+    /// (or gained less) than the bot thinks it did.
+    pub fn discrepancy(&self) -> i64 {
+        self.onchain_delta() - self.accounted_mist
+    }
+}
+
+/// Balance and accounted total at the start of the open window.
+#[derive(Debug, Clone, Copy)]
+struct Boundary {
+    ms: u64,
+    balance: u64,
+    accounted_total: i64,
+}
+
+/// Periodic wallet-balance vs. trade-log P&L comparison.
+pub struct Reconciler {
+    interval_ms: u64,
+    tolerance_mist: u64,
+    /// Running net P&L of every landed submission since startup.
+    accounted_total: i64,
+    start: Option<Boundary>,
+    next_due_ms: u64,
+}
+
+impl Reconciler {
+    /// `tolerance_mist` is the largest |discrepancy| per window that is
+    /// not flagged.
+    pub fn new(interval_ms: u64, tolerance_mist: u64) -> Self {
+        Self {
+            interval_ms,
+            tolerance_mist,
+            accounted_total: 0,
+            start: None,
+            next_due_ms: 0,
+        }
+    }
+
+    /// Record one landed submission: profit (0 if it reverted) minus gas.
+    pub fn record(&mut self, net_mist: i64) {
+        self.accounted_total += net_mist;
+    }
+
+    /// Whether a balance sample is due. The first is due immediately to
+    /// open the first window.
+    pub fn is_due(&self, now_ms: u64) -> bool {
+        now_ms >= self.next_due_ms
+    }
+
+    /// The balance fetch failed; try again shortly rather than every cycle.
+    pub fn retry_later(&mut self, now_ms: u64) {
+        self.next_due_ms = now_ms + RETRY_MS.min(self.interval_ms);
+    }
+
+    /// Take a balance sample. Closes the open window (if any) and opens the
+    /// next one.
+    pub fn observe(&mut self, balance: u64, now_ms: u64) -> Option<Window> {
+        let end = Boundary {
+            ms: now_ms,
+            balance,
+            accounted_total: self.accounted_total,
+        };
+        self.next_due_ms = now_ms + self.interval_ms;
+        let start = self.start.replace(end)?;
+        Some(Window {
+            start_ms: start.ms,
+            end_ms: end.ms,
+            start_balance: start.balance,
+            end_balance: end.balance,
+            accounted_mist: end.accounted_total - start.accounted_total,
+        })
+    }
+
+    /// Whether the window's discrepancy exceeds the tolerance.
+    pub fn is_discrepant(&self, window: &Window) -> bool {
+        window.discrepancy().unsigned_abs() > self.tolerance_mist
+    }
+
+    /// Log a closed window; returns whether it was flagged.
+    pub fn report(&self, window: &Window) -> bool {
+        let flagged = self.is_discrepant(window);
+        if flagged {
+            warn!(
+                window_ms = %(window.end_ms - window.start_ms),
+                start_balance = %window.start_balance,
+                end_balance = %window.end_balance,
+                onchain_delta = %window.onchain_delta(),
+                accounted = %window.accounted_mist,
+                discrepancy = %window.discrepancy(),
+                tolerance = %self.tolerance_mist,
+                "⚠️  On-chain balance change disagrees with trade-log P&L"
+            );
+        } else {
+            info!(
+                window_ms = %(window.end_ms - window.start_ms),
+                start_balance = %window.start_balance,
+                end_balance = %window.end_balance,
+                onchain_delta = %window.onchain_delta(),
+                accounted = %window.accounted_mist,
+                discrepancy = %window.discrepancy(),
+                "P&L reconciled against wallet balance"
+            );
+        }
+        flagged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_sample_opens_window() {
+        let mut r = Reconciler::new(60_000, 1_000);
+        assert!(r.is_due(0));
+        assert!(r.observe(5_000_000, 1_000).is_none());
+        assert!(!r.is_due(30_000));
+        assert!(r.is_due(61_000));
+    }
+
+    #[test]
+    fn test_matching_window_not_flagged() {
+        let mut r = Reconciler::new(60_000, 1_000);
+        r.observe(5_000_000, 0);
+        r.record(300_000 - 20_000);
+        r.record(-20_000); // reverted: gas only
+        let w = r.observe(5_260_000, 60_000).unwrap();
+        assert_eq!(w.onchain_delta(), 260_000);
+        assert_eq!(w.accounted_mist, 260_000);
+        assert_eq!(w.discrepancy(), 0);
+        assert!(!r.is_discrepant(&w));
+    }
+
+    #[test]
+    fn test_untracked_loss_flagged() {
+        let mut r = Reconciler::new(60_000, 1_000);
+        r.observe(5_000_000, 0);
+        r.record(100_000);
+        // Wallet actually lost 50k
+        let w = r.observe(4_950_000, 60_000).unwrap();
+        assert_eq!(w.discrepancy(), -150_000);
+        assert!(r.is_discrepant(&w));
+    }
+
+    #[test]
+    fn test_windows_are_independent() {
+        let mut r = Reconciler::new(60_000, 1_000);
+        r.observe(1_000_000, 0);
+        r.record(500_000);
+        r.observe(1_500_000, 60_000).unwrap();
+        // Second window only counts what was recorded after the boundary
+        r.record(-10_000);
+        let w = r.observe(1_490_000, 120_000).unwrap();
+        assert_eq!(w.start_ms, 60_000);
+        assert_eq!(w.accounted_mist, -10_000);
+        assert_eq!(w.discrepancy(), 0);
+    }
+
+    #[test]
+    fn test_retry_after_failed_fetch() {
+        let mut r = Reconciler::new(3_600_000, 1_000);
+        r.retry_later(1_000);
+        assert!(!r.is_due(1_000 + RETRY_MS - 1));
+        assert!(r.is_due(1_000 + RETRY_MS));
+    }
+}
@@ -0,0 +1,416 @@
+//! Runtime settings: every knob the bot reads from the environment on top of
+//! [`Config`], read in one place. The struct's fields feed
+//! [`Config::config_hash`], so a new knob is hashed unless it is explicitly
+//! `#[serde(skip)]`ped — reserved for knobs that can't change what gets
+//! traded (listen addresses, log files, profiling) and for credentials.
+//! Telemetry keeps reading its own.
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use arb_collector::deepbook_book::{DEFAULT_BOOK_TICKS, DEFAULT_DEEPBOOK_V3_PACKAGE};
+use arb_collector::ws_endpoints::DEFAULT_MAX_FAILURES;
+use arb_collector::PoolWarmup;
+use arb_strategy::scanner::DEFAULT_QUAD_HOP_MIN_CROSS_RATE;
+use arb_strategy::{MissingFeePolicy, SearchMethod};
+use arb_types::notifier::DEFAULT_MIN_INTERVAL;
+use arb_types::Config;
+use serde::{Serialize, Serializer};
+
+/// Strategy watchdog fires after this many missed poll intervals...
+const STRATEGY_WATCHDOG_MULTIPLIER: u64 = 60;
+/// ...but never sooner than this (ms), so slow RPC cycles aren't mistaken for hangs.
+const MIN_STRATEGY_WATCHDOG_MS: u64 = 30_000;
+
+/// How PTBs are built (`PTB_BUILD_MODE`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PtbBuildMode {
+    /// Via the node's `unsafe_moveCall`.
+    Rpc,
+    /// Encoded here (the node path is kept as the fallback).
+    Local,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuntimeSettings {
+    // ── Pool cache ──
+    /// `POOL_WARMUP_UPDATES`
+    pub pool_warmup_updates: u32,
+    /// `POOL_WARMUP_MS`
+    pub pool_warmup_ms: u64,
+    /// `POOL_EVICT_INTERVAL_MS` (0 = never evict)
+    pub pool_evict_interval_ms: u64,
+    /// `POOL_EVICT_MAX_AGE_MS`
+    pub pool_evict_max_age_ms: u64,
+    /// `POOLS_RELOAD_INTERVAL_MS`
+    pub pools_reload_interval_ms: u64,
+
+    // ── Collectors ──
+    /// `DEEPBOOK_V3_PACKAGE_ID`
+    pub deepbook_v3_package_id: String,
+    /// `DEEPBOOK_BOOK_TICKS`
+    pub deepbook_book_ticks: u64,
+    /// `POLL_INTERVAL_MIN_MS` (default `POLL_INTERVAL_MS`)
+    pub poll_interval_min_ms: u64,
+    /// `POLL_INTERVAL_MAX_MS` (default `POLL_INTERVAL_MS`)
+    pub poll_interval_max_ms: u64,
+    /// `USE_WEBSOCKET`
+    pub use_websocket: bool,
+    /// `WS_MODE`: `event` or `tx`
+    pub ws_mode: String,
+    /// `WS_FAILOVER_AFTER`
+    pub ws_failover_after: u32,
+    /// `WS_IDLE_TIMEOUT_MS`
+    pub ws_idle_timeout_ms: u64,
+    /// `WS_REFETCH_DEBOUNCE_MS`
+    pub ws_refetch_debounce_ms: u64,
+    /// `WS_MAX_SUBSCRIPTIONS`
+    pub ws_max_subscriptions: usize,
+    /// `WS_REBALANCE_INTERVAL_MS`
+    pub ws_rebalance_interval_ms: u64,
+
+    // ── Scanning ──
+    /// `MISSING_FEE_POLICY`
+    #[serde(serialize_with = "debug_name")]
+    pub missing_fee_policy: MissingFeePolicy,
+    /// `SEARCH_METHOD`
+    #[serde(serialize_with = "debug_name")]
+    pub search_method: SearchMethod,
+    /// `ENABLE_QUAD_HOP`: scan 4-cycles of the token graph (opt-in)
+    pub enable_quad_hop: bool,
+    /// `QUAD_HOP_MIN_CROSS_RATE`
+    pub quad_hop_min_cross_rate: f64,
+    /// `STRATEGY_WEIGHTS`, as read at startup (SIGHUP re-reads it)
+    pub strategy_weights: String,
+    /// `MAX_OPPORTUNITIES_PER_CYCLE`: only the top-K ranked are processed (0 = all)
+    pub max_opportunities_per_cycle: usize,
+
+    // ── Building and dry-runs ──
+    /// `DRY_RUN_BATCH_SIZE`: candidates dry-run per cycle, in one batch request
+    pub dry_run_batch_size: usize,
+    /// `DRY_RUN_TIMEOUT_MS`
+    pub dry_run_timeout_ms: u64,
+    /// `PTB_BUILD_MODE`
+    pub ptb_build_mode: PtbBuildMode,
+    /// `PTB_BUILD_MAX_RETRIES`
+    pub ptb_build_max_retries: u32,
+    /// `BUILD_MAX_CONSECUTIVE_FAILURES`
+    pub build_max_consecutive_failures: u32,
+    /// `BUILD_SUSPEND_MS`
+    pub build_suspend_ms: u64,
+    /// `INVENTORY_COINS`
+    pub inventory_coins: Vec<String>,
+    /// `INVENTORY_REFRESH_MS`
+    pub inventory_refresh_ms: u64,
+
+    // ── Gas ──
+    /// `GAS_PRICE_REFRESH_MS` (0 = flat `MAX_GAS_BUDGET`)
+    pub gas_price_refresh_ms: u64,
+    /// `GAS_COIN_POOL_SIZE` (0 or 1 = let the builder pick)
+    pub gas_coin_pool_size: usize,
+    /// `GAS_COIN_REFRESH_MS`
+    pub gas_coin_refresh_ms: u64,
+    /// `MIN_GAS_BALANCE_MIST`
+    pub min_gas_balance_mist: u64,
+    /// `GAS_REFRESH_MARGIN_MIST`
+    pub gas_refresh_margin_mist: u64,
+    /// `GAS_ALERT_THRESHOLD_MIST` (default `MIN_GAS_BALANCE_MIST`)
+    pub gas_alert_threshold_mist: u64,
+    /// `TARGET_GAS_COIN_COUNT`, raised to the gas coin pool's size
+    pub target_gas_coin_count: usize,
+
+    // ── Risk ──
+    /// `STRATEGY_QUARANTINE_FAILURES`
+    pub strategy_quarantine_failures: u32,
+    /// `STRATEGY_QUARANTINE_LOSS_MIST`
+    pub strategy_quarantine_loss_mist: i64,
+    /// `STRATEGY_QUARANTINE_COOLDOWN_MS`
+    pub strategy_quarantine_cooldown_ms: u64,
+    /// `OPPORTUNITY_FAILURE_COOLDOWN_MS`
+    pub opportunity_failure_cooldown_ms: u64,
+    /// `OPPORTUNITY_SUCCESS_COOLDOWN_MS`
+    pub opportunity_success_cooldown_ms: u64,
+    /// `STRATEGY_WATCHDOG_TIMEOUT_MS`
+    pub strategy_watchdog_timeout_ms: u64,
+
+    // ── Reporting ──
+    /// `PNL_SUMMARY_EVERY_TRADES`
+    pub pnl_summary_every_trades: u64,
+    /// `PNL_SUMMARY_INTERVAL_MS`
+    pub pnl_summary_interval_ms: u64,
+    /// `PNL_RECONCILE_INTERVAL_MS` (0 = off)
+    pub pnl_reconcile_interval_ms: u64,
+    /// `PNL_RECONCILE_TOLERANCE_MIST`
+    pub pnl_reconcile_tolerance_mist: u64,
+
+    // ── Alerts ──
+    /// `ALERT_BATCH_WINDOW_MS`
+    pub alert_batch_window_ms: u64,
+    /// `ALERT_DIGEST_INTERVAL_MS`
+    pub alert_digest_interval_ms: u64,
+    /// `ALERT_MAX_PER_HOUR`
+    pub alert_max_per_hour: usize,
+    /// `ALERT_WEBHOOK_INTERVAL_MS`
+    pub alert_webhook_interval_ms: u64,
+    /// `ALERT_TELEGRAM_BOT_TOKEN` (empty = log-only alerts)
+    #[serde(skip)]
+    pub alert_telegram_bot_token: String,
+    /// `ALERT_TELEGRAM_CHAT_ID`
+    #[serde(skip)]
+    pub alert_telegram_chat_id: String,
+    /// `ALERT_WEBHOOK_URL` (empty = off)
+    #[serde(skip)]
+    pub alert_webhook_url: String,
+
+    // ── Operational (not hashed) ──
+    /// `METRICS_PORT` (0 = off)
+    #[serde(skip)]
+    pub metrics_port: u16,
+    /// `METRICS_BIND`
+    #[serde(skip)]
+    pub metrics_bind: String,
+    /// `STATUS_PORT` (0 = off)
+    #[serde(skip)]
+    pub status_port: u16,
+    /// `STATUS_BIND`
+    #[serde(skip)]
+    pub status_bind: String,
+    /// `BREAKER_STATE_PATH` (empty = not persisted)
+    #[serde(skip)]
+    pub breaker_state_path: String,
+    /// `TRADE_LOG_PATH` (empty = off)
+    #[serde(skip)]
+    pub trade_log_path: String,
+    /// `LOG_ROTATE_MAX_BYTES`
+    #[serde(skip)]
+    pub log_rotate_max_bytes: u64,
+    /// `LOG_ROTATE_DAILY`
+    #[serde(skip)]
+    pub log_rotate_daily: bool,
+    /// `LOG_COMPRESS`
+    #[serde(skip)]
+    pub log_compress: bool,
+    /// `LOG_RETENTION_DAYS`
+    #[serde(skip)]
+    pub log_retention_days: u64,
+    /// `PROFILE_DIR`
+    #[serde(skip)]
+    pub profile_dir: String,
+    /// `PROFILE_CPU_SECS`
+    #[serde(skip)]
+    pub profile_cpu_secs: u64,
+    /// `PROFILE_SAMPLE_INTERVAL_MS`
+    #[serde(skip)]
+    pub profile_sample_interval_ms: u64,
+}
+
+impl RuntimeSettings {
+    /// Read every knob from the process environment.
+    pub fn from_env(config: &Config) -> Result<Self> {
+        Self::read(config, |name| std::env::var(name).ok())
+    }
+
+    /// Read every knob via `var`, falling back to its default when unset or
+    /// empty. A value that doesn't parse is an error, not the default.
+    pub fn read(config: &Config, var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        fn or<T>(var: &impl Fn(&str) -> Option<String>, name: &str, default: T) -> Result<T>
+        where
+            T: std::str::FromStr,
+            T::Err: std::fmt::Display,
+        {
+            match var(name).filter(|v| !v.trim().is_empty()) {
+                Some(v) => v.trim().parse().map_err(|e| anyhow!("Invalid {} '{}': {}", name, v, e)),
+                None => Ok(default),
+            }
+        }
+        let string = |name: &str, default: &str| var(name).unwrap_or_else(|| default.to_string());
+
+        let poll_interval_min_ms = or(&var, "POLL_INTERVAL_MIN_MS", config.poll_interval_ms)?;
+        let poll_interval_max_ms = or(&var, "POLL_INTERVAL_MAX_MS", config.poll_interval_ms)?;
+        ensure!(
+            poll_interval_min_ms <= poll_interval_max_ms,
+            "POLL_INTERVAL_MIN_MS ({}) is above POLL_INTERVAL_MAX_MS ({})",
+            poll_interval_min_ms,
+            poll_interval_max_ms
+        );
+        let ptb_build_mode = match string("PTB_BUILD_MODE", "rpc").trim().to_lowercase().as_str() {
+            "rpc" => PtbBuildMode::Rpc,
+            "local" => PtbBuildMode::Local,
+            other => bail!("Invalid PTB_BUILD_MODE '{}': expected 'rpc' or 'local'", other),
+        };
+        let gas_coin_pool_size = or(&var, "GAS_COIN_POOL_SIZE", 0)?;
+        let min_gas_balance_mist = or(&var, "MIN_GAS_BALANCE_MIST", 100_000_000)?;
+
+        Ok(Self {
+            pool_warmup_updates: or(&var, "POOL_WARMUP_UPDATES", 1)?,
+            pool_warmup_ms: or(&var, "POOL_WARMUP_MS", 0)?,
+            pool_evict_interval_ms: or(&var, "POOL_EVICT_INTERVAL_MS", 0)?,
+            pool_evict_max_age_ms: or(&var, "POOL_EVICT_MAX_AGE_MS", 600_000)?,
+            pools_reload_interval_ms: or(&var, "POOLS_RELOAD_INTERVAL_MS", 10_000)?,
+
+            deepbook_v3_package_id: var("DEEPBOOK_V3_PACKAGE_ID")
+                .filter(|p| !p.is_empty())
+                .unwrap_or_else(|| DEFAULT_DEEPBOOK_V3_PACKAGE.to_string()),
+            deepbook_book_ticks: or(&var, "DEEPBOOK_BOOK_TICKS", DEFAULT_BOOK_TICKS)?,
+            poll_interval_min_ms,
+            poll_interval_max_ms,
+            use_websocket: or(&var, "USE_WEBSOCKET", false)?,
+            ws_mode: string("WS_MODE", "event"),
+            ws_failover_after: or(&var, "WS_FAILOVER_AFTER", DEFAULT_MAX_FAILURES)?,
+            ws_idle_timeout_ms: or(&var, "WS_IDLE_TIMEOUT_MS", 60_000)?,
+            ws_refetch_debounce_ms: or(&var, "WS_REFETCH_DEBOUNCE_MS", 200)?,
+            ws_max_subscriptions: or(&var, "WS_MAX_SUBSCRIPTIONS", 100)?,
+            ws_rebalance_interval_ms: or(&var, "WS_REBALANCE_INTERVAL_MS", 300_000)?,
+
+            missing_fee_policy: string("MISSING_FEE_POLICY", "skip")
+                .parse()
+                .context("Invalid MISSING_FEE_POLICY")?,
+            search_method: string("SEARCH_METHOD", "ternary").parse().context("Invalid SEARCH_METHOD")?,
+            enable_quad_hop: or(&var, "ENABLE_QUAD_HOP", false)?,
+            quad_hop_min_cross_rate: or(&var, "QUAD_HOP_MIN_CROSS_RATE", DEFAULT_QUAD_HOP_MIN_CROSS_RATE)?,
+            strategy_weights: string("STRATEGY_WEIGHTS", ""),
+            max_opportunities_per_cycle: or(&var, "MAX_OPPORTUNITIES_PER_CYCLE", 50)?,
+
+            dry_run_batch_size: or(&var, "DRY_RUN_BATCH_SIZE", 3)?,
+            dry_run_timeout_ms: or(&var, "DRY_RUN_TIMEOUT_MS", 2_000)?,
+            ptb_build_mode,
+            ptb_build_max_retries: or(&var, "PTB_BUILD_MAX_RETRIES", 1)?,
+            build_max_consecutive_failures: or(&var, "BUILD_MAX_CONSECUTIVE_FAILURES", 3)?,
+            build_suspend_ms: or(&var, "BUILD_SUSPEND_MS", 600_000)?,
+            inventory_coins: string("INVENTORY_COINS", "")
+                .split(',')
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(str::to_string)
+                .collect(),
+            inventory_refresh_ms: or(&var, "INVENTORY_REFRESH_MS", 30_000)?,
+
+            gas_price_refresh_ms: or(&var, "GAS_PRICE_REFRESH_MS", 60_000)?,
+            gas_coin_pool_size,
+            gas_coin_refresh_ms: or(&var, "GAS_COIN_REFRESH_MS", 10_000)?,
+            min_gas_balance_mist,
+            gas_refresh_margin_mist: or(&var, "GAS_REFRESH_MARGIN_MIST", 50_000_000)?,
+            gas_alert_threshold_mist: or(&var, "GAS_ALERT_THRESHOLD_MIST", min_gas_balance_mist)?,
+            // At least the gas coin pool's size, so merging doesn't undo its splits
+            target_gas_coin_count: or::<usize>(&var, "TARGET_GAS_COIN_COUNT", 1)?.max(gas_coin_pool_size),
+
+            strategy_quarantine_failures: or(&var, "STRATEGY_QUARANTINE_FAILURES", 10)?,
+            strategy_quarantine_loss_mist: or(&var, "STRATEGY_QUARANTINE_LOSS_MIST", 200_000_000)?,
+            strategy_quarantine_cooldown_ms: or(&var, "STRATEGY_QUARANTINE_COOLDOWN_MS", 1_800_000)?,
+            opportunity_failure_cooldown_ms: or(&var, "OPPORTUNITY_FAILURE_COOLDOWN_MS", 10_000)?,
+            opportunity_success_cooldown_ms: or(&var, "OPPORTUNITY_SUCCESS_COOLDOWN_MS", 2_000)?,
+            strategy_watchdog_timeout_ms: or(
+                &var,
+                "STRATEGY_WATCHDOG_TIMEOUT_MS",
+                (config.poll_interval_ms * STRATEGY_WATCHDOG_MULTIPLIER).max(MIN_STRATEGY_WATCHDOG_MS),
+            )?,
+
+            pnl_summary_every_trades: or(&var, "PNL_SUMMARY_EVERY_TRADES", 10)?,
+            pnl_summary_interval_ms: or(&var, "PNL_SUMMARY_INTERVAL_MS", 900_000)?,
+            pnl_reconcile_interval_ms: or(&var, "PNL_RECONCILE_INTERVAL_MS", 0)?,
+            pnl_reconcile_tolerance_mist: or(&var, "PNL_RECONCILE_TOLERANCE_MIST", 10_000_000)?,
+
+            alert_batch_window_ms: or(&var, "ALERT_BATCH_WINDOW_MS", 300_000)?,
+            alert_digest_interval_ms: or(&var, "ALERT_DIGEST_INTERVAL_MS", 86_400_000)?,
+            alert_max_per_hour: or(&var, "ALERT_MAX_PER_HOUR", 20)?,
+            alert_webhook_interval_ms: or(&var, "ALERT_WEBHOOK_INTERVAL_MS", DEFAULT_MIN_INTERVAL.as_millis() as u64)?,
+            alert_telegram_bot_token: string("ALERT_TELEGRAM_BOT_TOKEN", ""),
+            alert_telegram_chat_id: string("ALERT_TELEGRAM_CHAT_ID", ""),
+            alert_webhook_url: string("ALERT_WEBHOOK_URL", ""),
+
+            metrics_port: or(&var, "METRICS_PORT", 0)?,
+            metrics_bind: string("METRICS_BIND", "127.0.0.1"),
+            status_port: or(&var, "STATUS_PORT", 0)?,
+            status_bind: string("STATUS_BIND", "127.0.0.1"),
+            breaker_state_path: string("BREAKER_STATE_PATH", ""),
+            trade_log_path: string("TRADE_LOG_PATH", ""),
+            log_rotate_max_bytes: or(&var, "LOG_ROTATE_MAX_BYTES", 0)?,
+            log_rotate_daily: or(&var, "LOG_ROTATE_DAILY", true)?,
+            log_compress: or(&var, "LOG_COMPRESS", true)?,
+            log_retention_days: or(&var, "LOG_RETENTION_DAYS", 0)?,
+            profile_dir: string("PROFILE_DIR", "./debug"),
+            profile_cpu_secs: or(&var, "PROFILE_CPU_SECS", 30)?,
+            profile_sample_interval_ms: or(&var, "PROFILE_SAMPLE_INTERVAL_MS", 10_000)?,
+        })
+    }
+
+    pub fn pool_warmup(&self) -> PoolWarmup {
+        PoolWarmup { min_updates: self.pool_warmup_updates, min_age_ms: self.pool_warmup_ms }
+    }
+}
+
+/// Serialize a settings enum by its `Debug` name.
+fn debug_name<T: std::fmt::Debug, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("{:?}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn read(vars: &[(&str, &str)]) -> RuntimeSettings {
+        let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        RuntimeSettings::read(&Config::for_tests("http://localhost", Vec::new()), |name| vars.get(name).cloned())
+            .unwrap()
+    }
+
+    fn hash(vars: &[(&str, &str)]) -> String {
+        Config::for_tests("http://localhost", Vec::new()).config_hash(&read(vars))
+    }
+
+    #[test]
+    fn test_trading_knobs_change_the_hash() {
+        let base = hash(&[]);
+        for (name, value) in [
+            ("PTB_BUILD_MODE", "local"),
+            ("INVENTORY_COINS", "0x2::sui::SUI"),
+            ("STRATEGY_QUARANTINE_FAILURES", "3"),
+            ("STRATEGY_QUARANTINE_LOSS_MIST", "1"),
+            ("STRATEGY_QUARANTINE_COOLDOWN_MS", "1"),
+            ("OPPORTUNITY_FAILURE_COOLDOWN_MS", "1"),
+            ("OPPORTUNITY_SUCCESS_COOLDOWN_MS", "1"),
+            ("DRY_RUN_BATCH_SIZE", "1"),
+            ("SEARCH_METHOD", "golden"),
+            ("GAS_COIN_POOL_SIZE", "4"),
+            ("ALERT_MAX_PER_HOUR", "5"),
+        ] {
+            assert_ne!(hash(&[(name, value)]), base, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_operational_knobs_leave_the_hash_alone() {
+        let base = hash(&[]);
+        for (name, value) in [
+            ("METRICS_PORT", "9100"),
+            ("TRADE_LOG_PATH", "/tmp/trades.jsonl"),
+            ("PROFILE_DIR", "/tmp"),
+            ("ALERT_TELEGRAM_BOT_TOKEN", "123:secret"),
+            ("ALERT_WEBHOOK_URL", "https://hooks.example.com/secret"),
+        ] {
+            assert_eq!(hash(&[(name, value)]), base, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_build_mode_is_rejected() {
+        let config = Config::for_tests("http://localhost", Vec::new());
+        let err = RuntimeSettings::read(&config, |name| (name == "PTB_BUILD_MODE").then(|| "remote".to_string()))
+            .unwrap_err();
+        assert!(err.to_string().contains("PTB_BUILD_MODE"), "{err}");
+    }
+
+    #[test]
+    fn test_unparseable_knobs_are_rejected() {
+        let config = Config::for_tests("http://localhost", Vec::new());
+        for (name, value) in [("MIN_GAS_BALANCE_MIST", "1e8"), ("USE_WEBSOCKET", "yes"), ("DRY_RUN_BATCH_SIZE", "-1")] {
+            let err = RuntimeSettings::read(&config, |n| (n == name).then(|| value.to_string())).unwrap_err();
+            assert!(err.to_string().contains(name), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_empty_knobs_take_the_default() {
+        assert_eq!(read(&[("MIN_GAS_BALANCE_MIST", "")]).min_gas_balance_mist, 100_000_000);
+    }
+}
@@ -0,0 +1,179 @@
+//! Startup configuration checks, shared by the bot and `arbctl doctor`.
+
+use arb_collector::DexPackage;
+use arb_types::pool::Dex;
+use arb_types::Config;
+use tracing::{error, info, warn};
+
+/// How serious a configuration finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// Degrades some strategies; the bot still runs.
+    Warning,
+    /// The bot will fail or trade nothing until this is fixed.
+    Error,
+}
+
+/// One problem found in the configuration, with what to do about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub level: Level,
+    pub message: String,
+}
+
+impl Finding {
+    fn warning(message: impl Into<String>) -> Self {
+        Self { level: Level::Warning, message: message.into() }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self { level: Level::Error, message: message.into() }
+    }
+}
+
+/// Check critical configuration without touching the network.
+pub fn check_config(config: &Config) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    // 1. Package ID must be set (not placeholder)
+    if config.package_id == "0x0" || config.package_id == "0x..." || config.package_id.is_empty() {
+        findings.push(Finding::error(format!(
+            "PACKAGE_ID is not set ({}) — deploy the Move package first with `sui client publish`",
+            config.package_id
+        )));
+    }
+
+    // 2. AdminCap and PauseFlag
+    if config.admin_cap_id == "0x..." || config.admin_cap_id.is_empty() {
+        findings.push(Finding::error("ADMIN_CAP_ID is not set — required for admin operations"));
+    }
+    if config.pause_flag_id == "0x..." || config.pause_flag_id.is_empty() {
+        findings.push(Finding::error("PAUSE_FLAG_ID is not set — required for all strategy calls"));
+    }
+
+    // 3. Monitored pools
+    if config.monitored_pools.is_empty() {
+        findings.push(Finding::error(
+            "MONITORED_POOLS is empty — no pools to monitor. Add pool configs to start trading.",
+        ));
+    } else {
+        // Validate pool config format
+        for (i, pool) in config.monitored_pools.iter().enumerate() {
+            if let Err(e) = pool.dex.parse::<Dex>() {
+                findings.push(Finding::warning(format!(
+                    "Pool {}: {} — it will not be parsed",
+                    i, e
+                )));
+            }
+            if !pool.pool_id.starts_with("0x") {
+                findings.push(Finding::warning(format!(
+                    "Pool {}: ID {} doesn't start with 0x",
+                    i, pool.pool_id
+                )));
+            }
+        }
+
+        // Check for DeepBook pools without DEEP fee coin
+        let has_deepbook = config
+            .monitored_pools
+            .iter()
+            .any(|p| p.dex.parse::<Dex>().is_ok_and(|d| d == Dex::DeepBook));
+        if has_deepbook
+            && (config.deep_fee_coin_id.is_empty()
+                || config.deep_fee_coin_id == "0x..."
+                || config.deep_fee_coin_id == "0x0")
+        {
+            findings.push(Finding::warning(
+                "DeepBook pools configured but DEEP_FEE_COIN_ID is not set — \
+                 DeepBook strategies will abort. Get a Coin<DEEP> object: \
+                 `sui client gas --coin-type 0xdeeb...::deep::DEEP`",
+            ));
+        }
+    }
+
+    // 4. DEX shared objects
+    if config.cetus_global_config.is_empty() {
+        findings.push(Finding::warning("CETUS_GLOBAL_CONFIG not set — Cetus strategies will fail"));
+    }
+    if config.turbos_versioned.is_empty() {
+        findings.push(Finding::warning("TURBOS_VERSIONED not set — Turbos strategies will fail"));
+    }
+
+    // 5. Strategy params sanity
+    if config.min_profit_mist == 0 {
+        findings.push(Finding::warning(
+            "MIN_PROFIT_MIST is 0 — bot will attempt tiny unprofitable trades",
+        ));
+    }
+    if config.max_gas_budget < 10_000_000 {
+        findings.push(Finding::warning(format!(
+            "MAX_GAS_BUDGET is very low ({}) — transactions may run out of gas",
+            config.max_gas_budget
+        )));
+    }
+
+    findings
+}
+
+/// Validate critical configuration at startup.
+/// Warns on non-fatal issues, errors on blockers. Returns the number of errors.
+pub fn validate_startup(config: &Config) -> u32 {
+    let findings = check_config(config);
+    let errors = findings.iter().filter(|f| f.level == Level::Error).count() as u32;
+    let warnings = findings.len() as u32 - errors;
+
+    for f in &findings {
+        match f.level {
+            Level::Error => error!("{}", f.message),
+            Level::Warning => warn!("{}", f.message),
+        }
+    }
+
+    // Summary
+    if errors > 0 {
+        error!(
+            errors = %errors,
+            warnings = %warnings,
+            "⛔ Startup validation found {} critical error(s) — bot will likely fail",
+            errors
+        );
+    } else if warnings > 0 {
+        warn!(
+            warnings = %warnings,
+            "⚠️  Startup validation passed with {} warning(s)",
+            warnings
+        );
+    } else {
+        info!("✅ Startup validation passed — all checks OK");
+    }
+
+    errors
+}
+
+/// Build the list of DEX package IDs to subscribe to from config.
+pub fn build_dex_packages(config: &Config) -> Vec<DexPackage> {
+    let mut packages = Vec::new();
+
+    // Add package IDs from environment if set
+    let dex_names = ["CETUS", "TURBOS", "DEEPBOOK", "AFTERMATH", "FLOWX", "KRIYA", "BLUEFIN"];
+
+    for name in &dex_names {
+        let env_key = format!("{}_PACKAGE_ID", name);
+        if let Ok(pkg_id) = std::env::var(&env_key) {
+            if !pkg_id.is_empty() {
+                packages.push(DexPackage {
+                    package_id: pkg_id,
+                    dex_name: name.to_lowercase(),
+                });
+            }
+        }
+    }
+
+    // Always include the arb package itself for ArbExecuted events
+    packages.push(DexPackage {
+        package_id: config.package_id.clone(),
+        dex_name: "arbmove".to_string(),
+    });
+
+    packages
+}
@@ -0,0 +1,173 @@
+//! HTTP `/status` endpoint for supervisor and Kubernetes health checks.
+//!
+//! Off unless `STATUS_PORT` is set. `GET /status` returns the bot's state as
+//! JSON — collector heartbeat age, pool cache, circuit breaker, gas balance,
+//! last trade, adaptive components still in cold start, and session totals
+//! — with 200 while healthy and 503 once the
+//! collectors have gone quiet or the breaker has tripped, so a liveness
+//! probe can restart the process. Same minimal responder as the metrics
+//! endpoint.
+
+use anyhow::{Context, Result};
+use arb_types::clock::unix_now_ms;
+use arb_types::Metrics;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tracing::debug;
+
+use crate::metrics_server::{read_request, write_response};
+
+/// What the endpoint reports on. Cheap to clone; clones share the state.
+#[derive(Debug, Clone)]
+pub struct StatusState {
+    metrics: Arc<Metrics>,
+    collector_heartbeat: Arc<AtomicU64>,
+    /// Unhealthy once the collector heartbeat is older than this
+    max_heartbeat_age_ms: u64,
+}
+
+impl StatusState {
+    pub fn new(metrics: Arc<Metrics>, collector_heartbeat: Arc<AtomicU64>, max_heartbeat_age_ms: u64) -> Self {
+        Self { metrics, collector_heartbeat, max_heartbeat_age_ms }
+    }
+
+    /// Whether the bot is healthy at `now_ms`, and the status document.
+    pub fn report(&self, now_ms: u64) -> (bool, Value) {
+        let heartbeat_age_ms = now_ms.saturating_sub(self.collector_heartbeat.load(Ordering::Relaxed));
+        let collector_alive = heartbeat_age_ms <= self.max_heartbeat_age_ms;
+        let tripped = self.metrics.breaker_tripped();
+        let healthy = collector_alive && !tripped;
+
+        let mut problems = Vec::new();
+        if !collector_alive {
+            problems.push(format!("no collector heartbeat for {}ms", heartbeat_age_ms));
+        }
+        if tripped {
+            problems.push("circuit breaker tripped".to_string());
+        }
+
+        let snap = self.metrics.snapshot();
+        let body = json!({
+            "healthy": healthy,
+            "problems": problems,
+            "collector_heartbeat_age_ms": heartbeat_age_ms,
+            "pool_cache_size": snap.pool_cache_size,
+            "pool_staleness_ms": snap.pool_staleness_ms,
+            "circuit_breaker": snap.circuit_breaker,
+            "gas_balance_mist": snap.gas_balance_mist,
+            "fee_type_mismatches": snap.fee_type_mismatches,
+            "last_trade": snap.last_trade,
+            // Still on static defaults; not a health problem
+            "cold_start": snap.cold_components,
+            "session": {
+                "cycles": snap.cycles,
+                "trades": snap.trades,
+                "trades_succeeded": snap.trades_succeeded,
+                "trades_failed": snap.trades_failed,
+                "profit_mist": snap.profit_mist,
+                "gas_mist": snap.gas_mist,
+                "net_profit_mist": snap.net_profit_mist,
+                "breaker_trips": snap.breaker_trips,
+            },
+        });
+        (healthy, body)
+    }
+}
+
+/// Bind the endpoint, failing startup if the address is taken.
+pub async fn bind(addr: &str) -> Result<TcpListener> {
+    TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind status endpoint on {}", addr))
+}
+
+/// Answer health checks until the process exits.
+pub async fn serve(listener: TcpListener, state: StatusState) {
+    loop {
+        let Ok((socket, peer)) = listener.accept().await else { continue };
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = respond(socket, &state).await {
+                debug!(peer = %peer, error = %e, "Status request failed");
+            }
+        });
+    }
+}
+
+async fn respond(mut socket: TcpStream, state: &StatusState) -> std::io::Result<()> {
+    let (method, path) = read_request(&mut socket).await?;
+    if method != "GET" || path != "/status" {
+        return write_response(socket, "404 Not Found", "text/plain; charset=utf-8", "not found\n").await;
+    }
+    let (healthy, body) = state.report(unix_now_ms());
+    let status = if healthy { "200 OK" } else { "503 Service Unavailable" };
+    write_response(socket, status, "application/json", &body.to_string()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arb_types::{BreakerStatus, LastTrade};
+
+    async fn spawn(state: StatusState) -> String {
+        let listener = bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, state));
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_status_codes_follow_heartbeat_and_breaker() {
+        let metrics = Arc::new(Metrics::new());
+        let heartbeat = Arc::new(AtomicU64::new(unix_now_ms()));
+        metrics.record_trade(true, 1_000, 100);
+        metrics.set_gas_balance(5_000_000_000);
+        metrics.set_cold_components(&["gas_estimate.two_hop", "gas_estimate.tri_hop"]);
+        metrics.set_last_trade(LastTrade {
+            digest: Some("Dg1".to_string()),
+            strategy: "arb_cetus_to_turbos".to_string(),
+            outcome: "success".to_string(),
+            profit_mist: 1_000,
+            gas_mist: 100,
+            at_ms: 0,
+        });
+        let url = spawn(StatusState::new(metrics.clone(), heartbeat.clone(), 30_000)).await;
+        let client = reqwest::Client::new();
+
+        // Healthy
+        let resp = client.get(format!("{}/status", url)).send().await.unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.headers()["content-type"], "application/json");
+        let body: Value = resp.json().await.unwrap();
+        assert_eq!(body["healthy"], true);
+        assert_eq!(body["gas_balance_mist"], 5_000_000_000u64);
+        assert_eq!(body["last_trade"]["digest"], "Dg1");
+        assert_eq!(body["session"]["net_profit_mist"], 900);
+        assert_eq!(body["circuit_breaker"]["tripped"], false);
+        assert_eq!(body["cold_start"], json!(["gas_estimate.two_hop", "gas_estimate.tri_hop"]));
+
+        // Collectors silent past the limit
+        heartbeat.store(unix_now_ms() - 60_000, Ordering::Relaxed);
+        let resp = client.get(format!("{}/status", url)).send().await.unwrap();
+        assert_eq!(resp.status(), 503);
+        let body: Value = resp.json().await.unwrap();
+        assert!(body["problems"][0].as_str().unwrap().starts_with("no collector heartbeat"));
+
+        // Fresh heartbeat, but the breaker has tripped
+        heartbeat.store(unix_now_ms(), Ordering::Relaxed);
+        metrics.set_breaker(BreakerStatus {
+            tripped: true,
+            trip_reason: Some("3 consecutive failures".to_string()),
+            ..Default::default()
+        });
+        let resp = client.get(format!("{}/status", url)).send().await.unwrap();
+        assert_eq!(resp.status(), 503);
+        let body: Value = resp.json().await.unwrap();
+        assert_eq!(body["circuit_breaker"]["trip_reason"], "3 consecutive failures");
+
+        let resp = client.get(format!("{}/metrics", url)).send().await.unwrap();
+        assert_eq!(resp.status(), 404);
+    }
+}
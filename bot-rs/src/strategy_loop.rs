@@ -0,0 +1,1135 @@
+//! The strategy loop: scan the pool cache each cycle, then build, sign,
+//! and submit the best opportunity behind the circuit breaker, gas, and
+//! collector-liveness guards.
+//!
+//! Everything the loop touches lives in [`StrategyState`], built by the
+//! `arb-bot` binary. Time comes from the state's [`Clock`] and chain
+//! access from the components' RPC clients, so the integration tests run
+//! the same loop against a mock RPC on a manual clock.
+
+use crate::alerts::{AlertRouter, Severity};
+use crate::profiling::{CycleAllocations, Profiler};
+use crate::reconcile::Reconciler;
+use crate::trade_log::TradeLog;
+use anyhow::Result;
+use arb_collector::flow::{FLOW_WINDOW_LONG_MS, FLOW_WINDOW_SHORT_MS};
+use arb_collector::{PollBackoff, PollPacing, PoolCache};
+use arb_executor::ptb_builder::{BuildError, PtbBuilder};
+use arb_executor::{
+    BuiltTx, CoinMerger, GasCharge, GasCoinPool, GasMonitor, Inventory, Signer, SubmitError, SubmitResult, Submitter,
+    TxMismatch,
+};
+use arb_strategy::conflicts::select_conflict_free;
+use arb_strategy::outcomes;
+use arb_strategy::{
+    build_opportunity_simulator, BreakerStore, CircuitBreaker, ColdStartMonitor, DryRunner, FailureTracker, GasEstimator,
+    OpportunityTracker, OutcomeStats, Scanner, StrategyQuarantine, StrategyWeights, TradeOutcome, TradeStats,
+    MIN_SIM_AMOUNT,
+};
+use arb_types::clock::Clock;
+use arb_types::log_throttle::DEFAULT_INTERVAL;
+use arb_types::opportunity::ArbOpportunity;
+use arb_types::pool::{PoolState, TradeSizeLimits};
+use arb_types::{clear_throttled, warn_throttled, BreakerStatus, BuildInfo, CycleClock, LastTrade, Metrics};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::field::Empty;
+use tracing::{debug, error, info, info_span, warn, Instrument};
+
+/// Maximum allowed staleness (ms) for pool data before strategy loop skips a cycle.
+pub const MAX_POOL_STALENESS_MS: u64 = 10_000; // 10 seconds
+
+/// State owned by the strategy loop.
+///
+/// Held behind a mutex that the running loop locks for its whole lifetime. If
+/// the watchdog aborts a hung loop the guard is dropped with it, so the
+/// respawned loop resumes with the same breaker, gas, and alert state.
+pub struct StrategyState {
+    pub cache: PoolCache,
+    pub collector_heartbeat: Arc<AtomicU64>,
+    pub poll_backoff: PollBackoff,
+    pub poll_pacing: PollPacing,
+    pub scanner: Scanner,
+    pub dry_runner: DryRunner,
+    pub submitter: Submitter,
+    pub ptb_builder: PtbBuilder,
+    pub signer: Signer,
+    pub gas_monitor: GasMonitor,
+    pub coin_merger: CoinMerger,
+    pub gas_coins: Option<GasCoinPool>,
+    pub circuit_breaker: CircuitBreaker,
+    pub breaker_store: Option<BreakerStore>,
+    pub build_failures: FailureTracker,
+    pub quarantine: StrategyQuarantine,
+    pub opportunity_tracker: OpportunityTracker,
+    pub gas_estimator: GasEstimator,
+    pub cold_start: ColdStartMonitor,
+    pub outcome_stats: OutcomeStats,
+    pub trade_stats: TradeStats,
+    pub trade_log: Option<TradeLog>,
+    pub reconciler: Option<Reconciler>,
+    pub strategy_weights: Arc<RwLock<StrategyWeights>>,
+    pub inventory: Option<Inventory>,
+    pub alerts: AlertRouter,
+    pub metrics: Arc<Metrics>,
+    pub profiler: Arc<Profiler>,
+    /// Source of each cycle's "now"
+    pub clock: Clock,
+    pub poll_interval: Duration,
+    pub enable_quad_hop: bool,
+    pub dry_run_enabled: bool,
+    pub paper_trading: bool,
+    pub dry_run_batch_size: usize,
+    pub max_opportunities: usize,
+    pub build: BuildInfo,
+    /// Latest config hash; changes when a hot reload does.
+    pub config_hash: watch::Receiver<String>,
+    pub total_trades: u64,
+    pub total_profit: i64,
+    pub total_gas: u64,
+}
+
+/// Run the strategy loop, bumping `heartbeat` (in the state's clock) at
+/// the start of every cycle.
+pub async fn run_strategy_loop(
+    state: Arc<tokio::sync::Mutex<StrategyState>>,
+    heartbeat: Arc<AtomicU64>,
+    restart_reason: Option<String>,
+) {
+    let mut guard = state.lock().await;
+    let StrategyState {
+        cache,
+        collector_heartbeat,
+        poll_backoff,
+        poll_pacing,
+        scanner,
+        dry_runner,
+        submitter,
+        ptb_builder,
+        signer,
+        gas_monitor,
+        coin_merger,
+        gas_coins,
+        circuit_breaker,
+        breaker_store,
+        build_failures,
+        quarantine,
+        opportunity_tracker,
+        gas_estimator,
+        cold_start,
+        outcome_stats,
+        trade_stats,
+        trade_log,
+        reconciler,
+        strategy_weights,
+        inventory,
+        alerts,
+        metrics,
+        profiler,
+        clock: time,
+        poll_interval,
+        enable_quad_hop,
+        dry_run_enabled,
+        paper_trading,
+        dry_run_batch_size,
+        max_opportunities,
+        build,
+        config_hash,
+        total_trades,
+        total_profit,
+        total_gas,
+    } = &mut *guard;
+
+    if let Some(reason) = restart_reason {
+        alerts.raise(Severity::Critical, "strategy.restarted", &reason, time.now_ms());
+    }
+
+    let mut interval = tokio::time::interval(*poll_interval);
+    heartbeat.store(time.now_ms(), Ordering::Relaxed);
+    let sender = signer.address();
+
+    info!("Strategy loop started ({}ms tick)", poll_interval.as_millis());
+
+    // When the collectors went silent: alerted once, not every cycle
+    let mut collector_dead_since: Option<u64> = None;
+
+    loop {
+        // Run as soon as a pool changes, and at least every tick
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = cache.changed() => {}
+        }
+        // One "now" for every guard in this cycle. Post-submit bookkeeping
+        // takes one fresh reading once the submission settles; elapsed-time
+        // checks still read the clock.
+        let clock = time.cycle();
+        // Logged when the body exits, however it does
+        let _allocations = CycleAllocations::start(profiler, clock.now_ms());
+        heartbeat.store(clock.now_ms(), Ordering::Relaxed);
+        metrics.record_cycle();
+        alerts.tick(clock.now_ms());
+        if config_hash.has_changed().unwrap_or(false) {
+            build.config_hash = config_hash.borrow_and_update().clone();
+            alerts.set_build_tag(&build.to_string());
+        }
+
+        // Periodic per-strategy P&L roll-up
+        if trade_stats.is_due(clock.now_ms()) {
+            trade_stats.log_summary(clock.now_ms());
+            log_quarantine_stats(quarantine, clock.now_ms());
+        }
+
+        // Periodic wallet-balance reconciliation (between cycles: nothing in flight)
+        if let Some(reconciler) = reconciler.as_mut().filter(|r| r.is_due(clock.now_ms())) {
+            match gas_monitor.fetch_balance().await {
+                Ok(balance) => {
+                    clear_throttled!("main.reconcile_fetch");
+                    if let Some(window) = reconciler.observe(balance, clock.now_ms()) {
+                        if reconciler.report(&window) {
+                            alerts.raise(
+                                Severity::Warning,
+                                "pnl.discrepancy",
+                                &format!(
+                                    "wallet moved {} MIST, trade log says {} MIST (off by {})",
+                                    window.onchain_delta(),
+                                    window.accounted_mist,
+                                    window.discrepancy()
+                                ),
+                                clock.now_ms(),
+                            );
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn_throttled!(
+                        "main.reconcile_fetch",
+                        DEFAULT_INTERVAL,
+                        error = %e,
+                        "P&L reconciliation balance fetch failed"
+                    );
+                    reconciler.retry_later(clock.now_ms());
+                }
+            }
+        }
+
+        // 0a. Circuit breaker check (persisting last cycle's trades and any
+        // cooldown reset)
+        let trading_allowed = circuit_breaker.is_trading_allowed(clock.now_ms());
+        publish_breaker(metrics, circuit_breaker);
+        save_breaker(breaker_store, circuit_breaker);
+        if !trading_allowed {
+            continue;
+        }
+
+        // 0b. Gas balance check
+        let gas_check = gas_monitor.check_balance(clock.now_ms()).await;
+        if let Some(balance) = gas_monitor.balance() {
+            metrics.set_gas_balance(balance);
+        }
+        if let Err(e) = gas_check {
+            warn_throttled!("main.gas_low", DEFAULT_INTERVAL, error = %e, "Gas balance insufficient — skipping cycle");
+            alerts.raise(Severity::Warning, "gas.low", &e.to_string(), clock.now_ms());
+            continue;
+        }
+        clear_throttled!("main.gas_low");
+
+        // 0c. Periodic coin dust merge, else topping up the gas coin pool
+        // (skipped when paper trading: nothing is ever submitted)
+        if !*paper_trading {
+            let reshape = match coin_merger.maybe_merge().await {
+                Ok(Some(merge_tx)) => Some(merge_tx),
+                _ => match gas_coins.as_ref() {
+                    Some(pool) => pool.maybe_split().await.unwrap_or_else(|e| {
+                        warn!(error = %format!("{e:#}"), "Failed to build gas coin split");
+                        None
+                    }),
+                    None => None,
+                },
+            };
+            if let Some(merge_tx) = reshape {
+                match signer.sign_transaction(&merge_tx.tx_bytes, &merge_tx.expected) {
+                    Ok(sig) => {
+                        let submitted = submitter.submit(&merge_tx.tx_bytes, &sig).await;
+                        ptb_builder.invalidate_owned_objects();
+                        if let Some(pool) = gas_coins.as_ref() {
+                            if let Err(e) = pool.refresh().await {
+                                warn!(error = %format!("{e:#}"), "Gas coin refresh failed");
+                            }
+                        }
+                        match submitted {
+                            Ok(result) => {
+                                if result.success {
+                                    info!(
+                                        digest = %result.digest,
+                                        gas = %result.gas_cost_mist,
+                                        "Coin merge successful"
+                                    );
+                                    gas_monitor.deduct_gas(result.gas_paid_by(&sender).max(0) as u64);
+                                    alerts.raise(
+                                        Severity::Info,
+                                        "coin_merge.ok",
+                                        &format!("merged gas coins ({})", result.digest),
+                                        clock.now_ms(),
+                                    );
+                                } else {
+                                    warn!(error = ?result.error_message, "Coin merge failed on-chain");
+                                    alerts.raise(
+                                        Severity::Warning,
+                                        "coin_merge.failed",
+                                        &format!("{:?}", result.error_message),
+                                        clock.now_ms(),
+                                    );
+                                }
+                            }
+                            Err(e) => warn!(error = %e, "Coin merge submission failed"),
+                        }
+                    }
+                    Err(e) => report_sign_failure(alerts, &e, "coin merge", clock.now_ms()),
+                }
+            }
+        }
+
+        // 0d. Check collector liveness via heartbeat
+        let hb_age = clock.now_ms().saturating_sub(
+            collector_heartbeat.load(Ordering::Relaxed),
+        );
+        metrics.set_collector_heartbeat_age(hb_age);
+        metrics.set_poll_backoff(poll_backoff.multiplier(), poll_backoff.rate_limited_total());
+        metrics.set_poll_interval(poll_pacing.current_interval_ms());
+        if hb_age > MAX_POOL_STALENESS_MS * 3 && poll_backoff.is_backing_off() {
+            // Alive, but the node is throttling it: not a dead collector
+            warn_throttled!(
+                "main.collector_rate_limited",
+                DEFAULT_INTERVAL,
+                stale_ms = %hb_age,
+                backoff = %poll_backoff.multiplier(),
+                "Collector backing off from RPC rate limits — skipping cycle"
+            );
+            alerts.raise(
+                Severity::Warning,
+                "collector.rate_limited",
+                &format!("RPC rate-limited, polling {}x slower", poll_backoff.multiplier()),
+                clock.now_ms(),
+            );
+            continue;
+        }
+        clear_throttled!("main.collector_rate_limited");
+        if hb_age > MAX_POOL_STALENESS_MS * 3 {
+            warn_throttled!(
+                "main.collector_dead",
+                DEFAULT_INTERVAL,
+                stale_ms = %hb_age,
+                "All collectors appear dead — skipping cycle"
+            );
+            if collector_dead_since.is_none() {
+                collector_dead_since = Some(clock.now_ms());
+                alerts.raise(
+                    Severity::Critical,
+                    "collector.dead",
+                    &format!("no collector heartbeat for {}s", hb_age / 1000),
+                    clock.now_ms(),
+                );
+            }
+            continue;
+        }
+        clear_throttled!("main.collector_dead");
+        if let Some(since) = collector_dead_since.take() {
+            alerts.raise(
+                Severity::Warning,
+                "collector.recovered",
+                &format!("collector heartbeat back after {}s", clock.now_ms().saturating_sub(since) / 1000),
+                clock.now_ms(),
+            );
+        }
+
+        // 1. Read pool states from cache
+        let pools = cache.snapshot();
+        metrics.set_pools(cache.len(), &pools, clock.now_ms());
+        if pools.is_empty() {
+            continue;
+        }
+
+        // 1b. Staleness guard: skip if ALL pools are too old
+        let now = clock.now_ms();
+        let fresh_count = pools
+            .iter()
+            .filter(|p| p.staleness_ms(now) <= MAX_POOL_STALENESS_MS)
+            .count();
+        if fresh_count == 0 {
+            warn_throttled!("main.pools_stale", DEFAULT_INTERVAL, "All pool data is stale — skipping cycle");
+            alerts.raise(
+                Severity::Warning,
+                "pools.stale",
+                &format!("all {} pools older than {}ms", pools.len(), MAX_POOL_STALENESS_MS),
+                now,
+            );
+            continue;
+        }
+        clear_throttled!("main.pools_stale");
+
+        // 2. Scan for opportunities (two-hop + tri-hop, and quad-hop if enabled)
+        let scan_span = info_span!("scan", pools = %pools.len(), found = Empty);
+        let mut opportunities = scan_span.in_scope(|| {
+            let mut opps = scanner.scan_two_hop(&pools, clock);
+            opps.extend(scanner.scan_tri_hop(&pools, clock));
+            if *enable_quad_hop {
+                opps.extend(scanner.scan_quad_hop(&pools, clock));
+            }
+            opps
+        });
+
+        // Learned gas estimates; a no-op while in cold start
+        for c in gas_estimator.components() {
+            cold_start.observe(c);
+        }
+        metrics.set_cold_components(&cold_start.cold_components());
+        for opp in &mut opportunities {
+            gas_estimator.apply(opp);
+        }
+        // Size every candidate on its own pools so two-, tri- and quad-hops
+        // compare, dropping unprofitable and overlapping ones
+        let mut opportunities = scan_span.in_scope(|| scanner.rank(&pools, opportunities));
+        scan_span.record("found", opportunities.len());
+
+        // Pools that show up in opportunities rank higher for WS subscriptions
+        for opp in &opportunities {
+            cache.activity().record_participation(&opp.pool_ids, now);
+        }
+
+        if opportunities.is_empty() {
+            continue;
+        }
+
+        // Re-sort combined opportunities by expected value, weighing each
+        // route's history of full fills, partial fills, and revert costs,
+        // scaled by the operator's strategy weight. Equal scores go to
+        // routes through pools under one-sided swap flow.
+        let weights = strategy_weights.read().unwrap_or_else(|e| e.into_inner()).clone();
+        opportunities.sort_by_cached_key(|o| {
+            let score = weights.score(o, outcome_stats.ev(o));
+            std::cmp::Reverse((score, cache.flow().pressure(&o.pool_ids, now)))
+        });
+
+        // Drop strategies switched off by a zero weight, suspended for
+        // repeated PTB build failures, or quarantined for failing/losing,
+        // and opportunities attempted too recently
+        opportunity_tracker.prune(now);
+        opportunities.retain(|o| {
+            if weights.is_disabled(o.strategy) {
+                debug!(strategy = %o.strategy, "Strategy weight is 0 — skipping opportunity");
+                return false;
+            }
+            let suspended = build_failures.is_suspended(o.strategy, now);
+            if suspended {
+                debug!(strategy = %o.strategy, "Strategy suspended — skipping opportunity");
+                return false;
+            }
+            if quarantine.is_quarantined(o.strategy, now) {
+                debug!(strategy = %o.strategy, "Strategy quarantined — skipping opportunity");
+                return false;
+            }
+            let cooling_down = opportunity_tracker.is_cooling_down(o, now);
+            if cooling_down {
+                debug!(strategy = %o.strategy, pools = ?o.pool_ids, "Opportunity recently attempted — skipping");
+            }
+            !cooling_down
+        });
+
+        // Backpressure: a chaotic market can flood the scanner; the long
+        // tail of the ranking is marginal, so only the top-K move on
+        if *max_opportunities > 0 && opportunities.len() > *max_opportunities {
+            debug!(
+                found = %opportunities.len(),
+                kept = %max_opportunities,
+                "Opportunity cap reached — dropping the tail"
+            );
+            opportunities.truncate(*max_opportunities);
+        }
+
+        // 3. Prepare the best candidates. With dry-run on, up to
+        // `dry_run_batch_size` non-conflicting opportunities are revalidated,
+        // sized, and built, then dry-run together in one RPC round trip.
+        // Each build leases its own gas coin, so never prepare more
+        // candidates than there are coins free to pay for them.
+        let mut batch_size = if *dry_run_enabled { (*dry_run_batch_size).max(1) } else { 1 };
+        if let Some(pool) = gas_coins.as_ref() {
+            batch_size = batch_size.min(pool.free_count());
+            if batch_size == 0 {
+                debug!("Every gas coin is in flight — skipping cycle");
+                continue;
+            }
+        }
+        let mut prepared = Vec::with_capacity(batch_size);
+        for opp in select_conflict_free(opportunities, &pools, batch_size) {
+            let fraction = ptb_builder.min_profit_fraction();
+            let weight = weights.weight(opp.strategy);
+            let Some((opp, span)) =
+                prepare_candidate(opp, scanner, cache, build, fraction, weight, clock, &scan_span)
+            else {
+                continue;
+            };
+            let opp = fund_from_inventory(opp, inventory.as_ref());
+            let build_span = info_span!(parent: &span, "build");
+            match build_ptb(ptb_builder, build_failures, alerts, &opp, clock, build_span).await {
+                Ok(tx) => {
+                    quarantine.record_attempt(opp.strategy);
+                    prepared.push(Prepared { opp, tx, span });
+                }
+                // Says nothing about the opportunity: it may go next cycle
+                Err(e) if e.is_transient() => {}
+                Err(_) => opportunity_tracker.record_failure(&opp, clock.now_ms()),
+            }
+        }
+        if prepared.is_empty() {
+            continue;
+        }
+
+        // 4. Dry-run validation: keep the most profitable candidate that passes
+        let (best, tx, opp_span) = if *dry_run_enabled {
+            let dry_run_spans: Vec<_> = prepared
+                .iter()
+                .map(|c| info_span!(parent: &c.span, "dry_run", batched = Empty, elapsed_ms = Empty))
+                .collect();
+            let mut opps: Vec<ArbOpportunity> = prepared.iter().map(|c| c.opp.clone()).collect();
+            let txs: Vec<String> = prepared.iter().map(|c| c.tx.tx_bytes.clone()).collect();
+            let validation = dry_runner.validate_batch(&mut opps, &txs).await;
+            for span in &dry_run_spans {
+                span.record("batched", validation.batched);
+                span.record("elapsed_ms", validation.elapsed_ms);
+            }
+            drop(dry_run_spans);
+
+            let mut passed: Option<(ArbOpportunity, tracing::Span)> = None;
+            for ((opp, candidate), outcome) in opps.into_iter().zip(prepared).zip(validation.outcomes) {
+                match outcome {
+                    Ok(true) => {
+                        gas_estimator.record(opp.pool_ids.len(), opp.estimated_gas);
+                        info!(
+                            strategy = %opp.strategy,
+                            gas = %opp.estimated_gas,
+                            net_profit = %opp.net_profit,
+                            "Dry-run passed"
+                        );
+                        if passed.as_ref().is_none_or(|(p, _)| opp.net_profit > p.net_profit) {
+                            passed = Some((opp, candidate.span));
+                        }
+                    }
+                    Ok(false) => {
+                        warn!(strategy = %opp.strategy, "Opportunity no longer profitable after dry-run");
+                        candidate.span.record("outcome", "dry_run_failed");
+                        quarantine.record_dry_run_failure(opp.strategy, clock.now_ms());
+                        opportunity_tracker.record_failure(&opp, clock.now_ms());
+                    }
+                    Err(e) => {
+                        warn!(strategy = %opp.strategy, error = %e, "Dry-run failed");
+                        candidate.span.record("outcome", "dry_run_failed");
+                        opportunity_tracker.record_failure(&opp, clock.now_ms());
+                    }
+                }
+            }
+
+            // One failure per cycle, however many candidates were dry-run
+            let Some((best, opp_span)) = passed else {
+                record_failure(circuit_breaker, breaker_store, alerts, metrics, 0, clock.now_ms());
+                continue;
+            };
+
+            // 4b. Rebuild PTB with tighter min_profit from dry-run actuals
+            let build_span = info_span!(parent: &opp_span, "build", rebuild = true);
+            let tx_final = match build_ptb(ptb_builder, build_failures, alerts, &best, clock, build_span).await {
+                Ok(tx) => tx,
+                Err(e) => {
+                    if !e.is_transient() {
+                        opportunity_tracker.record_failure(&best, clock.now_ms());
+                    }
+                    continue;
+                }
+            };
+            (best, tx_final, opp_span)
+        } else {
+            let Prepared { opp, tx, span } = prepared.swap_remove(0);
+            (opp, tx, span)
+        };
+
+        // 5. Sign and submit. Paper trading dry-runs the final PTB instead
+        // and books the result exactly as if it had landed.
+        let submitted = if *paper_trading {
+            paper_fill(dry_runner, &tx.tx_bytes)
+                .instrument(info_span!(parent: &opp_span, "paper_fill"))
+                .await
+        } else {
+            let signature = match info_span!(parent: &opp_span, "sign")
+                .in_scope(|| signer.sign_transaction(&tx.tx_bytes, &tx.expected))
+            {
+                Ok(sig) => sig,
+                Err(e) => {
+                    report_sign_failure(alerts, &e, &best.strategy.to_string(), clock.now_ms());
+                    opp_span.record("outcome", "sign_refused");
+                    opportunity_tracker.record_failure(&best, clock.now_ms());
+                    continue;
+                }
+            };
+
+            let submitted = submitter
+                .submit(&tx.tx_bytes, &signature)
+                .instrument(info_span!(parent: &opp_span, "submit"))
+                .await;
+            // Whatever happened, the objects it used may have new versions
+            ptb_builder.invalidate_owned_objects();
+            match settle_submission(submitter, submitted).instrument(info_span!(parent: &opp_span, "confirm")).await {
+                Some(submitted) => submitted,
+                None => {
+                    // May or may not have landed: neither a success nor a
+                    // failure for the breaker. Reconciliation catches it.
+                    // Back off for the longer cooldown rather than risk
+                    // trading the same spread twice.
+                    opp_span.record("outcome", "unconfirmed");
+                    let settled_ms = time.now_ms();
+                    opportunity_tracker.record_failure(&best, settled_ms);
+                    alerts.raise(
+                        Severity::Warning,
+                        "trade.unconfirmed",
+                        &format!("{} submission outcome unknown", best.strategy),
+                        settled_ms,
+                    );
+                    continue;
+                }
+            }
+        };
+        opp_span.record("outcome", submit_outcome(&submitted));
+        // One post-submit timestamp for everything booked below
+        let settled_ms = time.now_ms();
+        match submitted {
+            Ok(result) => {
+                *total_trades += 1;
+                *total_gas += result.gas_cost_mist;
+                let profit = if result.success { result.profit_mist.unwrap_or(0) } else { 0 };
+                metrics.record_trade(result.success, profit, result.gas_cost_mist);
+                metrics.set_last_trade(LastTrade {
+                    digest: Some(result.digest.clone()),
+                    strategy: best.strategy.to_string(),
+                    outcome: if result.success { "success" } else { "reverted" }.to_string(),
+                    profit_mist: profit,
+                    gas_mist: result.gas_cost_mist,
+                    at_ms: settled_ms,
+                });
+                // Only gas we paid: a sponsor's gas coin is not our balance
+                let gas_paid = result.gas_paid_by(&sender);
+                if !*paper_trading {
+                    gas_monitor.deduct_gas(gas_paid.max(0) as u64);
+                }
+                log_trade_result(&result, build, *paper_trading, total_profit, *total_trades, *total_gas);
+                if let Some(log) = trade_log.as_mut() {
+                    log.record(&best, Ok(&result), build, settled_ms);
+                }
+                if let Some(reconciler) = reconciler.as_mut() {
+                    reconciler.record(profit as i64 - gas_paid);
+                }
+                let outcome = if result.success {
+                    TradeOutcome::Success {
+                        profit_mist: result.profit_mist.unwrap_or(0),
+                        gas_mist: result.gas_cost_mist,
+                    }
+                } else {
+                    TradeOutcome::Reverted { gas_mist: result.gas_cost_mist }
+                };
+                trade_stats.record(best.strategy, best.net_profit, outcome);
+                let kind = outcomes::classify(
+                    &best,
+                    result.success,
+                    result.profit_mist,
+                    result.gas_cost_mist,
+                    result.error_message.as_deref(),
+                );
+                debug!(strategy = %best.strategy, outcome = ?kind, "Trade outcome classified");
+                outcome_stats.record(&best, kind, result.profit_mist, result.gas_cost_mist);
+                // Report to circuit breaker
+                if result.success {
+                    if !*dry_run_enabled {
+                        gas_estimator.record(best.pool_ids.len(), result.gas_cost_mist);
+                    }
+                    let net = result.profit_mist.unwrap_or(0) as i64
+                        - result.gas_cost_mist as i64;
+                    circuit_breaker.record_success(net, settled_ms);
+                    quarantine.record_success(best.strategy, net, settled_ms);
+                    opportunity_tracker.record_success(&best, settled_ms);
+                    gas_monitor.record_trade(settled_ms);
+                    let mode = if *paper_trading { "PAPER " } else { "" };
+                    alerts.raise(
+                        Severity::Info,
+                        "trade.ok",
+                        &format!("{}{} net {} MIST", mode, best.strategy, net),
+                        settled_ms,
+                    );
+                } else {
+                    alerts.raise(
+                        Severity::Warning,
+                        "trade.reverted",
+                        &format!("{} reverted: {:?}", best.strategy, result.error_message),
+                        settled_ms,
+                    );
+                    record_failure(circuit_breaker, breaker_store, alerts, metrics, -(result.gas_cost_mist as i64), settled_ms);
+                    quarantine.record_onchain_failure(best.strategy, -(result.gas_cost_mist as i64), settled_ms);
+                    opportunity_tracker.record_failure(&best, settled_ms);
+                }
+            }
+            Err(e) => {
+                error!(error = %e, "Transaction submission failed");
+                if let Some(log) = trade_log.as_mut() {
+                    log.record(&best, Err(&e), build, settled_ms);
+                }
+                trade_stats.record(best.strategy, best.net_profit, TradeOutcome::Failed);
+                metrics.record_submit_error();
+                metrics.set_last_trade(LastTrade {
+                    digest: None,
+                    strategy: best.strategy.to_string(),
+                    outcome: "submit_failed".to_string(),
+                    profit_mist: 0,
+                    gas_mist: 0,
+                    at_ms: settled_ms,
+                });
+                alerts.raise(Severity::Warning, "trade.submit_failed", &e.to_string(), settled_ms);
+                record_failure(circuit_breaker, breaker_store, alerts, metrics, 0, settled_ms);
+                opportunity_tracker.record_failure(&best, settled_ms);
+            }
+        }
+    }
+}
+
+/// A candidate that passed revalidation, sizing, and the pre-build guards,
+/// with its built PTB and `opportunity` trace span.
+struct Prepared {
+    opp: ArbOpportunity,
+    tx: BuiltTx,
+    span: tracing::Span,
+}
+
+/// Switch a sized flash-loan arb to its direct (inventory-funded) variant
+/// when one exists and the wallet's inventory coin covers the input.
+fn fund_from_inventory(mut opp: ArbOpportunity, inventory: Option<&Inventory>) -> ArbOpportunity {
+    let (Some(inventory), Some(direct)) = (inventory, opp.strategy.direct_variant()) else {
+        return opp;
+    };
+    let Some(coin_type) = opp.type_args.first() else {
+        return opp;
+    };
+    if let Some(coin) = inventory.covering(coin_type, opp.amount_in) {
+        debug!(
+            strategy = %opp.strategy,
+            direct = ?direct,
+            amount = %opp.amount_in,
+            inventory = %coin.balance,
+            "Funding from inventory — no flash loan"
+        );
+        opp.strategy = direct;
+    }
+    opp
+}
+/// Log each strategy's quarantine record alongside the P&L summary.
+fn log_quarantine_stats(quarantine: &StrategyQuarantine, now: u64) {
+    for (strategy, r) in quarantine.stats() {
+        info!(
+            strategy = %strategy,
+            attempts = %r.attempts,
+            dry_run_failures = %r.dry_run_failures,
+            onchain_failures = %r.onchain_failures,
+            realized_pnl = %r.realized_pnl_mist,
+            quarantines = %r.quarantines,
+            quarantined = %r.quarantined_until_ms.is_some_and(|until| now < until),
+            "🩺 Strategy health"
+        );
+    }
+}
+/// Per-pool swap flow over `window_ms` for the decision record, one
+/// `net_a_in/trades` entry per leg joined by `>`.
+fn flow_summary(cache: &PoolCache, pool_ids: &[String], window_ms: u64, now: u64) -> String {
+    pool_ids
+        .iter()
+        .map(|id| {
+            let w = cache.flow().window(id, window_ms, now);
+            format!("{}/{}", w.net_a_in, w.trades)
+        })
+        .collect::<Vec<_>>()
+        .join(">")
+}
+
+/// Step 3 for one candidate: revalidate its legs against the current
+/// cache, optimize the trade size, and apply the guards. Returns the sized
+/// opportunity and its `opportunity` span, or `None` if it drops out.
+#[allow(clippy::too_many_arguments)]
+fn prepare_candidate(
+    mut best: ArbOpportunity,
+    scanner: &Scanner,
+    cache: &PoolCache,
+    build: &BuildInfo,
+    min_profit_fraction: f64,
+    weight: f64,
+    clock: CycleClock,
+    scan_span: &tracing::Span,
+) -> Option<(ArbOpportunity, tracing::Span)> {
+    // Each processed opportunity is its own trace; stages are child spans.
+    let opp_span = info_span!(
+        parent: None,
+        "opportunity",
+        strategy = %best.strategy,
+        hops = %best.pool_ids.len(),
+        amount_in = Empty,
+        expected_profit = Empty,
+        net_profit = Empty,
+        outcome = Empty,
+        weight = %weight,
+        flow_1m = %flow_summary(cache, &best.pool_ids, FLOW_WINDOW_SHORT_MS, clock.now_ms()),
+        flow_5m = %flow_summary(cache, &best.pool_ids, FLOW_WINDOW_LONG_MS, clock.now_ms()),
+        version = %build.version,
+        commit = %build.commit,
+        config_hash = %build.config_hash,
+    );
+    opp_span.follows_from(scan_span);
+
+    // Revalidate legs against the current cache — a pool can go stale
+    // or be quarantined between scan and build. A tri-hop that lost only
+    // its third leg is degraded to a two-hop over the remaining legs.
+    let pools = cache.snapshot();
+    let bad_legs = scanner.unusable_legs(&best, &pools, clock.now_ms());
+    if !bad_legs.is_empty() {
+        if best.pool_ids.len() == 3 && bad_legs == [2] {
+            match scanner.degrade_tri_hop(&best, &pools, clock) {
+                Some(two_hop) => {
+                    info!(
+                        tri_strategy = %best.strategy,
+                        failed_pool = %best.pool_ids[2],
+                        two_hop_strategy = %two_hop.strategy,
+                        pools = ?two_hop.pool_ids,
+                        amount = %two_hop.amount_in,
+                        expected_profit = %two_hop.expected_profit,
+                        "Tri-hop third leg unusable — degraded to two-hop"
+                    );
+                    best = two_hop;
+                }
+                None => {
+                    debug!(
+                        failed_pool = %best.pool_ids[2],
+                        "Tri-hop third leg unusable — no profitable two-hop fallback"
+                    );
+                    return None;
+                }
+            }
+        } else {
+            debug!(
+                strategy = %best.strategy,
+                legs = ?bad_legs,
+                "Opportunity legs no longer usable — skipping"
+            );
+            return None;
+        }
+    }
+
+    // Run the local optimizer (grid + SEARCH_METHOD line search): two-hop
+    // on the flash and sell legs, tri- and quad-hop through every leg
+    let optimize_span = info_span!(parent: &opp_span, "optimize").entered();
+    let legs: Option<Vec<&PoolState>> =
+        best.pool_ids.iter().map(|id| pools.iter().find(|p| &p.object_id == id)).collect();
+    if let Some(legs) = legs {
+        let Some((simulate, hi)) = build_opportunity_simulator(best.strategy, &legs, scanner.missing_fee) else {
+            debug!(pools = ?best.pool_ids, "Pool fee rate unknown or legs unusable — skipping");
+            return None;
+        };
+        let limits = TradeSizeLimits::for_pools(&legs, &scanner.trade_size_defaults);
+        let (optimal_amount, max_profit) =
+            scanner.search_method.optimize_lots(MIN_SIM_AMOUNT, hi, 100_000, limits, &*simulate);
+
+        if max_profit > 0 {
+            debug!(
+                prev_amount = %best.amount_in,
+                new_amount = %optimal_amount,
+                prev_profit = %best.expected_profit,
+                new_profit = %max_profit,
+                hops = %legs.len(),
+                method = ?scanner.search_method,
+                "Trade size optimized"
+            );
+            best.amount_in = optimal_amount;
+            best.expected_profit = max_profit;
+            best.net_profit = max_profit as i64 - best.estimated_gas as i64;
+        }
+    }
+    drop(optimize_span);
+    opp_span.record("amount_in", best.amount_in);
+    opp_span.record("expected_profit", best.expected_profit);
+
+    // Post-optimization guards
+    // Guard: skip if optimizer couldn't find a profitable trade
+    if best.expected_profit == 0 {
+        debug!("Optimizer found no profitable amount — skipping");
+        return None;
+    }
+
+    // Guard: dust amounts waste an execution attempt for negligible gain
+    if scanner.is_dust(best.amount_in) {
+        debug!(
+            amount = %best.amount_in,
+            floor = %scanner.min_trade_amount_mist,
+            "Optimal amount below dust floor — skipping"
+        );
+        return None;
+    }
+
+    // Guard: check opportunity staleness (prices may have moved)
+    let opp_age_ms = clock.now_ms().saturating_sub(best.detected_at_ms);
+    if opp_age_ms > 3_000 {
+        debug!(
+            age_ms = %opp_age_ms,
+            "Opportunity too stale (>3s) — skipping"
+        );
+        return None;
+    }
+
+    // Guard: net profit must still be positive after gas
+    best.net_profit = best.expected_profit as i64 - best.estimated_gas as i64;
+    if best.net_profit <= 0 {
+        debug!(
+            expected_profit = %best.expected_profit,
+            estimated_gas = %best.estimated_gas,
+            "Net profit non-positive after optimization — skipping"
+        );
+        return None;
+    }
+
+    info!(
+        strategy = %best.strategy,
+        amount = %best.amount_in,
+        expected_profit = %best.expected_profit,
+        net_profit = %best.net_profit,
+        min_profit_onchain = %best.min_profit(min_profit_fraction),
+        pools = ?best.pool_ids,
+        config_hash = %build.config_hash,
+        "Processing opportunity"
+    );
+
+    // Guard: final size must be a whole lot at or above every venue's minimum
+    match scanner.trade_limits(&best, &pools) {
+        Some(limits) if limits.allows(best.amount_in) => {}
+        limits => {
+            debug!(
+                amount = %best.amount_in,
+                limits = ?limits,
+                "Trade size below venue minimum or not a whole lot — skipping"
+            );
+            return None;
+        }
+    }
+
+    opp_span.record("net_profit", best.net_profit);
+
+    Some((best, opp_span))
+}
+
+/// Build the PTB for `opp`, recording the outcome with the per-strategy
+/// failure tracker. Only rejected builds count toward suspension — transient
+/// failures (RPC errors already retried by the builder, no free gas coin)
+/// are not the strategy's fault.
+async fn build_ptb(
+    ptb_builder: &PtbBuilder,
+    tracker: &mut FailureTracker,
+    alerts: &mut AlertRouter,
+    opp: &ArbOpportunity,
+    clock: CycleClock,
+    span: tracing::Span,
+) -> Result<BuiltTx, BuildError> {
+    match ptb_builder.build(opp).instrument(span).await {
+        Ok(tx) => {
+            tracker.record_success(opp.strategy);
+            Ok(tx)
+        }
+        Err(e) if e.is_transient() => {
+            warn!(strategy = %opp.strategy, error = %e, "PTB build failed (transient)");
+            Err(e)
+        }
+        Err(e) => {
+            warn!(strategy = %opp.strategy, error = %e, "PTB build rejected");
+            let now = clock.now_ms();
+            if tracker.record_failure(opp.strategy, &e.message, now) {
+                let consecutive = tracker.entry(opp.strategy).map_or(0, |f| f.consecutive);
+                alerts.raise(
+                    Severity::Warning,
+                    "build.suspended",
+                    &format!(
+                        "{} suspended after {} rejected PTB builds: {}",
+                        opp.strategy, consecutive, e
+                    ),
+                    now,
+                );
+            }
+            Err(e)
+        }
+    }
+}
+
+/// A signing failure. Node-built bytes that don't match what we asked for
+/// mean the RPC endpoint is broken or hostile — page on it.
+fn report_sign_failure(alerts: &mut AlertRouter, e: &anyhow::Error, what: &str, now: u64) {
+    if let Some(mismatch) = e.downcast_ref::<TxMismatch>() {
+        error!(tx = %what, error = %mismatch, "SECURITY: node returned a transaction we did not request — not signed");
+        alerts.raise(
+            Severity::Critical,
+            "security.tx_mismatch",
+            &format!("{}: {}", what, mismatch),
+            now,
+        );
+    } else {
+        error!(tx = %what, error = %format!("{e:#}"), "Failed to sign transaction");
+    }
+}
+
+/// Short outcome label recorded on the `opportunity` span.
+fn submit_outcome(result: &Result<SubmitResult>) -> &'static str {
+    match result {
+        Ok(r) if r.success => "success",
+        Ok(_) => "reverted",
+        Err(_) => "submit_failed",
+    }
+}
+/// Record a failure with the circuit breaker, raising a critical alert (and
+/// persisting the trip straight away) if it trips.
+fn record_failure(
+    breaker: &mut CircuitBreaker,
+    store: &mut Option<BreakerStore>,
+    alerts: &mut AlertRouter,
+    metrics: &Metrics,
+    loss_mist: i64,
+    now: u64,
+) {
+    if breaker.record_failure(loss_mist, now) {
+        metrics.record_breaker_trip();
+        publish_breaker(metrics, breaker);
+        save_breaker(store, breaker);
+        let stats = breaker.stats();
+        alerts.raise(
+            Severity::Critical,
+            "breaker.tripped",
+            &format!(
+                "circuit breaker tripped: {} (pnl {} MIST)",
+                stats.trip_reason.unwrap_or_default(),
+                stats.cumulative_pnl_mist
+            ),
+            now,
+        );
+    }
+}
+
+/// Write the breaker's state to its store, if persistence is enabled and
+/// the state changed. A failed write is logged, not fatal.
+pub fn save_breaker(store: &mut Option<BreakerStore>, breaker: &CircuitBreaker) {
+    let Some(store) = store.as_mut() else {
+        return;
+    };
+    match store.save(breaker) {
+        Ok(_) => clear_throttled!("main.breaker_save"),
+        Err(e) => warn_throttled!(
+            "main.breaker_save",
+            DEFAULT_INTERVAL,
+            path = %store.path().display(),
+            error = %e,
+            "Failed to persist circuit breaker state"
+        ),
+    }
+}
+
+/// Report the breaker's state to the metrics (and so the status endpoint).
+fn publish_breaker(metrics: &Metrics, breaker: &CircuitBreaker) {
+    let stats = breaker.stats();
+    metrics.set_breaker(BreakerStatus {
+        tripped: stats.is_tripped,
+        trip_reason: stats.trip_reason,
+        consecutive_failures: stats.consecutive_failures,
+        cumulative_pnl_mist: stats.cumulative_pnl_mist,
+        window_pnl_mist: stats.window_pnl_mist,
+    });
+}
+/// Turn a submission into a landed result or a definite failure. A
+/// [`SubmitError::PossiblyExecuted`] is settled by looking its digest up:
+/// found is the on-chain result, not found is a failure (nothing executed).
+/// `None` if the lookups can't tell.
+async fn settle_submission(
+    submitter: &Submitter,
+    submitted: Result<SubmitResult, SubmitError>,
+) -> Option<Result<SubmitResult>> {
+    let (digest, message) = match submitted {
+        Err(SubmitError::PossiblyExecuted { digest, message }) => (digest, message),
+        other => return Some(other.map_err(Into::into)),
+    };
+    warn!(digest = %digest, error = %message, "Submission outcome unknown — looking up the digest");
+    match submitter.confirm(&digest).await {
+        Ok(Some(result)) => Some(Ok(result)),
+        Ok(None) => Some(Err(anyhow::anyhow!("Transaction {} never landed: {}", digest, message))),
+        Err(e) => {
+            error!(digest = %digest, error = %format!("{e:#}"), "Could not determine whether the transaction executed");
+            None
+        }
+    }
+}
+
+/// Paper trading's stand-in for [`Submitter::submit`]: dry-run the final
+/// PTB and report it as a landed transaction, with the dry-run's gas and
+/// the profit from its `ArbExecuted` event. Nothing is signed or sent.
+async fn paper_fill(dry_runner: &DryRunner, tx_bytes: &str) -> Result<SubmitResult> {
+    let result = dry_runner.dry_run_tx(tx_bytes).await?;
+    let profit_mist = if result.success { result.arb_profit() } else { None };
+    Ok(SubmitResult {
+        digest: PAPER_DIGEST.to_string(),
+        success: result.success,
+        gas_cost_mist: result.gas_cost_mist,
+        gas: GasCharge {
+            payer: None,
+            computation_mist: result.gas_cost_mist,
+            storage_mist: 0,
+            rebate_mist: 0,
+        },
+        profit_mist,
+        error_message: result.error_message,
+    })
+}
+
+/// Digest recorded for paper fills.
+const PAPER_DIGEST: &str = "paper";
+
+/// Log a trade result and update running totals.
+fn log_trade_result(
+    result: &arb_executor::SubmitResult,
+    build: &BuildInfo,
+    paper: bool,
+    total_profit: &mut i64,
+    total_trades: u64,
+    total_gas: u64,
+) {
+    if result.success {
+        let profit = result.profit_mist.unwrap_or(0);
+        *total_profit += profit as i64 - result.gas_cost_mist as i64;
+
+        info!(
+            digest = %result.digest,
+            profit = %profit,
+            gas = %result.gas_cost_mist,
+            total_trades = %total_trades,
+            total_profit = %total_profit,
+            total_gas = %total_gas,
+            version = %build.version,
+            commit = %build.commit,
+            config_hash = %build.config_hash,
+            "{}",
+            if paper { "📝 PAPER arb filled (simulated)" } else { "✅ Arb executed successfully" }
+        );
+    } else {
+        warn!(
+            digest = %result.digest,
+            error = ?result.error_message,
+            version = %build.version,
+            commit = %build.commit,
+            config_hash = %build.config_hash,
+            "{}",
+            if paper { "📝 PAPER arb failed in dry-run (simulated)" } else { "❌ Transaction failed on-chain" }
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,97 @@
+//! Tracing subscriber setup, with optional OpenTelemetry trace export.
+//!
+//! Logs always go to stdout via `tracing_subscriber::fmt`. When built with the
+//! `otel` feature and `OTEL_ENDPOINT` is set, pipeline spans are also exported
+//! over OTLP/HTTP: each opportunity is a root `opportunity` span (one trace)
+//! with `optimize` → `build` → `dry_run` → `sign` → `submit` children, linked
+//! to the cycle's `scan` span.
+
+use anyhow::Result;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Service name reported on exported traces.
+#[cfg(feature = "otel")]
+const SERVICE_NAME: &str = "arb-move-bot";
+
+/// Flushes pending spans on drop. Keep alive for the life of `main`.
+pub struct TelemetryGuard {
+    #[cfg(feature = "otel")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("Failed to flush OpenTelemetry spans: {}", e);
+            }
+        }
+    }
+}
+
+/// Install the global subscriber. `otel_endpoint` is the full OTLP/HTTP traces
+/// URL (e.g. `http://localhost:4318/v1/traces`); `None` disables export.
+pub fn init(otel_endpoint: Option<String>) -> Result<TelemetryGuard> {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    let fmt = tracing_subscriber::fmt::layer()
+        .with_target(true)
+        .with_thread_ids(false);
+
+    #[cfg(feature = "otel")]
+    {
+        use opentelemetry::trace::TracerProvider;
+
+        let provider = otel_endpoint
+            .as_deref()
+            .map(build_provider)
+            .transpose()?;
+        let otel = provider
+            .as_ref()
+            .map(|p| tracing_opentelemetry::layer().with_tracer(p.tracer(SERVICE_NAME)));
+
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(fmt)
+            .with(otel)
+            .init();
+
+        if let Some(endpoint) = &otel_endpoint {
+            tracing::info!(endpoint = %endpoint, "OpenTelemetry trace export enabled");
+        }
+        Ok(TelemetryGuard { provider })
+    }
+
+    #[cfg(not(feature = "otel"))]
+    {
+        tracing_subscriber::registry().with(filter).with(fmt).init();
+
+        if otel_endpoint.is_some() {
+            tracing::warn!("OTEL_ENDPOINT is set but the bot was built without the `otel` feature — traces not exported");
+        }
+        Ok(TelemetryGuard {})
+    }
+}
+
+#[cfg(feature = "otel")]
+fn build_provider(endpoint: &str) -> Result<opentelemetry_sdk::trace::SdkTracerProvider> {
+    use anyhow::Context;
+    use opentelemetry_otlp::WithExportConfig;
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()
+        .context("Failed to build OTLP span exporter")?;
+
+    Ok(opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            opentelemetry_sdk::Resource::builder()
+                .with_service_name(SERVICE_NAME)
+                .build(),
+        )
+        .build())
+}
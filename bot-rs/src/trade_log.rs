@@ -0,0 +1,291 @@
+//! Durable JSONL record of every submitted trade.
+//!
+//! One line per submission: the opportunity as sized and validated, what
+//! landed on-chain, and the build that sent it. Written through a
+//! [`RotatingFile`] so a weeks-long run doesn't fill the disk.
+//!
+//! The strategy loop only queues lines; a background task does the file
+//! I/O, so a slow disk never stalls a cycle. On open the existing log
+//! (rotated segments included) is read back into lifetime [`TradeTotals`],
+//! so statistics survive restarts. Pruned segments drop out of "lifetime".
+//!
+//! Paper-trading lines carry `"paper": true`; a log only counts the lines
+//! of its own mode, so simulated fills never show up in live totals.
+
+use anyhow::Result;
+use arb_executor::SubmitResult;
+use arb_types::log_throttle::DEFAULT_INTERVAL;
+use arb_types::opportunity::ArbOpportunity;
+use arb_types::rotating_file::{RotatingFile, RotationPolicy};
+use arb_types::{warn_throttled, BuildInfo};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use tokio::sync::{mpsc, oneshot};
+use tracing::warn;
+
+/// Lines queued ahead of the writer before new ones are dropped.
+const QUEUE_CAPACITY: usize = 1024;
+
+/// Submission counts and amounts, as recorded in the trade log.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TradeTotals {
+    pub trades: u64,
+    pub succeeded: u64,
+    /// Reverted on-chain or rejected by the RPC
+    pub failed: u64,
+    pub profit_mist: u64,
+    pub gas_mist: u64,
+}
+
+impl TradeTotals {
+    /// Profit minus all gas charged.
+    pub fn net_mist(&self) -> i64 {
+        self.profit_mist as i64 - self.gas_mist as i64
+    }
+
+    pub fn combined(&self, other: &Self) -> Self {
+        Self {
+            trades: self.trades + other.trades,
+            succeeded: self.succeeded + other.succeeded,
+            failed: self.failed + other.failed,
+            profit_mist: self.profit_mist + other.profit_mist,
+            gas_mist: self.gas_mist + other.gas_mist,
+        }
+    }
+
+    /// Count one log line. Returns false if it isn't a trade record.
+    fn add(&mut self, line: &Value) -> bool {
+        let Some(outcome) = line.get("outcome") else { return false };
+        if outcome.get("submit_error").is_some() {
+            self.trades += 1;
+            self.failed += 1;
+            return true;
+        }
+        let Some(success) = outcome.get("success").and_then(Value::as_bool) else { return false };
+        self.trades += 1;
+        if success {
+            self.succeeded += 1;
+        } else {
+            self.failed += 1;
+        }
+        self.profit_mist += outcome.get("profit_mist").and_then(Value::as_u64).unwrap_or(0);
+        self.gas_mist += outcome.get("gas_mist").and_then(Value::as_u64).unwrap_or(0);
+        true
+    }
+}
+
+enum Command {
+    Line(String, u64),
+    Flush(oneshot::Sender<()>),
+}
+
+pub struct TradeLog {
+    tx: mpsc::Sender<Command>,
+    path: PathBuf,
+    /// Totals read from the log at startup
+    history: TradeTotals,
+    /// Totals recorded since
+    session: TradeTotals,
+    /// Whether this session is paper trading
+    paper: bool,
+}
+
+impl TradeLog {
+    /// Open the log, read the history of the same mode (paper or live), and
+    /// start the writer task. Must be called within a tokio runtime.
+    pub fn open(path: impl Into<PathBuf>, policy: RotationPolicy, paper: bool, now_ms: u64) -> Result<Self> {
+        let path = path.into();
+        let file = RotatingFile::open(&path, policy, now_ms)?;
+
+        let mut history = TradeTotals::default();
+        let mut unreadable = 0usize;
+        for line in file.read_lines()? {
+            let Ok(value) = serde_json::from_str::<Value>(&line) else {
+                unreadable += usize::from(!line.trim().is_empty());
+                continue;
+            };
+            if is_paper(&value) != paper {
+                continue;
+            }
+            if !history.add(&value) {
+                unreadable += 1;
+            }
+        }
+        if unreadable > 0 {
+            warn!(path = %path.display(), lines = %unreadable, "Skipped unreadable trade log lines");
+        }
+
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(write_lines(file, rx));
+        Ok(Self { tx, path, history, session: TradeTotals::default(), paper })
+    }
+
+    /// Queue one submission. `result` is `Err` when it never landed.
+    /// Never blocks: if the writer has fallen behind, the line is dropped
+    /// with a warning — the trade already happened.
+    pub fn record(
+        &mut self,
+        opp: &ArbOpportunity,
+        result: Result<&SubmitResult, &anyhow::Error>,
+        build: &BuildInfo,
+        now_ms: u64,
+    ) {
+        let outcome = match result {
+            Ok(r) => json!({
+                "digest": r.digest,
+                "success": r.success,
+                "profit_mist": r.profit_mist,
+                "gas_mist": r.gas_cost_mist,
+                "error": r.error_message,
+            }),
+            Err(e) => json!({ "submit_error": format!("{:#}", e) }),
+        };
+        let mut line = json!({
+            "ts_ms": now_ms,
+            "strategy": opp.strategy,
+            "pools": opp.pool_ids,
+            "amount_in": opp.amount_in,
+            "expected_profit": opp.expected_profit,
+            "estimated_gas": opp.estimated_gas,
+            "expected_net": opp.net_profit,
+            "outcome": outcome,
+            "version": build.version,
+            "commit": build.commit,
+            "config_hash": build.config_hash,
+        });
+        if self.paper {
+            line["paper"] = json!(true);
+        }
+        self.session.add(&line);
+
+        if self.tx.try_send(Command::Line(line.to_string(), now_ms)).is_err() {
+            warn_throttled!(
+                "trade_log.queue_full",
+                DEFAULT_INTERVAL,
+                path = %self.path.display(),
+                "Trade log writer is behind — line dropped"
+            );
+        }
+    }
+
+    /// Wait until every queued line is written.
+    pub async fn flush(&self) {
+        let (done, written) = oneshot::channel();
+        if self.tx.send(Command::Flush(done)).await.is_ok() {
+            let _ = written.await;
+        }
+    }
+
+    /// Totals read from the log at startup.
+    pub fn history(&self) -> TradeTotals {
+        self.history
+    }
+
+    /// History plus this session.
+    pub fn lifetime(&self) -> TradeTotals {
+        self.history.combined(&self.session)
+    }
+}
+
+fn is_paper(line: &Value) -> bool {
+    line.get("paper").and_then(Value::as_bool).unwrap_or(false)
+}
+
+/// Writer task: append queued lines until every sender is gone.
+async fn write_lines(mut file: RotatingFile, mut rx: mpsc::Receiver<Command>) {
+    while let Some(command) = rx.recv().await {
+        match command {
+            Command::Line(line, now_ms) => {
+                if let Err(e) = file.append_line(&line, now_ms) {
+                    warn_throttled!(
+                        "trade_log.write_failed",
+                        DEFAULT_INTERVAL,
+                        path = %file.path().display(),
+                        error = %e,
+                        "Failed to write trade log"
+                    );
+                }
+            }
+            Command::Flush(done) => {
+                let _ = done.send(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arb_executor::GasCharge;
+    use arb_types::opportunity::StrategyType;
+
+    fn opp() -> ArbOpportunity {
+        ArbOpportunity {
+            strategy: StrategyType::CetusToTurbos,
+            amount_in: 1_000_000_000,
+            expected_profit: 3_000_000,
+            estimated_gas: 1_000_000,
+            net_profit: 2_000_000,
+            pool_ids: vec!["0x1".to_string(), "0x2".to_string()],
+            type_args: Vec::new(),
+            detected_at_ms: 0,
+        }
+    }
+
+    fn landed(success: bool, profit_mist: u64, gas_cost_mist: u64) -> SubmitResult {
+        SubmitResult {
+            digest: "D1g35t".to_string(),
+            success,
+            gas_cost_mist,
+            gas: GasCharge::default(),
+            profit_mist: success.then_some(profit_mist),
+            error_message: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lifetime_totals_survive_restart() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("arb-trade-log-{}", nanos)).join("trades.jsonl");
+        let build = BuildInfo {
+            version: "0.1.0".to_string(),
+            commit: "abc1234".to_string(),
+            config_hash: "cfg".to_string(),
+        };
+
+        let mut log = TradeLog::open(&path, RotationPolicy::default(), false, 0).unwrap();
+        log.record(&opp(), Ok(&landed(true, 3_000_000, 1_000_000)), &build, 1);
+        log.record(&opp(), Ok(&landed(false, 0, 400_000)), &build, 2);
+        log.record(&opp(), Err(&anyhow::anyhow!("rejected")), &build, 3);
+        log.flush().await;
+        assert_eq!(log.lifetime().trades, 3);
+        drop(log);
+
+        // A line torn by a crash is skipped; the next session appends cleanly
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut f| std::io::Write::write_all(&mut f, b"{\"ts_ms\":4,\"outc"))
+            .unwrap();
+        let mut log = TradeLog::open(&path, RotationPolicy::default(), false, 5).unwrap();
+        let history = log.history();
+        assert_eq!((history.trades, history.succeeded, history.failed), (3, 1, 2));
+        assert_eq!(history.net_mist(), 3_000_000 - 1_400_000);
+
+        log.record(&opp(), Ok(&landed(true, 2_000_000, 500_000)), &build, 6);
+        log.flush().await;
+        assert_eq!(log.lifetime().trades, 4);
+        assert_eq!(TradeLog::open(&path, RotationPolicy::default(), false, 7).unwrap().history(), log.lifetime());
+
+        // Paper fills go to the same file but only count toward paper totals
+        let mut paper = TradeLog::open(&path, RotationPolicy::default(), true, 8).unwrap();
+        assert_eq!(paper.history(), TradeTotals::default());
+        paper.record(&opp(), Ok(&landed(true, 9_000_000, 1_000_000)), &build, 9);
+        paper.flush().await;
+        assert_eq!(TradeLog::open(&path, RotationPolicy::default(), false, 10).unwrap().history(), log.lifetime());
+        assert_eq!(TradeLog::open(&path, RotationPolicy::default(), true, 11).unwrap().history(), paper.lifetime());
+    }
+}
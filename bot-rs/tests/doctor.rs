@@ -0,0 +1,280 @@
+//! `arbctl doctor` against mock RPC / WebSocket servers: a healthy setup
+//! passes, and each broken configuration is pinned on the right check.
+
+use arb_bot::doctor::{self, DoctorOptions, Report, Status};
+use arb_collector::fixtures::{self, PoolFixture};
+use arb_collector::rpc_poller::object_key;
+use arb_types::config::Config;
+use arb_types::test_support::{self, Reply};
+use arb_types::StrategyType;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+const PACKAGE: &str = "0xa11ce";
+const ADMIN_CAP: &str = "0xad";
+const PAUSE_FLAG: &str = "0xfa";
+const CETUS_CONFIG: &str = "0xc0";
+const TURBOS_VERSIONED: &str = "0x7e";
+
+/// What the mock chain holds.
+struct Chain {
+    pools: Vec<PoolFixture>,
+    /// object_key → object type
+    objects: HashMap<String, String>,
+    balance: u64,
+    /// module → exposed function names
+    functions: HashMap<&'static str, Vec<&'static str>>,
+}
+
+impl Chain {
+    fn healthy() -> Self {
+        let mut pools = fixtures::load_dex_for_tests("cetus").unwrap();
+        pools.extend(fixtures::load_dex_for_tests("turbos").unwrap());
+        let objects = [
+            (PACKAGE, "package".to_string()),
+            (ADMIN_CAP, format!("{PACKAGE}::admin::AdminCap")),
+            (PAUSE_FLAG, format!("{PACKAGE}::admin::PauseFlag")),
+            (CETUS_CONFIG, "0x1eab::config::GlobalConfig".to_string()),
+            (TURBOS_VERSIONED, "0x91bf::pool::Versioned".to_string()),
+        ]
+        .into_iter()
+        .map(|(id, ty)| (object_key(id), ty))
+        .collect();
+        let mut functions: HashMap<&'static str, Vec<&'static str>> = HashMap::new();
+        for s in StrategyType::ALL {
+            functions.entry(s.move_module()).or_default().push(s.move_function_name());
+        }
+        Self { pools, objects, balance: 5_000_000_000, functions }
+    }
+
+    fn modules(&self) -> Value {
+        let mut modules = Map::new();
+        for (module, fns) in &self.functions {
+            let exposed: Map<String, Value> = fns.iter().map(|f| (f.to_string(), json!({}))).collect();
+            modules.insert(module.to_string(), json!({ "exposedFunctions": exposed }));
+        }
+        Value::Object(modules)
+    }
+}
+
+fn respond(chain: &Chain, request: &Value) -> Value {
+    let params = &request["params"];
+    let result = match request["method"].as_str() {
+        Some("sui_getChainIdentifier") => json!("35834a8a"),
+        Some("suix_getBalance") => json!({ "totalBalance": chain.balance.to_string() }),
+        Some("sui_getNormalizedMoveModulesByPackage") if params[0] == PACKAGE => chain.modules(),
+        Some("sui_multiGetObjects") => Value::Array(
+            params[0]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|id| {
+                    let id = id.as_str().unwrap_or_default();
+                    if let Some(f) = chain.pools.iter().find(|f| f.pool_id == id) {
+                        f.object.clone()
+                    } else if let Some(ty) = chain.objects.get(&object_key(id)) {
+                        json!({ "data": { "objectId": id, "type": ty } })
+                    } else {
+                        json!({ "error": { "code": "notExists", "object_id": id } })
+                    }
+                })
+                .collect(),
+        ),
+        Some("suix_getDynamicFieldObject") => chain
+            .pools
+            .iter()
+            .find(|f| f.object["data"]["content"]["fields"]["inner"]["fields"]["id"]["id"] == params[0])
+            .and_then(|f| f.dynamic_field.clone())
+            .unwrap_or_else(|| json!({ "error": { "code": "dynamicFieldNotFound" } })),
+        _ => return json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32601, "message": "Method not found" } }),
+    };
+    json!({ "jsonrpc": "2.0", "id": 1, "result": result })
+}
+
+/// Mock node serving `chain`.
+async fn spawn_mock_rpc(chain: Arc<Mutex<Chain>>) -> String {
+    test_support::spawn_mock_rpc(move |request| Reply::Json(respond(&chain.lock().unwrap(), request))).await
+}
+
+/// WebSocket server that confirms every subscription, then sends `events`.
+async fn spawn_mock_ws(events: Vec<Value>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+        let Some(Ok(Message::Text(first))) = ws.next().await else { return };
+        let id = serde_json::from_str::<Value>(&first).unwrap()["id"].clone();
+        let confirm = json!({ "jsonrpc": "2.0", "id": id, "result": 1 });
+        ws.send(Message::Text(confirm.to_string().into())).await.unwrap();
+        for event in events {
+            let note = json!({ "jsonrpc": "2.0", "method": "suix_subscribeEvent", "params": { "subscription": 1, "result": event } });
+            ws.send(Message::Text(note.to_string().into())).await.unwrap();
+        }
+        // Hold the connection open
+        while ws.next().await.is_some() {}
+    });
+
+    format!("http://{}", addr)
+}
+
+fn config(rpc_url: String, pools: &[PoolFixture]) -> Config {
+    Config {
+        private_key_hex: format!("0x{}", "2a".repeat(32)),
+        package_id: PACKAGE.into(),
+        admin_cap_id: ADMIN_CAP.into(),
+        pause_flag_id: PAUSE_FLAG.into(),
+        cetus_global_config: CETUS_CONFIG.into(),
+        turbos_versioned: TURBOS_VERSIONED.into(),
+        ..Config::for_tests(rpc_url, pools.iter().map(PoolFixture::pool_config).collect())
+    }
+}
+
+/// Run doctor against a mock chain, after `breakage` has edited it.
+async fn diagnose(breakage: impl FnOnce(&mut Chain, &mut Config)) -> Report {
+    let chain = Chain::healthy();
+    let pools = chain.pools.clone();
+    let chain = Arc::new(Mutex::new(chain));
+    let url = spawn_mock_rpc(chain.clone()).await;
+    let mut config = config(url, &pools);
+    breakage(&mut chain.lock().unwrap(), &mut config);
+    doctor::run(&config, &DoctorOptions::default()).await
+}
+
+fn status(report: &Report, name: &str) -> Status {
+    report.check(name).unwrap_or_else(|| panic!("{} did not run", name)).status
+}
+
+fn message<'a>(report: &'a Report, name: &str) -> &'a str {
+    &report.check(name).unwrap().message
+}
+
+#[tokio::test]
+async fn test_healthy_setup_passes() {
+    let report = diagnose(|_, _| {}).await;
+    for name in ["config", "rpc", "wallet", "objects", "abi", "pools"] {
+        assert_eq!(status(&report, name), Status::Pass, "{}: {}", name, message(&report, name));
+    }
+    assert_eq!(status(&report, "inventory"), Status::Skip);
+    assert_eq!(status(&report, "ws"), Status::Skip);
+    assert!(report.healthy());
+    assert_eq!(report.to_json()["healthy"], true);
+}
+
+#[tokio::test]
+async fn test_unreachable_rpc() {
+    let report = diagnose(|_, config| config.rpc_url = "http://127.0.0.1:1".into()).await;
+    assert_eq!(status(&report, "rpc"), Status::Fail);
+    assert!(message(&report, "rpc").contains("unreachable"));
+    assert!(report.critical_failures().contains(&"rpc"));
+    assert_eq!(report.to_json()["healthy"], false);
+}
+
+#[tokio::test]
+async fn test_hung_rpc_is_time_bounded() {
+    // Accepts requests, never answers
+    let url = test_support::spawn_mock_rpc(|_| Reply::Hang).await;
+    let opts = DoctorOptions { check_timeout: Duration::from_millis(200), ..DoctorOptions::default() };
+    let started = std::time::Instant::now();
+    let report = doctor::run(&config(url, &[]), &opts).await;
+    assert_eq!(status(&report, "rpc"), Status::Fail);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_unfunded_wallet() {
+    let report = diagnose(|chain, _| chain.balance = 1_000).await;
+    assert_eq!(status(&report, "wallet"), Status::Fail);
+    assert!(message(&report, "wallet").contains("fund the wallet"));
+    assert_eq!(report.critical_failures(), ["wallet"]);
+
+    let report = diagnose(|_, config| config.private_key_hex = "0xabc".into()).await;
+    assert!(message(&report, "wallet").contains("SUI_PRIVATE_KEY is invalid"));
+}
+
+#[tokio::test]
+async fn test_missing_or_mistyped_objects() {
+    let report = diagnose(|chain, _| {
+        chain.objects.remove(&object_key(ADMIN_CAP));
+    })
+    .await;
+    assert_eq!(status(&report, "objects"), Status::Fail);
+    assert!(message(&report, "objects").contains("ADMIN_CAP_ID 0xad does not exist"));
+
+    // PAUSE_FLAG_ID pointing at the AdminCap
+    let report = diagnose(|_, config| config.pause_flag_id = ADMIN_CAP.into()).await;
+    assert!(message(&report, "objects").contains("expected *::admin::PauseFlag"));
+    assert_eq!(report.critical_failures(), ["objects"]);
+}
+
+#[tokio::test]
+async fn test_package_abi_mismatch() {
+    let report = diagnose(|chain, _| {
+        chain.functions.get_mut("two_hop").unwrap().retain(|f| *f != "arb_cetus_to_turbos");
+    })
+    .await;
+    assert_eq!(status(&report, "abi"), Status::Fail, "{}", message(&report, "abi"));
+    assert!(message(&report, "abi").contains("two_hop::arb_cetus_to_turbos"));
+
+    // A package from before the direct module only loses inventory mode
+    let report = diagnose(|chain, _| {
+        chain.functions.remove("direct");
+    })
+    .await;
+    assert_eq!(status(&report, "abi"), Status::Warn);
+    assert!(report.healthy());
+}
+
+#[tokio::test]
+async fn test_unparseable_pools() {
+    let report = diagnose(|chain, _| {
+        chain.pools.truncate(1);
+    })
+    .await;
+    assert_eq!(status(&report, "pools"), Status::Warn);
+    assert!(message(&report, "pools").contains("missing or unparseable"));
+
+    let report = diagnose(|chain, _| chain.pools.clear()).await;
+    assert_eq!(status(&report, "pools"), Status::Fail);
+    assert_eq!(report.critical_failures(), ["pools"]);
+}
+
+#[tokio::test]
+async fn test_skipped_checks_do_not_run() {
+    let chain = Arc::new(Mutex::new(Chain::healthy()));
+    let pools = chain.lock().unwrap().pools.clone();
+    let url = spawn_mock_rpc(chain).await;
+    let mut opts = DoctorOptions::default();
+    opts.skip.extend(["wallet".to_string(), "pools".to_string()]);
+    let report = doctor::run(&config(url, &pools), &opts).await;
+    assert_eq!(status(&report, "wallet"), Status::Skip);
+    assert_eq!(status(&report, "pools"), Status::Skip);
+    assert_eq!(status(&report, "rpc"), Status::Pass);
+}
+
+#[tokio::test]
+async fn test_ws_delivery() {
+    let ws_only = || {
+        let mut opts = DoctorOptions { use_ws: true, ws_listen: Duration::from_millis(500), ..DoctorOptions::default() };
+        opts.skip.extend(["config", "rpc", "wallet", "objects", "abi", "pools"].map(String::from));
+        opts
+    };
+
+    let url = spawn_mock_ws(vec![json!({ "type": "0x1eab::pool::SwapEvent" })]).await;
+    let report = doctor::run(&config(url, &[]), &ws_only()).await;
+    assert_eq!(status(&report, "ws"), Status::Pass, "{}", message(&report, "ws"));
+    assert!(message(&report, "ws").contains("SwapEvent"));
+
+    // Subscribed, but nothing arrives
+    let url = spawn_mock_ws(vec![]).await;
+    let report = doctor::run(&config(url, &[]), &ws_only()).await;
+    assert_eq!(status(&report, "ws"), Status::Warn);
+    // WS is a fallback-backed feature: never fails the run
+    assert!(report.healthy());
+}
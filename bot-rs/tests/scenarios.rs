@@ -0,0 +1,664 @@
+//! End-to-end scenario harness: replays every `tests/scenarios/*.json`
+//! against a mock JSON-RPC server and a mock WebSocket node, both with
+//! fault injection, running the bot's real event stream ([`WsStream`]) and
+//! strategy loop ([`run_strategy_loop`]) on a manual clock.
+//!
+//! A scenario names the synthetic fixture pools it monitors (by DEX), the
+//! circuit breaker's limits, any `RuntimeSettings` knobs (by env var name),
+//! and a list of scenes. Each scene moves the clock to `at_ms` (from the
+//! scenario's start), sets the chain state the mocks serve, and lets the
+//! collectors and the loop run against it:
+//!
+//! ```json
+//! { "name": "...", "pools": ["cetus", "turbos"],
+//!   "breaker": { "max_consecutive_failures": 2, "cooldown_ms": 10000 },
+//!   "env": { "OPPORTUNITY_FAILURE_COOLDOWN_MS": "1000" },
+//!   "scenes": [{
+//!     "name": "spread opens", "at_ms": 500,
+//!     "overrides": { "turbos": { "sqrt_price": "34000000000000000" } },
+//!     "fault": "rate_limited" | "malformed" | "rpc_error" | { "missing": "cetus" },
+//!     "ws": "disconnect",
+//!     "poller": "down",
+//!     "submit": "success" | "revert" | "rejected" | "version_conflict",
+//!     "expect": { "fetch_ok": true, "pools_updated": 2, "pools_streamed": 2, "pools_cached": 2,
+//!                 "min_opportunities": 1, "max_opportunities": 0, "submissions": 1, "outcome": "success",
+//!                 "breaker_tripped": false, "alerts": ["collector.dead"] }
+//!   }] }
+//! ```
+//!
+//! - `overrides` patch fields of the fixture's `data.content.fields` and
+//!   persist into later scenes.
+//! - `fault` applies to every RPC call in the scene, the loop's and the
+//!   stream's re-fetches included.
+//! - The WebSocket node sends one swap event per monitored pool per scene;
+//!   the stream re-fetches each pool over RPC, as it does for events that
+//!   don't carry the post-swap state. `"ws": "disconnect"` drops the
+//!   stream's connection and refuses reconnects for the scene; the stream
+//!   is restarted like the bot's supervisor does, and resubscribes once a
+//!   scene lets it.
+//! - The fallback RPC poller polls once per scene; `"poller": "down"` skips
+//!   it. What either collector fetched lands in the loop's cache stamped
+//!   with the scene's time, and the collector heartbeat moves if the poll
+//!   succeeded or the stream processed an event, so with both silent the
+//!   pools and heartbeat stay as old as the last update.
+//! - `submit` is how the node answers `sui_executeTransactionBlock`
+//!   (default `success`): `rejected` fails input checks, `version_conflict`
+//!   reports a stale input object version.
+//! - `pools_updated` counts the pools either collector fetched during the
+//!   scene, `pools_streamed` those the stream re-fetched.
+//! - `submissions` counts the transactions the loop sent during the scene,
+//!   `outcome` is the metrics' last trade outcome, `breaker_tripped` the
+//!   breaker state the loop last published, and `alerts` keys the loop
+//!   delivered during the scene.
+//!
+//! Adding a regression scenario is a new JSON file — no code changes.
+
+use arb_bot::alerts::{AlertConfig, AlertRouter, AlertTransport};
+use arb_bot::profiling::Profiler;
+use arb_bot::settings::RuntimeSettings;
+use arb_bot::strategy_loop::{run_strategy_loop, StrategyState};
+use arb_collector::fixtures::{self, PoolFixture, SYNTHETIC_FIXTURES_DIR};
+use arb_collector::{rpc_poller, DexPackage, PollBackoff, PollPacing, PoolCache, WsEndpoints, WsStream};
+use arb_executor::ptb_builder::PtbBuilder;
+use arb_executor::{CoinMerger, GasMonitor, Signer, Submitter};
+use arb_strategy::{
+    CircuitBreaker, ColdStartMonitor, DryRunner, FailureTracker, GasEstimator, OpportunityTracker, OutcomeStats,
+    Scanner, StrategyQuarantine, StrategyWeights, TradeStats,
+};
+use arb_types::clock::{unix_now_ms, Clock};
+use arb_types::config::Config;
+use arb_types::encoding::{encode_base58, parse_address};
+use arb_types::test_support::{self, Reply};
+use arb_types::{BuildInfo, Metrics};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, watch};
+use tokio_tungstenite::tungstenite::Message;
+
+const SCENARIOS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/scenarios");
+
+const EXPECT_KEYS: &[&str] = &[
+    "fetch_ok",
+    "pools_updated",
+    "pools_streamed",
+    "pools_cached",
+    "min_opportunities",
+    "max_opportunities",
+    "submissions",
+    "outcome",
+    "breaker_tripped",
+    "alerts",
+];
+
+/// The bot's package and the shared objects its Cetus/Turbos calls take.
+const PACKAGE_ID: &str = "0xbeef";
+const SHARED_OBJECTS: &[&str] = &["0xf1a9", "0xce75", "0x7b05", "0x6"];
+const ADMIN_CAP_ID: &str = "0xca9";
+const GAS_COIN_ID: &str = "0x9a5";
+
+/// Strategy-loop cycles to let run per scene. The first starts after the
+/// scene is set up, and each starts only once the previous one finished.
+const CYCLES_PER_SCENE: u64 = 3;
+
+/// How long the WebSocket stream gets to (re)connect, or to process one
+/// event, before the scene fails.
+const WS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What the mock serves for the current scene.
+#[derive(Default)]
+struct ChainState {
+    pools: Vec<PoolFixture>,
+    fault: Option<Value>,
+    submit: String,
+    sender: String,
+    /// Transactions submitted so far
+    submissions: u64,
+}
+
+/// Reply to one request under the current scene.
+fn respond(state: &mut ChainState, request: &Value) -> Reply {
+    match state.fault.as_ref().and_then(Value::as_str) {
+        Some("rate_limited") => return Reply::status(429),
+        Some("malformed") => return Reply::Raw(200, "{\"jsonrpc\": \"2.0\", \"result\": [".to_string()),
+        Some("rpc_error") => return rpc_error(-32603, "Internal error"),
+        _ => {}
+    }
+    let missing = state.fault.as_ref().and_then(|f| f.get("missing")).and_then(Value::as_str);
+
+    let params = &request["params"];
+    let result = match request["method"].as_str() {
+        Some("sui_multiGetObjects") => Value::Array(
+            params[0]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|id| object(state, id.as_str().unwrap_or_default(), missing))
+                .collect(),
+        ),
+        Some("sui_getObject") => object(state, params[0].as_str().unwrap_or_default(), missing),
+        Some("suix_getDynamicFieldObject") => state
+            .pools
+            .iter()
+            .find(|f| f.object["data"]["content"]["fields"]["inner"]["fields"]["id"]["id"] == params[0])
+            .and_then(|f| f.dynamic_field.clone())
+            .unwrap_or_else(|| json!({ "error": { "code": "dynamicFieldNotFound" } })),
+        // Every parameter taken mutably; the builder only needs the count
+        Some("sui_getNormalizedMoveFunction") => {
+            json!({ "isEntry": true, "parameters": vec![json!({ "MutableReference": {} }); 16] })
+        }
+        Some("suix_getReferenceGasPrice") => json!("750"),
+        Some("suix_getBalance") => json!({ "totalBalance": "5000000000" }),
+        Some("suix_getCoins") => json!({
+            "data": [{ "coinObjectId": GAS_COIN_ID, "version": "4", "digest": digest(9), "balance": "5000000000" }],
+            "hasNextPage": false,
+        }),
+        Some("sui_executeTransactionBlock") => {
+            state.submissions += 1;
+            let n = state.submissions;
+            match state.submit.as_str() {
+                "success" => execution(&state.sender, n, "success", Some("40000000")),
+                "revert" => execution(&state.sender, n, "failure", None),
+                "rejected" => {
+                    return rpc_error(-32002, "Error checking transaction input objects: ObjectNotFound")
+                }
+                "version_conflict" => {
+                    return rpc_error(
+                        -32002,
+                        &format!(
+                            "Transaction is rejected as invalid by more than 1/3 of validators by stake \
+                             (non-retryable). Non-retryable errors: [ObjectVersionUnavailableForConsumption \
+                             {{ provided_obj_ref: ({}, SequenceNumber(4), o#{}), current_version: SequenceNumber(5) }}]",
+                            GAS_COIN_ID,
+                            digest(9)
+                        ),
+                    )
+                }
+                other => panic!("unknown submit outcome {:?}", other),
+            }
+        }
+        _ => return rpc_error(-32601, "Method not found"),
+    };
+    Reply::Json(json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+}
+
+fn rpc_error(code: i64, message: &str) -> Reply {
+    Reply::Json(json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": code, "message": message } }))
+}
+
+/// A 32-byte Base58 object digest.
+fn digest(n: u8) -> String {
+    encode_base58(&[n; 32])
+}
+
+/// `id` as `sui_multiGetObjects` returns it: a monitored pool (unless the
+/// scene hides its DEX), a shared object the calls take, or the admin cap.
+fn object(state: &ChainState, id: &str, missing: Option<&str>) -> Value {
+    let not_found = json!({ "error": { "code": "notExists", "object_id": id } });
+    let Ok(addr) = parse_address(id) else {
+        return not_found;
+    };
+    let is = |other: &str| parse_address(other).is_ok_and(|a| a == addr);
+    if let Some(pool) = state.pools.iter().find(|f| is(&f.pool_id)) {
+        return if Some(pool.dex.as_str()) == missing { not_found } else { pool.object.clone() };
+    }
+    let owner = if is(ADMIN_CAP_ID) {
+        json!({ "AddressOwner": state.sender })
+    } else if SHARED_OBJECTS.iter().any(|s| is(s)) {
+        json!({ "Shared": { "initial_shared_version": 1 } })
+    } else {
+        return not_found;
+    };
+    json!({ "data": { "objectId": id, "version": "2", "digest": digest(1), "owner": owner } })
+}
+
+/// A `sui_executeTransactionBlock` result with effects and, on success,
+/// the package's `ArbExecuted` event.
+fn execution(sender: &str, n: u64, status: &str, profit: Option<&str>) -> Value {
+    let events: Vec<Value> = profit
+        .map(|p| json!({ "type": format!("{}::events::ArbExecuted", PACKAGE_ID), "parsedJson": { "profit": p } }))
+        .into_iter()
+        .collect();
+    json!({
+        "digest": encode_base58(&n.to_be_bytes()),
+        "effects": {
+            "status": if status == "success" { json!({ "status": "success" }) }
+                      else { json!({ "status": "failure", "error": "MoveAbort(min_profit) in command 0" }) },
+            "gasUsed": { "computationCost": "2000000", "storageCost": "1000000", "storageRebate": "500000" },
+            "gasObject": { "owner": { "AddressOwner": sender } },
+        },
+        "events": events,
+    })
+}
+
+/// Mock WebSocket node: confirms every subscription request and forwards
+/// each event sent on `events` to every subscribed connection. While `up`
+/// is false it drops live connections and closes new ones before the
+/// handshake.
+struct MockWs {
+    url: String,
+    up: watch::Sender<bool>,
+    events: broadcast::Sender<Value>,
+    /// Open connections with a confirmed subscription
+    subscribed: Arc<AtomicUsize>,
+}
+
+async fn spawn_mock_ws() -> MockWs {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let (up, up_rx) = watch::channel(true);
+    let (events, _) = broadcast::channel(64);
+    let subscribed = Arc::new(AtomicUsize::new(0));
+
+    let (events_tx, count) = (events.clone(), subscribed.clone());
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            if !*up_rx.borrow() {
+                continue;
+            }
+            let (mut up_rx, mut events_rx, count) = (up_rx.clone(), events_tx.subscribe(), count.clone());
+            tokio::spawn(async move {
+                let Ok(mut ws) = tokio_tungstenite::accept_async(socket).await else { return };
+                let mut counted = false;
+                loop {
+                    let reply = tokio::select! {
+                        msg = ws.next() => match msg {
+                            Some(Ok(Message::Text(text))) => {
+                                let request: Value = serde_json::from_str(&text).unwrap();
+                                if !counted {
+                                    counted = true;
+                                    count.fetch_add(1, Ordering::SeqCst);
+                                }
+                                json!({ "jsonrpc": "2.0", "id": request["id"], "result": 7 })
+                            }
+                            // Pings are answered by tungstenite
+                            Some(Ok(_)) => continue,
+                            _ => break,
+                        },
+                        Ok(event) = events_rx.recv() => json!({
+                            "jsonrpc": "2.0",
+                            "method": "suix_subscribeEvent",
+                            "params": { "subscription": 7, "result": event },
+                        }),
+                        _ = up_rx.wait_for(|up| !up) => break,
+                    };
+                    if ws.send(Message::Text(reply.to_string().into())).await.is_err() {
+                        break;
+                    }
+                }
+                if counted {
+                    count.fetch_sub(1, Ordering::SeqCst);
+                }
+            });
+        }
+    });
+    MockWs { url, up, events, subscribed }
+}
+
+/// The package `fixture`'s pool type is defined in.
+fn package_of(fixture: &PoolFixture) -> String {
+    let pool_type = fixture.object_type().expect("fixture without an object type");
+    pool_type.split("::").next().unwrap().to_string()
+}
+
+/// A swap event on `fixture`'s pool that names the pool but not its
+/// post-swap state, so the stream re-fetches it.
+fn swap_event(fixture: &PoolFixture) -> Value {
+    let pool_field = if fixture.dex == "deepbook" { "pool_id" } else { "pool" };
+    json!({
+        "type": format!("{}::pool::SwapEvent", package_of(fixture)),
+        "parsedJson": { pool_field: fixture.pool_id },
+    })
+}
+
+/// Wait up to [`WS_TIMEOUT`] for `done`.
+async fn wait_until(what: &str, done: impl Fn() -> bool) {
+    tokio::time::timeout(WS_TIMEOUT, async {
+        while !done() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("timed out waiting for {}", what));
+}
+
+/// Records each alert message the loop delivers.
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl AlertTransport for Recorder {
+    fn send(&self, text: &str) {
+        self.0.lock().unwrap().push(text.to_string());
+    }
+}
+
+/// The strategy loop's state as the bot builds it, minus the optional
+/// pieces (trade log, reconciler, inventory, gas coin pool), building
+/// transactions locally with every chain call going to `config.rpc_url`.
+#[allow(clippy::too_many_arguments)]
+fn strategy_state(
+    config: &Config,
+    settings: &RuntimeSettings,
+    signer: Signer,
+    cache: PoolCache,
+    collector_heartbeat: Arc<AtomicU64>,
+    clock: Clock,
+    metrics: Arc<Metrics>,
+    alerts: Arc<Mutex<Vec<String>>>,
+) -> StrategyState {
+    let sender = signer.address();
+    let rpc_url = &config.rpc_url;
+    let alert_config = AlertConfig { batch_window_ms: 0, max_per_window: 1_000, ..AlertConfig::default() };
+    let build = BuildInfo { version: "test".into(), commit: "test".into(), config_hash: "test".into() };
+    let (_, config_hash) = watch::channel(build.config_hash.clone());
+    StrategyState {
+        cache,
+        collector_heartbeat,
+        poll_backoff: PollBackoff::default(),
+        poll_pacing: PollPacing::new(Duration::from_millis(config.poll_interval_ms), Duration::ZERO, Duration::ZERO),
+        scanner: Scanner::new(config.min_profit_mist).with_metrics(metrics.clone()),
+        dry_runner: DryRunner::new(rpc_url, &config.package_id, &sender, config.max_gas_budget),
+        submitter: Submitter::new(rpc_url),
+        ptb_builder: PtbBuilder::new(config, &sender).with_local_build().unwrap(),
+        signer,
+        gas_monitor: GasMonitor::new(rpc_url, &sender, settings.min_gas_balance_mist),
+        coin_merger: CoinMerger::new(rpc_url, &sender),
+        gas_coins: None,
+        circuit_breaker: CircuitBreaker::new(
+            config.cb_max_consecutive_failures,
+            config.cb_max_cumulative_loss_mist,
+            config.cb_cooldown_ms,
+        )
+        .with_loss_window(config.cb_loss_window_ms),
+        breaker_store: None,
+        build_failures: FailureTracker::new(settings.build_max_consecutive_failures, settings.build_suspend_ms),
+        quarantine: StrategyQuarantine::new(
+            settings.strategy_quarantine_failures,
+            settings.strategy_quarantine_loss_mist,
+            settings.strategy_quarantine_cooldown_ms,
+        ),
+        opportunity_tracker: OpportunityTracker::new(
+            settings.opportunity_failure_cooldown_ms,
+            settings.opportunity_success_cooldown_ms,
+        ),
+        gas_estimator: GasEstimator::new(),
+        cold_start: ColdStartMonitor::new(),
+        outcome_stats: OutcomeStats::new(),
+        trade_stats: TradeStats::new(
+            settings.pnl_summary_every_trades,
+            settings.pnl_summary_interval_ms,
+            clock.now_ms(),
+        ),
+        trade_log: None,
+        reconciler: None,
+        strategy_weights: Arc::new(RwLock::new(StrategyWeights::parse(&settings.strategy_weights).unwrap())),
+        inventory: None,
+        alerts: AlertRouter::new(alert_config, Box::new(Recorder(alerts)), clock.now_ms()),
+        metrics,
+        profiler: Arc::new(Profiler::new(&settings.profile_dir, settings.profile_cpu_secs)),
+        clock,
+        poll_interval: Duration::from_millis(config.poll_interval_ms),
+        enable_quad_hop: settings.enable_quad_hop,
+        dry_run_enabled: config.dry_run_before_submit,
+        paper_trading: config.paper_trading,
+        dry_run_batch_size: settings.dry_run_batch_size,
+        max_opportunities: settings.max_opportunities_per_cycle,
+        build,
+        config_hash,
+        total_trades: 0,
+        total_profit: 0,
+        total_gas: 0,
+    }
+}
+
+/// What one scene observed.
+#[derive(Debug)]
+struct Observed {
+    fetch_ok: bool,
+    pools_updated: usize,
+    pools_streamed: usize,
+    pools_cached: usize,
+    opportunities: usize,
+    submissions: u64,
+    outcome: Option<String>,
+    breaker_tripped: bool,
+    alerts: Vec<String>,
+}
+
+/// Run one scenario file; returns a description of every failed expectation.
+async fn run_scenario(path: &Path, fixtures: &[PoolFixture]) -> Vec<String> {
+    let scenario: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap())
+        .unwrap_or_else(|e| panic!("{}: invalid JSON: {}", path.display(), e));
+    let name = scenario["name"].as_str().unwrap_or("unnamed");
+
+    let mut pools: Vec<PoolFixture> = scenario["pools"]
+        .as_array()
+        .unwrap_or_else(|| panic!("{}: missing pools", name))
+        .iter()
+        .map(|dex| {
+            fixtures
+                .iter()
+                .find(|f| Some(f.dex.as_str()) == dex.as_str())
+                .unwrap_or_else(|| panic!("{}: no fixture for {}", name, dex))
+                .clone()
+        })
+        .collect();
+
+    let signer = Signer::from_hex(&format!("0x{}", "2a".repeat(32))).unwrap();
+    let state = Arc::new(Mutex::new(ChainState { sender: signer.address(), ..ChainState::default() }));
+    let chain = state.clone();
+    let rpc_url = test_support::spawn_mock_rpc(move |request| respond(&mut chain.lock().unwrap(), request)).await;
+    let node_ws = spawn_mock_ws().await;
+
+    let mut config = Config::for_tests(rpc_url, pools.iter().map(PoolFixture::pool_config).collect());
+    config.poll_interval_ms = 10;
+    config.dry_run_before_submit = false;
+    config.package_id = PACKAGE_ID.into();
+    config.admin_cap_id = ADMIN_CAP_ID.into();
+    config.pause_flag_id = SHARED_OBJECTS[0].into();
+    config.cetus_global_config = SHARED_OBJECTS[1].into();
+    config.turbos_versioned = SHARED_OBJECTS[2].into();
+    // The loss limit `Config::from_env` defaults to
+    config.cb_max_cumulative_loss_mist = 1_000_000_000;
+    let breaker = &scenario["breaker"];
+    if let Some(n) = breaker["max_consecutive_failures"].as_u64() {
+        config.cb_max_consecutive_failures = n as u32;
+    }
+    if let Some(ms) = breaker["cooldown_ms"].as_u64() {
+        config.cb_cooldown_ms = ms;
+    }
+    config.cb_loss_window_ms = config.cb_cooldown_ms;
+    let env = scenario["env"].clone();
+    let settings = RuntimeSettings::read(&config, |k| env[k].as_str().map(str::to_string))
+        .unwrap_or_else(|e| panic!("{}: invalid env: {:#}", name, e));
+
+    let start_ms = unix_now_ms();
+    let clock = Clock::manual(start_ms);
+    let cache = PoolCache::new();
+    let heartbeat = Arc::new(AtomicU64::new(start_ms));
+    let metrics = Arc::new(Metrics::new());
+    let alerts = Arc::new(Mutex::new(Vec::new()));
+    let loop_state = strategy_state(
+        &config,
+        &settings,
+        signer,
+        cache.clone(),
+        heartbeat.clone(),
+        clock.clone(),
+        metrics.clone(),
+        alerts.clone(),
+    );
+    let strategy = tokio::spawn(run_strategy_loop(
+        Arc::new(tokio::sync::Mutex::new(loop_state)),
+        Arc::new(AtomicU64::new(start_ms)),
+        None,
+    ));
+
+    // The event stream, restarted whenever it ends as the bot's supervisor
+    // does (just sooner), into its own cache and heartbeat
+    let streamed = PoolCache::new();
+    let ws_heartbeat = Arc::new(AtomicU64::new(0));
+    let dex_packages = pools
+        .iter()
+        .map(|f| DexPackage { package_id: package_of(f), dex_name: f.dex.clone() })
+        .collect();
+    let stream = WsStream::new(&node_ws.url, &config.rpc_url, dex_packages, pools.iter().map(PoolFixture::meta).collect())
+        .with_endpoints(WsEndpoints::new(vec![node_ws.url.clone()]).with_reconnect_delay(Duration::from_millis(10)))
+        .with_idle_timeout(Duration::from_secs(60))
+        .with_refetch_debounce(Duration::ZERO)
+        .with_heartbeat(ws_heartbeat.clone());
+    let stream_cache = streamed.clone();
+    let collector = tokio::spawn(async move {
+        loop {
+            let _ = stream.run(stream_cache.clone()).await;
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    });
+    let scanner = Scanner::new(config.min_profit_mist);
+
+    let mut failures = Vec::new();
+    for (i, scene) in scenario["scenes"].as_array().into_iter().flatten().enumerate() {
+        let scene_name = format!("{} / scene {} ({})", name, i + 1, scene["name"].as_str().unwrap_or(""));
+
+        // Chain state for this scene
+        if let Some(overrides) = scene["overrides"].as_object() {
+            for (dex, fields) in overrides {
+                let pool = pools
+                    .iter_mut()
+                    .find(|f| &f.dex == dex)
+                    .unwrap_or_else(|| panic!("{}: override for unmonitored {}", scene_name, dex));
+                for (k, v) in fields.as_object().into_iter().flatten() {
+                    pool.object["data"]["content"]["fields"][k] = v.clone();
+                }
+            }
+        }
+        {
+            let mut s = state.lock().unwrap();
+            s.pools = pools.clone();
+            s.fault = scene.get("fault").cloned();
+            s.submit = scene["submit"].as_str().unwrap_or("success").to_string();
+        }
+
+        // Collectors: one poll and one event per pool, landing in the loop's
+        // cache at the scene's time (so the clock moves only once they're done)
+        let fetched = PoolCache::new();
+        let poller_up = scene["poller"].as_str() != Some("down");
+        let fetch_ok = poller_up && rpc_poller::seed_cache(&config, &fetched).await.is_ok();
+
+        let ws_up = scene["ws"].as_str() != Some("disconnect");
+        node_ws.up.send_replace(ws_up);
+        let subscribed = node_ws.subscribed.clone();
+        if ws_up {
+            wait_until("the stream to subscribe", || subscribed.load(Ordering::SeqCst) > 0).await;
+        } else {
+            wait_until("the stream to disconnect", || subscribed.load(Ordering::SeqCst) == 0).await;
+        }
+        for pool in &pools {
+            streamed.remove(&pool.pool_id);
+        }
+        let mut ws_events = 0;
+        if ws_up {
+            // One at a time: the heartbeat moves once the event is processed
+            for pool in &pools {
+                ws_heartbeat.store(0, Ordering::SeqCst);
+                node_ws.events.send(swap_event(pool)).unwrap();
+                let hb = ws_heartbeat.clone();
+                wait_until("the stream to process an event", || hb.load(Ordering::SeqCst) != 0).await;
+                ws_events += 1;
+            }
+        }
+
+        let at_ms = start_ms + scene["at_ms"].as_u64().unwrap_or(0);
+        assert!(at_ms >= clock.now_ms(), "{}: at_ms goes back in time", scene_name);
+        clock.advance(at_ms - clock.now_ms());
+        let submitted_before = state.lock().unwrap().submissions;
+        let alerts_before = alerts.lock().unwrap().len();
+        let streamed_now = streamed.snapshot();
+        let mut updated: Vec<String> = Vec::new();
+        for mut pool in streamed_now.iter().cloned().chain(fetched.snapshot()) {
+            pool.last_updated_ms = at_ms;
+            if !updated.contains(&pool.object_id) {
+                updated.push(pool.object_id.clone());
+            }
+            cache.upsert(pool.object_id.clone(), pool);
+        }
+        if fetch_ok || ws_events > 0 {
+            heartbeat.store(at_ms, Ordering::Relaxed);
+        }
+
+        // Strategy loop
+        let first_cycle = metrics.snapshot().cycles;
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while metrics.snapshot().cycles < first_cycle + CYCLES_PER_SCENE {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("{}: strategy loop stopped cycling", scene_name));
+
+        let snapshot = cache.snapshot();
+        let cycle = clock.cycle();
+        let mut opps = scanner.scan_two_hop(&snapshot, cycle);
+        opps.extend(scanner.scan_tri_hop(&snapshot, cycle));
+        let metrics = metrics.snapshot();
+        let observed = Observed {
+            fetch_ok,
+            pools_updated: updated.len(),
+            pools_streamed: streamed_now.len(),
+            pools_cached: snapshot.len(),
+            opportunities: opps.len(),
+            submissions: state.lock().unwrap().submissions - submitted_before,
+            outcome: metrics.last_trade.map(|t| t.outcome),
+            breaker_tripped: metrics.circuit_breaker.tripped,
+            alerts: alerts.lock().unwrap()[alerts_before..].to_vec(),
+        };
+
+        for (key, want) in scene["expect"].as_object().into_iter().flatten() {
+            assert!(EXPECT_KEYS.contains(&key.as_str()), "{}: unknown expectation {:?}", scene_name, key);
+            let ok = match key.as_str() {
+                "fetch_ok" => want.as_bool() == Some(observed.fetch_ok),
+                "pools_updated" => want.as_u64() == Some(observed.pools_updated as u64),
+                "pools_streamed" => want.as_u64() == Some(observed.pools_streamed as u64),
+                "pools_cached" => want.as_u64() == Some(observed.pools_cached as u64),
+                "min_opportunities" => want.as_u64().is_some_and(|n| observed.opportunities as u64 >= n),
+                "max_opportunities" => want.as_u64().is_some_and(|n| observed.opportunities as u64 <= n),
+                "submissions" => want.as_u64() == Some(observed.submissions),
+                "outcome" => want.as_str() == observed.outcome.as_deref(),
+                "breaker_tripped" => want.as_bool() == Some(observed.breaker_tripped),
+                // Delivered as "[SEVERITY] key: message"
+                "alerts" => want.as_array().into_iter().flatten().all(|k| {
+                    let key = format!("] {}:", k.as_str().unwrap_or_default());
+                    observed.alerts.iter().any(|a| a.contains(&key))
+                }),
+                _ => unreachable!(),
+            };
+            if !ok {
+                failures.push(format!("{}: expected {} = {}, observed {:?}", scene_name, key, want, observed));
+            }
+        }
+    }
+    strategy.abort();
+    collector.abort();
+    failures
+}
+
+#[tokio::test]
+async fn test_scenarios() {
+    let fixtures = fixtures::load_all(Path::new(SYNTHETIC_FIXTURES_DIR)).unwrap();
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(SCENARIOS_DIR)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|e| e == "json"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no scenarios in {}", SCENARIOS_DIR);
+
+    let mut failures = Vec::new();
+    for path in &paths {
+        failures.extend(run_scenario(path, &fixtures).await);
+    }
+    assert!(failures.is_empty(), "scenario failures:\n{}", failures.join("\n"));
+}
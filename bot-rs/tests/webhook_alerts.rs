@@ -0,0 +1,131 @@
+//! Low-gas and circuit-breaker webhook alerts against a mock webhook and RPC:
+//! payload shape, and the once-per-interval rate limit shared by both.
+
+use arb_executor::GasMonitor;
+use arb_strategy::CircuitBreaker;
+use arb_types::test_support::{self, Reply};
+use arb_types::Notifier;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const OWNER: &str = "0xa11ce";
+
+/// A webhook that records every body it receives.
+async fn spawn_webhook() -> (String, Arc<Mutex<Vec<Value>>>) {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let log = received.clone();
+    let url = test_support::spawn_mock_rpc(move |body| {
+        log.lock().unwrap().push(body.clone());
+        Reply::Json(json!({}))
+    })
+    .await;
+    (format!("{}/hook", url), received)
+}
+
+/// An RPC whose `suix_getBalance` answers with the current `balance`.
+async fn spawn_balance_rpc(balance: Arc<AtomicU64>) -> String {
+    test_support::spawn_mock_rpc(move |_| {
+        Reply::Json(json!({
+            "jsonrpc": "2.0", "id": 1,
+            "result": { "totalBalance": balance.load(Ordering::SeqCst).to_string() }
+        }))
+    })
+    .await
+}
+
+/// Wait (briefly) for the webhook to have received `count` notifications.
+async fn received(log: &Mutex<Vec<Value>>, count: usize) -> Vec<Value> {
+    for _ in 0..100 {
+        if log.lock().unwrap().len() >= count {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    log.lock().unwrap().clone()
+}
+
+#[tokio::test]
+async fn test_low_gas_alert_payload_and_rate_limit() {
+    let (hook, log) = spawn_webhook().await;
+    let balance = Arc::new(AtomicU64::new(500_000_000));
+    let rpc = spawn_balance_rpc(balance.clone()).await;
+    let notifier = Notifier::new(&hook).with_min_interval(Duration::from_secs(3600));
+    let mut monitor = GasMonitor::new(&rpc, OWNER, 100_000_000).with_notifier(notifier.clone());
+    let hour = 3_600_000;
+
+    // Healthy: nothing sent
+    assert!(monitor.check_balance(0).await.is_ok());
+    monitor.record_trade(1_000);
+
+    // First drop below the minimum alerts straight away
+    balance.store(50_000_000, Ordering::SeqCst);
+    assert!(monitor.check_balance(7_201_000).await.is_err());
+    let sent = received(&log, 1).await;
+    assert_eq!(sent.len(), 1);
+    let alert = &sent[0];
+    assert_eq!(alert["event"], "gas.low");
+    assert_eq!(alert["content"], alert["text"]);
+    let text = alert["text"].as_str().unwrap();
+    assert!(text.contains(OWNER) && text.contains("0.0500 SUI"), "{text}");
+    assert!(text.contains("2h 0m ago"), "{text}");
+    assert_eq!(alert["details"]["address"], OWNER);
+    assert_eq!(alert["details"]["balance_sui"], 0.05);
+    assert_eq!(alert["details"]["balance_mist"], 50_000_000);
+    assert_eq!(alert["details"]["secs_since_last_trade"], 7_200);
+
+    // Still low, and flapping, within the hour: no repeats
+    assert!(monitor.check_balance(7_300_000).await.is_err());
+    balance.store(500_000_000, Ordering::SeqCst);
+    assert!(monitor.check_balance(7_400_000).await.is_ok());
+    balance.store(50_000_000, Ordering::SeqCst);
+    assert!(monitor.check_balance(7_500_000).await.is_err());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(log.lock().unwrap().len(), 1);
+
+    // An hour after the first alert it's repeated
+    assert!(monitor.check_balance(7_201_000 + hour).await.is_err());
+    assert_eq!(received(&log, 2).await.len(), 2);
+}
+
+#[tokio::test]
+async fn test_alert_threshold_warns_before_trading_pauses() {
+    let (hook, log) = spawn_webhook().await;
+    let rpc = spawn_balance_rpc(Arc::new(AtomicU64::new(150_000_000))).await;
+    let mut monitor = GasMonitor::new(&rpc, OWNER, 100_000_000)
+        .with_notifier(Notifier::new(&hook))
+        .with_alert_threshold(200_000_000);
+
+    // Above the minimum, so trading continues, but below the alert threshold
+    assert!(monitor.check_balance(0).await.is_ok());
+    let sent = received(&log, 1).await;
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0]["details"]["secs_since_last_trade"], Value::Null);
+    assert!(sent[0]["text"].as_str().unwrap().contains("none since startup"));
+}
+
+#[tokio::test]
+async fn test_breaker_trip_shares_notifier() {
+    let (hook, log) = spawn_webhook().await;
+    let notifier = Notifier::new(&hook);
+    let mut breaker = CircuitBreaker::new(2, 1_000_000_000, 60_000).with_notifier(notifier.clone());
+
+    assert!(!breaker.record_failure(-1_000, 1_000));
+    assert!(breaker.record_failure(-2_000, 2_000));
+    let sent = received(&log, 1).await;
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0]["event"], "breaker.tripped");
+    assert!(sent[0]["content"].as_str().unwrap().contains("2 consecutive failures"));
+    assert_eq!(sent[0]["details"]["consecutive_failures"], 2);
+    assert_eq!(sent[0]["details"]["cumulative_pnl_mist"], -3_000);
+    assert_eq!(sent[0]["details"]["cooldown_ms"], 60_000);
+    assert_eq!(notifier.last_sent("breaker.tripped"), Some(2_000));
+
+    // A trip right after the cooldown is inside the notifier's hour: not re-sent
+    assert!(breaker.is_trading_allowed(62_000));
+    breaker.record_failure(-1_000, 63_000);
+    assert!(breaker.record_failure(-1_000, 64_000));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(log.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,92 @@
+/// Thin wrapper around Kriya CLMM (concentrated liquidity).
+/// Same shape as FlowX CLMM — Balance<T> at pool level with a
+/// hot-potato FlashSwapReceipt — but the receipt reports its debts.
+/// Pool has 2 type params: Pool<X, Y>.
+module arb_move::kriya_clmm_adapter {
+    use sui::balance::{Self, Balance};
+    use sui::clock::Clock;
+
+    use kriya_clmm::pool::Pool;
+    use kriya_clmm::trade::{Self, FlashSwapReceipt};
+    use kriya_clmm::version::Version;
+
+    /// Minimum sqrt price for a2b swaps (price decreases).
+    const MIN_SQRT_PRICE: u128 = 4295048016;
+    /// Maximum sqrt price for b2a swaps (price increases).
+    const MAX_SQRT_PRICE: u128 = 79226673515401279992447579055;
+
+    // ── Flash swap (receipt-based, caller settles) ──
+
+    /// Flash swap A→B. Returns balances and a FlashSwapReceipt that MUST be settled via pay().
+    public(package) fun swap_a2b<A, B>(
+        pool: &mut Pool<A, B>,
+        amount: u64,
+        version: &Version,
+        clock: &Clock,
+        ctx: &TxContext,
+    ): (Balance<A>, Balance<B>, FlashSwapReceipt) {
+        trade::flash_swap<A, B>(
+            pool,
+            true,       // a2b
+            true,       // by_amount_in
+            amount,
+            MIN_SQRT_PRICE,
+            clock,
+            version,
+            ctx,
+        )
+    }
+
+    /// Amount of A owed on an a2b FlashSwapReceipt.
+    public(package) fun pay_amount_a(receipt: &FlashSwapReceipt): u64 {
+        let (owed_a, _) = trade::swap_receipt_debts(receipt);
+        owed_a
+    }
+
+    /// Settle a FlashSwapReceipt by providing owed balances.
+    public(package) fun pay<A, B>(
+        pool: &mut Pool<A, B>,
+        receipt: FlashSwapReceipt,
+        balance_a: Balance<A>,
+        balance_b: Balance<B>,
+        version: &Version,
+        ctx: &TxContext,
+    ) {
+        trade::repay_flash_swap<A, B>(pool, receipt, balance_a, balance_b, version, ctx);
+    }
+
+    // ── Convenience: swap with immediate settlement ──
+
+    /// Swap B→A with immediate repayment. Consumes input Balance<B>, returns Balance<A>.
+    public(package) fun swap_b2a_direct<A, B>(
+        pool: &mut Pool<A, B>,
+        input: Balance<B>,
+        version: &Version,
+        clock: &Clock,
+        ctx: &TxContext,
+    ): Balance<A> {
+        let amount = balance::value(&input);
+        let (recv_a, recv_b, receipt) = trade::flash_swap<A, B>(
+            pool,
+            false,      // b2a
+            true,       // by_amount_in
+            amount,
+            MAX_SQRT_PRICE,
+            clock,
+            version,
+            ctx,
+        );
+        balance::destroy_zero(recv_b);
+        pay<A, B>(pool, receipt, balance::zero<A>(), input, version, ctx);
+        recv_a
+    }
+
+    // ── Tests ──
+
+    #[test]
+    fun test_constants() {
+        assert!(MIN_SQRT_PRICE == 4295048016);
+        assert!(MAX_SQRT_PRICE == 79226673515401279992447579055);
+        assert!(MIN_SQRT_PRICE < MAX_SQRT_PRICE);
+    }
+}
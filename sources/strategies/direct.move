@@ -0,0 +1,109 @@
+/// Inventory-funded (flash-loan-free) arbitrage strategies.
+/// Each entry function splits the input from the caller's own Coin<A>, buys on one DEX,
+/// sells on another, and merges the proceeds back into the same coin — no flash loan,
+/// no flash fee. The inventory coin keeps its object ID across trades.
+/// All functions require AdminCap for authorization.
+/// Supported DEXes: Cetus CLMM, Turbos CLMM.
+module arb_move::direct {
+    use sui::coin::{Self, Coin};
+    use sui::clock::Clock;
+
+    // ── DEX pool types ──
+    use cetusclmm::pool::{Pool as CetusPool};
+    use cetusclmm::config::GlobalConfig;
+    use turbos_clmm::pool::{Pool as TurbosPool, Versioned};
+
+    // ── Internal modules ──
+    use arb_move::admin::{AdminCap, PauseFlag};
+    use arb_move::profit;
+    use arb_move::events;
+    use arb_move::cetus_adapter;
+    use arb_move::turbos_adapter;
+
+    const E_ZERO_AMOUNT: u64 = 1;
+    /// The inventory coin holds less than `amount`.
+    const E_INSUFFICIENT_INVENTORY: u64 = 2;
+
+    // ════════════════════════════════════════════════════════════
+    //  Cetus ↔ Turbos
+    // ════════════════════════════════════════════════════════════
+
+    /// Swap A→B on Cetus with inventory, sell B→A on Turbos, merge A back into inventory.
+    /// Exploits: Cetus price(A/B) < Turbos price(A/B).
+    entry fun direct_cetus_to_turbos<A, B, TurbosFee>(
+        _admin: &AdminCap,
+        pause: &PauseFlag,
+        cetus_config: &GlobalConfig,
+        cetus_pool: &mut CetusPool<A, B>,
+        turbos_pool: &mut TurbosPool<A, B, TurbosFee>,
+        turbos_versioned: &Versioned,
+        inventory: &mut Coin<A>,
+        amount: u64,
+        min_profit: u64,
+        clock: &Clock,
+        ctx: &mut TxContext,
+    ) {
+        assert!(amount > 0, E_ZERO_AMOUNT);
+        assert!(coin::value(inventory) >= amount, E_INSUFFICIENT_INVENTORY);
+        arb_move::admin::assert_not_paused(pause);
+
+        // 1. Buy B with our own A on Cetus
+        let coin_a_in = coin::split(inventory, amount, ctx);
+        let coin_b = cetus_adapter::swap_coin_a2b<A, B>(
+            cetus_config, cetus_pool, coin_a_in, clock, ctx,
+        );
+
+        // 2. Sell B→A on Turbos
+        let b_amount = coin::value(&coin_b);
+        let coin_a_out = turbos_adapter::swap_b_to_a<A, B, TurbosFee>(
+            turbos_pool, coin_b, b_amount, clock, turbos_versioned, ctx,
+        );
+
+        // 3. Validate profit against the A we put in
+        let received = coin::value(&coin_a_out);
+        profit::assert_profit(received, amount, min_profit);
+
+        // 4. Return principal + profit to inventory
+        events::emit_arb_executed(b"direct_cetus_to_turbos", amount, received);
+        coin::join(inventory, coin_a_out);
+    }
+
+    /// Swap A→B on Turbos with inventory, sell B→A on Cetus, merge A back into inventory.
+    /// Exploits: Turbos price(A/B) < Cetus price(A/B).
+    entry fun direct_turbos_to_cetus<A, B, TurbosFee>(
+        _admin: &AdminCap,
+        pause: &PauseFlag,
+        cetus_config: &GlobalConfig,
+        cetus_pool: &mut CetusPool<A, B>,
+        turbos_pool: &mut TurbosPool<A, B, TurbosFee>,
+        turbos_versioned: &Versioned,
+        inventory: &mut Coin<A>,
+        amount: u64,
+        min_profit: u64,
+        clock: &Clock,
+        ctx: &mut TxContext,
+    ) {
+        assert!(amount > 0, E_ZERO_AMOUNT);
+        assert!(coin::value(inventory) >= amount, E_INSUFFICIENT_INVENTORY);
+        arb_move::admin::assert_not_paused(pause);
+
+        // 1. Buy B with our own A on Turbos
+        let coin_a_in = coin::split(inventory, amount, ctx);
+        let coin_b = turbos_adapter::swap_a_to_b<A, B, TurbosFee>(
+            turbos_pool, coin_a_in, amount, clock, turbos_versioned, ctx,
+        );
+
+        // 2. Sell B→A on Cetus
+        let coin_a_out = cetus_adapter::swap_coin_b2a<A, B>(
+            cetus_config, cetus_pool, coin_b, clock, ctx,
+        );
+
+        // 3. Validate profit against the A we put in
+        let received = coin::value(&coin_a_out);
+        profit::assert_profit(received, amount, min_profit);
+
+        // 4. Return principal + profit to inventory
+        events::emit_arb_executed(b"direct_turbos_to_cetus", amount, received);
+        coin::join(inventory, coin_a_out);
+    }
+}
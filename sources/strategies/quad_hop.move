@@ -0,0 +1,85 @@
+/// Quad-hop arbitrage strategies (A → B → C → D → A).
+/// Flash-borrow A, route through four pools, repay, keep profit.
+/// Supported DEXes: Cetus CLMM.
+module arb_move::quad_hop {
+    use sui::coin;
+    use sui::balance;
+    use sui::clock::Clock;
+
+    // ── DEX pool types ──
+    use cetusclmm::pool::{Pool as CetusPool};
+    use cetusclmm::config::GlobalConfig;
+
+    // ── Internal modules ──
+    use arb_move::admin::{AdminCap, PauseFlag};
+    use arb_move::profit;
+    use arb_move::events;
+    use arb_move::cetus_adapter;
+
+    const E_ZERO_AMOUNT: u64 = 1;
+
+    // ════════════════════════════════════════════════════════════
+    //  All-Cetus quad: Cetus(A/B) → Cetus(B/C) → Cetus(C/D) → Cetus(D/A)
+    // ════════════════════════════════════════════════════════════
+
+    /// A→B on Cetus pool_ab, B→C on pool_bc, C→D on pool_cd, D→A on pool_da.
+    /// Flash swap on pool_ab, repay with profit in A.
+    entry fun quad_cetus_cetus_cetus_cetus<A, B, C, D>(
+        _admin: &AdminCap,
+        pause: &PauseFlag,
+        config: &GlobalConfig,
+        pool_ab: &mut CetusPool<A, B>,
+        pool_bc: &mut CetusPool<B, C>,
+        pool_cd: &mut CetusPool<C, D>,
+        pool_da: &mut CetusPool<D, A>,
+        amount: u64,
+        min_profit: u64,
+        clock: &Clock,
+        ctx: &mut TxContext,
+    ) {
+        assert!(amount > 0, E_ZERO_AMOUNT);
+        arb_move::admin::assert_not_paused(pause);
+
+        // 1. Flash swap A→B on pool_ab
+        let (recv_a, recv_b, receipt) = cetus_adapter::flash_swap_a2b<A, B>(
+            config, pool_ab, amount, clock,
+        );
+        balance::destroy_zero(recv_a);
+        let b_amount = balance::value(&recv_b);
+
+        // 2. Swap B→C on pool_bc
+        let recv_c = cetus_adapter::swap_a2b<B, C>(
+            config, pool_bc, recv_b, b_amount, clock,
+        );
+        let c_amount = balance::value(&recv_c);
+
+        // 3. Swap C→D on pool_cd
+        let recv_d = cetus_adapter::swap_a2b<C, D>(
+            config, pool_cd, recv_c, c_amount, clock,
+        );
+        let d_amount = balance::value(&recv_d);
+
+        // 4. Swap D→A on pool_da
+        let recv_a_final = cetus_adapter::swap_a2b<D, A>(
+            config, pool_da, recv_d, d_amount, clock,
+        );
+
+        // 5. Validate profit
+        let owed = cetus_adapter::swap_pay_amount(&receipt);
+        let mut coin_a_out = coin::from_balance(recv_a_final, ctx);
+        let received = coin::value(&coin_a_out);
+        profit::assert_profit(received, owed, min_profit);
+
+        // 6. Repay
+        let repay = coin::split(&mut coin_a_out, owed, ctx);
+        cetus_adapter::repay_flash_swap<A, B>(
+            config, pool_ab,
+            coin::into_balance(repay),
+            balance::zero<B>(),
+            receipt,
+        );
+
+        events::emit_arb_executed(b"quad_cccc", owed, received);
+        transfer::public_transfer(coin_a_out, tx_context::sender(ctx));
+    }
+}
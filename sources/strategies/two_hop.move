@@ -1,7 +1,7 @@
 /// Two-hop (DEX-to-DEX) arbitrage strategies.
 /// Each entry function flash-borrows from one DEX, swaps on another, repays, and keeps profit.
 /// All functions require AdminCap for authorization.
-/// Supported DEXes: Cetus CLMM, Turbos CLMM, DeepBook V3, Aftermath AMM, FlowX CLMM v3.
+/// Supported DEXes: Cetus CLMM, Turbos CLMM, DeepBook V3, Aftermath AMM, FlowX CLMM v3, Kriya CLMM.
 module arb_move::two_hop {
     use sui::coin::{Self, Coin};
     use sui::balance;
@@ -23,6 +23,9 @@ module arb_move::two_hop {
     // FlowX CLMM v3
     use flowx_clmm::pool::{Pool as FlowxPool};
     use flowx_clmm::versioned::{Versioned as FlowxVersioned};
+    // Kriya CLMM
+    use kriya_clmm::pool::{Pool as KriyaPool};
+    use kriya_clmm::version::{Version as KriyaVersion};
 
     // ── Internal modules ──
     use arb_move::admin::{AdminCap, PauseFlag};
@@ -33,6 +36,7 @@ module arb_move::two_hop {
     use arb_move::deepbook_adapter;
     use arb_move::aftermath_adapter;
     use arb_move::flowx_clmm_adapter;
+    use arb_move::kriya_clmm_adapter;
 
     const E_ZERO_AMOUNT: u64 = 1;
     /// Maximum u64 — used as Aftermath slippage to disable their internal check.
@@ -849,4 +853,102 @@ module arb_move::two_hop {
         events::emit_arb_executed(b"flowx_clmm_to_deepbook", amount, received);
         transfer::public_transfer(base_out, tx_context::sender(ctx));
     }
+
+    // ════════════════════════════════════════════════════════════
+    //  Cetus ↔ Kriya CLMM
+    //  Kriya CLMM supports flash swaps (hot-potato FlashSwapReceipt).
+    // ════════════════════════════════════════════════════════════
+
+    /// Flash swap A→B on Cetus, sell B→A on Kriya CLMM, repay Cetus, keep A profit.
+    entry fun arb_cetus_to_kriya<A, B>(
+        _admin: &AdminCap,
+        pause: &PauseFlag,
+        cetus_config: &GlobalConfig,
+        cetus_pool: &mut CetusPool<A, B>,
+        kriya_pool: &mut KriyaPool<A, B>,
+        kriya_version: &KriyaVersion,
+        amount: u64,
+        min_profit: u64,
+        clock: &Clock,
+        ctx: &mut TxContext,
+    ) {
+        assert!(amount > 0, E_ZERO_AMOUNT);
+        arb_move::admin::assert_not_paused(pause);
+
+        // 1. Flash swap A→B on Cetus
+        let (recv_a, recv_b, receipt) = cetus_adapter::flash_swap_a2b<A, B>(
+            cetus_config, cetus_pool, amount, clock,
+        );
+        balance::destroy_zero(recv_a);
+
+        // 2. Sell B→A on Kriya CLMM
+        let recv_a_final = kriya_clmm_adapter::swap_b2a_direct<A, B>(
+            kriya_pool, recv_b, kriya_version, clock, ctx,
+        );
+
+        // 3. Validate
+        let mut coin_a_out = coin::from_balance(recv_a_final, ctx);
+        let owed = cetus_adapter::swap_pay_amount(&receipt);
+        let received = coin::value(&coin_a_out);
+        profit::assert_profit(received, owed, min_profit);
+
+        // 4. Repay Cetus
+        let repay = coin::split(&mut coin_a_out, owed, ctx);
+        cetus_adapter::repay_flash_swap<A, B>(
+            cetus_config, cetus_pool,
+            coin::into_balance(repay),
+            balance::zero<B>(),
+            receipt,
+        );
+
+        events::emit_arb_executed(b"cetus_to_kriya", owed, received);
+        transfer::public_transfer(coin_a_out, tx_context::sender(ctx));
+    }
+
+    /// Flash swap A→B on Kriya CLMM, sell B→A on Cetus, repay Kriya, keep A profit.
+    entry fun arb_kriya_to_cetus<A, B>(
+        _admin: &AdminCap,
+        pause: &PauseFlag,
+        cetus_config: &GlobalConfig,
+        cetus_pool: &mut CetusPool<A, B>,
+        kriya_pool: &mut KriyaPool<A, B>,
+        kriya_version: &KriyaVersion,
+        amount: u64,
+        min_profit: u64,
+        clock: &Clock,
+        ctx: &mut TxContext,
+    ) {
+        assert!(amount > 0, E_ZERO_AMOUNT);
+        arb_move::admin::assert_not_paused(pause);
+
+        // 1. Flash swap A→B on Kriya CLMM
+        let (recv_a, recv_b, receipt) = kriya_clmm_adapter::swap_a2b<A, B>(
+            kriya_pool, amount, kriya_version, clock, ctx,
+        );
+        balance::destroy_zero(recv_a);
+
+        // 2. Sell B→A on Cetus (Balance level)
+        let b_amount = balance::value(&recv_b);
+        let recv_a_final = cetus_adapter::swap_b2a<A, B>(
+            cetus_config, cetus_pool, recv_b, b_amount, clock,
+        );
+
+        // 3. Convert to Coin, validate profit
+        let mut coin_a_out = coin::from_balance(recv_a_final, ctx);
+        let owed = kriya_clmm_adapter::pay_amount_a(&receipt);
+        let received = coin::value(&coin_a_out);
+        profit::assert_profit(received, owed, min_profit);
+
+        // 4. Repay Kriya CLMM with A
+        let repay = coin::split(&mut coin_a_out, owed, ctx);
+        kriya_clmm_adapter::pay<A, B>(
+            kriya_pool, receipt,
+            coin::into_balance(repay),
+            balance::zero<B>(),
+            kriya_version, ctx,
+        );
+
+        events::emit_arb_executed(b"kriya_to_cetus", owed, received);
+        transfer::public_transfer(coin_a_out, tx_context::sender(ctx));
+    }
 }